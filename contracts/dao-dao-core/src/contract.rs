@@ -1,30 +1,53 @@
+use std::collections::BTreeMap;
+
+use cosmwasm_schema::serde::de::{DeserializeOwned, IgnoredAny};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    from_json_binary, to_json_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Order,
-    Reply, Response, StdError, StdResult, SubMsg, WasmMsg,
+    coins, from_json_binary, to_json_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Decimal, Deps,
+    DepsMut, Empty, Env, Event, IbcMsg, MessageInfo, Order, QuerierWrapper, Reply, Response,
+    StakingMsg, StdError, StdResult, Storage, SubMsg, Uint128, WasmMsg,
 };
 use cw2::{get_contract_version, set_contract_version, ContractVersion};
-use cw_paginate_storage::{paginate_map, paginate_map_keys, paginate_map_values};
-use cw_storage_plus::Map;
-use cw_utils::{parse_reply_instantiate_data, Duration};
+use cw20::Cw20Coin;
+use cw_paginate_storage::{
+    clamp_limit, order, paginate_map, paginate_map_keys, paginate_map_values, MAX_LIMIT,
+};
+use cw_storage_plus::{Bound, Map, PrimaryKey};
+use cw_utils::{parse_reply_instantiate_data, Duration, Expiration};
+use dao_hooks::lifecycle::{dao_lifecycle_hooks, DaoLifecycleHookMsg};
 use dao_interface::{
-    msg::{ExecuteMsg, InitialItem, InstantiateMsg, MigrateMsg, QueryMsg},
+    msg::{ExecuteMsg, InitialItem, InstantiateMsg, MigrateMsg, ProposalHookMsg, QueryMsg},
     query::{
-        AdminNominationResponse, Cw20BalanceResponse, DaoURIResponse, DumpStateResponse,
-        GetItemResponse, PauseInfoResponse, ProposalModuleCountResponse, SubDao,
+        AdminNominationResponse, CachedCw20BalanceResponse, CircuitBreakerResponse,
+        ColdSpendResponse, Cw20BalanceResponse, DaoURIResponse, DryRunIssue, DryRunProblem,
+        DryRunResponse, DumpStateResponse, GetItemResponse, GlobalProposalResponse,
+        ManagedContractResponse, PauseInfoResponse, ProposalModuleCountResponse, SubDao,
+        TreasuryTiersResponse, VaultPositionResponse, VaultResponse,
     },
+    receive::{ReceiveHandler, ReceiveHandlerMsg},
+    registry::{OfficialCodeId, RegistrarQuery},
+    remote::RemoteCommand,
     state::{
-        Admin, Config, ModuleInstantiateCallback, ModuleInstantiateInfo, ProposalModule,
-        ProposalModuleStatus,
+        Admin, CircuitBreaker, CircuitBreakerTrip, ColdSpend, Config, ManagedContract,
+        ManagedContractRegistration, Merge, MergeRole, MergeStep, ModuleInstantiateCallback,
+        ModuleInstantiateInfo, ProposalModule, ProposalModuleStatus, TreasuryTiers,
     },
+    vault::{PositionResponse, Vault, VaultExecuteMsg, VaultQueryMsg, VaultRegistration},
     voting,
 };
+use dao_voting::proposal::MAX_PROPOSAL_SIZE;
 
 use crate::error::ContractError;
 use crate::state::{
-    ACTIVE_PROPOSAL_MODULE_COUNT, ADMIN, CONFIG, CW20_LIST, CW721_LIST, ITEMS, NOMINATED_ADMIN,
-    PAUSED, PROPOSAL_MODULES, SUBDAO_LIST, TOTAL_PROPOSAL_MODULE_COUNT, VOTING_MODULE,
+    ACTIVE_PROPOSAL_MODULE_COUNT, ADMIN, BLOCK_EXECUTIONS, CIRCUIT_BREAKER, CIRCUIT_BREAKER_TRIP,
+    CODE_ID_REGISTRAR, COLD_SPENDS, COLD_SPEND_COUNT, CONFIG, CONFIG_HISTORY, CW20_BALANCES,
+    CW20_LIST, CW20_OUTFLOWS, CW721_LIST, FROZEN_CONTRACTS, FROZEN_DENOMS, GLOBAL_PROPOSALS,
+    GLOBAL_PROPOSAL_COUNT, GLOBAL_PROPOSAL_IDS, HOT_CW20_SPENT, HOT_PERIOD_END, HOT_SPENT, ITEMS,
+    LIFECYCLE_HOOKS, MANAGED_CONTRACTS, MERGE, NOMINATED_ADMIN, OFFICIAL_CODE_IDS, OUTFLOWS,
+    PAUSED, PENDING_MANAGED_CONTRACTS, PINNED_CODES, PROPOSAL_MODULES, PROPOSAL_MODULE_HISTORY,
+    RECEIVE_HANDLERS, REMOTE_MEMBERS, SUBDAO_LIST, TOTAL_PROPOSAL_MODULE_COUNT, TREASURY_TIERS,
+    VAULTS, VAULT_ALLOCATION_LIMIT, VAULT_DEPOSITS, VOTING_MODULE,
 };
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-dao-core";
@@ -33,6 +56,15 @@ pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 const PROPOSAL_MODULE_REPLY_ID: u64 = 0;
 const VOTE_MODULE_INSTANTIATE_REPLY_ID: u64 = 1;
 const VOTE_MODULE_UPDATE_REPLY_ID: u64 = 2;
+/// Instantiations of contracts with the DAO as admin reply on success
+/// with this offset plus the index of the message.
+const MANAGED_CONTRACT_REPLY_ID_OFFSET: u64 = 1 << 16;
+/// Lifecycle hook messages reply on error with this offset plus the
+/// index of the failed hook consumer.
+const FAILED_LIFECYCLE_HOOK_REPLY_ID_OFFSET: u64 = 1 << 32;
+/// The number of NFTs of each collection the absorbed DAO sends per
+/// `AdvanceMerge` during the `Nfts` step of a merge.
+const MERGE_NFT_BATCH: u32 = 30;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -51,7 +83,7 @@ pub fn instantiate(
         automatically_add_cw721s: msg.automatically_add_cw721s,
         dao_uri: msg.dao_uri,
     };
-    CONFIG.save(deps.storage, &config)?;
+    save_config(deps.storage, env.block.height, &config)?;
 
     let admin = msg
         .admin
@@ -106,36 +138,83 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
-    // No actions can be performed while the DAO is paused.
+    // No actions can be performed while the DAO is paused. Proposal
+    // hooks are exempt, as proposal modules remove consumers whose
+    // hooks fail.
     if let Some(expiration) = PAUSED.may_load(deps.storage)? {
-        if !expiration.is_expired(&env.block) {
+        if !expiration.is_expired(&env.block) && !matches!(msg, ExecuteMsg::ProposalHook(_)) {
             return Err(ContractError::Paused {});
         }
     }
 
     match msg {
-        ExecuteMsg::ExecuteAdminMsgs { msgs } => {
-            execute_admin_msgs(deps.as_ref(), info.sender, msgs)
-        }
+        ExecuteMsg::ExecuteAdminMsgs { msgs } => execute_admin_msgs(deps, env, info.sender, msgs),
         ExecuteMsg::ExecuteProposalHook { msgs } => {
-            execute_proposal_hook(deps.as_ref(), info.sender, msgs)
+            execute_proposal_hook(deps, env, info.sender, msgs)
         }
         ExecuteMsg::Pause { duration } => execute_pause(deps, env, info.sender, duration),
-        ExecuteMsg::Receive(_) => execute_receive_cw20(deps, info.sender),
+        ExecuteMsg::Receive(msg) => execute_receive_cw20(deps, env, info.sender, msg),
         ExecuteMsg::ReceiveNft(_) => execute_receive_cw721(deps, info.sender),
         ExecuteMsg::RemoveItem { key } => execute_remove_item(deps, env, info.sender, key),
         ExecuteMsg::SetItem { key, value } => execute_set_item(deps, env, info.sender, key, value),
         ExecuteMsg::UpdateConfig { config } => {
             execute_update_config(deps, env, info.sender, config)
         }
+        ExecuteMsg::RollbackConfig { height } => {
+            execute_rollback_config(deps, env, info.sender, height)
+        }
         ExecuteMsg::UpdateCw20List { to_add, to_remove } => {
             execute_update_cw20_list(deps, env, info.sender, to_add, to_remove)
         }
+        ExecuteMsg::RefreshCw20Balances { start_after, limit } => {
+            execute_refresh_cw20_balances(deps, env, start_after, limit)
+        }
         ExecuteMsg::UpdateCw721List { to_add, to_remove } => {
             execute_update_cw721_list(deps, env, info.sender, to_add, to_remove)
         }
+        ExecuteMsg::UpdateFrozenContracts { to_add, to_remove } => {
+            execute_update_frozen_contracts(deps, env, info.sender, to_add, to_remove)
+        }
+        ExecuteMsg::UpdateFrozenDenoms { to_add, to_remove } => {
+            execute_update_frozen_denoms(deps, env, info.sender, to_add, to_remove)
+        }
+        ExecuteMsg::UpdateVaults { to_add, to_remove } => {
+            execute_update_vaults(deps, env, info.sender, to_add, to_remove)
+        }
+        ExecuteMsg::UpdateVaultAllocationLimit { limit } => {
+            execute_update_vault_allocation_limit(deps, env, info.sender, limit)
+        }
+        ExecuteMsg::DepositToVault { vault, amount } => {
+            execute_deposit_to_vault(deps, env, info.sender, vault, amount)
+        }
+        ExecuteMsg::WithdrawFromVault { vault, amount } => {
+            execute_withdraw_from_vault(deps, env, info.sender, vault, amount)
+        }
+        ExecuteMsg::UpdateCircuitBreaker { circuit_breaker } => {
+            execute_update_circuit_breaker(deps, env, info.sender, circuit_breaker)
+        }
+        ExecuteMsg::ResetCircuitBreaker {} => execute_reset_circuit_breaker(deps, env, info.sender),
+        ExecuteMsg::UpdateManagedContracts { to_add, to_remove } => {
+            execute_update_managed_contracts(deps, env, info.sender, to_add, to_remove)
+        }
+        ExecuteMsg::UpdateReceiveHandlers { to_add, to_remove } => {
+            execute_update_receive_handlers(deps, env, info.sender, to_add, to_remove)
+        }
+        ExecuteMsg::UpdateRemoteMembers { to_add, to_remove } => {
+            execute_update_remote_members(deps, env, info.sender, to_add, to_remove)
+        }
+        ExecuteMsg::RemoteCommand { command } => execute_remote_command(deps, env, info, command),
+        ExecuteMsg::ProposeMerge { counterparty, role } => {
+            execute_propose_merge(deps, env, info.sender, counterparty, role)
+        }
+        ExecuteMsg::CancelMerge {} => execute_cancel_merge(deps, env, info.sender),
+        ExecuteMsg::AdvanceMerge {} => execute_advance_merge(deps, env),
+        ExecuteMsg::SkipMergeStep {} => execute_skip_merge_step(deps, env, info.sender),
+        ExecuteMsg::UpdatePinnedCodes { to_add, to_remove } => {
+            execute_update_pinned_codes(deps, env, info.sender, to_add, to_remove)
+        }
         ExecuteMsg::UpdateVotingModule { module } => {
-            execute_update_voting_module(env, info.sender, module)
+            execute_update_voting_module(deps, env, info.sender, module)
         }
         ExecuteMsg::UpdateProposalModules { to_add, to_disable } => {
             execute_update_proposal_modules(deps, env, info.sender, to_add, to_disable)
@@ -150,6 +229,27 @@ pub fn execute(
         ExecuteMsg::UpdateSubDaos { to_add, to_remove } => {
             execute_update_sub_daos_list(deps, env, info.sender, to_add, to_remove)
         }
+        ExecuteMsg::ProposalHook(msg) => execute_proposal_hook_msg(deps, info.sender, msg),
+        ExecuteMsg::AddLifecycleHook { address } => {
+            execute_add_lifecycle_hook(deps, env, info.sender, address)
+        }
+        ExecuteMsg::RemoveLifecycleHook { address } => {
+            execute_remove_lifecycle_hook(deps, env, info.sender, address)
+        }
+        ExecuteMsg::UpdateCodeIdRegistrar { registrar } => {
+            execute_update_code_id_registrar(deps, env, info.sender, registrar)
+        }
+        ExecuteMsg::SyncCodeIds {} => execute_sync_code_ids(deps, env, info.sender),
+        ExecuteMsg::UpdateTreasuryTiers { tiers } => {
+            execute_update_treasury_tiers(deps, env, info.sender, tiers)
+        }
+        ExecuteMsg::RequestColdSpend { msgs } => {
+            execute_request_cold_spend(deps, env, info.sender, msgs)
+        }
+        ExecuteMsg::ConfirmColdSpend { id } => {
+            execute_confirm_cold_spend(deps, env, info.sender, id)
+        }
+        ExecuteMsg::CancelColdSpend { id } => execute_cancel_cold_spend(deps, env, info.sender, id),
     }
 }
 
@@ -175,7 +275,8 @@ pub fn execute_pause(
 }
 
 pub fn execute_admin_msgs(
-    deps: Deps,
+    deps: DepsMut,
+    env: Env,
     sender: Addr,
     msgs: Vec<CosmosMsg<Empty>>,
 ) -> Result<Response, ContractError> {
@@ -186,13 +287,16 @@ pub fn execute_admin_msgs(
         return Err(ContractError::Unauthorized {});
     }
 
-    Ok(Response::default()
-        .add_attribute("action", "execute_admin_msgs")
-        .add_messages(msgs))
+    // Admin messages are held to the same freeze lists, official
+    // code IDs, and circuit breaker as proposals, but are not
+    // proposal executions.
+    let response = proposal_msgs_response(deps, &env, msgs, false)?;
+    Ok(response.add_attribute("action", "execute_admin_msgs"))
 }
 
 pub fn execute_proposal_hook(
-    deps: Deps,
+    deps: DepsMut,
+    env: Env,
     sender: Addr,
     msgs: Vec<CosmosMsg<Empty>>,
 ) -> Result<Response, ContractError> {
@@ -205,9 +309,514 @@ pub fn execute_proposal_hook(
         return Err(ContractError::ModuleDisabledCannotExecute { address: sender });
     }
 
-    Ok(Response::default()
-        .add_attribute("action", "execute_proposal_hook")
-        .add_messages(msgs))
+    check_treasury_tiers(deps.storage, &env, &sender, &msgs)?;
+
+    let response = proposal_msgs_response(deps, &env, msgs, true)?;
+    Ok(response.add_attribute("action", "execute_proposal_hook"))
+}
+
+/// Checks MSGS against the freeze list, official code IDs, and circuit
+/// breaker, and returns a response executing them. COUNT_EXECUTION is
+/// false for messages from the admin, and for messages executed on
+/// behalf of a proposal that has already been counted towards the
+/// circuit breaker's executions per block.
+fn proposal_msgs_response(
+    mut deps: DepsMut,
+    env: &Env,
+    msgs: Vec<CosmosMsg<Empty>>,
+    count_execution: bool,
+) -> Result<Response, ContractError> {
+    for (index, msg) in msgs.iter().enumerate() {
+        if let Some(target) = frozen_target(deps.storage, env, msg)? {
+            return Err(ContractError::Frozen { index, target });
+        }
+        if let Some(code_id) = msg_code_id(msg) {
+            check_official_code_id(deps.storage, code_id)?;
+        }
+    }
+
+    let tripped = check_circuit_breaker(deps.storage, env, &msgs, count_execution)?;
+
+    cache_cw20_spends(deps.branch(), env, &msgs)?;
+    let msgs = managed_contract_submsgs(deps.storage, env, msgs)?;
+
+    let response = Response::default().add_submessages(msgs);
+    Ok(match tripped {
+        Some(reason) => response
+            .add_event(Event::new("circuit_breaker_tripped").add_attribute("reason", reason)),
+        None => response,
+    })
+}
+
+/// Wraps MSGS in submessages, replying on success to those that
+/// instantiate a contract with the DAO as admin so that the new
+/// contract may be added to `MANAGED_CONTRACTS`.
+fn managed_contract_submsgs(
+    storage: &mut dyn Storage,
+    env: &Env,
+    msgs: Vec<CosmosMsg<Empty>>,
+) -> StdResult<Vec<SubMsg>> {
+    msgs.into_iter()
+        .enumerate()
+        .map(|(index, msg)| match &msg {
+            CosmosMsg::Wasm(WasmMsg::Instantiate {
+                admin: Some(admin),
+                code_id,
+                label,
+                ..
+            }) if *admin == env.contract.address => {
+                let id = MANAGED_CONTRACT_REPLY_ID_OFFSET + index as u64;
+                PENDING_MANAGED_CONTRACTS.save(
+                    storage,
+                    id,
+                    &ManagedContract {
+                        code_id: *code_id,
+                        label: label.clone(),
+                    },
+                )?;
+                Ok(SubMsg::reply_on_success(msg, id))
+            }
+            _ => Ok(SubMsg::new(msg)),
+        })
+        .collect()
+}
+
+/// Returns the native tokens that MSG moves out of the treasury,
+/// including those sent over IBC or delegated to a validator. Funds
+/// sent to the DAO itself stay in the treasury.
+fn native_outflow<'a>(env: &Env, msg: &'a CosmosMsg<Empty>) -> &'a [Coin] {
+    match msg {
+        CosmosMsg::Bank(BankMsg::Send { to_address, amount })
+            if *to_address != env.contract.address =>
+        {
+            amount
+        }
+        CosmosMsg::Bank(BankMsg::Burn { amount }) => amount,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr,
+            funds,
+            ..
+        }) if *contract_addr != env.contract.address => funds,
+        CosmosMsg::Wasm(WasmMsg::Instantiate { funds, .. })
+        | CosmosMsg::Wasm(WasmMsg::Instantiate2 { funds, .. }) => funds,
+        CosmosMsg::Ibc(IbcMsg::Transfer { amount, .. })
+        | CosmosMsg::Staking(StakingMsg::Delegate { amount, .. }) => std::slice::from_ref(amount),
+        _ => &[],
+    }
+}
+
+/// Returns true if MSG may move funds out of the treasury in a way
+/// that can not be read from it, as a stargate message may.
+fn is_unmetered_msg(msg: &CosmosMsg<Empty>) -> bool {
+    matches!(msg, CosmosMsg::Stargate { .. })
+}
+
+/// Errors with the index of the first message in MSGS that may move
+/// funds in a way that can not be metered, if there is one.
+fn check_metered(msgs: &[CosmosMsg<Empty>]) -> Result<(), ContractError> {
+    match msgs.iter().position(is_unmetered_msg) {
+        Some(index) => Err(ContractError::UnmeteredOutflow { index }),
+        None => Ok(()),
+    }
+}
+
+/// A cw20 message that moves tokens out of the treasury, or allows
+/// another address to.
+struct Cw20Outflow {
+    /// The cw20 token contract.
+    token: String,
+    /// The address the tokens are moved to or may be spent by, if
+    /// any.
+    recipient: Option<String>,
+    amount: Uint128,
+}
+
+/// Returns the cw20 tokens that MSG moves out of the treasury, if it
+/// transfers, sends, burns, or grants an allowance of a cw20 token.
+/// Tokens transferred to the DAO itself stay in the treasury.
+fn cw20_outflow(env: &Env, msg: &CosmosMsg<Empty>) -> Option<Cw20Outflow> {
+    let (token, msg) = match msg {
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr, msg, ..
+        }) if *contract_addr != env.contract.address => (contract_addr, msg),
+        _ => return None,
+    };
+    let (recipient, amount) = match from_json_binary(msg).ok()? {
+        cw20::Cw20ExecuteMsg::Transfer { recipient, amount } => (Some(recipient), amount),
+        cw20::Cw20ExecuteMsg::Send {
+            contract, amount, ..
+        } => (Some(contract), amount),
+        cw20::Cw20ExecuteMsg::IncreaseAllowance {
+            spender, amount, ..
+        } => (Some(spender), amount),
+        cw20::Cw20ExecuteMsg::Burn { amount } => (None, amount),
+        _ => return None,
+    };
+    if recipient.as_deref() == Some(env.contract.address.as_str()) {
+        return None;
+    }
+    Some(Cw20Outflow {
+        token: token.clone(),
+        recipient,
+        amount,
+    })
+}
+
+/// Returns the native tokens that MSG deposits into a yield vault, if
+/// it executes `DepositToVault` on the DAO itself.
+fn vault_deposit(
+    storage: &dyn Storage,
+    env: &Env,
+    msg: &CosmosMsg<Empty>,
+) -> StdResult<Option<Coin>> {
+    match self_execute_msg(env, msg) {
+        Some(ExecuteMsg::DepositToVault { vault, amount }) => Ok(VAULTS
+            .may_load(storage, Addr::unchecked(vault))?
+            .map(|vault| Coin {
+                denom: vault.denom,
+                amount,
+            })),
+        _ => Ok(None),
+    }
+}
+
+/// Returns true if MSG moves, or may move, native or cw20 tokens out
+/// of the treasury, or executes a vault deposit or merge on the DAO
+/// itself.
+fn is_treasury_msg(env: &Env, msg: &CosmosMsg<Empty>) -> bool {
+    !native_outflow(env, msg).is_empty()
+        || cw20_outflow(env, msg).is_some()
+        || is_unmetered_msg(msg)
+        || matches!(
+            self_execute_msg(env, msg),
+            Some(
+                ExecuteMsg::DepositToVault { .. }
+                    | ExecuteMsg::ProposeMerge { .. }
+                    | ExecuteMsg::AdvanceMerge {}
+            )
+        )
+}
+
+/// Checks the messages of a proposal being executed against the
+/// circuit breaker, and records the proposal's outflows and, if
+/// COUNT_EXECUTION is set, its execution. If this execution exceeds
+/// the circuit breaker's bounds, the circuit breaker trips and the
+/// reason is returned. The execution that trips the circuit breaker
+/// is not itself blocked, but messages whose outflows can not be
+/// metered are rejected while outflows are limited.
+fn check_circuit_breaker(
+    storage: &mut dyn Storage,
+    env: &Env,
+    msgs: &[CosmosMsg<Empty>],
+    count_execution: bool,
+) -> Result<Option<String>, ContractError> {
+    if CIRCUIT_BREAKER_TRIP.may_load(storage)?.is_some() {
+        if msgs.iter().any(|msg| is_treasury_msg(env, msg)) {
+            return Err(ContractError::CircuitBreakerTripped {});
+        }
+        return Ok(None);
+    }
+    let circuit_breaker = match CIRCUIT_BREAKER.may_load(storage)? {
+        Some(circuit_breaker) => circuit_breaker,
+        None => return Ok(None),
+    };
+    if !circuit_breaker.outflow_limits.is_empty() || !circuit_breaker.cw20_outflow_limits.is_empty()
+    {
+        check_metered(msgs)?;
+    }
+
+    let mut reason = None;
+
+    if let Some(max) = circuit_breaker
+        .max_executions_per_block
+        .filter(|_| count_execution)
+    {
+        let count = match BLOCK_EXECUTIONS.may_load(storage)? {
+            Some((height, count)) if height == env.block.height => count + 1,
+            _ => 1,
+        };
+        BLOCK_EXECUTIONS.save(storage, &(env.block.height, count))?;
+        if count > max {
+            reason = Some(format!(
+                "{count} proposals executed at height {}, exceeding {max}",
+                env.block.height
+            ));
+        }
+    }
+
+    let mut spent: BTreeMap<&str, Uint128> = BTreeMap::new();
+    for coin in msgs.iter().flat_map(|msg| native_outflow(env, msg)) {
+        *spent.entry(coin.denom.as_str()).or_default() += coin.amount;
+    }
+    for limit in circuit_breaker.outflow_limits {
+        let total = record_outflow(
+            storage,
+            env,
+            &OUTFLOWS,
+            limit.denom.clone(),
+            circuit_breaker.window,
+            spent.get(limit.denom.as_str()).copied(),
+        )?;
+        if total > limit.amount && reason.is_none() {
+            reason = Some(format!(
+                "{total}{} left the treasury within the window, exceeding {limit}",
+                limit.denom
+            ));
+        }
+    }
+
+    let mut cw20_spent: BTreeMap<String, Uint128> = BTreeMap::new();
+    for outflow in msgs.iter().filter_map(|msg| cw20_outflow(env, msg)) {
+        *cw20_spent.entry(outflow.token).or_default() += outflow.amount;
+    }
+    for limit in circuit_breaker.cw20_outflow_limits {
+        let total = record_outflow(
+            storage,
+            env,
+            &CW20_OUTFLOWS,
+            Addr::unchecked(&limit.address),
+            circuit_breaker.window,
+            cw20_spent.get(&limit.address).copied(),
+        )?;
+        if total > limit.amount && reason.is_none() {
+            reason = Some(format!(
+                "{total} of cw20 {} left the treasury within the window, exceeding {}",
+                limit.address, limit.amount
+            ));
+        }
+    }
+
+    if let Some(reason) = &reason {
+        CIRCUIT_BREAKER_TRIP.save(
+            storage,
+            &CircuitBreakerTrip {
+                height: env.block.height,
+                reason: reason.clone(),
+            },
+        )?;
+    }
+    Ok(reason)
+}
+
+/// Adds AMOUNT, if any, to the outflows recorded for KEY, drops those
+/// that have left the circuit breaker's WINDOW, and returns the total
+/// that remains.
+fn record_outflow<'a, K: PrimaryKey<'a> + Clone>(
+    storage: &mut dyn Storage,
+    env: &Env,
+    outflows: &Map<'a, K, Vec<(Expiration, Uint128)>>,
+    key: K,
+    window: Duration,
+    amount: Option<Uint128>,
+) -> StdResult<Uint128> {
+    let mut entries = outflows.may_load(storage, key.clone())?.unwrap_or_default();
+    entries.retain(|(expiration, _)| !expiration.is_expired(&env.block));
+    if let Some(amount) = amount {
+        entries.push((window.after(&env.block), amount));
+    }
+    let total = entries
+        .iter()
+        .fold(Uint128::zero(), |total, (_, amount)| total + amount);
+    if entries.is_empty() {
+        outflows.remove(storage, key);
+    } else {
+        outflows.save(storage, key, &entries)?;
+    }
+    Ok(total)
+}
+
+/// Returns MSG parsed as a message to this contract, if it is one.
+fn self_execute_msg(env: &Env, msg: &CosmosMsg<Empty>) -> Option<ExecuteMsg> {
+    match msg {
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr, msg, ..
+        }) if *contract_addr == env.contract.address => from_json_binary(msg).ok(),
+        _ => None,
+    }
+}
+
+/// Checks the messages of a proposal from MODULE against the DAO's
+/// treasury tiers, if set. Proposals may not change the tiers, and
+/// only the cold module may confirm cold spends. Proposals from any
+/// other module are hot: they may not register vaults, propose
+/// merges, or send messages whose outflows can not be metered, and
+/// their native, cw20, and vault deposit outflows are recorded
+/// against the hot budget.
+fn check_treasury_tiers(
+    storage: &mut dyn Storage,
+    env: &Env,
+    module: &Addr,
+    msgs: &[CosmosMsg<Empty>],
+) -> Result<(), ContractError> {
+    let tiers = match TREASURY_TIERS.may_load(storage)? {
+        Some(tiers) => tiers,
+        None => return Ok(()),
+    };
+    let is_cold = tiers.cold_module.as_ref() == Some(module);
+
+    for msg in msgs {
+        match self_execute_msg(env, msg) {
+            Some(ExecuteMsg::UpdateTreasuryTiers { .. }) => {
+                return Err(ContractError::ColdSpendRequired {})
+            }
+            // Vaults may be handed, and merges move, the whole
+            // treasury.
+            Some(ExecuteMsg::UpdateVaults { .. } | ExecuteMsg::ProposeMerge { .. }) if !is_cold => {
+                return Err(ContractError::ColdSpendRequired {})
+            }
+            Some(ExecuteMsg::ConfirmColdSpend { .. }) => {
+                if tiers
+                    .cold_module
+                    .as_ref()
+                    .map_or(false, |cold_module| cold_module != module)
+                {
+                    return Err(ContractError::Unauthorized {});
+                }
+            }
+            _ => (),
+        }
+    }
+
+    if is_cold {
+        return Ok(());
+    }
+    check_metered(msgs)?;
+
+    let mut spent: BTreeMap<String, Uint128> = BTreeMap::new();
+    let mut cw20_spent: BTreeMap<String, Uint128> = BTreeMap::new();
+    for msg in msgs {
+        let native = native_outflow(env, msg).iter().cloned();
+        for coin in native.chain(vault_deposit(storage, env, msg)?) {
+            *spent.entry(coin.denom).or_default() += coin.amount;
+        }
+        if let Some(outflow) = cw20_outflow(env, msg) {
+            *cw20_spent.entry(outflow.token).or_default() += outflow.amount;
+        }
+    }
+    if spent.is_empty() && cw20_spent.is_empty() {
+        return Ok(());
+    }
+
+    if HOT_PERIOD_END
+        .may_load(storage)?
+        .map_or(true, |end| end.is_expired(&env.block))
+    {
+        clear_hot_spent(storage)?;
+        HOT_PERIOD_END.save(storage, &tiers.period.after(&env.block))?;
+    }
+    for (denom, amount) in spent {
+        let budget = tiers
+            .hot_budget
+            .iter()
+            .find(|coin| coin.denom == denom)
+            .map_or(Uint128::zero(), |coin| coin.amount);
+        let total = HOT_SPENT
+            .may_load(storage, denom.clone())?
+            .unwrap_or_default()
+            + amount;
+        if total > budget {
+            return Err(ContractError::HotBudgetExceeded { denom });
+        }
+        HOT_SPENT.save(storage, denom, &total)?;
+    }
+    for (token, amount) in cw20_spent {
+        let budget = tiers
+            .hot_cw20_budget
+            .iter()
+            .find(|coin| coin.address == token)
+            .map_or(Uint128::zero(), |coin| coin.amount);
+        let token = Addr::unchecked(token);
+        let total = HOT_CW20_SPENT
+            .may_load(storage, token.clone())?
+            .unwrap_or_default()
+            + amount;
+        if total > budget {
+            return Err(ContractError::HotBudgetExceeded {
+                denom: token.into_string(),
+            });
+        }
+        HOT_CW20_SPENT.save(storage, token, &total)?;
+    }
+    Ok(())
+}
+
+fn clear_hot_spent(storage: &mut dyn Storage) -> StdResult<()> {
+    let denoms = HOT_SPENT
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<String>>>()?;
+    for denom in denoms {
+        HOT_SPENT.remove(storage, denom);
+    }
+    let tokens = HOT_CW20_SPENT
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<Addr>>>()?;
+    for token in tokens {
+        HOT_CW20_SPENT.remove(storage, token);
+    }
+    Ok(())
+}
+
+/// Returns the frozen contract or denom that MSG interacts with, if
+/// any. This includes contracts that receive native or cw20 tokens
+/// from the treasury, and denoms sent over IBC or delegated. While
+/// anything is frozen, stargate messages, which can not be read, are
+/// treated as frozen too. Messages to the DAO itself are never frozen
+/// so that the freeze lists may always be updated by proposal.
+fn frozen_target(
+    storage: &dyn Storage,
+    env: &Env,
+    msg: &CosmosMsg<Empty>,
+) -> StdResult<Option<String>> {
+    let (mut contracts, funds): (Vec<String>, &[Coin]) = match msg {
+        CosmosMsg::Bank(BankMsg::Send { to_address, amount }) => (vec![to_address.clone()], amount),
+        CosmosMsg::Bank(BankMsg::Burn { amount }) => (vec![], amount),
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr,
+            funds,
+            ..
+        }) => (vec![contract_addr.clone()], funds),
+        CosmosMsg::Wasm(WasmMsg::Instantiate { funds, .. })
+        | CosmosMsg::Wasm(WasmMsg::Instantiate2 { funds, .. }) => (vec![], funds),
+        CosmosMsg::Ibc(IbcMsg::Transfer { amount, .. })
+        | CosmosMsg::Staking(StakingMsg::Delegate { amount, .. }) => {
+            (vec![], std::slice::from_ref(amount))
+        }
+        CosmosMsg::Stargate { type_url, .. } => {
+            let anything_frozen = FROZEN_CONTRACTS
+                .keys(storage, None, None, Order::Ascending)
+                .next()
+                .is_some()
+                || FROZEN_DENOMS
+                    .keys(storage, None, None, Order::Ascending)
+                    .next()
+                    .is_some();
+            return Ok(anything_frozen.then(|| type_url.clone()));
+        }
+        CosmosMsg::Wasm(WasmMsg::Migrate { contract_addr, .. })
+        | CosmosMsg::Wasm(WasmMsg::UpdateAdmin { contract_addr, .. })
+        | CosmosMsg::Wasm(WasmMsg::ClearAdmin { contract_addr }) => {
+            (vec![contract_addr.clone()], &[])
+        }
+        _ => (vec![], &[]),
+    };
+    if let Some(Cw20Outflow {
+        recipient: Some(recipient),
+        ..
+    }) = cw20_outflow(env, msg)
+    {
+        contracts.push(recipient);
+    }
+    for contract in contracts {
+        if contract != env.contract.address
+            && FROZEN_CONTRACTS.has(storage, Addr::unchecked(&contract))
+        {
+            return Ok(Some(contract));
+        }
+    }
+    Ok(funds
+        .iter()
+        .find(|coin| FROZEN_DENOMS.has(storage, coin.denom.clone()))
+        .map(|coin| coin.denom.clone()))
 }
 
 pub fn execute_nominate_admin(
@@ -228,14 +837,26 @@ pub fn execute_nominate_admin(
         return Err(ContractError::PendingNomination {});
     }
 
-    match &nomination {
-        Some(nomination) => NOMINATED_ADMIN.save(deps.storage, nomination)?,
+    let hook = match &nomination {
+        Some(nomination) => {
+            NOMINATED_ADMIN.save(deps.storage, nomination)?;
+            DaoLifecycleHookMsg::AdminNominated {
+                nominee: nomination.clone(),
+            }
+        }
         // If no admin set to default of the contract. This allows the
         // contract to later set a new admin via governance.
-        None => ADMIN.save(deps.storage, &env.contract.address)?,
-    }
+        None => {
+            ADMIN.save(deps.storage, &env.contract.address)?;
+            DaoLifecycleHookMsg::AdminChanged {
+                old_admin: current_admin,
+                new_admin: env.contract.address,
+            }
+        }
+    };
 
     Ok(Response::default()
+        .add_submessages(lifecycle_hooks(deps.storage, hook)?)
         .add_attribute("action", "execute_nominate_admin")
         .add_attribute(
             "nomination",
@@ -255,10 +876,17 @@ pub fn execute_accept_admin_nomination(
     if sender != nomination {
         return Err(ContractError::Unauthorized {});
     }
+    let old_admin = ADMIN.load(deps.storage)?;
     NOMINATED_ADMIN.remove(deps.storage);
     ADMIN.save(deps.storage, &nomination)?;
 
+    let hook = DaoLifecycleHookMsg::AdminChanged {
+        old_admin,
+        new_admin: nomination,
+    };
+
     Ok(Response::default()
+        .add_submessages(lifecycle_hooks(deps.storage, hook)?)
         .add_attribute("action", "execute_accept_admin_nomination")
         .add_attribute("new_admin", sender))
 }
@@ -273,14 +901,16 @@ pub fn execute_withdraw_admin_nomination(
     }
 
     // Check that there is indeed a nomination to withdraw.
-    let current_nomination = NOMINATED_ADMIN.may_load(deps.storage)?;
-    if current_nomination.is_none() {
-        return Err(ContractError::NoAdminNomination {});
-    }
+    let nominee = NOMINATED_ADMIN
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoAdminNomination {})?;
 
     NOMINATED_ADMIN.remove(deps.storage);
 
+    let hook = DaoLifecycleHookMsg::AdminNominationWithdrawn { nominee };
+
     Ok(Response::default()
+        .add_submessages(lifecycle_hooks(deps.storage, hook)?)
         .add_attribute("action", "execute_withdraw_admin_nomination")
         .add_attribute("sender", sender))
 }
@@ -295,7 +925,7 @@ pub fn execute_update_config(
         return Err(ContractError::Unauthorized {});
     }
 
-    CONFIG.save(deps.storage, &config)?;
+    save_config(deps.storage, env.block.height, &config)?;
     // We incur some gas costs by having the config's fields in the
     // response. This has the benefit that it makes it reasonably
     // simple to ask "when did this field in the config change" by
@@ -311,7 +941,85 @@ pub fn execute_update_config(
         ))
 }
 
+pub fn execute_rollback_config(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    height: u64,
+) -> Result<Response, ContractError> {
+    if sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let config = config_at_height(deps.storage, height)?
+        .ok_or(ContractError::NoConfigAtHeight { height })?;
+    save_config(deps.storage, env.block.height, &config)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_rollback_config")
+        .add_attribute("height", height.to_string())
+        .add_attribute("name", config.name)
+        .add_attribute("description", config.description)
+        .add_attribute(
+            "image_url",
+            config.image_url.unwrap_or_else(|| "None".to_string()),
+        ))
+}
+
+/// Saves `config` as the current config and records it in the config
+/// history at `height`.
+fn save_config(storage: &mut dyn Storage, height: u64, config: &Config) -> StdResult<()> {
+    CONFIG.save(storage, config)?;
+    CONFIG_HISTORY.save(storage, height, config)
+}
+
+/// Loads the config that was in effect at the end of block `height`.
+fn config_at_height(storage: &dyn Storage, height: u64) -> StdResult<Option<Config>> {
+    CONFIG_HISTORY
+        .range(
+            storage,
+            None,
+            Some(Bound::inclusive(height)),
+            Order::Descending,
+        )
+        .next()
+        .transpose()
+        .map(|entry| entry.map(|(_, config)| config))
+}
+
+/// Saves `module` as a current proposal module and records it in the
+/// proposal module history at `height`.
+fn save_proposal_module(
+    storage: &mut dyn Storage,
+    height: u64,
+    module: &ProposalModule,
+) -> StdResult<()> {
+    PROPOSAL_MODULES.save(storage, module.address.clone(), module)?;
+    PROPOSAL_MODULE_HISTORY.save(storage, (module.address.clone(), height), module)
+}
+
+/// Loads the proposal module at `address` as of the end of block
+/// `height`, if it had been added by then.
+fn proposal_module_at_height(
+    storage: &dyn Storage,
+    address: Addr,
+    height: u64,
+) -> StdResult<Option<ProposalModule>> {
+    PROPOSAL_MODULE_HISTORY
+        .prefix(address)
+        .range(
+            storage,
+            None,
+            Some(Bound::inclusive(height)),
+            Order::Descending,
+        )
+        .next()
+        .transpose()
+        .map(|entry| entry.map(|(_, module)| module))
+}
+
 pub fn execute_update_voting_module(
+    deps: DepsMut,
     env: Env,
     sender: Addr,
     module: ModuleInstantiateInfo,
@@ -319,6 +1027,7 @@ pub fn execute_update_voting_module(
     if env.contract.address != sender {
         return Err(ContractError::Unauthorized {});
     }
+    check_official_code_id(deps.storage, module.code_id)?;
 
     let wasm = module.into_wasm_msg(env.contract.address);
     let submessage = SubMsg::reply_on_success(wasm, VOTE_MODULE_UPDATE_REPLY_ID);
@@ -338,6 +1047,9 @@ pub fn execute_update_proposal_modules(
     if env.contract.address != sender {
         return Err(ContractError::Unauthorized {});
     }
+    for module in to_add.iter() {
+        check_official_code_id(deps.storage, module.code_id)?;
+    }
 
     let disable_count = to_disable.len() as u32;
     for addr in to_disable {
@@ -355,7 +1067,7 @@ pub fn execute_update_proposal_modules(
         }
 
         module.status = ProposalModuleStatus::Disabled {};
-        PROPOSAL_MODULES.save(deps.storage, addr, &module)?;
+        save_proposal_module(deps.storage, env.block.height, &module)?;
     }
 
     // If disabling this module will cause there to be no active modules, return error.
@@ -382,11 +1094,11 @@ pub fn execute_update_proposal_modules(
 /// Updates a set of addresses in state applying VERIFY to each item
 /// that will be added.
 fn do_update_addr_list(
-    deps: DepsMut,
+    mut deps: DepsMut,
     map: Map<Addr, Empty>,
     to_add: Vec<String>,
     to_remove: Vec<String>,
-    verify: impl Fn(&Addr, Deps) -> StdResult<()>,
+    verify: impl Fn(&Addr, DepsMut) -> StdResult<()>,
 ) -> Result<(), ContractError> {
     let to_add = to_add
         .into_iter()
@@ -399,7 +1111,7 @@ fn do_update_addr_list(
         .collect::<Result<Vec<_>, _>>()?;
 
     for addr in to_add {
-        verify(&addr, deps.as_ref())?;
+        verify(&addr, deps.branch())?;
         map.save(deps.storage, addr, &Empty {})?;
     }
     for addr in to_remove {
@@ -419,21 +1131,18 @@ pub fn execute_update_cw20_list(
     if env.contract.address != sender {
         return Err(ContractError::Unauthorized {});
     }
+    for addr in to_remove.iter() {
+        CW20_BALANCES.remove(deps.storage, deps.api.addr_validate(addr)?);
+    }
     do_update_addr_list(deps, CW20_LIST, to_add, to_remove, |addr, deps| {
         // Perform a balance query here as this is the query performed
         // by the `Cw20Balances` query.
-        let _info: cw20::BalanceResponse = deps.querier.query_wasm_smart(
-            addr,
-            &cw20::Cw20QueryMsg::Balance {
-                address: env.contract.address.to_string(),
-            },
-        )?;
-        Ok(())
+        cache_cw20_balance(deps, &env, addr.clone())
     })?;
     Ok(Response::default().add_attribute("action", "update_cw20_list"))
 }
 
-pub fn execute_update_cw721_list(
+pub fn execute_update_frozen_contracts(
     deps: DepsMut,
     env: Env,
     sender: Addr,
@@ -443,92 +1152,1339 @@ pub fn execute_update_cw721_list(
     if env.contract.address != sender {
         return Err(ContractError::Unauthorized {});
     }
-    do_update_addr_list(deps, CW721_LIST, to_add, to_remove, |addr, deps| {
-        let _info: cw721::ContractInfoResponse = deps
-            .querier
-            .query_wasm_smart(addr, &cw721::Cw721QueryMsg::ContractInfo {})?;
-        Ok(())
-    })?;
-    Ok(Response::default().add_attribute("action", "update_cw721_list"))
+    do_update_addr_list(deps, FROZEN_CONTRACTS, to_add, to_remove, |_, _| Ok(()))?;
+    Ok(Response::default().add_attribute("action", "update_frozen_contracts"))
 }
 
-pub fn execute_set_item(
+pub fn execute_update_frozen_denoms(
     deps: DepsMut,
     env: Env,
     sender: Addr,
-    key: String,
-    value: String,
+    to_add: Vec<String>,
+    to_remove: Vec<String>,
 ) -> Result<Response, ContractError> {
     if env.contract.address != sender {
         return Err(ContractError::Unauthorized {});
     }
-
-    ITEMS.save(deps.storage, key.clone(), &value)?;
-    Ok(Response::default()
-        .add_attribute("action", "execute_set_item")
-        .add_attribute("key", key)
-        .add_attribute("addr", value))
+    for denom in to_add {
+        if !is_valid_denom(&denom) {
+            return Err(ContractError::InvalidDenom { denom });
+        }
+        FROZEN_DENOMS.save(deps.storage, denom, &Empty {})?;
+    }
+    for denom in to_remove {
+        FROZEN_DENOMS.remove(deps.storage, denom);
+    }
+    Ok(Response::default().add_attribute("action", "update_frozen_denoms"))
 }
 
-pub fn execute_remove_item(
+pub fn execute_update_vaults(
     deps: DepsMut,
     env: Env,
     sender: Addr,
-    key: String,
+    to_add: Vec<VaultRegistration>,
+    to_remove: Vec<String>,
 ) -> Result<Response, ContractError> {
     if env.contract.address != sender {
         return Err(ContractError::Unauthorized {});
     }
+    let is_empty = |storage: &dyn Storage, addr: &Addr| -> StdResult<bool> {
+        Ok(VAULT_DEPOSITS
+            .may_load(storage, addr.clone())?
+            .unwrap_or_default()
+            .is_zero())
+    };
 
-    if ITEMS.has(deps.storage, key.clone()) {
-        ITEMS.remove(deps.storage, key.clone());
-        Ok(Response::default()
-            .add_attribute("action", "execute_remove_item")
-            .add_attribute("key", key))
-    } else {
-        Err(ContractError::KeyMissing {})
+    for address in to_remove {
+        let addr = deps.api.addr_validate(&address)?;
+        if !is_empty(deps.storage, &addr)? {
+            return Err(ContractError::VaultNotEmpty { address });
+        }
+        VAULTS.remove(deps.storage, addr.clone());
+        VAULT_DEPOSITS.remove(deps.storage, addr);
+    }
+    for VaultRegistration {
+        address,
+        denom,
+        cap,
+    } in to_add
+    {
+        let addr = deps.api.addr_validate(&address)?;
+        if !is_valid_denom(&denom) {
+            return Err(ContractError::InvalidDenom { denom });
+        }
+        // Deposits are tracked in the vault's denom, so it may not
+        // change while funds are deposited.
+        if let Some(existing) = VAULTS.may_load(deps.storage, addr.clone())? {
+            if existing.denom != denom && !is_empty(deps.storage, &addr)? {
+                return Err(ContractError::VaultNotEmpty { address });
+            }
+        }
+        VAULTS.save(deps.storage, addr, &Vault { denom, cap })?;
     }
+
+    Ok(Response::default().add_attribute("action", "update_vaults"))
 }
 
-pub fn execute_update_sub_daos_list(
+pub fn execute_update_vault_allocation_limit(
     deps: DepsMut,
     env: Env,
     sender: Addr,
-    to_add: Vec<SubDao>,
-    to_remove: Vec<String>,
+    limit: Decimal,
 ) -> Result<Response, ContractError> {
     if env.contract.address != sender {
         return Err(ContractError::Unauthorized {});
     }
-
-    for addr in to_remove {
-        let addr = deps.api.addr_validate(&addr)?;
-        SUBDAO_LIST.remove(deps.storage, &addr);
-    }
-
-    for subdao in to_add {
-        let addr = deps.api.addr_validate(&subdao.addr)?;
-        SUBDAO_LIST.save(deps.storage, &addr, &subdao.charter)?;
+    if limit > Decimal::one() {
+        return Err(ContractError::InvalidVaultAllocationLimit {});
     }
-
+    VAULT_ALLOCATION_LIMIT.save(deps.storage, &limit)?;
     Ok(Response::default()
-        .add_attribute("action", "execute_update_sub_daos_list")
-        .add_attribute("sender", sender))
+        .add_attribute("action", "update_vault_allocation_limit")
+        .add_attribute("limit", limit.to_string()))
 }
 
-pub fn execute_receive_cw20(deps: DepsMut, sender: Addr) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
-    if !config.automatically_add_cw20s {
-        Ok(Response::new())
-    } else {
-        CW20_LIST.save(deps.storage, sender.clone(), &Empty {})?;
-        Ok(Response::new()
-            .add_attribute("action", "receive_cw20")
-            .add_attribute("token", sender))
+pub fn execute_deposit_to_vault(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    vault: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
     }
+    let addr = deps.api.addr_validate(&vault)?;
+    let Vault { denom, cap } = VAULTS
+        .may_load(deps.storage, addr.clone())?
+        .ok_or(ContractError::VaultNotRegistered { address: vault })?;
+    deposit_to_vault(deps, &env, addr, denom, cap, amount)
 }
 
-pub fn execute_receive_cw721(deps: DepsMut, sender: Addr) -> Result<Response, ContractError> {
+/// Deposits AMOUNT of DENOM into the vault at ADDR, checking its cap
+/// and the vault allocation limit.
+fn deposit_to_vault(
+    deps: DepsMut,
+    env: &Env,
+    addr: Addr,
+    denom: String,
+    cap: Uint128,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    if amount.is_zero() {
+        return Err(ContractError::ZeroVaultAmount {});
+    }
+
+    let deposited = VAULT_DEPOSITS
+        .may_load(deps.storage, addr.clone())?
+        .unwrap_or_default()
+        .checked_add(amount)
+        .map_err(StdError::overflow)?;
+    if deposited > cap {
+        return Err(ContractError::VaultCapExceeded { cap });
+    }
+
+    // The DAO's holdings of DENOM include what is already deposited,
+    // so the limit applies to its holdings as a whole rather than to
+    // what remains in the treasury.
+    let mut in_vaults = Uint128::zero();
+    for entry in VAULTS.range(deps.storage, None, None, Order::Ascending) {
+        let (other, other_vault) = entry?;
+        if other_vault.denom == denom {
+            in_vaults += VAULT_DEPOSITS
+                .may_load(deps.storage, other)?
+                .unwrap_or_default();
+        }
+    }
+    let balance = deps
+        .querier
+        .query_balance(&env.contract.address, &denom)?
+        .amount;
+    let limit = VAULT_ALLOCATION_LIMIT
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    let holdings = balance.checked_add(in_vaults).map_err(StdError::overflow)?;
+    if in_vaults.saturating_add(amount) > holdings * limit {
+        return Err(ContractError::VaultAllocationExceeded { limit, denom });
+    }
+
+    VAULT_DEPOSITS.save(deps.storage, addr.clone(), &deposited)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "deposit_to_vault")
+        .add_attribute("vault", addr.as_str())
+        .add_attribute("amount", amount)
+        .add_message(WasmMsg::Execute {
+            contract_addr: addr.into_string(),
+            msg: to_json_binary(&VaultExecuteMsg::Deposit {})?,
+            funds: coins(amount.u128(), denom),
+        }))
+}
+
+pub fn execute_withdraw_from_vault(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    vault: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    let addr = deps.api.addr_validate(&vault)?;
+    if !VAULTS.has(deps.storage, addr.clone()) {
+        return Err(ContractError::VaultNotRegistered { address: vault });
+    }
+    if amount.is_zero() {
+        return Err(ContractError::ZeroVaultAmount {});
+    }
+
+    // Withdrawals beyond what was deposited are yield, and do not
+    // reduce the deposited amount below zero.
+    let deposited = VAULT_DEPOSITS
+        .may_load(deps.storage, addr.clone())?
+        .unwrap_or_default()
+        .saturating_sub(amount);
+    VAULT_DEPOSITS.save(deps.storage, addr.clone(), &deposited)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "withdraw_from_vault")
+        .add_attribute("vault", addr.as_str())
+        .add_attribute("amount", amount)
+        .add_message(WasmMsg::Execute {
+            contract_addr: addr.into_string(),
+            msg: to_json_binary(&VaultExecuteMsg::Withdraw { amount })?,
+            funds: vec![],
+        }))
+}
+
+pub fn execute_refresh_cw20_balances(
+    mut deps: DepsMut,
+    env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    // Pages in the same order as `Cw20TokenList` so its entries may
+    // be used as START_AFTER.
+    let addrs = list_cw20s(deps.as_ref(), start_after, limit, None)?;
+    for addr in addrs.iter() {
+        cache_cw20_balance(deps.branch(), &env, addr.clone())?;
+    }
+    Ok(Response::default()
+        .add_attribute("action", "refresh_cw20_balances")
+        .add_attribute("refreshed", addrs.len().to_string()))
+}
+
+/// Queries the contract's balance of TOKEN and saves it to the cw20
+/// balance cache.
+fn cache_cw20_balance(deps: DepsMut, env: &Env, token: Addr) -> StdResult<()> {
+    let balance: cw20::BalanceResponse = deps.querier.query_wasm_smart(
+        token.clone(),
+        &cw20::Cw20QueryMsg::Balance {
+            address: env.contract.address.to_string(),
+        },
+    )?;
+    CW20_BALANCES.save(deps.storage, token, &(balance.balance, env.block.height))
+}
+
+/// Deducts the amount of any transfer, send, or burn of a registered
+/// cw20 token in MSGS from the cw20 balance cache. Spends via an
+/// allowance are not seen here and are picked up by
+/// `RefreshCw20Balances`.
+fn cache_cw20_spends(deps: DepsMut, env: &Env, msgs: &[CosmosMsg<Empty>]) -> StdResult<()> {
+    for msg in msgs {
+        let (token, msg) = match msg {
+            // The address is only used as a lookup key so it need
+            // not be validated.
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr, msg, ..
+            }) => (Addr::unchecked(contract_addr), msg),
+            _ => continue,
+        };
+        let balance = match CW20_BALANCES.may_load(deps.storage, token.clone())? {
+            Some((balance, _)) => balance,
+            None => continue,
+        };
+        let spent = match from_json_binary(msg) {
+            Ok(cw20::Cw20ExecuteMsg::Transfer { amount, .. })
+            | Ok(cw20::Cw20ExecuteMsg::Send { amount, .. })
+            | Ok(cw20::Cw20ExecuteMsg::Burn { amount }) => amount,
+            _ => continue,
+        };
+        CW20_BALANCES.save(
+            deps.storage,
+            token,
+            &(balance.saturating_sub(spent), env.block.height),
+        )?;
+    }
+    Ok(())
+}
+
+pub fn execute_update_cw721_list(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    to_add: Vec<String>,
+    to_remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    do_update_addr_list(deps, CW721_LIST, to_add, to_remove, |addr, deps| {
+        let _info: cw721::ContractInfoResponse = deps
+            .querier
+            .query_wasm_smart(addr, &cw721::Cw721QueryMsg::ContractInfo {})?;
+        Ok(())
+    })?;
+    Ok(Response::default().add_attribute("action", "update_cw721_list"))
+}
+
+pub fn execute_update_circuit_breaker(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    circuit_breaker: Option<CircuitBreaker>,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match circuit_breaker {
+        Some(circuit_breaker) => {
+            let mut denoms = circuit_breaker
+                .outflow_limits
+                .iter()
+                .map(|limit| limit.denom.as_str())
+                .collect::<Vec<_>>();
+            denoms.sort_unstable();
+            denoms.dedup();
+            let mut tokens = circuit_breaker
+                .cw20_outflow_limits
+                .iter()
+                .map(|limit| deps.api.addr_validate(&limit.address))
+                .collect::<StdResult<Vec<_>>>()?;
+            tokens.sort_unstable();
+            tokens.dedup();
+            if denoms.len() != circuit_breaker.outflow_limits.len()
+                || tokens.len() != circuit_breaker.cw20_outflow_limits.len()
+                || circuit_breaker
+                    .outflow_limits
+                    .iter()
+                    .any(|limit| limit.amount.is_zero())
+                || circuit_breaker
+                    .cw20_outflow_limits
+                    .iter()
+                    .any(|limit| limit.amount.is_zero())
+                || circuit_breaker.max_executions_per_block == Some(0)
+            {
+                return Err(ContractError::InvalidCircuitBreaker {});
+            }
+            CIRCUIT_BREAKER.save(deps.storage, &circuit_breaker)?;
+        }
+        None => CIRCUIT_BREAKER.remove(deps.storage),
+    }
+
+    Ok(Response::default().add_attribute("action", "update_circuit_breaker"))
+}
+
+pub fn execute_reset_circuit_breaker(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    CIRCUIT_BREAKER_TRIP.remove(deps.storage);
+    BLOCK_EXECUTIONS.remove(deps.storage);
+    let denoms = OUTFLOWS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<String>>>()?;
+    for denom in denoms {
+        OUTFLOWS.remove(deps.storage, denom);
+    }
+    let tokens = CW20_OUTFLOWS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<Addr>>>()?;
+    for token in tokens {
+        CW20_OUTFLOWS.remove(deps.storage, token);
+    }
+
+    Ok(Response::default().add_attribute("action", "reset_circuit_breaker"))
+}
+
+pub fn execute_update_treasury_tiers(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    tiers: Option<TreasuryTiers>,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match tiers {
+        Some(tiers) => {
+            let mut denoms = tiers
+                .hot_budget
+                .iter()
+                .map(|coin| coin.denom.as_str())
+                .collect::<Vec<_>>();
+            denoms.sort_unstable();
+            denoms.dedup();
+            if denoms.len() != tiers.hot_budget.len()
+                || tiers.hot_budget.iter().any(|coin| coin.amount.is_zero())
+            {
+                return Err(ContractError::InvalidTreasuryTiers {});
+            }
+            let mut tokens = tiers
+                .hot_cw20_budget
+                .iter()
+                .map(|coin| deps.api.addr_validate(&coin.address))
+                .collect::<StdResult<Vec<_>>>()?;
+            tokens.sort_unstable();
+            tokens.dedup();
+            if tokens.len() != tiers.hot_cw20_budget.len()
+                || tiers
+                    .hot_cw20_budget
+                    .iter()
+                    .any(|coin| coin.amount.is_zero())
+            {
+                return Err(ContractError::InvalidTreasuryTiers {});
+            }
+            if let Some(cold_module) = &tiers.cold_module {
+                let cold_module = deps.api.addr_validate(cold_module.as_str())?;
+                if !PROPOSAL_MODULES.has(deps.storage, cold_module) {
+                    return Err(ContractError::InvalidTreasuryTiers {});
+                }
+            }
+            TREASURY_TIERS.save(deps.storage, &tiers)?;
+        }
+        None => TREASURY_TIERS.remove(deps.storage),
+    }
+    // Changing the budget starts a new period.
+    clear_hot_spent(deps.storage)?;
+    HOT_PERIOD_END.remove(deps.storage);
+
+    Ok(Response::default().add_attribute("action", "update_treasury_tiers"))
+}
+
+pub fn execute_request_cold_spend(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    msgs: Vec<CosmosMsg<Empty>>,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    let tiers = TREASURY_TIERS
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoTreasuryTiers {})?;
+
+    let id = COLD_SPEND_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+    COLD_SPEND_COUNT.save(deps.storage, &id)?;
+    let confirmable_at = tiers.cold_delay.after(&env.block);
+    COLD_SPENDS.save(
+        deps.storage,
+        id,
+        &ColdSpend {
+            msgs,
+            confirmable_at,
+        },
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("action", "request_cold_spend")
+        .add_attribute("id", id.to_string())
+        .add_attribute("confirmable_at", confirmable_at.to_string()))
+}
+
+pub fn execute_confirm_cold_spend(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    id: u64,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    let spend = COLD_SPENDS
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::NoSuchColdSpend { id })?;
+    if !spend.confirmable_at.is_expired(&env.block) {
+        return Err(ContractError::ColdSpendNotReady {
+            id,
+            confirmable_at: spend.confirmable_at,
+        });
+    }
+    COLD_SPENDS.remove(deps.storage, id);
+
+    let response = proposal_msgs_response(deps, &env, spend.msgs, false)?;
+    Ok(response
+        .add_attribute("action", "confirm_cold_spend")
+        .add_attribute("id", id.to_string()))
+}
+
+pub fn execute_cancel_cold_spend(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    id: u64,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if !COLD_SPENDS.has(deps.storage, id) {
+        return Err(ContractError::NoSuchColdSpend { id });
+    }
+    COLD_SPENDS.remove(deps.storage, id);
+
+    Ok(Response::default()
+        .add_attribute("action", "cancel_cold_spend")
+        .add_attribute("id", id.to_string()))
+}
+
+pub fn execute_update_managed_contracts(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    to_add: Vec<ManagedContractRegistration>,
+    to_remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for address in to_remove {
+        let addr = deps.api.addr_validate(&address)?;
+        MANAGED_CONTRACTS.remove(deps.storage, addr);
+    }
+    for ManagedContractRegistration {
+        address,
+        code_id,
+        label,
+    } in to_add
+    {
+        let addr = deps.api.addr_validate(&address)?;
+        MANAGED_CONTRACTS.save(deps.storage, addr, &ManagedContract { code_id, label })?;
+    }
+
+    Ok(Response::default().add_attribute("action", "update_managed_contracts"))
+}
+
+pub fn execute_propose_merge(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    counterparty: String,
+    role: MergeRole,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(merge) = MERGE.may_load(deps.storage)? {
+        if merge.step != MergeStep::Complete {
+            return Err(ContractError::MergeInProgress {
+                counterparty: merge.counterparty.into_string(),
+            });
+        }
+    }
+    let counterparty = deps.api.addr_validate(&counterparty)?;
+    if counterparty == env.contract.address {
+        return Err(ContractError::SelfMerge {});
+    }
+
+    MERGE.save(
+        deps.storage,
+        &Merge {
+            counterparty: counterparty.clone(),
+            role,
+            step: MergeStep::AwaitingCounterparty,
+        },
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("action", "propose_merge")
+        .add_attribute("counterparty", counterparty))
+}
+
+pub fn execute_cancel_merge(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let merge = MERGE
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoMerge {})?;
+    if merge.step != MergeStep::AwaitingCounterparty {
+        return Err(ContractError::MergeStarted {});
+    }
+    // The counterparty may have begun its side of the merge before
+    // this DAO has, in which case the merge is committed. A
+    // counterparty that can not be queried has not begun.
+    let mirrored: Option<Merge> = deps
+        .querier
+        .query_wasm_smart(&merge.counterparty, &QueryMsg::Merge {})
+        .unwrap_or(None);
+    if mirrored.map_or(false, |mirrored| {
+        mirrored.counterparty == env.contract.address
+            && mirrored.role != merge.role
+            && mirrored.step != MergeStep::AwaitingCounterparty
+    }) {
+        return Err(ContractError::MergeStarted {});
+    }
+    MERGE.remove(deps.storage);
+
+    Ok(Response::default().add_attribute("action", "cancel_merge"))
+}
+
+pub fn execute_advance_merge(mut deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let mut merge = MERGE
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoMerge {})?;
+
+    if merge.step == MergeStep::AwaitingCounterparty {
+        // The counterparty must have proposed the same merge from its
+        // side. Once either DAO begins, neither may cancel.
+        let mirrored: Option<Merge> = deps
+            .querier
+            .query_wasm_smart(&merge.counterparty, &QueryMsg::Merge {})?;
+        let mirrored = mirrored.map_or(false, |mirrored| {
+            mirrored.counterparty == env.contract.address && mirrored.role != merge.role
+        });
+        if !mirrored {
+            return Err(ContractError::MergeNotMirrored {});
+        }
+        merge.step = match merge.role {
+            MergeRole::Absorbed => MergeStep::Tokens,
+            MergeRole::Absorbing => MergeStep::Items,
+        };
+    }
+
+    let step = merge.step;
+    let (next, resp) = match step {
+        MergeStep::AwaitingCounterparty => unreachable!("merge was confirmed above"),
+        MergeStep::Tokens => {
+            // Deposits can not be sent with the rest of the treasury,
+            // so vaults must be emptied first.
+            for entry in VAULT_DEPOSITS.range(deps.storage, None, None, Order::Ascending) {
+                let (vault, deposited) = entry?;
+                if !deposited.is_zero() {
+                    return Err(ContractError::VaultNotEmpty {
+                        address: vault.into_string(),
+                    });
+                }
+            }
+            let msgs = merge_tokens(deps.storage, &deps.querier, &env, &merge.counterparty)?;
+            (
+                MergeStep::Nfts,
+                proposal_msgs_response(deps.branch(), &env, msgs, false)?,
+            )
+        }
+        MergeStep::Nfts => {
+            let msgs = merge_nfts(deps.storage, &deps.querier, &env, &merge.counterparty)?;
+            // Stay on this step until there is nothing left to send.
+            let next = if msgs.is_empty() {
+                MergeStep::Complete
+            } else {
+                MergeStep::Nfts
+            };
+            (
+                next,
+                proposal_msgs_response(deps.branch(), &env, msgs, false)?,
+            )
+        }
+        MergeStep::Items => (
+            MergeStep::SubDaos,
+            merge_items(deps.storage, &deps.querier, &merge.counterparty)?,
+        ),
+        MergeStep::SubDaos => (
+            MergeStep::Complete,
+            merge_sub_daos(deps.storage, &deps.querier, &merge.counterparty)?,
+        ),
+        MergeStep::Complete => return Err(ContractError::MergeComplete {}),
+    };
+    merge.step = next;
+    MERGE.save(deps.storage, &merge)?;
+
+    Ok(resp
+        .add_attribute("action", "advance_merge")
+        .add_attribute("counterparty", merge.counterparty)
+        .add_attribute("step", format!("{step:?}")))
+}
+
+pub fn execute_skip_merge_step(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut merge = MERGE
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoMerge {})?;
+    let step = merge.step;
+    merge.step = match step {
+        MergeStep::AwaitingCounterparty => return Err(ContractError::MergeNotStarted {}),
+        MergeStep::Tokens => MergeStep::Nfts,
+        MergeStep::Items => MergeStep::SubDaos,
+        MergeStep::Nfts | MergeStep::SubDaos => MergeStep::Complete,
+        MergeStep::Complete => return Err(ContractError::MergeComplete {}),
+    };
+    MERGE.save(deps.storage, &merge)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "skip_merge_step")
+        .add_attribute("step", format!("{step:?}")))
+}
+
+/// Returns messages sending the DAO's native and registered cw20
+/// balances, other than frozen ones, to RECIPIENT. Like a proposal's
+/// messages, these are checked against the freeze list and circuit
+/// breaker before they are executed.
+fn merge_tokens(
+    storage: &mut dyn Storage,
+    querier: &QuerierWrapper,
+    env: &Env,
+    recipient: &Addr,
+) -> StdResult<Vec<CosmosMsg>> {
+    let mut msgs = vec![];
+
+    let natives: Vec<Coin> = querier
+        .query_all_balances(&env.contract.address)?
+        .into_iter()
+        .filter(|coin| !FROZEN_DENOMS.has(storage, coin.denom.clone()))
+        .collect();
+    if !natives.is_empty() {
+        msgs.push(
+            BankMsg::Send {
+                to_address: recipient.to_string(),
+                amount: natives,
+            }
+            .into(),
+        );
+    }
+
+    let cw20s = CW20_LIST
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<Addr>>>()?;
+    for cw20 in cw20s {
+        if FROZEN_CONTRACTS.has(storage, cw20.clone()) {
+            continue;
+        }
+        let balance: cw20::BalanceResponse = querier.query_wasm_smart(
+            &cw20,
+            &cw20::Cw20QueryMsg::Balance {
+                address: env.contract.address.to_string(),
+            },
+        )?;
+        CW20_BALANCES.save(storage, cw20.clone(), &(Uint128::zero(), env.block.height))?;
+        if !balance.balance.is_zero() {
+            msgs.push(
+                WasmMsg::Execute {
+                    contract_addr: cw20.into_string(),
+                    msg: to_json_binary(&cw20::Cw20ExecuteMsg::Transfer {
+                        recipient: recipient.to_string(),
+                        amount: balance.balance,
+                    })?,
+                    funds: vec![],
+                }
+                .into(),
+            );
+        }
+    }
+
+    Ok(msgs)
+}
+
+/// Returns messages sending up to `MERGE_NFT_BATCH` NFTs from each
+/// registered, unfrozen cw721 collection to RECIPIENT.
+fn merge_nfts(
+    storage: &dyn Storage,
+    querier: &QuerierWrapper,
+    env: &Env,
+    recipient: &Addr,
+) -> StdResult<Vec<CosmosMsg>> {
+    let mut msgs = vec![];
+    let cw721s = CW721_LIST
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<Addr>>>()?;
+    for cw721 in cw721s {
+        if FROZEN_CONTRACTS.has(storage, cw721.clone()) {
+            continue;
+        }
+        let tokens: cw721::TokensResponse = querier.query_wasm_smart(
+            &cw721,
+            &cw721::Cw721QueryMsg::Tokens {
+                owner: env.contract.address.to_string(),
+                start_after: None,
+                limit: Some(MERGE_NFT_BATCH),
+            },
+        )?;
+        for token_id in tokens.tokens {
+            msgs.push(
+                WasmMsg::Execute {
+                    contract_addr: cw721.to_string(),
+                    msg: to_json_binary(&cw721::Cw721ExecuteMsg::TransferNft {
+                        recipient: recipient.to_string(),
+                        token_id,
+                    })?,
+                    funds: vec![],
+                }
+                .into(),
+            );
+        }
+    }
+    Ok(msgs)
+}
+
+/// Copies the items of the absorbed DAO, skipping keys this DAO
+/// already has a value for.
+fn merge_items(
+    storage: &mut dyn Storage,
+    querier: &QuerierWrapper,
+    absorbed: &Addr,
+) -> Result<Response, ContractError> {
+    let items: Vec<(String, String)> = query_all_pages(
+        querier,
+        absorbed,
+        |start_after| QueryMsg::ListItems {
+            start_after,
+            limit: Some(MAX_LIMIT),
+            descending: None,
+        },
+        |(key, _)| key.clone(),
+    )?;
+    let mut skipped = vec![];
+    for (key, value) in items {
+        if ITEMS.has(storage, key.clone()) {
+            skipped.push(key);
+        } else {
+            ITEMS.save(storage, key, &value)?;
+        }
+    }
+    Ok(Response::default().add_attribute("skipped_items", skipped.join(",")))
+}
+
+/// Queries every page of a list query on CONTRACT. PAGE builds the
+/// query for the entries after a cursor, which CURSOR gets from the
+/// last entry of the previous page. Pages are `MAX_LIMIT` long.
+fn query_all_pages<T: DeserializeOwned>(
+    querier: &QuerierWrapper,
+    contract: &Addr,
+    page: impl Fn(Option<String>) -> QueryMsg,
+    cursor: impl Fn(&T) -> String,
+) -> StdResult<Vec<T>> {
+    let mut entries = vec![];
+    let mut start_after = None;
+    loop {
+        let next: Vec<T> = querier.query_wasm_smart(contract, &page(start_after))?;
+        let last = next.len() < MAX_LIMIT as usize;
+        start_after = next.last().map(&cursor);
+        entries.extend(next);
+        if last {
+            return Ok(entries);
+        }
+    }
+}
+
+/// Adds the SubDAOs and registered tokens of the absorbed DAO to this
+/// DAO.
+fn merge_sub_daos(
+    storage: &mut dyn Storage,
+    querier: &QuerierWrapper,
+    absorbed: &Addr,
+) -> Result<Response, ContractError> {
+    let sub_daos: Vec<SubDao> = query_all_pages(
+        querier,
+        absorbed,
+        |start_after| QueryMsg::ListSubDaos {
+            start_after,
+            limit: Some(MAX_LIMIT),
+            descending: None,
+        },
+        |sub_dao: &SubDao| sub_dao.addr.clone(),
+    )?;
+    let mut added = vec![];
+    for SubDao { addr, charter } in sub_daos {
+        let addr = Addr::unchecked(addr);
+        if !SUBDAO_LIST.has(storage, &addr) {
+            SUBDAO_LIST.save(storage, &addr, &charter)?;
+            added.push(addr);
+        }
+    }
+
+    let cw20s: Vec<Addr> = query_all_pages(
+        querier,
+        absorbed,
+        |start_after| QueryMsg::Cw20TokenList {
+            start_after,
+            limit: Some(MAX_LIMIT),
+            descending: None,
+        },
+        |cw20: &Addr| cw20.to_string(),
+    )?;
+    for cw20 in cw20s {
+        CW20_LIST.save(storage, cw20, &Empty {})?;
+    }
+    let cw721s: Vec<Addr> = query_all_pages(
+        querier,
+        absorbed,
+        |start_after| QueryMsg::Cw721TokenList {
+            start_after,
+            limit: Some(MAX_LIMIT),
+            descending: None,
+        },
+        |cw721: &Addr| cw721.to_string(),
+    )?;
+    for cw721 in cw721s {
+        CW721_LIST.save(storage, cw721, &Empty {})?;
+    }
+
+    let hooks = if added.is_empty() {
+        vec![]
+    } else {
+        lifecycle_hooks(
+            storage,
+            DaoLifecycleHookMsg::SubDaosUpdated {
+                added,
+                removed: vec![],
+            },
+        )?
+    };
+    Ok(Response::default().add_submessages(hooks))
+}
+
+pub fn execute_update_pinned_codes(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    to_add: Vec<u64>,
+    to_remove: Vec<u64>,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for code_id in to_add.iter() {
+        PINNED_CODES.save(deps.storage, *code_id, &Empty {})?;
+    }
+    for code_id in to_remove.iter() {
+        PINNED_CODES.remove(deps.storage, *code_id);
+    }
+
+    // wasmd only accepts `MsgPinCodes` and `MsgUnpinCodes` from the
+    // wasm module's authority, usually x/gov, so the list is recorded
+    // for a chain governance proposal to act on rather than sent.
+    Ok(Response::default().add_attribute("action", "update_pinned_codes"))
+}
+
+pub fn execute_update_code_id_registrar(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    registrar: Option<String>,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match registrar {
+        Some(registrar) => {
+            let registrar = deps.api.addr_validate(&registrar)?;
+            // The official code IDs may only be changed by the DAO,
+            // so the registrar must be a contract it administers.
+            let info = deps.querier.query_wasm_contract_info(&registrar)?;
+            if info.admin.as_deref() != Some(env.contract.address.as_str()) {
+                return Err(ContractError::UncontrolledCodeIdRegistrar {});
+            }
+            CODE_ID_REGISTRAR.save(deps.storage, &registrar)?;
+            let count = sync_code_ids(deps, &registrar)?;
+            Ok(Response::default()
+                .add_attribute("action", "update_code_id_registrar")
+                .add_attribute("registrar", registrar)
+                .add_attribute("code_ids", count.to_string()))
+        }
+        None => {
+            CODE_ID_REGISTRAR.remove(deps.storage);
+            clear_official_code_ids(deps.storage)?;
+            Ok(Response::default()
+                .add_attribute("action", "update_code_id_registrar")
+                .add_attribute("registrar", "None"))
+        }
+    }
+}
+
+pub fn execute_sync_code_ids(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+) -> Result<Response, ContractError> {
+    let registrar = CODE_ID_REGISTRAR
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoCodeIdRegistrar {})?;
+    if sender != registrar && sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+    let count = sync_code_ids(deps, &registrar)?;
+    Ok(Response::default()
+        .add_attribute("action", "sync_code_ids")
+        .add_attribute("registrar", registrar)
+        .add_attribute("code_ids", count.to_string()))
+}
+
+/// Replaces the official code IDs with those listed by REGISTRAR,
+/// returning the number listed.
+fn sync_code_ids(deps: DepsMut, registrar: &Addr) -> Result<usize, ContractError> {
+    let code_ids: Vec<OfficialCodeId> = deps
+        .querier
+        .query_wasm_smart(registrar, &RegistrarQuery::CodeIds {})?;
+    clear_official_code_ids(deps.storage)?;
+    for official in code_ids.iter() {
+        OFFICIAL_CODE_IDS.save(deps.storage, official.code_id, official)?;
+    }
+    Ok(code_ids.len())
+}
+
+fn clear_official_code_ids(storage: &mut dyn Storage) -> StdResult<()> {
+    let code_ids = OFFICIAL_CODE_IDS
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<u64>>>()?;
+    for code_id in code_ids {
+        OFFICIAL_CODE_IDS.remove(storage, code_id);
+    }
+    Ok(())
+}
+
+/// The code ID that MSG instantiates or migrates a contract to, if
+/// any.
+fn msg_code_id(msg: &CosmosMsg) -> Option<u64> {
+    match msg {
+        CosmosMsg::Wasm(WasmMsg::Instantiate { code_id, .. })
+        | CosmosMsg::Wasm(WasmMsg::Instantiate2 { code_id, .. }) => Some(*code_id),
+        CosmosMsg::Wasm(WasmMsg::Migrate { new_code_id, .. }) => Some(*new_code_id),
+        _ => None,
+    }
+}
+
+/// Errors if a code ID registrar is set and CODE_ID is not one of the
+/// official code IDs.
+fn check_official_code_id(storage: &dyn Storage, code_id: u64) -> Result<(), ContractError> {
+    if CODE_ID_REGISTRAR.may_load(storage)?.is_some() && !OFFICIAL_CODE_IDS.has(storage, code_id) {
+        return Err(ContractError::UnofficialCodeId { code_id });
+    }
+    Ok(())
+}
+
+pub fn execute_set_item(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    key: String,
+    value: String,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    ITEMS.save(deps.storage, key.clone(), &value)?;
+    Ok(Response::default()
+        .add_attribute("action", "execute_set_item")
+        .add_attribute("key", key)
+        .add_attribute("addr", value))
+}
+
+pub fn execute_remove_item(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    key: String,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if ITEMS.has(deps.storage, key.clone()) {
+        ITEMS.remove(deps.storage, key.clone());
+        Ok(Response::default()
+            .add_attribute("action", "execute_remove_item")
+            .add_attribute("key", key))
+    } else {
+        Err(ContractError::KeyMissing {})
+    }
+}
+
+pub fn execute_update_sub_daos_list(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    to_add: Vec<SubDao>,
+    to_remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut removed = vec![];
+    for addr in to_remove {
+        let addr = deps.api.addr_validate(&addr)?;
+        if SUBDAO_LIST.has(deps.storage, &addr) {
+            SUBDAO_LIST.remove(deps.storage, &addr);
+            removed.push(addr);
+        }
+    }
+
+    let mut added = vec![];
+    for subdao in to_add {
+        let addr = deps.api.addr_validate(&subdao.addr)?;
+        SUBDAO_LIST.save(deps.storage, &addr, &subdao.charter)?;
+        added.push(addr);
+    }
+
+    let hooks = if added.is_empty() && removed.is_empty() {
+        vec![]
+    } else {
+        lifecycle_hooks(
+            deps.storage,
+            DaoLifecycleHookMsg::SubDaosUpdated { added, removed },
+        )?
+    };
+
+    Ok(Response::default()
+        .add_submessages(hooks)
+        .add_attribute("action", "execute_update_sub_daos_list")
+        .add_attribute("sender", sender))
+}
+
+pub fn execute_proposal_hook_msg(
+    deps: DepsMut,
+    sender: Addr,
+    msg: ProposalHookMsg,
+) -> Result<Response, ContractError> {
+    if !PROPOSAL_MODULES.has(deps.storage, sender.clone()) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match msg {
+        ProposalHookMsg::NewProposal { id, .. } => {
+            // Proposal IDs are unique within a module, but guard
+            // against indexing a proposal twice all the same.
+            if let Some(global_id) = GLOBAL_PROPOSAL_IDS.may_load(deps.storage, (&sender, id))? {
+                return Ok(Response::default()
+                    .add_attribute("action", "execute_proposal_hook")
+                    .add_attribute("global_id", global_id.to_string()));
+            }
+            let global_id = GLOBAL_PROPOSAL_COUNT
+                .may_load(deps.storage)?
+                .unwrap_or_default()
+                + 1;
+            GLOBAL_PROPOSAL_COUNT.save(deps.storage, &global_id)?;
+            GLOBAL_PROPOSALS.save(deps.storage, global_id, &(sender.clone(), id))?;
+            GLOBAL_PROPOSAL_IDS.save(deps.storage, (&sender, id), &global_id)?;
+
+            Ok(Response::default()
+                .add_attribute("action", "execute_proposal_hook")
+                .add_attribute("module", sender)
+                .add_attribute("id", id.to_string())
+                .add_attribute("global_id", global_id.to_string()))
+        }
+        ProposalHookMsg::ProposalStatusChanged { .. } => {
+            Ok(Response::default().add_attribute("action", "execute_proposal_hook"))
+        }
+    }
+}
+
+pub fn execute_add_lifecycle_hook(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    address: String,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let addr = deps.api.addr_validate(&address)?;
+    LIFECYCLE_HOOKS.add_hook(deps.storage, addr)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_add_lifecycle_hook")
+        .add_attribute("address", address))
+}
+
+pub fn execute_remove_lifecycle_hook(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    address: String,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let addr = deps.api.addr_validate(&address)?;
+    LIFECYCLE_HOOKS.remove_hook(deps.storage, addr)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_remove_lifecycle_hook")
+        .add_attribute("address", address))
+}
+
+fn lifecycle_hooks(storage: &dyn Storage, msg: DaoLifecycleHookMsg) -> StdResult<Vec<SubMsg>> {
+    dao_lifecycle_hooks(
+        LIFECYCLE_HOOKS,
+        storage,
+        msg,
+        FAILED_LIFECYCLE_HOOK_REPLY_ID_OFFSET,
+    )
+}
+
+pub fn execute_receive_cw20(
+    mut deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    receive: cw20::Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    // Anyone may call `Receive`, so only tokens registered before
+    // this transfer are trusted to have sent what they claim.
+    let registered = CW20_LIST.has(deps.storage, sender.clone());
+    let config = CONFIG.load(deps.storage)?;
+    let mut response = if !config.automatically_add_cw20s {
+        Response::new()
+    } else {
+        CW20_LIST.save(deps.storage, sender.clone(), &Empty {})?;
+        Response::new()
+            .add_attribute("action", "receive_cw20")
+            .add_attribute("token", &sender)
+    };
+
+    // Memos are JSON objects with a single key naming their
+    // route. Anything else, or a route without a handler, is treated
+    // as a plain transfer to the treasury.
+    let route = from_json_binary::<BTreeMap<String, IgnoredAny>>(&receive.msg)
+        .ok()
+        .filter(|memo| memo.len() == 1)
+        .and_then(|memo| memo.into_keys().next());
+    let mut forwarded = vec![];
+    if let Some(route) = route {
+        if let Some(handler) = RECEIVE_HANDLERS.may_load(deps.storage, route.clone())? {
+            if !registered {
+                return Err(ContractError::UnregisteredCw20 {
+                    token: sender.into_string(),
+                });
+            }
+            // The tokens are forwarded along with the notification,
+            // so handlers are only ever told of tokens they hold.
+            forwarded.push(CosmosMsg::from(WasmMsg::Execute {
+                contract_addr: sender.to_string(),
+                msg: to_json_binary(&cw20::Cw20ExecuteMsg::Send {
+                    contract: handler.into_string(),
+                    amount: receive.amount,
+                    msg: to_json_binary(&ReceiveHandlerMsg::Cw20Received {
+                        sender: receive.sender,
+                        memo: receive.msg,
+                    })?,
+                })?,
+                funds: vec![],
+            }));
+            response = response
+                .add_messages(forwarded.clone())
+                .add_attribute("route", route);
+        }
+    }
+
+    // A token whose balance can not be queried may still be
+    // received. Its cached balance is left for `RefreshCw20Balances`.
+    if CW20_LIST.has(deps.storage, sender.clone())
+        && cache_cw20_balance(deps.branch(), &env, sender).is_ok()
+    {
+        cache_cw20_spends(deps, &env, &forwarded)?;
+    }
+    Ok(response)
+}
+
+pub fn execute_update_receive_handlers(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    to_add: Vec<ReceiveHandler>,
+    to_remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for route in to_remove {
+        RECEIVE_HANDLERS.remove(deps.storage, route);
+    }
+    for ReceiveHandler { route, address } in to_add {
+        if route.is_empty() {
+            return Err(ContractError::EmptyReceiveRoute {});
+        }
+        let address = deps.api.addr_validate(&address)?;
+        RECEIVE_HANDLERS.save(deps.storage, route, &address)?;
+    }
+
+    Ok(Response::default().add_attribute("action", "update_receive_handlers"))
+}
+
+pub fn execute_update_remote_members(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    to_add: Vec<String>,
+    to_remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    do_update_addr_list(deps, REMOTE_MEMBERS, to_add, to_remove, |_, _| Ok(()))?;
+    Ok(Response::default().add_attribute("action", "update_remote_members"))
+}
+
+pub fn execute_remote_command(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    command: RemoteCommand,
+) -> Result<Response, ContractError> {
+    let response = match command {
+        // The funds are already in the treasury.
+        RemoteCommand::Fund {} => Response::default(),
+        RemoteCommand::DepositToVault { vault } => {
+            if !REMOTE_MEMBERS.has(deps.storage, info.sender.clone()) {
+                return Err(ContractError::Unauthorized {});
+            }
+            let addr = deps.api.addr_validate(&vault)?;
+            let Vault { denom, cap } = VAULTS
+                .may_load(deps.storage, addr.clone())?
+                .ok_or(ContractError::VaultNotRegistered { address: vault })?;
+            // Only the transferred funds may be deposited, so remote
+            // members can not move the treasury's existing funds.
+            let amount: Uint128 = info
+                .funds
+                .iter()
+                .filter(|coin| coin.denom == denom)
+                .map(|coin| coin.amount)
+                .sum();
+            deposit_to_vault(deps, &env, addr, denom, cap, amount)?
+        }
+    };
+    Ok(response
+        .add_attribute("action", "remote_command")
+        .add_attribute("sender", info.sender))
+}
+
+pub fn execute_receive_cw721(deps: DepsMut, sender: Addr) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     if !config.automatically_add_cw721s {
         Ok(Response::new())
@@ -546,37 +2502,136 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::Admin {} => query_admin(deps),
         QueryMsg::AdminNomination {} => query_admin_nomination(deps),
         QueryMsg::Config {} => query_config(deps),
-        QueryMsg::Cw20TokenList { start_after, limit } => query_cw20_list(deps, start_after, limit),
+        QueryMsg::ConfigAtHeight { height } => query_config_at_height(deps, height),
+        QueryMsg::Cw20TokenList {
+            start_after,
+            limit,
+            descending,
+        } => query_cw20_list(deps, start_after, limit, descending),
         QueryMsg::Cw20Balances { start_after, limit } => {
             query_cw20_balances(deps, env, start_after, limit)
         }
-        QueryMsg::Cw721TokenList { start_after, limit } => {
-            query_cw721_list(deps, start_after, limit)
+        QueryMsg::CachedCw20Balances { start_after, limit } => {
+            query_cached_cw20_balances(deps, env, start_after, limit)
         }
+        QueryMsg::Cw721TokenList {
+            start_after,
+            limit,
+            descending,
+        } => query_cw721_list(deps, start_after, limit, descending),
+        QueryMsg::FrozenContracts { start_after, limit } => {
+            query_frozen_contracts(deps, start_after, limit)
+        }
+        QueryMsg::FrozenDenoms { start_after, limit } => {
+            query_frozen_denoms(deps, start_after, limit)
+        }
+        QueryMsg::Vaults { start_after, limit } => query_vaults(deps, start_after, limit),
+        QueryMsg::VaultPosition { vault } => query_vault_position(deps, env, vault),
+        QueryMsg::VaultAllocationLimit {} => to_json_binary(
+            &VAULT_ALLOCATION_LIMIT
+                .may_load(deps.storage)?
+                .unwrap_or_default(),
+        ),
+        QueryMsg::CircuitBreaker {} => query_circuit_breaker(deps, env),
+        QueryMsg::ReceiveHandlers { start_after, limit } => {
+            query_receive_handlers(deps, start_after, limit)
+        }
+        QueryMsg::RemoteMembers { start_after, limit } => {
+            query_remote_members(deps, start_after, limit)
+        }
+        QueryMsg::Merge {} => to_json_binary(&MERGE.may_load(deps.storage)?),
+        QueryMsg::ManagedContracts { start_after, limit } => {
+            query_managed_contracts(deps, start_after, limit)
+        }
+        QueryMsg::PinnedCodes { start_after, limit } => {
+            query_pinned_codes(deps, start_after, limit)
+        }
+        QueryMsg::CodeIdRegistrar {} => to_json_binary(&CODE_ID_REGISTRAR.may_load(deps.storage)?),
+        QueryMsg::OfficialCodeIds { start_after, limit } => {
+            query_official_code_ids(deps, start_after, limit)
+        }
+        QueryMsg::TreasuryTiers {} => query_treasury_tiers(deps, env),
+        QueryMsg::ColdSpends { start_after, limit } => query_cold_spends(deps, start_after, limit),
         QueryMsg::DumpState {} => query_dump_state(deps, env),
         QueryMsg::GetItem { key } => query_get_item(deps, key),
         QueryMsg::Info {} => query_info(deps),
-        QueryMsg::ListItems { start_after, limit } => query_list_items(deps, start_after, limit),
+        QueryMsg::ListItems {
+            start_after,
+            limit,
+            descending,
+        } => query_list_items(deps, start_after, limit, descending),
         QueryMsg::PauseInfo {} => query_paused(deps, env),
-        QueryMsg::ProposalModules { start_after, limit } => {
-            query_proposal_modules(deps, start_after, limit)
-        }
+        QueryMsg::ProposalModules {
+            start_after,
+            limit,
+            descending,
+        } => query_proposal_modules(deps, start_after, limit, descending),
         QueryMsg::ProposalModuleCount {} => query_proposal_module_count(deps),
         QueryMsg::TotalPowerAtHeight { height } => query_total_power_at_height(deps, height),
         QueryMsg::VotingModule {} => query_voting_module(deps),
         QueryMsg::VotingPowerAtHeight { address, height } => {
             query_voting_power_at_height(deps, address, height)
         }
-        QueryMsg::ActiveProposalModules { start_after, limit } => {
-            query_active_proposal_modules(deps, start_after, limit)
-        }
-        QueryMsg::ListSubDaos { start_after, limit } => {
-            query_list_sub_daos(deps, start_after, limit)
+        QueryMsg::ActiveProposalModules {
+            start_after,
+            limit,
+            descending,
+        } => query_active_proposal_modules(deps, start_after, limit, descending),
+        QueryMsg::ProposalModulesAtHeight {
+            height,
+            start_after,
+            limit,
+        } => query_proposal_modules_at_height(deps, height, start_after, limit),
+        QueryMsg::ListSubDaos {
+            start_after,
+            limit,
+            descending,
+        } => query_list_sub_daos(deps, start_after, limit, descending),
+        QueryMsg::ProposalGlobal { module_prefix, id } => {
+            query_proposal_global(deps, module_prefix, id)
         }
+        QueryMsg::ProposalByGlobalId { global_id } => query_proposal_by_global_id(deps, global_id),
+        QueryMsg::LifecycleHooks {} => to_json_binary(&LIFECYCLE_HOOKS.query_hooks(deps)?),
         QueryMsg::DaoURI {} => query_dao_uri(deps),
+        QueryMsg::DryRunMsgs { msgs } => query_dry_run_msgs(deps, env, msgs),
     }
 }
 
+pub fn query_proposal_global(deps: Deps, module_prefix: String, id: u64) -> StdResult<Binary> {
+    let module = PROPOSAL_MODULES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, module)| module))
+        .find(|module| {
+            module
+                .as_ref()
+                .map_or(true, |module| module.prefix == module_prefix)
+        })
+        .transpose()?
+        .ok_or_else(|| StdError::not_found("proposal module"))?;
+    let global_id = GLOBAL_PROPOSAL_IDS
+        .may_load(deps.storage, (&module.address, id))?
+        .ok_or_else(|| StdError::not_found("global proposal"))?;
+    to_json_binary(&GlobalProposalResponse {
+        global_id,
+        module: module.address,
+        module_prefix: module.prefix,
+        id,
+    })
+}
+
+pub fn query_proposal_by_global_id(deps: Deps, global_id: u64) -> StdResult<Binary> {
+    let (module, id) = GLOBAL_PROPOSALS
+        .may_load(deps.storage, global_id)?
+        .ok_or_else(|| StdError::not_found("global proposal"))?;
+    let module = PROPOSAL_MODULES.load(deps.storage, module)?;
+    to_json_binary(&GlobalProposalResponse {
+        global_id,
+        module: module.address,
+        module_prefix: module.prefix,
+        id,
+    })
+}
+
 pub fn query_admin(deps: Deps) -> StdResult<Binary> {
     let admin = ADMIN.load(deps.storage)?;
     to_json_binary(&admin)
@@ -592,6 +2647,10 @@ pub fn query_config(deps: Deps) -> StdResult<Binary> {
     to_json_binary(&config)
 }
 
+pub fn query_config_at_height(deps: Deps, height: u64) -> StdResult<Binary> {
+    to_json_binary(&config_at_height(deps.storage, height)?)
+}
+
 pub fn query_voting_module(deps: Deps) -> StdResult<Binary> {
     let voting_module = VOTING_MODULE.load(deps.storage)?;
     to_json_binary(&voting_module)
@@ -601,6 +2660,7 @@ pub fn query_proposal_modules(
     deps: Deps,
     start_after: Option<String>,
     limit: Option<u32>,
+    descending: Option<bool>,
 ) -> StdResult<Binary> {
     // This query is will run out of gas due to the size of the
     // returned message before it runs out of compute so taking a
@@ -622,7 +2682,7 @@ pub fn query_proposal_modules(
             .map(|s| deps.api.addr_validate(&s))
             .transpose()?,
         limit,
-        cosmwasm_std::Order::Ascending,
+        order(descending),
     )?)
 }
 
@@ -630,6 +2690,7 @@ pub fn query_active_proposal_modules(
     deps: Deps,
     start_after: Option<String>,
     limit: Option<u32>,
+    descending: Option<bool>,
 ) -> StdResult<Binary> {
     // Note: this is not gas efficient as we need to potentially visit all modules in order to
     // filter out the modules with active status.
@@ -640,7 +2701,7 @@ pub fn query_active_proposal_modules(
             .map(|s| deps.api.addr_validate(&s))
             .transpose()?,
         None,
-        cosmwasm_std::Order::Ascending,
+        order(descending),
     )?;
 
     let limit = limit.unwrap_or(values.len() as u32);
@@ -654,6 +2715,31 @@ pub fn query_active_proposal_modules(
     )
 }
 
+pub fn query_proposal_modules_at_height(
+    deps: Deps,
+    height: u64,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let start_after = start_after
+        .map(|s| deps.api.addr_validate(&s))
+        .transpose()?;
+    // Modules are never removed, so every module that existed at
+    // `height` is still a key of `PROPOSAL_MODULES`.
+    let modules = PROPOSAL_MODULES
+        .keys(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .map(|address| proposal_module_at_height(deps.storage, address?, height))
+        .filter_map(|module| module.transpose())
+        .take(clamp_limit(limit))
+        .collect::<StdResult<Vec<ProposalModule>>>()?;
+    to_json_binary(&modules)
+}
+
 fn get_pause_info(deps: Deps, env: Env) -> StdResult<PauseInfoResponse> {
     Ok(match PAUSED.may_load(deps.storage)? {
         Some(expiration) => {
@@ -730,36 +2816,284 @@ pub fn query_list_items(
     deps: Deps,
     start_after: Option<String>,
     limit: Option<u32>,
+    descending: Option<bool>,
 ) -> StdResult<Binary> {
     to_json_binary(&paginate_map(
         deps,
         &ITEMS,
         start_after,
         limit,
-        cosmwasm_std::Order::Descending,
+        order(Some(descending.unwrap_or(true))),
     )?)
 }
 
+pub fn query_dry_run_msgs(deps: Deps, env: Env, msgs: Vec<CosmosMsg<Empty>>) -> StdResult<Binary> {
+    let mut problems = vec![];
+
+    let size = cosmwasm_std::to_vec(&msgs)?.len() as u64;
+    if size > MAX_PROPOSAL_SIZE {
+        problems.push(DryRunProblem {
+            msg_index: None,
+            issue: DryRunIssue::TooLarge {
+                size,
+                max: MAX_PROPOSAL_SIZE,
+            },
+        });
+    }
+
+    // Native denoms and registered cw20 addresses mapped to the
+    // amount spent by the messages so far and the DAO's balance.
+    let mut spends = BTreeMap::new();
+    let tripped = CIRCUIT_BREAKER_TRIP.may_load(deps.storage)?.is_some();
+    for (index, msg) in msgs.iter().enumerate() {
+        let mut issues = vec![];
+        if let Some(target) = frozen_target(deps.storage, &env, msg)? {
+            issues.push(DryRunIssue::Frozen { target });
+        }
+        if tripped && is_treasury_msg(&env, msg) {
+            issues.push(DryRunIssue::CircuitBreakerTripped {});
+        }
+        if let Some(code_id) = msg_code_id(msg) {
+            if let Err(ContractError::UnofficialCodeId { code_id }) =
+                check_official_code_id(deps.storage, code_id)
+            {
+                issues.push(DryRunIssue::UnofficialCodeId { code_id });
+            }
+        }
+        match msg {
+            CosmosMsg::Bank(BankMsg::Send { to_address, amount }) => {
+                dry_run_address(deps, to_address, &mut issues);
+                dry_run_native_spend(deps, &env, amount, &mut spends, &mut issues)?;
+            }
+            CosmosMsg::Bank(BankMsg::Burn { amount }) => {
+                dry_run_native_spend(deps, &env, amount, &mut spends, &mut issues)?;
+            }
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr,
+                msg,
+                funds,
+            }) => {
+                dry_run_native_spend(deps, &env, funds, &mut spends, &mut issues)?;
+                if let Some(contract) = dry_run_contract(deps, contract_addr, &mut issues) {
+                    dry_run_cw20_spend(deps, &env, contract, msg, &mut spends, &mut issues)?;
+                }
+            }
+            CosmosMsg::Wasm(WasmMsg::Instantiate { admin, funds, .. })
+            | CosmosMsg::Wasm(WasmMsg::Instantiate2 { admin, funds, .. }) => {
+                if let Some(admin) = admin {
+                    dry_run_address(deps, admin, &mut issues);
+                }
+                dry_run_native_spend(deps, &env, funds, &mut spends, &mut issues)?;
+            }
+            CosmosMsg::Ibc(IbcMsg::Transfer { amount, .. })
+            | CosmosMsg::Staking(StakingMsg::Delegate { amount, .. }) => {
+                let amount = std::slice::from_ref(amount);
+                dry_run_native_spend(deps, &env, amount, &mut spends, &mut issues)?;
+            }
+            CosmosMsg::Wasm(WasmMsg::Migrate { contract_addr, .. }) => {
+                dry_run_contract(deps, contract_addr, &mut issues);
+            }
+            CosmosMsg::Wasm(WasmMsg::ClearAdmin { contract_addr }) => {
+                dry_run_contract(deps, contract_addr, &mut issues);
+            }
+            CosmosMsg::Wasm(WasmMsg::UpdateAdmin {
+                contract_addr,
+                admin,
+            }) => {
+                dry_run_contract(deps, contract_addr, &mut issues);
+                dry_run_address(deps, admin, &mut issues);
+            }
+            _ => (),
+        }
+        problems.extend(issues.into_iter().map(|issue| DryRunProblem {
+            msg_index: Some(index as u32),
+            issue,
+        }));
+    }
+
+    to_json_binary(&DryRunResponse { problems })
+}
+
+/// Checks that DENOM has the format the Cosmos SDK requires of coin
+/// denominations: `[a-zA-Z][a-zA-Z0-9/:._-]{2,127}`.
+fn is_valid_denom(denom: &str) -> bool {
+    let mut chars = denom.chars();
+    (3..=128).contains(&denom.len())
+        && chars.next().map_or(false, |c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || "/:._-".contains(c))
+}
+
+fn dry_run_address(deps: Deps, address: &str, issues: &mut Vec<DryRunIssue>) -> Option<Addr> {
+    match deps.api.addr_validate(address) {
+        Ok(addr) => Some(addr),
+        Err(_) => {
+            issues.push(DryRunIssue::InvalidAddress {
+                address: address.to_string(),
+            });
+            None
+        }
+    }
+}
+
+fn dry_run_contract(deps: Deps, address: &str, issues: &mut Vec<DryRunIssue>) -> Option<Addr> {
+    let addr = dry_run_address(deps, address, issues)?;
+    match deps.querier.query_wasm_contract_info(&addr) {
+        Ok(_) => Some(addr),
+        Err(_) => {
+            issues.push(DryRunIssue::NotAContract {
+                address: address.to_string(),
+            });
+            None
+        }
+    }
+}
+
+/// Adds AMOUNT to the amount of DENOM spent so far, and records an
+/// issue if this exceeds the DAO's balance. BALANCE is only queried
+/// the first time DENOM is spent.
+fn dry_run_spend(
+    spends: &mut BTreeMap<String, (Uint128, Uint128)>,
+    denom: String,
+    amount: Uint128,
+    balance: impl FnOnce() -> StdResult<Uint128>,
+    issues: &mut Vec<DryRunIssue>,
+) -> StdResult<()> {
+    let (spent, available) = match spends.get(&denom) {
+        Some(spend) => *spend,
+        None => (Uint128::zero(), balance()?),
+    };
+    let required = spent.saturating_add(amount);
+    if required > available {
+        issues.push(DryRunIssue::InsufficientBalance {
+            denom: denom.clone(),
+            required,
+            available,
+        });
+    }
+    spends.insert(denom, (required, available));
+    Ok(())
+}
+
+fn dry_run_native_spend(
+    deps: Deps,
+    env: &Env,
+    coins: &[Coin],
+    spends: &mut BTreeMap<String, (Uint128, Uint128)>,
+    issues: &mut Vec<DryRunIssue>,
+) -> StdResult<()> {
+    for coin in coins {
+        if !is_valid_denom(&coin.denom) {
+            issues.push(DryRunIssue::InvalidDenom {
+                denom: coin.denom.clone(),
+            });
+        } else if coin.amount.is_zero() {
+            issues.push(DryRunIssue::ZeroAmount {
+                denom: coin.denom.clone(),
+            });
+        } else {
+            dry_run_spend(
+                spends,
+                coin.denom.clone(),
+                coin.amount,
+                || {
+                    Ok(deps
+                        .querier
+                        .query_balance(&env.contract.address, &coin.denom)?
+                        .amount)
+                },
+                issues,
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Checks MSG against the DAO's balance of TOKEN if MSG is a cw20
+/// transfer, send, or burn. Other messages are ignored.
+fn dry_run_cw20_spend(
+    deps: Deps,
+    env: &Env,
+    token: Addr,
+    msg: &Binary,
+    spends: &mut BTreeMap<String, (Uint128, Uint128)>,
+    issues: &mut Vec<DryRunIssue>,
+) -> StdResult<()> {
+    let amount = match from_json_binary(msg) {
+        Ok(cw20::Cw20ExecuteMsg::Transfer { recipient, amount }) => {
+            dry_run_address(deps, &recipient, issues);
+            amount
+        }
+        Ok(cw20::Cw20ExecuteMsg::Send {
+            contract, amount, ..
+        }) => {
+            dry_run_address(deps, &contract, issues);
+            amount
+        }
+        Ok(cw20::Cw20ExecuteMsg::Burn { amount }) => amount,
+        _ => return Ok(()),
+    };
+    if amount.is_zero() {
+        issues.push(DryRunIssue::ZeroAmount {
+            denom: token.into_string(),
+        });
+        return Ok(());
+    }
+    if !CW20_LIST.has(deps.storage, token.clone()) {
+        issues.push(DryRunIssue::UnregisteredCw20 {
+            address: token.into_string(),
+        });
+        return Ok(());
+    }
+    dry_run_spend(
+        spends,
+        token.to_string(),
+        amount,
+        || {
+            let balance: cw20::BalanceResponse = deps.querier.query_wasm_smart(
+                &token,
+                &cw20::Cw20QueryMsg::Balance {
+                    address: env.contract.address.to_string(),
+                },
+            )?;
+            Ok(balance.balance)
+        },
+        issues,
+    )
+}
+
 pub fn query_cw20_list(
     deps: Deps,
     start_after: Option<String>,
     limit: Option<u32>,
+    descending: Option<bool>,
 ) -> StdResult<Binary> {
-    to_json_binary(&paginate_map_keys(
+    to_json_binary(&list_cw20s(deps, start_after, limit, descending)?)
+}
+
+/// Lists the registered cw20 tokens, in descending order unless
+/// DESCENDING is set to false.
+fn list_cw20s(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+    descending: Option<bool>,
+) -> StdResult<Vec<Addr>> {
+    paginate_map_keys(
         deps,
         &CW20_LIST,
         start_after
             .map(|s| deps.api.addr_validate(&s))
             .transpose()?,
         limit,
-        cosmwasm_std::Order::Descending,
-    )?)
+        order(Some(descending.unwrap_or(true))),
+    )
 }
 
 pub fn query_cw721_list(
     deps: Deps,
     start_after: Option<String>,
     limit: Option<u32>,
+    descending: Option<bool>,
 ) -> StdResult<Binary> {
     to_json_binary(&paginate_map_keys(
         deps,
@@ -768,10 +3102,270 @@ pub fn query_cw721_list(
             .map(|s| deps.api.addr_validate(&s))
             .transpose()?,
         limit,
-        cosmwasm_std::Order::Descending,
+        order(Some(descending.unwrap_or(true))),
+    )?)
+}
+
+pub fn query_frozen_contracts(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    to_json_binary(&paginate_map_keys(
+        deps,
+        &FROZEN_CONTRACTS,
+        start_after
+            .map(|s| deps.api.addr_validate(&s))
+            .transpose()?,
+        limit,
+        cosmwasm_std::Order::Ascending,
+    )?)
+}
+
+pub fn query_frozen_denoms(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    to_json_binary(&paginate_map_keys(
+        deps,
+        &FROZEN_DENOMS,
+        start_after,
+        limit,
+        cosmwasm_std::Order::Ascending,
+    )?)
+}
+
+pub fn query_vaults(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let vaults = paginate_map(
+        deps,
+        &VAULTS,
+        start_after
+            .map(|s| deps.api.addr_validate(&s))
+            .transpose()?,
+        limit,
+        cosmwasm_std::Order::Ascending,
+    )?;
+    let vaults = vaults
+        .into_iter()
+        .map(|(vault, Vault { denom, cap })| {
+            Ok(VaultResponse {
+                deposited: VAULT_DEPOSITS
+                    .may_load(deps.storage, vault.clone())?
+                    .unwrap_or_default(),
+                vault,
+                denom,
+                cap,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    to_json_binary(&vaults)
+}
+
+pub fn query_vault_position(deps: Deps, env: Env, vault: String) -> StdResult<Binary> {
+    let vault = deps.api.addr_validate(&vault)?;
+    let Vault { denom, .. } = VAULTS.load(deps.storage, vault.clone())?;
+    let deposited = VAULT_DEPOSITS
+        .may_load(deps.storage, vault.clone())?
+        .unwrap_or_default();
+    let position: PositionResponse = deps.querier.query_wasm_smart(
+        &vault,
+        &VaultQueryMsg::Position {
+            depositor: env.contract.address.into_string(),
+        },
+    )?;
+    to_json_binary(&VaultPositionResponse {
+        vault,
+        denom,
+        deposited,
+        value: position.amount,
+    })
+}
+
+pub fn query_circuit_breaker(deps: Deps, env: Env) -> StdResult<Binary> {
+    let outflows = OUTFLOWS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (denom, window) = item?;
+            let amount = window
+                .iter()
+                .filter(|(expiration, _)| !expiration.is_expired(&env.block))
+                .fold(Uint128::zero(), |total, (_, amount)| total + amount);
+            Ok(Coin { denom, amount })
+        })
+        .filter(|coin| coin.as_ref().map_or(true, |coin| !coin.amount.is_zero()))
+        .collect::<StdResult<Vec<Coin>>>()?;
+    let cw20_outflows = CW20_OUTFLOWS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (token, window) = item?;
+            let amount = window
+                .iter()
+                .filter(|(expiration, _)| !expiration.is_expired(&env.block))
+                .fold(Uint128::zero(), |total, (_, amount)| total + amount);
+            Ok(Cw20Coin {
+                address: token.into_string(),
+                amount,
+            })
+        })
+        .filter(|coin| coin.as_ref().map_or(true, |coin| !coin.amount.is_zero()))
+        .collect::<StdResult<Vec<Cw20Coin>>>()?;
+    to_json_binary(&CircuitBreakerResponse {
+        circuit_breaker: CIRCUIT_BREAKER.may_load(deps.storage)?,
+        tripped: CIRCUIT_BREAKER_TRIP.may_load(deps.storage)?,
+        outflows,
+        cw20_outflows,
+    })
+}
+
+pub fn query_managed_contracts(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let contracts = paginate_map(
+        deps,
+        &MANAGED_CONTRACTS,
+        start_after
+            .map(|s| deps.api.addr_validate(&s))
+            .transpose()?,
+        limit,
+        cosmwasm_std::Order::Ascending,
+    )?;
+    let contracts = contracts
+        .into_iter()
+        .map(
+            |(address, ManagedContract { code_id, label })| ManagedContractResponse {
+                address,
+                code_id,
+                label,
+            },
+        )
+        .collect::<Vec<_>>();
+    to_json_binary(&contracts)
+}
+
+pub fn query_receive_handlers(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let handlers = paginate_map(
+        deps,
+        &RECEIVE_HANDLERS,
+        start_after,
+        limit,
+        cosmwasm_std::Order::Ascending,
+    )?;
+    let handlers = handlers
+        .into_iter()
+        .map(|(route, address)| ReceiveHandler {
+            route,
+            address: address.into_string(),
+        })
+        .collect::<Vec<_>>();
+    to_json_binary(&handlers)
+}
+
+pub fn query_remote_members(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    to_json_binary(&paginate_map_keys(
+        deps,
+        &REMOTE_MEMBERS,
+        start_after
+            .map(|s| deps.api.addr_validate(&s))
+            .transpose()?,
+        Some(clamp_limit(limit) as u32),
+        cosmwasm_std::Order::Ascending,
+    )?)
+}
+
+pub fn query_pinned_codes(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    to_json_binary(&paginate_map_keys(
+        deps,
+        &PINNED_CODES,
+        start_after,
+        limit,
+        cosmwasm_std::Order::Ascending,
+    )?)
+}
+
+pub fn query_official_code_ids(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    to_json_binary(&paginate_map_values(
+        deps,
+        &OFFICIAL_CODE_IDS,
+        start_after,
+        limit,
+        cosmwasm_std::Order::Ascending,
     )?)
 }
 
+pub fn query_treasury_tiers(deps: Deps, env: Env) -> StdResult<Binary> {
+    let period_ends = HOT_PERIOD_END
+        .may_load(deps.storage)?
+        .filter(|end| !end.is_expired(&env.block));
+    let (hot_spent, hot_cw20_spent) = if period_ends.is_some() {
+        (
+            HOT_SPENT
+                .range(deps.storage, None, None, Order::Ascending)
+                .map(|item| item.map(|(denom, amount)| Coin { denom, amount }))
+                .collect::<StdResult<Vec<Coin>>>()?,
+            HOT_CW20_SPENT
+                .range(deps.storage, None, None, Order::Ascending)
+                .map(|item| {
+                    item.map(|(address, amount)| Cw20Coin {
+                        address: address.into_string(),
+                        amount,
+                    })
+                })
+                .collect::<StdResult<Vec<Cw20Coin>>>()?,
+        )
+    } else {
+        (vec![], vec![])
+    };
+    to_json_binary(&TreasuryTiersResponse {
+        tiers: TREASURY_TIERS.may_load(deps.storage)?,
+        hot_spent,
+        hot_cw20_spent,
+        period_ends,
+    })
+}
+
+pub fn query_cold_spends(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let spends = paginate_map(
+        deps,
+        &COLD_SPENDS,
+        start_after,
+        limit,
+        cosmwasm_std::Order::Ascending,
+    )?;
+    to_json_binary(
+        &spends
+            .into_iter()
+            .map(|(id, spend)| ColdSpendResponse { id, spend })
+            .collect::<Vec<_>>(),
+    )
+}
+
 pub fn query_cw20_balances(
     deps: Deps,
     env: Env,
@@ -805,10 +3399,53 @@ pub fn query_cw20_balances(
     to_json_binary(&balances)
 }
 
+pub fn query_cached_cw20_balances(
+    deps: Deps,
+    env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let addrs = paginate_map_keys(
+        deps,
+        &CW20_LIST,
+        start_after
+            .map(|a| deps.api.addr_validate(&a))
+            .transpose()?,
+        limit,
+        cosmwasm_std::Order::Descending,
+    )?;
+    let balances = addrs
+        .into_iter()
+        .map(|addr| {
+            // Tokens registered before the cache existed have no
+            // entry until they are received, spent, or refreshed.
+            let (balance, height) = match CW20_BALANCES.may_load(deps.storage, addr.clone())? {
+                Some(cached) => cached,
+                None => {
+                    let balance: cw20::BalanceResponse = deps.querier.query_wasm_smart(
+                        addr.clone(),
+                        &cw20::Cw20QueryMsg::Balance {
+                            address: env.contract.address.to_string(),
+                        },
+                    )?;
+                    (balance.balance, env.block.height)
+                }
+            };
+            Ok(CachedCw20BalanceResponse {
+                addr,
+                balance,
+                height,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    to_json_binary(&balances)
+}
+
 pub fn query_list_sub_daos(
     deps: Deps,
     start_after: Option<String>,
     limit: Option<u32>,
+    descending: Option<bool>,
 ) -> StdResult<Binary> {
     let start_at = start_after
         .map(|addr| deps.api.addr_validate(&addr))
@@ -819,7 +3456,7 @@ pub fn query_list_sub_daos(
         &SUBDAO_LIST,
         start_at.as_ref(),
         limit,
-        cosmwasm_std::Order::Ascending,
+        order(descending),
     )?;
 
     let subdaos: Vec<SubDao> = subdaos
@@ -883,14 +3520,15 @@ pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, Con
                         status: ProposalModuleStatus::Enabled {},
                         prefix,
                     };
-                    PROPOSAL_MODULES.save(deps.storage, address, proposal_module)?;
+                    save_proposal_module(deps.storage, env.block.height, proposal_module)?;
                     Ok(())
                 })?;
 
             // Update config to have the V2 "dao_uri" field.
             let v1_config = v1::state::CONFIG.load(deps.storage)?;
-            CONFIG.save(
+            save_config(
                 deps.storage,
+                env.block.height,
                 &Config {
                     name: v1_config.name,
                     description: v1_config.description,
@@ -924,12 +3562,46 @@ pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, Con
 
             Ok(response)
         }
-        MigrateMsg::FromCompatible {} => Ok(Response::default()),
+        MigrateMsg::FromCompatible {} => {
+            // Record the current config so that it may be rolled back
+            // to if the DAO predates config history.
+            if CONFIG_HISTORY
+                .keys(deps.storage, None, None, Order::Descending)
+                .next()
+                .is_none()
+            {
+                let config = CONFIG.load(deps.storage)?;
+                CONFIG_HISTORY.save(deps.storage, env.block.height, &config)?;
+            }
+            // Likewise, record proposal modules that predate proposal
+            // module history.
+            let unrecorded = PROPOSAL_MODULES
+                .range(deps.storage, None, None, Order::Ascending)
+                .map(|entry| entry.map(|(_, module)| module))
+                .collect::<StdResult<Vec<ProposalModule>>>()?
+                .into_iter()
+                .filter(|module| {
+                    PROPOSAL_MODULE_HISTORY
+                        .prefix(module.address.clone())
+                        .keys(deps.storage, None, None, Order::Ascending)
+                        .next()
+                        .is_none()
+                })
+                .collect::<Vec<_>>();
+            for module in unrecorded {
+                PROPOSAL_MODULE_HISTORY.save(
+                    deps.storage,
+                    (module.address.clone(), env.block.height),
+                    &module,
+                )?;
+            }
+            Ok(Response::default())
+        }
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
     match msg.id {
         PROPOSAL_MODULE_REPLY_ID => {
             let res = parse_reply_instantiate_data(msg)?;
@@ -943,7 +3615,7 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
                 prefix,
             };
 
-            PROPOSAL_MODULES.save(deps.storage, prop_module_addr, &prop_module)?;
+            save_proposal_module(deps.storage, env.block.height, &prop_module)?;
 
             // Save active and total proposal module counts.
             ACTIVE_PROPOSAL_MODULE_COUNT
@@ -996,6 +3668,21 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
 
             Ok(Response::default().add_attribute("voting_module", vote_module_addr))
         }
+        id if (MANAGED_CONTRACT_REPLY_ID_OFFSET..FAILED_LIFECYCLE_HOOK_REPLY_ID_OFFSET)
+            .contains(&id) =>
+        {
+            let managed = PENDING_MANAGED_CONTRACTS.load(deps.storage, id)?;
+            PENDING_MANAGED_CONTRACTS.remove(deps.storage, id);
+            let res = parse_reply_instantiate_data(msg)?;
+            let addr = deps.api.addr_validate(&res.contract_address)?;
+            MANAGED_CONTRACTS.save(deps.storage, addr.clone(), &managed)?;
+            Ok(Response::default().add_attribute("managed_contract", addr))
+        }
+        id if id >= FAILED_LIFECYCLE_HOOK_REPLY_ID_OFFSET => {
+            let index = id - FAILED_LIFECYCLE_HOOK_REPLY_ID_OFFSET;
+            let addr = LIFECYCLE_HOOKS.remove_hook_by_index(deps.storage, index)?;
+            Ok(Response::default().add_attribute("removed_lifecycle_hook", addr))
+        }
         _ => Err(ContractError::UnknownReplyID {}),
     }
 }
@@ -1,5 +1,6 @@
-use cosmwasm_std::{Addr, StdError};
-use cw_utils::ParseReplyError;
+use cosmwasm_std::{Addr, Decimal, StdError, Uint128};
+use cw_hooks::HookError;
+use cw_utils::{Expiration, ParseReplyError};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -10,6 +11,9 @@ pub enum ContractError {
     #[error(transparent)]
     ParseReplyError(#[from] ParseReplyError),
 
+    #[error(transparent)]
+    HookError(#[from] HookError),
+
     #[error("Unauthorized.")]
     Unauthorized {},
 
@@ -56,4 +60,100 @@ pub enum ContractError {
 
     #[error("Can not migrate. Current version is up to date.")]
     AlreadyMigrated {},
+
+    #[error("No config was recorded at or before height ({height}).")]
+    NoConfigAtHeight { height: u64 },
+
+    #[error("Invalid denom ({denom}).")]
+    InvalidDenom { denom: String },
+
+    #[error("Proposal message ({index}) targets frozen ({target}).")]
+    Frozen { index: usize, target: String },
+
+    #[error("Vault ({address}) is not registered.")]
+    VaultNotRegistered { address: String },
+
+    #[error("Vault ({address}) must be emptied before it is removed or its denom changed.")]
+    VaultNotEmpty { address: String },
+
+    #[error("Vault deposit and withdrawal amounts must be non-zero.")]
+    ZeroVaultAmount {},
+
+    #[error("Deposit would exceed the vault's cap of ({cap}).")]
+    VaultCapExceeded { cap: Uint128 },
+
+    #[error(
+        "Deposit would exceed the vault allocation limit of ({limit}) of the DAO's ({denom})."
+    )]
+    VaultAllocationExceeded { limit: Decimal, denom: String },
+
+    #[error("Vault allocation limit must be between zero and one.")]
+    InvalidVaultAllocationLimit {},
+
+    #[error(
+        "The circuit breaker has tripped. Proposals may not move treasury funds until it is reset."
+    )]
+    CircuitBreakerTripped {},
+
+    #[error("Circuit breaker limits must be non-zero and list each denom once.")]
+    InvalidCircuitBreaker {},
+
+    #[error("No merge has been proposed.")]
+    NoMerge {},
+
+    #[error("A merge with ({counterparty}) is already in progress.")]
+    MergeInProgress { counterparty: String },
+
+    #[error("A DAO may not merge with itself.")]
+    SelfMerge {},
+
+    #[error("The counterparty has not proposed the mirrored merge.")]
+    MergeNotMirrored {},
+
+    #[error("The merge has begun and may no longer be cancelled.")]
+    MergeStarted {},
+
+    #[error("The merge has not begun. Cancel it instead.")]
+    MergeNotStarted {},
+
+    #[error("The merge is complete.")]
+    MergeComplete {},
+
+    #[error("Receive handler routes may not be empty.")]
+    EmptyReceiveRoute {},
+
+    #[error("Cw20 ({token}) must be registered before it may be sent to a receive handler.")]
+    UnregisteredCw20 { token: String },
+
+    #[error("Code ID ({code_id}) is not an official code ID.")]
+    UnofficialCodeId { code_id: u64 },
+
+    #[error("No code ID registrar is set.")]
+    NoCodeIdRegistrar {},
+
+    #[error("The code ID registrar's admin must be the DAO.")]
+    UncontrolledCodeIdRegistrar {},
+
+    #[error(
+        "Hot budget limits must be non-zero and list each denom once, and the cold module must be a proposal module."
+    )]
+    InvalidTreasuryTiers {},
+
+    #[error("No treasury tiers are set.")]
+    NoTreasuryTiers {},
+
+    #[error("Proposal would exceed the hot budget for ({denom}). Request a cold spend instead.")]
+    HotBudgetExceeded { denom: String },
+
+    #[error("Message may only be executed through the cold tier.")]
+    ColdSpendRequired {},
+
+    #[error("No cold spend with ID ({id}).")]
+    NoSuchColdSpend { id: u64 },
+
+    #[error("Cold spend ({id}) may not be confirmed until ({confirmable_at}).")]
+    ColdSpendNotReady { id: u64, confirmable_at: Expiration },
+
+    #[error("Proposal message ({index}) may move funds in a way that can not be metered.")]
+    UnmeteredOutflow { index: usize },
 }
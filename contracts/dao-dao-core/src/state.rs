@@ -1,7 +1,15 @@
-use cosmwasm_std::{Addr, Empty};
+use cosmwasm_std::{Addr, Decimal, Empty, Uint128};
+use cw_hooks::Hooks;
 use cw_storage_plus::{Item, Map};
 use cw_utils::Expiration;
-use dao_interface::state::{Config, ProposalModule};
+use dao_interface::{
+    registry::OfficialCodeId,
+    state::{
+        CircuitBreaker, CircuitBreakerTrip, ColdSpend, Config, ManagedContract, Merge,
+        ProposalModule, TreasuryTiers,
+    },
+    vault::Vault,
+};
 
 /// The admin of the contract. Typically a DAO. The contract admin may
 /// unilaterally execute messages on this contract.
@@ -23,6 +31,10 @@ pub const NOMINATED_ADMIN: Item<Addr> = Item::new("nominated_admin");
 /// The current configuration of the module.
 pub const CONFIG: Item<Config> = Item::new("config_v2");
 
+/// The module's configuration keyed by the height at which it was
+/// set. Used to roll back the config to a previous height.
+pub const CONFIG_HISTORY: Map<u64, Config> = Map::new("config_history");
+
 /// The time the DAO will unpause. Here be dragons: this is not set if
 /// the DAO has never been paused.
 pub const PAUSED: Item<Expiration> = Item::new("paused");
@@ -35,6 +47,12 @@ pub const VOTING_MODULE: Item<Addr> = Item::new("voting_module");
 /// to create a new namespace for the changed state.
 pub const PROPOSAL_MODULES: Map<Addr, ProposalModule> = Map::new("proposal_modules_v2");
 
+/// Each proposal module keyed by the heights at which it was added or
+/// its status changed. Used to look up the proposal modules as of a
+/// past height.
+pub const PROPOSAL_MODULE_HISTORY: Map<(Addr, u64), ProposalModule> =
+    Map::new("proposal_module_history");
+
 /// The count of active proposal modules associated with this contract.
 pub const ACTIVE_PROPOSAL_MODULE_COUNT: Item<u32> = Item::new("active_proposal_module_count");
 
@@ -47,9 +65,100 @@ pub const ITEMS: Map<String, String> = Map::new("items");
 /// Set of cw20 tokens that have been registered with this contract's
 /// treasury.
 pub const CW20_LIST: Map<Addr, Empty> = Map::new("cw20s");
+/// The balance of each registered cw20 token, and the height it was
+/// last updated at. Updated when tokens are received or spent by the
+/// DAO, and by `RefreshCw20Balances`.
+pub const CW20_BALANCES: Map<Addr, (Uint128, u64)> = Map::new("cw20_balances");
 /// Set of cw721 tokens that have been registered with this contract's
 /// treasury.
 pub const CW721_LIST: Map<Addr, Empty> = Map::new("cw721s");
 
+/// Set of contracts that proposals may not execute, migrate, or
+/// change the admin of.
+pub const FROZEN_CONTRACTS: Map<Addr, Empty> = Map::new("frozen_contracts");
+/// Set of native denoms that proposals may not send, burn, or attach
+/// as funds.
+pub const FROZEN_DENOMS: Map<String, Empty> = Map::new("frozen_denoms");
+
+/// Set of addresses that may send remote commands other than `Fund`.
+pub const REMOTE_MEMBERS: Map<Addr, Empty> = Map::new("remote_members");
+
+/// Yield vaults that treasury funds may be deposited into.
+pub const VAULTS: Map<Addr, Vault> = Map::new("vaults");
+/// The amount deposited in each vault, less what has been withdrawn.
+pub const VAULT_DEPOSITS: Map<Addr, Uint128> = Map::new("vault_deposits");
+/// The largest share of the DAO's holdings of a denom that may be
+/// deposited in vaults. No deposits may be made until this is set.
+pub const VAULT_ALLOCATION_LIMIT: Item<Decimal> = Item::new("vault_allocation_limit");
+
+/// Bounds on treasury outflows and proposal executions, if set.
+pub const CIRCUIT_BREAKER: Item<CircuitBreaker> = Item::new("circuit_breaker");
+/// Set when the circuit breaker trips and removed when the DAO resets
+/// it. Proposals may not move treasury funds while this is set.
+pub const CIRCUIT_BREAKER_TRIP: Item<CircuitBreakerTrip> = Item::new("circuit_breaker_trip");
+/// Native tokens that have left the treasury by proposal, by denom,
+/// with the expiration at which each outflow leaves the circuit
+/// breaker's window. Only denoms with an outflow limit are recorded.
+pub const OUTFLOWS: Map<String, Vec<(Expiration, Uint128)>> = Map::new("outflows");
+/// As `OUTFLOWS`, for cw20 tokens by token contract.
+pub const CW20_OUTFLOWS: Map<Addr, Vec<(Expiration, Uint128)>> = Map::new("cw20_outflows");
+/// The most recent height at which a proposal was executed and the
+/// number of proposals executed at that height.
+pub const BLOCK_EXECUTIONS: Item<(u64, u32)> = Item::new("block_executions");
+
+/// The split of the native treasury into hot and cold tiers, if set.
+pub const TREASURY_TIERS: Item<TreasuryTiers> = Item::new("treasury_tiers");
+/// Native tokens spent from the hot budget in the current period, by
+/// denom.
+pub const HOT_SPENT: Map<String, Uint128> = Map::new("hot_spent");
+/// As `HOT_SPENT`, for cw20 tokens by token contract.
+pub const HOT_CW20_SPENT: Map<Addr, Uint128> = Map::new("hot_cw20_spent");
+/// When the current hot budget period ends. Not set until a proposal
+/// first spends from the hot budget.
+pub const HOT_PERIOD_END: Item<Expiration> = Item::new("hot_period_end");
+/// Cold spends awaiting confirmation, by ID.
+pub const COLD_SPENDS: Map<u64, ColdSpend> = Map::new("cold_spends");
+/// The number of cold spends that have been requested.
+pub const COLD_SPEND_COUNT: Item<u64> = Item::new("cold_spend_count");
+
+/// Contracts the DAO administers.
+pub const MANAGED_CONTRACTS: Map<Addr, ManagedContract> = Map::new("managed_contracts");
+
+/// Contracts notified of cw20 tokens received with a memo, by the
+/// memo's route.
+pub const RECEIVE_HANDLERS: Map<String, Addr> = Map::new("receive_handlers");
+
+/// Instantiations awaiting a reply before they are added to
+/// `MANAGED_CONTRACTS`, keyed by reply ID.
+pub const PENDING_MANAGED_CONTRACTS: Map<u64, ManagedContract> =
+    Map::new("pending_managed_contracts");
+
+/// The DAO's merge with another DAO, if one has been proposed.
+pub const MERGE: Item<Merge> = Item::new("merge");
+
+/// Set of code IDs that the DAO would like pinned in the chain's wasm
+/// cache.
+pub const PINNED_CODES: Map<u64, Empty> = Map::new("pinned_codes");
+
+/// The registrar that official code IDs are synced from. While set,
+/// modules may only be installed and contracts migrated by proposal
+/// with code IDs in `OFFICIAL_CODE_IDS`.
+pub const CODE_ID_REGISTRAR: Item<Addr> = Item::new("code_id_registrar");
+/// The official code IDs as of the last sync with the registrar.
+pub const OFFICIAL_CODE_IDS: Map<u64, OfficialCodeId> = Map::new("official_code_ids");
+
 /// List of SubDAOs associated to this DAO. Each SubDAO has an optional charter.
 pub const SUBDAO_LIST: Map<&Addr, Option<String>> = Map::new("sub_daos");
+
+/// The number of proposals that have been assigned a global index.
+pub const GLOBAL_PROPOSAL_COUNT: Item<u64> = Item::new("global_proposal_count");
+/// Maps a proposal's global index to its proposal module and ID in
+/// that module.
+pub const GLOBAL_PROPOSALS: Map<u64, (Addr, u64)> = Map::new("global_proposals");
+/// Maps a proposal module and proposal ID to the proposal's global
+/// index.
+pub const GLOBAL_PROPOSAL_IDS: Map<(&Addr, u64), u64> = Map::new("global_proposal_ids");
+
+/// Consumers of DAO lifecycle hooks, which are fired when SubDAOs are
+/// added or removed and when the admin changes.
+pub const LIFECYCLE_HOOKS: Hooks = Hooks::new("lifecycle_hooks");
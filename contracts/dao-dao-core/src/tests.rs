@@ -1,26 +1,42 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{
-    from_slice,
-    testing::{mock_dependencies, mock_env},
-    to_json_binary, Addr, CosmosMsg, Empty, Storage, Uint128, WasmMsg,
+    coin, coins, from_json_binary, from_slice,
+    testing::{mock_dependencies, mock_dependencies_with_balance, mock_env, mock_info},
+    to_json_binary, to_json_string, Addr, BankMsg, Binary, ContractInfoResponse, ContractResult,
+    CosmosMsg, Decimal, Empty, IbcMsg, IbcTimeout, Response, StakingMsg, StdError, StdResult,
+    Storage, SubMsg, SystemResult, Uint128, WasmMsg, WasmQuery,
 };
 use cw2::{set_contract_version, ContractVersion};
-use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+use cw_hooks::HooksResponse;
+use cw_multi_test::{next_block, App, AppResponse, BankSudo, Contract, ContractWrapper, Executor};
 use cw_storage_plus::{Item, Map};
 use cw_utils::{Duration, Expiration};
+use dao_hooks::lifecycle::{DaoLifecycleHookExecuteMsg, DaoLifecycleHookMsg};
 use dao_interface::{
-    msg::{ExecuteMsg, InitialItem, InstantiateMsg, MigrateMsg, QueryMsg},
+    msg::{ExecuteMsg, InitialItem, InstantiateMsg, MigrateMsg, ProposalHookMsg, QueryMsg},
     query::{
-        AdminNominationResponse, Cw20BalanceResponse, DaoURIResponse, DumpStateResponse,
-        GetItemResponse, PauseInfoResponse, ProposalModuleCountResponse, SubDao,
+        AdminNominationResponse, CachedCw20BalanceResponse, CircuitBreakerResponse,
+        ColdSpendResponse, Cw20BalanceResponse, DaoURIResponse, DryRunIssue, DryRunProblem,
+        DryRunResponse, DumpStateResponse, GetItemResponse, GlobalProposalResponse,
+        ManagedContractResponse, PauseInfoResponse, ProposalModuleCountResponse, SubDao,
+        TreasuryTiersResponse, VaultResponse,
     },
-    state::{Admin, Config, ModuleInstantiateInfo, ProposalModule, ProposalModuleStatus},
+    receive::{ReceiveHandler, ReceiveHandlerMsg},
+    registry::OfficialCodeId,
+    remote::RemoteCommand,
+    state::{
+        Admin, CircuitBreaker, Config, ManagedContractRegistration, Merge, MergeRole, MergeStep,
+        ModuleInstantiateInfo, ProposalModule, ProposalModuleStatus, TreasuryTiers,
+    },
+    vault::{VaultExecuteMsg, VaultRegistration},
     voting::{InfoResponse, VotingPowerAtHeightResponse},
 };
 
 use crate::{
-    contract::{derive_proposal_module_prefix, migrate, CONTRACT_NAME, CONTRACT_VERSION},
-    state::PROPOSAL_MODULES,
+    contract::{
+        derive_proposal_module_prefix, execute, migrate, query, CONTRACT_NAME, CONTRACT_VERSION,
+    },
+    state::{ADMIN, PROPOSAL_MODULES},
     ContractError,
 };
 
@@ -289,6 +305,7 @@ fn test_update_config() {
             &QueryMsg::ProposalModules {
                 start_after: None,
                 limit: None,
+                descending: None,
             },
         )
         .unwrap();
@@ -336,6 +353,220 @@ fn test_update_config() {
     assert_eq!(dao_uri.dao_uri, expected_config.dao_uri);
 }
 
+#[test]
+fn test_rollback_config() {
+    let (gov_addr, mut app) = do_standard_instantiate(true, None);
+    let modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            gov_addr.clone(),
+            &QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+                descending: None,
+            },
+        )
+        .unwrap();
+    let execute = |app: &mut App, msg: ExecuteMsg| {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            modules[0].address.clone(),
+            &dao_proposal_sudo::msg::ExecuteMsg::Execute {
+                msgs: vec![WasmMsg::Execute {
+                    contract_addr: gov_addr.to_string(),
+                    funds: vec![],
+                    msg: to_json_binary(&msg).unwrap(),
+                }
+                .into()],
+            },
+            &[],
+        )
+    };
+
+    let original: Config = app
+        .wrap()
+        .query_wasm_smart(gov_addr.clone(), &QueryMsg::Config {})
+        .unwrap();
+    let instantiate_height = app.block_info().height;
+
+    app.update_block(next_block);
+    let updated = Config {
+        name: "Misconfigured DAO".to_string(),
+        automatically_add_cw20s: false,
+        ..original.clone()
+    };
+    execute(
+        &mut app,
+        ExecuteMsg::UpdateConfig {
+            config: updated.clone(),
+        },
+    )
+    .unwrap();
+
+    let historic: Option<Config> = app
+        .wrap()
+        .query_wasm_smart(
+            gov_addr.clone(),
+            &QueryMsg::ConfigAtHeight {
+                height: instantiate_height,
+            },
+        )
+        .unwrap();
+    assert_eq!(historic, Some(original.clone()));
+    let historic: Option<Config> = app
+        .wrap()
+        .query_wasm_smart(
+            gov_addr.clone(),
+            &QueryMsg::ConfigAtHeight {
+                height: instantiate_height - 1,
+            },
+        )
+        .unwrap();
+    assert_eq!(historic, None);
+
+    // Only the DAO may roll back its config.
+    test_unauthorized(
+        &mut app,
+        gov_addr.clone(),
+        ExecuteMsg::RollbackConfig {
+            height: instantiate_height,
+        },
+    );
+
+    // Can not roll back to before the DAO existed.
+    let err: ContractError = execute(
+        &mut app,
+        ExecuteMsg::RollbackConfig {
+            height: instantiate_height - 1,
+        },
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(
+        err,
+        ContractError::NoConfigAtHeight {
+            height: instantiate_height - 1
+        }
+    );
+
+    app.update_block(next_block);
+    execute(
+        &mut app,
+        ExecuteMsg::RollbackConfig {
+            height: instantiate_height,
+        },
+    )
+    .unwrap();
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(gov_addr.clone(), &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(config, original);
+
+    // The misconfigured config remains in the history.
+    let historic: Option<Config> = app
+        .wrap()
+        .query_wasm_smart(
+            gov_addr,
+            &QueryMsg::ConfigAtHeight {
+                height: instantiate_height + 1,
+            },
+        )
+        .unwrap();
+    assert_eq!(historic, Some(updated));
+}
+
+#[test]
+fn test_proposal_modules_at_height() {
+    let (gov_addr, mut app) = do_standard_instantiate(true, None);
+    let sudo_id = app.store_code(sudo_proposal_contract());
+    let modules_at_height = |app: &App, height: u64| -> Vec<ProposalModule> {
+        app.wrap()
+            .query_wasm_smart(
+                gov_addr.clone(),
+                &QueryMsg::ProposalModulesAtHeight {
+                    height,
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap()
+    };
+    let original: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            gov_addr.clone(),
+            &QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+                descending: None,
+            },
+        )
+        .unwrap();
+    let instantiate_height = app.block_info().height;
+
+    // Add a module and disable the original.
+    app.update_block(next_block);
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        original[0].address.clone(),
+        &dao_proposal_sudo::msg::ExecuteMsg::Execute {
+            msgs: vec![WasmMsg::Execute {
+                contract_addr: gov_addr.to_string(),
+                funds: vec![],
+                msg: to_json_binary(&ExecuteMsg::UpdateProposalModules {
+                    to_add: vec![ModuleInstantiateInfo {
+                        code_id: sudo_id,
+                        msg: to_json_binary(&dao_proposal_sudo::msg::InstantiateMsg {
+                            root: CREATOR_ADDR.to_string(),
+                        })
+                        .unwrap(),
+                        admin: Some(Admin::CoreModule {}),
+                        funds: vec![],
+                        label: "new governance module".to_string(),
+                    }],
+                    to_disable: vec![original[0].address.to_string()],
+                })
+                .unwrap(),
+            }
+            .into()],
+        },
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(modules_at_height(&app, instantiate_height - 1), vec![]);
+    assert_eq!(modules_at_height(&app, instantiate_height), original);
+
+    let current = modules_at_height(&app, instantiate_height + 1);
+    assert_eq!(current.len(), 2);
+    let disabled = current
+        .iter()
+        .find(|module| module.address == original[0].address)
+        .unwrap();
+    assert_eq!(disabled.status, ProposalModuleStatus::Disabled);
+    let added = current
+        .iter()
+        .find(|module| module.address != original[0].address)
+        .unwrap();
+    assert_eq!(added.status, ProposalModuleStatus::Enabled);
+
+    // Pagination follows `ProposalModules`.
+    let page: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            gov_addr,
+            &QueryMsg::ProposalModulesAtHeight {
+                height: instantiate_height + 1,
+                start_after: Some(current[0].address.to_string()),
+                limit: Some(1),
+            },
+        )
+        .unwrap();
+    assert_eq!(page, vec![current[1].clone()]);
+}
+
 fn test_swap_governance(swaps: Vec<(u32, u32)>) {
     let mut app = App::default();
     let propmod_id = app.store_code(sudo_proposal_contract());
@@ -388,6 +619,7 @@ fn test_swap_governance(swaps: Vec<(u32, u32)>) {
             &QueryMsg::ProposalModules {
                 start_after: None,
                 limit: None,
+                descending: None,
             },
         )
         .unwrap();
@@ -417,6 +649,7 @@ fn test_swap_governance(swaps: Vec<(u32, u32)>) {
                 &QueryMsg::ProposalModules {
                     start_after: None,
                     limit: None,
+                    descending: None,
                 },
             )
             .unwrap();
@@ -567,6 +800,7 @@ fn test_removed_modules_can_not_execute() {
             &QueryMsg::ProposalModules {
                 start_after: None,
                 limit: None,
+                descending: None,
             },
         )
         .unwrap();
@@ -652,6 +886,7 @@ fn test_removed_modules_can_not_execute() {
             &QueryMsg::ActiveProposalModules {
                 start_after: None,
                 limit: None,
+                descending: None,
             },
         )
         .unwrap();
@@ -728,6 +963,7 @@ fn test_module_already_disabled() {
             &QueryMsg::ProposalModules {
                 start_after: None,
                 limit: None,
+                descending: None,
             },
         )
         .unwrap();
@@ -835,6 +1071,7 @@ fn test_swap_voting_module() {
             &QueryMsg::ProposalModules {
                 start_after: None,
                 limit: None,
+                descending: None,
             },
         )
         .unwrap();
@@ -970,6 +1207,11 @@ fn test_permissions() {
 
 fn do_standard_instantiate(auto_add: bool, admin: Option<String>) -> (Addr, App) {
     let mut app = App::default();
+    let gov_addr = instantiate_standard_dao(&mut app, auto_add, admin);
+    (gov_addr, app)
+}
+
+fn instantiate_standard_dao(app: &mut App, auto_add: bool, admin: Option<String>) -> Addr {
     let govmod_id = app.store_code(sudo_proposal_contract());
     let voting_id = app.store_code(cw20_balances_voting());
     let gov_id = app.store_code(cw_core_contract());
@@ -1018,18 +1260,15 @@ fn do_standard_instantiate(auto_add: bool, admin: Option<String>) -> (Addr, App)
         initial_items: None,
     };
 
-    let gov_addr = app
-        .instantiate_contract(
-            gov_id,
-            Addr::unchecked(CREATOR_ADDR),
-            &gov_instantiate,
-            &[],
-            "cw-governance",
-            None,
-        )
-        .unwrap();
-
-    (gov_addr, app)
+    app.instantiate_contract(
+        gov_id,
+        Addr::unchecked(CREATOR_ADDR),
+        &gov_instantiate,
+        &[],
+        "cw-governance",
+        None,
+    )
+    .unwrap()
 }
 
 #[test]
@@ -1044,6 +1283,7 @@ fn test_admin_permissions() {
             &QueryMsg::ProposalModules {
                 start_after: None,
                 limit: None,
+                descending: None,
             },
         )
         .unwrap();
@@ -1529,6 +1769,7 @@ fn list_items(
             &QueryMsg::ListItems {
                 start_after: start_at,
                 limit,
+                descending: None,
             },
         )
         .unwrap()
@@ -1906,6 +2147,7 @@ fn test_cw20_receive_auto_add() {
             &QueryMsg::Cw20TokenList {
                 start_after: None,
                 limit: None,
+                descending: None,
             },
         )
         .unwrap();
@@ -1979,6 +2221,7 @@ fn test_cw20_receive_auto_add() {
             &QueryMsg::Cw20TokenList {
                 start_after: None,
                 limit: None,
+                descending: None,
             },
         )
         .unwrap();
@@ -2041,6 +2284,7 @@ fn test_cw20_receive_no_auto_add() {
             &QueryMsg::Cw20TokenList {
                 start_after: None,
                 limit: None,
+                descending: None,
             },
         )
         .unwrap();
@@ -2064,6 +2308,7 @@ fn test_cw20_receive_no_auto_add() {
             &QueryMsg::Cw20TokenList {
                 start_after: None,
                 limit: None,
+                descending: None,
             },
         )
         .unwrap();
@@ -2071,1048 +2316,3952 @@ fn test_cw20_receive_no_auto_add() {
 }
 
 #[test]
-fn test_cw721_receive() {
+fn test_cached_cw20_balances() {
     let (gov_addr, mut app) = do_standard_instantiate(true, None);
 
-    let cw721_id = app.store_code(cw721_contract());
-
-    let cw721_addr = app
-        .instantiate_contract(
-            cw721_id,
-            Addr::unchecked(CREATOR_ADDR),
-            &cw721_base::msg::InstantiateMsg {
-                name: "ekez".to_string(),
-                symbol: "ekez".to_string(),
-                minter: CREATOR_ADDR.to_string(),
-            },
-            &[],
-            "cw721",
-            None,
-        )
+    let voting_module: Addr = app
+        .wrap()
+        .query_wasm_smart(gov_addr.clone(), &QueryMsg::VotingModule {})
         .unwrap();
-
-    let another_cw721 = app
-        .instantiate_contract(
-            cw721_id,
-            Addr::unchecked(CREATOR_ADDR),
-            &cw721_base::msg::InstantiateMsg {
-                name: "ekez".to_string(),
-                symbol: "ekez".to_string(),
-                minter: CREATOR_ADDR.to_string(),
-            },
-            &[],
-            "cw721",
-            None,
+    let gov_token: Addr = app
+        .wrap()
+        .query_wasm_smart(
+            voting_module,
+            &dao_interface::voting::Query::TokenContract {},
         )
         .unwrap();
 
+    let query_cached = |app: &App| -> Vec<CachedCw20BalanceResponse> {
+        app.wrap()
+            .query_wasm_smart(
+                gov_addr.clone(),
+                &QueryMsg::CachedCw20Balances {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap()
+    };
+    assert_eq!(query_cached(&app), vec![]);
+
+    // Receiving the token adds it to the cache.
     app.execute_contract(
         Addr::unchecked(CREATOR_ADDR),
-        cw721_addr.clone(),
-        &cw721_base::msg::ExecuteMsg::<Option<Empty>, Empty>::Mint {
-            token_id: "ekez".to_string(),
-            owner: CREATOR_ADDR.to_string(),
-            token_uri: None,
-            extension: None,
+        gov_token.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: gov_addr.to_string(),
+            amount: Uint128::new(1),
+            msg: to_json_binary(&"").unwrap(),
         },
         &[],
     )
     .unwrap();
+    let received_at = app.block_info().height;
+    assert_eq!(
+        query_cached(&app),
+        vec![CachedCw20BalanceResponse {
+            addr: gov_token.clone(),
+            balance: Uint128::new(1),
+            height: received_at,
+        }]
+    );
 
+    // Transfers do not notify the DAO, so are not seen until the
+    // cache is refreshed.
+    app.update_block(next_block);
     app.execute_contract(
         Addr::unchecked(CREATOR_ADDR),
-        cw721_addr.clone(),
-        &cw721_base::msg::ExecuteMsg::<Option<Empty>, Empty>::SendNft {
-            contract: gov_addr.to_string(),
-            token_id: "ekez".to_string(),
-            msg: to_json_binary("").unwrap(),
+        gov_token.clone(),
+        &cw20::Cw20ExecuteMsg::Transfer {
+            recipient: gov_addr.to_string(),
+            amount: Uint128::new(1),
         },
         &[],
     )
     .unwrap();
+    assert_eq!(query_cached(&app)[0].balance, Uint128::new(1));
 
-    let cw721_list: Vec<Addr> = app
-        .wrap()
-        .query_wasm_smart(
-            gov_addr.clone(),
-            &QueryMsg::Cw721TokenList {
-                start_after: None,
-                limit: None,
-            },
-        )
-        .unwrap();
-    assert_eq!(cw721_list, vec![cw721_addr.clone()]);
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        gov_addr.clone(),
+        &ExecuteMsg::RefreshCw20Balances {
+            start_after: None,
+            limit: None,
+        },
+        &[],
+    )
+    .unwrap();
+    let refreshed_at = app.block_info().height;
+    assert_eq!(
+        query_cached(&app),
+        vec![CachedCw20BalanceResponse {
+            addr: gov_token.clone(),
+            balance: Uint128::new(2),
+            height: refreshed_at,
+        }]
+    );
 
-    // Try to add an invalid cw721.
-    let err: ContractError = app
-        .execute_contract(
-            Addr::unchecked(gov_addr.clone()),
-            gov_addr.clone(),
-            &ExecuteMsg::UpdateCw721List {
-                to_add: vec!["new".to_string(), cw721_addr.to_string()],
-                to_remove: vec![cw721_addr.to_string()],
-            },
-            &[],
-        )
-        .unwrap_err()
-        .downcast()
-        .unwrap();
-    assert!(matches!(err, ContractError::Std(_)));
-
-    // Test that non-DAO can not update the list.
-    let err: ContractError = app
-        .execute_contract(
-            Addr::unchecked("ekez"),
-            gov_addr.clone(),
-            &ExecuteMsg::UpdateCw721List {
-                to_add: vec![],
-                to_remove: vec![cw721_addr.to_string()],
-            },
-            &[],
-        )
-        .unwrap_err()
-        .downcast()
-        .unwrap();
-    assert!(matches!(err, ContractError::Unauthorized {}));
-
-    // Add a real cw721.
+    // Spending tokens updates the cache.
+    app.update_block(next_block);
     app.execute_contract(
-        Addr::unchecked(gov_addr.clone()),
         gov_addr.clone(),
-        &ExecuteMsg::UpdateCw721List {
-            to_add: vec![another_cw721.to_string(), cw721_addr.to_string()],
-            to_remove: vec![cw721_addr.to_string()],
+        gov_addr.clone(),
+        &ExecuteMsg::ExecuteAdminMsgs {
+            msgs: vec![WasmMsg::Execute {
+                contract_addr: gov_token.to_string(),
+                msg: to_json_binary(&cw20::Cw20ExecuteMsg::Transfer {
+                    recipient: CREATOR_ADDR.to_string(),
+                    amount: Uint128::new(1),
+                })
+                .unwrap(),
+                funds: vec![],
+            }
+            .into()],
         },
         &[],
     )
     .unwrap();
+    assert_eq!(
+        query_cached(&app),
+        vec![CachedCw20BalanceResponse {
+            addr: gov_token.clone(),
+            balance: Uint128::new(1),
+            height: app.block_info().height,
+        }]
+    );
 
-    let cw20_list: Vec<Addr> = app
-        .wrap()
-        .query_wasm_smart(
-            gov_addr,
-            &QueryMsg::Cw721TokenList {
-                start_after: None,
-                limit: None,
-            },
-        )
-        .unwrap();
-    assert_eq!(cw20_list, vec![another_cw721]);
-}
-
-#[test]
-fn test_cw721_receive_no_auto_add() {
-    let (gov_addr, mut app) = do_standard_instantiate(false, None);
-
-    let cw721_id = app.store_code(cw721_contract());
-
-    let cw721_addr = app
-        .instantiate_contract(
-            cw721_id,
-            Addr::unchecked(CREATOR_ADDR),
-            &cw721_base::msg::InstantiateMsg {
-                name: "ekez".to_string(),
-                symbol: "ekez".to_string(),
-                minter: CREATOR_ADDR.to_string(),
-            },
-            &[],
-            "cw721",
-            None,
-        )
-        .unwrap();
-
-    let another_cw721 = app
-        .instantiate_contract(
-            cw721_id,
-            Addr::unchecked(CREATOR_ADDR),
-            &cw721_base::msg::InstantiateMsg {
-                name: "ekez".to_string(),
-                symbol: "ekez".to_string(),
-                minter: CREATOR_ADDR.to_string(),
-            },
-            &[],
-            "cw721",
-            None,
-        )
-        .unwrap();
-
+    // Removing the token removes it from the cache.
     app.execute_contract(
-        Addr::unchecked(CREATOR_ADDR),
-        cw721_addr.clone(),
-        &cw721_base::msg::ExecuteMsg::<Option<Empty>, Empty>::Mint {
-            token_id: "ekez".to_string(),
-            owner: CREATOR_ADDR.to_string(),
-            token_uri: None,
-            extension: None,
+        gov_addr.clone(),
+        gov_addr.clone(),
+        &ExecuteMsg::UpdateCw20List {
+            to_add: vec![],
+            to_remove: vec![gov_token.to_string()],
         },
         &[],
     )
     .unwrap();
+    assert_eq!(query_cached(&app), vec![]);
+}
 
-    app.execute_contract(
-        Addr::unchecked(CREATOR_ADDR),
-        cw721_addr.clone(),
-        &cw721_base::msg::ExecuteMsg::<Option<Empty>, Empty>::SendNft {
-            contract: gov_addr.to_string(),
-            token_id: "ekez".to_string(),
-            msg: to_json_binary("").unwrap(),
+#[test]
+fn test_update_pinned_codes() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let dao = env.contract.address.clone();
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("ekez", &[]),
+        ExecuteMsg::UpdatePinnedCodes {
+            to_add: vec![1],
+            to_remove: vec![],
         },
-        &[],
     )
-    .unwrap();
-
-    let cw721_list: Vec<Addr> = app
-        .wrap()
-        .query_wasm_smart(
-            gov_addr.clone(),
-            &QueryMsg::Cw721TokenList {
-                start_after: None,
-                limit: None,
-            },
-        )
-        .unwrap();
-    assert_eq!(cw721_list, Vec::<Addr>::new());
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
 
-    // Duplicates OK. Just adds one.
-    app.execute_contract(
-        Addr::unchecked(gov_addr.clone()),
-        gov_addr.clone(),
-        &ExecuteMsg::UpdateCw721List {
-            to_add: vec![
-                another_cw721.to_string(),
-                cw721_addr.to_string(),
-                cw721_addr.to_string(),
-            ],
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(dao.as_str(), &[]),
+        ExecuteMsg::UpdatePinnedCodes {
+            to_add: vec![1, 2],
             to_remove: vec![],
         },
-        &[],
     )
     .unwrap();
+    // The codes are recorded for chain governance to pin, as the DAO
+    // may not pin them itself.
+    assert!(res.messages.is_empty());
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(dao.as_str(), &[]),
+        ExecuteMsg::UpdatePinnedCodes {
+            to_add: vec![],
+            to_remove: vec![1],
+        },
+    )
+    .unwrap();
+    assert!(res.messages.is_empty());
 
-    let cw20_list: Vec<Addr> = app
-        .wrap()
-        .query_wasm_smart(
-            gov_addr,
-            &QueryMsg::Cw721TokenList {
+    let pinned: Vec<u64> = from_json_binary(
+        &query(
+            deps.as_ref(),
+            env,
+            QueryMsg::PinnedCodes {
                 start_after: None,
                 limit: None,
             },
         )
-        .unwrap();
-    assert_eq!(cw20_list, vec![another_cw721, cw721_addr]);
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(pinned, vec![2]);
 }
 
 #[test]
-fn test_pause() {
-    let (core_addr, mut app) = do_standard_instantiate(false, None);
-
-    let start_height = app.block_info().height;
-
-    let proposal_modules: Vec<ProposalModule> = app
-        .wrap()
-        .query_wasm_smart(
-            core_addr.clone(),
-            &QueryMsg::ProposalModules {
-                start_after: None,
-                limit: None,
+fn test_freeze_list() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let dao = env.contract.address.clone();
+    let module = Addr::unchecked("proposal");
+    PROPOSAL_MODULES
+        .save(
+            deps.as_mut().storage,
+            module.clone(),
+            &ProposalModule {
+                address: module.clone(),
+                prefix: "A".to_string(),
+                status: ProposalModuleStatus::Enabled,
             },
         )
         .unwrap();
 
-    assert_eq!(proposal_modules.len(), 1);
-    let proposal_module = proposal_modules.into_iter().next().unwrap();
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("ekez", &[]),
+        ExecuteMsg::UpdateFrozenContracts {
+            to_add: vec!["exploited".to_string()],
+            to_remove: vec![],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
 
-    let paused: PauseInfoResponse = app
-        .wrap()
-        .query_wasm_smart(core_addr.clone(), &QueryMsg::PauseInfo {})
-        .unwrap();
-    assert_eq!(paused, PauseInfoResponse::Unpaused {});
-    let all_state: DumpStateResponse = app
-        .wrap()
-        .query_wasm_smart(core_addr.clone(), &QueryMsg::DumpState {})
-        .unwrap();
-    assert_eq!(all_state.pause_info, PauseInfoResponse::Unpaused {});
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(dao.as_str(), &[]),
+        ExecuteMsg::UpdateFrozenDenoms {
+            to_add: vec!["1bad".to_string()],
+            to_remove: vec![],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidDenom {
+            denom: "1bad".to_string()
+        }
+    );
 
-    // DAO is not paused. Check that we can execute things.
-    //
-    // Tests intentionally use the core address to send these
-    // messsages to simulate a worst case scenerio where the core
-    // contract has a vulnerability.
-    app.execute_contract(
-        core_addr.clone(),
-        core_addr.clone(),
-        &ExecuteMsg::UpdateConfig {
-            config: Config {
-                dao_uri: None,
-                name: "The Empire Strikes Back".to_string(),
-                description: "haha lol we have pwned your DAO".to_string(),
-                image_url: None,
-                automatically_add_cw20s: true,
-                automatically_add_cw721s: true,
-            },
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(dao.as_str(), &[]),
+        ExecuteMsg::UpdateFrozenContracts {
+            to_add: vec!["exploited".to_string(), dao.to_string()],
+            to_remove: vec![],
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(dao.as_str(), &[]),
+        ExecuteMsg::UpdateFrozenDenoms {
+            to_add: vec!["ubad".to_string()],
+            to_remove: vec![],
         },
-        &[],
     )
     .unwrap();
 
-    // Oh no the DAO is under attack! Quick! Pause the DAO while we
-    // figure out what to do!
-    let err: ContractError = app
-        .execute_contract(
-            proposal_module.address.clone(),
-            core_addr.clone(),
-            &ExecuteMsg::Pause {
-                duration: Duration::Height(10),
+    let frozen: Vec<String> = from_json_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::FrozenDenoms {
+                start_after: None,
+                limit: None,
             },
-            &[],
         )
-        .unwrap_err()
-        .downcast()
-        .unwrap();
-
-    // Only the DAO may call this on itself. Proposal modules must use
-    // the execute hook.
-    assert_eq!(err, ContractError::Unauthorized {});
-
-    app.execute_contract(
-        proposal_module.address.clone(),
-        core_addr.clone(),
-        &ExecuteMsg::ExecuteProposalHook {
-            msgs: vec![WasmMsg::Execute {
-                contract_addr: core_addr.to_string(),
-                msg: to_json_binary(&ExecuteMsg::Pause {
-                    duration: Duration::Height(10),
-                })
-                .unwrap(),
-                funds: vec![],
-            }
-            .into()],
-        },
-        &[],
+        .unwrap(),
     )
     .unwrap();
+    assert_eq!(frozen, vec!["ubad".to_string()]);
 
-    let paused: PauseInfoResponse = app
-        .wrap()
-        .query_wasm_smart(core_addr.clone(), &QueryMsg::PauseInfo {})
-        .unwrap();
+    let execute_exploited: CosmosMsg = WasmMsg::Execute {
+        contract_addr: "exploited".to_string(),
+        msg: to_json_binary(&"").unwrap(),
+        funds: vec![],
+    }
+    .into();
+    let send_bad: CosmosMsg = BankMsg::Send {
+        to_address: "ekez".to_string(),
+        amount: vec![coin(1, "ujuno"), coin(1, "ubad")],
+    }
+    .into();
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(module.as_str(), &[]),
+        ExecuteMsg::ExecuteProposalHook {
+            msgs: vec![send_bad.clone()],
+        },
+    )
+    .unwrap_err();
     assert_eq!(
-        paused,
-        PauseInfoResponse::Paused {
-            expiration: Expiration::AtHeight(start_height + 10)
+        err,
+        ContractError::Frozen {
+            index: 0,
+            target: "ubad".to_string()
         }
     );
-    let all_state: DumpStateResponse = app
-        .wrap()
-        .query_wasm_smart(core_addr.clone(), &QueryMsg::DumpState {})
-        .unwrap();
+
+    // Frozen contracts may not receive native or cw20 tokens.
+    let to_exploited: Vec<CosmosMsg> = vec![
+        BankMsg::Send {
+            to_address: "exploited".to_string(),
+            amount: vec![coin(1, "ujuno")],
+        }
+        .into(),
+        WasmMsg::Execute {
+            contract_addr: "token".to_string(),
+            msg: to_json_binary(&cw20::Cw20ExecuteMsg::Transfer {
+                recipient: "exploited".to_string(),
+                amount: Uint128::new(1),
+            })
+            .unwrap(),
+            funds: vec![],
+        }
+        .into(),
+        WasmMsg::Execute {
+            contract_addr: "token".to_string(),
+            msg: to_json_binary(&cw20::Cw20ExecuteMsg::Send {
+                contract: "exploited".to_string(),
+                amount: Uint128::new(1),
+                msg: Binary::default(),
+            })
+            .unwrap(),
+            funds: vec![],
+        }
+        .into(),
+    ];
+    for msg in to_exploited {
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(module.as_str(), &[]),
+            ExecuteMsg::ExecuteProposalHook { msgs: vec![msg] },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Frozen {
+                index: 0,
+                target: "exploited".to_string()
+            }
+        );
+    }
+
+    // Messages to the DAO itself are never frozen, so the freeze
+    // list may be updated by proposal.
+    let unfreeze: CosmosMsg = WasmMsg::Execute {
+        contract_addr: dao.to_string(),
+        msg: to_json_binary(&ExecuteMsg::UpdateFrozenContracts {
+            to_add: vec![],
+            to_remove: vec!["exploited".to_string()],
+        })
+        .unwrap(),
+        funds: vec![],
+    }
+    .into();
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(module.as_str(), &[]),
+        ExecuteMsg::ExecuteProposalHook {
+            msgs: vec![unfreeze.clone(), execute_exploited.clone()],
+        },
+    )
+    .unwrap_err();
     assert_eq!(
-        all_state.pause_info,
-        PauseInfoResponse::Paused {
-            expiration: Expiration::AtHeight(start_height + 10)
+        err,
+        ContractError::Frozen {
+            index: 1,
+            target: "exploited".to_string()
         }
     );
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(module.as_str(), &[]),
+        ExecuteMsg::ExecuteProposalHook {
+            msgs: vec![unfreeze],
+        },
+    )
+    .unwrap();
 
-    let err: ContractError = app
-        .execute_contract(
-            core_addr.clone(),
-            core_addr.clone(),
-            &ExecuteMsg::UpdateConfig {
-                config: Config {
-                    dao_uri: None,
-                    name: "The Empire Strikes Back Again".to_string(),
-                    description: "haha lol we have pwned your DAO again".to_string(),
-                    image_url: None,
-                    automatically_add_cw20s: true,
-                    automatically_add_cw721s: true,
-                },
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(dao.as_str(), &[]),
+        ExecuteMsg::UpdateFrozenContracts {
+            to_add: vec![],
+            to_remove: vec!["exploited".to_string()],
+        },
+    )
+    .unwrap();
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info(module.as_str(), &[]),
+        ExecuteMsg::ExecuteProposalHook {
+            msgs: vec![execute_exploited.clone()],
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![SubMsg::new(execute_exploited)]);
+}
+
+#[test]
+fn test_freeze_all_outflows() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let dao = env.contract.address.clone();
+    let module = Addr::unchecked("proposal");
+    let admin = Addr::unchecked("admin");
+    PROPOSAL_MODULES
+        .save(
+            deps.as_mut().storage,
+            module.clone(),
+            &ProposalModule {
+                address: module.clone(),
+                prefix: "A".to_string(),
+                status: ProposalModuleStatus::Enabled,
             },
-            &[],
         )
-        .unwrap_err()
-        .downcast()
         .unwrap();
+    ADMIN.save(deps.as_mut().storage, &admin).unwrap();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(dao.as_str(), &[]),
+        ExecuteMsg::UpdateFrozenDenoms {
+            to_add: vec!["ubad".to_string()],
+            to_remove: vec![],
+        },
+    )
+    .unwrap();
 
-    assert!(matches!(err, ContractError::Paused { .. }));
-
-    let err: ContractError = app
-        .execute_contract(
-            proposal_module.address.clone(),
-            core_addr.clone(),
-            &ExecuteMsg::ExecuteProposalHook {
-                msgs: vec![WasmMsg::Execute {
-                    contract_addr: core_addr.to_string(),
-                    msg: to_json_binary(&ExecuteMsg::Pause {
-                        duration: Duration::Height(10),
-                    })
-                    .unwrap(),
-                    funds: vec![],
-                }
-                .into()],
-            },
-            &[],
+    let ibc_transfer: CosmosMsg = IbcMsg::Transfer {
+        channel_id: "channel-0".to_string(),
+        to_address: "cosmos1ekez".to_string(),
+        amount: coin(1, "ubad"),
+        timeout: IbcTimeout::with_timestamp(env.block.time.plus_seconds(60)),
+    }
+    .into();
+    let delegate: CosmosMsg = StakingMsg::Delegate {
+        validator: "validator".to_string(),
+        amount: coin(1, "ubad"),
+    }
+    .into();
+    let instantiate2: CosmosMsg = WasmMsg::Instantiate2 {
+        admin: None,
+        code_id: 1,
+        label: "label".to_string(),
+        msg: to_json_binary(&Empty {}).unwrap(),
+        funds: coins(1, "ubad"),
+        salt: Binary::from(b"salt".to_vec()),
+    }
+    .into();
+    for msg in [ibc_transfer.clone(), delegate, instantiate2] {
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(module.as_str(), &[]),
+            ExecuteMsg::ExecuteProposalHook { msgs: vec![msg] },
         )
-        .unwrap_err()
-        .downcast()
-        .unwrap();
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Frozen {
+                index: 0,
+                target: "ubad".to_string()
+            }
+        );
+    }
 
-    assert!(matches!(err, ContractError::Paused { .. }));
+    // Stargate messages can not be read, so they are frozen while
+    // anything is.
+    let stargate = CosmosMsg::Stargate {
+        type_url: "/cosmos.bank.v1beta1.MsgSend".to_string(),
+        value: Binary::default(),
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(module.as_str(), &[]),
+        ExecuteMsg::ExecuteProposalHook {
+            msgs: vec![stargate],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Frozen {
+            index: 0,
+            target: "/cosmos.bank.v1beta1.MsgSend".to_string()
+        }
+    );
 
-    app.update_block(|block| block.height += 9);
+    // The admin is held to the freeze too.
+    let err = execute(
+        deps.as_mut(),
+        env,
+        mock_info(admin.as_str(), &[]),
+        ExecuteMsg::ExecuteAdminMsgs {
+            msgs: vec![ibc_transfer],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Frozen {
+            index: 0,
+            target: "ubad".to_string()
+        }
+    );
+}
 
-    // Still not unpaused.
-    let err: ContractError = app
-        .execute_contract(
-            proposal_module.address.clone(),
-            core_addr.clone(),
-            &ExecuteMsg::ExecuteProposalHook {
-                msgs: vec![WasmMsg::Execute {
-                    contract_addr: core_addr.to_string(),
-                    msg: to_json_binary(&ExecuteMsg::Pause {
-                        duration: Duration::Height(10),
-                    })
-                    .unwrap(),
-                    funds: vec![],
-                }
-                .into()],
-            },
-            &[],
-        )
-        .unwrap_err()
-        .downcast()
-        .unwrap();
+#[test]
+fn test_vaults() {
+    let mut deps = mock_dependencies_with_balance(&coins(100, "ujuno"));
+    let env = mock_env();
+    let dao = env.contract.address.clone();
+    let dao_info = mock_info(dao.as_str(), &[]);
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("ekez", &[]),
+        ExecuteMsg::UpdateVaults {
+            to_add: vec![],
+            to_remove: vec![],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
 
-    assert!(matches!(err, ContractError::Paused { .. }));
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        dao_info.clone(),
+        ExecuteMsg::UpdateVaults {
+            to_add: vec![
+                VaultRegistration {
+                    address: "vault".to_string(),
+                    denom: "ujuno".to_string(),
+                    cap: Uint128::new(30),
+                },
+                VaultRegistration {
+                    address: "vaulttwo".to_string(),
+                    denom: "ujuno".to_string(),
+                    cap: Uint128::new(100),
+                },
+            ],
+            to_remove: vec![],
+        },
+    )
+    .unwrap();
 
-    app.update_block(|block| block.height += 1);
+    let deposit = |vault: &str, amount: u128| ExecuteMsg::DepositToVault {
+        vault: vault.to_string(),
+        amount: Uint128::new(amount),
+    };
 
-    let paused: PauseInfoResponse = app
-        .wrap()
-        .query_wasm_smart(core_addr.clone(), &QueryMsg::PauseInfo {})
-        .unwrap();
-    assert_eq!(paused, PauseInfoResponse::Unpaused {});
-    let all_state: DumpStateResponse = app
-        .wrap()
-        .query_wasm_smart(core_addr.clone(), &QueryMsg::DumpState {})
-        .unwrap();
-    assert_eq!(all_state.pause_info, PauseInfoResponse::Unpaused {});
+    // Nothing may be deposited until an allocation limit is set.
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        dao_info.clone(),
+        deposit("vault", 10),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::VaultAllocationExceeded {
+            limit: Decimal::zero(),
+            denom: "ujuno".to_string()
+        }
+    );
 
-    // Now its unpaused so we should be able to pause again.
-    app.execute_contract(
-        proposal_module.address,
-        core_addr.clone(),
-        &ExecuteMsg::ExecuteProposalHook {
-            msgs: vec![WasmMsg::Execute {
-                contract_addr: core_addr.to_string(),
-                msg: to_json_binary(&ExecuteMsg::Pause {
-                    duration: Duration::Height(10),
-                })
-                .unwrap(),
-                funds: vec![],
-            }
-            .into()],
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        dao_info.clone(),
+        ExecuteMsg::UpdateVaultAllocationLimit {
+            limit: Decimal::percent(150),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidVaultAllocationLimit {});
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        dao_info.clone(),
+        ExecuteMsg::UpdateVaultAllocationLimit {
+            limit: Decimal::percent(50),
         },
-        &[],
     )
     .unwrap();
 
-    let paused: PauseInfoResponse = app
-        .wrap()
-        .query_wasm_smart(core_addr.clone(), &QueryMsg::PauseInfo {})
-        .unwrap();
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        dao_info.clone(),
+        deposit("vault", 40),
+    )
+    .unwrap_err();
     assert_eq!(
-        paused,
-        PauseInfoResponse::Paused {
-            expiration: Expiration::AtHeight(start_height + 20)
+        err,
+        ContractError::VaultCapExceeded {
+            cap: Uint128::new(30)
         }
     );
-    let all_state: DumpStateResponse = app
-        .wrap()
-        .query_wasm_smart(core_addr, &QueryMsg::DumpState {})
-        .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        dao_info.clone(),
+        deposit("vault", 30),
+    )
+    .unwrap();
     assert_eq!(
-        all_state.pause_info,
-        PauseInfoResponse::Paused {
-            expiration: Expiration::AtHeight(start_height + 20)
+        res.messages,
+        vec![SubMsg::new(WasmMsg::Execute {
+            contract_addr: "vault".to_string(),
+            msg: to_json_binary(&VaultExecuteMsg::Deposit {}).unwrap(),
+            funds: coins(30, "ujuno"),
+        })]
+    );
+
+    // The mock balance is not reduced by deposits, so the DAO holds
+    // 130 ujuno of which at most 65 may be in vaults.
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        dao_info.clone(),
+        deposit("vaulttwo", 40),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::VaultAllocationExceeded { .. }));
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        dao_info.clone(),
+        deposit("vaulttwo", 35),
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        dao_info.clone(),
+        ExecuteMsg::UpdateVaults {
+            to_add: vec![],
+            to_remove: vec!["vault".to_string()],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::VaultNotEmpty {
+            address: "vault".to_string()
         }
     );
-}
 
-#[test]
-fn test_dump_state_proposal_modules() {
-    let (core_addr, app) = do_standard_instantiate(false, None);
-    let proposal_modules: Vec<ProposalModule> = app
-        .wrap()
-        .query_wasm_smart(
-            core_addr.clone(),
-            &QueryMsg::ProposalModules {
+    // Withdrawing yield does not take the deposited amount below
+    // zero.
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        dao_info.clone(),
+        ExecuteMsg::WithdrawFromVault {
+            vault: "vault".to_string(),
+            amount: Uint128::new(40),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(WasmMsg::Execute {
+            contract_addr: "vault".to_string(),
+            msg: to_json_binary(&VaultExecuteMsg::Withdraw {
+                amount: Uint128::new(40)
+            })
+            .unwrap(),
+            funds: vec![],
+        })]
+    );
+
+    let vaults: Vec<VaultResponse> = from_json_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::Vaults {
                 start_after: None,
                 limit: None,
             },
         )
-        .unwrap();
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        vaults,
+        vec![
+            VaultResponse {
+                vault: Addr::unchecked("vault"),
+                denom: "ujuno".to_string(),
+                cap: Uint128::new(30),
+                deposited: Uint128::zero(),
+            },
+            VaultResponse {
+                vault: Addr::unchecked("vaulttwo"),
+                denom: "ujuno".to_string(),
+                cap: Uint128::new(100),
+                deposited: Uint128::new(35),
+            },
+        ]
+    );
 
-    assert_eq!(proposal_modules.len(), 1);
-    let proposal_module = proposal_modules.into_iter().next().unwrap();
+    execute(
+        deps.as_mut(),
+        env,
+        dao_info,
+        ExecuteMsg::UpdateVaults {
+            to_add: vec![],
+            to_remove: vec!["vault".to_string()],
+        },
+    )
+    .unwrap();
+}
 
-    let all_state: DumpStateResponse = app
-        .wrap()
-        .query_wasm_smart(core_addr, &QueryMsg::DumpState {})
-        .unwrap();
-    assert_eq!(all_state.pause_info, PauseInfoResponse::Unpaused {});
-    assert_eq!(all_state.proposal_modules.len(), 1);
-    assert_eq!(all_state.proposal_modules[0], proposal_module);
-}
-
-// Note that this isn't actually testing that we are migrating from the previous version since
-// with multitest contract instantiation we can't manipulate storage to the previous version of state before invoking migrate. So if anything,
-// this just tests the idempotency of migrate.
 #[test]
-fn test_migrate_from_compatible() {
-    let mut app = App::default();
-    let govmod_id = app.store_code(sudo_proposal_contract());
-    let voting_id = app.store_code(cw20_balances_voting());
-    let gov_id = app.store_code(cw_core_contract());
-    let cw20_id = app.store_code(cw20_contract());
-
-    let govmod_instantiate = dao_proposal_sudo::msg::InstantiateMsg {
-        root: CREATOR_ADDR.to_string(),
-    };
-    let voting_instantiate = dao_voting_cw20_balance::msg::InstantiateMsg {
-        token_info: dao_voting_cw20_balance::msg::TokenInfo::New {
-            code_id: cw20_id,
-            label: "DAO DAO voting".to_string(),
-            name: "DAO DAO".to_string(),
-            symbol: "DAO".to_string(),
-            decimals: 6,
-            initial_balances: vec![cw20::Cw20Coin {
-                address: CREATOR_ADDR.to_string(),
-                amount: Uint128::from(2u64),
+fn test_remote_commands() {
+    let mut deps = mock_dependencies_with_balance(&coins(100, "ujuno"));
+    let env = mock_env();
+    let dao = env.contract.address.clone();
+    let dao_info = mock_info(dao.as_str(), &[]);
+
+    // Anyone may fund the DAO.
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("stranger", &coins(10, "ujuno")),
+        ExecuteMsg::RemoteCommand {
+            command: RemoteCommand::Fund {},
+        },
+    )
+    .unwrap();
+    assert!(res.messages.is_empty());
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        dao_info.clone(),
+        ExecuteMsg::UpdateVaults {
+            to_add: vec![VaultRegistration {
+                address: "vault".to_string(),
+                denom: "ujuno".to_string(),
+                cap: Uint128::new(30),
             }],
-            marketing: None,
+            to_remove: vec![],
         },
-    };
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        dao_info.clone(),
+        ExecuteMsg::UpdateVaultAllocationLimit {
+            limit: Decimal::one(),
+        },
+    )
+    .unwrap();
 
-    // Instantiate the core module with an admin to do migrations.
-    let gov_instantiate = InstantiateMsg {
-        dao_uri: None,
-        admin: None,
-        name: "DAO DAO".to_string(),
-        description: "A DAO that builds DAOs.".to_string(),
-        image_url: None,
-        automatically_add_cw20s: false,
-        automatically_add_cw721s: false,
-        voting_module_instantiate_info: ModuleInstantiateInfo {
-            code_id: voting_id,
-            msg: to_json_binary(&voting_instantiate).unwrap(),
-            admin: Some(Admin::CoreModule {}),
-            funds: vec![],
-            label: "voting module".to_string(),
+    let deposit = || ExecuteMsg::RemoteCommand {
+        command: RemoteCommand::DepositToVault {
+            vault: "vault".to_string(),
         },
-        proposal_modules_instantiate_info: vec![ModuleInstantiateInfo {
-            code_id: govmod_id,
-            msg: to_json_binary(&govmod_instantiate).unwrap(),
-            admin: Some(Admin::CoreModule {}),
-            funds: vec![],
-            label: "governance module".to_string(),
-        }],
-        initial_items: None,
     };
 
-    let core_addr = app
-        .instantiate_contract(
-            gov_id,
-            Addr::unchecked(CREATOR_ADDR),
-            &gov_instantiate,
-            &[],
-            "cw-governance",
-            Some(CREATOR_ADDR.to_string()),
-        )
-        .unwrap();
+    // Only remote members may deposit into vaults.
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("stranger", &coins(10, "ujuno")),
+        deposit(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
 
-    let state: DumpStateResponse = app
-        .wrap()
-        .query_wasm_smart(core_addr.clone(), &QueryMsg::DumpState {})
-        .unwrap();
+    // Only the DAO may add remote members.
+    let add_member = ExecuteMsg::UpdateRemoteMembers {
+        to_add: vec!["member".to_string()],
+        to_remove: vec![],
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("member", &[]),
+        add_member.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), dao_info.clone(), add_member).unwrap();
 
-    app.execute(
-        Addr::unchecked(CREATOR_ADDR),
-        CosmosMsg::Wasm(WasmMsg::Migrate {
-            contract_addr: core_addr.to_string(),
-            new_code_id: gov_id,
-            msg: to_json_binary(&MigrateMsg::FromCompatible {}).unwrap(),
-        }),
+    let members: Vec<Addr> = from_json_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::RemoteMembers {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(members, vec![Addr::unchecked("member")]);
+
+    // Remote members may only deposit the funds they transfer, not
+    // the treasury's.
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("member", &coins(10, "uatom")),
+        deposit(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::ZeroVaultAmount {});
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("member", &coins(10, "ujuno")),
+        deposit(),
     )
     .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(WasmMsg::Execute {
+            contract_addr: "vault".to_string(),
+            msg: to_json_binary(&VaultExecuteMsg::Deposit {}).unwrap(),
+            funds: coins(10, "ujuno"),
+        })]
+    );
 
-    let new_state: DumpStateResponse = app
-        .wrap()
-        .query_wasm_smart(core_addr, &QueryMsg::DumpState {})
-        .unwrap();
+    // Vault caps still apply.
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("member", &coins(25, "ujuno")),
+        deposit(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::VaultCapExceeded {
+            cap: Uint128::new(30)
+        }
+    );
 
-    assert_eq!(new_state, state);
+    // Removed members may no longer send commands other than `Fund`.
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        dao_info,
+        ExecuteMsg::UpdateRemoteMembers {
+            to_add: vec![],
+            to_remove: vec!["member".to_string()],
+        },
+    )
+    .unwrap();
+    let err = execute(
+        deps.as_mut(),
+        env,
+        mock_info("member", &coins(10, "ujuno")),
+        deposit(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
 }
 
 #[test]
-fn test_migrate_from_beta() {
-    use cw_core_v1 as v1;
-
-    let mut app = App::default();
-    let govmod_id = app.store_code(sudo_proposal_contract());
-    let voting_id = app.store_code(cw20_balances_voting());
-    let core_id = app.store_code(cw_core_contract());
-    let v1_core_id = app.store_code(v1_cw_core_contract());
-    let cw20_id = app.store_code(cw20_contract());
+fn test_circuit_breaker() {
+    let mut deps = mock_dependencies();
+    let mut env = mock_env();
+    let dao = env.contract.address.clone();
+    let module = Addr::unchecked("proposal");
+    PROPOSAL_MODULES
+        .save(
+            deps.as_mut().storage,
+            module.clone(),
+            &ProposalModule {
+                address: module.clone(),
+                prefix: "A".to_string(),
+                status: ProposalModuleStatus::Enabled,
+            },
+        )
+        .unwrap();
 
-    let proposal_instantiate = dao_proposal_sudo::msg::InstantiateMsg {
-        root: CREATOR_ADDR.to_string(),
+    let circuit_breaker = CircuitBreaker {
+        window: Duration::Height(10),
+        outflow_limits: vec![coin(100, "ujuno")],
+        cw20_outflow_limits: vec![],
+        max_executions_per_block: Some(2),
     };
-    let voting_instantiate = dao_voting_cw20_balance::msg::InstantiateMsg {
-        token_info: dao_voting_cw20_balance::msg::TokenInfo::New {
-            code_id: cw20_id,
-            label: "DAO DAO voting".to_string(),
-            name: "DAO DAO".to_string(),
-            symbol: "DAO".to_string(),
-            decimals: 6,
-            initial_balances: vec![cw20::Cw20Coin {
-                address: CREATOR_ADDR.to_string(),
-                amount: Uint128::from(2u64),
-            }],
-            marketing: None,
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("ekez", &[]),
+        ExecuteMsg::UpdateCircuitBreaker {
+            circuit_breaker: Some(circuit_breaker.clone()),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(dao.as_str(), &[]),
+        ExecuteMsg::UpdateCircuitBreaker {
+            circuit_breaker: Some(CircuitBreaker {
+                outflow_limits: vec![coin(100, "ujuno"), coin(10, "ujuno")],
+                ..circuit_breaker.clone()
+            }),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidCircuitBreaker {});
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(dao.as_str(), &[]),
+        ExecuteMsg::UpdateCircuitBreaker {
+            circuit_breaker: Some(circuit_breaker.clone()),
         },
+    )
+    .unwrap();
+
+    let send = |amount: u128| -> CosmosMsg {
+        BankMsg::Send {
+            to_address: "ekez".to_string(),
+            amount: coins(amount, "ujuno"),
+        }
+        .into()
     };
 
-    // Instantiate the core module with an admin to do migrations.
-    let v1_core_instantiate = v1::msg::InstantiateMsg {
-        admin: None,
-        name: "DAO DAO".to_string(),
-        description: "A DAO that builds DAOs.".to_string(),
-        image_url: None,
-        automatically_add_cw20s: false,
-        automatically_add_cw721s: false,
-        voting_module_instantiate_info: v1::msg::ModuleInstantiateInfo {
-            code_id: voting_id,
-            msg: to_json_binary(&voting_instantiate).unwrap(),
-            admin: v1::msg::Admin::CoreContract {},
-            label: "voting module".to_string(),
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(module.as_str(), &[]),
+        ExecuteMsg::ExecuteProposalHook {
+            msgs: vec![send(60)],
         },
-        proposal_modules_instantiate_info: vec![
-            v1::msg::ModuleInstantiateInfo {
-                code_id: govmod_id,
-                msg: to_json_binary(&proposal_instantiate).unwrap(),
-                admin: v1::msg::Admin::CoreContract {},
-                label: "governance module 1".to_string(),
-            },
-            v1::msg::ModuleInstantiateInfo {
-                code_id: govmod_id,
-                msg: to_json_binary(&proposal_instantiate).unwrap(),
-                admin: v1::msg::Admin::CoreContract {},
-                label: "governance module 2".to_string(),
+    )
+    .unwrap();
+    assert!(res.events.is_empty());
+
+    // Outflows are summed across the window, so the second spend
+    // trips the circuit breaker but is not itself blocked.
+    env.block.height += 1;
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(module.as_str(), &[]),
+        ExecuteMsg::ExecuteProposalHook {
+            msgs: vec![send(60)],
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(res.events[0].ty, "circuit_breaker_tripped");
+
+    let response: CircuitBreakerResponse =
+        from_json_binary(&query(deps.as_ref(), env.clone(), QueryMsg::CircuitBreaker {}).unwrap())
+            .unwrap();
+    assert_eq!(response.circuit_breaker, Some(circuit_breaker));
+    assert_eq!(response.tripped.unwrap().height, env.block.height);
+    assert_eq!(response.outflows, coins(120, "ujuno"));
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(module.as_str(), &[]),
+        ExecuteMsg::ExecuteProposalHook {
+            msgs: vec![send(1)],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::CircuitBreakerTripped {});
+
+    let dry_run: DryRunResponse = from_json_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::DryRunMsgs {
+                msgs: vec![send(1)],
             },
-        ],
-        initial_items: None,
-    };
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert!(dry_run.problems.contains(&DryRunProblem {
+        msg_index: Some(0),
+        issue: DryRunIssue::CircuitBreakerTripped {},
+    }));
+
+    // Messages that do not move funds, such as resetting the
+    // circuit breaker, may still be executed.
+    let reset: CosmosMsg = WasmMsg::Execute {
+        contract_addr: dao.to_string(),
+        msg: to_json_binary(&ExecuteMsg::ResetCircuitBreaker {}).unwrap(),
+        funds: vec![],
+    }
+    .into();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(module.as_str(), &[]),
+        ExecuteMsg::ExecuteProposalHook { msgs: vec![reset] },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(dao.as_str(), &[]),
+        ExecuteMsg::ResetCircuitBreaker {},
+    )
+    .unwrap();
 
-    let core_addr = app
-        .instantiate_contract(
-            v1_core_id,
-            Addr::unchecked(CREATOR_ADDR),
-            &v1_core_instantiate,
-            &[],
-            "cw-governance",
-            Some(CREATOR_ADDR.to_string()),
+    let response: CircuitBreakerResponse =
+        from_json_binary(&query(deps.as_ref(), env.clone(), QueryMsg::CircuitBreaker {}).unwrap())
+            .unwrap();
+    assert_eq!(response.tripped, None);
+    assert!(response.outflows.is_empty());
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(module.as_str(), &[]),
+        ExecuteMsg::ExecuteProposalHook {
+            msgs: vec![send(60)],
+        },
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_circuit_breaker_cw20() {
+    let mut deps = mock_dependencies();
+    let mut env = mock_env();
+    let dao = env.contract.address.clone();
+    let module = Addr::unchecked("proposal");
+    PROPOSAL_MODULES
+        .save(
+            deps.as_mut().storage,
+            module.clone(),
+            &ProposalModule {
+                address: module.clone(),
+                prefix: "A".to_string(),
+                status: ProposalModuleStatus::Enabled,
+            },
         )
         .unwrap();
 
-    app.execute(
-        Addr::unchecked(CREATOR_ADDR),
-        CosmosMsg::Wasm(WasmMsg::Migrate {
-            contract_addr: core_addr.to_string(),
-            new_code_id: core_id,
-            msg: to_json_binary(&MigrateMsg::FromV1 {
-                dao_uri: None,
-                params: None,
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(dao.as_str(), &[]),
+        ExecuteMsg::UpdateCircuitBreaker {
+            circuit_breaker: Some(CircuitBreaker {
+                window: Duration::Height(10),
+                outflow_limits: vec![],
+                cw20_outflow_limits: vec![cw20::Cw20Coin {
+                    address: "token".to_string(),
+                    amount: Uint128::new(100),
+                }],
+                max_executions_per_block: None,
+            }),
+        },
+    )
+    .unwrap();
+
+    let transfer = |token: &str, recipient: &str, amount: u128| -> CosmosMsg {
+        WasmMsg::Execute {
+            contract_addr: token.to_string(),
+            msg: to_json_binary(&cw20::Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount: Uint128::new(amount),
             })
             .unwrap(),
-        }),
+            funds: vec![],
+        }
+        .into()
+    };
+    let send = |amount: u128| -> CosmosMsg {
+        WasmMsg::Execute {
+            contract_addr: "token".to_string(),
+            msg: to_json_binary(&cw20::Cw20ExecuteMsg::Send {
+                contract: "ekez".to_string(),
+                amount: Uint128::new(amount),
+                msg: Binary::default(),
+            })
+            .unwrap(),
+            funds: vec![],
+        }
+        .into()
+    };
+
+    // Transfers of other tokens, and to the DAO itself, are not
+    // counted.
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(module.as_str(), &[]),
+        ExecuteMsg::ExecuteProposalHook {
+            msgs: vec![
+                transfer("other", "ekez", 1000),
+                transfer("token", dao.as_str(), 1000),
+                transfer("token", "ekez", 60),
+            ],
+        },
+    )
+    .unwrap();
+    assert!(res.events.is_empty());
+
+    // Draining the token over several proposals trips the circuit
+    // breaker.
+    env.block.height += 1;
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(module.as_str(), &[]),
+        ExecuteMsg::ExecuteProposalHook {
+            msgs: vec![send(60)],
+        },
     )
     .unwrap();
+    assert_eq!(res.events[0].ty, "circuit_breaker_tripped");
 
-    let new_state: DumpStateResponse = app
-        .wrap()
-        .query_wasm_smart(&core_addr, &QueryMsg::DumpState {})
-        .unwrap();
+    let response: CircuitBreakerResponse =
+        from_json_binary(&query(deps.as_ref(), env.clone(), QueryMsg::CircuitBreaker {}).unwrap())
+            .unwrap();
+    assert_eq!(
+        response.cw20_outflows,
+        vec![cw20::Cw20Coin {
+            address: "token".to_string(),
+            amount: Uint128::new(120),
+        }]
+    );
 
-    let proposal_modules = new_state.proposal_modules;
-    assert_eq!(2, proposal_modules.len());
-    for (idx, module) in proposal_modules.iter().enumerate() {
-        let prefix = derive_proposal_module_prefix(idx).unwrap();
-        assert_eq!(prefix, module.prefix);
-        assert_eq!(ProposalModuleStatus::Enabled, module.status);
-    }
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(module.as_str(), &[]),
+        ExecuteMsg::ExecuteProposalHook {
+            msgs: vec![transfer("token", "ekez", 1)],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::CircuitBreakerTripped {});
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(dao.as_str(), &[]),
+        ExecuteMsg::ResetCircuitBreaker {},
+    )
+    .unwrap();
+    let response: CircuitBreakerResponse =
+        from_json_binary(&query(deps.as_ref(), env, QueryMsg::CircuitBreaker {}).unwrap()).unwrap();
+    assert!(response.cw20_outflows.is_empty());
+}
 
-    // Check that we may not migrate more than once.
-    let err: ContractError = app
-        .execute(
-            Addr::unchecked(CREATOR_ADDR),
-            CosmosMsg::Wasm(WasmMsg::Migrate {
-                contract_addr: core_addr.to_string(),
-                new_code_id: core_id,
-                msg: to_json_binary(&MigrateMsg::FromV1 {
-                    dao_uri: None,
-                    params: None,
-                })
-                .unwrap(),
-            }),
+#[test]
+fn test_circuit_breaker_all_outflows() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let dao = env.contract.address.clone();
+    let module = Addr::unchecked("proposal");
+    let admin = Addr::unchecked("admin");
+    PROPOSAL_MODULES
+        .save(
+            deps.as_mut().storage,
+            module.clone(),
+            &ProposalModule {
+                address: module.clone(),
+                prefix: "A".to_string(),
+                status: ProposalModuleStatus::Enabled,
+            },
         )
-        .unwrap_err()
-        .downcast()
         .unwrap();
-    assert_eq!(err, ContractError::AlreadyMigrated {})
+    ADMIN.save(deps.as_mut().storage, &admin).unwrap();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(dao.as_str(), &[]),
+        ExecuteMsg::UpdateCircuitBreaker {
+            circuit_breaker: Some(CircuitBreaker {
+                window: Duration::Height(10),
+                outflow_limits: vec![coin(100, "ujuno")],
+                cw20_outflow_limits: vec![],
+                max_executions_per_block: None,
+            }),
+        },
+    )
+    .unwrap();
+
+    let ibc_transfer: CosmosMsg = IbcMsg::Transfer {
+        channel_id: "channel-0".to_string(),
+        to_address: "cosmos1ekez".to_string(),
+        amount: coin(60, "ujuno"),
+        timeout: IbcTimeout::with_timestamp(env.block.time.plus_seconds(60)),
+    }
+    .into();
+    let delegate: CosmosMsg = StakingMsg::Delegate {
+        validator: "validator".to_string(),
+        amount: coin(60, "ujuno"),
+    }
+    .into();
+    let instantiate2: CosmosMsg = WasmMsg::Instantiate2 {
+        admin: None,
+        code_id: 1,
+        label: "label".to_string(),
+        msg: to_json_binary(&Empty {}).unwrap(),
+        funds: coins(1, "ujuno"),
+        salt: Binary::from(b"salt".to_vec()),
+    }
+    .into();
+    let stargate = CosmosMsg::Stargate {
+        type_url: "/cosmos.bank.v1beta1.MsgSend".to_string(),
+        value: Binary::default(),
+    };
+
+    // Stargate messages can not be metered, so they are rejected
+    // while outflows are limited.
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(module.as_str(), &[]),
+        ExecuteMsg::ExecuteProposalHook {
+            msgs: vec![stargate.clone()],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::UnmeteredOutflow { index: 0 });
+
+    // IBC transfers and delegations are outflows, and admin messages
+    // are checked like proposals.
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(module.as_str(), &[]),
+        ExecuteMsg::ExecuteProposalHook {
+            msgs: vec![ibc_transfer.clone()],
+        },
+    )
+    .unwrap();
+    assert!(res.events.is_empty());
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(admin.as_str(), &[]),
+        ExecuteMsg::ExecuteAdminMsgs {
+            msgs: vec![delegate],
+        },
+    )
+    .unwrap();
+    assert_eq!(res.events[0].ty, "circuit_breaker_tripped");
+
+    for msgs in [vec![instantiate2], vec![stargate]] {
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(module.as_str(), &[]),
+            ExecuteMsg::ExecuteProposalHook { msgs },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::CircuitBreakerTripped {});
+    }
+    let err = execute(
+        deps.as_mut(),
+        env,
+        mock_info(admin.as_str(), &[]),
+        ExecuteMsg::ExecuteAdminMsgs {
+            msgs: vec![ibc_transfer],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::CircuitBreakerTripped {});
 }
 
 #[test]
-fn test_migrate_mock() {
+fn test_treasury_tiers() {
     let mut deps = mock_dependencies();
-    let dao_uri: String = "/dao/uri".to_string();
-    let msg = MigrateMsg::FromV1 {
-        dao_uri: Some(dao_uri.clone()),
-        params: None,
+    let mut env = mock_env();
+    let dao = env.contract.address.clone();
+    let module = Addr::unchecked("proposal");
+    let council = Addr::unchecked("council");
+    for (addr, prefix) in [(&module, "A"), (&council, "B")] {
+        PROPOSAL_MODULES
+            .save(
+                deps.as_mut().storage,
+                addr.clone(),
+                &ProposalModule {
+                    address: addr.clone(),
+                    prefix: prefix.to_string(),
+                    status: ProposalModuleStatus::Enabled,
+                },
+            )
+            .unwrap();
+    }
+
+    let tiers = TreasuryTiers {
+        hot_budget: vec![coin(100, "ujuno")],
+        hot_cw20_budget: vec![cw20::Cw20Coin {
+            address: "token".to_string(),
+            amount: Uint128::new(50),
+        }],
+        period: Duration::Height(10),
+        cold_delay: Duration::Height(5),
+        cold_module: Some(council.clone()),
+    };
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("ekez", &[]),
+        ExecuteMsg::UpdateTreasuryTiers {
+            tiers: Some(tiers.clone()),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(dao.as_str(), &[]),
+        ExecuteMsg::UpdateTreasuryTiers {
+            tiers: Some(TreasuryTiers {
+                cold_module: Some(Addr::unchecked("ekez")),
+                ..tiers.clone()
+            }),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidTreasuryTiers {});
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(dao.as_str(), &[]),
+        ExecuteMsg::UpdateTreasuryTiers {
+            tiers: Some(tiers.clone()),
+        },
+    )
+    .unwrap();
+
+    let send = |amount: u128, denom: &str| -> CosmosMsg {
+        BankMsg::Send {
+            to_address: "ekez".to_string(),
+            amount: coins(amount, denom),
+        }
+        .into()
+    };
+    let to_dao = |msg: &ExecuteMsg| -> CosmosMsg {
+        WasmMsg::Execute {
+            contract_addr: dao.to_string(),
+            msg: to_json_binary(msg).unwrap(),
+            funds: vec![],
+        }
+        .into()
+    };
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(module.as_str(), &[]),
+        ExecuteMsg::ExecuteProposalHook {
+            msgs: vec![send(60, "ujuno")],
+        },
+    )
+    .unwrap();
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(module.as_str(), &[]),
+        ExecuteMsg::ExecuteProposalHook {
+            msgs: vec![send(50, "ujuno")],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::HotBudgetExceeded {
+            denom: "ujuno".to_string()
+        }
+    );
+    // Denoms without a hot budget may only be spent cold.
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(module.as_str(), &[]),
+        ExecuteMsg::ExecuteProposalHook {
+            msgs: vec![send(1, "uatom")],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::HotBudgetExceeded {
+            denom: "uatom".to_string()
+        }
+    );
+
+    // Cw20 transfers and vault deposits count against the hot budget.
+    let transfer = |amount: u128| -> CosmosMsg {
+        WasmMsg::Execute {
+            contract_addr: "token".to_string(),
+            msg: to_json_binary(&cw20::Cw20ExecuteMsg::Transfer {
+                recipient: "ekez".to_string(),
+                amount: Uint128::new(amount),
+            })
+            .unwrap(),
+            funds: vec![],
+        }
+        .into()
     };
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(module.as_str(), &[]),
+        ExecuteMsg::ExecuteProposalHook {
+            msgs: vec![transfer(40)],
+        },
+    )
+    .unwrap();
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(module.as_str(), &[]),
+        ExecuteMsg::ExecuteProposalHook {
+            msgs: vec![transfer(20)],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::HotBudgetExceeded {
+            denom: "token".to_string()
+        }
+    );
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(dao.as_str(), &[]),
+        ExecuteMsg::UpdateVaults {
+            to_add: vec![VaultRegistration {
+                address: "vault".to_string(),
+                denom: "ujuno".to_string(),
+                cap: Uint128::new(1000),
+            }],
+            to_remove: vec![],
+        },
+    )
+    .unwrap();
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(module.as_str(), &[]),
+        ExecuteMsg::ExecuteProposalHook {
+            msgs: vec![to_dao(&ExecuteMsg::DepositToVault {
+                vault: "vault".to_string(),
+                amount: Uint128::new(50),
+            })],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::HotBudgetExceeded {
+            denom: "ujuno".to_string()
+        }
+    );
+
+    // Hot proposals may not register vaults or propose merges.
+    for msg in [
+        ExecuteMsg::UpdateVaults {
+            to_add: vec![],
+            to_remove: vec![],
+        },
+        ExecuteMsg::ProposeMerge {
+            counterparty: "other_dao".to_string(),
+            role: MergeRole::Absorbed,
+        },
+    ] {
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(module.as_str(), &[]),
+            ExecuteMsg::ExecuteProposalHook {
+                msgs: vec![to_dao(&msg)],
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::ColdSpendRequired {});
+    }
+
+    // The cold module's proposals are not bound by the hot budget.
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(council.as_str(), &[]),
+        ExecuteMsg::ExecuteProposalHook {
+            msgs: vec![send(500, "ujuno"), transfer(500)],
+        },
+    )
+    .unwrap();
+
+    let response: TreasuryTiersResponse =
+        from_json_binary(&query(deps.as_ref(), env.clone(), QueryMsg::TreasuryTiers {}).unwrap())
+            .unwrap();
+    assert_eq!(
+        response,
+        TreasuryTiersResponse {
+            tiers: Some(tiers.clone()),
+            hot_spent: coins(60, "ujuno"),
+            hot_cw20_spent: vec![cw20::Cw20Coin {
+                address: "token".to_string(),
+                amount: Uint128::new(40),
+            }],
+            period_ends: Some(Expiration::AtHeight(env.block.height + 10)),
+        }
+    );
+
+    // Proposals may not loosen the tiers.
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(module.as_str(), &[]),
+        ExecuteMsg::ExecuteProposalHook {
+            msgs: vec![to_dao(&ExecuteMsg::UpdateTreasuryTiers { tiers: None })],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::ColdSpendRequired {});
+
+    let requested_at = env.block.height;
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(dao.as_str(), &[]),
+        ExecuteMsg::RequestColdSpend {
+            msgs: vec![send(500, "ujuno")],
+        },
+    )
+    .unwrap();
+    let spends: Vec<ColdSpendResponse> = from_json_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::ColdSpends {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(spends.len(), 1);
+    assert_eq!(spends[0].id, 1);
+    assert_eq!(
+        spends[0].spend.confirmable_at,
+        Expiration::AtHeight(requested_at + 5)
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(dao.as_str(), &[]),
+        ExecuteMsg::ConfirmColdSpend { id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::ColdSpendNotReady {
+            id: 1,
+            confirmable_at: Expiration::AtHeight(requested_at + 5)
+        }
+    );
+
+    env.block.height += 5;
+    // Only the cold module's proposals may confirm cold spends.
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(module.as_str(), &[]),
+        ExecuteMsg::ExecuteProposalHook {
+            msgs: vec![to_dao(&ExecuteMsg::ConfirmColdSpend { id: 1 })],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(council.as_str(), &[]),
+        ExecuteMsg::ExecuteProposalHook {
+            msgs: vec![to_dao(&ExecuteMsg::ConfirmColdSpend { id: 1 })],
+        },
+    )
+    .unwrap();
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(dao.as_str(), &[]),
+        ExecuteMsg::ConfirmColdSpend { id: 1 },
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![SubMsg::new(send(500, "ujuno"))]);
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(dao.as_str(), &[]),
+        ExecuteMsg::ConfirmColdSpend { id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NoSuchColdSpend { id: 1 });
+
+    // The hot budget is restored once the period ends.
+    env.block.height += 5;
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(module.as_str(), &[]),
+        ExecuteMsg::ExecuteProposalHook {
+            msgs: vec![send(100, "ujuno")],
+        },
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_hot_budget_counts_all_outflows() {
+    let mut deps = mock_dependencies();
     let env = mock_env();
+    let dao = env.contract.address.clone();
+    let module = Addr::unchecked("proposal");
+    PROPOSAL_MODULES
+        .save(
+            deps.as_mut().storage,
+            module.clone(),
+            &ProposalModule {
+                address: module.clone(),
+                prefix: "A".to_string(),
+                status: ProposalModuleStatus::Enabled,
+            },
+        )
+        .unwrap();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(dao.as_str(), &[]),
+        ExecuteMsg::UpdateTreasuryTiers {
+            tiers: Some(TreasuryTiers {
+                hot_budget: vec![coin(100, "ujuno")],
+                hot_cw20_budget: vec![],
+                period: Duration::Height(10),
+                cold_delay: Duration::Height(5),
+                cold_module: None,
+            }),
+        },
+    )
+    .unwrap();
 
-    // Set starting version to v1.
-    set_contract_version(&mut deps.storage, CONTRACT_NAME, "0.1.0").unwrap();
+    // IBC transfers, delegations, and instantiations with funds all
+    // count against the hot budget.
+    let ibc_transfer: CosmosMsg = IbcMsg::Transfer {
+        channel_id: "channel-0".to_string(),
+        to_address: "cosmos1ekez".to_string(),
+        amount: coin(40, "ujuno"),
+        timeout: IbcTimeout::with_timestamp(env.block.time.plus_seconds(60)),
+    }
+    .into();
+    let delegate: CosmosMsg = StakingMsg::Delegate {
+        validator: "validator".to_string(),
+        amount: coin(40, "ujuno"),
+    }
+    .into();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(module.as_str(), &[]),
+        ExecuteMsg::ExecuteProposalHook {
+            msgs: vec![ibc_transfer, delegate],
+        },
+    )
+    .unwrap();
 
-    // Write to storage in old proposal module format
-    let proposal_modules_key = Addr::unchecked("addr");
-    let old_map: Map<Addr, Empty> = Map::new("proposal_modules");
-    let path = old_map.key(proposal_modules_key.clone());
-    deps.storage.set(&path, &to_json_binary(&Empty {}).unwrap());
+    let instantiate2: CosmosMsg = WasmMsg::Instantiate2 {
+        admin: None,
+        code_id: 1,
+        label: "label".to_string(),
+        msg: to_json_binary(&Empty {}).unwrap(),
+        funds: coins(40, "ujuno"),
+        salt: Binary::from(b"salt".to_vec()),
+    }
+    .into();
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(module.as_str(), &[]),
+        ExecuteMsg::ExecuteProposalHook {
+            msgs: vec![instantiate2],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::HotBudgetExceeded {
+            denom: "ujuno".to_string()
+        }
+    );
 
-    // Write to storage in old config format
-    #[cw_serde]
-    struct V1Config {
-        pub name: String,
-        pub description: String,
-        pub image_url: Option<String>,
-        pub automatically_add_cw20s: bool,
-        pub automatically_add_cw721s: bool,
+    // Stargate messages can not be metered, so hot modules may not
+    // send them.
+    let stargate = CosmosMsg::Stargate {
+        type_url: "/cosmos.bank.v1beta1.MsgSend".to_string(),
+        value: Binary::default(),
+    };
+    let err = execute(
+        deps.as_mut(),
+        env,
+        mock_info(module.as_str(), &[]),
+        ExecuteMsg::ExecuteProposalHook {
+            msgs: vec![stargate],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::UnmeteredOutflow { index: 0 });
+}
+
+#[test]
+fn test_global_proposal_index() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+
+    for (addr, prefix) in [("module_a", "A"), ("module_b", "B")] {
+        PROPOSAL_MODULES
+            .save(
+                deps.as_mut().storage,
+                Addr::unchecked(addr),
+                &ProposalModule {
+                    address: Addr::unchecked(addr),
+                    prefix: prefix.to_string(),
+                    status: ProposalModuleStatus::Enabled,
+                },
+            )
+            .unwrap();
     }
 
-    let v1_config = V1Config {
-        name: "core dao".to_string(),
-        description: "a dao".to_string(),
-        image_url: None,
-        automatically_add_cw20s: false,
-        automatically_add_cw721s: false,
+    let new_proposal = |id: u64| {
+        ExecuteMsg::ProposalHook(ProposalHookMsg::NewProposal {
+            id,
+            proposer: "ekez".to_string(),
+        })
     };
 
-    let config_item: Item<V1Config> = Item::new("config");
-    config_item.save(&mut deps.storage, &v1_config).unwrap();
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("ekez", &[]),
+        new_proposal(1),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    for (module, id) in [("module_a", 1), ("module_b", 1), ("module_a", 2)] {
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(module, &[]),
+            new_proposal(id),
+        )
+        .unwrap();
+    }
+    // Indexing is idempotent.
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("module_b", &[]),
+        new_proposal(1),
+    )
+    .unwrap();
+
+    let res: GlobalProposalResponse = from_json_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::ProposalGlobal {
+                module_prefix: "B".to_string(),
+                id: 1,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res,
+        GlobalProposalResponse {
+            global_id: 2,
+            module: Addr::unchecked("module_b"),
+            module_prefix: "B".to_string(),
+            id: 1,
+        }
+    );
+
+    let res: GlobalProposalResponse = from_json_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::ProposalByGlobalId { global_id: 3 },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.module, Addr::unchecked("module_a"));
+    assert_eq!(res.id, 2);
+
+    query(
+        deps.as_ref(),
+        env,
+        QueryMsg::ProposalByGlobalId { global_id: 4 },
+    )
+    .unwrap_err();
+}
+
+#[test]
+fn test_cw721_receive() {
+    let (gov_addr, mut app) = do_standard_instantiate(true, None);
+
+    let cw721_id = app.store_code(cw721_contract());
+
+    let cw721_addr = app
+        .instantiate_contract(
+            cw721_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &cw721_base::msg::InstantiateMsg {
+                name: "ekez".to_string(),
+                symbol: "ekez".to_string(),
+                minter: CREATOR_ADDR.to_string(),
+            },
+            &[],
+            "cw721",
+            None,
+        )
+        .unwrap();
+
+    let another_cw721 = app
+        .instantiate_contract(
+            cw721_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &cw721_base::msg::InstantiateMsg {
+                name: "ekez".to_string(),
+                symbol: "ekez".to_string(),
+                minter: CREATOR_ADDR.to_string(),
+            },
+            &[],
+            "cw721",
+            None,
+        )
+        .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        cw721_addr.clone(),
+        &cw721_base::msg::ExecuteMsg::<Option<Empty>, Empty>::Mint {
+            token_id: "ekez".to_string(),
+            owner: CREATOR_ADDR.to_string(),
+            token_uri: None,
+            extension: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        cw721_addr.clone(),
+        &cw721_base::msg::ExecuteMsg::<Option<Empty>, Empty>::SendNft {
+            contract: gov_addr.to_string(),
+            token_id: "ekez".to_string(),
+            msg: to_json_binary("").unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let cw721_list: Vec<Addr> = app
+        .wrap()
+        .query_wasm_smart(
+            gov_addr.clone(),
+            &QueryMsg::Cw721TokenList {
+                start_after: None,
+                limit: None,
+                descending: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(cw721_list, vec![cw721_addr.clone()]);
+
+    // Try to add an invalid cw721.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(gov_addr.clone()),
+            gov_addr.clone(),
+            &ExecuteMsg::UpdateCw721List {
+                to_add: vec!["new".to_string(), cw721_addr.to_string()],
+                to_remove: vec![cw721_addr.to_string()],
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Std(_)));
+
+    // Test that non-DAO can not update the list.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            gov_addr.clone(),
+            &ExecuteMsg::UpdateCw721List {
+                to_add: vec![],
+                to_remove: vec![cw721_addr.to_string()],
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    // Add a real cw721.
+    app.execute_contract(
+        Addr::unchecked(gov_addr.clone()),
+        gov_addr.clone(),
+        &ExecuteMsg::UpdateCw721List {
+            to_add: vec![another_cw721.to_string(), cw721_addr.to_string()],
+            to_remove: vec![cw721_addr.to_string()],
+        },
+        &[],
+    )
+    .unwrap();
+
+    let cw20_list: Vec<Addr> = app
+        .wrap()
+        .query_wasm_smart(
+            gov_addr,
+            &QueryMsg::Cw721TokenList {
+                start_after: None,
+                limit: None,
+                descending: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(cw20_list, vec![another_cw721]);
+}
+
+#[test]
+fn test_cw721_receive_no_auto_add() {
+    let (gov_addr, mut app) = do_standard_instantiate(false, None);
+
+    let cw721_id = app.store_code(cw721_contract());
+
+    let cw721_addr = app
+        .instantiate_contract(
+            cw721_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &cw721_base::msg::InstantiateMsg {
+                name: "ekez".to_string(),
+                symbol: "ekez".to_string(),
+                minter: CREATOR_ADDR.to_string(),
+            },
+            &[],
+            "cw721",
+            None,
+        )
+        .unwrap();
+
+    let another_cw721 = app
+        .instantiate_contract(
+            cw721_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &cw721_base::msg::InstantiateMsg {
+                name: "ekez".to_string(),
+                symbol: "ekez".to_string(),
+                minter: CREATOR_ADDR.to_string(),
+            },
+            &[],
+            "cw721",
+            None,
+        )
+        .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        cw721_addr.clone(),
+        &cw721_base::msg::ExecuteMsg::<Option<Empty>, Empty>::Mint {
+            token_id: "ekez".to_string(),
+            owner: CREATOR_ADDR.to_string(),
+            token_uri: None,
+            extension: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        cw721_addr.clone(),
+        &cw721_base::msg::ExecuteMsg::<Option<Empty>, Empty>::SendNft {
+            contract: gov_addr.to_string(),
+            token_id: "ekez".to_string(),
+            msg: to_json_binary("").unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let cw721_list: Vec<Addr> = app
+        .wrap()
+        .query_wasm_smart(
+            gov_addr.clone(),
+            &QueryMsg::Cw721TokenList {
+                start_after: None,
+                limit: None,
+                descending: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(cw721_list, Vec::<Addr>::new());
+
+    // Duplicates OK. Just adds one.
+    app.execute_contract(
+        Addr::unchecked(gov_addr.clone()),
+        gov_addr.clone(),
+        &ExecuteMsg::UpdateCw721List {
+            to_add: vec![
+                another_cw721.to_string(),
+                cw721_addr.to_string(),
+                cw721_addr.to_string(),
+            ],
+            to_remove: vec![],
+        },
+        &[],
+    )
+    .unwrap();
+
+    let cw20_list: Vec<Addr> = app
+        .wrap()
+        .query_wasm_smart(
+            gov_addr,
+            &QueryMsg::Cw721TokenList {
+                start_after: None,
+                limit: None,
+                descending: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(cw20_list, vec![another_cw721, cw721_addr]);
+}
+
+#[test]
+fn test_pause() {
+    let (core_addr, mut app) = do_standard_instantiate(false, None);
+
+    let start_height = app.block_info().height;
+
+    let proposal_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr.clone(),
+            &QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+                descending: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(proposal_modules.len(), 1);
+    let proposal_module = proposal_modules.into_iter().next().unwrap();
+
+    let paused: PauseInfoResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr.clone(), &QueryMsg::PauseInfo {})
+        .unwrap();
+    assert_eq!(paused, PauseInfoResponse::Unpaused {});
+    let all_state: DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr.clone(), &QueryMsg::DumpState {})
+        .unwrap();
+    assert_eq!(all_state.pause_info, PauseInfoResponse::Unpaused {});
+
+    // DAO is not paused. Check that we can execute things.
+    //
+    // Tests intentionally use the core address to send these
+    // messsages to simulate a worst case scenerio where the core
+    // contract has a vulnerability.
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::UpdateConfig {
+            config: Config {
+                dao_uri: None,
+                name: "The Empire Strikes Back".to_string(),
+                description: "haha lol we have pwned your DAO".to_string(),
+                image_url: None,
+                automatically_add_cw20s: true,
+                automatically_add_cw721s: true,
+            },
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Oh no the DAO is under attack! Quick! Pause the DAO while we
+    // figure out what to do!
+    let err: ContractError = app
+        .execute_contract(
+            proposal_module.address.clone(),
+            core_addr.clone(),
+            &ExecuteMsg::Pause {
+                duration: Duration::Height(10),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+
+    // Only the DAO may call this on itself. Proposal modules must use
+    // the execute hook.
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    app.execute_contract(
+        proposal_module.address.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::ExecuteProposalHook {
+            msgs: vec![WasmMsg::Execute {
+                contract_addr: core_addr.to_string(),
+                msg: to_json_binary(&ExecuteMsg::Pause {
+                    duration: Duration::Height(10),
+                })
+                .unwrap(),
+                funds: vec![],
+            }
+            .into()],
+        },
+        &[],
+    )
+    .unwrap();
+
+    let paused: PauseInfoResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr.clone(), &QueryMsg::PauseInfo {})
+        .unwrap();
+    assert_eq!(
+        paused,
+        PauseInfoResponse::Paused {
+            expiration: Expiration::AtHeight(start_height + 10)
+        }
+    );
+    let all_state: DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr.clone(), &QueryMsg::DumpState {})
+        .unwrap();
+    assert_eq!(
+        all_state.pause_info,
+        PauseInfoResponse::Paused {
+            expiration: Expiration::AtHeight(start_height + 10)
+        }
+    );
+
+    let err: ContractError = app
+        .execute_contract(
+            core_addr.clone(),
+            core_addr.clone(),
+            &ExecuteMsg::UpdateConfig {
+                config: Config {
+                    dao_uri: None,
+                    name: "The Empire Strikes Back Again".to_string(),
+                    description: "haha lol we have pwned your DAO again".to_string(),
+                    image_url: None,
+                    automatically_add_cw20s: true,
+                    automatically_add_cw721s: true,
+                },
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+
+    assert!(matches!(err, ContractError::Paused { .. }));
+
+    let err: ContractError = app
+        .execute_contract(
+            proposal_module.address.clone(),
+            core_addr.clone(),
+            &ExecuteMsg::ExecuteProposalHook {
+                msgs: vec![WasmMsg::Execute {
+                    contract_addr: core_addr.to_string(),
+                    msg: to_json_binary(&ExecuteMsg::Pause {
+                        duration: Duration::Height(10),
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                }
+                .into()],
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+
+    assert!(matches!(err, ContractError::Paused { .. }));
+
+    app.update_block(|block| block.height += 9);
+
+    // Still not unpaused.
+    let err: ContractError = app
+        .execute_contract(
+            proposal_module.address.clone(),
+            core_addr.clone(),
+            &ExecuteMsg::ExecuteProposalHook {
+                msgs: vec![WasmMsg::Execute {
+                    contract_addr: core_addr.to_string(),
+                    msg: to_json_binary(&ExecuteMsg::Pause {
+                        duration: Duration::Height(10),
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                }
+                .into()],
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+
+    assert!(matches!(err, ContractError::Paused { .. }));
+
+    app.update_block(|block| block.height += 1);
+
+    let paused: PauseInfoResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr.clone(), &QueryMsg::PauseInfo {})
+        .unwrap();
+    assert_eq!(paused, PauseInfoResponse::Unpaused {});
+    let all_state: DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr.clone(), &QueryMsg::DumpState {})
+        .unwrap();
+    assert_eq!(all_state.pause_info, PauseInfoResponse::Unpaused {});
+
+    // Now its unpaused so we should be able to pause again.
+    app.execute_contract(
+        proposal_module.address,
+        core_addr.clone(),
+        &ExecuteMsg::ExecuteProposalHook {
+            msgs: vec![WasmMsg::Execute {
+                contract_addr: core_addr.to_string(),
+                msg: to_json_binary(&ExecuteMsg::Pause {
+                    duration: Duration::Height(10),
+                })
+                .unwrap(),
+                funds: vec![],
+            }
+            .into()],
+        },
+        &[],
+    )
+    .unwrap();
+
+    let paused: PauseInfoResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr.clone(), &QueryMsg::PauseInfo {})
+        .unwrap();
+    assert_eq!(
+        paused,
+        PauseInfoResponse::Paused {
+            expiration: Expiration::AtHeight(start_height + 20)
+        }
+    );
+    let all_state: DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr, &QueryMsg::DumpState {})
+        .unwrap();
+    assert_eq!(
+        all_state.pause_info,
+        PauseInfoResponse::Paused {
+            expiration: Expiration::AtHeight(start_height + 20)
+        }
+    );
+}
+
+#[test]
+fn test_dump_state_proposal_modules() {
+    let (core_addr, app) = do_standard_instantiate(false, None);
+    let proposal_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr.clone(),
+            &QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+                descending: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(proposal_modules.len(), 1);
+    let proposal_module = proposal_modules.into_iter().next().unwrap();
+
+    let all_state: DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr, &QueryMsg::DumpState {})
+        .unwrap();
+    assert_eq!(all_state.pause_info, PauseInfoResponse::Unpaused {});
+    assert_eq!(all_state.proposal_modules.len(), 1);
+    assert_eq!(all_state.proposal_modules[0], proposal_module);
+}
+
+// Note that this isn't actually testing that we are migrating from the previous version since
+// with multitest contract instantiation we can't manipulate storage to the previous version of state before invoking migrate. So if anything,
+// this just tests the idempotency of migrate.
+#[test]
+fn test_migrate_from_compatible() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(sudo_proposal_contract());
+    let voting_id = app.store_code(cw20_balances_voting());
+    let gov_id = app.store_code(cw_core_contract());
+    let cw20_id = app.store_code(cw20_contract());
+
+    let govmod_instantiate = dao_proposal_sudo::msg::InstantiateMsg {
+        root: CREATOR_ADDR.to_string(),
+    };
+    let voting_instantiate = dao_voting_cw20_balance::msg::InstantiateMsg {
+        token_info: dao_voting_cw20_balance::msg::TokenInfo::New {
+            code_id: cw20_id,
+            label: "DAO DAO voting".to_string(),
+            name: "DAO DAO".to_string(),
+            symbol: "DAO".to_string(),
+            decimals: 6,
+            initial_balances: vec![cw20::Cw20Coin {
+                address: CREATOR_ADDR.to_string(),
+                amount: Uint128::from(2u64),
+            }],
+            marketing: None,
+        },
+    };
+
+    // Instantiate the core module with an admin to do migrations.
+    let gov_instantiate = InstantiateMsg {
+        dao_uri: None,
+        admin: None,
+        name: "DAO DAO".to_string(),
+        description: "A DAO that builds DAOs.".to_string(),
+        image_url: None,
+        automatically_add_cw20s: false,
+        automatically_add_cw721s: false,
+        voting_module_instantiate_info: ModuleInstantiateInfo {
+            code_id: voting_id,
+            msg: to_json_binary(&voting_instantiate).unwrap(),
+            admin: Some(Admin::CoreModule {}),
+            funds: vec![],
+            label: "voting module".to_string(),
+        },
+        proposal_modules_instantiate_info: vec![ModuleInstantiateInfo {
+            code_id: govmod_id,
+            msg: to_json_binary(&govmod_instantiate).unwrap(),
+            admin: Some(Admin::CoreModule {}),
+            funds: vec![],
+            label: "governance module".to_string(),
+        }],
+        initial_items: None,
+    };
+
+    let core_addr = app
+        .instantiate_contract(
+            gov_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &gov_instantiate,
+            &[],
+            "cw-governance",
+            Some(CREATOR_ADDR.to_string()),
+        )
+        .unwrap();
+
+    let state: DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr.clone(), &QueryMsg::DumpState {})
+        .unwrap();
+
+    app.execute(
+        Addr::unchecked(CREATOR_ADDR),
+        CosmosMsg::Wasm(WasmMsg::Migrate {
+            contract_addr: core_addr.to_string(),
+            new_code_id: gov_id,
+            msg: to_json_binary(&MigrateMsg::FromCompatible {}).unwrap(),
+        }),
+    )
+    .unwrap();
+
+    let new_state: DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr, &QueryMsg::DumpState {})
+        .unwrap();
+
+    assert_eq!(new_state, state);
+}
+
+#[test]
+fn test_migrate_from_beta() {
+    use cw_core_v1 as v1;
+
+    let mut app = App::default();
+    let govmod_id = app.store_code(sudo_proposal_contract());
+    let voting_id = app.store_code(cw20_balances_voting());
+    let core_id = app.store_code(cw_core_contract());
+    let v1_core_id = app.store_code(v1_cw_core_contract());
+    let cw20_id = app.store_code(cw20_contract());
+
+    let proposal_instantiate = dao_proposal_sudo::msg::InstantiateMsg {
+        root: CREATOR_ADDR.to_string(),
+    };
+    let voting_instantiate = dao_voting_cw20_balance::msg::InstantiateMsg {
+        token_info: dao_voting_cw20_balance::msg::TokenInfo::New {
+            code_id: cw20_id,
+            label: "DAO DAO voting".to_string(),
+            name: "DAO DAO".to_string(),
+            symbol: "DAO".to_string(),
+            decimals: 6,
+            initial_balances: vec![cw20::Cw20Coin {
+                address: CREATOR_ADDR.to_string(),
+                amount: Uint128::from(2u64),
+            }],
+            marketing: None,
+        },
+    };
+
+    // Instantiate the core module with an admin to do migrations.
+    let v1_core_instantiate = v1::msg::InstantiateMsg {
+        admin: None,
+        name: "DAO DAO".to_string(),
+        description: "A DAO that builds DAOs.".to_string(),
+        image_url: None,
+        automatically_add_cw20s: false,
+        automatically_add_cw721s: false,
+        voting_module_instantiate_info: v1::msg::ModuleInstantiateInfo {
+            code_id: voting_id,
+            msg: to_json_binary(&voting_instantiate).unwrap(),
+            admin: v1::msg::Admin::CoreContract {},
+            label: "voting module".to_string(),
+        },
+        proposal_modules_instantiate_info: vec![
+            v1::msg::ModuleInstantiateInfo {
+                code_id: govmod_id,
+                msg: to_json_binary(&proposal_instantiate).unwrap(),
+                admin: v1::msg::Admin::CoreContract {},
+                label: "governance module 1".to_string(),
+            },
+            v1::msg::ModuleInstantiateInfo {
+                code_id: govmod_id,
+                msg: to_json_binary(&proposal_instantiate).unwrap(),
+                admin: v1::msg::Admin::CoreContract {},
+                label: "governance module 2".to_string(),
+            },
+        ],
+        initial_items: None,
+    };
+
+    let core_addr = app
+        .instantiate_contract(
+            v1_core_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &v1_core_instantiate,
+            &[],
+            "cw-governance",
+            Some(CREATOR_ADDR.to_string()),
+        )
+        .unwrap();
+
+    app.execute(
+        Addr::unchecked(CREATOR_ADDR),
+        CosmosMsg::Wasm(WasmMsg::Migrate {
+            contract_addr: core_addr.to_string(),
+            new_code_id: core_id,
+            msg: to_json_binary(&MigrateMsg::FromV1 {
+                dao_uri: None,
+                params: None,
+            })
+            .unwrap(),
+        }),
+    )
+    .unwrap();
+
+    let new_state: DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(&core_addr, &QueryMsg::DumpState {})
+        .unwrap();
+
+    let proposal_modules = new_state.proposal_modules;
+    assert_eq!(2, proposal_modules.len());
+    for (idx, module) in proposal_modules.iter().enumerate() {
+        let prefix = derive_proposal_module_prefix(idx).unwrap();
+        assert_eq!(prefix, module.prefix);
+        assert_eq!(ProposalModuleStatus::Enabled, module.status);
+    }
+
+    // Check that we may not migrate more than once.
+    let err: ContractError = app
+        .execute(
+            Addr::unchecked(CREATOR_ADDR),
+            CosmosMsg::Wasm(WasmMsg::Migrate {
+                contract_addr: core_addr.to_string(),
+                new_code_id: core_id,
+                msg: to_json_binary(&MigrateMsg::FromV1 {
+                    dao_uri: None,
+                    params: None,
+                })
+                .unwrap(),
+            }),
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::AlreadyMigrated {})
+}
+
+#[test]
+fn test_migrate_mock() {
+    let mut deps = mock_dependencies();
+    let dao_uri: String = "/dao/uri".to_string();
+    let msg = MigrateMsg::FromV1 {
+        dao_uri: Some(dao_uri.clone()),
+        params: None,
+    };
+    let env = mock_env();
+
+    // Set starting version to v1.
+    set_contract_version(&mut deps.storage, CONTRACT_NAME, "0.1.0").unwrap();
+
+    // Write to storage in old proposal module format
+    let proposal_modules_key = Addr::unchecked("addr");
+    let old_map: Map<Addr, Empty> = Map::new("proposal_modules");
+    let path = old_map.key(proposal_modules_key.clone());
+    deps.storage.set(&path, &to_json_binary(&Empty {}).unwrap());
+
+    // Write to storage in old config format
+    #[cw_serde]
+    struct V1Config {
+        pub name: String,
+        pub description: String,
+        pub image_url: Option<String>,
+        pub automatically_add_cw20s: bool,
+        pub automatically_add_cw721s: bool,
+    }
+
+    let v1_config = V1Config {
+        name: "core dao".to_string(),
+        description: "a dao".to_string(),
+        image_url: None,
+        automatically_add_cw20s: false,
+        automatically_add_cw721s: false,
+    };
+
+    let config_item: Item<V1Config> = Item::new("config");
+    config_item.save(&mut deps.storage, &v1_config).unwrap();
+
+    // Migrate to v2
+    migrate(deps.as_mut(), env, msg).unwrap();
+
+    let new_path = PROPOSAL_MODULES.key(proposal_modules_key);
+    let prop_module_bytes = deps.storage.get(&new_path).unwrap();
+    let module: ProposalModule = from_slice(&prop_module_bytes).unwrap();
+    assert_eq!(module.address, Addr::unchecked("addr"));
+    assert_eq!(module.prefix, derive_proposal_module_prefix(0).unwrap());
+    assert_eq!(module.status, ProposalModuleStatus::Enabled {});
+
+    let v2_config_item: Item<Config> = Item::new("config_v2");
+    let v2_config = v2_config_item.load(&deps.storage).unwrap();
+    assert_eq!(v2_config.dao_uri, Some(dao_uri));
+    assert_eq!(v2_config.name, v1_config.name);
+    assert_eq!(v2_config.description, v1_config.description);
+    assert_eq!(v2_config.image_url, v1_config.image_url);
+    assert_eq!(
+        v2_config.automatically_add_cw20s,
+        v1_config.automatically_add_cw20s
+    );
+    assert_eq!(
+        v2_config.automatically_add_cw721s,
+        v1_config.automatically_add_cw721s
+    )
+}
+
+#[test]
+fn test_execute_stargate_msg() {
+    let (core_addr, mut app) = do_standard_instantiate(true, None);
+    let proposal_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr.clone(),
+            &QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+                descending: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(proposal_modules.len(), 1);
+    let proposal_module = proposal_modules.into_iter().next().unwrap();
+
+    let res = app.execute_contract(
+        proposal_module.address,
+        core_addr,
+        &ExecuteMsg::ExecuteProposalHook {
+            msgs: vec![CosmosMsg::Stargate {
+                type_url: "foo_type".to_string(),
+                value: to_json_binary("foo_bin").unwrap(),
+            }],
+        },
+        &[],
+    );
+    // TODO: Once cw-multi-test supports executing stargate/ibc messages we can change this test assert
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_module_prefixes() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(sudo_proposal_contract());
+    let gov_id = app.store_code(cw_core_contract());
+
+    let govmod_instantiate = dao_proposal_sudo::msg::InstantiateMsg {
+        root: CREATOR_ADDR.to_string(),
+    };
+
+    let gov_instantiate = InstantiateMsg {
+        dao_uri: None,
+        admin: None,
+        name: "DAO DAO".to_string(),
+        description: "A DAO that builds DAOs.".to_string(),
+        image_url: None,
+        automatically_add_cw20s: true,
+        automatically_add_cw721s: true,
+        voting_module_instantiate_info: ModuleInstantiateInfo {
+            code_id: govmod_id,
+            msg: to_json_binary(&govmod_instantiate).unwrap(),
+            admin: Some(Admin::CoreModule {}),
+            funds: vec![],
+            label: "voting module".to_string(),
+        },
+        proposal_modules_instantiate_info: vec![
+            ModuleInstantiateInfo {
+                code_id: govmod_id,
+                msg: to_json_binary(&govmod_instantiate).unwrap(),
+                admin: Some(Admin::CoreModule {}),
+                funds: vec![],
+                label: "proposal module 1".to_string(),
+            },
+            ModuleInstantiateInfo {
+                code_id: govmod_id,
+                msg: to_json_binary(&govmod_instantiate).unwrap(),
+                admin: Some(Admin::CoreModule {}),
+                funds: vec![],
+                label: "proposal module 2".to_string(),
+            },
+            ModuleInstantiateInfo {
+                code_id: govmod_id,
+                msg: to_json_binary(&govmod_instantiate).unwrap(),
+                admin: Some(Admin::CoreModule {}),
+                funds: vec![],
+                label: "proposal module 2".to_string(),
+            },
+        ],
+        initial_items: None,
+    };
+
+    let gov_addr = app
+        .instantiate_contract(
+            gov_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &gov_instantiate,
+            &[],
+            "cw-governance",
+            None,
+        )
+        .unwrap();
+
+    let modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            gov_addr,
+            &QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+                descending: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(modules.len(), 3);
+
+    let module_1 = &modules[0];
+    assert_eq!(module_1.status, ProposalModuleStatus::Enabled {});
+    assert_eq!(module_1.prefix, "A");
+    assert_eq!(&module_1.address, &modules[0].address);
+
+    let module_2 = &modules[1];
+    assert_eq!(module_2.status, ProposalModuleStatus::Enabled {});
+    assert_eq!(module_2.prefix, "B");
+    assert_eq!(&module_2.address, &modules[1].address);
+
+    let module_3 = &modules[2];
+    assert_eq!(module_3.status, ProposalModuleStatus::Enabled {});
+    assert_eq!(module_3.prefix, "C");
+    assert_eq!(&module_3.address, &modules[2].address);
+}
+
+fn get_active_modules(app: &App, gov_addr: Addr) -> Vec<ProposalModule> {
+    let modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            gov_addr,
+            &QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+                descending: None,
+            },
+        )
+        .unwrap();
+
+    modules
+        .into_iter()
+        .filter(|module: &ProposalModule| module.status == ProposalModuleStatus::Enabled)
+        .collect()
+}
+
+fn query_proposal_module_count(app: &App, core_addr: &Addr) -> ProposalModuleCountResponse {
+    app.wrap()
+        .query_wasm_smart(core_addr, &QueryMsg::ProposalModuleCount {})
+        .unwrap()
+}
+
+#[test]
+fn test_add_remove_subdaos() {
+    let (core_addr, mut app) = do_standard_instantiate(false, None);
+
+    test_unauthorized(
+        &mut app,
+        core_addr.clone(),
+        ExecuteMsg::UpdateSubDaos {
+            to_add: vec![],
+            to_remove: vec![],
+        },
+    );
+
+    let to_add: Vec<SubDao> = vec![
+        SubDao {
+            addr: "subdao001".to_string(),
+            charter: None,
+        },
+        SubDao {
+            addr: "subdao002".to_string(),
+            charter: Some("cool charter bro".to_string()),
+        },
+        SubDao {
+            addr: "subdao005".to_string(),
+            charter: None,
+        },
+        SubDao {
+            addr: "subdao007".to_string(),
+            charter: None,
+        },
+    ];
+    let to_remove: Vec<String> = vec![];
+
+    app.execute_contract(
+        Addr::unchecked(core_addr.clone()),
+        core_addr.clone(),
+        &ExecuteMsg::UpdateSubDaos { to_add, to_remove },
+        &[],
+    )
+    .unwrap();
+
+    let res: Vec<SubDao> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr.clone(),
+            &QueryMsg::ListSubDaos {
+                start_after: None,
+                limit: None,
+                descending: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(res.len(), 4);
+
+    let to_remove: Vec<String> = vec!["subdao005".to_string()];
+
+    app.execute_contract(
+        Addr::unchecked(core_addr.clone()),
+        core_addr.clone(),
+        &ExecuteMsg::UpdateSubDaos {
+            to_add: vec![],
+            to_remove,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let res: Vec<SubDao> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr,
+            &QueryMsg::ListSubDaos {
+                start_after: None,
+                limit: None,
+                descending: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(res.len(), 3);
+
+    let test_res: SubDao = SubDao {
+        addr: "subdao002".to_string(),
+        charter: Some("cool charter bro".to_string()),
+    };
+
+    assert_eq!(res[1], test_res);
+
+    let full_result_set: Vec<SubDao> = vec![
+        SubDao {
+            addr: "subdao001".to_string(),
+            charter: None,
+        },
+        SubDao {
+            addr: "subdao002".to_string(),
+            charter: Some("cool charter bro".to_string()),
+        },
+        SubDao {
+            addr: "subdao007".to_string(),
+            charter: None,
+        },
+    ];
+
+    assert_eq!(res, full_result_set);
+}
+
+fn lifecycle_hook_consumer() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        |_, _, _, msg: DaoLifecycleHookExecuteMsg| -> StdResult<Response> {
+            let DaoLifecycleHookExecuteMsg::DaoLifecycleHook(msg) = msg;
+            Ok(Response::new().add_attribute("lifecycle_hook", to_json_string(&msg)?))
+        },
+        |_, _, _, _: Empty| -> StdResult<Response> { Ok(Response::new()) },
+        |_, _, _: Empty| -> StdResult<Binary> { Ok(Binary::default()) },
+    );
+    Box::new(contract)
+}
+
+fn lifecycle_hook_fired(res: &AppResponse, msg: &DaoLifecycleHookMsg) -> bool {
+    let expected = to_json_string(msg).unwrap();
+    res.events.iter().any(|event| {
+        event
+            .attributes
+            .iter()
+            .any(|attr| attr.key == "lifecycle_hook" && attr.value == expected)
+    })
+}
+
+#[test]
+fn test_lifecycle_hooks() {
+    let (core_addr, mut app) = do_standard_instantiate(false, None);
+    let consumer_id = app.store_code(lifecycle_hook_consumer());
+    let consumer = app
+        .instantiate_contract(
+            consumer_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &Empty {},
+            &[],
+            "consumer",
+            None,
+        )
+        .unwrap();
+
+    test_unauthorized(
+        &mut app,
+        core_addr.clone(),
+        ExecuteMsg::AddLifecycleHook {
+            address: consumer.to_string(),
+        },
+    );
+
+    // "bad" is not a contract so executing hooks on it will fail.
+    for address in [consumer.to_string(), "bad".to_string()] {
+        app.execute_contract(
+            core_addr.clone(),
+            core_addr.clone(),
+            &ExecuteMsg::AddLifecycleHook { address },
+            &[],
+        )
+        .unwrap();
+    }
+
+    let res = app
+        .execute_contract(
+            core_addr.clone(),
+            core_addr.clone(),
+            &ExecuteMsg::UpdateSubDaos {
+                to_add: vec![SubDao {
+                    addr: "subdao".to_string(),
+                    charter: None,
+                }],
+                to_remove: vec!["not_a_subdao".to_string()],
+            },
+            &[],
+        )
+        .unwrap();
+    assert!(lifecycle_hook_fired(
+        &res,
+        &DaoLifecycleHookMsg::SubDaosUpdated {
+            added: vec![Addr::unchecked("subdao")],
+            removed: vec![],
+        }
+    ));
+
+    // The failing consumer is removed without blocking the update.
+    let hooks: HooksResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr.clone(), &QueryMsg::LifecycleHooks {})
+        .unwrap();
+    assert_eq!(hooks.hooks, vec![consumer.to_string()]);
+
+    let res = app
+        .execute_contract(
+            core_addr.clone(),
+            core_addr.clone(),
+            &ExecuteMsg::NominateAdmin {
+                admin: Some("ekez".to_string()),
+            },
+            &[],
+        )
+        .unwrap();
+    assert!(lifecycle_hook_fired(
+        &res,
+        &DaoLifecycleHookMsg::AdminNominated {
+            nominee: Addr::unchecked("ekez"),
+        }
+    ));
+
+    let res = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            core_addr.clone(),
+            &ExecuteMsg::AcceptAdminNomination {},
+            &[],
+        )
+        .unwrap();
+    assert!(lifecycle_hook_fired(
+        &res,
+        &DaoLifecycleHookMsg::AdminChanged {
+            old_admin: core_addr.clone(),
+            new_admin: Addr::unchecked("ekez"),
+        }
+    ));
+
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::RemoveLifecycleHook {
+            address: consumer.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    let hooks: HooksResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr, &QueryMsg::LifecycleHooks {})
+        .unwrap();
+    assert!(hooks.hooks.is_empty());
+}
+
+#[test]
+pub fn test_migrate_update_version() {
+    let mut deps = mock_dependencies();
+    cw2::set_contract_version(&mut deps.storage, "my-contract", "old-version").unwrap();
+    migrate(deps.as_mut(), mock_env(), MigrateMsg::FromCompatible {}).unwrap();
+    let version = cw2::get_contract_version(&deps.storage).unwrap();
+    assert_eq!(version.version, CONTRACT_VERSION);
+    assert_eq!(version.contract, CONTRACT_NAME);
+}
+
+#[test]
+fn test_query_info() {
+    let (core_addr, app) = do_standard_instantiate(true, None);
+    let res: InfoResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr, &QueryMsg::Info {})
+        .unwrap();
+    assert_eq!(
+        res,
+        InfoResponse {
+            info: ContractVersion {
+                contract: CONTRACT_NAME.to_string(),
+                version: CONTRACT_VERSION.to_string()
+            }
+        }
+    )
+}
+
+#[test]
+fn test_dry_run_msgs() {
+    let (gov_addr, mut app) = do_standard_instantiate(true, None);
+    let voting_module: Addr = app
+        .wrap()
+        .query_wasm_smart(gov_addr.clone(), &QueryMsg::VotingModule {})
+        .unwrap();
+    let gov_token: Addr = app
+        .wrap()
+        .query_wasm_smart(
+            voting_module,
+            &dao_interface::voting::Query::TokenContract {},
+        )
+        .unwrap();
+
+    // Sending the DAO a gov token registers it.
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        gov_token.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: gov_addr.to_string(),
+            amount: Uint128::new(1),
+            msg: to_json_binary(&"").unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+    app.sudo(cw_multi_test::SudoMsg::Bank(BankSudo::Mint {
+        to_address: gov_addr.to_string(),
+        amount: coins(5, "ujuno"),
+    }))
+    .unwrap();
+
+    let msgs: Vec<CosmosMsg> = vec![
+        BankMsg::Send {
+            to_address: "ekez".to_string(),
+            amount: coins(5, "ujuno"),
+        }
+        .into(),
+        BankMsg::Send {
+            to_address: "EKEZ".to_string(),
+            amount: vec![coin(1, "ujuno"), coin(0, "uatom"), coin(1, "x")],
+        }
+        .into(),
+        WasmMsg::Execute {
+            contract_addr: "notacontract".to_string(),
+            msg: to_json_binary(&"").unwrap(),
+            funds: vec![],
+        }
+        .into(),
+        WasmMsg::Execute {
+            contract_addr: gov_token.to_string(),
+            msg: to_json_binary(&cw20::Cw20ExecuteMsg::Transfer {
+                recipient: "ekez".to_string(),
+                amount: Uint128::new(2),
+            })
+            .unwrap(),
+            funds: vec![],
+        }
+        .into(),
+    ];
+    let res: DryRunResponse = app
+        .wrap()
+        .query_wasm_smart(gov_addr.clone(), &QueryMsg::DryRunMsgs { msgs })
+        .unwrap();
+    assert_eq!(
+        res.problems,
+        vec![
+            DryRunProblem {
+                msg_index: Some(1),
+                issue: DryRunIssue::InvalidAddress {
+                    address: "EKEZ".to_string()
+                },
+            },
+            DryRunProblem {
+                msg_index: Some(1),
+                issue: DryRunIssue::InsufficientBalance {
+                    denom: "ujuno".to_string(),
+                    required: Uint128::new(6),
+                    available: Uint128::new(5),
+                },
+            },
+            DryRunProblem {
+                msg_index: Some(1),
+                issue: DryRunIssue::ZeroAmount {
+                    denom: "uatom".to_string()
+                },
+            },
+            DryRunProblem {
+                msg_index: Some(1),
+                issue: DryRunIssue::InvalidDenom {
+                    denom: "x".to_string()
+                },
+            },
+            DryRunProblem {
+                msg_index: Some(2),
+                issue: DryRunIssue::NotAContract {
+                    address: "notacontract".to_string()
+                },
+            },
+            DryRunProblem {
+                msg_index: Some(3),
+                issue: DryRunIssue::InsufficientBalance {
+                    denom: gov_token.to_string(),
+                    required: Uint128::new(2),
+                    available: Uint128::new(1),
+                },
+            },
+        ]
+    );
+
+    // Messages larger than a proposal may be are reported.
+    let msgs: Vec<CosmosMsg> = vec![WasmMsg::Execute {
+        contract_addr: gov_addr.to_string(),
+        msg: Binary::from(vec![0; 30_000]),
+        funds: vec![],
+    }
+    .into()];
+    let res: DryRunResponse = app
+        .wrap()
+        .query_wasm_smart(gov_addr, &QueryMsg::DryRunMsgs { msgs })
+        .unwrap();
+    assert_eq!(res.problems.len(), 1);
+    assert_eq!(res.problems[0].msg_index, None);
+    assert!(matches!(
+        res.problems[0].issue,
+        DryRunIssue::TooLarge { max: 30_000, .. }
+    ));
+}
 
-    // Migrate to v2
-    migrate(deps.as_mut(), env, msg).unwrap();
+#[test]
+fn test_managed_contracts() {
+    let (core_addr, mut app) = do_standard_instantiate(false, Some("admin".to_string()));
+    let cw20_id = app.store_code(cw20_contract());
 
-    let new_path = PROPOSAL_MODULES.key(proposal_modules_key);
-    let prop_module_bytes = deps.storage.get(&new_path).unwrap();
-    let module: ProposalModule = from_slice(&prop_module_bytes).unwrap();
-    assert_eq!(module.address, Addr::unchecked("addr"));
-    assert_eq!(module.prefix, derive_proposal_module_prefix(0).unwrap());
-    assert_eq!(module.status, ProposalModuleStatus::Enabled {});
+    let instantiate_cw20 = |admin: Option<String>, label: &str| -> CosmosMsg {
+        WasmMsg::Instantiate {
+            admin,
+            code_id: cw20_id,
+            msg: to_json_binary(&cw20_base::msg::InstantiateMsg {
+                name: "DAO".to_string(),
+                symbol: "DAO".to_string(),
+                decimals: 6,
+                initial_balances: vec![],
+                mint: None,
+                marketing: None,
+            })
+            .unwrap(),
+            funds: vec![],
+            label: label.to_string(),
+        }
+        .into()
+    };
 
-    let v2_config_item: Item<Config> = Item::new("config_v2");
-    let v2_config = v2_config_item.load(&deps.storage).unwrap();
-    assert_eq!(v2_config.dao_uri, Some(dao_uri));
-    assert_eq!(v2_config.name, v1_config.name);
-    assert_eq!(v2_config.description, v1_config.description);
-    assert_eq!(v2_config.image_url, v1_config.image_url);
-    assert_eq!(
-        v2_config.automatically_add_cw20s,
-        v1_config.automatically_add_cw20s
-    );
-    assert_eq!(
-        v2_config.automatically_add_cw721s,
-        v1_config.automatically_add_cw721s
+    // Only contracts instantiated with the DAO as admin are recorded.
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        core_addr.clone(),
+        &ExecuteMsg::ExecuteAdminMsgs {
+            msgs: vec![
+                instantiate_cw20(None, "unmanaged"),
+                instantiate_cw20(Some(core_addr.to_string()), "managed"),
+            ],
+        },
+        &[],
     )
-}
+    .unwrap();
 
-#[test]
-fn test_execute_stargate_msg() {
-    let (core_addr, mut app) = do_standard_instantiate(true, None);
-    let proposal_modules: Vec<ProposalModule> = app
+    let managed: Vec<ManagedContractResponse> = app
         .wrap()
         .query_wasm_smart(
             core_addr.clone(),
-            &QueryMsg::ProposalModules {
+            &QueryMsg::ManagedContracts {
                 start_after: None,
                 limit: None,
             },
         )
         .unwrap();
+    assert_eq!(managed.len(), 1);
+    assert_eq!(managed[0].code_id, cw20_id);
+    assert_eq!(managed[0].label, "managed");
+    let info = app
+        .wrap()
+        .query_wasm_contract_info(managed[0].address.clone())
+        .unwrap();
+    assert_eq!(info.admin, Some(core_addr.to_string()));
 
-    assert_eq!(proposal_modules.len(), 1);
-    let proposal_module = proposal_modules.into_iter().next().unwrap();
+    let update = ExecuteMsg::UpdateManagedContracts {
+        to_add: vec![ManagedContractRegistration {
+            address: "external".to_string(),
+            code_id: 1,
+            label: "external".to_string(),
+        }],
+        to_remove: vec![managed[0].address.to_string()],
+    };
+    let err: ContractError = app
+        .execute_contract(Addr::unchecked("admin"), core_addr.clone(), &update, &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+    app.execute_contract(core_addr.clone(), core_addr.clone(), &update, &[])
+        .unwrap();
 
-    let res = app.execute_contract(
-        proposal_module.address,
-        core_addr,
-        &ExecuteMsg::ExecuteProposalHook {
-            msgs: vec![CosmosMsg::Stargate {
-                type_url: "foo_type".to_string(),
-                value: to_json_binary("foo_bin").unwrap(),
-            }],
-        },
-        &[],
+    let managed: Vec<ManagedContractResponse> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr,
+            &QueryMsg::ManagedContracts {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        managed,
+        vec![ManagedContractResponse {
+            address: Addr::unchecked("external"),
+            code_id: 1,
+            label: "external".to_string(),
+        }]
     );
-    // TODO: Once cw-multi-test supports executing stargate/ibc messages we can change this test assert
-    assert!(res.is_err());
 }
 
 #[test]
-fn test_module_prefixes() {
-    let mut app = App::default();
-    let govmod_id = app.store_code(sudo_proposal_contract());
-    let gov_id = app.store_code(cw_core_contract());
-
-    let govmod_instantiate = dao_proposal_sudo::msg::InstantiateMsg {
-        root: CREATOR_ADDR.to_string(),
+fn test_merge() {
+    let (absorbing, mut app) = do_standard_instantiate(false, None);
+    let absorbed = instantiate_standard_dao(&mut app, false, None);
+
+    app.sudo(cw_multi_test::SudoMsg::Bank(BankSudo::Mint {
+        to_address: absorbed.to_string(),
+        amount: vec![coin(100, "ujuno"), coin(5, "ufrozen")],
+    }))
+    .unwrap();
+    let self_exec = |app: &mut App, dao: &Addr, msg: ExecuteMsg| {
+        app.execute_contract(dao.clone(), dao.clone(), &msg, &[])
     };
-
-    let gov_instantiate = InstantiateMsg {
-        dao_uri: None,
-        admin: None,
-        name: "DAO DAO".to_string(),
-        description: "A DAO that builds DAOs.".to_string(),
-        image_url: None,
-        automatically_add_cw20s: true,
-        automatically_add_cw721s: true,
-        voting_module_instantiate_info: ModuleInstantiateInfo {
-            code_id: govmod_id,
-            msg: to_json_binary(&govmod_instantiate).unwrap(),
-            admin: Some(Admin::CoreModule {}),
-            funds: vec![],
-            label: "voting module".to_string(),
+    self_exec(
+        &mut app,
+        &absorbed,
+        ExecuteMsg::UpdateFrozenDenoms {
+            to_add: vec!["ufrozen".to_string()],
+            to_remove: vec![],
         },
-        proposal_modules_instantiate_info: vec![
-            ModuleInstantiateInfo {
-                code_id: govmod_id,
-                msg: to_json_binary(&govmod_instantiate).unwrap(),
-                admin: Some(Admin::CoreModule {}),
-                funds: vec![],
-                label: "proposal module 1".to_string(),
-            },
-            ModuleInstantiateInfo {
-                code_id: govmod_id,
-                msg: to_json_binary(&govmod_instantiate).unwrap(),
-                admin: Some(Admin::CoreModule {}),
-                funds: vec![],
-                label: "proposal module 2".to_string(),
-            },
-            ModuleInstantiateInfo {
-                code_id: govmod_id,
-                msg: to_json_binary(&govmod_instantiate).unwrap(),
-                admin: Some(Admin::CoreModule {}),
-                funds: vec![],
-                label: "proposal module 2".to_string(),
+    )
+    .unwrap();
+    for (dao, key, value) in [
+        (&absorbing, "a", "kept"),
+        (&absorbed, "a", "dropped"),
+        (&absorbed, "b", "copied"),
+    ] {
+        self_exec(
+            &mut app,
+            dao,
+            ExecuteMsg::SetItem {
+                key: key.to_string(),
+                value: value.to_string(),
             },
-        ],
-        initial_items: None,
-    };
+        )
+        .unwrap();
+    }
+    self_exec(
+        &mut app,
+        &absorbed,
+        ExecuteMsg::UpdateSubDaos {
+            to_add: vec![SubDao {
+                addr: "subdao".to_string(),
+                charter: None,
+            }],
+            to_remove: vec![],
+        },
+    )
+    .unwrap();
 
-    let gov_addr = app
-        .instantiate_contract(
-            gov_id,
-            Addr::unchecked(CREATOR_ADDR),
-            &gov_instantiate,
+    test_unauthorized(
+        &mut app,
+        absorbed.clone(),
+        ExecuteMsg::ProposeMerge {
+            counterparty: absorbing.to_string(),
+            role: MergeRole::Absorbed,
+        },
+    );
+    let advance = |app: &mut App, dao: &Addr| {
+        app.execute_contract(
+            Addr::unchecked("anyone"),
+            dao.clone(),
+            &ExecuteMsg::AdvanceMerge {},
             &[],
-            "cw-governance",
-            None,
         )
+    };
+    let err: ContractError = advance(&mut app, &absorbed)
+        .unwrap_err()
+        .downcast()
         .unwrap();
+    assert_eq!(err, ContractError::NoMerge {});
 
-    let modules: Vec<ProposalModule> = app
-        .wrap()
-        .query_wasm_smart(
-            gov_addr,
-            &QueryMsg::ProposalModules {
-                start_after: None,
-                limit: None,
-            },
-        )
+    let err: ContractError = self_exec(
+        &mut app,
+        &absorbed,
+        ExecuteMsg::ProposeMerge {
+            counterparty: absorbed.to_string(),
+            role: MergeRole::Absorbed,
+        },
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, ContractError::SelfMerge {});
+
+    let propose = ExecuteMsg::ProposeMerge {
+        counterparty: absorbing.to_string(),
+        role: MergeRole::Absorbed,
+    };
+    self_exec(&mut app, &absorbed, propose.clone()).unwrap();
+
+    // The merge may not begin until the counterparty agrees.
+    let err: ContractError = advance(&mut app, &absorbed)
+        .unwrap_err()
+        .downcast()
         .unwrap();
+    assert_eq!(err, ContractError::MergeNotMirrored {});
 
-    assert_eq!(modules.len(), 3);
+    // Until then it may be cancelled.
+    self_exec(&mut app, &absorbed, ExecuteMsg::CancelMerge {}).unwrap();
+    let merge: Option<Merge> = app
+        .wrap()
+        .query_wasm_smart(absorbed.clone(), &QueryMsg::Merge {})
+        .unwrap();
+    assert_eq!(merge, None);
 
-    let module_1 = &modules[0];
-    assert_eq!(module_1.status, ProposalModuleStatus::Enabled {});
-    assert_eq!(module_1.prefix, "A");
-    assert_eq!(&module_1.address, &modules[0].address);
+    self_exec(&mut app, &absorbed, propose).unwrap();
+    let err: ContractError = self_exec(
+        &mut app,
+        &absorbed,
+        ExecuteMsg::ProposeMerge {
+            counterparty: "other".to_string(),
+            role: MergeRole::Absorbed,
+        },
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(
+        err,
+        ContractError::MergeInProgress {
+            counterparty: absorbing.to_string()
+        }
+    );
+    self_exec(
+        &mut app,
+        &absorbing,
+        ExecuteMsg::ProposeMerge {
+            counterparty: absorbed.to_string(),
+            role: MergeRole::Absorbing,
+        },
+    )
+    .unwrap();
 
-    let module_2 = &modules[1];
-    assert_eq!(module_2.status, ProposalModuleStatus::Enabled {});
-    assert_eq!(module_2.prefix, "B");
-    assert_eq!(&module_2.address, &modules[1].address);
+    let err: ContractError = self_exec(&mut app, &absorbed, ExecuteMsg::SkipMergeStep {})
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::MergeNotStarted {});
+
+    // Merge transfers are checked like a proposal's messages.
+    let freeze = |app: &mut App, frozen: bool| {
+        let (to_add, to_remove) = if frozen {
+            (vec![absorbing.to_string()], vec![])
+        } else {
+            (vec![], vec![absorbing.to_string()])
+        };
+        self_exec(
+            app,
+            &absorbed,
+            ExecuteMsg::UpdateFrozenContracts { to_add, to_remove },
+        )
+        .unwrap();
+    };
+    freeze(&mut app, true);
+    let err: ContractError = advance(&mut app, &absorbed)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::Frozen {
+            index: 0,
+            target: absorbing.to_string()
+        }
+    );
+    freeze(&mut app, false);
+    self_exec(
+        &mut app,
+        &absorbed,
+        ExecuteMsg::UpdateCircuitBreaker {
+            circuit_breaker: Some(CircuitBreaker {
+                window: Duration::Height(10),
+                outflow_limits: vec![coin(50, "ujuno")],
+                cw20_outflow_limits: vec![],
+                max_executions_per_block: None,
+            }),
+        },
+    )
+    .unwrap();
 
-    let module_3 = &modules[2];
-    assert_eq!(module_3.status, ProposalModuleStatus::Enabled {});
-    assert_eq!(module_3.prefix, "C");
-    assert_eq!(&module_3.address, &modules[2].address);
-}
+    // Unfrozen tokens move first, then NFTs.
+    advance(&mut app, &absorbed).unwrap();
+    let breaker: CircuitBreakerResponse = app
+        .wrap()
+        .query_wasm_smart(absorbed.clone(), &QueryMsg::CircuitBreaker {})
+        .unwrap();
+    assert_eq!(breaker.outflows, coins(100, "ujuno"));
+    assert!(breaker.tripped.is_some());
+    assert_eq!(
+        app.wrap()
+            .query_balance(&absorbing, "ujuno")
+            .unwrap()
+            .amount,
+        Uint128::new(100)
+    );
+    assert_eq!(
+        app.wrap()
+            .query_balance(&absorbed, "ufrozen")
+            .unwrap()
+            .amount,
+        Uint128::new(5)
+    );
+    let err: ContractError = self_exec(&mut app, &absorbed, ExecuteMsg::CancelMerge {})
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::MergeStarted {});
+    // Nor may the absorbing DAO cancel once the absorbed DAO has
+    // begun, though it has not yet advanced itself.
+    let err: ContractError = self_exec(&mut app, &absorbing, ExecuteMsg::CancelMerge {})
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::MergeStarted {});
 
-fn get_active_modules(app: &App, gov_addr: Addr) -> Vec<ProposalModule> {
-    let modules: Vec<ProposalModule> = app
+    // A step that can not complete may be skipped.
+    test_unauthorized(&mut app, absorbed.clone(), ExecuteMsg::SkipMergeStep {});
+    self_exec(&mut app, &absorbed, ExecuteMsg::SkipMergeStep {}).unwrap();
+    let merge: Option<Merge> = app
+        .wrap()
+        .query_wasm_smart(absorbed.clone(), &QueryMsg::Merge {})
+        .unwrap();
+    assert_eq!(
+        merge,
+        Some(Merge {
+            counterparty: absorbing.clone(),
+            role: MergeRole::Absorbed,
+            step: MergeStep::Complete,
+        })
+    );
+    let err: ContractError = advance(&mut app, &absorbed)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::MergeComplete {});
+
+    // The absorbing DAO copies items without overwriting its own, then
+    // takes on the SubDAOs.
+    advance(&mut app, &absorbing).unwrap();
+    for (key, value) in [("a", "kept"), ("b", "copied")] {
+        let item: GetItemResponse = app
+            .wrap()
+            .query_wasm_smart(
+                absorbing.clone(),
+                &QueryMsg::GetItem {
+                    key: key.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(item.item, Some(value.to_string()));
+    }
+    advance(&mut app, &absorbing).unwrap();
+    let sub_daos: Vec<SubDao> = app
         .wrap()
         .query_wasm_smart(
-            gov_addr,
-            &QueryMsg::ProposalModules {
+            absorbing.clone(),
+            &QueryMsg::ListSubDaos {
                 start_after: None,
                 limit: None,
+                descending: None,
             },
         )
         .unwrap();
+    assert_eq!(
+        sub_daos,
+        vec![SubDao {
+            addr: "subdao".to_string(),
+            charter: None,
+        }]
+    );
+    let merge: Option<Merge> = app
+        .wrap()
+        .query_wasm_smart(absorbing, &QueryMsg::Merge {})
+        .unwrap();
+    assert_eq!(merge.unwrap().step, MergeStep::Complete);
+}
 
-    modules
-        .into_iter()
-        .filter(|module: &ProposalModule| module.status == ProposalModuleStatus::Enabled)
-        .collect()
+#[cw_serde]
+enum ReceiveHandlerExecuteMsg {
+    Receive(cw20::Cw20ReceiveMsg),
 }
 
-fn query_proposal_module_count(app: &App, core_addr: &Addr) -> ProposalModuleCountResponse {
-    app.wrap()
-        .query_wasm_smart(core_addr, &QueryMsg::ProposalModuleCount {})
-        .unwrap()
+fn receive_handler() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        |_, _, _, msg: ReceiveHandlerExecuteMsg| -> StdResult<Response> {
+            let ReceiveHandlerExecuteMsg::Receive(cw20::Cw20ReceiveMsg { amount, msg, .. }) = msg;
+            let ReceiveHandlerMsg::Cw20Received { sender, memo } = from_json_binary(&msg)?;
+            let memo: std::collections::BTreeMap<String, u64> = from_json_binary(&memo)?;
+            match memo.get("pay_invoice") {
+                Some(1) => {
+                    Ok(Response::new().add_attribute("invoice_paid", format!("{sender}:{amount}")))
+                }
+                _ => Err(StdError::generic_err("no such invoice")),
+            }
+        },
+        |_, _, _, _: Empty| -> StdResult<Response> { Ok(Response::new()) },
+        |_, _, _: Empty| -> StdResult<Binary> { Ok(Binary::default()) },
+    );
+    Box::new(contract)
 }
 
 #[test]
-fn test_add_remove_subdaos() {
-    let (core_addr, mut app) = do_standard_instantiate(false, None);
+fn test_receive_handlers() {
+    let (core_addr, mut app) = do_standard_instantiate(true, None);
+    let handler_id = app.store_code(receive_handler());
+    let handler = app
+        .instantiate_contract(
+            handler_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &Empty {},
+            &[],
+            "invoices",
+            None,
+        )
+        .unwrap();
+    let voting_module: Addr = app
+        .wrap()
+        .query_wasm_smart(core_addr.clone(), &QueryMsg::VotingModule {})
+        .unwrap();
+    let gov_token: Addr = app
+        .wrap()
+        .query_wasm_smart(
+            voting_module,
+            &dao_interface::voting::Query::TokenContract {},
+        )
+        .unwrap();
 
     test_unauthorized(
         &mut app,
         core_addr.clone(),
-        ExecuteMsg::UpdateSubDaos {
+        ExecuteMsg::UpdateReceiveHandlers {
             to_add: vec![],
             to_remove: vec![],
         },
     );
-
-    let to_add: Vec<SubDao> = vec![
-        SubDao {
-            addr: "subdao001".to_string(),
-            charter: None,
-        },
-        SubDao {
-            addr: "subdao002".to_string(),
-            charter: Some("cool charter bro".to_string()),
-        },
-        SubDao {
-            addr: "subdao005".to_string(),
-            charter: None,
-        },
-        SubDao {
-            addr: "subdao007".to_string(),
-            charter: None,
-        },
-    ];
-    let to_remove: Vec<String> = vec![];
-
-    app.execute_contract(
-        Addr::unchecked(core_addr.clone()),
-        core_addr.clone(),
-        &ExecuteMsg::UpdateSubDaos { to_add, to_remove },
-        &[],
-    )
-    .unwrap();
-
-    let res: Vec<SubDao> = app
-        .wrap()
-        .query_wasm_smart(
+    let err: ContractError = app
+        .execute_contract(
             core_addr.clone(),
-            &QueryMsg::ListSubDaos {
-                start_after: None,
-                limit: None,
+            core_addr.clone(),
+            &ExecuteMsg::UpdateReceiveHandlers {
+                to_add: vec![ReceiveHandler {
+                    route: "".to_string(),
+                    address: handler.to_string(),
+                }],
+                to_remove: vec![],
             },
+            &[],
         )
+        .unwrap_err()
+        .downcast()
         .unwrap();
+    assert_eq!(err, ContractError::EmptyReceiveRoute {});
 
-    assert_eq!(res.len(), 4);
-
-    let to_remove: Vec<String> = vec!["subdao005".to_string()];
-
+    let handlers = vec![ReceiveHandler {
+        route: "pay_invoice".to_string(),
+        address: handler.to_string(),
+    }];
     app.execute_contract(
-        Addr::unchecked(core_addr.clone()),
         core_addr.clone(),
-        &ExecuteMsg::UpdateSubDaos {
-            to_add: vec![],
-            to_remove,
+        core_addr.clone(),
+        &ExecuteMsg::UpdateReceiveHandlers {
+            to_add: handlers.clone(),
+            to_remove: vec![],
         },
         &[],
     )
     .unwrap();
-
-    let res: Vec<SubDao> = app
+    let registered: Vec<ReceiveHandler> = app
         .wrap()
         .query_wasm_smart(
-            core_addr,
-            &QueryMsg::ListSubDaos {
+            core_addr.clone(),
+            &QueryMsg::ReceiveHandlers {
                 start_after: None,
                 limit: None,
             },
         )
         .unwrap();
+    assert_eq!(registered, handlers);
 
-    assert_eq!(res.len(), 3);
-
-    let test_res: SubDao = SubDao {
-        addr: "subdao002".to_string(),
-        charter: Some("cool charter bro".to_string()),
+    let send = |app: &mut App, memo: &str| {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            gov_token.clone(),
+            &cw20::Cw20ExecuteMsg::Send {
+                contract: core_addr.to_string(),
+                amount: Uint128::new(1),
+                msg: Binary::from(memo.as_bytes()),
+            },
+            &[],
+        )
     };
 
-    assert_eq!(res[1], test_res);
+    // Tokens must be registered before they are forwarded, so a
+    // caller can not claim to have sent tokens it has not.
+    let err: ContractError = send(&mut app, r#"{"pay_invoice":1}"#)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::UnregisteredCw20 {
+            token: gov_token.to_string()
+        }
+    );
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            core_addr.clone(),
+            &ExecuteMsg::Receive(cw20::Cw20ReceiveMsg {
+                sender: "ekez".to_string(),
+                amount: Uint128::new(100),
+                msg: Binary::from(br#"{"pay_invoice":1}"#.to_vec()),
+            }),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::UnregisteredCw20 {
+            token: "ekez".to_string()
+        }
+    );
+    // Plain transfers register the token.
+    send(&mut app, r#""thanks""#).unwrap();
 
-    let full_result_set: Vec<SubDao> = vec![
-        SubDao {
-            addr: "subdao001".to_string(),
-            charter: None,
-        },
-        SubDao {
-            addr: "subdao002".to_string(),
-            charter: Some("cool charter bro".to_string()),
-        },
-        SubDao {
-            addr: "subdao007".to_string(),
-            charter: None,
-        },
-    ];
+    // Payments with the handler's route are forwarded to it.
+    let res = send(&mut app, r#"{"pay_invoice":1}"#).unwrap();
+    assert!(res.events.iter().any(|event| event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "invoice_paid" && attr.value == format!("{CREATOR_ADDR}:1"))));
 
-    assert_eq!(res, full_result_set);
-}
+    // A payment the handler rejects is not received.
+    send(&mut app, r#"{"pay_invoice":2}"#).unwrap_err();
 
-#[test]
-pub fn test_migrate_update_version() {
-    let mut deps = mock_dependencies();
-    cw2::set_contract_version(&mut deps.storage, "my-contract", "old-version").unwrap();
-    migrate(deps.as_mut(), mock_env(), MigrateMsg::FromCompatible {}).unwrap();
-    let version = cw2::get_contract_version(&deps.storage).unwrap();
-    assert_eq!(version.version, CONTRACT_VERSION);
-    assert_eq!(version.contract, CONTRACT_NAME);
+    // Memos without a handler, and messages that are not memos, are
+    // plain transfers.
+    send(&mut app, r#"{"fund_campaign":{"id":3}}"#).unwrap();
+    let balance = |app: &App, address: &Addr| -> Uint128 {
+        let balance: cw20::BalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                gov_token.clone(),
+                &cw20::Cw20QueryMsg::Balance {
+                    address: address.to_string(),
+                },
+            )
+            .unwrap();
+        balance.balance
+    };
+    assert_eq!(balance(&app, &core_addr), Uint128::new(2));
+    assert_eq!(balance(&app, &handler), Uint128::new(1));
 }
 
 #[test]
-fn test_query_info() {
-    let (core_addr, app) = do_standard_instantiate(true, None);
-    let res: InfoResponse = app
-        .wrap()
-        .query_wasm_smart(core_addr, &QueryMsg::Info {})
+fn test_code_id_registry() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let dao = env.contract.address.clone();
+    let module = Addr::unchecked("proposal");
+    PROPOSAL_MODULES
+        .save(
+            deps.as_mut().storage,
+            module.clone(),
+            &ProposalModule {
+                address: module.clone(),
+                prefix: "A".to_string(),
+                status: ProposalModuleStatus::Enabled,
+            },
+        )
         .unwrap();
-    assert_eq!(
-        res,
-        InfoResponse {
-            info: ContractVersion {
-                contract: CONTRACT_NAME.to_string(),
-                version: CONTRACT_VERSION.to_string()
+    let official = vec![
+        OfficialCodeId {
+            module_type: "dao-proposal-single".to_string(),
+            version: "2.4.0".to_string(),
+            code_id: 12,
+        },
+        OfficialCodeId {
+            module_type: "dao-voting-cw4".to_string(),
+            version: "2.4.0".to_string(),
+            code_id: 7,
+        },
+    ];
+    let listed = official.clone();
+    let admin = dao.to_string();
+    deps.querier.update_wasm(move |query| match query {
+        WasmQuery::Smart { contract_addr, .. } if contract_addr.ends_with("registrar") => {
+            SystemResult::Ok(ContractResult::Ok(to_json_binary(&listed).unwrap()))
+        }
+        WasmQuery::ContractInfo { contract_addr } => {
+            let mut info = ContractInfoResponse::new(1, "creator");
+            // Only "registrar" is administered by the DAO.
+            if contract_addr != "other_registrar" {
+                info.admin = Some(admin.clone());
             }
+            SystemResult::Ok(ContractResult::Ok(to_json_binary(&info).unwrap()))
+        }
+        _ => unimplemented!(),
+    });
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("ekez", &[]),
+        ExecuteMsg::UpdateCodeIdRegistrar {
+            registrar: Some("registrar".to_string()),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("ekez", &[]),
+        ExecuteMsg::SyncCodeIds {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NoCodeIdRegistrar {});
+
+    // The registrar must be administered by the DAO.
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(dao.as_str(), &[]),
+        ExecuteMsg::UpdateCodeIdRegistrar {
+            registrar: Some("other_registrar".to_string()),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::UncontrolledCodeIdRegistrar {});
+
+    // Without a registrar any code ID may be used.
+    let migrate = |new_code_id| ExecuteMsg::ExecuteProposalHook {
+        msgs: vec![WasmMsg::Migrate {
+            contract_addr: "managed".to_string(),
+            new_code_id,
+            msg: to_json_binary(&Empty {}).unwrap(),
+        }
+        .into()],
+    };
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(module.as_str(), &[]),
+        migrate(13),
+    )
+    .unwrap();
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(dao.as_str(), &[]),
+        ExecuteMsg::UpdateCodeIdRegistrar {
+            registrar: Some("registrar".to_string()),
+        },
+    )
+    .unwrap();
+    let registrar: Option<Addr> =
+        from_json_binary(&query(deps.as_ref(), env.clone(), QueryMsg::CodeIdRegistrar {}).unwrap())
+            .unwrap();
+    assert_eq!(registrar, Some(Addr::unchecked("registrar")));
+    let code_ids: Vec<OfficialCodeId> = from_json_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::OfficialCodeIds {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(code_ids, vec![official[1].clone(), official[0].clone()]);
+
+    // Only the DAO and the registrar may sync the code IDs.
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("ekez", &[]),
+        ExecuteMsg::SyncCodeIds {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("registrar", &[]),
+        ExecuteMsg::SyncCodeIds {},
+    )
+    .unwrap();
+
+    // With a registrar, proposals may only migrate contracts and
+    // install modules with official code IDs.
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(module.as_str(), &[]),
+        migrate(13),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::UnofficialCodeId { code_id: 13 });
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(module.as_str(), &[]),
+        migrate(12),
+    )
+    .unwrap();
+
+    // Contracts may also only be instantiated with official code IDs.
+    let instantiate = |code_id| ExecuteMsg::ExecuteProposalHook {
+        msgs: vec![WasmMsg::Instantiate {
+            admin: None,
+            code_id,
+            msg: to_json_binary(&Empty {}).unwrap(),
+            funds: vec![],
+            label: "contract".to_string(),
         }
+        .into()],
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(module.as_str(), &[]),
+        instantiate(13),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::UnofficialCodeId { code_id: 13 });
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(module.as_str(), &[]),
+        instantiate(7),
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(dao.as_str(), &[]),
+        ExecuteMsg::UpdateVotingModule {
+            module: ModuleInstantiateInfo {
+                code_id: 8,
+                msg: to_json_binary(&Empty {}).unwrap(),
+                admin: None,
+                funds: vec![],
+                label: "voting module".to_string(),
+            },
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::UnofficialCodeId { code_id: 8 });
+
+    let dry_run: DryRunResponse = from_json_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::DryRunMsgs {
+                msgs: vec![WasmMsg::Migrate {
+                    contract_addr: "managed".to_string(),
+                    new_code_id: 13,
+                    msg: to_json_binary(&Empty {}).unwrap(),
+                }
+                .into()],
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert!(dry_run.problems.contains(&DryRunProblem {
+        msg_index: Some(0),
+        issue: DryRunIssue::UnofficialCodeId { code_id: 13 },
+    }));
+
+    // Removing the registrar lifts the restriction.
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(dao.as_str(), &[]),
+        ExecuteMsg::UpdateCodeIdRegistrar { registrar: None },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        env,
+        mock_info(module.as_str(), &[]),
+        migrate(13),
     )
+    .unwrap();
 }
@@ -1,7 +1,8 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Uint128,
+    to_json_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
+    Uint128,
 };
 use cw2::set_contract_version;
 use cw_storage_plus::Item;
@@ -50,6 +51,7 @@ pub fn execute(
 ) -> Result<Response, ContractError> {
     match msg {
         ExecuteMsg::Receive(msg) => execute_receive(deps, info.sender, msg),
+        ExecuteMsg::ReceiveNft(msg) => execute_receive_nft(deps, info.sender, msg),
         ExecuteMsg::Fund {} => execute_fund(deps, info),
         ExecuteMsg::Withdraw {} => execute_withdraw(deps, info),
     }
@@ -111,18 +113,7 @@ fn do_fund(
     counterparty.provided = true;
     storage.save(deps.storage, &counterparty)?;
 
-    let messages = if counterparty.provided && other_counterparty.provided {
-        vec![
-            counterparty
-                .promise
-                .into_send_message(&other_counterparty.address)?,
-            other_counterparty
-                .promise
-                .into_send_message(&counterparty.address)?,
-        ]
-    } else {
-        vec![]
-    };
+    let messages = settlement_messages(&counterparty, &other_counterparty)?;
 
     Ok(Response::new()
         .add_attribute("method", "fund_escrow")
@@ -130,6 +121,22 @@ fn do_fund(
         .add_messages(messages))
 }
 
+/// Gets the messages that complete the escrow if both counterparties
+/// have provided everything they promised. Settlement happens in the
+/// same transaction as the final provision so it is atomic.
+fn settlement_messages(
+    counterparty: &CheckedCounterparty,
+    other_counterparty: &CheckedCounterparty,
+) -> StdResult<Vec<CosmosMsg>> {
+    if counterparty.is_complete() && other_counterparty.is_complete() {
+        let mut messages = counterparty.send_provided_messages(&other_counterparty.address)?;
+        messages.extend(other_counterparty.send_provided_messages(&counterparty.address)?);
+        Ok(messages)
+    } else {
+        Ok(vec![])
+    }
+}
+
 pub fn execute_receive(
     deps: DepsMut,
     token_contract: Addr,
@@ -168,6 +175,40 @@ pub fn execute_receive(
     )
 }
 
+pub fn execute_receive_nft(
+    deps: DepsMut,
+    nft_contract: Addr,
+    msg: cw721::Cw721ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let sender = deps.api.addr_validate(&msg.sender)?;
+
+    let CounterpartyResponse {
+        mut counterparty,
+        other_counterparty,
+        storage,
+    } = get_counterparty(deps.as_ref(), &sender)?;
+
+    let nft = counterparty
+        .nfts
+        .iter_mut()
+        .find(|nft| nft.contract_addr == nft_contract && nft.token_id == msg.token_id)
+        // Must provide one of the promised NFTs.
+        .ok_or(ContractError::InvalidFunds {})?;
+    if nft.provided {
+        return Err(ContractError::AlreadyProvided {});
+    }
+    nft.provided = true;
+    storage.save(deps.storage, &counterparty)?;
+
+    let messages = settlement_messages(&counterparty, &other_counterparty)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "fund_escrow_nft")
+        .add_attribute("counterparty", counterparty.address)
+        .add_attribute("token_id", msg.token_id)
+        .add_messages(messages))
+}
+
 pub fn execute_fund(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
     let CounterpartyResponse {
         counterparty,
@@ -201,7 +242,7 @@ pub fn execute_withdraw(deps: DepsMut, info: MessageInfo) -> Result<Response, Co
         storage,
     } = get_counterparty(deps.as_ref(), &info.sender)?;
 
-    if !counterparty.provided {
+    if !counterparty.has_provided() {
         return Err(ContractError::NoProvision {});
     }
 
@@ -210,23 +251,23 @@ pub fn execute_withdraw(deps: DepsMut, info: MessageInfo) -> Result<Response, Co
     // happens no more withdrawals are allowed. This check isn't
     // strictly needed because the contract won't have enough balance
     // anyhow, but we may as well error nicely.
-    if counterparty.provided && other_counterparty.provided {
+    if counterparty.is_complete() && other_counterparty.is_complete() {
         return Err(ContractError::Complete {});
     }
 
-    let message = counterparty
-        .promise
-        .clone()
-        .into_send_message(&counterparty.address)?;
+    let messages = counterparty.send_provided_messages(&counterparty.address)?;
 
     let mut counterparty = counterparty;
     counterparty.provided = false;
+    for nft in counterparty.nfts.iter_mut() {
+        nft.provided = false;
+    }
     storage.save(deps.storage, &counterparty)?;
 
     Ok(Response::new()
         .add_attribute("method", "withdraw")
         .add_attribute("counterparty", counterparty.address)
-        .add_message(message))
+        .add_messages(messages))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -30,4 +30,7 @@ pub enum ContractError {
 
     #[error("Invalid amount. Expected ({expected}), got ({actual})")]
     InvalidAmount { expected: Uint128, actual: Uint128 },
+
+    #[error("Can not promise the same NFT more than once")]
+    DuplicateNft {},
 }
@@ -15,6 +15,16 @@ pub enum TokenInfo {
     },
 }
 
+/// Information about cw721 tokens being used on one side of the
+/// escrow.
+#[cw_serde]
+pub struct NftInfo {
+    /// The address of the cw721 contract.
+    pub contract_addr: String,
+    /// The IDs of the tokens being exchanged.
+    pub token_ids: Vec<String>,
+}
+
 /// Information about a counterparty in this escrow transaction and
 /// their promised funds.
 #[cw_serde]
@@ -23,6 +33,11 @@ pub struct Counterparty {
     pub address: String,
     /// The funds they have promised to provide.
     pub promise: TokenInfo,
+    /// The cw721 tokens they have promised to provide alongside their
+    /// funds. If any are promised, the funds promise may be for zero
+    /// tokens.
+    #[serde(default)]
+    pub nfts: Vec<NftInfo>,
 }
 
 #[cw_serde]
@@ -35,10 +50,13 @@ pub struct InstantiateMsg {
 pub enum ExecuteMsg {
     /// Used to provide cw20 tokens to satisfy a funds promise.
     Receive(cw20::Cw20ReceiveMsg),
+    /// Used to provide cw721 tokens to satisfy a promise.
+    ReceiveNft(cw721::Cw721ReceiveMsg),
     /// Provides native tokens to satisfy a funds promise.
     Fund {},
-    /// Withdraws provided funds. Only allowed if the other
-    /// counterparty has yet to provide their promised funds.
+    /// Withdraws provided funds and cw721 tokens. Only allowed if the
+    /// other counterparty has yet to provide everything they
+    /// promised.
     Withdraw {},
 }
 
@@ -1,9 +1,11 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{to_json_binary, Addr, BankMsg, Coin, CosmosMsg, Deps, StdError, Uint128, WasmMsg};
+use cosmwasm_std::{
+    to_json_binary, Addr, BankMsg, Coin, CosmosMsg, Deps, StdError, StdResult, Uint128, WasmMsg,
+};
 use cw_storage_plus::Item;
 
 use crate::{
-    msg::{Counterparty, TokenInfo},
+    msg::{Counterparty, NftInfo, TokenInfo},
     ContractError,
 };
 
@@ -19,11 +21,21 @@ pub enum CheckedTokenInfo {
     },
 }
 
+#[cw_serde]
+pub struct CheckedNft {
+    pub contract_addr: Addr,
+    pub token_id: String,
+    pub provided: bool,
+}
+
 #[cw_serde]
 pub struct CheckedCounterparty {
     pub address: Addr,
     pub promise: CheckedTokenInfo,
+    /// True if the funds in `promise` have been provided.
     pub provided: bool,
+    #[serde(default)]
+    pub nfts: Vec<CheckedNft>,
 }
 
 pub const COUNTERPARTY_ONE: Item<CheckedCounterparty> = Item::new("counterparty_one");
@@ -31,19 +43,60 @@ pub const COUNTERPARTY_TWO: Item<CheckedCounterparty> = Item::new("counterparty_
 
 impl Counterparty {
     pub fn into_checked(self, deps: Deps) -> Result<CheckedCounterparty, ContractError> {
+        let mut nfts: Vec<CheckedNft> = vec![];
+        for info in self.nfts {
+            for nft in info.into_checked(deps)? {
+                if nfts.iter().any(|other| {
+                    other.contract_addr == nft.contract_addr && other.token_id == nft.token_id
+                }) {
+                    return Err(ContractError::DuplicateNft {});
+                }
+                nfts.push(nft);
+            }
+        }
         Ok(CheckedCounterparty {
             address: deps.api.addr_validate(&self.address)?,
             provided: false,
-            promise: self.promise.into_checked(deps)?,
+            promise: self.promise.into_checked(deps, !nfts.is_empty())?,
+            nfts,
         })
     }
 }
 
+impl NftInfo {
+    pub fn into_checked(self, deps: Deps) -> Result<Vec<CheckedNft>, ContractError> {
+        if self.token_ids.is_empty() {
+            return Err(ContractError::ZeroTokens {});
+        }
+        let contract_addr = deps.api.addr_validate(&self.contract_addr)?;
+        // Make sure we are dealing with a cw721.
+        let _: cw721::ContractInfoResponse = deps.querier.query_wasm_smart(
+            contract_addr.clone(),
+            &cw721::Cw721QueryMsg::ContractInfo {},
+        )?;
+        Ok(self
+            .token_ids
+            .into_iter()
+            .map(|token_id| CheckedNft {
+                contract_addr: contract_addr.clone(),
+                token_id,
+                provided: false,
+            })
+            .collect())
+    }
+}
+
 impl TokenInfo {
-    pub fn into_checked(self, deps: Deps) -> Result<CheckedTokenInfo, ContractError> {
+    /// Validates the token info. Unless `allow_zero` is set, the
+    /// amount of tokens must be non-zero.
+    pub fn into_checked(
+        self,
+        deps: Deps,
+        allow_zero: bool,
+    ) -> Result<CheckedTokenInfo, ContractError> {
         match self {
             TokenInfo::Native { denom, amount } => {
-                if amount.is_zero() {
+                if amount.is_zero() && !allow_zero {
                     Err(ContractError::ZeroTokens {})
                 } else {
                     Ok(CheckedTokenInfo::Native { denom, amount })
@@ -53,7 +106,7 @@ impl TokenInfo {
                 contract_addr,
                 amount,
             } => {
-                if amount.is_zero() {
+                if amount.is_zero() && !allow_zero {
                     Err(ContractError::ZeroTokens {})
                 } else {
                     let contract_addr = deps.api.addr_validate(&contract_addr)?;
@@ -73,6 +126,12 @@ impl TokenInfo {
 }
 
 impl CheckedTokenInfo {
+    pub fn is_zero(&self) -> bool {
+        match self {
+            Self::Native { amount, .. } | Self::Cw20 { amount, .. } => amount.is_zero(),
+        }
+    }
+
     pub fn into_send_message(self, recipient: &Addr) -> Result<CosmosMsg, StdError> {
         Ok(match self {
             Self::Native { denom, amount } => BankMsg::Send {
@@ -96,6 +155,41 @@ impl CheckedTokenInfo {
     }
 }
 
+impl CheckedCounterparty {
+    /// True if the counterparty has provided everything it promised.
+    pub fn is_complete(&self) -> bool {
+        (self.provided || self.promise.is_zero()) && self.nfts.iter().all(|nft| nft.provided)
+    }
+
+    /// True if the counterparty has provided any of its promise.
+    pub fn has_provided(&self) -> bool {
+        self.provided || self.nfts.iter().any(|nft| nft.provided)
+    }
+
+    /// Gets messages that send everything this counterparty has
+    /// provided to `recipient`.
+    pub fn send_provided_messages(&self, recipient: &Addr) -> StdResult<Vec<CosmosMsg>> {
+        let mut messages = vec![];
+        if self.provided {
+            messages.push(self.promise.clone().into_send_message(recipient)?);
+        }
+        for nft in self.nfts.iter().filter(|nft| nft.provided) {
+            messages.push(
+                WasmMsg::Execute {
+                    contract_addr: nft.contract_addr.to_string(),
+                    msg: to_json_binary(&cw721::Cw721ExecuteMsg::TransferNft {
+                        recipient: recipient.to_string(),
+                        token_id: nft.token_id.clone(),
+                    })?,
+                    funds: vec![],
+                }
+                .into(),
+            );
+        }
+        Ok(messages)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
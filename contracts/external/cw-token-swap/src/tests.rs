@@ -8,7 +8,8 @@ use cw_multi_test::{App, BankSudo, Contract, ContractWrapper, Executor, SudoMsg}
 use crate::{
     contract::{migrate, CONTRACT_NAME, CONTRACT_VERSION},
     msg::{
-        Counterparty, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, StatusResponse, TokenInfo,
+        Counterparty, ExecuteMsg, InstantiateMsg, MigrateMsg, NftInfo, QueryMsg, StatusResponse,
+        TokenInfo,
     },
     state::{CheckedCounterparty, CheckedTokenInfo},
     ContractError,
@@ -35,6 +36,15 @@ fn cw20_contract() -> Box<dyn Contract<Empty>> {
     Box::new(contract)
 }
 
+fn cw721_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw721_base::entry::execute,
+        cw721_base::entry::instantiate,
+        cw721_base::entry::query,
+    );
+    Box::new(contract)
+}
+
 #[test]
 fn test_simple_escrow() {
     let mut app = App::default();
@@ -74,6 +84,7 @@ fn test_simple_escrow() {
                         denom: "ujuno".to_string(),
                         amount: Uint128::new(100),
                     },
+                    nfts: vec![],
                 },
                 counterparty_two: Counterparty {
                     address: DAO2.to_string(),
@@ -81,6 +92,7 @@ fn test_simple_escrow() {
                         contract_addr: cw20.to_string(),
                         amount: Uint128::new(100),
                     },
+                    nfts: vec![],
                 },
             },
             &[],
@@ -175,6 +187,7 @@ fn test_withdraw() {
                         denom: "ujuno".to_string(),
                         amount: Uint128::new(100),
                     },
+                    nfts: vec![],
                 },
                 counterparty_two: Counterparty {
                     address: DAO2.to_string(),
@@ -182,6 +195,7 @@ fn test_withdraw() {
                         contract_addr: cw20.to_string(),
                         amount: Uint128::new(100),
                     },
+                    nfts: vec![],
                 },
             },
             &[],
@@ -359,6 +373,7 @@ fn test_withdraw_post_completion() {
                         denom: "ujuno".to_string(),
                         amount: Uint128::new(100),
                     },
+                    nfts: vec![],
                 },
                 counterparty_two: Counterparty {
                     address: DAO2.to_string(),
@@ -366,6 +381,7 @@ fn test_withdraw_post_completion() {
                         contract_addr: cw20.to_string(),
                         amount: Uint128::new(100),
                     },
+                    nfts: vec![],
                 },
             },
             &[],
@@ -468,6 +484,7 @@ fn test_invalid_instantiate() {
                         denom: "ujuno".to_string(),
                         amount: Uint128::new(0),
                     },
+                    nfts: vec![],
                 },
                 counterparty_two: Counterparty {
                     address: DAO2.to_string(),
@@ -475,6 +492,7 @@ fn test_invalid_instantiate() {
                         contract_addr: cw20.to_string(),
                         amount: Uint128::new(100),
                     },
+                    nfts: vec![],
                 },
             },
             &[],
@@ -499,6 +517,7 @@ fn test_invalid_instantiate() {
                         denom: "ujuno".to_string(),
                         amount: Uint128::new(100),
                     },
+                    nfts: vec![],
                 },
                 counterparty_two: Counterparty {
                     address: DAO2.to_string(),
@@ -506,6 +525,7 @@ fn test_invalid_instantiate() {
                         contract_addr: cw20.to_string(),
                         amount: Uint128::new(0),
                     },
+                    nfts: vec![],
                 },
             },
             &[],
@@ -537,6 +557,7 @@ fn test_non_distincy_counterparties() {
                         denom: "ujuno".to_string(),
                         amount: Uint128::new(110),
                     },
+                    nfts: vec![],
                 },
                 counterparty_two: Counterparty {
                     address: DAO1.to_string(),
@@ -544,6 +565,7 @@ fn test_non_distincy_counterparties() {
                         denom: "ujuno".to_string(),
                         amount: Uint128::new(10),
                     },
+                    nfts: vec![],
                 },
             },
             &[],
@@ -596,6 +618,7 @@ fn test_fund_non_counterparty() {
                         denom: "ujuno".to_string(),
                         amount: Uint128::new(100),
                     },
+                    nfts: vec![],
                 },
                 counterparty_two: Counterparty {
                     address: DAO2.to_string(),
@@ -603,6 +626,7 @@ fn test_fund_non_counterparty() {
                         contract_addr: cw20.to_string(),
                         amount: Uint128::new(100),
                     },
+                    nfts: vec![],
                 },
             },
             &[],
@@ -693,6 +717,7 @@ fn test_fund_twice() {
                         denom: "ujuno".to_string(),
                         amount: Uint128::new(100),
                     },
+                    nfts: vec![],
                 },
                 counterparty_two: Counterparty {
                     address: DAO2.to_string(),
@@ -700,6 +725,7 @@ fn test_fund_twice() {
                         contract_addr: cw20.to_string(),
                         amount: Uint128::new(100),
                     },
+                    nfts: vec![],
                 },
             },
             &[],
@@ -813,6 +839,7 @@ fn test_fund_invalid_amount() {
                         denom: "ujuno".to_string(),
                         amount: Uint128::new(100),
                     },
+                    nfts: vec![],
                 },
                 counterparty_two: Counterparty {
                     address: DAO2.to_string(),
@@ -820,6 +847,7 @@ fn test_fund_invalid_amount() {
                         contract_addr: cw20.to_string(),
                         amount: Uint128::new(100),
                     },
+                    nfts: vec![],
                 },
             },
             &[],
@@ -896,6 +924,7 @@ fn test_fund_invalid_denom() {
                         denom: "ujuno".to_string(),
                         amount: Uint128::new(100),
                     },
+                    nfts: vec![],
                 },
                 counterparty_two: Counterparty {
                     address: DAO2.to_string(),
@@ -903,6 +932,7 @@ fn test_fund_invalid_denom() {
                         denom: "uekez".to_string(),
                         amount: Uint128::new(100),
                     },
+                    nfts: vec![],
                 },
             },
             &[],
@@ -999,6 +1029,7 @@ fn test_fund_invalid_cw20() {
                         denom: "ujuno".to_string(),
                         amount: Uint128::new(100),
                     },
+                    nfts: vec![],
                 },
                 counterparty_two: Counterparty {
                     address: DAO2.to_string(),
@@ -1006,6 +1037,7 @@ fn test_fund_invalid_cw20() {
                         contract_addr: cw20.to_string(),
                         amount: Uint128::new(100),
                     },
+                    nfts: vec![],
                 },
             },
             &[],
@@ -1052,6 +1084,194 @@ fn test_fund_invalid_cw20() {
     assert_eq!(err, ContractError::InvalidFunds {})
 }
 
+#[test]
+fn test_nft_escrow() {
+    let mut app = App::default();
+
+    let cw20_code = app.store_code(cw20_contract());
+    let cw721_code = app.store_code(cw721_contract());
+    let escrow_code = app.store_code(escrow_contract());
+
+    let cw20 = app
+        .instantiate_contract(
+            cw20_code,
+            Addr::unchecked(DAO1),
+            &cw20_base::msg::InstantiateMsg {
+                name: "coin coin".to_string(),
+                symbol: "coin".to_string(),
+                decimals: 6,
+                initial_balances: vec![Cw20Coin {
+                    address: DAO1.to_string(),
+                    amount: Uint128::new(100),
+                }],
+                mint: None,
+                marketing: None,
+            },
+            &[],
+            "coin",
+            None,
+        )
+        .unwrap();
+
+    let cw721 = app
+        .instantiate_contract(
+            cw721_code,
+            Addr::unchecked(DAO1),
+            &cw721_base::msg::InstantiateMsg {
+                name: "art".to_string(),
+                symbol: "art".to_string(),
+                minter: DAO1.to_string(),
+            },
+            &[],
+            "art",
+            None,
+        )
+        .unwrap();
+    for token_id in ["1", "2"] {
+        app.execute_contract(
+            Addr::unchecked(DAO1),
+            cw721.clone(),
+            &cw721_base::msg::ExecuteMsg::<Option<Empty>, Empty>::Mint {
+                token_id: token_id.to_string(),
+                owner: DAO1.to_string(),
+                token_uri: None,
+                extension: None,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    // DAO1 promises a cw20 and an NFT for native tokens.
+    let escrow = app
+        .instantiate_contract(
+            escrow_code,
+            Addr::unchecked(DAO1),
+            &InstantiateMsg {
+                counterparty_one: Counterparty {
+                    address: DAO1.to_string(),
+                    promise: TokenInfo::Cw20 {
+                        contract_addr: cw20.to_string(),
+                        amount: Uint128::new(100),
+                    },
+                    nfts: vec![NftInfo {
+                        contract_addr: cw721.to_string(),
+                        token_ids: vec!["1".to_string()],
+                    }],
+                },
+                counterparty_two: Counterparty {
+                    address: DAO2.to_string(),
+                    promise: TokenInfo::Native {
+                        denom: "ujuno".to_string(),
+                        amount: Uint128::new(100),
+                    },
+                    nfts: vec![],
+                },
+            },
+            &[],
+            "escrow",
+            None,
+        )
+        .unwrap();
+
+    let send_nft = |app: &mut App, token_id: &str| {
+        app.execute_contract(
+            Addr::unchecked(DAO1),
+            cw721.clone(),
+            &cw721_base::msg::ExecuteMsg::<Option<Empty>, Empty>::SendNft {
+                contract: escrow.to_string(),
+                token_id: token_id.to_string(),
+                msg: to_json_binary("").unwrap(),
+            },
+            &[],
+        )
+    };
+
+    // Only promised NFTs may be provided.
+    let err: ContractError = send_nft(&mut app, "2").unwrap_err().downcast().unwrap();
+    assert_eq!(err, ContractError::InvalidFunds {});
+
+    // Providing the NFT alone does not complete the escrow, and it
+    // may be withdrawn.
+    send_nft(&mut app, "1").unwrap();
+    app.execute_contract(
+        Addr::unchecked(DAO1),
+        escrow.clone(),
+        &ExecuteMsg::Withdraw {},
+        &[],
+    )
+    .unwrap();
+    let owner: cw721::OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(
+            cw721.clone(),
+            &cw721::Cw721QueryMsg::OwnerOf {
+                token_id: "1".to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(owner.owner, DAO1.to_string());
+
+    send_nft(&mut app, "1").unwrap();
+    app.execute_contract(
+        Addr::unchecked(DAO1),
+        cw20.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: escrow.to_string(),
+            amount: Uint128::new(100),
+            msg: to_json_binary("").unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.sudo(SudoMsg::Bank(BankSudo::Mint {
+        to_address: DAO2.to_string(),
+        amount: vec![Coin {
+            amount: Uint128::new(100),
+            denom: "ujuno".to_string(),
+        }],
+    }))
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(DAO2),
+        escrow,
+        &ExecuteMsg::Fund {},
+        &[Coin {
+            amount: Uint128::new(100),
+            denom: "ujuno".to_string(),
+        }],
+    )
+    .unwrap();
+
+    let owner: cw721::OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(
+            cw721,
+            &cw721::Cw721QueryMsg::OwnerOf {
+                token_id: "1".to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(owner.owner, DAO2.to_string());
+
+    let cw20_balance: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            cw20,
+            &cw20::Cw20QueryMsg::Balance {
+                address: DAO2.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(cw20_balance.balance, Uint128::new(100));
+
+    let dao1_balance = app.wrap().query_balance(DAO1, "ujuno").unwrap();
+    assert_eq!(dao1_balance.amount, Uint128::new(100));
+}
+
 #[test]
 pub fn test_migrate_update_version() {
     let mut deps = mock_dependencies();
@@ -1,20 +1,24 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    from_json_binary, to_json_binary, Binary, Coin, CosmosMsg, DelegationResponse, Deps, DepsMut,
-    DistributionMsg, Env, MessageInfo, Response, StakingMsg, StakingQuery, StdResult, Timestamp,
-    Uint128,
+    from_json_binary, to_json_binary, Addr, Binary, Coin, CosmosMsg, DelegationResponse, Deps,
+    DepsMut, DistributionMsg, Env, MessageInfo, Order, Response, StakingMsg, StakingQuery,
+    StdResult, Storage, Timestamp, Uint128,
 };
 use cw2::set_contract_version;
 use cw20::Cw20ReceiveMsg;
 use cw_denom::CheckedDenom;
 use cw_ownable::OwnershipError;
+use cw_paginate_storage::paginate_map_values;
 use cw_utils::{must_pay, nonpayable};
 
 use crate::error::ContractError;
 use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, ReceiveMsg};
-use crate::state::{PAYMENT, UNBONDING_DURATION_SECONDS};
-use crate::vesting::{Status, VestInit};
+use crate::state::{
+    record_past_recipient, RecipientChange, PAST_RECIPIENTS, PAUSED, PAYMENT, PENDING_RECIPIENT,
+    PENDING_TRANSFER, RECIPIENT_CHANGE_DELAY_SECONDS, UNBONDING_DURATION_SECONDS,
+};
+use crate::vesting::{Status, Vest, VestInit};
 
 const CONTRACT_NAME: &str = "crates.io:cw-vesting";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -125,6 +129,19 @@ pub fn execute(
             amount,
             during_unbonding,
         } => execute_register_slash(deps, env, info, validator, time, amount, during_unbonding),
+        ExecuteMsg::Pause {} => execute_pause(deps, info),
+        ExecuteMsg::Unpause {} => execute_unpause(deps, info),
+        ExecuteMsg::ProposeRecipient { recipient } => {
+            execute_propose_recipient(deps, env, info, recipient)
+        }
+        ExecuteMsg::CompleteRecipientChange {} => {
+            execute_complete_recipient_change(deps, env, info)
+        }
+        ExecuteMsg::RequestTransfer { recipient } => {
+            execute_request_transfer(deps, info, recipient)
+        }
+        ExecuteMsg::AcceptTransfer {} => execute_accept_transfer(deps, env, info),
+        ExecuteMsg::CancelTransfer {} => execute_cancel_transfer(deps, info),
     }
 }
 
@@ -188,6 +205,7 @@ pub fn execute_distribute(
     deps: DepsMut,
     request: Option<Uint128>,
 ) -> Result<Response, ContractError> {
+    assert_not_paused(deps.storage)?;
     let msg = PAYMENT.distribute(deps.storage, env.block.time, request)?;
 
     Ok(Response::new()
@@ -244,6 +262,7 @@ pub fn execute_delegate(
             if info.sender != vest.recipient {
                 return Err(ContractError::NotReceiver);
             }
+            assert_not_paused(deps.storage)?;
         }
         Status::Canceled { .. } => return Err(ContractError::Cancelled),
     }
@@ -285,6 +304,7 @@ pub fn execute_redelegate(
             if info.sender != vest.recipient {
                 return Err(ContractError::NotReceiver);
             }
+            assert_not_paused(deps.storage)?;
         }
         Status::Canceled { .. } => return Err(ContractError::Cancelled),
     }
@@ -350,6 +370,7 @@ pub fn execute_undelegate(
             if info.sender != vest.recipient {
                 return Err(ContractError::NotReceiver);
             }
+            assert_not_paused(deps.storage)?;
         }
         // Anyone can undelegate while the contract is in the canceled
         // state. This is to prevent us from neededing to undelegate
@@ -387,6 +408,7 @@ pub fn execute_set_withdraw_address(
             if info.sender != vest.recipient {
                 return Err(ContractError::NotReceiver);
             }
+            assert_not_paused(deps.storage)?;
         }
         // In the cancelled state the owner is receiving staking
         // rewards and may update the withdraw address.
@@ -443,6 +465,194 @@ pub fn execute_register_slash(
     }
 }
 
+pub fn execute_pause(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    let vest = PAYMENT.get_vest(deps.storage)?;
+    if info.sender != vest.recipient {
+        return Err(ContractError::NotReceiver);
+    }
+    assert_not_paused(deps.storage)?;
+    // Only the owner may unpause the payment or approve a new
+    // receiver, so without one the vest would be stuck.
+    cw_ownable::get_ownership(deps.storage)?
+        .owner
+        .ok_or(OwnershipError::NoOwner)?;
+
+    PAUSED.save(deps.storage, &true)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "pause")
+        .add_attribute("recipient", vest.recipient))
+}
+
+pub fn execute_unpause(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    if !PAUSED.may_load(deps.storage)?.unwrap_or_default() {
+        return Err(ContractError::NotPaused);
+    }
+    PAUSED.remove(deps.storage);
+    PENDING_RECIPIENT.remove(deps.storage);
+
+    Ok(Response::default().add_attribute("method", "unpause"))
+}
+
+pub fn execute_propose_recipient(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    let vest = PAYMENT.get_vest(deps.storage)?;
+    if info.sender != vest.recipient {
+        return Err(ContractError::NotReceiver);
+    }
+    if !PAUSED.may_load(deps.storage)?.unwrap_or_default() {
+        return Err(ContractError::NotPaused);
+    }
+    if PENDING_RECIPIENT.may_load(deps.storage)?.is_some() {
+        return Err(ContractError::RecipientChangePending);
+    }
+
+    let change = RecipientChange {
+        recipient: deps.api.addr_validate(&recipient)?,
+        ready_at: env.block.time.plus_seconds(RECIPIENT_CHANGE_DELAY_SECONDS),
+    };
+    PENDING_RECIPIENT.save(deps.storage, &change)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "propose_recipient")
+        .add_attribute("recipient", change.recipient)
+        .add_attribute("ready_at", change.ready_at.to_string()))
+}
+
+pub fn execute_complete_recipient_change(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    let change = PENDING_RECIPIENT
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoPendingRecipient)?;
+    if env.block.time < change.ready_at {
+        return Err(ContractError::RecipientChangeTimelocked {
+            ready_at: change.ready_at,
+        });
+    }
+
+    PAUSED.remove(deps.storage);
+    PENDING_RECIPIENT.remove(deps.storage);
+    let (vest, msg) = change_recipient(deps, &env, change.recipient)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "complete_recipient_change")
+        .add_attribute("recipient", vest.recipient)
+        .add_messages(msg))
+}
+
+pub fn execute_request_transfer(
+    deps: DepsMut,
+    info: MessageInfo,
+    recipient: String,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    let vest = PAYMENT.get_vest(deps.storage)?;
+    if info.sender != vest.recipient {
+        return Err(ContractError::NotReceiver);
+    }
+    assert_not_paused(deps.storage)?;
+
+    let recipient = deps.api.addr_validate(&recipient)?;
+    PENDING_TRANSFER.save(deps.storage, &recipient)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "request_transfer")
+        .add_attribute("recipient", recipient))
+}
+
+pub fn execute_accept_transfer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    // A transfer requested before the receiver paused the payment may
+    // have been made by whoever compromised their key.
+    assert_not_paused(deps.storage)?;
+
+    let recipient = PENDING_TRANSFER
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoPendingTransfer)?;
+    PENDING_TRANSFER.remove(deps.storage);
+    let (vest, msg) = change_recipient(deps, &env, recipient)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "accept_transfer")
+        .add_attribute("recipient", vest.recipient)
+        .add_messages(msg))
+}
+
+pub fn execute_cancel_transfer(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    let vest = PAYMENT.get_vest(deps.storage)?;
+    if info.sender != vest.recipient {
+        cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    }
+    if PENDING_TRANSFER.may_load(deps.storage)?.is_none() {
+        return Err(ContractError::NoPendingTransfer);
+    }
+    PENDING_TRANSFER.remove(deps.storage);
+
+    Ok(Response::default().add_attribute("method", "cancel_transfer"))
+}
+
+/// Moves the vest to RECIPIENT, recording the previous receiver in
+/// the recipient history. Returns the updated vest and, if needed, a
+/// message directing staking rewards to the new receiver.
+fn change_recipient(
+    deps: DepsMut,
+    env: &Env,
+    recipient: Addr,
+) -> Result<(Vest, Option<DistributionMsg>), ContractError> {
+    let previous = PAYMENT.get_vest(deps.storage)?.recipient;
+    record_past_recipient(deps.storage, previous, env.block.time)?;
+    let vest = PAYMENT.set_recipient(deps.storage, recipient)?;
+
+    // Staking rewards go to the receiver while the payment is
+    // funded. Once canceled they go to the owner, so are left alone.
+    let msg = match vest.status {
+        Status::Funded if vest.denom.is_native(&deps.querier.query_bonded_denom()?) => {
+            Some(DistributionMsg::SetWithdrawAddress {
+                address: vest.recipient.to_string(),
+            })
+        }
+        _ => None,
+    };
+    Ok((vest, msg))
+}
+
+/// Errors if the vest receiver has paused the payment.
+fn assert_not_paused(storage: &dyn Storage) -> Result<(), ContractError> {
+    if PAUSED.may_load(storage)?.unwrap_or_default() {
+        Err(ContractError::Paused)
+    } else {
+        Ok(())
+    }
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -459,7 +669,18 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
                 .get_vest(deps.storage)?
                 .vested(t.unwrap_or(env.block.time)),
         ),
+        QueryMsg::VestingPoints {} => to_json_binary(&PAYMENT.get_vest(deps.storage)?.points()),
         QueryMsg::TotalToVest {} => to_json_binary(&PAYMENT.get_vest(deps.storage)?.total()),
         QueryMsg::VestDuration {} => to_json_binary(&PAYMENT.duration(deps.storage)?),
+        QueryMsg::Paused {} => to_json_binary(&PAUSED.may_load(deps.storage)?.unwrap_or_default()),
+        QueryMsg::PendingRecipient {} => to_json_binary(&PENDING_RECIPIENT.may_load(deps.storage)?),
+        QueryMsg::PendingTransfer {} => to_json_binary(&PENDING_TRANSFER.may_load(deps.storage)?),
+        QueryMsg::PastRecipients { start_after, limit } => to_json_binary(&paginate_map_values(
+            deps,
+            &PAST_RECIPIENTS,
+            start_after,
+            limit,
+            Order::Ascending,
+        )?),
     }
 }
@@ -1,4 +1,4 @@
-use cosmwasm_std::{StdError, Uint128};
+use cosmwasm_std::{StdError, Timestamp, Uint128};
 use cw_denom::DenomError;
 use cw_ownable::OwnershipError;
 use cw_utils::PaymentError;
@@ -81,4 +81,22 @@ pub enum ContractError {
 
     #[error("can't register a slash event occuring in the future")]
     FutureSlash,
+
+    #[error("payment is paused")]
+    Paused,
+
+    #[error("payment is not paused")]
+    NotPaused,
+
+    #[error("no vest receiver change is pending")]
+    NoPendingRecipient,
+
+    #[error("a vest receiver change is already pending")]
+    RecipientChangePending,
+
+    #[error("no vest transfer is pending")]
+    NoPendingTransfer,
+
+    #[error("vest receiver change may not be completed until ({ready_at})")]
+    RecipientChangeTimelocked { ready_at: Timestamp },
 }
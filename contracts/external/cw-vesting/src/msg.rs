@@ -179,6 +179,44 @@ pub enum ExecuteMsg {
         /// the common case where the slash impacted bonding tokens.
         during_unbonding: bool,
     },
+    /// Pauses the payment. While paused, vested tokens may not be
+    /// distributed and the vest receiver may not stake or set the
+    /// staking reward withdraw address. Only callable by the vest
+    /// receiver.
+    ///
+    /// This is intended for use if the receiver's key is
+    /// compromised. As the key may also be held by an attacker, the
+    /// receiver can not unpause the payment. It may be unpaused by
+    /// the owner, or by completing a change of vest receiver. Fails
+    /// if the contract has no owner.
+    Pause {},
+    /// Unpauses the payment, discarding any pending change of vest
+    /// receiver. Only callable by the owner.
+    Unpause {},
+    /// Proposes a new vest receiver. The owner may approve the
+    /// change with `CompleteRecipientChange` once
+    /// `RECIPIENT_CHANGE_DELAY_SECONDS` have passed, or reject it
+    /// with `Unpause`. Fails if a change is already pending. Only
+    /// callable by the vest receiver while the payment is paused.
+    ProposeRecipient { recipient: String },
+    /// Completes a pending change of vest receiver and unpauses the
+    /// payment. Future distributions and staking rewards go to the
+    /// new receiver. Only callable by the owner once the delay has
+    /// passed.
+    CompleteRecipientChange {},
+    /// Requests that the remainder of the vest be transferred to a
+    /// new receiver, for example when moving to a new wallet or
+    /// selling the vesting position. The transfer takes effect once
+    /// accepted by the owner. Requesting again replaces any pending
+    /// request. Only callable by the vest receiver while the payment
+    /// is not paused.
+    RequestTransfer { recipient: String },
+    /// Accepts the pending transfer, moving the vest to the new
+    /// receiver. Only callable by the owner.
+    AcceptTransfer {},
+    /// Discards the pending transfer. Callable by the owner or the
+    /// vest receiver.
+    CancelTransfer {},
 }
 
 #[cw_serde]
@@ -210,6 +248,10 @@ pub enum QueryMsg {
     /// current time is used.
     #[returns(::cosmwasm_std::Uint128)]
     Vested { t: Option<Timestamp> },
+    /// Gets the (time, vested) points defining the vesting curve,
+    /// from which `vested(t)` at any time can be plotted.
+    #[returns(Vec<(::cosmwasm_std::Timestamp, ::cosmwasm_std::Uint128)>)]
+    VestingPoints {},
     /// Gets the total amount that will ever vest, `max(vested(t))`.
     ///
     /// Note that if the contract is canceled at time c, this value
@@ -227,4 +269,21 @@ pub enum QueryMsg {
     /// query methods and their return types.
     #[returns(::cosmwasm_std::Uint128)]
     Stake(StakeTrackerQuery),
+    /// Returns true if the payment has been paused by the vest
+    /// receiver.
+    #[returns(bool)]
+    Paused {},
+    /// Gets the pending change of vest receiver, if any.
+    #[returns(Option<crate::state::RecipientChange>)]
+    PendingRecipient {},
+    /// Gets the receiver of the pending transfer, if any.
+    #[returns(Option<::cosmwasm_std::Addr>)]
+    PendingTransfer {},
+    /// Lists the addresses that previously received the vest, oldest
+    /// first.
+    #[returns(Vec<crate::state::PastRecipient>)]
+    PastRecipients {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
 }
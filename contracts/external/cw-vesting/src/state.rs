@@ -1,6 +1,52 @@
-use cw_storage_plus::Item;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, StdResult, Storage, Timestamp};
+use cw_storage_plus::{Item, Map};
 
 use crate::vesting::Payment;
 
 pub const PAYMENT: Payment = Payment::new("vesting", "staked", "validator", "cardinality");
 pub const UNBONDING_DURATION_SECONDS: Item<u64> = Item::new("ubs");
+
+/// The number of seconds that must pass between a new vest receiver
+/// being proposed and the change taking effect.
+pub const RECIPIENT_CHANGE_DELAY_SECONDS: u64 = 60 * 60 * 24 * 7; // one week
+
+/// A pending change to the vest receiver.
+#[cw_serde]
+pub struct RecipientChange {
+    /// The address that will receive the vest once the change is
+    /// completed.
+    pub recipient: Addr,
+    /// The time after which the change may be completed.
+    pub ready_at: Timestamp,
+}
+
+/// Set if the vest receiver has paused the payment.
+pub const PAUSED: Item<bool> = Item::new("paused");
+pub const PENDING_RECIPIENT: Item<RecipientChange> = Item::new("pending_recipient");
+/// A transfer of the vest requested by the vest receiver, pending
+/// the owner's approval.
+pub const PENDING_TRANSFER: Item<Addr> = Item::new("pending_transfer");
+
+/// An address that has previously received the vest.
+#[cw_serde]
+pub struct PastRecipient {
+    pub recipient: Addr,
+    /// The time at which the vest moved to a new receiver.
+    pub until: Timestamp,
+}
+
+/// Past vest receivers, keyed by the order in which they were
+/// replaced.
+pub const PAST_RECIPIENTS: Map<u64, PastRecipient> = Map::new("past_recipients");
+const PAST_RECIPIENT_COUNT: Item<u64> = Item::new("past_recipient_count");
+
+pub(crate) fn record_past_recipient(
+    storage: &mut dyn Storage,
+    recipient: Addr,
+    until: Timestamp,
+) -> StdResult<()> {
+    let index = PAST_RECIPIENT_COUNT.may_load(storage)?.unwrap_or_default();
+    PAST_RECIPIENT_COUNT.save(storage, &(index + 1))?;
+    PAST_RECIPIENTS.save(storage, index, &PastRecipient { recipient, until })
+}
@@ -6,6 +6,7 @@ use dao_testing::contracts::cw_vesting_contract;
 
 use crate::{
     msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    state::{PastRecipient, RecipientChange},
     vesting::{Schedule, Vest},
     StakeTrackerQuery,
 };
@@ -127,6 +128,11 @@ impl SuiteBuilder {
         self.instantiate.schedule = s;
         self
     }
+
+    pub fn with_owner(mut self, owner: Option<&str>) -> Self {
+        self.instantiate.owner = owner.map(str::to_string);
+        self
+    }
 }
 
 impl Suite {
@@ -337,6 +343,95 @@ impl Suite {
             )
             .map(|_| ())
     }
+
+    pub fn pause<S: Into<String>>(&mut self, sender: S) -> anyhow::Result<()> {
+        self.app
+            .execute_contract(
+                Addr::unchecked(sender),
+                self.vesting.clone(),
+                &ExecuteMsg::Pause {},
+                &[],
+            )
+            .map(|_| ())
+    }
+
+    pub fn unpause<S: Into<String>>(&mut self, sender: S) -> anyhow::Result<()> {
+        self.app
+            .execute_contract(
+                Addr::unchecked(sender),
+                self.vesting.clone(),
+                &ExecuteMsg::Unpause {},
+                &[],
+            )
+            .map(|_| ())
+    }
+
+    pub fn propose_recipient<S: Into<String>>(
+        &mut self,
+        sender: S,
+        recipient: S,
+    ) -> anyhow::Result<()> {
+        self.app
+            .execute_contract(
+                Addr::unchecked(sender),
+                self.vesting.clone(),
+                &ExecuteMsg::ProposeRecipient {
+                    recipient: recipient.into(),
+                },
+                &[],
+            )
+            .map(|_| ())
+    }
+
+    pub fn complete_recipient_change<S: Into<String>>(&mut self, sender: S) -> anyhow::Result<()> {
+        self.app
+            .execute_contract(
+                Addr::unchecked(sender),
+                self.vesting.clone(),
+                &ExecuteMsg::CompleteRecipientChange {},
+                &[],
+            )
+            .map(|_| ())
+    }
+
+    pub fn request_transfer<S: Into<String>>(
+        &mut self,
+        sender: S,
+        recipient: S,
+    ) -> anyhow::Result<()> {
+        self.app
+            .execute_contract(
+                Addr::unchecked(sender),
+                self.vesting.clone(),
+                &ExecuteMsg::RequestTransfer {
+                    recipient: recipient.into(),
+                },
+                &[],
+            )
+            .map(|_| ())
+    }
+
+    pub fn accept_transfer<S: Into<String>>(&mut self, sender: S) -> anyhow::Result<()> {
+        self.app
+            .execute_contract(
+                Addr::unchecked(sender),
+                self.vesting.clone(),
+                &ExecuteMsg::AcceptTransfer {},
+                &[],
+            )
+            .map(|_| ())
+    }
+
+    pub fn cancel_transfer<S: Into<String>>(&mut self, sender: S) -> anyhow::Result<()> {
+        self.app
+            .execute_contract(
+                Addr::unchecked(sender),
+                self.vesting.clone(),
+                &ExecuteMsg::CancelTransfer {},
+                &[],
+            )
+            .map(|_| ())
+    }
 }
 
 // query
@@ -394,4 +489,38 @@ impl Suite {
             .query_wasm_smart(&self.vesting, &QueryMsg::VestDuration {})
             .unwrap()
     }
+
+    pub fn query_paused(&self) -> bool {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.vesting, &QueryMsg::Paused {})
+            .unwrap()
+    }
+
+    pub fn query_pending_recipient(&self) -> Option<RecipientChange> {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.vesting, &QueryMsg::PendingRecipient {})
+            .unwrap()
+    }
+
+    pub fn query_pending_transfer(&self) -> Option<Addr> {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.vesting, &QueryMsg::PendingTransfer {})
+            .unwrap()
+    }
+
+    pub fn query_past_recipients(&self) -> Vec<PastRecipient> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                &self.vesting,
+                &QueryMsg::PastRecipients {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap()
+    }
 }
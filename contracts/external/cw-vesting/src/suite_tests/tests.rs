@@ -1,8 +1,9 @@
-use cosmwasm_std::{Timestamp, Uint128, Uint64};
+use cosmwasm_std::{Addr, Timestamp, Uint128, Uint64};
 use cw_multi_test::App;
 use cw_ownable::OwnershipError;
 
 use crate::{
+    state::PastRecipient,
     vesting::{Schedule, Status},
     ContractError,
 };
@@ -680,3 +681,161 @@ fn test_piecewise_and_queries() {
     let duration = suite.query_duration();
     assert_eq!(duration, None);
 }
+
+/// The receiver may pause the vest if their key is compromised, and
+/// after a delay move the payment to a new address with the owner's
+/// approval.
+#[test]
+fn test_pause_and_change_recipient() {
+    let mut suite = SuiteBuilder::default().build();
+    suite.a_day_passes();
+
+    // only the receiver may pause.
+    let res = suite.pause("random");
+    is_error!(res, ContractError::NotReceiver.to_string().as_str());
+    let res = suite.propose_recipient("recipient", "new_recipient");
+    is_error!(res, ContractError::NotPaused.to_string().as_str());
+
+    suite.pause("recipient").unwrap();
+    assert!(suite.query_paused());
+
+    let res = suite.distribute("random", None);
+    is_error!(res, ContractError::Paused.to_string().as_str());
+    let res = suite.delegate(Uint128::new(10));
+    is_error!(res, ContractError::Paused.to_string().as_str());
+
+    // a pending change may not be replaced, and the owner may
+    // unpause, discarding it.
+    suite.propose_recipient("recipient", "attacker").unwrap();
+    let res = suite.propose_recipient("recipient", "new_recipient");
+    is_error!(
+        res,
+        ContractError::RecipientChangePending.to_string().as_str()
+    );
+    suite.a_week_passes();
+    let res = suite.complete_recipient_change("random");
+    is_error!(res, OwnershipError::NotOwner.to_string().as_str());
+    let res = suite.unpause("recipient");
+    is_error!(res, OwnershipError::NotOwner.to_string().as_str());
+    suite.unpause("owner").unwrap();
+    assert!(!suite.query_paused());
+    assert_eq!(suite.query_pending_recipient(), None);
+
+    suite.pause("recipient").unwrap();
+    suite
+        .propose_recipient("recipient", "new_recipient")
+        .unwrap();
+    let ready_at = suite.query_pending_recipient().unwrap().ready_at;
+
+    let res = suite.complete_recipient_change("owner");
+    is_error!(
+        res,
+        ContractError::RecipientChangeTimelocked { ready_at }
+            .to_string()
+            .as_str()
+    );
+
+    // only the owner may complete the change.
+    suite.a_week_passes();
+    let res = suite.complete_recipient_change("recipient");
+    is_error!(res, OwnershipError::NotOwner.to_string().as_str());
+    suite.complete_recipient_change("owner").unwrap();
+    assert!(!suite.query_paused());
+    assert_eq!(suite.query_vest().recipient, "new_recipient");
+
+    let res = suite.complete_recipient_change("owner");
+    is_error!(res, ContractError::NoPendingRecipient.to_string().as_str());
+
+    // the new recipient receives the vested tokens.
+    suite.distribute("random", None).unwrap();
+    assert_eq!(
+        suite.query_vesting_token_balance("new_recipient"),
+        suite.total
+    );
+
+    // without an owner nobody could unpause the payment.
+    let mut suite = SuiteBuilder::default().with_owner(None).build();
+    let res = suite.pause("recipient");
+    is_error!(res, OwnershipError::NoOwner.to_string().as_str());
+}
+
+/// The receiver may transfer the remainder of the vest to a new
+/// address with the owner's approval.
+#[test]
+fn test_transfer_vest() {
+    let mut suite = SuiteBuilder::default().build();
+    suite.a_day_passes();
+
+    // only the receiver may request a transfer.
+    let res = suite.request_transfer("random", "buyer");
+    is_error!(res, ContractError::NotReceiver.to_string().as_str());
+    let res = suite.accept_transfer("owner");
+    is_error!(res, ContractError::NoPendingTransfer.to_string().as_str());
+
+    suite.request_transfer("recipient", "attacker").unwrap();
+    suite.request_transfer("recipient", "buyer").unwrap();
+    assert_eq!(
+        suite.query_pending_transfer(),
+        Some(Addr::unchecked("buyer"))
+    );
+
+    // only the owner may accept it.
+    let res = suite.accept_transfer("recipient");
+    is_error!(res, OwnershipError::NotOwner.to_string().as_str());
+
+    // either party may cancel it.
+    let res = suite.cancel_transfer("random");
+    is_error!(res, OwnershipError::NotOwner.to_string().as_str());
+    suite.cancel_transfer("recipient").unwrap();
+    assert_eq!(suite.query_pending_transfer(), None);
+    let res = suite.cancel_transfer("owner");
+    is_error!(res, ContractError::NoPendingTransfer.to_string().as_str());
+
+    // transfers may not be accepted while the payment is paused.
+    suite.request_transfer("recipient", "buyer").unwrap();
+    suite.pause("recipient").unwrap();
+    let res = suite.accept_transfer("owner");
+    is_error!(res, ContractError::Paused.to_string().as_str());
+    suite.unpause("owner").unwrap();
+
+    // tokens vested before the transfer go to the new receiver
+    // along with the rest of the vest.
+    suite.distribute("random", Some(Uint128::new(10))).unwrap();
+    suite.accept_transfer("owner").unwrap();
+    let transferred_at = suite.time();
+    assert_eq!(suite.query_vest().recipient, "buyer");
+    assert_eq!(suite.query_pending_transfer(), None);
+    assert_eq!(
+        suite.query_past_recipients(),
+        vec![PastRecipient {
+            recipient: Addr::unchecked("recipient"),
+            until: transferred_at,
+        }]
+    );
+
+    suite.a_week_passes();
+    suite.distribute("random", None).unwrap();
+    assert_eq!(
+        suite.query_vesting_token_balance("recipient"),
+        Uint128::new(10)
+    );
+    assert_eq!(
+        suite.query_vesting_token_balance("buyer"),
+        suite.total - Uint128::new(10)
+    );
+
+    // the history also covers emergency recipient changes.
+    suite.pause("buyer").unwrap();
+    suite.propose_recipient("buyer", "new_wallet").unwrap();
+    suite.a_week_passes();
+    suite.complete_recipient_change("owner").unwrap();
+    let past: Vec<Addr> = suite
+        .query_past_recipients()
+        .into_iter()
+        .map(|p| p.recipient)
+        .collect();
+    assert_eq!(
+        past,
+        vec![Addr::unchecked("recipient"), Addr::unchecked("buyer")]
+    );
+}
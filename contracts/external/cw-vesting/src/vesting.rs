@@ -6,7 +6,7 @@ use cosmwasm_std::{
 };
 use cw_denom::CheckedDenom;
 use cw_storage_plus::Item;
-use wynd_utils::{Curve, PiecewiseLinear, SaturatingLinear};
+use wynd_utils::{Curve, CurveError, PiecewiseLinear, SaturatingLinear};
 
 use cw_stake_tracker::{StakeTracker, StakeTrackerQuery};
 
@@ -330,6 +330,16 @@ impl<'a> Payment<'a> {
         Ok(())
     }
 
+    /// Changes the vest receiver. The receiver's claims on vested
+    /// tokens move with the vest, so no accounting changes are
+    /// needed.
+    pub fn set_recipient(&self, storage: &mut dyn Storage, recipient: Addr) -> StdResult<Vest> {
+        let mut v = self.vesting.load(storage)?;
+        v.recipient = recipient;
+        self.vesting.save(storage, &v)?;
+        Ok(v)
+    }
+
     pub fn set_funded(&self, storage: &mut dyn Storage) -> Result<(), ContractError> {
         let mut v = self.vesting.load(storage)?;
         debug_assert!(v.status == Status::Unfunded);
@@ -437,6 +447,28 @@ impl Vest {
         self.vested.value(elapsed)
     }
 
+    /// Gets the points that define the vesting curve as (time,
+    /// vested) pairs. `vested(t)` linearly interpolates between
+    /// consecutive points and is constant before the first and
+    /// after the last point, so these are sufficient to plot the
+    /// vest. Once canceled, the single point is the amount that
+    /// vested before cancelation.
+    pub fn points(&self) -> Vec<(Timestamp, Uint128)> {
+        let at = |x: u64, y: Uint128| (self.start_time.plus_seconds(x), y);
+        match &self.vested {
+            Curve::Constant { y } => vec![at(0, *y)],
+            Curve::SaturatingLinear(SaturatingLinear {
+                min_x,
+                min_y,
+                max_x,
+                max_y,
+            }) => vec![at(*min_x, *min_y), at(*max_x, *max_y)],
+            Curve::PiecewiseLinear(PiecewiseLinear { steps }) => {
+                steps.iter().map(|(x, y)| at(*x, *y)).collect()
+            }
+        }
+    }
+
     /// Cancels the current vest. No additional tokens will vest after `t`.
     pub fn cancel(&mut self, t: Timestamp, owner_withdrawable: Uint128) {
         debug_assert!(!matches!(self.status, Status::Canceled { .. }));
@@ -467,12 +499,19 @@ impl Schedule {
     /// 2. it must end at total,
     /// 3. it must never decrease.
     ///
-    /// Piecewise curves must have at least two steps. One step would
-    /// be a constant vest (why would you want this?).
+    /// Piecewise curves must have at least two steps, with strictly
+    /// increasing times. One step would be a constant vest (why would
+    /// you want this?).
     ///
     /// A schedule is valid if `total` is zero: nothing will ever be
     /// paid out. Consumers should consider validating that `total` is
     /// non-zero.
+    pub fn validate(&self, total: Uint128, duration_seconds: u64) -> Result<(), ContractError> {
+        self.clone().into_curve(total, duration_seconds).map(|_| ())
+    }
+
+    /// Converts the schedule into the curve tracking vested(t),
+    /// validating it as described in `Schedule::validate`.
     pub fn into_curve(self, total: Uint128, duration_seconds: u64) -> Result<Curve, ContractError> {
         let c = match self {
             Schedule::SaturatingLinear => {
@@ -482,6 +521,11 @@ impl Schedule {
                 if steps.len() < 2 {
                     return Err(ContractError::ConstantVest);
                 }
+                // Each point must come strictly after the one before
+                // it, otherwise vested(t) is ambiguous.
+                if steps.windows(2).any(|pair| pair[0].0 >= pair[1].0) {
+                    return Err(CurveError::PointsOutOfOrder.into());
+                }
                 Curve::PiecewiseLinear(wynd_utils::PiecewiseLinear { steps })
             }
         };
@@ -158,6 +158,62 @@ fn test_vesting_validation() {
             max: Uint128::new(2)
         })
     );
+
+    // Two points at the same time.
+    let schedule = Schedule::PiecewiseLinear(vec![
+        (1, Uint128::zero()),
+        (2, Uint128::one()),
+        (2, Uint128::new(2)),
+    ]);
+    assert_eq!(
+        schedule.validate(Uint128::new(2), 100),
+        Err(ContractError::Curve(CurveError::PointsOutOfOrder))
+    );
+
+    let schedule = Schedule::PiecewiseLinear(vec![
+        (1, Uint128::zero()),
+        (2, Uint128::one()),
+        (3, Uint128::new(2)),
+    ]);
+    assert_eq!(schedule.validate(Uint128::new(2), 100), Ok(()));
+}
+
+#[test]
+fn test_vesting_points() {
+    let vest = Vest::new(VestInit {
+        schedule: Schedule::PiecewiseLinear(vec![
+            (1, Uint128::zero()),
+            (3, Uint128::new(4)),
+            (5, Uint128::new(8)),
+        ]),
+        total: Uint128::new(8),
+        start_time: Timestamp::from_seconds(10),
+        ..Default::default()
+    })
+    .unwrap();
+    assert_eq!(
+        vest.points(),
+        vec![
+            (Timestamp::from_seconds(11), Uint128::zero()),
+            (Timestamp::from_seconds(13), Uint128::new(4)),
+            (Timestamp::from_seconds(15), Uint128::new(8)),
+        ]
+    );
+
+    let mut vest = Vest::new(VestInit::default()).unwrap();
+    assert_eq!(
+        vest.points(),
+        vec![
+            (Timestamp::from_seconds(0), Uint128::zero()),
+            (Timestamp::from_seconds(100), Uint128::new(100_000_000)),
+        ]
+    );
+
+    vest.cancel(Timestamp::from_seconds(50), Uint128::new(50_000_000));
+    assert_eq!(
+        vest.points(),
+        vec![(Timestamp::from_seconds(0), Uint128::new(50_000_000))]
+    );
 }
 
 // owner and vestee. vestee has vested 50 tokens out of 100. 10 are
@@ -20,7 +20,8 @@ use crate::{
         CodeIdPair, MigrationMsgs, MigrationParams, ModulesAddrs, TestState, V1CodeIds, V2CodeIds,
     },
     utils::state_queries::{
-        query_proposal_count_v1, query_proposal_count_v2, query_proposal_v1, query_proposal_v2,
+        query_open_proposal_count_v1, query_open_proposal_count_v2, query_proposal_count_v1,
+        query_proposal_count_v2, query_proposal_v1, query_proposal_v2,
         query_single_voting_power_v1, query_single_voting_power_v2, query_total_voting_power_v1,
         query_total_voting_power_v2,
     },
@@ -230,6 +231,7 @@ fn execute_migration_v1_v2(
         &dao_interface::msg::QueryMsg::ProposalModules {
             start_after: None,
             limit: None,
+            descending: None,
         },
     )?;
 
@@ -370,6 +372,7 @@ pub fn reply(deps: DepsMut, env: Env, reply: Reply) -> Result<Response, Contract
 
 fn query_state_v1(deps: Deps, module_addrs: ModulesAddrs) -> Result<TestState, ContractError> {
     let proposal_counts = query_proposal_count_v1(deps, module_addrs.proposals.clone())?;
+    let open_proposal_counts = query_open_proposal_count_v1(deps, module_addrs.proposals.clone())?;
     let (proposals, sample_proposal_data) = query_proposal_v1(deps, module_addrs.proposals)?;
     let total_voting_power = query_total_voting_power_v1(
         deps,
@@ -385,6 +388,7 @@ fn query_state_v1(deps: Deps, module_addrs: ModulesAddrs) -> Result<TestState, C
 
     Ok(TestState {
         proposal_counts,
+        open_proposal_counts,
         proposals,
         total_voting_power,
         single_voting_power,
@@ -393,6 +397,7 @@ fn query_state_v1(deps: Deps, module_addrs: ModulesAddrs) -> Result<TestState, C
 
 fn query_state_v2(deps: Deps, module_addrs: ModulesAddrs) -> Result<TestState, ContractError> {
     let proposal_counts = query_proposal_count_v2(deps, module_addrs.proposals.clone())?;
+    let open_proposal_counts = query_open_proposal_count_v2(deps, module_addrs.proposals.clone())?;
     let (proposals, sample_proposal_data) =
         query_proposal_v2(deps, module_addrs.proposals.clone())?;
     let total_voting_power = query_total_voting_power_v2(
@@ -409,6 +414,7 @@ fn query_state_v2(deps: Deps, module_addrs: ModulesAddrs) -> Result<TestState, C
 
     Ok(TestState {
         proposal_counts,
+        open_proposal_counts,
         proposals,
         total_voting_power,
         single_voting_power,
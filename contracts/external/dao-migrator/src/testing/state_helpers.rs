@@ -50,10 +50,12 @@ pub fn query_proposal_v1(
         expiration: v1_expiration_to_v2(proposal.expiration),
         threshold: v1_threshold_to_v2(proposal.threshold),
         total_power: proposal.total_power,
+        quorum_power: None,
         msgs: proposal.msgs,
         status: v1_status_to_v2(proposal.status),
         votes: v1_votes_to_v2(proposal.votes),
         allow_revoting: proposal.allow_revoting,
+        instant_finality: false,
     };
 
     (proposal_count, proposal)
@@ -80,6 +82,7 @@ pub fn query_proposal_v2(
             &dao_proposal_single::msg::QueryMsg::ListProposals {
                 start_after: None,
                 limit: None,
+                descending: None,
             },
         )
         .unwrap()
@@ -17,7 +17,10 @@ use crate::{
     ContractError,
 };
 
-use super::{helpers::demo_contract, setup::setup_dao_v1_multiple_proposals};
+use super::{
+    helpers::{demo_contract, set_dummy_proposal, SENDER_ADDR},
+    setup::setup_dao_v1_multiple_proposals,
+};
 
 pub fn basic_test(voting_type: VotingType, from_core: bool) {
     let (mut app, module_addrs, v1_code_ids) = setup_dao_v1(voting_type.clone());
@@ -78,6 +81,7 @@ pub fn basic_test(voting_type: VotingType, from_core: bool) {
             &dao_interface::msg::QueryMsg::ProposalModules {
                 start_after: None,
                 limit: None,
+                descending: None,
             },
         )
         .unwrap();
@@ -146,6 +150,7 @@ fn test_migrator_address_is_first() {
             &dao_interface::msg::QueryMsg::ProposalModules {
                 start_after: None,
                 limit: None,
+                descending: None,
             },
         )
         .unwrap();
@@ -355,9 +360,85 @@ fn test_sub_daos() {
             &dao_interface::msg::QueryMsg::ListSubDaos {
                 start_after: None,
                 limit: None,
+                descending: None,
             },
         )
         .unwrap();
 
     assert_eq!(sub_daos, vec![sub_dao]);
 }
+
+#[test]
+fn test_migrate_with_open_proposals() {
+    let (mut app, module_addrs, v1_code_ids) = setup_dao_v1(VotingType::Cw4);
+    let sender = Addr::unchecked(SENDER_ADDR);
+
+    // The setup leaves one open proposal. Add two more.
+    for _ in 0..2 {
+        set_dummy_proposal(
+            app.borrow_mut(),
+            sender.clone(),
+            module_addrs.core.clone(),
+            module_addrs.proposals[0].clone(),
+        );
+    }
+    let open_v1: Vec<u64> = app
+        .wrap()
+        .query_wasm_smart::<cw_proposal_single_v1::query::ProposalListResponse>(
+            module_addrs.proposals[0].clone(),
+            &cw_proposal_single_v1::msg::QueryMsg::ListProposals {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap()
+        .proposals
+        .into_iter()
+        .filter(|p| matches!(p.proposal.status, voting_v1::Status::Open))
+        .map(|p| p.id)
+        .collect();
+    assert_eq!(open_v1, vec![1, 2, 3]);
+
+    // The migrator checks that the open proposals are still open
+    // after the migration.
+    execute_migration(app.borrow_mut(), &module_addrs, v1_code_ids, None, None).unwrap();
+
+    let open_v2: Vec<u64> = app
+        .wrap()
+        .query_wasm_smart::<dao_proposal_single::query::ProposalListResponse>(
+            module_addrs.proposals[0].clone(),
+            &dao_proposal_single::msg::QueryMsg::ListProposals {
+                start_after: None,
+                limit: None,
+                descending: None,
+            },
+        )
+        .unwrap()
+        .proposals
+        .into_iter()
+        .filter(|p| p.proposal.status == dao_voting::status::Status::Open)
+        .map(|p| p.id)
+        .collect();
+    assert_eq!(open_v2, open_v1);
+
+    // Open proposals may still be voted on once migrated.
+    app.execute_contract(
+        sender,
+        module_addrs.proposals[0].clone(),
+        &dao_proposal_single::msg::ExecuteMsg::Vote {
+            proposal_id: 2,
+            vote: dao_voting::voting::Vote::Yes,
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+    let proposal: dao_proposal_single::query::ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            module_addrs.proposals[0].clone(),
+            &dao_proposal_single::msg::QueryMsg::Proposal { proposal_id: 2 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.status, dao_voting::status::Status::Passed);
+}
@@ -124,6 +124,8 @@ pub struct SingleProposalData {
 #[cw_serde]
 pub struct TestState {
     pub proposal_counts: Vec<u64>,
+    /// Number of open proposals in each proposal module
+    pub open_proposal_counts: Vec<u64>,
     pub proposals: Vec<dao_proposal_single::proposal::SingleChoiceProposal>,
     pub total_voting_power: Uint128,
     /// This is the voting power of the proposer of the sample proposal
@@ -30,6 +30,77 @@ pub fn query_proposal_count_v2(deps: Deps, proposals_addrs: Vec<Addr>) -> StdRes
         .collect()
 }
 
+/// Open proposals are counted among at most this many of each proposal
+/// module's most recent proposals. Older proposals have almost always
+/// closed, and bounding the scan bounds the gas the migration uses.
+const OPEN_PROPOSAL_SCAN_LIMIT: u64 = 300;
+/// The number of proposals queried at a time while counting.
+const OPEN_PROPOSAL_PAGE_SIZE: u64 = 30;
+
+pub fn query_open_proposal_count_v1(deps: Deps, proposals_addrs: Vec<Addr>) -> StdResult<Vec<u64>> {
+    proposals_addrs
+        .into_iter()
+        .map(|proposal_addr| {
+            let mut count = 0;
+            let mut scanned = 0;
+            let mut start_before = None;
+            while scanned < OPEN_PROPOSAL_SCAN_LIMIT {
+                let page: cw_proposal_single_v1::query::ProposalListResponse =
+                    deps.querier.query_wasm_smart(
+                        proposal_addr.clone(),
+                        &cw_proposal_single_v1::msg::QueryMsg::ReverseProposals {
+                            start_before,
+                            limit: Some(OPEN_PROPOSAL_PAGE_SIZE),
+                        },
+                    )?;
+                match page.proposals.last() {
+                    Some(last) => start_before = Some(last.id),
+                    None => break,
+                }
+                scanned += page.proposals.len() as u64;
+                count += page
+                    .proposals
+                    .iter()
+                    .filter(|p| matches!(p.proposal.status, voting_v1::Status::Open))
+                    .count() as u64;
+            }
+            Ok(count)
+        })
+        .collect()
+}
+
+pub fn query_open_proposal_count_v2(deps: Deps, proposals_addrs: Vec<Addr>) -> StdResult<Vec<u64>> {
+    proposals_addrs
+        .into_iter()
+        .map(|proposal_addr| {
+            let mut count = 0;
+            let mut scanned = 0;
+            let mut start_before = None;
+            while scanned < OPEN_PROPOSAL_SCAN_LIMIT {
+                let page: dao_proposal_single::query::ProposalListResponse =
+                    deps.querier.query_wasm_smart(
+                        proposal_addr.clone(),
+                        &dao_proposal_single::msg::QueryMsg::ReverseProposals {
+                            start_before,
+                            limit: Some(OPEN_PROPOSAL_PAGE_SIZE),
+                        },
+                    )?;
+                match page.proposals.last() {
+                    Some(last) => start_before = Some(last.id),
+                    None => break,
+                }
+                scanned += page.proposals.len() as u64;
+                count += page
+                    .proposals
+                    .iter()
+                    .filter(|p| p.proposal.status == dao_voting::status::Status::Open)
+                    .count() as u64;
+            }
+            Ok(count)
+        })
+        .collect()
+}
+
 pub fn query_proposal_v1(
     deps: Deps,
     proposals_addrs: Vec<Addr>,
@@ -79,10 +150,12 @@ pub fn query_proposal_v1(
                 expiration: v1_expiration_to_v2(proposal.expiration),
                 threshold: v1_threshold_to_v2(proposal.threshold),
                 total_power: proposal.total_power,
+                quorum_power: None,
                 msgs: proposal.msgs,
                 status: v1_status_to_v2(proposal.status),
                 votes: v1_votes_to_v2(proposal.votes),
                 allow_revoting: proposal.allow_revoting,
+                instant_finality: false,
             })
         })
         .collect::<Result<Vec<dao_proposal_single::proposal::SingleChoiceProposal>, ContractError>>(
@@ -0,0 +1,287 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Order, Response,
+    StdResult, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw4::Member;
+use cw_storage_plus::Bound;
+use cw_utils::Duration;
+
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, InstantiateMsg, OnboardingResponse, QueryMsg};
+use crate::state::{Config, Onboarding, CONFIG, ONBOARDING};
+
+pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-onboarding";
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+pub const DEFAULT_LIMIT: u32 = 10;
+pub const MAX_LIMIT: u32 = 30;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    cw_ownable::initialize_owner(deps.storage, deps.api, msg.owner.as_deref())?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    if msg.epochs == 0 || matches!(msg.epoch_duration, Duration::Height(0) | Duration::Time(0)) {
+        return Err(ContractError::ZeroEpochs);
+    }
+    if msg.initial_percent > Decimal::one() {
+        return Err(ContractError::InvalidInitialPercent);
+    }
+
+    let config = Config {
+        group: deps.api.addr_validate(&msg.group)?,
+        initial_percent: msg.initial_percent,
+        epoch_duration: msg.epoch_duration,
+        epochs: msg.epochs,
+    };
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "instantiate")
+        .add_attribute("creator", info.sender)
+        .add_attribute("group", config.group))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Approve { address, weight } => {
+            execute_approve(deps, env, info, address, weight)
+        }
+        ExecuteMsg::Revoke { address } => execute_revoke(deps, info, address),
+        ExecuteMsg::UpdateWeights { start_after, limit } => {
+            execute_update_weights(deps, env, start_after, limit)
+        }
+        ExecuteMsg::UpdateGroupAdmin { admin } => execute_update_group_admin(deps, info, admin),
+        ExecuteMsg::UpdateOwnership(action) => execute_update_owner(deps, info, env, action),
+    }
+}
+
+/// Creates a message updating the members of the group.
+fn update_members(group: &Addr, add: Vec<Member>, remove: Vec<String>) -> StdResult<WasmMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: group.to_string(),
+        msg: to_json_binary(&cw4_group::msg::ExecuteMsg::UpdateMembers { remove, add })?,
+        funds: vec![],
+    })
+}
+
+pub fn execute_approve(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    address: String,
+    weight: u64,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    if weight == 0 {
+        return Err(ContractError::ZeroWeight);
+    }
+    let addr = deps.api.addr_validate(&address)?;
+    if ONBOARDING.has(deps.storage, &addr) {
+        return Err(ContractError::AlreadyOnboarding { addr });
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let mut onboarding = Onboarding {
+        weight,
+        applied_weight: 0,
+        start_height: env.block.height,
+        start_time: env.block.time,
+    };
+    onboarding.applied_weight = onboarding.vested_weight(&config, &env.block);
+    ONBOARDING.save(deps.storage, &addr, &onboarding)?;
+
+    let msg = update_members(
+        &config.group,
+        vec![Member {
+            addr: addr.to_string(),
+            weight: onboarding.applied_weight,
+        }],
+        vec![],
+    )?;
+
+    Ok(Response::default()
+        .add_message(msg)
+        .add_attribute("method", "approve")
+        .add_attribute("address", addr)
+        .add_attribute("weight", weight.to_string())
+        .add_attribute("initial_weight", onboarding.applied_weight.to_string()))
+}
+
+pub fn execute_revoke(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    let addr = deps.api.addr_validate(&address)?;
+    if !ONBOARDING.has(deps.storage, &addr) {
+        return Err(ContractError::NotOnboarding { addr });
+    }
+    ONBOARDING.remove(deps.storage, &addr);
+
+    let config = CONFIG.load(deps.storage)?;
+    let msg = update_members(&config.group, vec![], vec![addr.to_string()])?;
+
+    Ok(Response::default()
+        .add_message(msg)
+        .add_attribute("method", "revoke")
+        .add_attribute("address", addr))
+}
+
+pub fn execute_update_weights(
+    deps: DepsMut,
+    env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    let onboarding = ONBOARDING
+        .range(
+            deps.storage,
+            start_after.as_ref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut add = vec![];
+    let mut completed = 0u32;
+    for (addr, mut onboarding) in onboarding {
+        let vested = onboarding.vested_weight(&config, &env.block);
+        if vested != onboarding.applied_weight {
+            add.push(Member {
+                addr: addr.to_string(),
+                weight: vested,
+            });
+            onboarding.applied_weight = vested;
+        }
+        if vested == onboarding.weight {
+            ONBOARDING.remove(deps.storage, &addr);
+            completed += 1;
+        } else {
+            ONBOARDING.save(deps.storage, &addr, &onboarding)?;
+        }
+    }
+
+    let updated = add.len();
+    let response = if add.is_empty() {
+        Response::default()
+    } else {
+        Response::default().add_message(update_members(&config.group, add, vec![])?)
+    };
+
+    Ok(response
+        .add_attribute("method", "update_weights")
+        .add_attribute("updated", updated.to_string())
+        .add_attribute("completed", completed.to_string()))
+}
+
+pub fn execute_update_group_admin(
+    deps: DepsMut,
+    info: MessageInfo,
+    admin: Option<String>,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let msg = WasmMsg::Execute {
+        contract_addr: config.group.to_string(),
+        msg: to_json_binary(&cw4_group::msg::ExecuteMsg::UpdateAdmin {
+            admin: admin.clone(),
+        })?,
+        funds: vec![],
+    };
+
+    Ok(Response::default()
+        .add_message(msg)
+        .add_attribute("method", "update_group_admin")
+        .add_attribute("admin", admin.unwrap_or_else(|| "None".to_string())))
+}
+
+pub fn execute_update_owner(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    action: cw_ownable::Action,
+) -> Result<Response, ContractError> {
+    let ownership = cw_ownable::update_ownership(deps, &env.block, &info.sender, action)?;
+    Ok(Response::default().add_attributes(ownership.into_attributes()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_json_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::Onboarding { address } => query_onboarding(deps, env, address),
+        QueryMsg::ListOnboarding { start_after, limit } => {
+            query_list_onboarding(deps, env, start_after, limit)
+        }
+        QueryMsg::Ownership {} => to_json_binary(&cw_ownable::get_ownership(deps.storage)?),
+    }
+}
+
+pub fn query_onboarding(deps: Deps, env: Env, address: String) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    let addr = deps.api.addr_validate(&address)?;
+    let response = ONBOARDING
+        .may_load(deps.storage, &addr)?
+        .map(|onboarding| OnboardingResponse {
+            vested_weight: onboarding.vested_weight(&config, &env.block),
+            address: addr,
+            onboarding,
+        });
+    to_json_binary(&response)
+}
+
+pub fn query_list_onboarding(
+    deps: Deps,
+    env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    let response = ONBOARDING
+        .range(
+            deps.storage,
+            start_after.as_ref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|entry| {
+            entry.map(|(address, onboarding)| OnboardingResponse {
+                vested_weight: onboarding.vested_weight(&config, &env.block),
+                address,
+                onboarding,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    to_json_binary(&response)
+}
@@ -0,0 +1,27 @@
+use cosmwasm_std::{Addr, StdError};
+use cw_ownable::OwnershipError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error(transparent)]
+    Ownable(#[from] OwnershipError),
+
+    #[error("epoch duration and number of epochs must be non-zero")]
+    ZeroEpochs,
+
+    #[error("initial weight percentage must be between zero and one")]
+    InvalidInitialPercent,
+
+    #[error("onboarded weight must be non-zero")]
+    ZeroWeight,
+
+    #[error("({addr}) is already being onboarded")]
+    AlreadyOnboarding { addr: Addr },
+
+    #[error("({addr}) is not being onboarded")]
+    NotOnboarding { addr: Addr },
+}
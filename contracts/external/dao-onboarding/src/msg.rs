@@ -0,0 +1,79 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Decimal};
+use cw_ownable::cw_ownable_execute;
+use cw_utils::Duration;
+
+use crate::state::Onboarding;
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// The owner of the contract, typically a DAO. The owner may
+    /// approve and revoke members. If not set, the contract has no
+    /// owner.
+    pub owner: Option<String>,
+    /// The cw4-group contract that onboarded members are added
+    /// to. This contract must be made the group's admin before
+    /// members may be approved.
+    pub group: String,
+    /// The portion of an onboarded member's weight that they receive
+    /// upon approval, for example 25%.
+    pub initial_percent: Decimal,
+    /// The length of each vesting epoch.
+    pub epoch_duration: Duration,
+    /// The number of epochs over which the remainder of an onboarded
+    /// member's weight vests.
+    pub epochs: u64,
+}
+
+#[cw_ownable_execute]
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Callable only by the owner. Adds `address` to the group with
+    /// its initial weight. The remainder of `weight` vests over the
+    /// configured number of epochs.
+    Approve { address: String, weight: u64 },
+    /// Callable only by the owner. Removes a member that has not yet
+    /// fully vested from the group.
+    Revoke { address: String },
+    /// Callable by anyone. Updates the group weights of onboarding
+    /// members to their currently vested weight. Members that have
+    /// fully vested complete onboarding and may no longer be revoked.
+    UpdateWeights {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Callable only by the owner. Sets the admin of the group
+    /// contract, returning control of the group's membership to the
+    /// owner.
+    UpdateGroupAdmin { admin: Option<String> },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Returns the contract's config.
+    #[returns(crate::state::Config)]
+    Config {},
+    /// Returns the onboarding status of an address, or `None` if the
+    /// address is not being onboarded.
+    #[returns(Option<OnboardingResponse>)]
+    Onboarding { address: String },
+    /// Lists members that are being onboarded.
+    #[returns(Vec<OnboardingResponse>)]
+    ListOnboarding {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns info about the contract ownership, if set.
+    #[returns(::cw_ownable::Ownership<::cosmwasm_std::Addr>)]
+    Ownership {},
+}
+
+#[cw_serde]
+pub struct OnboardingResponse {
+    pub address: Addr,
+    pub onboarding: Onboarding,
+    /// The weight that has vested as of the current block. Applied to
+    /// the group by `UpdateWeights`.
+    pub vested_weight: u64,
+}
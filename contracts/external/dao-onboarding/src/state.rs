@@ -0,0 +1,53 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, BlockInfo, Decimal, Timestamp, Uint128};
+use cw_storage_plus::{Item, Map};
+use cw_utils::Duration;
+
+#[cw_serde]
+pub struct Config {
+    /// The cw4-group contract that onboarded members are added
+    /// to. This contract must be the group's admin.
+    pub group: Addr,
+    /// The portion of an onboarded member's weight that they receive
+    /// upon approval.
+    pub initial_percent: Decimal,
+    /// The length of each vesting epoch.
+    pub epoch_duration: Duration,
+    /// The number of epochs over which the remainder of an onboarded
+    /// member's weight vests.
+    pub epochs: u64,
+}
+
+/// A member that has been approved and whose weight has not yet fully
+/// vested. Members are in their probation period while onboarding and
+/// may be revoked by the owner.
+#[cw_serde]
+pub struct Onboarding {
+    /// The weight the member will have once fully vested.
+    pub weight: u64,
+    /// The weight the member currently has in the group.
+    pub applied_weight: u64,
+    /// The block height at which the member was approved.
+    pub start_height: u64,
+    /// The time at which the member was approved.
+    pub start_time: Timestamp,
+}
+
+impl Onboarding {
+    /// The weight that has vested as of `block`.
+    pub fn vested_weight(&self, config: &Config, block: &BlockInfo) -> u64 {
+        let elapsed = match config.epoch_duration {
+            Duration::Height(blocks) => (block.height - self.start_height) / blocks,
+            Duration::Time(seconds) => (block.time.seconds() - self.start_time.seconds()) / seconds,
+        }
+        .min(config.epochs);
+
+        let weight = Uint128::from(self.weight);
+        let initial = weight * config.initial_percent;
+        let vested = initial + (weight - initial).multiply_ratio(elapsed, config.epochs);
+        vested.u128() as u64
+    }
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const ONBOARDING: Map<&Addr, Onboarding> = Map::new("onboarding");
@@ -0,0 +1,320 @@
+use cosmwasm_std::{Addr, Decimal, Empty};
+use cw4::{Member, MemberResponse};
+use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+use cw_ownable::OwnershipError;
+use cw_utils::Duration;
+
+use crate::{
+    msg::{ExecuteMsg, InstantiateMsg, OnboardingResponse, QueryMsg},
+    ContractError,
+};
+
+const DAO: &str = "dao";
+const ALICE: &str = "alice";
+const BOB: &str = "bob";
+
+fn onboarding_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        crate::contract::execute,
+        crate::contract::instantiate,
+        crate::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn cw4_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw4_group::contract::execute,
+        cw4_group::contract::instantiate,
+        cw4_group::contract::query,
+    );
+    Box::new(contract)
+}
+
+/// Instantiates a group and an onboarding contract that is the
+/// group's admin. Returns (group, onboarding).
+fn setup_test_case(app: &mut App) -> (Addr, Addr) {
+    let cw4_id = app.store_code(cw4_contract());
+    let onboarding_id = app.store_code(onboarding_contract());
+
+    let group = app
+        .instantiate_contract(
+            cw4_id,
+            Addr::unchecked(DAO),
+            &cw4_group::msg::InstantiateMsg {
+                admin: Some(DAO.to_string()),
+                members: vec![Member {
+                    addr: DAO.to_string(),
+                    weight: 1,
+                }],
+            },
+            &[],
+            "group",
+            None,
+        )
+        .unwrap();
+    let onboarding = app
+        .instantiate_contract(
+            onboarding_id,
+            Addr::unchecked(DAO),
+            &InstantiateMsg {
+                owner: Some(DAO.to_string()),
+                group: group.to_string(),
+                initial_percent: Decimal::percent(25),
+                epoch_duration: Duration::Time(100),
+                epochs: 3,
+            },
+            &[],
+            "onboarding",
+            None,
+        )
+        .unwrap();
+    app.execute_contract(
+        Addr::unchecked(DAO),
+        group.clone(),
+        &cw4_group::msg::ExecuteMsg::UpdateAdmin {
+            admin: Some(onboarding.to_string()),
+        },
+        &[],
+    )
+    .unwrap();
+
+    (group, onboarding)
+}
+
+fn query_weight(app: &App, group: &Addr, addr: &str) -> Option<u64> {
+    let res: MemberResponse = app
+        .wrap()
+        .query_wasm_smart(
+            group,
+            &cw4_group::msg::QueryMsg::Member {
+                addr: addr.to_string(),
+                at_height: None,
+            },
+        )
+        .unwrap();
+    res.weight
+}
+
+fn update_weights(app: &mut App, onboarding: &Addr) {
+    app.execute_contract(
+        Addr::unchecked(ALICE),
+        onboarding.clone(),
+        &ExecuteMsg::UpdateWeights {
+            start_after: None,
+            limit: None,
+        },
+        &[],
+    )
+    .unwrap();
+}
+
+fn advance_time(app: &mut App, seconds: u64) {
+    app.update_block(|block| {
+        block.height += 1;
+        block.time = block.time.plus_seconds(seconds);
+    });
+}
+
+#[test]
+fn test_instantiate_validation() {
+    let mut app = App::default();
+    let onboarding_id = app.store_code(onboarding_contract());
+    let instantiate = |app: &mut App, msg: InstantiateMsg| -> ContractError {
+        app.instantiate_contract(
+            onboarding_id,
+            Addr::unchecked(DAO),
+            &msg,
+            &[],
+            "onboarding",
+            None,
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap()
+    };
+    let msg = InstantiateMsg {
+        owner: Some(DAO.to_string()),
+        group: "group".to_string(),
+        initial_percent: Decimal::percent(25),
+        epoch_duration: Duration::Height(10),
+        epochs: 0,
+    };
+
+    let err = instantiate(&mut app, msg.clone());
+    assert_eq!(err, ContractError::ZeroEpochs);
+
+    let err = instantiate(
+        &mut app,
+        InstantiateMsg {
+            epochs: 2,
+            epoch_duration: Duration::Time(0),
+            ..msg.clone()
+        },
+    );
+    assert_eq!(err, ContractError::ZeroEpochs);
+
+    let err = instantiate(
+        &mut app,
+        InstantiateMsg {
+            epochs: 2,
+            initial_percent: Decimal::percent(101),
+            ..msg
+        },
+    );
+    assert_eq!(err, ContractError::InvalidInitialPercent);
+}
+
+#[test]
+fn test_vesting_onboarding() {
+    let mut app = App::default();
+    let (group, onboarding) = setup_test_case(&mut app);
+
+    // Only the owner may approve members.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ALICE),
+            onboarding.clone(),
+            &ExecuteMsg::Approve {
+                address: ALICE.to_string(),
+                weight: 100,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Ownable(OwnershipError::NotOwner));
+
+    // A quarter of the weight is received immediately.
+    app.execute_contract(
+        Addr::unchecked(DAO),
+        onboarding.clone(),
+        &ExecuteMsg::Approve {
+            address: ALICE.to_string(),
+            weight: 100,
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(query_weight(&app, &group, ALICE), Some(25));
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(DAO),
+            onboarding.clone(),
+            &ExecuteMsg::Approve {
+                address: ALICE.to_string(),
+                weight: 100,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::AlreadyOnboarding {
+            addr: Addr::unchecked(ALICE)
+        }
+    );
+
+    // The remainder vests over three epochs.
+    advance_time(&mut app, 100);
+    let status: Option<OnboardingResponse> = app
+        .wrap()
+        .query_wasm_smart(
+            onboarding.clone(),
+            &QueryMsg::Onboarding {
+                address: ALICE.to_string(),
+            },
+        )
+        .unwrap();
+    let status = status.unwrap();
+    assert_eq!(status.vested_weight, 50);
+    assert_eq!(status.onboarding.applied_weight, 25);
+
+    update_weights(&mut app, &onboarding);
+    assert_eq!(query_weight(&app, &group, ALICE), Some(50));
+
+    // Members may be revoked during their probation period.
+    app.execute_contract(
+        Addr::unchecked(DAO),
+        onboarding.clone(),
+        &ExecuteMsg::Approve {
+            address: BOB.to_string(),
+            weight: 40,
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(query_weight(&app, &group, BOB), Some(10));
+    app.execute_contract(
+        Addr::unchecked(DAO),
+        onboarding.clone(),
+        &ExecuteMsg::Revoke {
+            address: BOB.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(query_weight(&app, &group, BOB), None);
+
+    // Once fully vested, onboarding is complete.
+    advance_time(&mut app, 250);
+    update_weights(&mut app, &onboarding);
+    assert_eq!(query_weight(&app, &group, ALICE), Some(100));
+
+    let list: Vec<OnboardingResponse> = app
+        .wrap()
+        .query_wasm_smart(
+            onboarding.clone(),
+            &QueryMsg::ListOnboarding {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert!(list.is_empty());
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(DAO),
+            onboarding.clone(),
+            &ExecuteMsg::Revoke {
+                address: ALICE.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::NotOnboarding {
+            addr: Addr::unchecked(ALICE)
+        }
+    );
+
+    // The owner may take back control of the group.
+    app.execute_contract(
+        Addr::unchecked(DAO),
+        onboarding,
+        &ExecuteMsg::UpdateGroupAdmin {
+            admin: Some(DAO.to_string()),
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(DAO),
+        group.clone(),
+        &cw4_group::msg::ExecuteMsg::UpdateMembers {
+            remove: vec![ALICE.to_string()],
+            add: vec![],
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(query_weight(&app, &group, ALICE), None);
+}
@@ -0,0 +1,273 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Order,
+    Response, StdResult, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw_storage_plus::Bound;
+use cw_utils::Duration;
+
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::state::{advance_queue_id, Config, Queued, Strike, CONFIG, QUEUE};
+
+pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-timelock";
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+pub const DEFAULT_LIMIT: u32 = 10;
+pub const MAX_LIMIT: u32 = 30;
+
+fn validate_delay(delay: Duration) -> Result<Duration, ContractError> {
+    if matches!(delay, Duration::Height(0) | Duration::Time(0)) {
+        Err(ContractError::ZeroDelay {})
+    } else {
+        Ok(delay)
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let config = Config {
+        // The DAO instantiates this contract when adding it as a
+        // proposal module, which allows it to execute messages.
+        dao: info.sender,
+        delay: validate_delay(msg.delay)?,
+        vetoer: msg
+            .vetoer
+            .map(|vetoer| deps.api.addr_validate(&vetoer))
+            .transpose()?,
+    };
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "instantiate")
+        .add_attribute("dao", config.dao))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Queue { msgs, proposer } => {
+            execute_queue(deps, env, info.sender, msgs, proposer)
+        }
+        ExecuteMsg::Execute { id } => execute_execute(deps, env, id),
+        ExecuteMsg::Cancel { id } => execute_cancel(deps, info.sender, id),
+        ExecuteMsg::Strike { id, strikes } => execute_strike(deps, info.sender, id, strikes),
+        ExecuteMsg::Consent { id } => execute_consent(deps, info.sender, id),
+        ExecuteMsg::UpdateConfig { delay, vetoer } => {
+            execute_update_config(deps, info.sender, delay, vetoer)
+        }
+    }
+}
+
+pub fn execute_queue(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    msgs: Vec<CosmosMsg<Empty>>,
+    proposer: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if sender != config.dao {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let id = advance_queue_id(deps.storage)?;
+    let queued = Queued {
+        id,
+        msgs,
+        executable_at: config.delay.after(&env.block),
+        proposer: proposer
+            .map(|proposer| deps.api.addr_validate(&proposer))
+            .transpose()?,
+        struck: vec![],
+        consented: false,
+    };
+    QUEUE.save(deps.storage, id, &queued)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "queue")
+        .add_attribute("id", id.to_string())
+        .add_attribute("executable_at", queued.executable_at.to_string()))
+}
+
+pub fn execute_execute(deps: DepsMut, env: Env, id: u64) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let queued = QUEUE
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::NotQueued { id })?;
+    if !queued.executable_at.is_expired(&env.block) {
+        return Err(ContractError::Timelocked {
+            id,
+            executable_at: queued.executable_at,
+        });
+    }
+    if !queued.struck.is_empty() && !queued.consented {
+        return Err(ContractError::ConsentRequired { id });
+    }
+    QUEUE.remove(deps.storage, id);
+
+    let msgs = queued
+        .msgs
+        .into_iter()
+        .enumerate()
+        .filter(|(index, _)| {
+            !queued
+                .struck
+                .iter()
+                .any(|strike| strike.index as usize == *index)
+        })
+        .map(|(_, msg)| msg)
+        .collect();
+    let msg = WasmMsg::Execute {
+        contract_addr: config.dao.to_string(),
+        msg: to_json_binary(&dao_interface::msg::ExecuteMsg::ExecuteProposalHook { msgs })?,
+        funds: vec![],
+    };
+
+    Ok(Response::default()
+        .add_message(msg)
+        .add_attribute("method", "execute")
+        .add_attribute("id", id.to_string()))
+}
+
+pub fn execute_cancel(deps: DepsMut, sender: Addr, id: u64) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if sender != config.dao && Some(&sender) != config.vetoer.as_ref() {
+        return Err(ContractError::Unauthorized {});
+    }
+    if !QUEUE.has(deps.storage, id) {
+        return Err(ContractError::NotQueued { id });
+    }
+    QUEUE.remove(deps.storage, id);
+
+    Ok(Response::default()
+        .add_attribute("method", "cancel")
+        .add_attribute("id", id.to_string())
+        .add_attribute("sender", sender))
+}
+
+pub fn execute_strike(
+    deps: DepsMut,
+    sender: Addr,
+    id: u64,
+    strikes: Vec<Strike>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if sender != config.dao && Some(&sender) != config.vetoer.as_ref() {
+        return Err(ContractError::Unauthorized {});
+    }
+    let mut queued = QUEUE
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::NotQueued { id })?;
+
+    let mut response = Response::default()
+        .add_attribute("method", "strike")
+        .add_attribute("id", id.to_string())
+        .add_attribute("sender", sender);
+    for strike in strikes.iter() {
+        if strike.index as usize >= queued.msgs.len()
+            || queued.struck.iter().any(|s| s.index == strike.index)
+        {
+            return Err(ContractError::InvalidStrikes {});
+        }
+        response = response
+            .add_attribute("struck", strike.index.to_string())
+            .add_attribute("reason", &strike.reason);
+        queued.struck.push(strike.clone());
+    }
+    if strikes.is_empty() || queued.struck.len() == queued.msgs.len() {
+        return Err(ContractError::InvalidStrikes {});
+    }
+    queued.struck.sort_by_key(|strike| strike.index);
+    // The proposer consents to what is left after every strike.
+    queued.consented = false;
+    QUEUE.save(deps.storage, id, &queued)?;
+
+    Ok(response)
+}
+
+pub fn execute_consent(deps: DepsMut, sender: Addr, id: u64) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut queued = QUEUE
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::NotQueued { id })?;
+    if sender != *queued.proposer.as_ref().unwrap_or(&config.dao) {
+        return Err(ContractError::Unauthorized {});
+    }
+    if queued.struck.is_empty() {
+        return Err(ContractError::NothingToConsent { id });
+    }
+    queued.consented = true;
+    QUEUE.save(deps.storage, id, &queued)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "consent")
+        .add_attribute("id", id.to_string())
+        .add_attribute("sender", sender))
+}
+
+pub fn execute_update_config(
+    deps: DepsMut,
+    sender: Addr,
+    delay: Duration,
+    vetoer: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if sender != config.dao {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    config.delay = validate_delay(delay)?;
+    config.vetoer = vetoer
+        .map(|vetoer| deps.api.addr_validate(&vetoer))
+        .transpose()?;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default().add_attribute("method", "update_config"))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_json_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::Queued { id } => to_json_binary(&QUEUE.load(deps.storage, id)?),
+        QueryMsg::ListQueued { start_after, limit } => query_list_queued(deps, start_after, limit),
+        QueryMsg::Dao {} => to_json_binary(&CONFIG.load(deps.storage)?.dao),
+        QueryMsg::Info {} => {
+            let info = cw2::get_contract_version(deps.storage)?;
+            to_json_binary(&dao_interface::voting::InfoResponse { info })
+        }
+    }
+}
+
+pub fn query_list_queued(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let queued = QUEUE
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|entry| entry.map(|(_, queued)| queued))
+        .collect::<StdResult<Vec<_>>>()?;
+    to_json_binary(&queued)
+}
@@ -0,0 +1,30 @@
+use cosmwasm_std::StdError;
+use cw_utils::Expiration;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("timelock delay must be non-zero")]
+    ZeroDelay {},
+
+    #[error("no messages are queued with ID ({id})")]
+    NotQueued { id: u64 },
+
+    #[error("strikes must be of distinct, unstruck messages and leave at least one message. cancel the messages to strike them all")]
+    InvalidStrikes {},
+
+    #[error("queued messages ({id}) have been struck and may not be executed until the proposer consents")]
+    ConsentRequired { id: u64 },
+
+    #[error("queued messages ({id}) have no strikes to consent to")]
+    NothingToConsent { id: u64 },
+
+    #[error("queued messages ({id}) may not be executed until ({executable_at})")]
+    Timelocked { id: u64, executable_at: Expiration },
+}
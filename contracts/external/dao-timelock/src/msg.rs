@@ -0,0 +1,73 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{CosmosMsg, Empty};
+use cw_utils::Duration;
+
+use crate::state::Strike;
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// How long queued messages are held before they may be
+    /// executed.
+    pub delay: Duration,
+    /// An address that may cancel queued messages before they are
+    /// executed.
+    pub vetoer: Option<String>,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Callable by the DAO. Queues messages for execution once the
+    /// timelock's delay has passed.
+    Queue {
+        msgs: Vec<CosmosMsg<Empty>>,
+        /// The address that proposed the messages, if any. Only the
+        /// proposer may consent to executing the messages left after
+        /// a strike.
+        #[serde(default)]
+        proposer: Option<String>,
+    },
+    /// Callable by anyone once the delay has passed. Sends the
+    /// queued messages to the DAO for execution.
+    Execute { id: u64 },
+    /// Callable by the vetoer or the DAO. Removes queued messages
+    /// without executing them.
+    Cancel { id: u64 },
+    /// Callable by the vetoer or the DAO. Strikes individual queued
+    /// messages, giving a reason for each, so that they are not
+    /// executed. The remaining messages may only be executed once
+    /// the proposer consents with `Consent`.
+    Strike { id: u64, strikes: Vec<Strike> },
+    /// Callable by the proposer of queued messages, or by the DAO if
+    /// they have no proposer. Consents to executing the messages that
+    /// have not been struck.
+    Consent { id: u64 },
+    /// Callable by the DAO. Updates the delay and vetoer. The delay
+    /// of already queued messages is not changed.
+    UpdateConfig {
+        delay: Duration,
+        vetoer: Option<String>,
+    },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Gets the timelock's config.
+    #[returns(crate::state::Config)]
+    Config {},
+    /// Gets queued messages by ID.
+    #[returns(crate::state::Queued)]
+    Queued { id: u64 },
+    /// Lists queued messages in ascending order of ID.
+    #[returns(Vec<crate::state::Queued>)]
+    ListQueued {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Gets the DAO this timelock belongs to.
+    #[returns(cosmwasm_std::Addr)]
+    Dao {},
+    /// Returns contract version info.
+    #[returns(dao_interface::voting::InfoResponse)]
+    Info {},
+}
@@ -0,0 +1,57 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, CosmosMsg, Empty, StdResult, Storage};
+use cw_storage_plus::{Item, Map};
+use cw_utils::{Duration, Expiration};
+
+#[cw_serde]
+pub struct Config {
+    /// The DAO that queues messages and executes them once their
+    /// delay has passed.
+    pub dao: Addr,
+    /// How long queued messages are held before they may be
+    /// executed.
+    pub delay: Duration,
+    /// An address that may cancel queued messages before they are
+    /// executed.
+    pub vetoer: Option<Addr>,
+}
+
+/// A queued message struck by the vetoer.
+#[cw_serde]
+pub struct Strike {
+    /// The index of the message in the queued messages.
+    pub index: u32,
+    /// Why the message was struck.
+    pub reason: String,
+}
+
+/// Messages from a passed proposal that are waiting to be executed.
+#[cw_serde]
+pub struct Queued {
+    pub id: u64,
+    pub msgs: Vec<CosmosMsg<Empty>>,
+    /// When the messages may be executed.
+    pub executable_at: Expiration,
+    /// The address that proposed the messages, if known.
+    #[serde(default)]
+    pub proposer: Option<Addr>,
+    /// Messages that will not be executed.
+    #[serde(default)]
+    pub struck: Vec<Strike>,
+    /// Whether the proposer has consented to executing the messages
+    /// that have not been struck. Reset by each strike.
+    #[serde(default)]
+    pub consented: bool,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const QUEUE: Map<u64, Queued> = Map::new("queue");
+
+/// Used internally to track the ID of the last queued messages.
+const QUEUE_COUNT: Item<u64> = Item::new("queue_count");
+
+pub(crate) fn advance_queue_id(store: &mut dyn Storage) -> StdResult<u64> {
+    let id: u64 = QUEUE_COUNT.may_load(store)?.unwrap_or_default() + 1;
+    QUEUE_COUNT.save(store, &id)?;
+    Ok(id)
+}
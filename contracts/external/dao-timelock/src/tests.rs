@@ -0,0 +1,441 @@
+use cosmwasm_std::{to_json_binary, Addr, CosmosMsg, Empty, WasmMsg};
+use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+use cw_utils::{Duration, Expiration};
+use dao_interface::{
+    msg::{
+        ExecuteMsg as DaoExecuteMsg, InstantiateMsg as DaoInstantiateMsg, QueryMsg as DaoQueryMsg,
+    },
+    query::GetItemResponse,
+    state::{Admin, ModuleInstantiateInfo, ProposalModule},
+};
+
+use crate::{
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    state::{Config, Queued, Strike},
+    ContractError,
+};
+
+const CREATOR: &str = "creator";
+const VETOER: &str = "vetoer";
+
+fn timelock_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        crate::contract::execute,
+        crate::contract::instantiate,
+        crate::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn sudo_proposal_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        dao_proposal_sudo::contract::execute,
+        dao_proposal_sudo::contract::instantiate,
+        dao_proposal_sudo::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn cw_core_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        dao_dao_core::contract::execute,
+        dao_dao_core::contract::instantiate,
+        dao_dao_core::contract::query,
+    )
+    .with_reply(dao_dao_core::contract::reply);
+    Box::new(contract)
+}
+
+/// Instantiates a DAO with a sudo proposal module and a timelock
+/// proposal module. Returns (dao, sudo, timelock).
+fn setup_test_case(app: &mut App) -> (Addr, Addr, Addr) {
+    let sudo_id = app.store_code(sudo_proposal_contract());
+    let timelock_id = app.store_code(timelock_contract());
+    let core_id = app.store_code(cw_core_contract());
+
+    let sudo_instantiate = to_json_binary(&dao_proposal_sudo::msg::InstantiateMsg {
+        root: CREATOR.to_string(),
+    })
+    .unwrap();
+    let dao = app
+        .instantiate_contract(
+            core_id,
+            Addr::unchecked(CREATOR),
+            &DaoInstantiateMsg {
+                dao_uri: None,
+                admin: None,
+                name: "DAO DAO".to_string(),
+                description: "A DAO that builds DAOs.".to_string(),
+                image_url: None,
+                automatically_add_cw20s: true,
+                automatically_add_cw721s: true,
+                voting_module_instantiate_info: ModuleInstantiateInfo {
+                    code_id: sudo_id,
+                    msg: sudo_instantiate.clone(),
+                    admin: Some(Admin::CoreModule {}),
+                    funds: vec![],
+                    label: "voting module".to_string(),
+                },
+                proposal_modules_instantiate_info: vec![
+                    ModuleInstantiateInfo {
+                        code_id: sudo_id,
+                        msg: sudo_instantiate,
+                        admin: Some(Admin::CoreModule {}),
+                        funds: vec![],
+                        label: "sudo".to_string(),
+                    },
+                    ModuleInstantiateInfo {
+                        code_id: timelock_id,
+                        msg: to_json_binary(&InstantiateMsg {
+                            delay: Duration::Height(10),
+                            vetoer: Some(VETOER.to_string()),
+                        })
+                        .unwrap(),
+                        admin: Some(Admin::CoreModule {}),
+                        funds: vec![],
+                        label: "timelock".to_string(),
+                    },
+                ],
+                initial_items: None,
+            },
+            &[],
+            "dao",
+            None,
+        )
+        .unwrap();
+
+    let modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            dao.clone(),
+            &DaoQueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+                descending: None,
+            },
+        )
+        .unwrap();
+    let (timelock, sudo): (Vec<_>, Vec<_>) = modules
+        .into_iter()
+        .map(|module| module.address)
+        .partition(|address| app.contract_data(address).unwrap().code_id == timelock_id);
+
+    (dao, sudo[0].clone(), timelock[0].clone())
+}
+
+fn set_item_msg(dao: &Addr, key: &str) -> CosmosMsg {
+    WasmMsg::Execute {
+        contract_addr: dao.to_string(),
+        msg: to_json_binary(&DaoExecuteMsg::SetItem {
+            key: key.to_string(),
+            value: "value".to_string(),
+        })
+        .unwrap(),
+        funds: vec![],
+    }
+    .into()
+}
+
+/// Has the DAO queue a message setting an item in the DAO.
+fn queue_set_item(app: &mut App, dao: &Addr, sudo: &Addr, timelock: &Addr, key: &str) {
+    queue(app, sudo, timelock, vec![set_item_msg(dao, key)], None)
+}
+
+/// Has the DAO queue MSGS proposed by PROPOSER.
+fn queue(
+    app: &mut App,
+    sudo: &Addr,
+    timelock: &Addr,
+    msgs: Vec<CosmosMsg>,
+    proposer: Option<&str>,
+) {
+    app.execute_contract(
+        Addr::unchecked(CREATOR),
+        sudo.clone(),
+        &dao_proposal_sudo::msg::ExecuteMsg::Execute {
+            msgs: vec![WasmMsg::Execute {
+                contract_addr: timelock.to_string(),
+                msg: to_json_binary(&ExecuteMsg::Queue {
+                    msgs,
+                    proposer: proposer.map(str::to_string),
+                })
+                .unwrap(),
+                funds: vec![],
+            }
+            .into()],
+        },
+        &[],
+    )
+    .unwrap();
+}
+
+fn get_item(app: &App, dao: &Addr, key: &str) -> Option<String> {
+    let res: GetItemResponse = app
+        .wrap()
+        .query_wasm_smart(
+            dao,
+            &DaoQueryMsg::GetItem {
+                key: key.to_string(),
+            },
+        )
+        .unwrap();
+    res.item
+}
+
+#[test]
+fn test_timelock_execute() {
+    let mut app = App::default();
+    let (dao, sudo, timelock) = setup_test_case(&mut app);
+
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(timelock.clone(), &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(
+        config,
+        Config {
+            dao: dao.clone(),
+            delay: Duration::Height(10),
+            vetoer: Some(Addr::unchecked(VETOER)),
+        }
+    );
+
+    // Only the DAO may queue messages.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR),
+            timelock.clone(),
+            &ExecuteMsg::Queue {
+                msgs: vec![],
+                proposer: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    queue_set_item(&mut app, &dao, &sudo, &timelock, "foo");
+    let queue: Vec<Queued> = app
+        .wrap()
+        .query_wasm_smart(
+            timelock.clone(),
+            &QueryMsg::ListQueued {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(queue.len(), 1);
+    let executable_at = Expiration::AtHeight(app.block_info().height + 10);
+    assert_eq!(queue[0].executable_at, executable_at);
+
+    // Messages may not be executed until the delay has passed.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR),
+            timelock.clone(),
+            &ExecuteMsg::Execute { id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::Timelocked {
+            id: 1,
+            executable_at
+        }
+    );
+
+    app.update_block(|block| block.height += 10);
+    app.execute_contract(
+        Addr::unchecked(CREATOR),
+        timelock.clone(),
+        &ExecuteMsg::Execute { id: 1 },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(get_item(&app, &dao, "foo"), Some("value".to_string()));
+
+    // Messages may only be executed once.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR),
+            timelock,
+            &ExecuteMsg::Execute { id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NotQueued { id: 1 });
+}
+
+#[test]
+fn test_timelock_cancel() {
+    let mut app = App::default();
+    let (dao, sudo, timelock) = setup_test_case(&mut app);
+
+    queue_set_item(&mut app, &dao, &sudo, &timelock, "foo");
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR),
+            timelock.clone(),
+            &ExecuteMsg::Cancel { id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    app.execute_contract(
+        Addr::unchecked(VETOER),
+        timelock.clone(),
+        &ExecuteMsg::Cancel { id: 1 },
+        &[],
+    )
+    .unwrap();
+
+    app.update_block(|block| block.height += 10);
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR),
+            timelock,
+            &ExecuteMsg::Execute { id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NotQueued { id: 1 });
+    assert_eq!(get_item(&app, &dao, "foo"), None);
+}
+
+#[test]
+fn test_timelock_strike() {
+    let mut app = App::default();
+    let (dao, sudo, timelock) = setup_test_case(&mut app);
+
+    queue(
+        &mut app,
+        &sudo,
+        &timelock,
+        vec![
+            set_item_msg(&dao, "foo"),
+            set_item_msg(&dao, "bar"),
+            set_item_msg(&dao, "baz"),
+        ],
+        Some("proposer"),
+    );
+    let strike = |index: u32| Strike {
+        index,
+        reason: "too risky".to_string(),
+    };
+    let strike_msg = |strikes: Vec<Strike>| ExecuteMsg::Strike { id: 1, strikes };
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("proposer"),
+            timelock.clone(),
+            &strike_msg(vec![strike(1)]),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Strikes must be in range and may not strike every message.
+    for strikes in [
+        vec![],
+        vec![strike(3)],
+        vec![strike(1), strike(1)],
+        vec![strike(0), strike(1), strike(2)],
+    ] {
+        let err: ContractError = app
+            .execute_contract(
+                Addr::unchecked(VETOER),
+                timelock.clone(),
+                &strike_msg(strikes),
+                &[],
+            )
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+        assert_eq!(err, ContractError::InvalidStrikes {});
+    }
+
+    app.execute_contract(
+        Addr::unchecked(VETOER),
+        timelock.clone(),
+        &strike_msg(vec![strike(1)]),
+        &[],
+    )
+    .unwrap();
+    let queued: Queued = app
+        .wrap()
+        .query_wasm_smart(timelock.clone(), &QueryMsg::Queued { id: 1 })
+        .unwrap();
+    assert_eq!(queued.proposer, Some(Addr::unchecked("proposer")));
+    assert_eq!(queued.struck, vec![strike(1)]);
+    assert!(!queued.consented);
+
+    // Struck messages may not be executed without consent.
+    app.update_block(|block| block.height += 10);
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR),
+            timelock.clone(),
+            &ExecuteMsg::Execute { id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::ConsentRequired { id: 1 });
+
+    // Only the proposer may consent.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(VETOER),
+            timelock.clone(),
+            &ExecuteMsg::Consent { id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+    app.execute_contract(
+        Addr::unchecked("proposer"),
+        timelock.clone(),
+        &ExecuteMsg::Consent { id: 1 },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked(CREATOR),
+        timelock.clone(),
+        &ExecuteMsg::Execute { id: 1 },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(get_item(&app, &dao, "foo"), Some("value".to_string()));
+    assert_eq!(get_item(&app, &dao, "bar"), None);
+    assert_eq!(get_item(&app, &dao, "baz"), Some("value".to_string()));
+
+    // Without strikes there is nothing to consent to.
+    queue_set_item(&mut app, &dao, &sudo, &timelock, "qux");
+    let err: ContractError = app
+        .execute_contract(dao, timelock, &ExecuteMsg::Consent { id: 2 }, &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NothingToConsent { id: 2 });
+}
@@ -1,22 +1,27 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Order, Response, StdResult, SubMsg,
-    WasmMsg,
+    to_json_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Order, Reply, Response,
+    StdResult, Storage, SubMsg, WasmMsg,
 };
 use cw2::set_contract_version;
-use cw_paginate_storage::paginate_map_values;
+use cw_paginate_storage::{clamp_limit, paginate_map_values};
+use cw_storage_plus::Bound;
 use dao_pre_propose_base::{
     error::PreProposeError, msg::ExecuteMsg as ExecuteBase, state::PreProposeContract,
 };
-use dao_voting::deposit::DepositRefundPolicy;
+use dao_voting::deposit::{CheckedDepositInfo, DepositRefundPolicy};
 use dao_voting::proposal::SingleChoiceProposeMsg as ProposeMsg;
 
 use crate::msg::{
     ApproverProposeMessage, ExecuteExt, ExecuteMsg, InstantiateExt, InstantiateMsg, ProposeMessage,
-    ProposeMessageInternal, QueryExt, QueryMsg,
+    ProposeMessageInternal, QueryExt, QueryMsg, ReviewerWorkload, UncheckedReviewConfig,
+};
+use crate::state::{
+    advance_approval_id, assign_review, load_assignment, unassign_review, AssignmentMode,
+    PendingProposal, ReviewConfig, APPROVER, NEXT_REVIEWER, PENDING_PROPOSALS, REVIEWER_ASSIGNED,
+    REVIEWER_QUEUES, REVIEWS_DUE, REVIEW_CONFIG,
 };
-use crate::state::{advance_approval_id, PendingProposal, APPROVER, PENDING_PROPOSALS};
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-pre-propose-approval-single";
 pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -46,7 +51,14 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, PreProposeError> {
     match msg {
-        ExecuteMsg::Propose { msg } => execute_propose(deps, env, info, msg),
+        ExecuteMsg::Propose { msg } => {
+            let config = PrePropose::default().config.load(deps.storage)?;
+            execute_propose(deps, env, info, msg, config.deposit_info)
+        }
+        ExecuteMsg::ProposeWithDepositOption { msg, option } => {
+            let deposit_info = PrePropose::default().deposit_option(deps.storage, option)?;
+            execute_propose(deps, env, info, msg, Some(deposit_info))
+        }
 
         ExecuteMsg::AddProposalSubmittedHook { address } => {
             execute_add_approver_hook(deps, info, address)
@@ -59,6 +71,13 @@ pub fn execute(
             ExecuteExt::Approve { id } => execute_approve(deps, info, id),
             ExecuteExt::Reject { id } => execute_reject(deps, info, id),
             ExecuteExt::UpdateApprover { address } => execute_update_approver(deps, info, address),
+            ExecuteExt::UpdateReviewConfig { config } => {
+                execute_update_review_config(deps, info, config)
+            }
+            ExecuteExt::ClaimReview { id } => execute_claim_review(deps, env, info, id),
+            ExecuteExt::AssignReview { id, reviewer } => {
+                execute_assign_review(deps, env, info, id, reviewer)
+            }
         },
         // Default pre-propose-base behavior for all other messages
         _ => PrePropose::default().execute(deps, env, info, msg),
@@ -70,19 +89,15 @@ pub fn execute_propose(
     env: Env,
     info: MessageInfo,
     msg: ProposeMessage,
+    deposit_info: Option<CheckedDepositInfo>,
 ) -> Result<Response, PreProposeError> {
     let pre_propose_base = PrePropose::default();
-    let config = pre_propose_base.config.load(deps.storage)?;
 
     pre_propose_base.check_can_submit(deps.as_ref(), info.sender.clone())?;
 
-    // Take deposit, if configured.
-    let deposit_messages = if let Some(ref deposit_info) = config.deposit_info {
-        deposit_info.check_native_deposit_paid(&info)?;
-        deposit_info.get_take_deposit_messages(&info.sender, &env.contract.address)?
-    } else {
-        vec![]
-    };
+    // Take deposit and submission fee, if configured.
+    let deposit_messages =
+        pre_propose_base.take_deposit_and_fee(deps.as_ref(), &env, &info, deposit_info.as_ref())?;
 
     let approval_id = advance_approval_id(deps.storage)?;
 
@@ -119,6 +134,15 @@ pub fn execute_propose(
                 Ok(SubMsg::new(execute_msg))
             })?;
 
+    // Notify submission hook receivers that a proposal is awaiting
+    // approval.
+    let submission_hooks = pre_propose_base.submission_created_hooks(
+        deps.storage,
+        info.sender.clone(),
+        approval_id,
+        true,
+    )?;
+
     // Save the proposal and its information as pending.
     PENDING_PROPOSALS.save(
         deps.storage,
@@ -127,15 +151,41 @@ pub fn execute_propose(
             approval_id,
             proposer: info.sender,
             msg: propose_msg_internal,
-            deposit: config.deposit_info,
+            deposit: deposit_info,
         },
     )?;
 
+    // Hand the proposal to the next reviewer if reviews are assigned
+    // in turn.
+    let reviewer = match REVIEW_CONFIG.may_load(deps.storage)? {
+        Some(review_config) if review_config.mode == AssignmentMode::RoundRobin => {
+            let index = NEXT_REVIEWER.may_load(deps.storage)?.unwrap_or_default();
+            let reviewer =
+                review_config.reviewers[index as usize % review_config.reviewers.len()].clone();
+            NEXT_REVIEWER.save(deps.storage, &(index + 1))?;
+            assign_review(
+                deps.storage,
+                approval_id,
+                reviewer.clone(),
+                review_config.due_height(&env),
+            )?;
+            Some(reviewer)
+        }
+        _ => None,
+    };
+
     Ok(Response::default()
         .add_messages(deposit_messages)
         .add_submessages(hooks_msgs)
+        .add_submessages(submission_hooks)
         .add_attribute("method", "pre-propose")
-        .add_attribute("id", approval_id.to_string()))
+        .add_attribute("id", approval_id.to_string())
+        .add_attribute(
+            "reviewer",
+            reviewer
+                .map(|r| r.into_string())
+                .unwrap_or_else(|| "None".to_string()),
+        ))
 }
 
 pub fn execute_approve(
@@ -173,6 +223,7 @@ pub fn execute_approve(
                 funds: vec![],
             };
             PENDING_PROPOSALS.remove(deps.storage, id);
+            unassign_review(deps.storage, id)?;
 
             Ok(Response::default()
                 .add_message(propose_messsage)
@@ -202,6 +253,7 @@ pub fn execute_reject(
         .ok_or(PreProposeError::ProposalNotFound {})?;
 
     PENDING_PROPOSALS.remove(deps.storage, id);
+    unassign_review(deps.storage, id)?;
 
     let messages = if let Some(ref deposit_info) = deposit {
         // Refund can be issued if proposal if deposits are always
@@ -243,6 +295,115 @@ pub fn execute_update_approver(
     Ok(Response::default())
 }
 
+pub fn execute_update_review_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    config: Option<UncheckedReviewConfig>,
+) -> Result<Response, PreProposeError> {
+    // Check sender is the approver
+    let approver = APPROVER.load(deps.storage)?;
+    if approver != info.sender {
+        return Err(PreProposeError::Unauthorized {});
+    }
+
+    let config = config
+        .map(|config| -> Result<ReviewConfig, PreProposeError> {
+            if config.reviewers.is_empty() || config.review_blocks == Some(0) {
+                return Err(PreProposeError::InvalidReviewConfig {});
+            }
+            let mut reviewers = config
+                .reviewers
+                .iter()
+                .map(|r| deps.api.addr_validate(r))
+                .collect::<StdResult<Vec<_>>>()?;
+            reviewers.sort();
+            reviewers.dedup();
+            Ok(ReviewConfig {
+                reviewers,
+                mode: config.mode,
+                review_blocks: config.review_blocks,
+            })
+        })
+        .transpose()?;
+
+    // Proposals held by anyone who is no longer a reviewer are
+    // released by ignoring their assignments (see `load_assignment`)
+    // rather than removing them, which could take unbounded gas.
+    NEXT_REVIEWER.remove(deps.storage);
+    match config {
+        Some(config) => REVIEW_CONFIG.save(deps.storage, &config)?,
+        None => REVIEW_CONFIG.remove(deps.storage),
+    }
+
+    Ok(Response::default().add_attribute("method", "update_review_config"))
+}
+
+/// Loads the review config and checks that REVIEWER is one of its
+/// reviewers, and that a proposal with ID is pending.
+fn load_review_config(
+    storage: &dyn Storage,
+    reviewer: &Addr,
+    id: u64,
+) -> Result<ReviewConfig, PreProposeError> {
+    let config = REVIEW_CONFIG
+        .may_load(storage)?
+        .ok_or(PreProposeError::ReviewsDisabled {})?;
+    if !config.reviewers.contains(reviewer) {
+        return Err(PreProposeError::NotReviewer {});
+    }
+    if !PENDING_PROPOSALS.has(storage, id) {
+        return Err(PreProposeError::ProposalNotFound {});
+    }
+    Ok(config)
+}
+
+pub fn execute_claim_review(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, PreProposeError> {
+    let config = load_review_config(deps.storage, &info.sender, id)?;
+    if load_assignment(deps.storage, id)?.is_some() {
+        return Err(PreProposeError::ReviewAlreadyAssigned { id });
+    }
+
+    assign_review(
+        deps.storage,
+        id,
+        info.sender.clone(),
+        config.due_height(&env),
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("method", "claim_review")
+        .add_attribute("id", id.to_string())
+        .add_attribute("reviewer", info.sender))
+}
+
+pub fn execute_assign_review(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+    reviewer: String,
+) -> Result<Response, PreProposeError> {
+    // Check sender is the approver
+    let approver = APPROVER.load(deps.storage)?;
+    if approver != info.sender {
+        return Err(PreProposeError::Unauthorized {});
+    }
+
+    let reviewer = deps.api.addr_validate(&reviewer)?;
+    let config = load_review_config(deps.storage, &reviewer, id)?;
+    assign_review(deps.storage, id, reviewer.clone(), config.due_height(&env))?;
+
+    Ok(Response::default()
+        .add_attribute("method", "assign_review")
+        .add_attribute("id", id.to_string())
+        .add_attribute("reviewer", reviewer))
+}
+
 pub fn execute_add_approver_hook(
     deps: DepsMut,
     info: MessageInfo,
@@ -317,7 +478,98 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
                 limit,
                 Order::Ascending,
             )?),
+            QueryExt::ReviewConfig {} => to_json_binary(&REVIEW_CONFIG.may_load(deps.storage)?),
+            QueryExt::ReviewAssignment { id } => {
+                to_json_binary(&load_assignment(deps.storage, id)?)
+            }
+            QueryExt::ReviewerPendingProposals {
+                reviewer,
+                start_after,
+                limit,
+            } => to_json_binary(&query_reviewer_pending_proposals(
+                deps,
+                reviewer,
+                start_after,
+                limit,
+            )?),
+            QueryExt::ReviewerWorkloads { start_after, limit } => {
+                to_json_binary(&query_reviewer_workloads(deps, env, start_after, limit)?)
+            }
         },
         _ => PrePropose::default().query(deps, env, msg),
     }
 }
+
+pub fn query_reviewer_pending_proposals(
+    deps: Deps,
+    reviewer: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<PendingProposal>> {
+    let reviewer = deps.api.addr_validate(&reviewer)?;
+    if !is_reviewer(deps.storage, &reviewer)? {
+        return Ok(vec![]);
+    }
+    REVIEWER_QUEUES
+        .prefix(&reviewer)
+        .keys(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(clamp_limit(limit))
+        .map(|id| PENDING_PROPOSALS.load(deps.storage, id?))
+        .collect()
+}
+
+pub fn query_reviewer_workloads(
+    deps: Deps,
+    env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<ReviewerWorkload>> {
+    let reviewers = REVIEW_CONFIG
+        .may_load(deps.storage)?
+        .map(|config| config.reviewers)
+        .unwrap_or_default();
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    reviewers
+        .into_iter()
+        .filter(|reviewer| start_after.as_ref().map_or(true, |start| reviewer > start))
+        .take(clamp_limit(limit))
+        .map(|reviewer| {
+            let assigned = REVIEWER_ASSIGNED
+                .may_load(deps.storage, &reviewer)?
+                .unwrap_or_default();
+            // Reviews due before this block are overdue.
+            let overdue = REVIEWS_DUE
+                .sub_prefix(&reviewer)
+                .keys_raw(
+                    deps.storage,
+                    None,
+                    Some(Bound::exclusive((env.block.height, 0))),
+                    Order::Ascending,
+                )
+                .count() as u64;
+            Ok(ReviewerWorkload {
+                reviewer,
+                assigned,
+                overdue,
+            })
+        })
+        .collect()
+}
+
+fn is_reviewer(storage: &dyn Storage, address: &Addr) -> StdResult<bool> {
+    Ok(REVIEW_CONFIG
+        .may_load(storage)?
+        .map_or(false, |config| config.reviewers.contains(address)))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, PreProposeError> {
+    PrePropose::default().reply(deps, env, msg)
+}
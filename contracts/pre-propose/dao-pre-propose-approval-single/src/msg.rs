@@ -1,5 +1,5 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{CosmosMsg, Empty};
+use cosmwasm_std::{Addr, CosmosMsg, Empty};
 use dao_pre_propose_base::msg::{
     ExecuteMsg as ExecuteBase, InstantiateMsg as InstantiateBase, QueryMsg as QueryBase,
 };
@@ -23,6 +23,13 @@ pub enum ProposeMessage {
     },
 }
 
+#[cw_serde]
+pub struct UncheckedReviewConfig {
+    pub reviewers: Vec<String>,
+    pub mode: crate::state::AssignmentMode,
+    pub review_blocks: Option<u64>,
+}
+
 #[cw_serde]
 pub struct InstantiateExt {
     pub approver: String,
@@ -36,6 +43,28 @@ pub enum ExecuteExt {
     Reject { id: u64 },
     /// Updates the approver, can only be called the current approver
     UpdateApprover { address: String },
+    /// Sets or removes the reviewers among whom pending proposals are
+    /// split, only callable by approver. Assignments held by addresses
+    /// that are no longer reviewers are ignored, and proposals they
+    /// held may be claimed or reassigned.
+    UpdateReviewConfig {
+        config: Option<UncheckedReviewConfig>,
+    },
+    /// Claims the review of an unassigned pending proposal, only
+    /// callable by a reviewer.
+    ClaimReview { id: u64 },
+    /// Assigns the review of a pending proposal to a reviewer, only
+    /// callable by approver.
+    AssignReview { id: u64, reviewer: String },
+}
+
+#[cw_serde]
+pub struct ReviewerWorkload {
+    pub reviewer: Addr,
+    /// The number of pending proposals assigned to the reviewer.
+    pub assigned: u64,
+    /// The number of those proposals whose due height has passed.
+    pub overdue: u64,
 }
 
 #[cw_serde]
@@ -58,6 +87,26 @@ pub enum QueryExt {
         start_before: Option<u64>,
         limit: Option<u32>,
     },
+    /// The review config, if reviews are enabled
+    #[returns(Option<crate::state::ReviewConfig>)]
+    ReviewConfig {},
+    /// The reviewer assigned to a pending proposal, if any
+    #[returns(Option<crate::state::ReviewAssignment>)]
+    ReviewAssignment { id: u64 },
+    /// List of pending proposals assigned to a reviewer
+    #[returns(Vec<crate::state::PendingProposal>)]
+    ReviewerPendingProposals {
+        reviewer: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// The number of assigned and overdue pending proposals for each
+    /// reviewer, ordered by reviewer address
+    #[returns(Vec<ReviewerWorkload>)]
+    ReviewerWorkloads {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 }
 
 pub type InstantiateMsg = InstantiateBase<InstantiateExt>;
@@ -1,5 +1,5 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, StdResult, Storage};
+use cosmwasm_std::{Addr, Empty, Env, StdResult, Storage};
 use cw_storage_plus::{Item, Map};
 
 use dao_voting::deposit::CheckedDepositInfo;
@@ -19,12 +19,126 @@ pub struct PendingProposal {
     pub deposit: Option<CheckedDepositInfo>,
 }
 
+/// How pending proposals are assigned to reviewers.
+#[cw_serde]
+#[derive(Copy)]
+pub enum AssignmentMode {
+    /// Reviewers claim pending proposals themselves.
+    SelfClaim,
+    /// Pending proposals are assigned to reviewers in turn as they
+    /// are submitted.
+    RoundRobin,
+}
+
+/// Configuration for splitting the review of pending proposals
+/// between the members of an approver committee.
+#[cw_serde]
+pub struct ReviewConfig {
+    pub reviewers: Vec<Addr>,
+    pub mode: AssignmentMode,
+    /// The number of blocks a reviewer has to review a proposal once
+    /// it is assigned to them. If `None`, reviews have no due height.
+    pub review_blocks: Option<u64>,
+}
+
+impl ReviewConfig {
+    /// The height by which a review assigned now is due.
+    pub fn due_height(&self, env: &Env) -> Option<u64> {
+        self.review_blocks.map(|blocks| env.block.height + blocks)
+    }
+}
+
+#[cw_serde]
+pub struct ReviewAssignment {
+    pub reviewer: Addr,
+    /// The block height by which the reviewer ought to have reviewed
+    /// the proposal.
+    pub due_height: Option<u64>,
+}
+
 pub const APPROVER: Item<Addr> = Item::new("approver");
 pub const PENDING_PROPOSALS: Map<u64, PendingProposal> = Map::new("pending_proposals");
 
+pub const REVIEW_CONFIG: Item<ReviewConfig> = Item::new("review_config");
+/// Maps approval IDs to the reviewer assigned to them.
+pub const REVIEW_ASSIGNMENTS: Map<u64, ReviewAssignment> = Map::new("review_assignments");
+/// The approval IDs assigned to each reviewer, for listing a
+/// reviewer's queue.
+pub const REVIEWER_QUEUES: Map<(&Addr, u64), Empty> = Map::new("reviewer_queues");
+/// The number of pending proposals assigned to each reviewer.
+pub const REVIEWER_ASSIGNED: Map<&Addr, u64> = Map::new("reviewer_assigned");
+/// Assignments with a due height, keyed by reviewer, due height, and
+/// approval ID, for counting a reviewer's overdue reviews.
+pub const REVIEWS_DUE: Map<(&Addr, u64, u64), Empty> = Map::new("reviews_due");
+/// Index into the reviewer list of the next reviewer to receive a
+/// round-robin assignment.
+pub const NEXT_REVIEWER: Item<u64> = Item::new("next_reviewer");
+
 /// Used internally to track the current approval_id.
 const CURRENT_ID: Item<u64> = Item::new("current_id");
 
+/// Assigns the pending proposal with APPROVAL_ID to REVIEWER, replacing
+/// any existing assignment.
+pub(crate) fn assign_review(
+    store: &mut dyn Storage,
+    approval_id: u64,
+    reviewer: Addr,
+    due_height: Option<u64>,
+) -> StdResult<()> {
+    unassign_review(store, approval_id)?;
+    REVIEWER_QUEUES.save(store, (&reviewer, approval_id), &Empty {})?;
+    REVIEWER_ASSIGNED.update(store, &reviewer, |assigned| -> StdResult<_> {
+        Ok(assigned.unwrap_or_default() + 1)
+    })?;
+    if let Some(due_height) = due_height {
+        REVIEWS_DUE.save(store, (&reviewer, due_height, approval_id), &Empty {})?;
+    }
+    REVIEW_ASSIGNMENTS.save(
+        store,
+        approval_id,
+        &ReviewAssignment {
+            reviewer,
+            due_height,
+        },
+    )
+}
+
+/// Removes the assignment of the pending proposal with APPROVAL_ID, if
+/// it has one.
+pub(crate) fn unassign_review(store: &mut dyn Storage, approval_id: u64) -> StdResult<()> {
+    if let Some(assignment) = REVIEW_ASSIGNMENTS.may_load(store, approval_id)? {
+        let reviewer = &assignment.reviewer;
+        REVIEWER_QUEUES.remove(store, (reviewer, approval_id));
+        match REVIEWER_ASSIGNED
+            .may_load(store, reviewer)?
+            .unwrap_or_default()
+        {
+            0 | 1 => REVIEWER_ASSIGNED.remove(store, reviewer),
+            assigned => REVIEWER_ASSIGNED.save(store, reviewer, &(assigned - 1))?,
+        }
+        if let Some(due_height) = assignment.due_height {
+            REVIEWS_DUE.remove(store, (reviewer, due_height, approval_id));
+        }
+        REVIEW_ASSIGNMENTS.remove(store, approval_id);
+    }
+    Ok(())
+}
+
+/// Loads the assignment of the pending proposal with APPROVAL_ID,
+/// ignoring assignments to addresses that are no longer reviewers.
+pub(crate) fn load_assignment(
+    store: &dyn Storage,
+    approval_id: u64,
+) -> StdResult<Option<ReviewAssignment>> {
+    let reviewers = REVIEW_CONFIG
+        .may_load(store)?
+        .map(|config| config.reviewers)
+        .unwrap_or_default();
+    Ok(REVIEW_ASSIGNMENTS
+        .may_load(store, approval_id)?
+        .filter(|assignment| reviewers.contains(&assignment.reviewer)))
+}
+
 pub(crate) fn advance_approval_id(store: &mut dyn Storage) -> StdResult<u64> {
     let id: u64 = CURRENT_ID.may_load(store)?.unwrap_or_default() + 1;
     CURRENT_ID.save(store, &id)?;
@@ -17,7 +17,11 @@ use dao_voting::{
     voting::Vote,
 };
 
-use crate::{contract::*, msg::*, state::PendingProposal};
+use crate::{
+    contract::*,
+    msg::*,
+    state::{AssignmentMode, PendingProposal, ReviewAssignment},
+};
 
 fn cw_dao_proposal_single_contract() -> Box<dyn Contract<Empty>> {
     let contract = ContractWrapper::new(
@@ -31,7 +35,7 @@ fn cw_dao_proposal_single_contract() -> Box<dyn Contract<Empty>> {
 }
 
 fn cw_pre_propose_base_proposal_single() -> Box<dyn Contract<Empty>> {
-    let contract = ContractWrapper::new(execute, instantiate, query);
+    let contract = ContractWrapper::new(execute, instantiate, query).with_reply(reply);
     Box::new(contract)
 }
 
@@ -141,6 +145,7 @@ fn setup_default_test(
             &dao_interface::msg::QueryMsg::ProposalModules {
                 start_after: None,
                 limit: None,
+                descending: None,
             },
         )
         .unwrap();
@@ -1551,3 +1556,206 @@ fn test_withdraw() {
     let balance = get_balance_native(&app, core_addr.as_str(), "ujuno");
     assert_eq!(balance, Uint128::new(30));
 }
+
+#[test]
+fn test_review_assignment() {
+    let mut app = App::default();
+    let DefaultTestSetup { pre_propose, .. } = setup_default_test(&mut app, None, true);
+
+    let update_review_config = |app: &mut App, sender: &str, config| {
+        app.execute_contract(
+            Addr::unchecked(sender),
+            pre_propose.clone(),
+            &ExecuteMsg::Extension {
+                msg: ExecuteExt::UpdateReviewConfig { config },
+            },
+            &[],
+        )
+    };
+    let review = |app: &mut App, sender: &str, msg| {
+        app.execute_contract(
+            Addr::unchecked(sender),
+            pre_propose.clone(),
+            &ExecuteMsg::Extension { msg },
+            &[],
+        )
+    };
+    let query_queue = |app: &App, reviewer: &str| -> Vec<u64> {
+        let pending: Vec<PendingProposal> = app
+            .wrap()
+            .query_wasm_smart(
+                pre_propose.clone(),
+                &QueryMsg::QueryExtension {
+                    msg: QueryExt::ReviewerPendingProposals {
+                        reviewer: reviewer.to_string(),
+                        start_after: None,
+                        limit: None,
+                    },
+                },
+            )
+            .unwrap();
+        pending.into_iter().map(|p| p.approval_id).collect()
+    };
+
+    let round_robin = UncheckedReviewConfig {
+        reviewers: vec!["alice".to_string(), "bob".to_string()],
+        mode: AssignmentMode::RoundRobin,
+        review_blocks: Some(10),
+    };
+    let err: PreProposeError = update_review_config(&mut app, "ekez", Some(round_robin.clone()))
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, PreProposeError::Unauthorized {});
+    let err: PreProposeError = update_review_config(
+        &mut app,
+        "approver",
+        Some(UncheckedReviewConfig {
+            reviewers: vec![],
+            ..round_robin.clone()
+        }),
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, PreProposeError::InvalidReviewConfig {});
+
+    update_review_config(&mut app, "approver", Some(round_robin)).unwrap();
+
+    let first = make_pre_proposal(&mut app, pre_propose.clone(), "ekez", &[]);
+    let second = make_pre_proposal(&mut app, pre_propose.clone(), "ekez", &[]);
+    let third = make_pre_proposal(&mut app, pre_propose.clone(), "ekez", &[]);
+    assert_eq!(query_queue(&app, "alice"), vec![first, third]);
+    assert_eq!(query_queue(&app, "bob"), vec![second]);
+
+    let assignment: Option<ReviewAssignment> = app
+        .wrap()
+        .query_wasm_smart(
+            pre_propose.clone(),
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::ReviewAssignment { id: second },
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        assignment,
+        Some(ReviewAssignment {
+            reviewer: Addr::unchecked("bob"),
+            due_height: Some(app.block_info().height + 10),
+        })
+    );
+
+    app.update_block(|block| block.height += 11);
+    approve_proposal(&mut app, pre_propose.clone(), "approver", first);
+    let workloads: Vec<ReviewerWorkload> = app
+        .wrap()
+        .query_wasm_smart(
+            pre_propose.clone(),
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::ReviewerWorkloads {
+                    start_after: None,
+                    limit: None,
+                },
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        workloads,
+        vec![
+            ReviewerWorkload {
+                reviewer: Addr::unchecked("alice"),
+                assigned: 1,
+                overdue: 1,
+            },
+            ReviewerWorkload {
+                reviewer: Addr::unchecked("bob"),
+                assigned: 1,
+                overdue: 1,
+            },
+        ]
+    );
+    let workloads: Vec<ReviewerWorkload> = app
+        .wrap()
+        .query_wasm_smart(
+            pre_propose.clone(),
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::ReviewerWorkloads {
+                    start_after: Some("alice".to_string()),
+                    limit: Some(1),
+                },
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        workloads,
+        vec![ReviewerWorkload {
+            reviewer: Addr::unchecked("bob"),
+            assigned: 1,
+            overdue: 1,
+        }]
+    );
+
+    // Removing a reviewer releases the proposals assigned to them so
+    // that they may be claimed.
+    update_review_config(
+        &mut app,
+        "approver",
+        Some(UncheckedReviewConfig {
+            reviewers: vec!["bob".to_string()],
+            mode: AssignmentMode::SelfClaim,
+            review_blocks: None,
+        }),
+    )
+    .unwrap();
+    assert_eq!(query_queue(&app, "alice"), Vec::<u64>::new());
+
+    let err: PreProposeError = review(&mut app, "alice", ExecuteExt::ClaimReview { id: third })
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, PreProposeError::NotReviewer {});
+    let err: PreProposeError = review(&mut app, "bob", ExecuteExt::ClaimReview { id: second })
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, PreProposeError::ReviewAlreadyAssigned { id: second });
+
+    review(&mut app, "bob", ExecuteExt::ClaimReview { id: third }).unwrap();
+    assert_eq!(query_queue(&app, "bob"), vec![second, third]);
+
+    // Self-claim mode does not assign new proposals.
+    let fourth = make_pre_proposal(&mut app, pre_propose.clone(), "ekez", &[]);
+    assert_eq!(query_queue(&app, "bob"), vec![second, third]);
+    let err: PreProposeError = review(
+        &mut app,
+        "ekez",
+        ExecuteExt::AssignReview {
+            id: fourth,
+            reviewer: "bob".to_string(),
+        },
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, PreProposeError::Unauthorized {});
+    review(
+        &mut app,
+        "approver",
+        ExecuteExt::AssignReview {
+            id: fourth,
+            reviewer: "bob".to_string(),
+        },
+    )
+    .unwrap();
+
+    reject_proposal(&mut app, pre_propose.clone(), "approver", second);
+    assert_eq!(query_queue(&app, "bob"), vec![third, fourth]);
+
+    update_review_config(&mut app, "approver", None).unwrap();
+    assert_eq!(query_queue(&app, "bob"), Vec::<u64>::new());
+    let err: PreProposeError = review(&mut app, "bob", ExecuteExt::ClaimReview { id: third })
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, PreProposeError::ReviewsDisabled {});
+}
@@ -1,8 +1,8 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult,
-    WasmMsg,
+    to_json_binary, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Reply, Response,
+    StdResult, WasmMsg,
 };
 use cw2::set_contract_version;
 
@@ -82,6 +82,9 @@ pub fn execute(
     match msg {
         // Override default pre-propose-base behavior
         ExecuteMsg::Propose { msg } => execute_propose(deps, info, msg),
+        // Proposals made here take no deposit, so the option is
+        // ignored.
+        ExecuteMsg::ProposeWithDepositOption { msg, .. } => execute_propose(deps, info, msg),
         ExecuteMsg::ProposalCompletedHook {
             proposal_id,
             new_status,
@@ -126,12 +129,21 @@ pub fn execute_propose(
     )?;
     PROPOSAL_IDS.save(deps.storage, proposal_id, &pre_propose_id)?;
 
+    let submission_hooks = PrePropose::default().submission_created_hooks(
+        deps.storage,
+        info.sender,
+        proposal_id,
+        false,
+    )?;
+
     let propose_messsage = WasmMsg::Execute {
         contract_addr: proposal_module.into_string(),
         msg: to_json_binary(&sanitized_msg)?,
         funds: vec![],
     };
-    Ok(Response::default().add_message(propose_messsage))
+    Ok(Response::default()
+        .add_message(propose_messsage)
+        .add_submessages(submission_hooks))
 }
 
 pub fn execute_proposal_completed(
@@ -192,3 +204,8 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         _ => PrePropose::default().query(deps, env, msg),
     }
 }
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, PreProposeError> {
+    PrePropose::default().reply(deps, env, msg)
+}
@@ -46,7 +46,8 @@ fn cw_pre_propose_base_proposal_single() -> Box<dyn Contract<Empty>> {
         dao_pre_propose_approval_single::contract::execute,
         dao_pre_propose_approval_single::contract::instantiate,
         dao_pre_propose_approval_single::contract::query,
-    );
+    )
+    .with_reply(dao_pre_propose_approval_single::contract::reply);
     Box::new(contract)
 }
 
@@ -64,7 +65,8 @@ fn pre_propose_approver_contract() -> Box<dyn Contract<Empty>> {
         crate::contract::execute,
         crate::contract::instantiate,
         crate::contract::query,
-    );
+    )
+    .with_reply(crate::contract::reply);
     Box::new(contract)
 }
 
@@ -203,6 +205,7 @@ fn setup_default_test(
             &dao_interface::msg::QueryMsg::ProposalModules {
                 start_after: None,
                 limit: None,
+                descending: None,
             },
         )
         .unwrap();
@@ -257,6 +260,7 @@ fn setup_default_test(
             &dao_interface::msg::QueryMsg::ProposalModules {
                 start_after: None,
                 limit: None,
+                descending: None,
             },
         )
         .unwrap();
@@ -419,6 +423,7 @@ fn get_proposals(app: &App, module: Addr) -> ProposalListResponse {
             &dps::msg::QueryMsg::ListProposals {
                 start_after: None,
                 limit: None,
+                descending: None,
             },
         )
         .unwrap()
@@ -432,11 +437,12 @@ fn get_latest_proposal_id(app: &App, module: Addr) -> u64 {
             module,
             &dps::msg::QueryMsg::ListProposals {
                 start_after: None,
-                limit: None,
+                limit: Some(1),
+                descending: Some(true),
             },
         )
         .unwrap();
-    props.proposals[props.proposals.len() - 1].id
+    props.proposals[0].id
 }
 
 fn update_config(
@@ -1,7 +1,7 @@
 use cosmwasm_schema::cw_serde;
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
-use cosmwasm_std::{Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult};
+use cosmwasm_std::{Binary, Deps, DepsMut, Empty, Env, MessageInfo, Reply, Response, StdResult};
 use cw2::set_contract_version;
 
 use dao_pre_propose_base::{
@@ -82,6 +82,23 @@ pub fn execute(
                 choices,
             },
         },
+        ExecuteMsg::ProposeWithDepositOption {
+            msg:
+                ProposeMessage::Propose {
+                    title,
+                    description,
+                    choices,
+                },
+            option,
+        } => ExecuteInternal::ProposeWithDepositOption {
+            msg: ProposeMessageInternal::Propose {
+                proposer: Some(info.sender.to_string()),
+                title,
+                description,
+                choices,
+            },
+            option,
+        },
         ExecuteMsg::Extension { msg } => ExecuteInternal::Extension { msg },
         ExecuteMsg::Withdraw { denom } => ExecuteInternal::Withdraw { denom },
         ExecuteMsg::UpdateConfig {
@@ -91,12 +108,22 @@ pub fn execute(
             deposit_info,
             open_proposal_submission,
         },
+        ExecuteMsg::UpdateSubmissionFee { submission_fee } => {
+            ExecuteInternal::UpdateSubmissionFee { submission_fee }
+        }
+        ExecuteMsg::UpdateDepositOptions { options } => {
+            ExecuteInternal::UpdateDepositOptions { options }
+        }
         ExecuteMsg::AddProposalSubmittedHook { address } => {
             ExecuteInternal::AddProposalSubmittedHook { address }
         }
         ExecuteMsg::RemoveProposalSubmittedHook { address } => {
             ExecuteInternal::RemoveProposalSubmittedHook { address }
         }
+        ExecuteMsg::AddSubmissionHook { address } => ExecuteInternal::AddSubmissionHook { address },
+        ExecuteMsg::RemoveSubmissionHook { address } => {
+            ExecuteInternal::RemoveSubmissionHook { address }
+        }
         ExecuteBase::ProposalCompletedHook {
             proposal_id,
             new_status,
@@ -113,3 +140,8 @@ pub fn execute(
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     PrePropose::default().query(deps, env, msg)
 }
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, PreProposeError> {
+    PrePropose::default().reply(deps, env, msg)
+}
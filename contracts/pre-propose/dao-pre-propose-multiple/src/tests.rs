@@ -34,7 +34,7 @@ fn cw_dao_proposal_multiple_contract() -> Box<dyn Contract<Empty>> {
 }
 
 fn cw_pre_propose_base_proposal_single() -> Box<dyn Contract<Empty>> {
-    let contract = ContractWrapper::new(execute, instantiate, query);
+    let contract = ContractWrapper::new(execute, instantiate, query).with_reply(reply);
     Box::new(contract)
 }
 
@@ -141,6 +141,7 @@ fn setup_default_test(
             &dao_interface::msg::QueryMsg::ProposalModules {
                 start_after: None,
                 limit: None,
+                descending: None,
             },
         )
         .unwrap();
@@ -194,11 +195,15 @@ fn make_proposal(
                             description: "multiple choice option 1".to_string(),
                             msgs: vec![],
                             title: "title".to_string(),
+                            metadata: None,
+                            oracle_amounts: vec![],
                         },
                         MultipleChoiceOption {
                             description: "multiple choice option 2".to_string(),
                             msgs: vec![],
                             title: "title".to_string(),
+                            metadata: None,
+                            oracle_amounts: vec![],
                         },
                     ],
                 },
@@ -235,6 +240,8 @@ fn make_proposal(
                 vote_count: Uint128::zero(),
                 index: 0,
                 title: "title".to_string(),
+                metadata: None,
+                oracle_amounts: vec![],
             },
             CheckedMultipleChoiceOption {
                 description: "multiple choice option 2".to_string(),
@@ -243,6 +250,8 @@ fn make_proposal(
                 vote_count: Uint128::zero(),
                 index: 1,
                 title: "title".to_string(),
+                metadata: None,
+                oracle_amounts: vec![],
             },
             CheckedMultipleChoiceOption {
                 description: "None of the above".to_string(),
@@ -251,6 +260,8 @@ fn make_proposal(
                 vote_count: Uint128::zero(),
                 index: 2,
                 title: "None of the above".to_string(),
+                metadata: None,
+                oracle_amounts: vec![],
             },
         ]
     );
@@ -866,6 +877,8 @@ fn test_permissions() {
                             description: "multiple choice option 1".to_string(),
                             msgs: vec![],
                             title: "title".to_string(),
+                            metadata: None,
+                            oracle_amounts: vec![],
                         }],
                     },
                 },
@@ -972,6 +985,8 @@ fn test_no_deposit_required_members_submission() {
                             description: "multiple choice option 1".to_string(),
                             msgs: vec![],
                             title: "title".to_string(),
+                            metadata: None,
+                            oracle_amounts: vec![],
                         }],
                     },
                 },
@@ -1,12 +1,12 @@
 use cosmwasm_schema::write_api;
 use cosmwasm_std::Empty;
 use dao_pre_propose_base::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
-use dao_pre_propose_single::ProposeMessage;
+use dao_pre_propose_single::{ExecuteExt, ProposeMessage, QueryExt};
 
 fn main() {
     write_api! {
         instantiate: InstantiateMsg<Empty>,
-        query: QueryMsg<Empty>,
-        execute: ExecuteMsg<ProposeMessage, Empty>,
+        query: QueryMsg<QueryExt>,
+        execute: ExecuteMsg<ProposeMessage, ExecuteExt>,
     }
 }
@@ -1,17 +1,29 @@
-use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::{cw_serde, QueryResponses};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult,
+    to_json_binary, Addr, Binary, CosmosMsg, Decimal, Deps, DepsMut, Empty, Env, MessageInfo,
+    Order, Reply, Response, StdResult, Uint128,
 };
 use cw2::set_contract_version;
+use cw_paginate_storage::{clamp_limit, paginate_map_values};
 
 use dao_pre_propose_base::{
     error::PreProposeError,
     msg::{ExecuteMsg as ExecuteBase, InstantiateMsg as InstantiateBase, QueryMsg as QueryBase},
     state::PreProposeContract,
 };
-use dao_voting::proposal::SingleChoiceProposeMsg as ProposeMsg;
+use dao_voting::{
+    deposit::{CheckedDepositInfo, DepositRefundPolicy},
+    proposal::SingleChoiceProposeMsg as ProposeMsg,
+    status::Status,
+};
+
+use crate::state::{
+    advance_co_authored_id, advance_draft_id, close_draft, deposit_multiplier, CoAuthoredProposal,
+    CurvePoint, DepositShare, Draft, Reputation, SharedDeposit, CO_AUTHORED, DRAFTS,
+    MAX_OPEN_DRAFTS, OPEN_DRAFTS, PROPOSERS, REPUTATIONS, REPUTATION_CURVE, SHARED_DEPOSITS,
+};
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-pre-propose-single";
 pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -29,9 +41,108 @@ pub enum ProposeMessage {
     },
 }
 
+/// An address credited as a co-author of a proposal and the amount
+/// of its deposit they pay.
+#[cw_serde]
+pub struct CoAuthor {
+    pub address: String,
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub enum ExecuteExt {
+    /// Drafts a proposal. Any address may create a draft, even if it
+    /// may not create proposals itself. Drafts do not require a
+    /// deposit and do not enter voting until they are sponsored. An
+    /// address may have at most `MAX_OPEN_DRAFTS` drafts awaiting
+    /// sponsorship.
+    SubmitDraft {
+        title: String,
+        description: String,
+        msgs: Vec<CosmosMsg<Empty>>,
+    },
+    /// Sponsors a draft, creating a proposal from it. The sender must
+    /// be permitted to create proposals and pays the proposal deposit,
+    /// which is refunded to them according to the refund policy. The
+    /// drafter is set as the proposal's proposer.
+    Sponsor { draft_id: u64 },
+    /// Removes a draft that has not been sponsored. Only the drafter
+    /// may withdraw their draft.
+    WithdrawDraft { draft_id: u64 },
+    /// Sets the curve that scales each proposer's deposit by their
+    /// reputation. `None` removes the curve, so that every proposer
+    /// pays the configured deposit. Only the DAO may execute this
+    /// message.
+    UpdateReputationCurve { curve: Option<Vec<CurvePoint>> },
+    /// Starts a proposal whose deposit is shared with CO_AUTHORS. The
+    /// sender is the proposal's proposer and pays the deposit left
+    /// over after the co-authors' shares with this message. The
+    /// proposal enters voting once it is submitted with
+    /// `SubmitCoAuthored`.
+    ProposeWithCoAuthors {
+        title: String,
+        description: String,
+        msgs: Vec<CosmosMsg<Empty>>,
+        co_authors: Vec<CoAuthor>,
+    },
+    /// Pays the sender's share of a co-authored proposal's deposit.
+    FundCoAuthored { id: u64 },
+    /// Creates a proposal from a co-authored proposal whose deposit
+    /// has been paid in full. Only its proposer may submit it, and
+    /// they pay any submission fee.
+    SubmitCoAuthored { id: u64 },
+    /// Removes a co-authored proposal that has not been submitted and
+    /// returns the shares paid so far. Only its proposer may withdraw
+    /// it.
+    WithdrawCoAuthored { id: u64 },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryExt {
+    /// A draft awaiting sponsorship.
+    #[returns(crate::state::Draft)]
+    Draft { draft_id: u64 },
+    /// Lists drafts awaiting sponsorship in ascending order of ID.
+    #[returns(Vec<crate::state::Draft>)]
+    ListDrafts {
+        start_after: Option<u64>,
+        /// The maximum number of drafts to return. If no limit is set
+        /// 10 are returned, and at most 30 are.
+        limit: Option<u32>,
+    },
+    /// The outcomes of an address's past proposals and the deposit
+    /// they must pay to create a proposal.
+    #[returns(ReputationResponse)]
+    Reputation { address: String },
+    /// The curve mapping reputation scores to deposit multipliers, if
+    /// one is set.
+    #[returns(Option<Vec<crate::state::CurvePoint>>)]
+    ReputationCurve {},
+    /// A co-authored proposal that has not been submitted.
+    #[returns(crate::state::CoAuthoredProposal)]
+    CoAuthored { id: u64 },
+    /// The co-authors of a submitted proposal and their shares of its
+    /// deposit, if it was co-authored and has not completed.
+    #[returns(Option<crate::state::SharedDeposit>)]
+    SharedDeposit { proposal_id: u64 },
+}
+
+#[cw_serde]
+pub struct ReputationResponse {
+    pub reputation: Reputation,
+    /// Executed proposals less rejected ones.
+    pub score: i64,
+    /// The multiplier applied to the configured deposit for this
+    /// address.
+    pub multiplier: Decimal,
+    /// The deposit this address must pay to create a proposal.
+    pub deposit_info: Option<CheckedDepositInfo>,
+}
+
 pub type InstantiateMsg = InstantiateBase<Empty>;
-pub type ExecuteMsg = ExecuteBase<ProposeMessage, Empty>;
-pub type QueryMsg = QueryBase<Empty>;
+pub type ExecuteMsg = ExecuteBase<ProposeMessage, ExecuteExt>;
+pub type QueryMsg = QueryBase<QueryExt>;
 
 /// Internal version of the propose message that includes the
 /// `proposer` field. The module will fill this in based on the sender
@@ -41,7 +152,7 @@ enum ProposeMessageInternal {
     Propose(ProposeMsg),
 }
 
-type PrePropose = PreProposeContract<Empty, Empty, Empty, ProposeMessageInternal>;
+type PrePropose = PreProposeContract<Empty, ExecuteExt, QueryExt, ProposeMessageInternal>;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -66,7 +177,7 @@ pub fn execute(
     // message externally as that is to be set by this module. Here,
     // we transform an external message which omits that field into an
     // internal message which sets it.
-    type ExecuteInternal = ExecuteBase<ProposeMessageInternal, Empty>;
+    type ExecuteInternal = ExecuteBase<ProposeMessageInternal, ExecuteExt>;
     let internalized = match msg {
         ExecuteMsg::Propose {
             msg:
@@ -75,16 +186,77 @@ pub fn execute(
                     description,
                     msgs,
                 },
-        } => ExecuteInternal::Propose {
-            msg: ProposeMessageInternal::Propose(ProposeMsg {
+        } => {
+            let msg = ProposeMsg {
                 // Fill in proposer based on message sender.
                 proposer: Some(info.sender.to_string()),
                 title,
                 description,
                 msgs,
-            }),
+            };
+            let proposer = info.sender.clone();
+            let deposit_info = reputation_deposit(deps.as_ref(), &info.sender)?;
+            return execute_propose(deps, env, info, proposer, msg, deposit_info);
+        }
+        ExecuteMsg::ProposeWithDepositOption {
+            msg:
+                ProposeMessage::Propose {
+                    title,
+                    description,
+                    msgs,
+                },
+            option,
+        } => {
+            let msg = ProposeMsg {
+                proposer: Some(info.sender.to_string()),
+                title,
+                description,
+                msgs,
+            };
+            let proposer = info.sender.clone();
+            let deposit_info = PrePropose::default().deposit_option(deps.storage, option)?;
+            let deposit_info = scale_deposit(deps.as_ref(), &info.sender, Some(deposit_info))?;
+            return execute_propose(deps, env, info, proposer, msg, deposit_info);
+        }
+        ExecuteMsg::Extension { msg } => match msg {
+            ExecuteExt::SubmitDraft {
+                title,
+                description,
+                msgs,
+            } => return execute_submit_draft(deps, info, title, description, msgs),
+            ExecuteExt::Sponsor { draft_id } => return execute_sponsor(deps, env, info, draft_id),
+            ExecuteExt::WithdrawDraft { draft_id } => {
+                return execute_withdraw_draft(deps, info, draft_id)
+            }
+            ExecuteExt::UpdateReputationCurve { curve } => {
+                return execute_update_reputation_curve(deps, info, curve)
+            }
+            ExecuteExt::ProposeWithCoAuthors {
+                title,
+                description,
+                msgs,
+                co_authors,
+            } => {
+                return execute_propose_with_co_authors(
+                    deps,
+                    env,
+                    info,
+                    title,
+                    description,
+                    msgs,
+                    co_authors,
+                )
+            }
+            ExecuteExt::FundCoAuthored { id } => {
+                return execute_fund_co_authored(deps, env, info, id)
+            }
+            ExecuteExt::SubmitCoAuthored { id } => {
+                return execute_submit_co_authored(deps, env, info, id)
+            }
+            ExecuteExt::WithdrawCoAuthored { id } => {
+                return execute_withdraw_co_authored(deps, info, id)
+            }
         },
-        ExecuteMsg::Extension { msg } => ExecuteInternal::Extension { msg },
         ExecuteMsg::Withdraw { denom } => ExecuteInternal::Withdraw { denom },
         ExecuteMsg::UpdateConfig {
             deposit_info,
@@ -93,25 +265,528 @@ pub fn execute(
             deposit_info,
             open_proposal_submission,
         },
+        ExecuteMsg::UpdateSubmissionFee { submission_fee } => {
+            ExecuteInternal::UpdateSubmissionFee { submission_fee }
+        }
+        ExecuteMsg::UpdateDepositOptions { options } => {
+            ExecuteInternal::UpdateDepositOptions { options }
+        }
         ExecuteMsg::AddProposalSubmittedHook { address } => {
             ExecuteInternal::AddProposalSubmittedHook { address }
         }
         ExecuteMsg::RemoveProposalSubmittedHook { address } => {
             ExecuteInternal::RemoveProposalSubmittedHook { address }
         }
+        ExecuteMsg::AddSubmissionHook { address } => ExecuteInternal::AddSubmissionHook { address },
+        ExecuteMsg::RemoveSubmissionHook { address } => {
+            ExecuteInternal::RemoveSubmissionHook { address }
+        }
         ExecuteMsg::ProposalCompletedHook {
             proposal_id,
             new_status,
-        } => ExecuteInternal::ProposalCompletedHook {
-            proposal_id,
-            new_status,
-        },
+        } => {
+            return execute_proposal_completed_hook(deps, info, proposal_id, new_status);
+        }
     };
 
     PrePropose::default().execute(deps, env, info, internalized)
 }
 
+/// Gets the deposit that PAYER must pay to create a proposal: the
+/// configured deposit scaled by the multiplier for their reputation.
+fn reputation_deposit(deps: Deps, payer: &Addr) -> StdResult<Option<CheckedDepositInfo>> {
+    let config = PrePropose::default().config.load(deps.storage)?;
+    scale_deposit(deps, payer, config.deposit_info)
+}
+
+/// Scales DEPOSIT_INFO by the multiplier for PAYER's reputation.
+fn scale_deposit(
+    deps: Deps,
+    payer: &Addr,
+    deposit_info: Option<CheckedDepositInfo>,
+) -> StdResult<Option<CheckedDepositInfo>> {
+    let curve = match REPUTATION_CURVE.may_load(deps.storage)? {
+        Some(curve) => curve,
+        None => return Ok(deposit_info),
+    };
+    let score = REPUTATIONS
+        .may_load(deps.storage, payer)?
+        .unwrap_or_default()
+        .score();
+    let multiplier = deposit_multiplier(&curve, score);
+    // A deposit scaled to nothing is not taken at all.
+    Ok(deposit_info.and_then(|deposit_info| {
+        let amount = deposit_info.amount * multiplier;
+        (!amount.is_zero()).then_some(CheckedDepositInfo {
+            amount,
+            ..deposit_info
+        })
+    }))
+}
+
+/// Creates a proposal, taking DEPOSIT_INFO from the sender, and
+/// records PROPOSER so that the proposal's outcome counts towards
+/// their reputation.
+fn execute_propose(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposer: Addr,
+    msg: ProposeMsg,
+    deposit_info: Option<CheckedDepositInfo>,
+) -> Result<Response, PreProposeError> {
+    let proposal_module = PrePropose::default().proposal_module.load(deps.storage)?;
+    let next_id: u64 = deps.querier.query_wasm_smart(
+        &proposal_module,
+        &dao_interface::proposal::Query::NextProposalId {},
+    )?;
+    PROPOSERS.save(deps.storage, next_id, &proposer)?;
+
+    PrePropose::default().execute_propose_with_deposit(
+        deps,
+        env,
+        info,
+        ProposeMessageInternal::Propose(msg),
+        deposit_info,
+    )
+}
+
+pub fn execute_submit_draft(
+    deps: DepsMut,
+    info: MessageInfo,
+    title: String,
+    description: String,
+    msgs: Vec<CosmosMsg<Empty>>,
+) -> Result<Response, PreProposeError> {
+    let open = OPEN_DRAFTS
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    if open >= MAX_OPEN_DRAFTS {
+        return Err(PreProposeError::TooManyDrafts {
+            max: MAX_OPEN_DRAFTS,
+        });
+    }
+    OPEN_DRAFTS.save(deps.storage, &info.sender, &(open + 1))?;
+
+    let draft_id = advance_draft_id(deps.storage)?;
+    DRAFTS.save(
+        deps.storage,
+        draft_id,
+        &Draft {
+            draft_id,
+            drafter: info.sender.clone(),
+            title,
+            description,
+            msgs,
+        },
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("method", "submit_draft")
+        .add_attribute("drafter", info.sender)
+        .add_attribute("draft_id", draft_id.to_string()))
+}
+
+pub fn execute_sponsor(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    draft_id: u64,
+) -> Result<Response, PreProposeError> {
+    let Draft {
+        drafter,
+        title,
+        description,
+        msgs,
+        ..
+    } = DRAFTS
+        .may_load(deps.storage, draft_id)?
+        .ok_or(PreProposeError::ProposalNotFound {})?;
+    DRAFTS.remove(deps.storage, draft_id);
+    close_draft(deps.storage, &drafter)?;
+
+    let sponsor = info.sender.clone();
+    let deposit_info = reputation_deposit(deps.as_ref(), &sponsor)?;
+
+    // The sponsor is the message sender, so permission checks and
+    // the deposit are applied to them and the deposit is recorded
+    // as theirs for refunds. The drafter is credited as the
+    // proposer, and the proposal's outcome counts towards their
+    // reputation.
+    let response = execute_propose(
+        deps,
+        env,
+        info,
+        drafter.clone(),
+        ProposeMsg {
+            proposer: Some(drafter.to_string()),
+            title,
+            description,
+            msgs,
+        },
+        deposit_info,
+    )?;
+
+    Ok(response
+        .add_attribute("draft_id", draft_id.to_string())
+        .add_attribute("drafter", drafter)
+        .add_attribute("sponsor", sponsor))
+}
+
+pub fn execute_withdraw_draft(
+    deps: DepsMut,
+    info: MessageInfo,
+    draft_id: u64,
+) -> Result<Response, PreProposeError> {
+    let draft = DRAFTS
+        .may_load(deps.storage, draft_id)?
+        .ok_or(PreProposeError::ProposalNotFound {})?;
+    if draft.drafter != info.sender {
+        return Err(PreProposeError::Unauthorized {});
+    }
+    DRAFTS.remove(deps.storage, draft_id);
+    close_draft(deps.storage, &draft.drafter)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "withdraw_draft")
+        .add_attribute("draft_id", draft_id.to_string()))
+}
+
+pub fn execute_update_reputation_curve(
+    deps: DepsMut,
+    info: MessageInfo,
+    curve: Option<Vec<CurvePoint>>,
+) -> Result<Response, PreProposeError> {
+    let dao = PrePropose::default().dao.load(deps.storage)?;
+    if info.sender != dao {
+        return Err(PreProposeError::NotDao {});
+    }
+
+    match curve {
+        Some(curve) => {
+            if curve.is_empty()
+                || curve
+                    .windows(2)
+                    .any(|points| points[0].min_score >= points[1].min_score)
+            {
+                return Err(PreProposeError::InvalidReputationCurve {});
+            }
+            REPUTATION_CURVE.save(deps.storage, &curve)?;
+        }
+        None => REPUTATION_CURVE.remove(deps.storage),
+    }
+
+    Ok(Response::default()
+        .add_attribute("method", "update_reputation_curve")
+        .add_attribute("sender", info.sender))
+}
+
+/// Checks that the sender has paid AMOUNT of DEPOSIT_INFO's native
+/// deposit, or returns the messages that take AMOUNT of its cw20
+/// deposit from them.
+fn take_share(
+    env: &Env,
+    info: &MessageInfo,
+    deposit_info: &CheckedDepositInfo,
+    amount: Uint128,
+) -> Result<Vec<CosmosMsg>, PreProposeError> {
+    if amount.is_zero() {
+        return Ok(vec![]);
+    }
+    let share = CheckedDepositInfo {
+        amount,
+        ..deposit_info.clone()
+    };
+    share.check_native_deposit_paid(info)?;
+    Ok(share.get_take_deposit_messages(&info.sender, &env.contract.address)?)
+}
+
+/// Gets the messages returning each paid share of DEPOSIT to the
+/// address that paid it.
+fn return_shares(deposit: &SharedDeposit) -> StdResult<Vec<CosmosMsg>> {
+    let mut messages = vec![];
+    for share in deposit.shares.iter().filter(|share| share.paid) {
+        let returned = CheckedDepositInfo {
+            amount: share.amount,
+            ..deposit.deposit_info.clone()
+        };
+        messages.extend(returned.get_return_deposit_message(&share.address)?);
+    }
+    Ok(messages)
+}
+
+pub fn execute_propose_with_co_authors(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    title: String,
+    description: String,
+    msgs: Vec<CosmosMsg<Empty>>,
+    co_authors: Vec<CoAuthor>,
+) -> Result<Response, PreProposeError> {
+    // The deposit is fixed by the proposer's reputation now so that
+    // co-authors know what they are paying.
+    let deposit_info = reputation_deposit(deps.as_ref(), &info.sender)?
+        .ok_or(PreProposeError::DepositRequired {})?;
+
+    if co_authors.is_empty() {
+        return Err(PreProposeError::InvalidCoAuthors {});
+    }
+    let mut co_author_shares: Vec<DepositShare> = Vec::with_capacity(co_authors.len());
+    let mut co_authored = Uint128::zero();
+    for CoAuthor { address, amount } in co_authors {
+        let address = deps.api.addr_validate(&address)?;
+        if amount.is_zero()
+            || address == info.sender
+            || co_author_shares
+                .iter()
+                .any(|share| share.address == address)
+        {
+            return Err(PreProposeError::InvalidCoAuthors {});
+        }
+        co_authored = co_authored
+            .checked_add(amount)
+            .map_err(|_| PreProposeError::InvalidCoAuthors {})?;
+        co_author_shares.push(DepositShare {
+            address,
+            amount,
+            paid: false,
+        });
+    }
+    let proposer_share = deposit_info
+        .amount
+        .checked_sub(co_authored)
+        .map_err(|_| PreProposeError::InvalidCoAuthors {})?;
+
+    let messages = take_share(&env, &info, &deposit_info, proposer_share)?;
+
+    let mut shares = vec![DepositShare {
+        address: info.sender.clone(),
+        amount: proposer_share,
+        paid: true,
+    }];
+    shares.extend(co_author_shares);
+
+    let id = advance_co_authored_id(deps.storage)?;
+    CO_AUTHORED.save(
+        deps.storage,
+        id,
+        &CoAuthoredProposal {
+            id,
+            proposer: info.sender.clone(),
+            title,
+            description,
+            msgs,
+            deposit: SharedDeposit {
+                deposit_info,
+                shares,
+            },
+        },
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("method", "propose_with_co_authors")
+        .add_attribute("proposer", info.sender)
+        .add_attribute("co_authored_id", id.to_string())
+        .add_messages(messages))
+}
+
+pub fn execute_fund_co_authored(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, PreProposeError> {
+    let mut co_authored = CO_AUTHORED
+        .may_load(deps.storage, id)?
+        .ok_or(PreProposeError::ProposalNotFound {})?;
+    let deposit_info = co_authored.deposit.deposit_info.clone();
+    let share = co_authored
+        .deposit
+        .shares
+        .iter_mut()
+        .find(|share| share.address == info.sender && !share.paid)
+        .ok_or(PreProposeError::NoShareDue {})?;
+
+    let messages = take_share(&env, &info, &deposit_info, share.amount)?;
+    share.paid = true;
+    CO_AUTHORED.save(deps.storage, id, &co_authored)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "fund_co_authored")
+        .add_attribute("co_author", info.sender)
+        .add_attribute("co_authored_id", id.to_string())
+        .add_messages(messages))
+}
+
+pub fn execute_submit_co_authored(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, PreProposeError> {
+    let CoAuthoredProposal {
+        proposer,
+        title,
+        description,
+        msgs,
+        deposit,
+        ..
+    } = CO_AUTHORED
+        .may_load(deps.storage, id)?
+        .ok_or(PreProposeError::ProposalNotFound {})?;
+    if proposer != info.sender {
+        return Err(PreProposeError::Unauthorized {});
+    }
+    if deposit.shares.iter().any(|share| !share.paid) {
+        return Err(PreProposeError::SharesUnpaid {});
+    }
+    CO_AUTHORED.remove(deps.storage, id);
+
+    let proposal_module = PrePropose::default().proposal_module.load(deps.storage)?;
+    let next_id: u64 = deps.querier.query_wasm_smart(
+        &proposal_module,
+        &dao_interface::proposal::Query::NextProposalId {},
+    )?;
+    SHARED_DEPOSITS.save(deps.storage, next_id, &deposit)?;
+
+    // The deposit is already held by this module, so the base
+    // contract records no deposit for the proposal and only takes
+    // the submission fee. The shares are returned by this module
+    // when the proposal completes.
+    let response = execute_propose(
+        deps,
+        env,
+        info,
+        proposer.clone(),
+        ProposeMsg {
+            proposer: Some(proposer.to_string()),
+            title,
+            description,
+            msgs,
+        },
+        None,
+    )?;
+
+    Ok(response
+        .add_attribute("co_authored_id", id.to_string())
+        .add_attribute("proposal_id", next_id.to_string()))
+}
+
+pub fn execute_withdraw_co_authored(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, PreProposeError> {
+    let co_authored = CO_AUTHORED
+        .may_load(deps.storage, id)?
+        .ok_or(PreProposeError::ProposalNotFound {})?;
+    if co_authored.proposer != info.sender {
+        return Err(PreProposeError::Unauthorized {});
+    }
+    CO_AUTHORED.remove(deps.storage, id);
+
+    Ok(Response::default()
+        .add_attribute("method", "withdraw_co_authored")
+        .add_attribute("co_authored_id", id.to_string())
+        .add_messages(return_shares(&co_authored.deposit)?))
+}
+
+/// Returns proposal deposits through the base contract, then records
+/// the proposal's outcome against its proposer's reputation.
+pub fn execute_proposal_completed_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    proposal_id: u64,
+    new_status: Status,
+) -> Result<Response, PreProposeError> {
+    let mut response = PrePropose::default().execute_proposal_completed_hook(
+        deps.as_ref(),
+        info,
+        proposal_id,
+        new_status,
+    )?;
+
+    // Shared deposits are held by this module rather than the base
+    // contract, and are refunded to each contributor in proportion
+    // to what they paid under the same policy.
+    if let Some(deposit) = SHARED_DEPOSITS.may_load(deps.storage, proposal_id)? {
+        let refund_policy = &deposit.deposit_info.refund_policy;
+        let should_refund = (new_status == Status::Closed
+            && *refund_policy == DepositRefundPolicy::Always)
+            || (new_status == Status::Executed && *refund_policy != DepositRefundPolicy::Never);
+        let messages = if should_refund {
+            return_shares(&deposit)?
+        } else {
+            let dao = PrePropose::default().dao.load(deps.storage)?;
+            deposit.deposit_info.get_return_deposit_message(&dao)?
+        };
+        SHARED_DEPOSITS.remove(deps.storage, proposal_id);
+        response = response.add_messages(messages);
+    }
+
+    // The base contract only accepts closed and executed
+    // proposals, so any proposal that was not executed was closed.
+    if let Some(proposer) = PROPOSERS.may_load(deps.storage, proposal_id)? {
+        PROPOSERS.remove(deps.storage, proposal_id);
+        let mut reputation = REPUTATIONS
+            .may_load(deps.storage, &proposer)?
+            .unwrap_or_default();
+        if new_status == Status::Executed {
+            reputation.executed += 1;
+        } else {
+            reputation.rejected += 1;
+        }
+        REPUTATIONS.save(deps.storage, &proposer, &reputation)?;
+    }
+
+    Ok(response)
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    PrePropose::default().query(deps, env, msg)
+    match msg {
+        QueryMsg::QueryExtension { msg } => match msg {
+            QueryExt::Draft { draft_id } => to_json_binary(&DRAFTS.load(deps.storage, draft_id)?),
+            QueryExt::ListDrafts { start_after, limit } => to_json_binary(&paginate_map_values(
+                deps,
+                &DRAFTS,
+                start_after,
+                Some(clamp_limit(limit) as u32),
+                Order::Ascending,
+            )?),
+            QueryExt::Reputation { address } => to_json_binary(&query_reputation(deps, address)?),
+            QueryExt::ReputationCurve {} => {
+                to_json_binary(&REPUTATION_CURVE.may_load(deps.storage)?)
+            }
+            QueryExt::CoAuthored { id } => to_json_binary(&CO_AUTHORED.load(deps.storage, id)?),
+            QueryExt::SharedDeposit { proposal_id } => {
+                to_json_binary(&SHARED_DEPOSITS.may_load(deps.storage, proposal_id)?)
+            }
+        },
+        _ => PrePropose::default().query(deps, env, msg),
+    }
+}
+
+pub fn query_reputation(deps: Deps, address: String) -> StdResult<ReputationResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let reputation = REPUTATIONS
+        .may_load(deps.storage, &address)?
+        .unwrap_or_default();
+    let multiplier = REPUTATION_CURVE
+        .may_load(deps.storage)?
+        .map_or(Decimal::one(), |curve| {
+            deposit_multiplier(&curve, reputation.score())
+        });
+    Ok(ReputationResponse {
+        score: reputation.score(),
+        reputation,
+        multiplier,
+        deposit_info: reputation_deposit(deps, &address)?,
+    })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, PreProposeError> {
+    PrePropose::default().reply(deps, env, msg)
 }
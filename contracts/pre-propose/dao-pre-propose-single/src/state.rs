@@ -0,0 +1,151 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, CosmosMsg, Decimal, Empty, StdResult, Storage, Uint128};
+use cw_storage_plus::{Item, Map};
+use dao_voting::deposit::CheckedDepositInfo;
+
+/// A proposal that has been drafted by an address which may not be
+/// permitted to create proposals itself. Drafts enter voting once
+/// they are sponsored by an address that may create proposals.
+#[cw_serde]
+pub struct Draft {
+    /// The ID used to identify this draft.
+    pub draft_id: u64,
+    /// The address that wrote the draft. This address will be set as
+    /// the proposer of the proposal once the draft is sponsored.
+    pub drafter: Addr,
+    pub title: String,
+    pub description: String,
+    pub msgs: Vec<CosmosMsg<Empty>>,
+}
+
+pub const DRAFTS: Map<u64, Draft> = Map::new("drafts");
+
+/// The most drafts an address may have awaiting sponsorship. Drafts
+/// do not require a deposit, so this bounds the storage a drafter can
+/// take up.
+pub const MAX_OPEN_DRAFTS: u64 = 5;
+
+/// The number of drafts each address has awaiting sponsorship.
+pub const OPEN_DRAFTS: Map<&Addr, u64> = Map::new("open_drafts");
+
+/// Used internally to track the current draft_id.
+const CURRENT_DRAFT_ID: Item<u64> = Item::new("current_draft_id");
+
+pub(crate) fn advance_draft_id(store: &mut dyn Storage) -> StdResult<u64> {
+    let id: u64 = CURRENT_DRAFT_ID.may_load(store)?.unwrap_or_default() + 1;
+    CURRENT_DRAFT_ID.save(store, &id)?;
+    Ok(id)
+}
+
+/// Records that a draft by `drafter` was sponsored or withdrawn.
+pub(crate) fn close_draft(store: &mut dyn Storage, drafter: &Addr) -> StdResult<()> {
+    let open = OPEN_DRAFTS.may_load(store, drafter)?.unwrap_or_default();
+    if open <= 1 {
+        OPEN_DRAFTS.remove(store, drafter);
+    } else {
+        OPEN_DRAFTS.save(store, drafter, &(open - 1))?;
+    }
+    Ok(())
+}
+
+/// An address's share of a co-authored proposal's deposit.
+#[cw_serde]
+pub struct DepositShare {
+    pub address: Addr,
+    pub amount: Uint128,
+    /// Whether the share has been paid to this module.
+    pub paid: bool,
+}
+
+/// A deposit split between a proposal's proposer and its co-authors.
+#[cw_serde]
+pub struct SharedDeposit {
+    /// The deposit taken for the proposal. This is the deposit the
+    /// proposer would have paid alone when the proposal was created.
+    pub deposit_info: CheckedDepositInfo,
+    /// The proposer's share followed by each co-author's share. The
+    /// amounts sum to the deposit amount.
+    pub shares: Vec<DepositShare>,
+}
+
+/// A proposal whose deposit is being collected from its proposer
+/// and co-authors. It enters voting once the proposer submits it
+/// with every share paid.
+#[cw_serde]
+pub struct CoAuthoredProposal {
+    /// The ID used to identify this co-authored proposal before it
+    /// is submitted.
+    pub id: u64,
+    pub proposer: Addr,
+    pub title: String,
+    pub description: String,
+    pub msgs: Vec<CosmosMsg<Empty>>,
+    pub deposit: SharedDeposit,
+}
+
+pub const CO_AUTHORED: Map<u64, CoAuthoredProposal> = Map::new("co_authored");
+
+/// Used internally to track the current co-authored proposal ID.
+const CURRENT_CO_AUTHORED_ID: Item<u64> = Item::new("current_co_authored_id");
+
+pub(crate) fn advance_co_authored_id(store: &mut dyn Storage) -> StdResult<u64> {
+    let id: u64 = CURRENT_CO_AUTHORED_ID.may_load(store)?.unwrap_or_default() + 1;
+    CURRENT_CO_AUTHORED_ID.save(store, &id)?;
+    Ok(id)
+}
+
+/// The shared deposit of each submitted co-authored proposal, by
+/// proposal ID. Entries are removed once the deposit is settled.
+pub const SHARED_DEPOSITS: Map<u64, SharedDeposit> = Map::new("shared_deposits");
+
+/// The outcomes of the proposals an address has created through
+/// this module.
+#[cw_serde]
+#[derive(Default)]
+pub struct Reputation {
+    /// The number of the address's proposals that were executed.
+    pub executed: u64,
+    /// The number of the address's proposals that were closed
+    /// without being executed.
+    pub rejected: u64,
+}
+
+impl Reputation {
+    /// The address's reputation score: executed proposals less
+    /// rejected ones.
+    pub fn score(&self) -> i64 {
+        let executed = i64::try_from(self.executed).unwrap_or(i64::MAX);
+        let rejected = i64::try_from(self.rejected).unwrap_or(i64::MAX);
+        executed.saturating_sub(rejected)
+    }
+}
+
+/// A point on the reputation curve. Proposers whose score is at
+/// least `min_score` have their deposit multiplied by `multiplier`,
+/// unless a later point also applies to them.
+#[cw_serde]
+pub struct CurvePoint {
+    pub min_score: i64,
+    pub multiplier: Decimal,
+}
+
+pub const REPUTATIONS: Map<&Addr, Reputation> = Map::new("reputations");
+
+/// The curve mapping reputation scores to deposit multipliers,
+/// ordered by strictly increasing `min_score`. If unset, every
+/// proposer pays the configured deposit.
+pub const REPUTATION_CURVE: Item<Vec<CurvePoint>> = Item::new("reputation_curve");
+
+/// The proposer of each proposal created through this module that
+/// has not yet completed, by proposal ID.
+pub const PROPOSERS: Map<u64, Addr> = Map::new("proposers");
+
+/// Gets the deposit multiplier for a proposer with SCORE. The
+/// multiplier is one if no point on the curve applies.
+pub fn deposit_multiplier(curve: &[CurvePoint], score: i64) -> Decimal {
+    curve
+        .iter()
+        .rev()
+        .find(|point| point.min_score <= score)
+        .map_or(Decimal::one(), |point| point.multiplier)
+}
@@ -1,12 +1,16 @@
-use cosmwasm_std::{coins, from_slice, to_json_binary, Addr, Coin, Empty, Uint128};
+use cosmwasm_std::{coins, from_slice, to_json_binary, Addr, Coin, Decimal, Empty, Uint128};
 use cw2::ContractVersion;
 use cw20::Cw20Coin;
-use cw_denom::UncheckedDenom;
+use cw_denom::{CheckedDenom, UncheckedDenom};
 use cw_multi_test::{App, BankSudo, Contract, ContractWrapper, Executor};
 use cw_utils::Duration;
 use dao_interface::state::ProposalModule;
 use dao_interface::state::{Admin, ModuleInstantiateInfo};
-use dao_pre_propose_base::{error::PreProposeError, msg::DepositInfoResponse, state::Config};
+use dao_pre_propose_base::{
+    error::PreProposeError,
+    msg::{DepositInfoResponse, UncheckedSubmissionFee},
+    state::{Config, SubmissionFee, SubmissionFeeDestination},
+};
 use dao_proposal_single as dps;
 use dao_testing::helpers::instantiate_with_cw4_groups_governance;
 use dao_voting::{
@@ -19,6 +23,9 @@ use dao_voting::{
 use dps::query::ProposalResponse;
 
 use crate::contract::*;
+use crate::state::{
+    CoAuthoredProposal, CurvePoint, DepositShare, Draft, Reputation, SharedDeposit, MAX_OPEN_DRAFTS,
+};
 
 fn cw_dao_proposal_single_contract() -> Box<dyn Contract<Empty>> {
     let contract = ContractWrapper::new(
@@ -32,7 +39,7 @@ fn cw_dao_proposal_single_contract() -> Box<dyn Contract<Empty>> {
 }
 
 fn cw_pre_propose_base_proposal_single() -> Box<dyn Contract<Empty>> {
-    let contract = ContractWrapper::new(execute, instantiate, query);
+    let contract = ContractWrapper::new(execute, instantiate, query).with_reply(reply);
     Box::new(contract)
 }
 
@@ -139,6 +146,7 @@ fn setup_default_test(
             &dao_interface::msg::QueryMsg::ProposalModules {
                 start_after: None,
                 limit: None,
+                descending: None,
             },
         )
         .unwrap();
@@ -372,6 +380,42 @@ fn update_config_should_fail(
     .unwrap()
 }
 
+fn update_submission_fee(
+    app: &mut App,
+    module: Addr,
+    sender: &str,
+    submission_fee: Option<UncheckedSubmissionFee>,
+) -> Option<SubmissionFee> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        module.clone(),
+        &ExecuteMsg::UpdateSubmissionFee { submission_fee },
+        &[],
+    )
+    .unwrap();
+
+    app.wrap()
+        .query_wasm_smart(module, &QueryMsg::SubmissionFee {})
+        .unwrap()
+}
+
+fn update_submission_fee_should_fail(
+    app: &mut App,
+    module: Addr,
+    sender: &str,
+    submission_fee: Option<UncheckedSubmissionFee>,
+) -> PreProposeError {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        module,
+        &ExecuteMsg::UpdateSubmissionFee { submission_fee },
+        &[],
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap()
+}
+
 fn withdraw(app: &mut App, module: Addr, sender: &str, denom: Option<UncheckedDenom>) {
     app.execute_contract(
         Addr::unchecked(sender),
@@ -931,35 +975,206 @@ fn test_no_deposit_required_members_submission() {
 }
 
 #[test]
-fn test_execute_extension_does_nothing() {
+fn test_sponsor_draft() {
     let mut app = App::default();
     let DefaultTestSetup {
-        core_addr: _,
-        proposal_single: _,
+        core_addr,
+        proposal_single,
         pre_propose,
     } = setup_default_test(
-        &mut app, None, false, // no open proposal submission.
+        &mut app,
+        Some(UncheckedDepositInfo {
+            denom: DepositToken::Token {
+                denom: UncheckedDenom::Native("ujuno".to_string()),
+            },
+            amount: Uint128::new(10),
+            refund_policy: DepositRefundPolicy::Always,
+        }),
+        false, // no open proposal submission.
+    );
+
+    // Non-member drafts a proposal. No deposit is required.
+    app.execute_contract(
+        Addr::unchecked("nonmember"),
+        pre_propose.clone(),
+        &ExecuteMsg::Extension {
+            msg: ExecuteExt::SubmitDraft {
+                title: "I would like to join the DAO".to_string(),
+                description: "though, I am currently not a member.".to_string(),
+                msgs: vec![],
+            },
+        },
+        &[],
+    )
+    .unwrap();
+
+    let drafts: Vec<Draft> = app
+        .wrap()
+        .query_wasm_smart(
+            pre_propose.clone(),
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::ListDrafts {
+                    start_after: None,
+                    limit: None,
+                },
+            },
+        )
+        .unwrap();
+    assert_eq!(drafts.len(), 1);
+    assert_eq!(drafts[0].draft_id, 1);
+    assert_eq!(drafts[0].drafter, Addr::unchecked("nonmember"));
+
+    // Non-members may not sponsor drafts.
+    mint_natives(&mut app, "nonmember", coins(10, "ujuno"));
+    let err: PreProposeError = app
+        .execute_contract(
+            Addr::unchecked("nonmember"),
+            pre_propose.clone(),
+            &ExecuteMsg::Extension {
+                msg: ExecuteExt::Sponsor { draft_id: 1 },
+            },
+            &coins(10, "ujuno"),
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, PreProposeError::NotMember {});
+
+    // Only the drafter may withdraw the draft.
+    let err: PreProposeError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            pre_propose.clone(),
+            &ExecuteMsg::Extension {
+                msg: ExecuteExt::WithdrawDraft { draft_id: 1 },
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, PreProposeError::Unauthorized {});
+
+    // A member sponsors the draft and pays the deposit.
+    mint_natives(&mut app, "ekez", coins(10, "ujuno"));
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        pre_propose.clone(),
+        &ExecuteMsg::Extension {
+            msg: ExecuteExt::Sponsor { draft_id: 1 },
+        },
+        &coins(10, "ujuno"),
+    )
+    .unwrap();
+    assert_eq!(get_balance_native(&app, "ekez", "ujuno"), Uint128::zero());
+    assert_eq!(
+        get_balance_native(&app, "nonmember", "ujuno"),
+        Uint128::new(10)
     );
 
-    let res = app
+    // The drafter is the proposer, the sponsor holds the deposit.
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_single.clone(),
+            &dps::msg::QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.proposer, Addr::unchecked("nonmember"));
+    let deposit = get_deposit_info(&app, pre_propose.clone(), 1);
+    assert_eq!(deposit.proposer, Addr::unchecked("ekez"));
+
+    // Sponsored drafts are removed.
+    let err: PreProposeError = app
         .execute_contract(
             Addr::unchecked("ekez"),
             pre_propose,
             &ExecuteMsg::Extension {
-                msg: Empty::default(),
+                msg: ExecuteExt::Sponsor { draft_id: 1 },
             },
             &[],
         )
+        .unwrap_err()
+        .downcast()
         .unwrap();
+    assert_eq!(err, PreProposeError::ProposalNotFound {});
+
+    // The deposit is refunded to the sponsor.
+    let new_status = vote(&mut app, proposal_single.clone(), "ekez", 1, Vote::No);
+    assert_eq!(new_status, Status::Rejected);
+    close_proposal(&mut app, proposal_single, "ekez", 1);
+    assert_eq!(get_balance_native(&app, "ekez", "ujuno"), Uint128::new(10));
+    assert_eq!(
+        get_balance_native(&app, core_addr.as_str(), "ujuno"),
+        Uint128::zero()
+    );
+}
+
+#[test]
+fn test_withdraw_draft() {
+    let mut app = App::default();
+    let DefaultTestSetup {
+        core_addr: _,
+        proposal_single: _,
+        pre_propose,
+    } = setup_default_test(
+        &mut app, None, false, // no open proposal submission.
+    );
+
+    let submit_draft = |app: &mut App| {
+        app.execute_contract(
+            Addr::unchecked("nonmember"),
+            pre_propose.clone(),
+            &ExecuteMsg::Extension {
+                msg: ExecuteExt::SubmitDraft {
+                    title: "title".to_string(),
+                    description: "description".to_string(),
+                    msgs: vec![],
+                },
+            },
+            &[],
+        )
+    };
 
-    // There should be one event which is the invocation of the contract.
-    assert_eq!(res.events.len(), 1);
-    assert_eq!(res.events[0].ty, "execute".to_string());
-    assert_eq!(res.events[0].attributes.len(), 1);
+    // Drafters may only have so many drafts awaiting sponsorship.
+    for _ in 0..MAX_OPEN_DRAFTS {
+        submit_draft(&mut app).unwrap();
+    }
+    let err: PreProposeError = submit_draft(&mut app).unwrap_err().downcast().unwrap();
     assert_eq!(
-        res.events[0].attributes[0].key,
-        "_contract_address".to_string()
+        err,
+        PreProposeError::TooManyDrafts {
+            max: MAX_OPEN_DRAFTS
+        }
+    );
+
+    app.execute_contract(
+        Addr::unchecked("nonmember"),
+        pre_propose.clone(),
+        &ExecuteMsg::Extension {
+            msg: ExecuteExt::WithdrawDraft { draft_id: 1 },
+        },
+        &[],
     )
+    .unwrap();
+
+    let drafts: Vec<Draft> = app
+        .wrap()
+        .query_wasm_smart(
+            pre_propose.clone(),
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::ListDrafts {
+                    start_after: None,
+                    limit: None,
+                },
+            },
+        )
+        .unwrap();
+    let ids: Vec<u64> = drafts.iter().map(|draft| draft.draft_id).collect();
+    assert_eq!(ids, vec![2, 3, 4, 5]);
+
+    // Withdrawing a draft frees a slot.
+    submit_draft(&mut app).unwrap();
 }
 
 #[test]
@@ -1184,6 +1399,144 @@ fn test_update_config() {
     assert_eq!(err, PreProposeError::NotDao {});
 }
 
+#[test]
+fn test_submission_fee() {
+    let mut app = App::default();
+
+    let DefaultTestSetup {
+        core_addr,
+        proposal_single,
+        pre_propose,
+    } = setup_default_test(
+        &mut app,
+        Some(UncheckedDepositInfo {
+            denom: DepositToken::Token {
+                denom: UncheckedDenom::Native("ujuno".to_string()),
+            },
+            amount: Uint128::new(10),
+            refund_policy: DepositRefundPolicy::Always,
+        }),
+        false,
+    );
+
+    let fee = UncheckedSubmissionFee {
+        denom: UncheckedDenom::Native("ujuno".to_string()),
+        amount: Uint128::new(5),
+        destination: SubmissionFeeDestination::Dao,
+    };
+
+    // Only the DAO may set the fee.
+    let err =
+        update_submission_fee_should_fail(&mut app, pre_propose.clone(), "ekez", Some(fee.clone()));
+    assert_eq!(err, PreProposeError::NotDao {});
+    let err = update_submission_fee_should_fail(
+        &mut app,
+        pre_propose.clone(),
+        core_addr.as_str(),
+        Some(UncheckedSubmissionFee {
+            amount: Uint128::zero(),
+            ..fee.clone()
+        }),
+    );
+    assert_eq!(err, PreProposeError::ZeroSubmissionFee {});
+
+    let set = update_submission_fee(&mut app, pre_propose.clone(), core_addr.as_str(), Some(fee));
+    assert_eq!(
+        set,
+        Some(SubmissionFee {
+            denom: CheckedDenom::Native("ujuno".to_string()),
+            amount: Uint128::new(5),
+            destination: SubmissionFeeDestination::Dao,
+        })
+    );
+
+    // The deposit and fee must be paid together.
+    mint_natives(&mut app, "ekez", coins(15, "ujuno"));
+    let err: PreProposeError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            pre_propose.clone(),
+            &ExecuteMsg::Propose {
+                msg: ProposeMessage::Propose {
+                    title: "title".to_string(),
+                    description: "description".to_string(),
+                    msgs: vec![],
+                },
+            },
+            &coins(10, "ujuno"),
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        PreProposeError::InvalidPayment {
+            expected: coins(15, "ujuno")
+        }
+    );
+
+    let id = make_proposal(
+        &mut app,
+        pre_propose.clone(),
+        proposal_single.clone(),
+        "ekez",
+        &coins(15, "ujuno"),
+    );
+    assert_eq!(
+        get_balance_native(&app, core_addr.as_str(), "ujuno"),
+        Uint128::new(5)
+    );
+
+    // Only the deposit is refunded.
+    vote(&mut app, proposal_single.clone(), "ekez", id, Vote::Yes);
+    execute_proposal(&mut app, proposal_single.clone(), "ekez", id);
+    assert_eq!(get_balance_native(&app, "ekez", "ujuno"), Uint128::new(10));
+    assert_eq!(
+        get_balance_native(&app, core_addr.as_str(), "ujuno"),
+        Uint128::new(5)
+    );
+
+    // Burned fees do not reach the DAO.
+    update_submission_fee(
+        &mut app,
+        pre_propose.clone(),
+        core_addr.as_str(),
+        Some(UncheckedSubmissionFee {
+            denom: UncheckedDenom::Native("ujuno".to_string()),
+            amount: Uint128::new(5),
+            destination: SubmissionFeeDestination::Burn,
+        }),
+    );
+    mint_natives(&mut app, "ekez", coins(5, "ujuno"));
+    make_proposal(
+        &mut app,
+        pre_propose.clone(),
+        proposal_single.clone(),
+        "ekez",
+        &coins(15, "ujuno"),
+    );
+    assert_eq!(
+        get_balance_native(&app, core_addr.as_str(), "ujuno"),
+        Uint128::new(5)
+    );
+    assert_eq!(
+        get_balance_native(&app, pre_propose.as_str(), "ujuno"),
+        Uint128::new(10)
+    );
+
+    // Removing the fee goes back to only requiring the deposit.
+    let set = update_submission_fee(&mut app, pre_propose.clone(), core_addr.as_str(), None);
+    assert_eq!(set, None);
+    mint_natives(&mut app, "ekez", coins(10, "ujuno"));
+    make_proposal(
+        &mut app,
+        pre_propose,
+        proposal_single,
+        "ekez",
+        &coins(10, "ujuno"),
+    );
+}
+
 #[test]
 fn test_withdraw() {
     let mut app = App::default();
@@ -1356,3 +1709,527 @@ fn test_hook_management() {
     let hooks = query_hooks(app, pre_propose).hooks;
     assert_eq!(hooks, vec!["two".to_string()])
 }
+
+#[test]
+fn test_submission_hooks() {
+    let app = &mut App::default();
+    let DefaultTestSetup {
+        core_addr,
+        proposal_single,
+        pre_propose,
+    } = setup_default_test(app, None, true);
+
+    // Only the DAO may add submission hooks.
+    let err: PreProposeError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            pre_propose.clone(),
+            &ExecuteMsg::AddSubmissionHook {
+                address: "bot".to_string(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, PreProposeError::NotDao {});
+
+    app.execute_contract(
+        core_addr,
+        pre_propose.clone(),
+        &ExecuteMsg::AddSubmissionHook {
+            address: "bot".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let hooks: cw_hooks::HooksResponse = app
+        .wrap()
+        .query_wasm_smart(pre_propose.clone(), &QueryMsg::SubmissionHooks {})
+        .unwrap();
+    assert_eq!(hooks.hooks, vec!["bot".to_string()]);
+
+    // "bot" is not a contract, so handling the hook fails. This
+    // should not prevent the proposal from being created, and the
+    // receiver should be removed.
+    make_proposal(app, pre_propose.clone(), proposal_single, "ekez", &[]);
+
+    let hooks: cw_hooks::HooksResponse = app
+        .wrap()
+        .query_wasm_smart(pre_propose, &QueryMsg::SubmissionHooks {})
+        .unwrap();
+    assert!(hooks.hooks.is_empty());
+}
+
+#[test]
+fn test_reputation_deposits() {
+    let app = &mut App::default();
+    let DefaultTestSetup {
+        core_addr,
+        proposal_single,
+        pre_propose,
+    } = setup_default_test(
+        app,
+        Some(UncheckedDepositInfo {
+            denom: DepositToken::Token {
+                denom: UncheckedDenom::Native("ujuno".to_string()),
+            },
+            amount: Uint128::new(10),
+            refund_policy: DepositRefundPolicy::Always,
+        }),
+        false,
+    );
+    mint_natives(app, "ekez", coins(20, "ujuno"));
+
+    let update_curve = |curve: Option<Vec<CurvePoint>>| ExecuteMsg::Extension {
+        msg: ExecuteExt::UpdateReputationCurve { curve },
+    };
+    let reputation_of = |app: &App| -> ReputationResponse {
+        app.wrap()
+            .query_wasm_smart(
+                pre_propose.clone(),
+                &QueryMsg::QueryExtension {
+                    msg: QueryExt::Reputation {
+                        address: "ekez".to_string(),
+                    },
+                },
+            )
+            .unwrap()
+    };
+
+    // Only the DAO may set the curve.
+    let err: PreProposeError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            pre_propose.clone(),
+            &update_curve(None),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, PreProposeError::NotDao {});
+
+    let err: PreProposeError = app
+        .execute_contract(
+            core_addr.clone(),
+            pre_propose.clone(),
+            &update_curve(Some(vec![
+                CurvePoint {
+                    min_score: 1,
+                    multiplier: Decimal::percent(50),
+                },
+                CurvePoint {
+                    min_score: 1,
+                    multiplier: Decimal::zero(),
+                },
+            ])),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, PreProposeError::InvalidReputationCurve {});
+
+    app.execute_contract(
+        core_addr,
+        pre_propose.clone(),
+        &update_curve(Some(vec![
+            CurvePoint {
+                min_score: 1,
+                multiplier: Decimal::percent(50),
+            },
+            CurvePoint {
+                min_score: 2,
+                multiplier: Decimal::zero(),
+            },
+        ])),
+        &[],
+    )
+    .unwrap();
+
+    // Without a track record, the full deposit is due.
+    let reputation = reputation_of(app);
+    assert_eq!(reputation.score, 0);
+    assert_eq!(reputation.multiplier, Decimal::one());
+
+    let id = make_proposal(
+        app,
+        pre_propose.clone(),
+        proposal_single.clone(),
+        "ekez",
+        &coins(10, "ujuno"),
+    );
+    vote(app, proposal_single.clone(), "ekez", id, Vote::Yes);
+    execute_proposal(app, proposal_single.clone(), "ekez", id);
+
+    // An executed proposal halves the deposit.
+    let reputation = reputation_of(app);
+    assert_eq!(
+        reputation.reputation,
+        Reputation {
+            executed: 1,
+            rejected: 0,
+        }
+    );
+    assert_eq!(reputation.score, 1);
+    assert_eq!(reputation.multiplier, Decimal::percent(50));
+    assert_eq!(
+        reputation.deposit_info.map(|d| d.amount),
+        Some(Uint128::new(5))
+    );
+
+    let id = make_proposal(
+        app,
+        pre_propose.clone(),
+        proposal_single.clone(),
+        "ekez",
+        &coins(5, "ujuno"),
+    );
+    let info = get_deposit_info(app, pre_propose.clone(), id);
+    assert_eq!(info.deposit_info.map(|d| d.amount), Some(Uint128::new(5)));
+    vote(app, proposal_single.clone(), "ekez", id, Vote::No);
+    close_proposal(app, proposal_single, "ekez", id);
+
+    // The reduced deposit is refunded, and the rejection restores
+    // the full deposit.
+    assert_eq!(get_balance_native(app, "ekez", "ujuno"), Uint128::new(20));
+    let reputation = reputation_of(app);
+    assert_eq!(
+        reputation.reputation,
+        Reputation {
+            executed: 1,
+            rejected: 1,
+        }
+    );
+    assert_eq!(reputation.score, 0);
+    assert_eq!(
+        reputation.deposit_info.map(|d| d.amount),
+        Some(Uint128::new(10))
+    );
+}
+
+#[test]
+fn test_co_authored_proposal() {
+    let app = &mut App::default();
+    let DefaultTestSetup {
+        core_addr: _,
+        proposal_single,
+        pre_propose,
+    } = setup_default_test(
+        app,
+        Some(UncheckedDepositInfo {
+            denom: DepositToken::Token {
+                denom: UncheckedDenom::Native("ujuno".to_string()),
+            },
+            amount: Uint128::new(10),
+            refund_policy: DepositRefundPolicy::Always,
+        }),
+        false,
+    );
+    mint_natives(app, "ekez", coins(10, "ujuno"));
+    mint_natives(app, "keze", coins(10, "ujuno"));
+    mint_natives(app, "nonmember", coins(10, "ujuno"));
+
+    let propose = |co_authors: Vec<(&str, u128)>| ExecuteMsg::Extension {
+        msg: ExecuteExt::ProposeWithCoAuthors {
+            title: "title".to_string(),
+            description: "description".to_string(),
+            msgs: vec![],
+            co_authors: co_authors
+                .into_iter()
+                .map(|(address, amount)| CoAuthor {
+                    address: address.to_string(),
+                    amount: Uint128::new(amount),
+                })
+                .collect(),
+        },
+    };
+    let extension = |msg: ExecuteExt| ExecuteMsg::Extension { msg };
+
+    // Co-authors may not pay more than the deposit between them.
+    let err: PreProposeError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            pre_propose.clone(),
+            &propose(vec![("keze", 6), ("nonmember", 5)]),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, PreProposeError::InvalidCoAuthors {});
+
+    // The proposer pays what is left after the co-authors' shares.
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        pre_propose.clone(),
+        &propose(vec![("keze", 3), ("nonmember", 3)]),
+        &coins(4, "ujuno"),
+    )
+    .unwrap();
+
+    let err: PreProposeError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            pre_propose.clone(),
+            &extension(ExecuteExt::SubmitCoAuthored { id: 1 }),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, PreProposeError::SharesUnpaid {});
+
+    for co_author in ["keze", "nonmember"] {
+        app.execute_contract(
+            Addr::unchecked(co_author),
+            pre_propose.clone(),
+            &extension(ExecuteExt::FundCoAuthored { id: 1 }),
+            &coins(3, "ujuno"),
+        )
+        .unwrap();
+    }
+    let err: PreProposeError = app
+        .execute_contract(
+            Addr::unchecked("keze"),
+            pre_propose.clone(),
+            &extension(ExecuteExt::FundCoAuthored { id: 1 }),
+            &coins(3, "ujuno"),
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, PreProposeError::NoShareDue {});
+
+    let co_authored: CoAuthoredProposal = app
+        .wrap()
+        .query_wasm_smart(
+            pre_propose.clone(),
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::CoAuthored { id: 1 },
+            },
+        )
+        .unwrap();
+    let deposit = co_authored.deposit;
+    assert_eq!(
+        deposit.shares,
+        vec![
+            DepositShare {
+                address: Addr::unchecked("ekez"),
+                amount: Uint128::new(4),
+                paid: true,
+            },
+            DepositShare {
+                address: Addr::unchecked("keze"),
+                amount: Uint128::new(3),
+                paid: true,
+            },
+            DepositShare {
+                address: Addr::unchecked("nonmember"),
+                amount: Uint128::new(3),
+                paid: true,
+            },
+        ]
+    );
+
+    // Only the proposer may submit.
+    let err: PreProposeError = app
+        .execute_contract(
+            Addr::unchecked("keze"),
+            pre_propose.clone(),
+            &extension(ExecuteExt::SubmitCoAuthored { id: 1 }),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, PreProposeError::Unauthorized {});
+
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        pre_propose.clone(),
+        &extension(ExecuteExt::SubmitCoAuthored { id: 1 }),
+        &[],
+    )
+    .unwrap();
+
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_single.clone(),
+            &dps::msg::QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.proposer, Addr::unchecked("ekez"));
+
+    let shared: Option<SharedDeposit> = app
+        .wrap()
+        .query_wasm_smart(
+            pre_propose.clone(),
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::SharedDeposit { proposal_id: 1 },
+            },
+        )
+        .unwrap();
+    assert_eq!(shared, Some(deposit));
+    assert_eq!(
+        get_balance_native(app, pre_propose.as_str(), "ujuno"),
+        Uint128::new(10)
+    );
+
+    // Each contributor is refunded their share when the proposal
+    // closes.
+    vote(app, proposal_single.clone(), "ekez", 1, Vote::No);
+    close_proposal(app, proposal_single, "ekez", 1);
+    for address in ["ekez", "keze", "nonmember"] {
+        assert_eq!(get_balance_native(app, address, "ujuno"), Uint128::new(10));
+    }
+    assert_eq!(
+        get_balance_native(app, pre_propose.as_str(), "ujuno"),
+        Uint128::zero()
+    );
+
+    // The shared deposit is removed once it is settled.
+    let shared: Option<SharedDeposit> = app
+        .wrap()
+        .query_wasm_smart(
+            pre_propose,
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::SharedDeposit { proposal_id: 1 },
+            },
+        )
+        .unwrap();
+    assert_eq!(shared, None);
+}
+
+#[test]
+fn test_deposit_options() {
+    let mut app = App::default();
+
+    let DefaultTestSetup {
+        core_addr,
+        proposal_single,
+        pre_propose,
+    } = setup_default_test(
+        &mut app,
+        Some(UncheckedDepositInfo {
+            denom: DepositToken::Token {
+                denom: UncheckedDenom::Native("ujuno".to_string()),
+            },
+            amount: Uint128::new(10),
+            refund_policy: DepositRefundPolicy::Always,
+        }),
+        false,
+    );
+
+    let options = vec![UncheckedDepositInfo {
+        denom: DepositToken::Token {
+            denom: UncheckedDenom::Native("uusdc".to_string()),
+        },
+        amount: Uint128::new(5),
+        refund_policy: DepositRefundPolicy::Always,
+    }];
+
+    // Only the DAO may set deposit options.
+    let err: PreProposeError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            pre_propose.clone(),
+            &ExecuteMsg::UpdateDepositOptions {
+                options: options.clone(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, PreProposeError::NotDao {});
+
+    app.execute_contract(
+        core_addr.clone(),
+        pre_propose.clone(),
+        &ExecuteMsg::UpdateDepositOptions { options },
+        &[],
+    )
+    .unwrap();
+    let current: Vec<CheckedDepositInfo> = app
+        .wrap()
+        .query_wasm_smart(&pre_propose, &QueryMsg::DepositOptions {})
+        .unwrap();
+    let usdc_deposit = CheckedDepositInfo {
+        denom: CheckedDenom::Native("uusdc".to_string()),
+        amount: Uint128::new(5),
+        refund_policy: DepositRefundPolicy::Always,
+    };
+    assert_eq!(current, vec![usdc_deposit.clone()]);
+
+    let propose = |option: u32| ExecuteMsg::ProposeWithDepositOption {
+        msg: ProposeMessage::Propose {
+            title: "title".to_string(),
+            description: "description".to_string(),
+            msgs: vec![],
+        },
+        option,
+    };
+
+    let err: PreProposeError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            pre_propose.clone(),
+            &propose(1),
+            &coins(5, "uusdc"),
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, PreProposeError::NoSuchDepositOption { option: 1 });
+
+    // The proposer pays the option in place of the configured
+    // deposit, and is refunded in the same tokens.
+    mint_natives(&mut app, "ekez", coins(5, "uusdc"));
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        pre_propose.clone(),
+        &propose(0),
+        &coins(5, "uusdc"),
+    )
+    .unwrap();
+    assert_eq!(get_balance_native(&app, "ekez", "uusdc"), Uint128::zero());
+    let deposit = get_deposit_info(&app, pre_propose.clone(), 1);
+    assert_eq!(deposit.deposit_info, Some(usdc_deposit));
+
+    let status = vote(&mut app, proposal_single.clone(), "ekez", 1, Vote::No);
+    assert_eq!(status, Status::Rejected);
+    close_proposal(&mut app, proposal_single.clone(), "ekez", 1);
+    assert_eq!(get_balance_native(&app, "ekez", "uusdc"), Uint128::new(5));
+
+    // Removing the options leaves only the configured deposit.
+    app.execute_contract(
+        core_addr,
+        pre_propose.clone(),
+        &ExecuteMsg::UpdateDepositOptions { options: vec![] },
+        &[],
+    )
+    .unwrap();
+    let err: PreProposeError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            pre_propose.clone(),
+            &propose(0),
+            &coins(5, "uusdc"),
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, PreProposeError::NoSuchDepositOption { option: 0 });
+    mint_natives(&mut app, "ekez", coins(10, "ujuno"));
+    make_proposal(
+        &mut app,
+        pre_propose,
+        proposal_single,
+        "ekez",
+        &coins(10, "ujuno"),
+    );
+}
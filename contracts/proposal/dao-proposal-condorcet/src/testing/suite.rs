@@ -126,6 +126,7 @@ impl SuiteBuilder {
                 &dao_interface::msg::QueryMsg::ProposalModules {
                     start_after: None,
                     limit: None,
+                    descending: None,
                 },
             )
             .unwrap();
@@ -1,12 +1,13 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Reply, Response, StdResult,
-    Storage, SubMsg, WasmMsg,
+    to_json_binary, Addr, BankMsg, Binary, BlockInfo, CosmosMsg, Decimal, Deps, DepsMut, Empty,
+    Env, MessageInfo, Reply, Response, StdError, StdResult, Storage, SubMsg, Uint128, WasmMsg,
 };
 
 use cw2::set_contract_version;
 use cw_hooks::Hooks;
+use cw_paginate_storage::{clamp_limit_u64, order, range_bounds};
 use cw_storage_plus::Bound;
 use cw_utils::{parse_reply_instantiate_data, Duration};
 use dao_hooks::proposal::{new_proposal_hooks, proposal_status_changed_hooks};
@@ -15,10 +16,13 @@ use dao_interface::voting::IsActiveResponse;
 use dao_pre_propose_multiple::contract::ExecuteMsg as PreProposeMsg;
 use dao_voting::{
     multiple_choice::{
+        CheckedMultipleChoiceOption, MultipleChoiceOption, MultipleChoiceOptionType,
         MultipleChoiceOptions, MultipleChoiceVote, MultipleChoiceVotes, VotingStrategy,
+        MAX_NUM_CHOICES,
     },
     pre_propose::{PreProposeInfo, ProposalCreationPolicy},
-    proposal::{DEFAULT_LIMIT, MAX_PROPOSAL_SIZE},
+    proposal::MAX_PROPOSAL_SIZE,
+    quorum::{QuorumSource, UncheckedQuorumSource},
     reply::{
         failed_pre_propose_module_hook_id, mask_proposal_execution_proposal_id, TaggedReplyId,
     },
@@ -28,11 +32,16 @@ use dao_voting::{
 
 use crate::{msg::MigrateMsg, state::CREATION_POLICY};
 use crate::{
-    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    msg::{ExecuteMsg, InstantiateMsg, OracleQueryMsg, QueryMsg},
     proposal::{MultipleChoiceProposal, VoteResult},
-    query::{ProposalListResponse, ProposalResponse, VoteInfo, VoteListResponse, VoteResponse},
+    query::{
+        NominationResponse, ProposalListResponse, ProposalPhase, ProposalResponse, VoteInfo,
+        VoteListResponse, VoteResponse,
+    },
     state::{
-        Ballot, Config, BALLOTS, CONFIG, PROPOSALS, PROPOSAL_COUNT, PROPOSAL_HOOKS, VOTE_HOOKS,
+        Ballot, Config, Nomination, NominationConfig, BALLOTS, CONFIG, ELECTION_WINNERS,
+        HIDE_TALLIES, NOMINATIONS, NOMINATION_CONFIG, ORACLE, PROPOSALS, PROPOSAL_COUNT,
+        PROPOSAL_HOOKS, QUORUM_SOURCE, RUNOFFS, VOTE_HOOKS,
     },
     ContractError,
 };
@@ -50,6 +59,9 @@ pub fn instantiate(
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     msg.voting_strategy.validate()?;
+    if let VotingStrategy::Election { seats: 0, .. } = msg.voting_strategy {
+        return Err(ContractError::ZeroSeats {});
+    }
 
     let dao = info.sender;
 
@@ -133,6 +145,20 @@ pub fn execute(
         ExecuteMsg::UpdatePreProposeInfo { info: new_info } => {
             execute_update_proposal_creation_policy(deps, info, new_info)
         }
+        ExecuteMsg::UpdateTallyPrivacy { hide_tallies } => {
+            execute_update_tally_privacy(deps, info, hide_tallies)
+        }
+        ExecuteMsg::UpdateOracle { oracle } => execute_update_oracle(deps, info, oracle),
+        ExecuteMsg::UpdateQuorumSource { quorum_source } => {
+            execute_update_quorum_source(deps, info, quorum_source)
+        }
+        ExecuteMsg::UpdateNominationConfig { nomination } => {
+            execute_update_nomination_config(deps, info, nomination)
+        }
+        ExecuteMsg::Nominate {
+            proposal_id,
+            option,
+        } => execute_nominate(deps, env, info, proposal_id, option),
         ExecuteMsg::AddProposalHook { address } => {
             execute_add_proposal_hook(deps, env, info, address)
         }
@@ -197,10 +223,54 @@ pub fn execute_propose(
     }
 
     // Validate options.
-    let checked_multiple_choice_options = options.into_checked()?.options;
+    let checked_multiple_choice_options = options.into_checked(deps.api)?.options;
+    if checked_multiple_choice_options
+        .iter()
+        .any(|choice| !choice.oracle_amounts.is_empty())
+        && ORACLE.may_load(deps.storage)?.is_none()
+    {
+        return Err(ContractError::NoOracle {});
+    }
+    if let VotingStrategy::Election { seats, .. } = config.voting_strategy {
+        let candidates = checked_multiple_choice_options
+            .iter()
+            .filter(|choice| choice.option_type == MultipleChoiceOptionType::Standard)
+            .count() as u32;
+        if seats > candidates {
+            return Err(ContractError::TooManySeats { seats, candidates });
+        }
+    }
 
-    let expiration = config.max_voting_period.after(&env.block);
+    let nomination = NOMINATION_CONFIG
+        .may_load(deps.storage)?
+        .map(|nomination| Nomination {
+            ends: nomination.period.after(&env.block),
+            min_power: nomination.min_power,
+            max_nominations: nomination.max_nominations,
+            deposit: nomination.deposit,
+            nominators: vec![],
+        });
+    // Proposals with a nomination phase open for voting once it ends.
+    let (min_voting_period, expiration) = match &nomination {
+        Some(nomination) => (
+            config
+                .min_voting_period
+                .map(|min| nomination.ends + min)
+                .transpose()?,
+            (nomination.ends + config.max_voting_period)?,
+        ),
+        None => (
+            config.min_voting_period.map(|min| min.after(&env.block)),
+            config.max_voting_period.after(&env.block),
+        ),
+    };
     let total_power = get_total_power(deps.as_ref(), &config.dao, None)?;
+    let quorum_power = match QUORUM_SOURCE.may_load(deps.storage)? {
+        Some(quorum_source) => {
+            quorum_source.quorum_power(&deps.querier, total_power, env.block.height)?
+        }
+        None => None,
+    };
 
     let proposal = {
         // Limit mutability to this block.
@@ -209,10 +279,11 @@ pub fn execute_propose(
             description,
             proposer: proposer.clone(),
             start_height: env.block.height,
-            min_voting_period: config.min_voting_period.map(|min| min.after(&env.block)),
+            min_voting_period,
             expiration,
             voting_strategy: config.voting_strategy,
             total_power,
+            quorum_power,
             status: Status::Open,
             votes: MultipleChoiceVotes::zero(checked_multiple_choice_options.len()),
             allow_revoting: config.allow_revoting,
@@ -248,6 +319,9 @@ pub fn execute_propose(
     }
 
     PROPOSALS.save(deps.storage, id, &proposal)?;
+    if let Some(nomination) = nomination {
+        NOMINATIONS.save(deps.storage, id, &nomination)?;
+    }
 
     let hooks = new_proposal_hooks(PROPOSAL_HOOKS, deps.storage, id, proposer.as_str())?;
 
@@ -287,6 +361,11 @@ pub fn execute_vote(
     if prop.expiration.is_expired(&env.block) {
         return Err(ContractError::Expired { id: proposal_id });
     }
+    if let Some(nomination) = NOMINATIONS.may_load(deps.storage, proposal_id)? {
+        if !nomination.ends.is_expired(&env.block) {
+            return Err(ContractError::Nominating { id: proposal_id });
+        }
+    }
 
     let vote_power = get_voting_power(
         deps.as_ref(),
@@ -393,74 +472,126 @@ pub fn execute_execute(
 
     PROPOSALS.save(deps.storage, proposal_id, &prop)?;
 
-    let vote_result = prop.calculate_vote_result()?;
-    match vote_result {
-        VoteResult::Tie => Err(ContractError::Tie {}), // We don't anticipate this case as the proposal would not be in passed state, checked above.
-        VoteResult::SingleWinner(winning_choice) => {
-            let response = if !winning_choice.msgs.is_empty() {
-                let execute_message = WasmMsg::Execute {
-                    contract_addr: config.dao.to_string(),
-                    msg: to_json_binary(&dao_interface::msg::ExecuteMsg::ExecuteProposalHook {
-                        msgs: winning_choice.msgs,
-                    })?,
-                    funds: vec![],
-                };
-                match config.close_proposal_on_execution_failure {
-                    true => {
-                        let masked_proposal_id = mask_proposal_execution_proposal_id(proposal_id);
-                        Response::default().add_submessage(SubMsg::reply_on_error(
-                            execute_message,
-                            masked_proposal_id,
-                        ))
-                    }
-                    false => Response::default().add_message(execute_message),
-                }
-            } else {
-                Response::default()
-            };
-
-            let hooks = proposal_status_changed_hooks(
-                PROPOSAL_HOOKS,
+    // Election proposals execute the messages of each of their
+    // winners, in order of votes.
+    let winning_choices = match prop.election_winners(&env.block) {
+        Some(winners) => {
+            let winners: Vec<CheckedMultipleChoiceOption> = winners.into_iter().cloned().collect();
+            ELECTION_WINNERS.save(
                 deps.storage,
                 proposal_id,
-                old_status.to_string(),
-                prop.status.to_string(),
+                &winners.iter().map(|choice| choice.index).collect(),
             )?;
+            winners
+        }
+        None => match prop.calculate_vote_result()? {
+            VoteResult::Tie => return Err(ContractError::Tie {}), // We don't anticipate this case as the proposal would not be in passed state, checked above.
+            VoteResult::SingleWinner(winning_choice) => vec![winning_choice],
+        },
+    };
 
-            // Add prepropose / deposit module hook which will handle deposit refunds.
-            let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
-            let hooks = match proposal_creation_policy {
-                ProposalCreationPolicy::Anyone {} => hooks,
-                ProposalCreationPolicy::Module { addr } => {
-                    let msg = to_json_binary(&PreProposeMsg::ProposalCompletedHook {
-                        proposal_id,
-                        new_status: prop.status,
-                    })?;
-                    let mut hooks = hooks;
-                    hooks.push(SubMsg::reply_on_error(
-                        WasmMsg::Execute {
-                            contract_addr: addr.into_string(),
-                            msg,
-                            funds: vec![],
-                        },
-                        failed_pre_propose_module_hook_id(),
-                    ));
-                    hooks
-                }
-            };
+    let mut msgs = vec![];
+    for choice in winning_choices {
+        msgs.extend(resolve_oracle_amounts(deps.as_ref(), choice)?);
+    }
+    let response = if !msgs.is_empty() {
+        let execute_message = WasmMsg::Execute {
+            contract_addr: config.dao.to_string(),
+            msg: to_json_binary(&dao_interface::msg::ExecuteMsg::ExecuteProposalHook { msgs })?,
+            funds: vec![],
+        };
+        match config.close_proposal_on_execution_failure {
+            true => {
+                let masked_proposal_id = mask_proposal_execution_proposal_id(proposal_id);
+                Response::default()
+                    .add_submessage(SubMsg::reply_on_error(execute_message, masked_proposal_id))
+            }
+            false => Response::default().add_message(execute_message),
+        }
+    } else {
+        Response::default()
+    };
+
+    let hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        proposal_id,
+        old_status.to_string(),
+        prop.status.to_string(),
+    )?;
+
+    // Add prepropose / deposit module hook which will handle deposit refunds.
+    let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
+    let hooks = match proposal_creation_policy {
+        ProposalCreationPolicy::Anyone {} => hooks,
+        ProposalCreationPolicy::Module { addr } => {
+            let msg = to_json_binary(&PreProposeMsg::ProposalCompletedHook {
+                proposal_id,
+                new_status: prop.status,
+            })?;
+            let mut hooks = hooks;
+            hooks.push(SubMsg::reply_on_error(
+                WasmMsg::Execute {
+                    contract_addr: addr.into_string(),
+                    msg,
+                    funds: vec![],
+                },
+                failed_pre_propose_module_hook_id(),
+            ));
+            hooks
+        }
+    };
 
-            Ok(response
-                .add_submessages(hooks)
-                .add_attribute("action", "execute")
-                .add_attribute("sender", info.sender)
-                .add_attribute("proposal_id", proposal_id.to_string())
-                .add_attribute("dao", config.dao))
+    Ok(response
+        .add_messages(nomination_refunds(deps.storage, proposal_id)?)
+        .add_submessages(hooks)
+        .add_attribute("action", "execute")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("dao", config.dao))
+}
+
+/// Returns CHOICE's messages with their oracle amounts resolved,
+/// erroring if any resolves outside of its bounds.
+fn resolve_oracle_amounts(
+    deps: Deps,
+    choice: CheckedMultipleChoiceOption,
+) -> Result<Vec<CosmosMsg<Empty>>, ContractError> {
+    let mut msgs = choice.msgs;
+    if choice.oracle_amounts.is_empty() {
+        return Ok(msgs);
+    }
+    let oracle = ORACLE
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoOracle {})?;
+    for oracle_amount in choice.oracle_amounts {
+        let rate: Decimal = deps.querier.query_wasm_smart(
+            &oracle,
+            &OracleQueryMsg::Rate {
+                key: oracle_amount.key,
+            },
+        )?;
+        let amount = oracle_amount.base_amount * rate;
+        if amount < oracle_amount.min || amount > oracle_amount.max {
+            return Err(ContractError::OracleAmountOutOfBounds {
+                amount,
+                min: oracle_amount.min,
+                max: oracle_amount.max,
+            });
+        }
+        // Options are validated on creation so that this is a bank
+        // send of a single coin.
+        if let Some(CosmosMsg::Bank(BankMsg::Send { amount: coins, .. })) =
+            msgs.get_mut(oracle_amount.msg_index as usize)
+        {
+            coins[0].amount = amount;
         }
     }
+    Ok(msgs)
 }
 
 pub fn execute_close(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     proposal_id: u64,
@@ -478,6 +609,11 @@ pub fn execute_close(
 
     PROPOSALS.save(deps.storage, proposal_id, &prop)?;
 
+    let runoff = prop
+        .runoff_choices(&env.block)
+        .map(|choices| open_runoff(deps.branch(), &env, proposal_id, &prop, choices))
+        .transpose()?;
+
     let hooks = proposal_status_changed_hooks(
         PROPOSAL_HOOKS,
         deps.storage,
@@ -507,11 +643,80 @@ pub fn execute_close(
             hooks
         }
     };
-    Ok(Response::default()
+    let mut response = Response::default()
+        .add_messages(nomination_refunds(deps.storage, proposal_id)?)
         .add_submessages(hooks)
         .add_attribute("action", "close")
         .add_attribute("sender", info.sender)
-        .add_attribute("proposal_id", proposal_id.to_string()))
+        .add_attribute("proposal_id", proposal_id.to_string());
+    if let Some((runoff_id, runoff_hooks)) = runoff {
+        response = response
+            .add_submessages(runoff_hooks)
+            .add_attribute("runoff_proposal_id", runoff_id.to_string());
+    }
+    Ok(response)
+}
+
+/// Returns messages refunding the nomination deposits taken for
+/// PROPOSAL_ID, if any.
+fn nomination_refunds(storage: &dyn Storage, proposal_id: u64) -> StdResult<Vec<BankMsg>> {
+    Ok(match NOMINATIONS.may_load(storage, proposal_id)? {
+        Some(Nomination {
+            deposit: Some(deposit),
+            nominators,
+            ..
+        }) => nominators
+            .into_iter()
+            .map(|nominator| BankMsg::Send {
+                to_address: nominator.into_string(),
+                amount: vec![deposit.clone()],
+            })
+            .collect(),
+        _ => vec![],
+    })
+}
+
+/// Opens a runoff between CHOICES for the top-two runoff proposal
+/// PROPOSAL_ID. The runoff uses the proposal's voting power snapshot
+/// and is decided by a simple majority. Returns the runoff's ID and
+/// its new proposal hooks.
+fn open_runoff(
+    deps: DepsMut,
+    env: &Env,
+    proposal_id: u64,
+    prop: &MultipleChoiceProposal,
+    choices: Vec<CheckedMultipleChoiceOption>,
+) -> Result<(u64, Vec<SubMsg>), ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut runoff = MultipleChoiceProposal {
+        title: prop.title.clone(),
+        description: prop.description.clone(),
+        proposer: prop.proposer.clone(),
+        start_height: prop.start_height,
+        min_voting_period: config.min_voting_period.map(|min| min.after(&env.block)),
+        expiration: config.max_voting_period.after(&env.block),
+        voting_strategy: VotingStrategy::SingleChoice {
+            quorum: prop.voting_strategy.get_quorum(),
+        },
+        total_power: prop.total_power,
+        quorum_power: prop.quorum_power,
+        status: Status::Open,
+        votes: MultipleChoiceVotes::zero(choices.len()),
+        allow_revoting: config.allow_revoting,
+        choices,
+    };
+    runoff.update_status(&env.block)?;
+    let runoff_id = advance_proposal_id(deps.storage)?;
+    PROPOSALS.save(deps.storage, runoff_id, &runoff)?;
+    RUNOFFS.save(deps.storage, proposal_id, &runoff_id)?;
+
+    let hooks = new_proposal_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        runoff_id,
+        runoff.proposer.as_str(),
+    )?;
+    Ok((runoff_id, hooks))
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -534,6 +739,9 @@ pub fn execute_update_config(
     }
 
     voting_strategy.validate()?;
+    if let VotingStrategy::Election { seats: 0, .. } = voting_strategy {
+        return Err(ContractError::ZeroSeats {});
+    }
 
     let dao = deps.api.addr_validate(&dao)?;
 
@@ -650,6 +858,176 @@ pub fn execute_remove_proposal_hook(
         .add_attribute("address", address))
 }
 
+pub fn execute_update_tally_privacy(
+    deps: DepsMut,
+    info: MessageInfo,
+    hide_tallies: bool,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    HIDE_TALLIES.save(deps.storage, &hide_tallies)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "update_tally_privacy")
+        .add_attribute("hide_tallies", hide_tallies.to_string()))
+}
+
+pub fn execute_update_oracle(
+    deps: DepsMut,
+    info: MessageInfo,
+    oracle: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let oracle = oracle
+        .map(|oracle| deps.api.addr_validate(&oracle))
+        .transpose()?;
+    match oracle {
+        Some(ref oracle) => ORACLE.save(deps.storage, oracle)?,
+        None => ORACLE.remove(deps.storage),
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "update_oracle")
+        .add_attribute(
+            "oracle",
+            oracle
+                .map(|o| o.into_string())
+                .unwrap_or_else(|| "None".to_string()),
+        ))
+}
+
+pub fn execute_update_quorum_source(
+    deps: DepsMut,
+    info: MessageInfo,
+    quorum_source: UncheckedQuorumSource,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let quorum_source = quorum_source.into_checked(deps.api)?;
+    QUORUM_SOURCE.save(deps.storage, &quorum_source)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "update_quorum_source")
+        .add_attribute("sender", info.sender)
+        .add_attribute("quorum_source", format!("{quorum_source:?}")))
+}
+
+pub fn execute_update_nomination_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    nomination: Option<NominationConfig>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match nomination {
+        Some(nomination) => {
+            if matches!(nomination.period, Duration::Height(0) | Duration::Time(0))
+                || nomination.max_nominations == 0
+                || nomination
+                    .deposit
+                    .as_ref()
+                    .map_or(false, |deposit| deposit.amount.is_zero())
+            {
+                return Err(ContractError::InvalidNominationConfig {});
+            }
+            NOMINATION_CONFIG.save(deps.storage, &nomination)?;
+            Ok(Response::default()
+                .add_attribute("action", "update_nomination_config")
+                .add_attribute("period", format!("{:?}", nomination.period))
+                .add_attribute("max_nominations", nomination.max_nominations.to_string()))
+        }
+        None => {
+            NOMINATION_CONFIG.remove(deps.storage);
+            Ok(Response::default()
+                .add_attribute("action", "update_nomination_config")
+                .add_attribute("period", "None"))
+        }
+    }
+}
+
+pub fn execute_nominate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    option: MultipleChoiceOption,
+) -> Result<Response, ContractError> {
+    let mut nomination = NOMINATIONS
+        .may_load(deps.storage, proposal_id)?
+        .filter(|nomination| !nomination.ends.is_expired(&env.block))
+        .ok_or(ContractError::NotNominating { id: proposal_id })?;
+    let mut prop = PROPOSALS.load(deps.storage, proposal_id)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let power = get_voting_power(
+        deps.as_ref(),
+        info.sender.clone(),
+        &config.dao,
+        Some(prop.start_height),
+    )?;
+    if power.is_zero() || power < nomination.min_power {
+        return Err(ContractError::InsufficientNominationPower {
+            min: nomination.min_power,
+        });
+    }
+    if info.funds != nomination.deposit.iter().cloned().collect::<Vec<_>>() {
+        return Err(ContractError::WrongNominationDeposit {});
+    }
+    // The last choice is "None of the above", which does not count
+    // towards the maximum number of choices.
+    if nomination.nominators.len() >= nomination.max_nominations as usize
+        || prop.choices.len() > MAX_NUM_CHOICES as usize
+    {
+        return Err(ContractError::TooManyNominations {});
+    }
+    if !option.oracle_amounts.is_empty() && ORACLE.may_load(deps.storage)?.is_none() {
+        return Err(ContractError::NoOracle {});
+    }
+
+    // Insert the option before "None of the above". No votes may be
+    // cast until nominations close, so every choice has zero votes.
+    let mut none_option = prop
+        .choices
+        .pop()
+        .expect("proposals always have a none of the above choice");
+    let index = prop.choices.len() as u32;
+    prop.choices.push(option.into_checked(deps.api, index)?);
+    none_option.index = index + 1;
+    prop.choices.push(none_option);
+    prop.votes.vote_weights.push(Uint128::zero());
+
+    let proposal_size = cosmwasm_std::to_vec(&prop)?.len() as u64;
+    if proposal_size > MAX_PROPOSAL_SIZE {
+        return Err(ContractError::ProposalTooLarge {
+            size: proposal_size,
+            max: MAX_PROPOSAL_SIZE,
+        });
+    }
+
+    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+    nomination.nominators.push(info.sender.clone());
+    NOMINATIONS.save(deps.storage, proposal_id, &nomination)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "nominate")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("option_index", index.to_string()))
+}
+
 pub fn execute_add_vote_hook(
     deps: DepsMut,
     _env: Env,
@@ -729,17 +1107,20 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => query_config(deps),
         QueryMsg::Proposal { proposal_id } => query_proposal(deps, env, proposal_id),
-        QueryMsg::ListProposals { start_after, limit } => {
-            query_list_proposals(deps, env, start_after, limit)
-        }
+        QueryMsg::ListProposals {
+            start_after,
+            limit,
+            descending,
+        } => query_list_proposals(deps, env, start_after, limit, descending),
         QueryMsg::NextProposalId {} => query_next_proposal_id(deps),
         QueryMsg::ProposalCount {} => query_proposal_count(deps),
-        QueryMsg::GetVote { proposal_id, voter } => query_vote(deps, proposal_id, voter),
+        QueryMsg::GetVote { proposal_id, voter } => query_vote(deps, env, proposal_id, voter),
         QueryMsg::ListVotes {
             proposal_id,
             start_after,
             limit,
-        } => query_list_votes(deps, proposal_id, start_after, limit),
+            descending,
+        } => query_list_votes(deps, env, proposal_id, start_after, limit, descending),
         QueryMsg::Info {} => query_info(deps),
         QueryMsg::ReverseProposals {
             start_before,
@@ -749,7 +1130,47 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::ProposalHooks {} => to_json_binary(&PROPOSAL_HOOKS.query_hooks(deps)?),
         QueryMsg::VoteHooks {} => to_json_binary(&VOTE_HOOKS.query_hooks(deps)?),
         QueryMsg::Dao {} => query_dao(deps),
+        QueryMsg::TallyPrivacy {} => {
+            to_json_binary(&HIDE_TALLIES.may_load(deps.storage)?.unwrap_or_default())
+        }
+        QueryMsg::Oracle {} => to_json_binary(&ORACLE.may_load(deps.storage)?),
+        QueryMsg::QuorumSource {} => to_json_binary(
+            &QUORUM_SOURCE
+                .may_load(deps.storage)?
+                .unwrap_or(QuorumSource::TotalPower {}),
+        ),
+        QueryMsg::Runoff { proposal_id } => {
+            to_json_binary(&RUNOFFS.may_load(deps.storage, proposal_id)?)
+        }
+        QueryMsg::ElectionWinners { proposal_id } => query_election_winners(deps, env, proposal_id),
+        QueryMsg::NominationConfig {} => to_json_binary(&NOMINATION_CONFIG.may_load(deps.storage)?),
+        QueryMsg::Nomination { proposal_id } => query_nomination(deps, env, proposal_id),
+    }
+}
+
+pub fn query_nomination(deps: Deps, env: Env, proposal_id: u64) -> StdResult<Binary> {
+    let nomination = NOMINATIONS
+        .may_load(deps.storage, proposal_id)?
+        .map(|nomination| NominationResponse {
+            phase: if nomination.ends.is_expired(&env.block) {
+                ProposalPhase::Voting
+            } else {
+                ProposalPhase::Nomination
+            },
+            nomination,
+        });
+    to_json_binary(&nomination)
+}
+
+pub fn query_election_winners(deps: Deps, env: Env, proposal_id: u64) -> StdResult<Binary> {
+    if let Some(winners) = ELECTION_WINNERS.may_load(deps.storage, proposal_id)? {
+        return to_json_binary(&Some(winners));
     }
+    let proposal = PROPOSALS.load(deps.storage, proposal_id)?;
+    let winners: Option<Vec<u32>> = proposal
+        .election_winners(&env.block)
+        .map(|winners| winners.into_iter().map(|choice| choice.index).collect());
+    to_json_binary(&winners)
 }
 
 pub fn query_config(deps: Deps) -> StdResult<Binary> {
@@ -764,7 +1185,40 @@ pub fn query_dao(deps: Deps) -> StdResult<Binary> {
 
 pub fn query_proposal(deps: Deps, env: Env, id: u64) -> StdResult<Binary> {
     let proposal = PROPOSALS.load(deps.storage, id)?;
-    to_json_binary(&proposal.into_response(&env.block, id)?)
+    let hide_tallies = HIDE_TALLIES.may_load(deps.storage)?.unwrap_or_default();
+    to_json_binary(&proposal_response(proposal, &env.block, id, hide_tallies)?)
+}
+
+/// Converts a proposal into a response. If `hide_tallies` is set and
+/// the proposal is open, its vote weights are reported as zero.
+fn proposal_response(
+    proposal: MultipleChoiceProposal,
+    block: &BlockInfo,
+    id: u64,
+    hide_tallies: bool,
+) -> StdResult<ProposalResponse> {
+    let mut response = proposal.into_response(block, id)?;
+    if hide_tallies && response.proposal.status == Status::Open {
+        for weight in response.proposal.votes.vote_weights.iter_mut() {
+            *weight = Uint128::zero();
+        }
+    }
+    Ok(response)
+}
+
+/// Errors if votes on the proposal are hidden from queries.
+fn assert_votes_visible(deps: Deps, block: &BlockInfo, proposal_id: u64) -> StdResult<()> {
+    if !HIDE_TALLIES.may_load(deps.storage)?.unwrap_or_default() {
+        return Ok(());
+    }
+    let proposal = PROPOSALS.load(deps.storage, proposal_id)?;
+    if proposal.current_status(block)? == Status::Open {
+        Err(StdError::generic_err(
+            "votes are hidden until the proposal closes",
+        ))
+    } else {
+        Ok(())
+    }
 }
 
 pub fn query_creation_policy(deps: Deps) -> StdResult<Binary> {
@@ -777,15 +1231,17 @@ pub fn query_list_proposals(
     env: Env,
     start_after: Option<u64>,
     limit: Option<u64>,
+    descending: Option<bool>,
 ) -> StdResult<Binary> {
-    let min = start_after.map(Bound::exclusive);
-    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let order = order(descending);
+    let (min, max) = range_bounds(start_after, order);
+    let hide_tallies = HIDE_TALLIES.may_load(deps.storage)?.unwrap_or_default();
     let props: Vec<ProposalResponse> = PROPOSALS
-        .range(deps.storage, min, None, cosmwasm_std::Order::Ascending)
-        .take(limit as usize)
+        .range(deps.storage, min, max, order)
+        .take(clamp_limit_u64(limit))
         .collect::<Result<Vec<(u64, MultipleChoiceProposal)>, _>>()?
         .into_iter()
-        .map(|(id, proposal)| proposal.into_response(&env.block, id))
+        .map(|(id, proposal)| proposal_response(proposal, &env.block, id, hide_tallies))
         .collect::<StdResult<Vec<ProposalResponse>>>()?;
 
     to_json_binary(&ProposalListResponse { proposals: props })
@@ -797,14 +1253,14 @@ pub fn query_reverse_proposals(
     start_before: Option<u64>,
     limit: Option<u64>,
 ) -> StdResult<Binary> {
-    let limit = limit.unwrap_or(DEFAULT_LIMIT);
     let max = start_before.map(Bound::exclusive);
+    let hide_tallies = HIDE_TALLIES.may_load(deps.storage)?.unwrap_or_default();
     let props: Vec<ProposalResponse> = PROPOSALS
         .range(deps.storage, None, max, cosmwasm_std::Order::Descending)
-        .take(limit as usize)
+        .take(clamp_limit_u64(limit))
         .collect::<Result<Vec<(u64, MultipleChoiceProposal)>, _>>()?
         .into_iter()
-        .map(|(id, proposal)| proposal.into_response(&env.block, id))
+        .map(|(id, proposal)| proposal_response(proposal, &env.block, id, hide_tallies))
         .collect::<StdResult<Vec<ProposalResponse>>>()?;
 
     to_json_binary(&ProposalListResponse { proposals: props })
@@ -819,7 +1275,8 @@ pub fn query_proposal_count(deps: Deps) -> StdResult<Binary> {
     to_json_binary(&proposal_count)
 }
 
-pub fn query_vote(deps: Deps, proposal_id: u64, voter: String) -> StdResult<Binary> {
+pub fn query_vote(deps: Deps, env: Env, proposal_id: u64, voter: String) -> StdResult<Binary> {
+    assert_votes_visible(deps, &env.block, proposal_id)?;
     let voter = deps.api.addr_validate(&voter)?;
     let ballot = BALLOTS.may_load(deps.storage, (proposal_id, &voter))?;
     let vote = ballot.map(|ballot| VoteInfo {
@@ -833,20 +1290,23 @@ pub fn query_vote(deps: Deps, proposal_id: u64, voter: String) -> StdResult<Bina
 
 pub fn query_list_votes(
     deps: Deps,
+    env: Env,
     proposal_id: u64,
     start_after: Option<String>,
     limit: Option<u64>,
+    descending: Option<bool>,
 ) -> StdResult<Binary> {
-    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    assert_votes_visible(deps, &env.block, proposal_id)?;
     let start_after = start_after
         .map(|addr| deps.api.addr_validate(&addr))
         .transpose()?;
-    let min = start_after.as_ref().map(Bound::<&Addr>::exclusive);
+    let order = order(descending);
+    let (min, max) = range_bounds(start_after.as_ref(), order);
 
     let votes = BALLOTS
         .prefix(proposal_id)
-        .range(deps.storage, min, None, cosmwasm_std::Order::Ascending)
-        .take(limit as usize)
+        .range(deps.storage, min, max, order)
+        .take(clamp_limit_u64(limit))
         .map(|item| {
             let (voter, ballot) = item?;
             Ok(VoteInfo {
@@ -891,6 +1351,13 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
             let addr = VOTE_HOOKS.remove_hook_by_index(deps.storage, idx)?;
             Ok(Response::new().add_attribute("removed vote hook", format!("{addr}:{idx}")))
         }
+        // This module does not fire proposal outcome or subscription
+        // hooks, or hooks to replaced pre-propose modules.
+        TaggedReplyId::FailedOutcomeHook(_)
+        | TaggedReplyId::FailedSubscriptionHook(_)
+        | TaggedReplyId::FailedPreviousPreProposeModuleHook(_) => {
+            Err(ContractError::InvalidReplyID { id: msg.id })
+        }
         TaggedReplyId::PreProposeModuleInstantiation => {
             let res = parse_reply_instantiate_data(msg)?;
             let module = deps.api.addr_validate(&res.contract_address)?;
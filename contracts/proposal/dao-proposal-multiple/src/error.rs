@@ -1,6 +1,6 @@
 use std::u64;
 
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Uint128};
 use cw_hooks::HookError;
 use cw_utils::ParseReplyError;
 use dao_voting::{reply::error::TagError, threshold::ThresholdError};
@@ -98,4 +98,38 @@ pub enum ContractError {
 
     #[error("received a reply failure with an invalid ID: ({id})")]
     InvalidReplyID { id: u64 },
+
+    #[error("No oracle is configured to resolve oracle amounts.")]
+    NoOracle {},
+
+    #[error("Elections must have at least one seat.")]
+    ZeroSeats {},
+
+    #[error("Election has more seats ({seats}) than candidates ({candidates}).")]
+    TooManySeats { seats: u32, candidates: u32 },
+
+    #[error("Nomination period and max nominations must be non-zero, as must any deposit.")]
+    InvalidNominationConfig {},
+
+    #[error("Proposal ({id}) is not accepting nominations.")]
+    NotNominating { id: u64 },
+
+    #[error("Proposal ({id}) is accepting nominations and may not be voted on yet.")]
+    Nominating { id: u64 },
+
+    #[error("Must have at least ({min}) voting power to nominate an option.")]
+    InsufficientNominationPower { min: Uint128 },
+
+    #[error("No more options may be nominated to this proposal.")]
+    TooManyNominations {},
+
+    #[error("Nominating an option requires sending exactly its deposit.")]
+    WrongNominationDeposit {},
+
+    #[error("Oracle amount ({amount}) is outside of its bounds ({min} to {max}).")]
+    OracleAmountOutOfBounds {
+        amount: Uint128,
+        min: Uint128,
+        max: Uint128,
+    },
 }
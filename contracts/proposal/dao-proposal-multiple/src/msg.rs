@@ -2,10 +2,15 @@ use cosmwasm_schema::{cw_serde, QueryResponses};
 use cw_utils::Duration;
 use dao_dao_macros::proposal_module_query;
 use dao_voting::{
-    multiple_choice::{MultipleChoiceOptions, MultipleChoiceVote, VotingStrategy},
+    multiple_choice::{
+        MultipleChoiceOption, MultipleChoiceOptions, MultipleChoiceVote, VotingStrategy,
+    },
     pre_propose::PreProposeInfo,
+    quorum::UncheckedQuorumSource,
 };
 
+use crate::state::NominationConfig;
+
 #[cw_serde]
 pub struct InstantiateMsg {
     /// Voting params configuration
@@ -128,6 +133,37 @@ pub enum ExecuteMsg {
     UpdatePreProposeInfo {
         info: PreProposeInfo,
     },
+    /// Sets whether the tallies and votes of open proposals are
+    /// hidden from queries until the proposal closes. Only the DAO
+    /// may call this method.
+    UpdateTallyPrivacy {
+        hide_tallies: bool,
+    },
+    /// Sets or removes the oracle queried to resolve the oracle
+    /// amounts of options when they are executed. Only the DAO may
+    /// call this method.
+    UpdateOracle {
+        oracle: Option<String>,
+    },
+    /// Updates the voting power that quorum is computed against. This
+    /// applies to proposals created after the update. Only the DAO
+    /// may call this method.
+    UpdateQuorumSource {
+        quorum_source: UncheckedQuorumSource,
+    },
+    /// Sets or removes the nomination phase that proposals open
+    /// with. This applies to proposals created after the
+    /// update. Only the DAO may call this method.
+    UpdateNominationConfig {
+        nomination: Option<NominationConfig>,
+    },
+    /// Adds an option to a proposal in its nomination phase. The
+    /// sender must have the configured voting power and send the
+    /// configured deposit, if any.
+    Nominate {
+        proposal_id: u64,
+        option: MultipleChoiceOption,
+    },
     AddProposalHook {
         address: String,
     },
@@ -157,6 +193,8 @@ pub enum QueryMsg {
     ListProposals {
         start_after: Option<u64>,
         limit: Option<u64>,
+        /// Lists proposals in descending order of proposal ID if set.
+        descending: Option<bool>,
     },
     /// Lists all of the proposals that have been cast in this module
     /// in decending order of proposal ID.
@@ -174,6 +212,8 @@ pub enum QueryMsg {
         proposal_id: u64,
         start_after: Option<String>,
         limit: Option<u64>,
+        /// Lists votes in reverse alphabetical order if set.
+        descending: Option<bool>,
     },
     /// Returns the number of proposals that have been created in this module.
     #[returns(::std::primitive::u64)]
@@ -187,6 +227,42 @@ pub enum QueryMsg {
     /// Lists all of the consumers of vote hooks for this module.
     #[returns(::cw_hooks::HooksResponse)]
     VoteHooks {},
+    /// Returns true if the tallies and votes of open proposals are
+    /// hidden from queries.
+    #[returns(bool)]
+    TallyPrivacy {},
+    /// Returns the ID of the runoff opened when a top-two runoff
+    /// proposal was closed, if any.
+    #[returns(Option<u64>)]
+    Runoff { proposal_id: u64 },
+    /// Returns the indexes of the choices elected by an election
+    /// proposal, in order of votes, or `None` if it has not elected
+    /// any.
+    #[returns(Option<Vec<u32>>)]
+    ElectionWinners { proposal_id: u64 },
+    /// Returns the oracle used to resolve oracle amounts, if any.
+    #[returns(Option<::cosmwasm_std::Addr>)]
+    Oracle {},
+    /// Gets the voting power that quorum is computed against for new
+    /// proposals.
+    #[returns(::dao_voting::quorum::QuorumSource)]
+    QuorumSource {},
+    /// Gets the nomination phase new proposals open with, if any.
+    #[returns(Option<crate::state::NominationConfig>)]
+    NominationConfig {},
+    /// Gets a proposal's nomination phase and whether it has ended,
+    /// or `None` if the proposal did not open with one.
+    #[returns(Option<crate::query::NominationResponse>)]
+    Nomination { proposal_id: u64 },
+}
+
+/// The query made of the oracle to resolve an option's oracle
+/// amounts. The oracle must respond with a `Decimal`.
+#[cw_serde]
+pub enum OracleQueryMsg {
+    /// The rate for KEY, by which the oracle amount's base amount is
+    /// multiplied.
+    Rate { key: String },
 }
 
 #[cw_serde]
@@ -36,6 +36,11 @@ pub struct MultipleChoiceProposal {
     pub voting_strategy: VotingStrategy,
     /// The total power when the proposal started (used to calculate percentages)
     pub total_power: Uint128,
+    /// The voting power that quorum is computed against, if it
+    /// differs from `total_power`. Set when the module computes
+    /// quorum against active voting power only.
+    #[serde(default)]
+    pub quorum_power: Option<Uint128>,
     /// The vote tally.
     pub votes: MultipleChoiceVotes,
     /// Whether DAO members are allowed to change their votes.
@@ -63,6 +68,11 @@ impl MultipleChoiceProposal {
         Ok(ProposalResponse { id, proposal: self })
     }
 
+    /// The voting power that quorum is computed against.
+    pub fn quorum_power(&self) -> Uint128 {
+        self.quorum_power.unwrap_or(self.total_power)
+    }
+
     /// Gets the current status of the proposal.
     pub fn current_status(&self, block: &BlockInfo) -> StdResult<Status> {
         if self.status == Status::Open && self.is_passed(block)? {
@@ -106,10 +116,20 @@ impl MultipleChoiceProposal {
             }
         }
 
+        if let VotingStrategy::TopTwoRunoff { .. } = self.voting_strategy {
+            return Ok(self.majority_choice(block).map_or(false, |choice| {
+                choice.option_type == MultipleChoiceOptionType::Standard
+            }));
+        }
+
+        if let VotingStrategy::Election { .. } = self.voting_strategy {
+            return Ok(self.election_winners(block).is_some());
+        }
+
         // Proposal can only pass if quorum has been met.
         if does_vote_count_pass(
             self.votes.total(),
-            self.total_power,
+            self.quorum_power(),
             self.voting_strategy.get_quorum(),
         ) {
             let vote_result = self.calculate_vote_result()?;
@@ -141,6 +161,17 @@ impl MultipleChoiceProposal {
             return Ok(false);
         }
 
+        if let VotingStrategy::TopTwoRunoff { .. } = self.voting_strategy {
+            return Ok(match self.majority_choice(block) {
+                Some(choice) => choice.option_type == MultipleChoiceOptionType::None,
+                None => self.expiration.is_expired(block),
+            });
+        }
+
+        if let VotingStrategy::Election { .. } = self.voting_strategy {
+            return Ok(self.expiration.is_expired(block) && self.election_winners(block).is_none());
+        }
+
         let vote_result = self.calculate_vote_result()?;
         match vote_result {
             // Proposal is rejected if there is a tie, and either the proposal is expired or
@@ -154,7 +185,7 @@ impl MultipleChoiceProposal {
                 match (
                     does_vote_count_pass(
                         self.votes.total(),
-                        self.total_power,
+                        self.quorum_power(),
                         self.voting_strategy.get_quorum(),
                     ),
                     self.expiration.is_expired(block),
@@ -186,7 +217,9 @@ impl MultipleChoiceProposal {
     /// Find the option with the highest vote weight, and note if there is a tie.
     pub fn calculate_vote_result(&self) -> StdResult<VoteResult> {
         match self.voting_strategy {
-            VotingStrategy::SingleChoice { quorum: _ } => {
+            VotingStrategy::SingleChoice { .. }
+            | VotingStrategy::TopTwoRunoff { .. }
+            | VotingStrategy::Election { .. } => {
                 // We expect to have at least 3 vote weights
                 if let Some(max_weight) = self.votes.vote_weights.iter().max_by(|&a, &b| a.cmp(b)) {
                     let top_choices: Vec<(usize, &Uint128)> = self
@@ -218,6 +251,111 @@ impl MultipleChoiceProposal {
         }
     }
 
+    /// Returns the choice that has won a majority, if quorum has been
+    /// met. Before the proposal expires a choice needs a majority of
+    /// all voting power, as the outcome may not change after that.
+    /// Once expired, a majority of the votes cast is enough.
+    fn majority_choice(&self, block: &BlockInfo) -> Option<&CheckedMultipleChoiceOption> {
+        if !does_vote_count_pass(
+            self.votes.total(),
+            self.quorum_power(),
+            self.voting_strategy.get_quorum(),
+        ) {
+            return None;
+        }
+        let out_of = if self.expiration.is_expired(block) {
+            self.votes.total()
+        } else {
+            self.total_power
+        };
+        self.choices.iter().find(|choice| {
+            let weight = self.votes.vote_weights[choice.index as usize];
+            weight > out_of.saturating_sub(weight)
+        })
+    }
+
+    /// Returns the choices elected by an election proposal, in order
+    /// of votes, once it has expired having met quorum. These are the
+    /// standard choices with the most votes, up to the number of
+    /// seats, that have more votes than "None of the above". Returns
+    /// `None` if nothing was elected or if the last seat is tied.
+    pub fn election_winners(&self, block: &BlockInfo) -> Option<Vec<&CheckedMultipleChoiceOption>> {
+        let seats = match self.voting_strategy {
+            VotingStrategy::Election { seats, .. } => seats as usize,
+            _ => return None,
+        };
+        if !self.expiration.is_expired(block)
+            || !does_vote_count_pass(
+                self.votes.total(),
+                self.quorum_power(),
+                self.voting_strategy.get_quorum(),
+            )
+        {
+            return None;
+        }
+        let weight =
+            |choice: &CheckedMultipleChoiceOption| self.votes.vote_weights[choice.index as usize];
+        let none_weight = self
+            .choices
+            .iter()
+            .find(|choice| choice.option_type == MultipleChoiceOptionType::None)
+            .map(weight)
+            .unwrap_or_default();
+        let mut ranked: Vec<&CheckedMultipleChoiceOption> = self
+            .choices
+            .iter()
+            .filter(|choice| {
+                choice.option_type == MultipleChoiceOptionType::Standard
+                    && weight(choice) > none_weight
+            })
+            .collect();
+        // Sorting is stable, so tied choices keep their order.
+        ranked.sort_by(|a, b| weight(b).cmp(&weight(a)));
+        if ranked.is_empty()
+            || (ranked.len() > seats && weight(ranked[seats - 1]) == weight(ranked[seats]))
+        {
+            return None;
+        }
+        ranked.truncate(seats);
+        Some(ranked)
+    }
+
+    /// Returns the choices for a runoff if this is a top-two runoff
+    /// proposal that expired having met quorum without a majority
+    /// winner. The runoff is between the two choices with the most
+    /// votes, with ties going to the earlier choice.
+    pub fn runoff_choices(&self, block: &BlockInfo) -> Option<Vec<CheckedMultipleChoiceOption>> {
+        if !matches!(self.voting_strategy, VotingStrategy::TopTwoRunoff { .. })
+            || !self.expiration.is_expired(block)
+            || !does_vote_count_pass(
+                self.votes.total(),
+                self.quorum_power(),
+                self.voting_strategy.get_quorum(),
+            )
+            || self.majority_choice(block).is_some()
+        {
+            return None;
+        }
+        let mut ranked: Vec<&CheckedMultipleChoiceOption> = self.choices.iter().collect();
+        // Sorting is stable, so tied choices keep their order.
+        ranked.sort_by(|a, b| {
+            self.votes.vote_weights[b.index as usize]
+                .cmp(&self.votes.vote_weights[a.index as usize])
+        });
+        Some(
+            ranked
+                .into_iter()
+                .take(2)
+                .enumerate()
+                .map(|(index, choice)| CheckedMultipleChoiceOption {
+                    index: index as u32,
+                    vote_count: Uint128::zero(),
+                    ..choice.clone()
+                })
+                .collect(),
+        )
+    }
+
     /// Ensure that with the remaining vote power, the choice with the second highest votes
     /// cannot overtake the first choice.
     fn is_choice_unbeatable(
@@ -260,7 +398,7 @@ impl MultipleChoiceProposal {
 mod tests {
     use super::*;
 
-    use cosmwasm_std::testing::mock_env;
+    use cosmwasm_std::testing::{mock_env, MockApi};
     use dao_voting::multiple_choice::{MultipleChoiceOption, MultipleChoiceOptions};
 
     fn create_proposal(
@@ -277,11 +415,15 @@ mod tests {
                 description: "multiple choice option 1".to_string(),
                 msgs: vec![],
                 title: "title".to_string(),
+                metadata: None,
+                oracle_amounts: vec![],
             },
             MultipleChoiceOption {
                 description: "multiple choice option 2".to_string(),
                 msgs: vec![],
                 title: "title".to_string(),
+                metadata: None,
+                oracle_amounts: vec![],
             },
         ];
 
@@ -299,10 +441,14 @@ mod tests {
             start_height: mock_env().block.height,
             expiration,
             // The last option that gets added in into_checked is always the none of the above option
-            choices: mc_options.into_checked().unwrap().options,
+            choices: mc_options
+                .into_checked(&MockApi::default())
+                .unwrap()
+                .options,
             status: Status::Open,
             voting_strategy,
             total_power,
+            quorum_power: None,
             votes,
             allow_revoting,
             min_voting_period: None,
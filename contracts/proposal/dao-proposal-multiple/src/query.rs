@@ -1,4 +1,7 @@
-use crate::{proposal::MultipleChoiceProposal, state::Config};
+use crate::{
+    proposal::MultipleChoiceProposal,
+    state::{Config, Nomination},
+};
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Uint128};
 
@@ -48,3 +51,18 @@ pub struct VoterResponse {
 pub struct ConfigResponse {
     pub config: Config,
 }
+
+/// The phase a proposal with a nomination phase is in.
+#[cw_serde]
+pub enum ProposalPhase {
+    /// Options may be nominated, and votes may not be cast.
+    Nomination,
+    /// Votes may be cast on the nominated options.
+    Voting,
+}
+
+#[cw_serde]
+pub struct NominationResponse {
+    pub phase: ProposalPhase,
+    pub nomination: Nomination,
+}
@@ -1,12 +1,13 @@
 use crate::proposal::MultipleChoiceProposal;
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Coin, Uint128};
 use cw_hooks::Hooks;
 use cw_storage_plus::{Item, Map};
-use cw_utils::Duration;
+use cw_utils::{Duration, Expiration};
 use dao_voting::{
     multiple_choice::{MultipleChoiceVote, VotingStrategy},
     pre_propose::ProposalCreationPolicy,
+    quorum::QuorumSource,
 };
 
 /// The proposal module's configuration.
@@ -56,6 +57,35 @@ pub struct Ballot {
     pub rationale: Option<String>,
 }
 
+/// Configures the nomination phase that proposals open with.
+#[cw_serde]
+pub struct NominationConfig {
+    /// How long a proposal accepts nominations before voting opens.
+    pub period: Duration,
+    /// The voting power, at the time a proposal was created, an
+    /// address needs to nominate an option to it.
+    pub min_power: Uint128,
+    /// The most options that may be nominated to a single proposal.
+    pub max_nominations: u32,
+    /// A native token deposit taken for each nomination and refunded
+    /// to the nominator once the proposal is executed or closed.
+    pub deposit: Option<Coin>,
+}
+
+/// The nomination phase of a proposal. The nomination config at the
+/// time the proposal was created applies to it.
+#[cw_serde]
+pub struct Nomination {
+    /// When nominations close and voting opens.
+    pub ends: Expiration,
+    pub min_power: Uint128,
+    pub max_nominations: u32,
+    pub deposit: Option<Coin>,
+    /// The addresses that nominated options, in the order their
+    /// options were added to the proposal.
+    pub nominators: Vec<Addr>,
+}
+
 /// The current top level config for the module.
 pub const CONFIG: Item<Config> = Item::new("config");
 pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
@@ -65,6 +95,26 @@ pub const BALLOTS: Map<(u64, &Addr), Ballot> = Map::new("ballots");
 pub const PROPOSAL_HOOKS: Hooks = Hooks::new("proposal_hooks");
 /// Consumers of vote hooks.
 pub const VOTE_HOOKS: Hooks = Hooks::new("vote_hooks");
+/// If true, the tallies and votes of open proposals are hidden from
+/// queries until the proposal closes. Not set unless updated by the
+/// DAO.
+pub const HIDE_TALLIES: Item<bool> = Item::new("hide_tallies");
+/// Maps top-two runoff proposals to the runoff proposal opened when
+/// they were closed.
+pub const RUNOFFS: Map<u64, u64> = Map::new("runoffs");
+/// The indexes of the choices elected by executed election
+/// proposals, in order of votes.
+pub const ELECTION_WINNERS: Map<u64, Vec<u32>> = Map::new("election_winners");
+/// The contract queried to resolve the oracle amounts of options.
+pub const ORACLE: Item<Addr> = Item::new("oracle");
+/// The quorum source used for new proposals. If unset, quorum is
+/// computed against total voting power.
+pub const QUORUM_SOURCE: Item<QuorumSource> = Item::new("quorum_source");
+/// If set, new proposals open with a nomination phase.
+pub const NOMINATION_CONFIG: Item<NominationConfig> = Item::new("nomination_config");
+/// The nomination phases of proposals created while a nomination
+/// config was set.
+pub const NOMINATIONS: Map<u64, Nomination> = Map::new("nominations");
 /// The address of the pre-propose module associated with this
 /// proposal module (if any).
 pub const CREATION_POLICY: Item<ProposalCreationPolicy> = Item::new("creation_policy");
@@ -43,11 +43,15 @@ fn setup_test(_messages: Vec<CosmosMsg>) -> CommonTest {
             title: "title 1".to_string(),
             description: "multiple choice option 1".to_string(),
             msgs: vec![],
+            metadata: None,
+            oracle_amounts: vec![],
         },
         MultipleChoiceOption {
             title: "title 2".to_string(),
             description: "multiple choice option 2".to_string(),
             msgs: vec![],
+            metadata: None,
+            oracle_amounts: vec![],
         },
     ];
 
@@ -319,11 +323,15 @@ pub fn test_allow_voting_after_proposal_execution_pre_expiration_cw20() {
                 funds: vec![],
             }
             .into()],
+            metadata: None,
+            oracle_amounts: vec![],
         },
         MultipleChoiceOption {
             title: "title 2".to_string(),
             description: "multiple choice option 2".to_string(),
             msgs: vec![],
+            metadata: None,
+            oracle_amounts: vec![],
         },
     ];
 
@@ -141,6 +141,7 @@ where
             &dao_interface::msg::QueryMsg::ProposalModules {
                 start_after: None,
                 limit: None,
+                descending: None,
             },
         )
         .unwrap();
@@ -194,11 +195,15 @@ where
             description: "multiple choice option 1".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
         MultipleChoiceOption {
             description: "multiple choice option 2".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
     ];
 
@@ -52,6 +52,7 @@ pub fn query_multiple_proposal_module(app: &App, core_addr: &Addr) -> Addr {
             &dao_interface::msg::QueryMsg::ProposalModules {
                 start_after: None,
                 limit: None,
+                descending: None,
             },
         )
         .unwrap();
@@ -81,7 +82,11 @@ pub fn query_list_proposals(
     app.wrap()
         .query_wasm_smart(
             proposal_multiple,
-            &QueryMsg::ListProposals { start_after, limit },
+            &QueryMsg::ListProposals {
+                start_after,
+                limit,
+                descending: None,
+            },
         )
         .unwrap()
 }
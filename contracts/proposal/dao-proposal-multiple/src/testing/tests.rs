@@ -1,29 +1,38 @@
-use cosmwasm_std::{to_json_binary, Addr, Coin, CosmosMsg, Decimal, Empty, Timestamp, Uint128, WasmMsg};
+use cosmwasm_std::{
+    coins, to_json_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Empty,
+    Env, MessageInfo, Response, StdResult, Timestamp, Uint128, WasmMsg,
+};
 use cw20::Cw20Coin;
 use cw_denom::{CheckedDenom, UncheckedDenom};
 use cw_hooks::HooksResponse;
 use cw_multi_test::{next_block, App, BankSudo, Contract, ContractWrapper, Executor, SudoMsg};
-use cw_utils::Duration;
+use cw_storage_plus::Item;
+use cw_utils::{Duration, Expiration};
+use dao_interface::activity::{ActivityOracleQuery, InactivePowerAtHeightResponse};
 use dao_interface::state::ProposalModule;
 use dao_interface::state::{Admin, ModuleInstantiateInfo};
 use dao_voting::{
     deposit::{CheckedDepositInfo, DepositRefundPolicy, DepositToken, UncheckedDepositInfo},
     multiple_choice::{
         CheckedMultipleChoiceOption, MultipleChoiceOption, MultipleChoiceOptionType,
-        MultipleChoiceOptions, MultipleChoiceVote, MultipleChoiceVotes, VotingStrategy,
-        MAX_NUM_CHOICES,
+        MultipleChoiceOptions, MultipleChoiceVote, MultipleChoiceVotes, OracleAmount,
+        VotingStrategy, MAX_NUM_CHOICES,
     },
     pre_propose::PreProposeInfo,
+    quorum::{QuorumSource, UncheckedQuorumSource},
     status::Status,
     threshold::{ActiveThreshold, PercentageThreshold, Threshold},
 };
 use std::panic;
 
 use crate::{
-    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    msg::{ExecuteMsg, InstantiateMsg, OracleQueryMsg, QueryMsg},
     proposal::MultipleChoiceProposal,
-    query::{ProposalListResponse, ProposalResponse, VoteInfo, VoteListResponse, VoteResponse},
-    state::Config,
+    query::{
+        NominationResponse, ProposalListResponse, ProposalPhase, ProposalResponse, VoteInfo,
+        VoteListResponse, VoteResponse,
+    },
+    state::{Config, NominationConfig},
     testing::{
         do_votes::do_test_votes_cw20_balances,
         execute::make_proposal,
@@ -76,7 +85,8 @@ pub fn pre_propose_multiple_contract() -> Box<dyn Contract<Empty>> {
         cppm::contract::execute,
         cppm::contract::instantiate,
         cppm::contract::query,
-    );
+    )
+    .with_reply(cppm::contract::reply);
     Box::new(contract)
 }
 
@@ -143,11 +153,15 @@ fn test_propose() {
             description: "multiple choice option 1".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
         MultipleChoiceOption {
             description: "multiple choice option 1".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
     ];
 
@@ -170,6 +184,7 @@ fn test_propose() {
         status: Status::Open,
         voting_strategy,
         total_power: Uint128::new(100_000_000),
+        quorum_power: None,
         votes: MultipleChoiceVotes {
             vote_weights: vec![Uint128::zero(); 3],
         },
@@ -239,6 +254,8 @@ fn test_propose_wrong_num_choices() {
             description: "multiple choice option 1".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         };
         std::convert::TryInto::try_into(MAX_NUM_CHOICES + 1).unwrap()
     ];
@@ -340,11 +357,15 @@ fn test_no_early_pass_with_min_duration() {
             description: "multiple choice option 1".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
         MultipleChoiceOption {
             description: "multiple choice option 2".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
     ];
 
@@ -452,11 +473,15 @@ fn test_propose_with_messages() {
             description: "multiple choice option 1".to_string(),
             msgs: vec![CosmosMsg::Wasm(wasm_msg)],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
         MultipleChoiceOption {
             description: "multiple choice option 2".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
     ];
 
@@ -616,11 +641,15 @@ fn test_min_duration_same_as_proposal_duration() {
             description: "multiple choice option 1".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
         MultipleChoiceOption {
             description: "multiple choice option 2".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
     ];
 
@@ -896,11 +925,15 @@ fn test_take_proposal_deposit() {
             description: "multiple choice option 1".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
         MultipleChoiceOption {
             description: "multiple choice option 2".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
     ];
 
@@ -1012,11 +1045,15 @@ fn test_native_proposal_deposit() {
                     description: "multiple choice option 1".to_string(),
                     msgs: vec![],
                     title: "title".to_string(),
+                    metadata: None,
+                    oracle_amounts: vec![],
                 },
                 MultipleChoiceOption {
                     description: "multiple choice option 2".to_string(),
                     msgs: vec![],
                     title: "title".to_string(),
+                    metadata: None,
+                    oracle_amounts: vec![],
                 },
             ],
         };
@@ -1246,6 +1283,7 @@ fn test_query_list_votes() {
                 proposal_id: 1,
                 start_after: None,
                 limit: None,
+                descending: None,
             },
         )
         .unwrap();
@@ -1432,11 +1470,15 @@ fn test_cant_propose_zero_power() {
             description: "multiple choice option 1".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
         MultipleChoiceOption {
             description: "multiple choice option 2".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
     ];
 
@@ -1583,11 +1625,15 @@ fn test_cant_execute_not_member() {
             description: "multiple choice option 1".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
         MultipleChoiceOption {
             description: "multiple choice option 2".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
     ];
 
@@ -1673,11 +1719,15 @@ fn test_cant_execute_not_member_when_proposal_created() {
             description: "multiple choice option 1".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
         MultipleChoiceOption {
             description: "multiple choice option 2".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
     ];
 
@@ -1786,11 +1836,15 @@ fn test_open_proposal_submission() {
                     description: "multiple choice option 1".to_string(),
                     msgs: vec![],
                     title: "title".to_string(),
+                    metadata: None,
+                    oracle_amounts: vec![],
                 },
                 MultipleChoiceOption {
                     description: "multiple choice option 2".to_string(),
                     msgs: vec![],
                     title: "title".to_string(),
+                    metadata: None,
+                    oracle_amounts: vec![],
                 },
             ],
         },
@@ -1807,6 +1861,7 @@ fn test_open_proposal_submission() {
         min_voting_period: None,
         allow_revoting: false,
         total_power: Uint128::new(100_000_000),
+        quorum_power: None,
         status: Status::Open,
         voting_strategy: VotingStrategy::SingleChoice {
             quorum: PercentageThreshold::Percent(Decimal::percent(100)),
@@ -1819,6 +1874,8 @@ fn test_open_proposal_submission() {
                 vote_count: Uint128::zero(),
                 index: 0,
                 title: "title".to_string(),
+                metadata: None,
+                oracle_amounts: vec![],
             },
             CheckedMultipleChoiceOption {
                 description: "multiple choice option 2".to_string(),
@@ -1827,6 +1884,8 @@ fn test_open_proposal_submission() {
                 vote_count: Uint128::zero(),
                 index: 1,
                 title: "title".to_string(),
+                metadata: None,
+                oracle_amounts: vec![],
             },
             CheckedMultipleChoiceOption {
                 description: "None of the above".to_string(),
@@ -1835,6 +1894,8 @@ fn test_open_proposal_submission() {
                 vote_count: Uint128::zero(),
                 index: 2,
                 title: "None of the above".to_string(),
+                metadata: None,
+                oracle_amounts: vec![],
             },
         ],
         votes: MultipleChoiceVotes {
@@ -2096,11 +2157,15 @@ fn test_execute_expired_proposal() {
             description: "multiple choice option 1".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
         MultipleChoiceOption {
             description: "multiple choice option 2".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
     ];
 
@@ -2373,6 +2438,7 @@ fn test_query_list_proposals() {
             &dao_interface::msg::QueryMsg::ProposalModules {
                 start_after: None,
                 limit: None,
+                descending: None,
             },
         )
         .unwrap();
@@ -2385,11 +2451,15 @@ fn test_query_list_proposals() {
             description: "multiple choice option 1".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
         MultipleChoiceOption {
             description: "multiple choice option 2".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
     ];
 
@@ -2431,6 +2501,7 @@ fn test_query_list_proposals() {
             status: Status::Open,
             voting_strategy: voting_strategy.clone(),
             total_power: Uint128::new(100),
+            quorum_power: None,
             votes: MultipleChoiceVotes {
                 vote_weights: vec![Uint128::zero(); 3],
             },
@@ -2459,6 +2530,7 @@ fn test_query_list_proposals() {
             status: Status::Open,
             voting_strategy,
             total_power: Uint128::new(100),
+            quorum_power: None,
             votes: MultipleChoiceVotes {
                 vote_weights: vec![Uint128::zero(); 3],
             },
@@ -2653,11 +2725,15 @@ fn test_active_threshold_absolute() {
             description: "multiple choice option 1".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
         MultipleChoiceOption {
             description: "multiple choice option 2".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
     ];
 
@@ -2781,11 +2857,15 @@ fn test_active_threshold_percent() {
             description: "multiple choice option 1".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
         MultipleChoiceOption {
             description: "multiple choice option 2".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
     ];
 
@@ -2912,11 +2992,15 @@ fn test_active_threshold_none() {
             description: "multiple choice option 1".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
         MultipleChoiceOption {
             description: "multiple choice option 2".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
     ];
 
@@ -3000,11 +3084,15 @@ fn test_revoting() {
             description: "multiple choice option 1".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
         MultipleChoiceOption {
             description: "multiple choice option 2".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
     ];
     let mc_options = MultipleChoiceOptions { options };
@@ -3132,11 +3220,15 @@ fn test_allow_revoting_config_changes() {
             description: "multiple choice option 1".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
         MultipleChoiceOption {
             description: "multiple choice option 2".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
     ];
     let mc_options = MultipleChoiceOptions { options };
@@ -3283,11 +3375,15 @@ fn test_revoting_same_vote_twice() {
             description: "multiple choice option 1".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
         MultipleChoiceOption {
             description: "multiple choice option 2".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
     ];
     let mc_options = MultipleChoiceOptions { options };
@@ -3377,11 +3473,15 @@ fn test_invalid_revote_does_not_invalidate_initial_vote() {
             description: "multiple choice option 1".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
         MultipleChoiceOption {
             description: "multiple choice option 2".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
     ];
     let mc_options = MultipleChoiceOptions { options };
@@ -3604,11 +3704,15 @@ fn test_close_failed_proposal() {
             }
             .into()],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
         MultipleChoiceOption {
             description: "Don't burn".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
     ];
 
@@ -3688,11 +3792,15 @@ fn test_close_failed_proposal() {
                             }
                             .into()],
                             title: "title".to_string(),
+                            metadata: None,
+                            oracle_amounts: vec![],
                         },
                         MultipleChoiceOption {
                             description: "Don't disable".to_string(),
                             msgs: vec![],
                             title: "title".to_string(),
+                            metadata: None,
+                            oracle_amounts: vec![],
                         },
                     ],
                 },
@@ -3890,11 +3998,15 @@ fn test_no_double_refund_on_execute_fail_and_close() {
                 }
                 .into()],
                 title: "title".to_string(),
+                metadata: None,
+                oracle_amounts: vec![],
             },
             MultipleChoiceOption {
                 description: "hi there".to_string(),
                 msgs: vec![],
                 title: "title".to_string(),
+                metadata: None,
+                oracle_amounts: vec![],
             },
         ],
     };
@@ -3997,11 +4109,15 @@ pub fn test_not_allow_voting_on_expired_proposal() {
             description: "multiple choice option 1".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
         MultipleChoiceOption {
             description: "multiple choice option 2".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
     ];
     let mc_options = MultipleChoiceOptions { options };
@@ -4091,11 +4207,15 @@ fn test_next_proposal_id() {
             description: "multiple choice option 1".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
         MultipleChoiceOption {
             description: "multiple choice option 2".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
     ];
     let mc_options = MultipleChoiceOptions { options };
@@ -4163,11 +4283,15 @@ fn test_vote_with_rationale() {
             description: "multiple choice option 1".to_string(),
             msgs: vec![],
             title: "title 1".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
         MultipleChoiceOption {
             description: "multiple choice option 2".to_string(),
             msgs: vec![],
             title: "title 2".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
     ];
 
@@ -4259,11 +4383,15 @@ fn test_revote_with_rationale() {
             description: "multiple choice option 1".to_string(),
             msgs: vec![],
             title: "title 1".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
         MultipleChoiceOption {
             description: "multiple choice option 2".to_string(),
             msgs: vec![],
             title: "title 2".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
     ];
 
@@ -4413,11 +4541,15 @@ fn test_update_rationale() {
             description: "multiple choice option 1".to_string(),
             msgs: vec![],
             title: "title 1".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
         MultipleChoiceOption {
             description: "multiple choice option 2".to_string(),
             msgs: vec![],
             title: "title 2".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         },
     ];
 
@@ -4499,3 +4631,1024 @@ fn test_update_rationale() {
         Some("This may be a good idea, but I'm not sure. YOLO".to_string())
     );
 }
+
+#[test]
+fn test_tally_privacy() {
+    let mut app = App::default();
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        InstantiateMsg {
+            min_voting_period: None,
+            max_voting_period: Duration::Height(6),
+            only_members_execute: false,
+            allow_revoting: true,
+            voting_strategy: VotingStrategy::SingleChoice {
+                quorum: PercentageThreshold::Majority {},
+            },
+            close_proposal_on_execution_failure: false,
+            pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        },
+        Some(vec![
+            Cw20Coin {
+                address: "a-1".to_string(),
+                amount: Uint128::new(100_000_000),
+            },
+            Cw20Coin {
+                address: "a-2".to_string(),
+                amount: Uint128::new(100_000_000),
+            },
+        ]),
+    );
+    let govmod = query_multiple_proposal_module(&app, &core_addr);
+
+    // Only the DAO may hide tallies.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("a-1"),
+            govmod.clone(),
+            &ExecuteMsg::UpdateTallyPrivacy { hide_tallies: true },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    app.execute_contract(
+        core_addr,
+        govmod.clone(),
+        &ExecuteMsg::UpdateTallyPrivacy { hide_tallies: true },
+        &[],
+    )
+    .unwrap();
+    let hidden: bool = app
+        .wrap()
+        .query_wasm_smart(govmod.clone(), &QueryMsg::TallyPrivacy {})
+        .unwrap();
+    assert!(hidden);
+
+    let options = vec![
+        MultipleChoiceOption {
+            description: "multiple choice option 1".to_string(),
+            msgs: vec![],
+            title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
+        },
+        MultipleChoiceOption {
+            description: "multiple choice option 2".to_string(),
+            msgs: vec![],
+            title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
+        },
+    ];
+    app.execute_contract(
+        Addr::unchecked("a-1"),
+        govmod.clone(),
+        &ExecuteMsg::Propose {
+            title: "A simple text proposal".to_string(),
+            description: "A simple text proposal".to_string(),
+            choices: MultipleChoiceOptions { options },
+            proposer: None,
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("a-1"),
+        govmod.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: MultipleChoiceVote { option_id: 0 },
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // While the proposal is open its tally and votes are hidden.
+    let proposal = query_proposal(&app, &govmod, 1);
+    assert_eq!(proposal.proposal.status, Status::Open);
+    assert_eq!(
+        proposal.proposal.votes.vote_weights,
+        vec![Uint128::zero(); 3]
+    );
+    let list = query_list_proposals(&app, &govmod, None, None);
+    assert_eq!(
+        list.proposals[0].proposal.votes.vote_weights,
+        vec![Uint128::zero(); 3]
+    );
+    let res: StdResult<VoteListResponse> = app.wrap().query_wasm_smart(
+        govmod.clone(),
+        &QueryMsg::ListVotes {
+            proposal_id: 1,
+            start_after: None,
+            limit: None,
+            descending: None,
+        },
+    );
+    assert!(res.is_err());
+    let res: StdResult<VoteResponse> = app.wrap().query_wasm_smart(
+        govmod.clone(),
+        &QueryMsg::GetVote {
+            proposal_id: 1,
+            voter: "a-1".to_string(),
+        },
+    );
+    assert!(res.is_err());
+
+    // Once the proposal closes, everything is revealed.
+    app.update_block(|b| b.height += 6);
+    let proposal = query_proposal(&app, &govmod, 1);
+    assert_eq!(proposal.proposal.status, Status::Rejected);
+    assert_eq!(
+        proposal.proposal.votes.vote_weights,
+        vec![Uint128::new(100_000_000), Uint128::zero(), Uint128::zero()]
+    );
+    let vote: VoteResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod,
+            &QueryMsg::GetVote {
+                proposal_id: 1,
+                voter: "a-1".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(vote.vote.unwrap().vote, MultipleChoiceVote { option_id: 0 });
+}
+
+#[test]
+fn test_top_two_runoff() {
+    let mut app = App::default();
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        InstantiateMsg {
+            min_voting_period: None,
+            max_voting_period: Duration::Height(6),
+            only_members_execute: false,
+            allow_revoting: false,
+            voting_strategy: VotingStrategy::TopTwoRunoff {
+                quorum: PercentageThreshold::Majority {},
+            },
+            close_proposal_on_execution_failure: false,
+            pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        },
+        Some(vec![
+            Cw20Coin {
+                address: "a-1".to_string(),
+                amount: Uint128::new(40),
+            },
+            Cw20Coin {
+                address: "a-2".to_string(),
+                amount: Uint128::new(35),
+            },
+            Cw20Coin {
+                address: "a-3".to_string(),
+                amount: Uint128::new(25),
+            },
+        ]),
+    );
+    let govmod = query_multiple_proposal_module(&app, &core_addr);
+
+    let options: Vec<MultipleChoiceOption> = (1..=3)
+        .map(|i| MultipleChoiceOption {
+            description: format!("multiple choice option {i}"),
+            msgs: vec![],
+            title: format!("option {i}"),
+            metadata: None,
+            oracle_amounts: vec![],
+        })
+        .collect();
+    let propose = |app: &mut App| {
+        app.execute_contract(
+            Addr::unchecked("a-1"),
+            govmod.clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple text proposal".to_string(),
+                description: "A simple text proposal".to_string(),
+                choices: MultipleChoiceOptions {
+                    options: options.clone(),
+                },
+                proposer: None,
+            },
+            &[],
+        )
+        .unwrap();
+    };
+    let vote = |app: &mut App, voter: &str, proposal_id: u64, option_id: u32| {
+        app.execute_contract(
+            Addr::unchecked(voter),
+            govmod.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id,
+                vote: MultipleChoiceVote { option_id },
+                rationale: None,
+            },
+            &[],
+        )
+        .unwrap();
+    };
+
+    // A choice with a majority of all voting power passes outright.
+    propose(&mut app);
+    vote(&mut app, "a-1", 1, 0);
+    vote(&mut app, "a-3", 1, 0);
+    assert_eq!(
+        query_proposal(&app, &govmod, 1).proposal.status,
+        Status::Passed
+    );
+
+    // Without a majority, the proposal is rejected once voting closes
+    // and closing it opens a runoff.
+    propose(&mut app);
+    vote(&mut app, "a-1", 2, 0);
+    vote(&mut app, "a-2", 2, 1);
+    vote(&mut app, "a-3", 2, 2);
+    assert_eq!(
+        query_proposal(&app, &govmod, 2).proposal.status,
+        Status::Open
+    );
+    app.update_block(|block| block.height += 6);
+    assert_eq!(
+        query_proposal(&app, &govmod, 2).proposal.status,
+        Status::Rejected
+    );
+
+    app.execute_contract(
+        Addr::unchecked("a-3"),
+        govmod.clone(),
+        &ExecuteMsg::Close { proposal_id: 2 },
+        &[],
+    )
+    .unwrap();
+    let runoff_id: Option<u64> = app
+        .wrap()
+        .query_wasm_smart(govmod.clone(), &QueryMsg::Runoff { proposal_id: 2 })
+        .unwrap();
+    assert_eq!(runoff_id, Some(3));
+
+    let first_round = query_proposal(&app, &govmod, 2).proposal;
+    let runoff = query_proposal(&app, &govmod, 3).proposal;
+    assert_eq!(runoff.start_height, first_round.start_height);
+    assert_eq!(runoff.total_power, first_round.total_power);
+    assert_eq!(
+        runoff
+            .choices
+            .iter()
+            .map(|choice| (choice.index, choice.title.as_str()))
+            .collect::<Vec<_>>(),
+        vec![(0, "option 1"), (1, "option 2")]
+    );
+
+    // The runoff is decided by simple majority.
+    vote(&mut app, "a-1", 3, 0);
+    vote(&mut app, "a-2", 3, 1);
+    vote(&mut app, "a-3", 3, 1);
+    let runoff = query_proposal(&app, &govmod, 3).proposal;
+    assert_eq!(runoff.status, Status::Passed);
+    assert_eq!(
+        runoff.votes.vote_weights,
+        vec![Uint128::new(40), Uint128::new(60)]
+    );
+}
+
+const RATE: Item<Decimal> = Item::new("rate");
+
+fn oracle_instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    rate: Decimal,
+) -> StdResult<Response> {
+    RATE.save(deps.storage, &rate)?;
+    Ok(Response::default())
+}
+
+fn oracle_execute(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    rate: Decimal,
+) -> StdResult<Response> {
+    RATE.save(deps.storage, &rate)?;
+    Ok(Response::default())
+}
+
+fn oracle_query(deps: Deps, _env: Env, _msg: OracleQueryMsg) -> StdResult<Binary> {
+    to_json_binary(&RATE.load(deps.storage)?)
+}
+
+/// An oracle that responds with the same rate for every key. The
+/// rate is set on instantiation and by executing the contract with a
+/// new one.
+fn oracle_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(oracle_execute, oracle_instantiate, oracle_query);
+    Box::new(contract)
+}
+
+#[test]
+fn test_oracle_amounts() {
+    let mut app = App::default();
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        InstantiateMsg {
+            min_voting_period: None,
+            max_voting_period: Duration::Height(6),
+            only_members_execute: false,
+            allow_revoting: false,
+            voting_strategy: VotingStrategy::SingleChoice {
+                quorum: PercentageThreshold::Majority {},
+            },
+            close_proposal_on_execution_failure: false,
+            pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        },
+        Some(vec![Cw20Coin {
+            address: "a-1".to_string(),
+            amount: Uint128::new(100_000_000),
+        }]),
+    );
+    let govmod = query_multiple_proposal_module(&app, &core_addr);
+    app.sudo(SudoMsg::Bank(BankSudo::Mint {
+        to_address: core_addr.to_string(),
+        amount: coins(1000, "ujuno"),
+    }))
+    .unwrap();
+
+    let oracle_id = app.store_code(oracle_contract());
+    let oracle = app
+        .instantiate_contract(
+            oracle_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &Decimal::percent(200),
+            &[],
+            "oracle",
+            None,
+        )
+        .unwrap();
+
+    // Sends 100 tokens' worth of ujuno at the rate at execution time,
+    // so long as that is between 100 and 300 ujuno.
+    let choices = MultipleChoiceOptions {
+        options: vec![
+            MultipleChoiceOption {
+                description: "pay".to_string(),
+                msgs: vec![CosmosMsg::Bank(BankMsg::Send {
+                    to_address: "recipient".to_string(),
+                    amount: coins(1, "ujuno"),
+                })],
+                title: "pay".to_string(),
+                metadata: None,
+                oracle_amounts: vec![OracleAmount {
+                    msg_index: 0,
+                    key: "ujuno".to_string(),
+                    base_amount: Uint128::new(100),
+                    min: Uint128::new(100),
+                    max: Uint128::new(300),
+                }],
+            },
+            MultipleChoiceOption {
+                description: "don't pay".to_string(),
+                msgs: vec![],
+                title: "don't pay".to_string(),
+                metadata: None,
+                oracle_amounts: vec![],
+            },
+        ],
+    };
+    let propose = |app: &mut App| {
+        app.execute_contract(
+            Addr::unchecked("a-1"),
+            govmod.clone(),
+            &ExecuteMsg::Propose {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                choices: choices.clone(),
+                proposer: None,
+            },
+            &[],
+        )
+    };
+
+    // Proposals may not use oracle amounts until an oracle is set.
+    let err: ContractError = propose(&mut app).unwrap_err().downcast().unwrap();
+    assert!(matches!(err, ContractError::NoOracle {}));
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("a-1"),
+            govmod.clone(),
+            &ExecuteMsg::UpdateOracle {
+                oracle: Some(oracle.to_string()),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+    app.execute_contract(
+        core_addr.clone(),
+        govmod.clone(),
+        &ExecuteMsg::UpdateOracle {
+            oracle: Some(oracle.to_string()),
+        },
+        &[],
+    )
+    .unwrap();
+    let configured: Option<Addr> = app
+        .wrap()
+        .query_wasm_smart(govmod.clone(), &QueryMsg::Oracle {})
+        .unwrap();
+    assert_eq!(configured, Some(oracle.clone()));
+
+    propose(&mut app).unwrap();
+    app.execute_contract(
+        Addr::unchecked("a-1"),
+        govmod.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: MultipleChoiceVote { option_id: 0 },
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        query_proposal(&app, &govmod, 1).proposal.status,
+        Status::Passed
+    );
+
+    // Execution fails while the resolved amount is out of bounds.
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        oracle.clone(),
+        &Decimal::percent(500),
+        &[],
+    )
+    .unwrap();
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("a-1"),
+            govmod.clone(),
+            &ExecuteMsg::Execute { proposal_id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::OracleAmountOutOfBounds { amount, min, max }
+            if amount == Uint128::new(500) && min == Uint128::new(100) && max == Uint128::new(300)
+    ));
+    assert_eq!(
+        query_proposal(&app, &govmod, 1).proposal.status,
+        Status::Passed
+    );
+
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        oracle,
+        &Decimal::percent(250),
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("a-1"),
+        govmod.clone(),
+        &ExecuteMsg::Execute { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        query_balance_native(&app, "recipient", "ujuno"),
+        Uint128::new(250)
+    );
+    assert_eq!(
+        query_proposal(&app, &govmod, 1).proposal.status,
+        Status::Executed
+    );
+}
+
+const INACTIVE_POWER: Item<Uint128> = Item::new("inactive_power");
+
+fn activity_oracle_instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    power: Uint128,
+) -> StdResult<Response> {
+    INACTIVE_POWER.save(deps.storage, &power)?;
+    Ok(Response::default())
+}
+
+fn activity_oracle_query(deps: Deps, env: Env, msg: ActivityOracleQuery) -> StdResult<Binary> {
+    match msg {
+        ActivityOracleQuery::InactivePowerAtHeight { height } => {
+            to_json_binary(&InactivePowerAtHeightResponse {
+                power: INACTIVE_POWER.load(deps.storage)?,
+                height: height.unwrap_or(env.block.height),
+            })
+        }
+    }
+}
+
+/// An activity oracle that reports a fixed amount of inactive voting
+/// power, set at instantiation.
+fn activity_oracle_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        oracle_execute,
+        activity_oracle_instantiate,
+        activity_oracle_query,
+    );
+    Box::new(contract)
+}
+
+#[test]
+fn test_quorum_source() {
+    let mut app = App::default();
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        InstantiateMsg {
+            min_voting_period: None,
+            max_voting_period: Duration::Height(6),
+            only_members_execute: false,
+            allow_revoting: false,
+            voting_strategy: VotingStrategy::SingleChoice {
+                quorum: PercentageThreshold::Majority {},
+            },
+            close_proposal_on_execution_failure: false,
+            pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        },
+        Some(vec![
+            Cw20Coin {
+                address: "a-1".to_string(),
+                amount: Uint128::new(10),
+            },
+            Cw20Coin {
+                address: "a-2".to_string(),
+                amount: Uint128::new(90),
+            },
+        ]),
+    );
+    let govmod = query_multiple_proposal_module(&app, &core_addr);
+
+    let oracle_id = app.store_code(activity_oracle_contract());
+    let oracle = app
+        .instantiate_contract(
+            oracle_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &Uint128::new(90),
+            &[],
+            "oracle",
+            None,
+        )
+        .unwrap();
+
+    let quorum_source: QuorumSource = app
+        .wrap()
+        .query_wasm_smart(&govmod, &QueryMsg::QuorumSource {})
+        .unwrap();
+    assert_eq!(quorum_source, QuorumSource::TotalPower {});
+
+    // Only the DAO may update the quorum source.
+    let update = ExecuteMsg::UpdateQuorumSource {
+        quorum_source: UncheckedQuorumSource::ActivePower {
+            oracle: oracle.to_string(),
+        },
+    };
+    let err: ContractError = app
+        .execute_contract(Addr::unchecked("a-1"), govmod.clone(), &update, &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    let choices = MultipleChoiceOptions {
+        options: vec![
+            MultipleChoiceOption {
+                description: "a".to_string(),
+                msgs: vec![],
+                title: "a".to_string(),
+                metadata: None,
+                oracle_amounts: vec![],
+            },
+            MultipleChoiceOption {
+                description: "b".to_string(),
+                msgs: vec![],
+                title: "b".to_string(),
+                metadata: None,
+                oracle_amounts: vec![],
+            },
+        ],
+    };
+    let vote = |app: &mut App, proposal_id: u64| {
+        app.execute_contract(
+            Addr::unchecked("a-1"),
+            govmod.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id,
+                vote: MultipleChoiceVote { option_id: 0 },
+                rationale: None,
+            },
+            &[],
+        )
+        .unwrap();
+    };
+
+    // Against total power, a 10% turnout does not meet the majority
+    // quorum.
+    let total = make_proposal(&mut app, &govmod, "a-1", choices.clone());
+    vote(&mut app, total);
+
+    app.execute_contract(core_addr, govmod.clone(), &update, &[])
+        .unwrap();
+
+    // Against active power, the same turnout is 100%.
+    let active = make_proposal(&mut app, &govmod, "a-1", choices);
+    vote(&mut app, active);
+
+    let proposal = query_proposal(&app, &govmod, active);
+    assert_eq!(proposal.proposal.total_power, Uint128::new(100));
+    assert_eq!(proposal.proposal.quorum_power, Some(Uint128::new(10)));
+
+    app.update_block(|block| block.height += 6);
+
+    assert_eq!(
+        query_proposal(&app, &govmod, total).proposal.status,
+        Status::Rejected
+    );
+    assert_eq!(
+        query_proposal(&app, &govmod, active).proposal.status,
+        Status::Passed
+    );
+}
+
+#[test]
+fn test_election() {
+    let mut app = App::default();
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        InstantiateMsg {
+            min_voting_period: None,
+            max_voting_period: Duration::Height(6),
+            only_members_execute: false,
+            allow_revoting: false,
+            voting_strategy: VotingStrategy::Election {
+                quorum: PercentageThreshold::Majority {},
+                seats: 2,
+            },
+            close_proposal_on_execution_failure: false,
+            pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        },
+        Some(vec![
+            Cw20Coin {
+                address: "a-1".to_string(),
+                amount: Uint128::new(40),
+            },
+            Cw20Coin {
+                address: "a-2".to_string(),
+                amount: Uint128::new(20),
+            },
+            Cw20Coin {
+                address: "a-3".to_string(),
+                amount: Uint128::new(20),
+            },
+            Cw20Coin {
+                address: "a-4".to_string(),
+                amount: Uint128::new(20),
+            },
+        ]),
+    );
+    let govmod = query_multiple_proposal_module(&app, &core_addr);
+
+    let propose = |app: &mut App, candidates: u32| {
+        let options = (1..=candidates)
+            .map(|i| MultipleChoiceOption {
+                description: format!("candidate {i}"),
+                msgs: vec![],
+                title: format!("candidate {i}"),
+                metadata: None,
+                oracle_amounts: vec![],
+            })
+            .collect();
+        app.execute_contract(
+            Addr::unchecked("a-1"),
+            govmod.clone(),
+            &ExecuteMsg::Propose {
+                title: "An election".to_string(),
+                description: "An election".to_string(),
+                choices: MultipleChoiceOptions { options },
+                proposer: None,
+            },
+            &[],
+        )
+    };
+    let vote = |app: &mut App, voter: &str, proposal_id: u64, option_id: u32| {
+        app.execute_contract(
+            Addr::unchecked(voter),
+            govmod.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id,
+                vote: MultipleChoiceVote { option_id },
+                rationale: None,
+            },
+            &[],
+        )
+        .unwrap();
+    };
+    let winners = |app: &App, proposal_id: u64| -> Option<Vec<u32>> {
+        app.wrap()
+            .query_wasm_smart(govmod.clone(), &QueryMsg::ElectionWinners { proposal_id })
+            .unwrap()
+    };
+
+    // There must be at least as many candidates as seats.
+    let err: ContractError = propose(&mut app, 1).unwrap_err().downcast().unwrap();
+    assert!(matches!(
+        err,
+        ContractError::TooManySeats {
+            seats: 2,
+            candidates: 1
+        }
+    ));
+
+    // Candidates without more votes than "None of the above" are not
+    // elected, and nothing is decided until voting closes.
+    propose(&mut app, 4).unwrap();
+    vote(&mut app, "a-1", 1, 0);
+    vote(&mut app, "a-2", 1, 2);
+    vote(&mut app, "a-3", 1, 2);
+    vote(&mut app, "a-4", 1, 4);
+    assert!(matches!(
+        query_proposal(&app, &govmod, 1).proposal.status,
+        Status::Open
+    ));
+    assert_eq!(winners(&app, 1), None);
+
+    app.update_block(|block| block.height += 6);
+    assert!(matches!(
+        query_proposal(&app, &govmod, 1).proposal.status,
+        Status::Passed
+    ));
+    assert_eq!(winners(&app, 1), Some(vec![0, 2]));
+
+    app.execute_contract(
+        Addr::unchecked("a-1"),
+        govmod.clone(),
+        &ExecuteMsg::Execute { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+    assert!(matches!(
+        query_proposal(&app, &govmod, 1).proposal.status,
+        Status::Executed
+    ));
+    assert_eq!(winners(&app, 1), Some(vec![0, 2]));
+
+    // A tie for the last seat rejects the proposal.
+    propose(&mut app, 4).unwrap();
+    vote(&mut app, "a-1", 2, 0);
+    vote(&mut app, "a-2", 2, 1);
+    vote(&mut app, "a-3", 2, 2);
+    vote(&mut app, "a-4", 2, 3);
+    app.update_block(|block| block.height += 6);
+    assert!(matches!(
+        query_proposal(&app, &govmod, 2).proposal.status,
+        Status::Rejected
+    ));
+    assert_eq!(winners(&app, 2), None);
+}
+
+#[test]
+fn test_nomination_phase() {
+    let mut app = App::default();
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        InstantiateMsg {
+            min_voting_period: None,
+            max_voting_period: Duration::Height(6),
+            only_members_execute: false,
+            allow_revoting: false,
+            voting_strategy: VotingStrategy::SingleChoice {
+                quorum: PercentageThreshold::Majority {},
+            },
+            close_proposal_on_execution_failure: false,
+            pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        },
+        Some(vec![
+            Cw20Coin {
+                address: "a-1".to_string(),
+                amount: Uint128::new(60),
+            },
+            Cw20Coin {
+                address: "a-2".to_string(),
+                amount: Uint128::new(30),
+            },
+            Cw20Coin {
+                address: "a-3".to_string(),
+                amount: Uint128::new(10),
+            },
+        ]),
+    );
+    let govmod = query_multiple_proposal_module(&app, &core_addr);
+    for addr in ["a-1", "a-2", "a-3"] {
+        app.sudo(SudoMsg::Bank(BankSudo::Mint {
+            to_address: addr.to_string(),
+            amount: coins(10, "ujuno"),
+        }))
+        .unwrap();
+    }
+
+    let nomination_config = NominationConfig {
+        period: Duration::Height(4),
+        min_power: Uint128::new(20),
+        max_nominations: 2,
+        deposit: Some(Coin {
+            denom: "ujuno".to_string(),
+            amount: Uint128::new(10),
+        }),
+    };
+
+    // Only the DAO may configure nominations.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("a-1"),
+            govmod.clone(),
+            &ExecuteMsg::UpdateNominationConfig {
+                nomination: Some(nomination_config.clone()),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    let err: ContractError = app
+        .execute_contract(
+            core_addr.clone(),
+            govmod.clone(),
+            &ExecuteMsg::UpdateNominationConfig {
+                nomination: Some(NominationConfig {
+                    max_nominations: 0,
+                    ..nomination_config.clone()
+                }),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::InvalidNominationConfig {}));
+
+    app.execute_contract(
+        core_addr,
+        govmod.clone(),
+        &ExecuteMsg::UpdateNominationConfig {
+            nomination: Some(nomination_config.clone()),
+        },
+        &[],
+    )
+    .unwrap();
+    let config: Option<NominationConfig> = app
+        .wrap()
+        .query_wasm_smart(govmod.clone(), &QueryMsg::NominationConfig {})
+        .unwrap();
+    assert_eq!(config, Some(nomination_config));
+
+    let option = |title: &str| MultipleChoiceOption {
+        description: title.to_string(),
+        msgs: vec![],
+        title: title.to_string(),
+        metadata: None,
+        oracle_amounts: vec![],
+    };
+    let start_height = app.block_info().height;
+    app.execute_contract(
+        Addr::unchecked("a-1"),
+        govmod.clone(),
+        &ExecuteMsg::Propose {
+            title: "A nominated proposal".to_string(),
+            description: "A nominated proposal".to_string(),
+            choices: MultipleChoiceOptions {
+                options: vec![option("option 1"), option("option 2")],
+            },
+            proposer: None,
+        },
+        &[],
+    )
+    .unwrap();
+    let nomination = |app: &App| -> NominationResponse {
+        app.wrap()
+            .query_wasm_smart(govmod.clone(), &QueryMsg::Nomination { proposal_id: 1 })
+            .unwrap()
+    };
+    let nominate = |app: &mut App, sender: &str, title: &str, funds: &[Coin]| {
+        app.execute_contract(
+            Addr::unchecked(sender),
+            govmod.clone(),
+            &ExecuteMsg::Nominate {
+                proposal_id: 1,
+                option: option(title),
+            },
+            funds,
+        )
+    };
+
+    // Voting opens once nominations close.
+    assert_eq!(nomination(&app).phase, ProposalPhase::Nomination);
+    let proposal = query_proposal(&app, &govmod, 1);
+    assert_eq!(
+        proposal.proposal.expiration,
+        Expiration::AtHeight(start_height + 10)
+    );
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("a-1"),
+            govmod.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id: 1,
+                vote: MultipleChoiceVote { option_id: 0 },
+                rationale: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Nominating { id: 1 }));
+
+    // Nominators need enough voting power and the exact deposit.
+    let err: ContractError = nominate(&mut app, "a-3", "option 3", &coins(10, "ujuno"))
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::InsufficientNominationPower { .. }
+    ));
+    let err: ContractError = nominate(&mut app, "a-2", "option 3", &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::WrongNominationDeposit {}));
+
+    nominate(&mut app, "a-2", "option 3", &coins(10, "ujuno")).unwrap();
+    nominate(&mut app, "a-1", "option 4", &coins(10, "ujuno")).unwrap();
+    let err: ContractError = nominate(&mut app, "a-2", "option 5", &coins(10, "ujuno"))
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::TooManyNominations {}));
+
+    // Nominated options are added before "None of the above".
+    let proposal = query_proposal(&app, &govmod, 1);
+    let choices = proposal.proposal.choices;
+    assert_eq!(choices.len(), 5);
+    assert_eq!(choices[2].title, "option 3");
+    assert_eq!(choices[2].index, 2);
+    assert_eq!(choices[3].title, "option 4");
+    assert_eq!(choices[4].index, 4);
+    assert_eq!(choices[4].option_type, MultipleChoiceOptionType::None);
+    assert_eq!(proposal.proposal.votes.vote_weights.len(), 5);
+    assert_eq!(
+        nomination(&app).nomination.nominators,
+        vec![Addr::unchecked("a-2"), Addr::unchecked("a-1")]
+    );
+
+    app.update_block(|b| b.height += 4);
+    assert_eq!(nomination(&app).phase, ProposalPhase::Voting);
+    let err: ContractError = nominate(&mut app, "a-1", "option 5", &coins(10, "ujuno"))
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NotNominating { id: 1 }));
+
+    // Nominated options may win, and deposits are refunded once the
+    // proposal is executed.
+    app.execute_contract(
+        Addr::unchecked("a-1"),
+        govmod.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: MultipleChoiceVote { option_id: 2 },
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+    let proposal = query_proposal(&app, &govmod, 1);
+    assert_eq!(proposal.proposal.status, Status::Passed);
+    app.execute_contract(
+        Addr::unchecked("a-1"),
+        govmod,
+        &ExecuteMsg::Execute { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(query_balance_native(&app, "a-1", "ujuno"), Uint128::new(10));
+    assert_eq!(query_balance_native(&app, "a-2", "ujuno"), Uint128::new(10));
+}
@@ -1,50 +1,75 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Order, Reply,
-    Response, StdResult, Storage, SubMsg, WasmMsg,
+    to_json_binary, to_json_vec, Addr, BankMsg, Binary, BlockInfo, Coin, CosmosMsg, Decimal, Deps,
+    DepsMut, Empty, Env, HexBinary, MessageInfo, Order, Reply, Response, StdResult, Storage,
+    SubMsg, Uint128, WasmMsg,
 };
 use cw2::{get_contract_version, set_contract_version, ContractVersion};
 use cw_hooks::Hooks;
+use cw_paginate_storage::{clamp_limit, clamp_limit_u64, order, range_bounds};
 use cw_proposal_single_v1 as v1;
 use cw_storage_plus::Bound;
 use cw_utils::{parse_reply_instantiate_data, Duration};
-use dao_hooks::proposal::{new_proposal_hooks, proposal_status_changed_hooks};
+use dao_hooks::escalation::{proposal_escalated_hook, EscalationHookMsg};
+use dao_hooks::outcome::{proposal_passed_hooks, ProposalOutcomeHookMsg};
+use dao_hooks::proposal::{
+    new_proposal_hooks, proposal_status_changed_hooks, ProposalHookExecuteMsg, ProposalHookMsg,
+};
 use dao_hooks::vote::new_vote_hooks;
 use dao_interface::voting::IsActiveResponse;
 use dao_voting::pre_propose::{PreProposeInfo, ProposalCreationPolicy};
-use dao_voting::proposal::{
-    SingleChoiceProposeMsg as ProposeMsg, DEFAULT_LIMIT, MAX_PROPOSAL_SIZE,
-};
+use dao_voting::proposal::{SingleChoiceProposeMsg as ProposeMsg, MAX_PROPOSAL_SIZE};
 use dao_voting::reply::{
-    failed_pre_propose_module_hook_id, mask_proposal_execution_proposal_id, TaggedReplyId,
+    failed_pre_propose_module_hook_id, mask_previous_pre_propose_module_hook,
+    mask_proposal_execution_proposal_id, mask_subscription_id, TaggedReplyId,
 };
 use dao_voting::status::Status;
 use dao_voting::threshold::Threshold;
 use dao_voting::voting::{get_total_power, get_voting_power, validate_voting_period, Vote, Votes};
+use sha2::{Digest, Sha256};
 
 use crate::msg::MigrateMsg;
 use crate::proposal::{next_proposal_id, SingleChoiceProposal};
-use crate::state::{Config, CREATION_POLICY};
+use crate::state::{
+    BallotCap, Config, ConfigField, CriticalProposal, Discussion, OperationsAdmin, ProposalRebate,
+    QuorumSource, Subscription, SubscriptionFilter, VoteRebateConfig, ARCHIVED_PROPOSALS,
+    ARCHIVE_CURSOR, BALLOT_CAP, COUNCIL_MODE, CREATION_POLICY, CRITICAL_CONFIG, CRITICAL_PROPOSALS,
+    DISCUSSIONS, INSTANT_FINALITY, OPERATIONS_ADMIN, PENDING_PRE_PROPOSE_SWAP,
+    PREVIOUS_PRE_PROPOSE_MODULES, PROPOSAL_BALLOT_CAPS, PROPOSAL_REBATES, QUORUM_SOURCE,
+    REBATES_CLAIMED, SUBSCRIPTIONS, SUBSCRIPTION_COUNT, VOTE_REBATE,
+};
 
 use crate::v1_state::{
     v1_duration_to_v2, v1_expiration_to_v2, v1_status_to_v2, v1_threshold_to_v2, v1_votes_to_v2,
 };
 use crate::{
     error::ContractError,
-    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg, UncheckedCriticalConfig, UncheckedQuorumSource},
     proposal::advance_proposal_id,
     query::ProposalListResponse,
-    query::{ProposalResponse, VoteInfo, VoteListResponse, VoteResponse},
-    state::{Ballot, BALLOTS, CONFIG, PROPOSALS, PROPOSAL_COUNT, PROPOSAL_HOOKS, VOTE_HOOKS},
+    query::{
+        PendingVotesResponse, ProposalOutcomeResponse, ProposalResponse, SubscriptionResponse,
+        VoteInfo, VoteListResponse, VoteResponse,
+    },
+    state::{
+        Ballot, BALLOTS, CONFIG, OUTCOME_HOOKS, PROPOSALS, PROPOSAL_COUNT, PROPOSAL_HOOKS,
+        VOTE_HOOKS,
+    },
 };
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-proposal-single";
 pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// The maximum number of proposals examined by a `PendingVotes`
+/// query.
+const MAX_PENDING_VOTES_SCAN: usize = 100;
+
 /// Message type used for firing hooks to this module's pre-propose
 /// module, if one is installed.
 type PreProposeHookMsg = dao_pre_propose_base::msg::ExecuteMsg<Empty, Empty>;
+/// Message type used for querying this module's pre-propose module.
+type PreProposeQueryMsg = dao_pre_propose_base::msg::QueryMsg<Empty>;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -101,7 +126,31 @@ pub fn execute(
             description,
             msgs,
             proposer,
-        }) => execute_propose(deps, env, info.sender, title, description, msgs, proposer),
+        }) => execute_propose(
+            deps,
+            env,
+            info.sender,
+            title,
+            description,
+            msgs,
+            proposer,
+            false,
+        ),
+        ExecuteMsg::ProposeCritical(ProposeMsg {
+            title,
+            description,
+            msgs,
+            proposer,
+        }) => execute_propose(
+            deps,
+            env,
+            info.sender,
+            title,
+            description,
+            msgs,
+            proposer,
+            true,
+        ),
         ExecuteMsg::Vote {
             proposal_id,
             vote,
@@ -111,6 +160,11 @@ pub fn execute(
             proposal_id,
             rationale,
         } => execute_update_rationale(deps, info, proposal_id, rationale),
+        ExecuteMsg::SetDiscussion {
+            proposal_id,
+            uri,
+            freeze_hash,
+        } => execute_set_discussion(deps, env, info, proposal_id, uri, freeze_hash),
         ExecuteMsg::Execute { proposal_id } => execute_execute(deps, env, info, proposal_id),
         ExecuteMsg::Close { proposal_id } => execute_close(deps, env, info, proposal_id),
         ExecuteMsg::UpdateConfig {
@@ -135,6 +189,9 @@ pub fn execute(
         ExecuteMsg::UpdatePreProposeInfo { info: new_info } => {
             execute_update_proposal_creation_policy(deps, info, new_info)
         }
+        ExecuteMsg::SwapPreProposeModule { info: new_info } => {
+            execute_swap_pre_propose_module(deps, info, new_info)
+        }
         ExecuteMsg::AddProposalHook { address } => {
             execute_add_proposal_hook(deps, env, info, address)
         }
@@ -145,9 +202,91 @@ pub fn execute(
         ExecuteMsg::RemoveVoteHook { address } => {
             execute_remove_vote_hook(deps, env, info, address)
         }
+        ExecuteMsg::AddOutcomeHook { address } => {
+            execute_add_outcome_hook(deps, env, info, address)
+        }
+        ExecuteMsg::RemoveOutcomeHook { address } => {
+            execute_remove_outcome_hook(deps, env, info, address)
+        }
+        ExecuteMsg::AddSubscription { address, filter } => {
+            execute_add_subscription(deps, info, address, filter)
+        }
+        ExecuteMsg::RemoveSubscription { id } => execute_remove_subscription(deps, info, id),
+        ExecuteMsg::UpdateQuorumSource { quorum_source } => {
+            execute_update_quorum_source(deps, info, quorum_source)
+        }
+        ExecuteMsg::UpdateVoteRebate { rebate } => execute_update_vote_rebate(deps, info, rebate),
+        ExecuteMsg::ClaimVoteRebate { proposal_id } => {
+            execute_claim_vote_rebate(deps, env, info, proposal_id)
+        }
+        ExecuteMsg::WithdrawRebatePool { amount } => {
+            execute_withdraw_rebate_pool(deps, info, amount)
+        }
+        ExecuteMsg::UpdateBallotCap { cap } => execute_update_ballot_cap(deps, info, cap),
+        ExecuteMsg::Archive {
+            older_than_height,
+            limit,
+        } => execute_archive(deps, info, older_than_height, limit),
+        ExecuteMsg::UpdateCouncilMode { approvals } => {
+            execute_update_council_mode(deps, info, approvals)
+        }
+        ExecuteMsg::UpdateInstantFinality { enabled } => {
+            execute_update_instant_finality(deps, info, enabled)
+        }
+        ExecuteMsg::UpdateThreshold { threshold } => {
+            execute_update_config_field(deps, info, ConfigField::Threshold, |config| {
+                threshold.validate()?;
+                config.threshold = threshold;
+                Ok(())
+            })
+        }
+        ExecuteMsg::UpdateVotingPeriod {
+            min_voting_period,
+            max_voting_period,
+        } => execute_update_config_field(deps, info, ConfigField::VotingPeriod, |config| {
+            let (min_voting_period, max_voting_period) =
+                validate_voting_period(min_voting_period, max_voting_period)?;
+            config.min_voting_period = min_voting_period;
+            config.max_voting_period = max_voting_period;
+            Ok(())
+        }),
+        ExecuteMsg::UpdateOnlyMembersExecute {
+            only_members_execute,
+        } => execute_update_config_field(deps, info, ConfigField::OnlyMembersExecute, |config| {
+            config.only_members_execute = only_members_execute;
+            Ok(())
+        }),
+        ExecuteMsg::UpdateAllowRevoting { allow_revoting } => {
+            execute_update_config_field(deps, info, ConfigField::AllowRevoting, |config| {
+                config.allow_revoting = allow_revoting;
+                Ok(())
+            })
+        }
+        ExecuteMsg::UpdateCloseProposalOnExecutionFailure {
+            close_proposal_on_execution_failure,
+        } => execute_update_config_field(
+            deps,
+            info,
+            ConfigField::CloseProposalOnExecutionFailure,
+            |config| {
+                config.close_proposal_on_execution_failure = close_proposal_on_execution_failure;
+                Ok(())
+            },
+        ),
+        ExecuteMsg::UpdateOperationsAdmin { address, fields } => {
+            execute_update_operations_admin(deps, info, address, fields)
+        }
+        ExecuteMsg::UpdateCriticalConfig { config } => {
+            execute_update_critical_config(deps, info, config)
+        }
+        ExecuteMsg::Escalate { proposal_id } => execute_escalate(deps, env, info, proposal_id),
+        ExecuteMsg::DecideEscalated { proposal_id, pass } => {
+            execute_decide_escalated(deps, env, info, proposal_id, pass)
+        }
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn execute_propose(
     deps: DepsMut,
     env: Env,
@@ -156,12 +295,27 @@ pub fn execute_propose(
     description: String,
     msgs: Vec<CosmosMsg<Empty>>,
     proposer: Option<String>,
+    critical: bool,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
 
+    // Critical proposals are created directly by one of the critical
+    // config's proposers, bypassing the proposal creation policy.
+    let critical_config = if critical {
+        let critical_config = CRITICAL_CONFIG
+            .may_load(deps.storage)?
+            .ok_or(ContractError::CriticalProposalsDisabled {})?;
+        if !critical_config.proposers.contains(&sender) {
+            return Err(ContractError::Unauthorized {});
+        }
+        Some(critical_config)
+    } else {
+        None
+    };
+
     // Check that the sender is permitted to create proposals.
-    if !proposal_creation_policy.is_permitted(&sender) {
+    if critical_config.is_none() && !proposal_creation_policy.is_permitted(&sender) {
         return Err(ContractError::Unauthorized {});
     }
 
@@ -169,10 +323,11 @@ pub fn execute_propose(
     // pre-propose module, it must be specified. Otherwise, the
     // proposer should not be specified.
     let proposer = match (proposer, &proposal_creation_policy) {
+        (None, _) if critical => sender.clone(),
         (None, ProposalCreationPolicy::Anyone {}) => sender.clone(),
         // `is_permitted` above checks that an allowed module is
         // actually sending the propose message.
-        (Some(proposer), ProposalCreationPolicy::Module { .. }) => {
+        (Some(proposer), ProposalCreationPolicy::Module { .. }) if !critical => {
             deps.api.addr_validate(&proposer)?
         }
         _ => return Err(ContractError::InvalidProposer {}),
@@ -194,9 +349,21 @@ pub fn execute_propose(
         return Err(ContractError::InactiveDao {});
     }
 
-    let expiration = config.max_voting_period.after(&env.block);
+    let (min_voting_period, expiration) = match &critical_config {
+        Some(critical_config) => (None, critical_config.voting_period.after(&env.block)),
+        None => (
+            config.min_voting_period.map(|min| min.after(&env.block)),
+            config.max_voting_period.after(&env.block),
+        ),
+    };
 
     let total_power = get_total_power(deps.as_ref(), &config.dao, Some(env.block.height))?;
+    let quorum_power = match QUORUM_SOURCE.may_load(deps.storage)? {
+        Some(quorum_source) => {
+            quorum_source.quorum_power(&deps.querier, total_power, env.block.height)?
+        }
+        None => None,
+    };
 
     let proposal = {
         // Limit mutability to this block.
@@ -205,14 +372,16 @@ pub fn execute_propose(
             description,
             proposer: proposer.clone(),
             start_height: env.block.height,
-            min_voting_period: config.min_voting_period.map(|min| min.after(&env.block)),
+            min_voting_period,
             expiration,
             threshold: config.threshold,
             total_power,
+            quorum_power,
             msgs,
             status: Status::Open,
             votes: Votes::zero(),
             allow_revoting: config.allow_revoting,
+            instant_finality: INSTANT_FINALITY.may_load(deps.storage)?.is_some(),
         };
         // Update the proposal's status. Addresses case where proposal
         // expires on the same block as it is created.
@@ -245,14 +414,57 @@ pub fn execute_propose(
 
     PROPOSALS.save(deps.storage, id, &proposal)?;
 
-    let hooks = new_proposal_hooks(PROPOSAL_HOOKS, deps.storage, id, proposer.as_str())?;
+    if let Some(rebate) = VOTE_REBATE.may_load(deps.storage)? {
+        PROPOSAL_REBATES.save(
+            deps.storage,
+            id,
+            &ProposalRebate {
+                remaining: rebate.proposal_budget,
+                config: rebate,
+            },
+        )?;
+    }
+
+    if let Some(cap) = BALLOT_CAP.may_load(deps.storage)? {
+        PROPOSAL_BALLOT_CAPS.save(deps.storage, id, &cap)?;
+    }
+
+    if let Some(critical_config) = critical_config {
+        // Critical proposals that have not reached quorum halfway
+        // through their voting period may be escalated.
+        let half = match critical_config.voting_period {
+            Duration::Height(blocks) => Duration::Height(blocks / 2),
+            Duration::Time(seconds) => Duration::Time(seconds / 2),
+        };
+        CRITICAL_PROPOSALS.save(
+            deps.storage,
+            id,
+            &CriticalProposal {
+                council: critical_config.council,
+                escalate_at: half.after(&env.block),
+                escalated: false,
+            },
+        )?;
+    }
+
+    let mut hooks = new_proposal_hooks(PROPOSAL_HOOKS, deps.storage, id, proposer.as_str())?;
+    hooks.extend(subscription_hooks(
+        deps.storage,
+        &env,
+        &proposal,
+        ProposalHookMsg::NewProposal {
+            id,
+            proposer: proposer.to_string(),
+        },
+    )?);
 
     Ok(Response::default()
         .add_submessages(hooks)
         .add_attribute("action", "propose")
         .add_attribute("sender", sender)
         .add_attribute("proposal_id", id.to_string())
-        .add_attribute("status", proposal.status.to_string()))
+        .add_attribute("status", proposal.status.to_string())
+        .add_attribute("critical", critical.to_string()))
 }
 
 pub fn execute_execute(
@@ -266,7 +478,9 @@ pub fn execute_execute(
         .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
 
     let config = CONFIG.load(deps.storage)?;
-    if config.only_members_execute {
+    // In council mode this module executes proposals itself when they
+    // pass.
+    if config.only_members_execute && info.sender != env.contract.address {
         let power = get_voting_power(
             deps.as_ref(),
             info.sender.clone(),
@@ -313,35 +527,34 @@ pub fn execute_execute(
         }
     };
 
-    let hooks = proposal_status_changed_hooks(
+    let mut hooks = proposal_status_changed_hooks(
         PROPOSAL_HOOKS,
         deps.storage,
         proposal_id,
         old_status.to_string(),
         prop.status.to_string(),
     )?;
+    hooks.extend(status_subscription_hooks(
+        deps.storage,
+        &env,
+        proposal_id,
+        &prop,
+        old_status,
+    )?);
+
+    // If the proposal's stored status was not yet passed, it passed
+    // by expiring and outcome hooks have not been fired for it.
+    if old_status != Status::Passed {
+        hooks.extend(outcome_hooks(deps.storage, proposal_id, &prop)?);
+    }
 
     // Add prepropose / deposit module hook which will handle deposit refunds.
-    let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
-    let hooks = match proposal_creation_policy {
-        ProposalCreationPolicy::Anyone {} => hooks,
-        ProposalCreationPolicy::Module { addr } => {
-            let msg = to_json_binary(&PreProposeHookMsg::ProposalCompletedHook {
-                proposal_id,
-                new_status: prop.status,
-            })?;
-            let mut hooks = hooks;
-            hooks.push(SubMsg::reply_on_error(
-                WasmMsg::Execute {
-                    contract_addr: addr.into_string(),
-                    msg,
-                    funds: vec![],
-                },
-                failed_pre_propose_module_hook_id(),
-            ));
-            hooks
-        }
-    };
+    let mut hooks = hooks;
+    hooks.extend(pre_propose_completed_hook(
+        deps.storage,
+        proposal_id,
+        prop.status,
+    )?);
 
     Ok(response
         .add_submessages(hooks)
@@ -385,9 +598,19 @@ pub fn execute_vote(
         return Err(ContractError::NotRegistered {});
     }
 
+    // Power above the ballot cap is abstained or discarded. As a
+    // voter's power and the cap are fixed for the proposal, a revote
+    // leaves the abstained excess unchanged.
+    let (vote_power, abstained) = match PROPOSAL_BALLOT_CAPS.may_load(deps.storage, proposal_id)? {
+        Some(cap) => cap.apply(vote_power, prop.total_power),
+        None => (vote_power, Uint128::zero()),
+    };
+    let first_ballot = !BALLOTS.has(deps.storage, (proposal_id, &info.sender));
+    let can_revote = prop.allow_revoting && !prop.is_final();
+
     BALLOTS.update(deps.storage, (proposal_id, &info.sender), |bal| match bal {
         Some(current_ballot) => {
-            if prop.allow_revoting {
+            if can_revote {
                 if current_ballot.vote == vote {
                     // Don't allow casting the same vote more than
                     // once. This seems liable to be confusing
@@ -420,18 +643,34 @@ pub fn execute_vote(
     let old_status = prop.status;
 
     prop.votes.add_vote(vote, vote_power);
+    if first_ballot {
+        prop.votes.add_vote(Vote::Abstain, abstained);
+    }
     prop.update_status(&env.block);
 
     PROPOSALS.save(deps.storage, proposal_id, &prop)?;
 
     let new_status = prop.status;
-    let change_hooks = proposal_status_changed_hooks(
+    let mut change_hooks = proposal_status_changed_hooks(
         PROPOSAL_HOOKS,
         deps.storage,
         proposal_id,
         old_status.to_string(),
         new_status.to_string(),
     )?;
+    change_hooks.extend(status_subscription_hooks(
+        deps.storage,
+        &env,
+        proposal_id,
+        &prop,
+        old_status,
+    )?);
+
+    let outcome_hooks = if old_status != Status::Passed && new_status == Status::Passed {
+        outcome_hooks(deps.storage, proposal_id, &prop)?
+    } else {
+        vec![]
+    };
 
     let vote_hooks = new_vote_hooks(
         VOTE_HOOKS,
@@ -441,9 +680,24 @@ pub fn execute_vote(
         vote.to_string(),
     )?;
 
+    let auto_execute = if old_status != Status::Passed
+        && new_status == Status::Passed
+        && COUNCIL_MODE.may_load(deps.storage)?.is_some()
+    {
+        vec![WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            msg: to_json_binary(&ExecuteMsg::Execute { proposal_id })?,
+            funds: vec![],
+        }]
+    } else {
+        vec![]
+    };
+
     Ok(Response::default()
         .add_submessages(change_hooks)
+        .add_submessages(outcome_hooks)
         .add_submessages(vote_hooks)
+        .add_messages(auto_execute)
         .add_attribute("action", "vote")
         .add_attribute("sender", info.sender)
         .add_attribute("proposal_id", proposal_id.to_string())
@@ -482,6 +736,45 @@ pub fn execute_update_rationale(
         .add_attribute("rationale", rationale.as_deref().unwrap_or("_none")))
 }
 
+pub fn execute_set_discussion(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    uri: String,
+    freeze_hash: Option<HexBinary>,
+) -> Result<Response, ContractError> {
+    let prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    if prop.proposer != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if prop.current_status(&env.block) != Status::Open {
+        return Err(ContractError::NotOpen {});
+    }
+    if !prop.votes.total().is_zero() {
+        return Err(ContractError::VotingStarted {});
+    }
+    if DISCUSSIONS.has(deps.storage, proposal_id) {
+        return Err(ContractError::DiscussionSet { id: proposal_id });
+    }
+
+    let response = Response::default()
+        .add_attribute("action", "set_discussion")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("uri", &uri)
+        .add_attribute(
+            "freeze_hash",
+            freeze_hash
+                .as_ref()
+                .map_or_else(|| "_none".to_string(), |hash| hash.to_hex()),
+        );
+    DISCUSSIONS.save(deps.storage, proposal_id, &Discussion { uri, freeze_hash })?;
+
+    Ok(response)
+}
+
 pub fn execute_close(
     deps: DepsMut,
     env: Env,
@@ -502,35 +795,28 @@ pub fn execute_close(
     prop.status = Status::Closed;
     PROPOSALS.save(deps.storage, proposal_id, &prop)?;
 
-    let hooks = proposal_status_changed_hooks(
+    let mut hooks = proposal_status_changed_hooks(
         PROPOSAL_HOOKS,
         deps.storage,
         proposal_id,
         old_status.to_string(),
         prop.status.to_string(),
     )?;
+    hooks.extend(status_subscription_hooks(
+        deps.storage,
+        &env,
+        proposal_id,
+        &prop,
+        old_status,
+    )?);
 
     // Add prepropose / deposit module hook which will handle deposit refunds.
-    let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
-    let hooks = match proposal_creation_policy {
-        ProposalCreationPolicy::Anyone {} => hooks,
-        ProposalCreationPolicy::Module { addr } => {
-            let msg = to_json_binary(&PreProposeHookMsg::ProposalCompletedHook {
-                proposal_id,
-                new_status: prop.status,
-            })?;
-            let mut hooks = hooks;
-            hooks.push(SubMsg::reply_on_error(
-                WasmMsg::Execute {
-                    contract_addr: addr.into_string(),
-                    msg,
-                    funds: vec![],
-                },
-                failed_pre_propose_module_hook_id(),
-            ));
-            hooks
-        }
-    };
+    let mut hooks = hooks;
+    hooks.extend(pre_propose_completed_hook(
+        deps.storage,
+        proposal_id,
+        prop.status,
+    )?);
 
     Ok(Response::default()
         .add_submessages(hooks)
@@ -563,167 +849,930 @@ pub fn execute_update_config(
     let (min_voting_period, max_voting_period) =
         validate_voting_period(min_voting_period, max_voting_period)?;
 
-    CONFIG.save(
-        deps.storage,
-        &Config {
-            threshold,
-            max_voting_period,
-            min_voting_period,
-            only_members_execute,
-            allow_revoting,
-            dao,
-            close_proposal_on_execution_failure,
-        },
-    )?;
+    let updated = Config {
+        threshold,
+        max_voting_period,
+        min_voting_period,
+        only_members_execute,
+        allow_revoting,
+        dao,
+        close_proposal_on_execution_failure,
+    };
+    assert_council_settings_unchanged(deps.storage, &config, &updated)?;
+    CONFIG.save(deps.storage, &updated)?;
 
     Ok(Response::default()
         .add_attribute("action", "update_config")
         .add_attribute("sender", info.sender))
 }
 
-pub fn execute_update_proposal_creation_policy(
+/// Updates a single FIELD of the config with UPDATE. The DAO may
+/// update any field, and the operations admin the fields it has been
+/// permitted to.
+pub fn execute_update_config_field(
     deps: DepsMut,
     info: MessageInfo,
-    new_info: PreProposeInfo,
+    field: ConfigField,
+    update: impl FnOnce(&mut Config) -> Result<(), ContractError>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
-    if config.dao != info.sender {
-        return Err(ContractError::Unauthorized {});
+    if info.sender != config.dao {
+        let permitted = OPERATIONS_ADMIN
+            .may_load(deps.storage)?
+            .map_or(false, |admin| {
+                admin.address == info.sender && admin.fields.contains(&field)
+            });
+        if !permitted {
+            return Err(ContractError::Unauthorized {});
+        }
     }
 
-    let (initial_policy, messages) = new_info.into_initial_policy_and_messages(config.dao)?;
-    CREATION_POLICY.save(deps.storage, &initial_policy)?;
+    let mut updated = config.clone();
+    update(&mut updated)?;
+    assert_council_settings_unchanged(deps.storage, &config, &updated)?;
+    CONFIG.save(deps.storage, &updated)?;
 
     Ok(Response::default()
-        .add_submessages(messages)
-        .add_attribute("action", "update_proposal_creation_policy")
-        .add_attribute("sender", info.sender)
-        .add_attribute("new_policy", format!("{initial_policy:?}")))
-}
-
-pub fn add_hook(
-    hooks: Hooks,
-    storage: &mut dyn Storage,
-    validated_address: Addr,
-) -> Result<(), ContractError> {
-    hooks
-        .add_hook(storage, validated_address)
-        .map_err(ContractError::HookError)?;
-    Ok(())
+        .add_attribute("action", "update_config_field")
+        .add_attribute("field", field.to_string())
+        .add_attribute("sender", info.sender))
 }
 
-pub fn remove_hook(
-    hooks: Hooks,
-    storage: &mut dyn Storage,
-    validate_address: Addr,
+/// Council mode manages the threshold, minimum voting period,
+/// revoting, and execution failure settings, restoring them once it
+/// is disabled, so they may not be changed while it is enabled.
+fn assert_council_settings_unchanged(
+    storage: &dyn Storage,
+    config: &Config,
+    updated: &Config,
 ) -> Result<(), ContractError> {
-    hooks
-        .remove_hook(storage, validate_address)
-        .map_err(ContractError::HookError)?;
+    let changed = config.threshold != updated.threshold
+        || config.min_voting_period != updated.min_voting_period
+        || config.allow_revoting != updated.allow_revoting
+        || config.close_proposal_on_execution_failure
+            != updated.close_proposal_on_execution_failure;
+    if changed && COUNCIL_MODE.may_load(storage)?.is_some() {
+        return Err(ContractError::CouncilModeEnabled {});
+    }
     Ok(())
 }
 
-pub fn execute_add_proposal_hook(
+pub fn execute_update_operations_admin(
     deps: DepsMut,
-    _env: Env,
     info: MessageInfo,
-    address: String,
+    address: Option<String>,
+    fields: Vec<ConfigField>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
-    if config.dao != info.sender {
-        // Only DAO can add hooks
+    if info.sender != config.dao {
         return Err(ContractError::Unauthorized {});
     }
 
-    let validated_address = deps.api.addr_validate(&address)?;
-
-    add_hook(PROPOSAL_HOOKS, deps.storage, validated_address)?;
+    let admin = address
+        .map(|address| deps.api.addr_validate(&address))
+        .transpose()?;
+    match &admin {
+        Some(address) => OPERATIONS_ADMIN.save(
+            deps.storage,
+            &OperationsAdmin {
+                address: address.clone(),
+                fields,
+            },
+        )?,
+        None => OPERATIONS_ADMIN.remove(deps.storage),
+    }
 
     Ok(Response::default()
-        .add_attribute("action", "add_proposal_hook")
-        .add_attribute("address", address))
+        .add_attribute("action", "update_operations_admin")
+        .add_attribute(
+            "operations_admin",
+            admin
+                .map(|a| a.into_string())
+                .unwrap_or_else(|| "None".to_string()),
+        ))
 }
 
-pub fn execute_remove_proposal_hook(
+pub fn execute_update_critical_config(
     deps: DepsMut,
-    _env: Env,
     info: MessageInfo,
-    address: String,
+    critical_config: Option<UncheckedCriticalConfig>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
-    if config.dao != info.sender {
-        // Only DAO can remove hooks
+    if info.sender != config.dao {
         return Err(ContractError::Unauthorized {});
     }
 
-    let validated_address = deps.api.addr_validate(&address)?;
+    match critical_config {
+        Some(critical_config) => {
+            let critical_config = critical_config.into_checked(deps.api)?;
+            let zero = match critical_config.voting_period {
+                Duration::Height(blocks) => blocks == 0,
+                Duration::Time(seconds) => seconds == 0,
+            };
+            if zero || critical_config.proposers.is_empty() {
+                return Err(ContractError::InvalidCriticalConfig {});
+            }
+            validate_voting_period(
+                Some(critical_config.voting_period),
+                config.max_voting_period,
+            )?;
+            CRITICAL_CONFIG.save(deps.storage, &critical_config)?;
+        }
+        None => CRITICAL_CONFIG.remove(deps.storage),
+    }
 
-    remove_hook(PROPOSAL_HOOKS, deps.storage, validated_address)?;
+    Ok(Response::default().add_attribute("action", "update_critical_config"))
+}
+
+pub fn execute_escalate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let mut prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    let mut critical = CRITICAL_PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NotCritical { id: proposal_id })?;
+
+    if critical.escalated {
+        return Err(ContractError::AlreadyEscalated {});
+    }
+    prop.update_status(&env.block);
+    if prop.status != Status::Open {
+        return Err(ContractError::NotOpen {});
+    }
+    if !critical.escalate_at.is_expired(&env.block) {
+        return Err(ContractError::EscalationNotDue {});
+    }
+    if prop.reached_quorum() {
+        return Err(ContractError::QuorumReached {});
+    }
+
+    critical.escalated = true;
+    CRITICAL_PROPOSALS.save(deps.storage, proposal_id, &critical)?;
+
+    let hook = proposal_escalated_hook(
+        &critical.council,
+        EscalationHookMsg::ProposalEscalated { id: proposal_id },
+    )?;
 
     Ok(Response::default()
-        .add_attribute("action", "remove_proposal_hook")
-        .add_attribute("address", address))
+        .add_message(hook)
+        .add_attribute("action", "escalate")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("council", critical.council))
 }
 
-pub fn execute_add_vote_hook(
+pub fn execute_decide_escalated(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
-    address: String,
+    proposal_id: u64,
+    pass: bool,
 ) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
-    if config.dao != info.sender {
-        // Only DAO can add hooks
+    let mut prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    let critical = CRITICAL_PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NotCritical { id: proposal_id })?;
+
+    if !critical.escalated {
+        return Err(ContractError::NotEscalated {});
+    }
+    if info.sender != critical.council {
         return Err(ContractError::Unauthorized {});
     }
 
-    let validated_address = deps.api.addr_validate(&address)?;
+    let old_status = prop.status;
+    prop.update_status(&env.block);
+    if prop.status != Status::Open {
+        return Err(ContractError::NotOpen {});
+    }
+    prop.status = if pass {
+        Status::Passed
+    } else {
+        Status::Rejected
+    };
+    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
 
-    add_hook(VOTE_HOOKS, deps.storage, validated_address)?;
+    let mut change_hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        proposal_id,
+        old_status.to_string(),
+        prop.status.to_string(),
+    )?;
+    change_hooks.extend(status_subscription_hooks(
+        deps.storage,
+        &env,
+        proposal_id,
+        &prop,
+        old_status,
+    )?);
+    let outcome_hooks = if pass {
+        outcome_hooks(deps.storage, proposal_id, &prop)?
+    } else {
+        vec![]
+    };
 
     Ok(Response::default()
-        .add_attribute("action", "add_vote_hook")
-        .add_attribute("address", address))
+        .add_submessages(change_hooks)
+        .add_submessages(outcome_hooks)
+        .add_attribute("action", "decide_escalated")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("status", prop.status.to_string()))
 }
 
-pub fn execute_remove_vote_hook(
+pub fn execute_update_proposal_creation_policy(
     deps: DepsMut,
-    _env: Env,
     info: MessageInfo,
-    address: String,
+    new_info: PreProposeInfo,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     if config.dao != info.sender {
-        // Only DAO can remove hooks
         return Err(ContractError::Unauthorized {});
     }
 
-    let validated_address = deps.api.addr_validate(&address)?;
-
-    remove_hook(VOTE_HOOKS, deps.storage, validated_address)?;
+    let (initial_policy, messages) = new_info.into_initial_policy_and_messages(config.dao)?;
+    CREATION_POLICY.save(deps.storage, &initial_policy)?;
 
     Ok(Response::default()
-        .add_attribute("action", "remove_vote_hook")
+        .add_submessages(messages)
+        .add_attribute("action", "update_proposal_creation_policy")
+        .add_attribute("sender", info.sender)
+        .add_attribute("new_policy", format!("{initial_policy:?}")))
+}
+
+pub fn execute_swap_pre_propose_module(
+    deps: DepsMut,
+    info: MessageInfo,
+    new_info: PreProposeInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let previous = match CREATION_POLICY.load(deps.storage)? {
+        ProposalCreationPolicy::Anyone {} => return Err(ContractError::NoPreProposeModule {}),
+        ProposalCreationPolicy::Module { addr } => addr,
+    };
+    // If no proposals have been created since the last swap, the
+    // module replaced then holds the deposits of the proposals up to
+    // this ID and the module being replaced now holds none.
+    let last_proposal_id = PROPOSAL_COUNT.may_load(deps.storage)?.unwrap_or_default();
+    if !PREVIOUS_PRE_PROPOSE_MODULES.has(deps.storage, last_proposal_id) {
+        PREVIOUS_PRE_PROPOSE_MODULES.save(deps.storage, last_proposal_id, &previous)?;
+    }
+
+    let (initial_policy, messages) = new_info.into_initial_policy_and_messages(config.dao)?;
+    CREATION_POLICY.save(deps.storage, &initial_policy)?;
+    if !messages.is_empty() {
+        PENDING_PRE_PROPOSE_SWAP.save(deps.storage, &Empty {})?;
+    }
+
+    Ok(Response::default()
+        .add_submessages(messages)
+        .add_attribute("action", "swap_pre_propose_module")
+        .add_attribute("sender", info.sender)
+        .add_attribute("previous_module", previous)
+        .add_attribute("last_proposal_id", last_proposal_id.to_string()))
+}
+
+/// Returns the hook informing a pre-propose module that PROPOSAL_ID
+/// has completed, if there is a module to inform. Proposals created
+/// before a `SwapPreProposeModule` are sent to the module that was
+/// replaced, as it holds their deposits.
+fn pre_propose_completed_hook(
+    storage: &dyn Storage,
+    proposal_id: u64,
+    new_status: Status,
+) -> StdResult<Option<SubMsg>> {
+    let msg = to_json_binary(&PreProposeHookMsg::ProposalCompletedHook {
+        proposal_id,
+        new_status,
+    })?;
+    let previous = PREVIOUS_PRE_PROPOSE_MODULES
+        .range(
+            storage,
+            Some(Bound::inclusive(proposal_id)),
+            None,
+            Order::Ascending,
+        )
+        .next()
+        .transpose()?;
+    let (addr, reply_id) = match previous {
+        Some((_, addr)) => (addr, mask_previous_pre_propose_module_hook(proposal_id)),
+        None => match CREATION_POLICY.load(storage)? {
+            ProposalCreationPolicy::Anyone {} => return Ok(None),
+            ProposalCreationPolicy::Module { addr } => (addr, failed_pre_propose_module_hook_id()),
+        },
+    };
+    Ok(Some(SubMsg::reply_on_error(
+        WasmMsg::Execute {
+            contract_addr: addr.into_string(),
+            msg,
+            funds: vec![],
+        },
+        reply_id,
+    )))
+}
+
+pub fn execute_update_quorum_source(
+    deps: DepsMut,
+    info: MessageInfo,
+    quorum_source: UncheckedQuorumSource,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let quorum_source = quorum_source.into_checked(deps.api)?;
+    QUORUM_SOURCE.save(deps.storage, &quorum_source)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "update_quorum_source")
+        .add_attribute("sender", info.sender)
+        .add_attribute("quorum_source", format!("{quorum_source:?}")))
+}
+
+pub fn execute_update_vote_rebate(
+    deps: DepsMut,
+    info: MessageInfo,
+    rebate: Option<VoteRebateConfig>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match rebate {
+        Some(rebate) => {
+            if rebate.amount.is_zero() || rebate.amount > rebate.proposal_budget {
+                return Err(ContractError::InvalidVoteRebate {});
+            }
+            VOTE_REBATE.save(deps.storage, &rebate)?;
+            Ok(Response::default()
+                .add_attribute("action", "update_vote_rebate")
+                .add_attribute("denom", rebate.denom)
+                .add_attribute("amount", rebate.amount)
+                .add_attribute("proposal_budget", rebate.proposal_budget)
+                .add_attribute("min_power", rebate.min_power))
+        }
+        None => {
+            VOTE_REBATE.remove(deps.storage);
+            Ok(Response::default()
+                .add_attribute("action", "update_vote_rebate")
+                .add_attribute("denom", "none"))
+        }
+    }
+}
+
+pub fn execute_update_ballot_cap(
+    deps: DepsMut,
+    info: MessageInfo,
+    cap: Option<BallotCap>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match cap {
+        Some(cap) => {
+            if cap.max_share.is_zero() || cap.max_share > Decimal::one() {
+                return Err(ContractError::InvalidBallotCap {});
+            }
+            BALLOT_CAP.save(deps.storage, &cap)?;
+            Ok(Response::default()
+                .add_attribute("action", "update_ballot_cap")
+                .add_attribute("max_share", cap.max_share.to_string()))
+        }
+        None => {
+            BALLOT_CAP.remove(deps.storage);
+            Ok(Response::default()
+                .add_attribute("action", "update_ballot_cap")
+                .add_attribute("max_share", "none"))
+        }
+    }
+}
+
+pub fn execute_update_council_mode(
+    deps: DepsMut,
+    info: MessageInfo,
+    approvals: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match approvals {
+        Some(approvals) => {
+            let threshold = Threshold::AbsoluteCount {
+                threshold: approvals,
+            };
+            threshold.validate()?;
+            // Keep the config from before council mode was first
+            // enabled so that it may be restored once disabled.
+            let prior = match COUNCIL_MODE.may_load(deps.storage)? {
+                Some(prior) => prior,
+                None => config.clone(),
+            };
+            config.threshold = threshold;
+            config.min_voting_period = None;
+            config.allow_revoting = false;
+            // A failed execution would otherwise revert the vote that
+            // passed the proposal.
+            config.close_proposal_on_execution_failure = true;
+            CONFIG.save(deps.storage, &config)?;
+            COUNCIL_MODE.save(deps.storage, &prior)?;
+            Ok(Response::default()
+                .add_attribute("action", "update_council_mode")
+                .add_attribute("approvals", approvals))
+        }
+        None => {
+            if let Some(prior) = COUNCIL_MODE.may_load(deps.storage)? {
+                config.threshold = prior.threshold;
+                config.min_voting_period = prior.min_voting_period;
+                config.allow_revoting = prior.allow_revoting;
+                config.close_proposal_on_execution_failure =
+                    prior.close_proposal_on_execution_failure;
+                CONFIG.save(deps.storage, &config)?;
+                COUNCIL_MODE.remove(deps.storage);
+            }
+            Ok(Response::default()
+                .add_attribute("action", "update_council_mode")
+                .add_attribute("approvals", "none"))
+        }
+    }
+}
+
+pub fn execute_update_instant_finality(
+    deps: DepsMut,
+    info: MessageInfo,
+    enabled: bool,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if enabled {
+        INSTANT_FINALITY.save(deps.storage, &Empty {})?;
+    } else {
+        INSTANT_FINALITY.remove(deps.storage);
+    }
+    Ok(Response::default()
+        .add_attribute("action", "update_instant_finality")
+        .add_attribute("enabled", enabled.to_string()))
+}
+
+pub fn execute_claim_vote_rebate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    let mut rebate = PROPOSAL_REBATES
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoVoteRebate { id: proposal_id })?;
+
+    // Votes may be cast until the proposal expires, even if it has
+    // already passed or been rejected.
+    if !prop.expiration.is_expired(&env.block) {
+        return Err(ContractError::RebateVotingOpen {});
+    }
+    if !prop.reached_quorum() {
+        return Err(ContractError::RebateQuorumNotReached {});
+    }
+
+    let ballot = BALLOTS
+        .may_load(deps.storage, (proposal_id, &info.sender))?
+        .ok_or_else(|| ContractError::NoSuchVote {
+            id: proposal_id,
+            voter: info.sender.to_string(),
+        })?;
+    if ballot.power < rebate.config.min_power {
+        return Err(ContractError::RebatePowerTooLow {});
+    }
+    if REBATES_CLAIMED.has(deps.storage, (proposal_id, &info.sender)) {
+        return Err(ContractError::RebateClaimed {});
+    }
+    if rebate.remaining < rebate.config.amount {
+        return Err(ContractError::RebateBudgetExhausted {});
+    }
+
+    let pool = deps
+        .querier
+        .query_balance(&env.contract.address, &rebate.config.denom)?;
+    if pool.amount < rebate.config.amount {
+        return Err(ContractError::RebatePoolEmpty {
+            denom: rebate.config.denom,
+        });
+    }
+
+    rebate.remaining -= rebate.config.amount;
+    PROPOSAL_REBATES.save(deps.storage, proposal_id, &rebate)?;
+    REBATES_CLAIMED.save(deps.storage, (proposal_id, &info.sender), &Empty {})?;
+
+    Ok(Response::default()
+        .add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: vec![Coin {
+                denom: rebate.config.denom,
+                amount: rebate.config.amount,
+            }],
+        })
+        .add_attribute("action", "claim_vote_rebate")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("amount", rebate.config.amount))
+}
+
+pub fn execute_withdraw_rebate_pool(
+    deps: DepsMut,
+    info: MessageInfo,
+    amount: Coin,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "withdraw_rebate_pool")
+        .add_attribute("amount", amount.to_string())
+        .add_message(BankMsg::Send {
+            to_address: config.dao.into_string(),
+            amount: vec![amount],
+        }))
+}
+
+pub fn add_hook(
+    hooks: Hooks,
+    storage: &mut dyn Storage,
+    validated_address: Addr,
+) -> Result<(), ContractError> {
+    hooks
+        .add_hook(storage, validated_address)
+        .map_err(ContractError::HookError)?;
+    Ok(())
+}
+
+pub fn remove_hook(
+    hooks: Hooks,
+    storage: &mut dyn Storage,
+    validate_address: Addr,
+) -> Result<(), ContractError> {
+    hooks
+        .remove_hook(storage, validate_address)
+        .map_err(ContractError::HookError)?;
+    Ok(())
+}
+
+pub fn execute_add_proposal_hook(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        // Only DAO can add hooks
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let validated_address = deps.api.addr_validate(&address)?;
+
+    add_hook(PROPOSAL_HOOKS, deps.storage, validated_address)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "add_proposal_hook")
         .add_attribute("address", address))
 }
 
+pub fn execute_remove_proposal_hook(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        // Only DAO can remove hooks
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let validated_address = deps.api.addr_validate(&address)?;
+
+    remove_hook(PROPOSAL_HOOKS, deps.storage, validated_address)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "remove_proposal_hook")
+        .add_attribute("address", address))
+}
+
+pub fn execute_add_vote_hook(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        // Only DAO can add hooks
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let validated_address = deps.api.addr_validate(&address)?;
+
+    add_hook(VOTE_HOOKS, deps.storage, validated_address)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "add_vote_hook")
+        .add_attribute("address", address))
+}
+
+pub fn execute_remove_vote_hook(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        // Only DAO can remove hooks
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let validated_address = deps.api.addr_validate(&address)?;
+
+    remove_hook(VOTE_HOOKS, deps.storage, validated_address)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "remove_vote_hook")
+        .add_attribute("address", address))
+}
+
+pub fn execute_add_outcome_hook(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        // Only DAO can add hooks
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let validated_address = deps.api.addr_validate(&address)?;
+
+    add_hook(OUTCOME_HOOKS, deps.storage, validated_address)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "add_outcome_hook")
+        .add_attribute("address", address))
+}
+
+pub fn execute_remove_outcome_hook(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        // Only DAO can remove hooks
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let validated_address = deps.api.addr_validate(&address)?;
+
+    remove_hook(OUTCOME_HOOKS, deps.storage, validated_address)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "remove_outcome_hook")
+        .add_attribute("address", address))
+}
+
+pub fn execute_archive(
+    deps: DepsMut,
+    info: MessageInfo,
+    older_than_height: u64,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let limit = clamp_limit(limit);
+    let start = ARCHIVE_CURSOR.may_load(deps.storage)?.map(Bound::exclusive);
+    let candidates = PROPOSALS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let examined = candidates.len();
+    let mut archived = 0u64;
+    let mut cursor = None;
+    for (id, mut prop) in candidates {
+        // Proposals are created in order, so every later proposal is
+        // too recent as well.
+        if prop.start_height >= older_than_height {
+            cursor = None;
+            break;
+        }
+        cursor = Some(id);
+        if !matches!(
+            prop.status,
+            Status::Executed | Status::Closed | Status::ExecutionFailed
+        ) || ARCHIVED_PROPOSALS.has(deps.storage, id)
+        {
+            continue;
+        }
+        let hash = Sha256::digest(to_json_vec(&prop)?);
+        ARCHIVED_PROPOSALS.save(deps.storage, id, &Binary::from(hash.as_slice()))?;
+        prop.description = String::new();
+        prop.msgs = vec![];
+        PROPOSALS.save(deps.storage, id, &prop)?;
+        archived += 1;
+    }
+
+    // Start over once the end of the proposals, or the cutoff, is
+    // reached.
+    match cursor {
+        Some(id) if examined == limit => ARCHIVE_CURSOR.save(deps.storage, &id)?,
+        _ => ARCHIVE_CURSOR.remove(deps.storage),
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "archive")
+        .add_attribute("archived", archived.to_string()))
+}
+
+pub fn execute_add_subscription(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+    filter: SubscriptionFilter,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let SubscriptionFilter::Tag { tag } = &filter {
+        if tag.is_empty() || tag.contains(char::is_whitespace) {
+            return Err(ContractError::InvalidSubscriptionTag {});
+        }
+    }
+    let subscriber = deps.api.addr_validate(&address)?;
+    let id = SUBSCRIPTION_COUNT
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        + 1;
+    SUBSCRIPTION_COUNT.save(deps.storage, &id)?;
+    SUBSCRIPTIONS.save(deps.storage, id, &Subscription { subscriber, filter })?;
+
+    Ok(Response::default()
+        .add_attribute("action", "add_subscription")
+        .add_attribute("subscription_id", id.to_string())
+        .add_attribute("address", address))
+}
+
+pub fn execute_remove_subscription(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if !SUBSCRIPTIONS.has(deps.storage, id) {
+        return Err(ContractError::NoSuchSubscription { id });
+    }
+    SUBSCRIPTIONS.remove(deps.storage, id);
+
+    Ok(Response::default()
+        .add_attribute("action", "remove_subscription")
+        .add_attribute("subscription_id", id.to_string()))
+}
+
+/// Prepares proposal hook messages for the subscriptions whose
+/// filter PROP passes. Like proposal hooks, these reply on error so
+/// that a failing subscriber is removed rather than blocking the
+/// proposal.
+fn subscription_hooks(
+    storage: &dyn Storage,
+    env: &Env,
+    prop: &SingleChoiceProposal,
+    msg: ProposalHookMsg,
+) -> StdResult<Vec<SubMsg>> {
+    let dao = CONFIG.load(storage)?.dao;
+    let msg = to_json_binary(&ProposalHookExecuteMsg::ProposalHook(msg))?;
+    let mut hooks = vec![];
+    for subscription in SUBSCRIPTIONS.range(storage, None, None, Order::Ascending) {
+        let (id, subscription) = subscription?;
+        if subscription
+            .filter
+            .matches(&dao, &env.contract.address, prop)
+        {
+            let execute = WasmMsg::Execute {
+                contract_addr: subscription.subscriber.into_string(),
+                msg: msg.clone(),
+                funds: vec![],
+            };
+            hooks.push(SubMsg::reply_on_error(execute, mask_subscription_id(id)));
+        }
+    }
+    Ok(hooks)
+}
+
+/// Prepares subscription hook messages for a change in the status of
+/// PROP from OLD_STATUS.
+fn status_subscription_hooks(
+    storage: &dyn Storage,
+    env: &Env,
+    proposal_id: u64,
+    prop: &SingleChoiceProposal,
+    old_status: Status,
+) -> StdResult<Vec<SubMsg>> {
+    if old_status == prop.status {
+        return Ok(vec![]);
+    }
+    subscription_hooks(
+        storage,
+        env,
+        prop,
+        ProposalHookMsg::ProposalStatusChanged {
+            id: proposal_id,
+            old_status: old_status.to_string(),
+            new_status: prop.status.to_string(),
+        },
+    )
+}
+
+/// Prepares outcome hook messages for a proposal that has just
+/// passed.
+fn outcome_hooks(
+    storage: &dyn Storage,
+    proposal_id: u64,
+    prop: &SingleChoiceProposal,
+) -> StdResult<Vec<SubMsg>> {
+    proposal_passed_hooks(
+        OUTCOME_HOOKS,
+        storage,
+        ProposalOutcomeHookMsg::ProposalPassed {
+            id: proposal_id,
+            yes: prop.votes.yes,
+            no: prop.votes.no,
+            abstain: prop.votes.abstain,
+            total_power: prop.total_power,
+            height: prop.start_height,
+        },
+    )
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => query_config(deps),
         QueryMsg::Dao {} => query_dao(deps),
         QueryMsg::Proposal { proposal_id } => query_proposal(deps, env, proposal_id),
-        QueryMsg::ListProposals { start_after, limit } => {
-            query_list_proposals(deps, env, start_after, limit)
-        }
+        QueryMsg::ListProposals {
+            start_after,
+            limit,
+            descending,
+        } => query_list_proposals(deps, env, start_after, limit, descending),
         QueryMsg::NextProposalId {} => query_next_proposal_id(deps),
         QueryMsg::ProposalCount {} => query_proposal_count(deps),
+        QueryMsg::PendingVotes {
+            voter,
+            start_after,
+            limit,
+        } => query_pending_votes(deps, env, voter, start_after, limit),
         QueryMsg::GetVote { proposal_id, voter } => query_vote(deps, proposal_id, voter),
         QueryMsg::ListVotes {
             proposal_id,
             start_after,
             limit,
-        } => query_list_votes(deps, proposal_id, start_after, limit),
+            descending,
+        } => query_list_votes(deps, proposal_id, start_after, limit, descending),
         QueryMsg::Info {} => query_info(deps),
         QueryMsg::ReverseProposals {
             start_before,
@@ -732,6 +1781,36 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::ProposalCreationPolicy {} => query_creation_policy(deps),
         QueryMsg::ProposalHooks {} => to_json_binary(&PROPOSAL_HOOKS.query_hooks(deps)?),
         QueryMsg::VoteHooks {} => to_json_binary(&VOTE_HOOKS.query_hooks(deps)?),
+        QueryMsg::ProposalOutcome { proposal_id } => query_proposal_outcome(deps, env, proposal_id),
+        QueryMsg::OutcomeHooks {} => to_json_binary(&OUTCOME_HOOKS.query_hooks(deps)?),
+        QueryMsg::CouncilMode {} => to_json_binary(&COUNCIL_MODE.may_load(deps.storage)?.is_some()),
+        QueryMsg::InstantFinality {} => {
+            to_json_binary(&INSTANT_FINALITY.may_load(deps.storage)?.is_some())
+        }
+        QueryMsg::ProposalArchiveHash { proposal_id } => {
+            to_json_binary(&ARCHIVED_PROPOSALS.may_load(deps.storage, proposal_id)?)
+        }
+        QueryMsg::Subscriptions { start_after, limit } => {
+            query_subscriptions(deps, start_after, limit)
+        }
+        QueryMsg::QuorumSource {} => to_json_binary(
+            &QUORUM_SOURCE
+                .may_load(deps.storage)?
+                .unwrap_or(QuorumSource::TotalPower {}),
+        ),
+        QueryMsg::VoteRebate {} => to_json_binary(&VOTE_REBATE.may_load(deps.storage)?),
+        QueryMsg::BallotCap {} => to_json_binary(&BALLOT_CAP.may_load(deps.storage)?),
+        QueryMsg::ProposalBallotCap { proposal_id } => {
+            to_json_binary(&PROPOSAL_BALLOT_CAPS.may_load(deps.storage, proposal_id)?)
+        }
+        QueryMsg::ProposalRebate { proposal_id } => {
+            to_json_binary(&PROPOSAL_REBATES.may_load(deps.storage, proposal_id)?)
+        }
+        QueryMsg::OperationsAdmin {} => to_json_binary(&OPERATIONS_ADMIN.may_load(deps.storage)?),
+        QueryMsg::CriticalConfig {} => to_json_binary(&CRITICAL_CONFIG.may_load(deps.storage)?),
+        QueryMsg::CriticalProposal { proposal_id } => {
+            to_json_binary(&CRITICAL_PROPOSALS.may_load(deps.storage, proposal_id)?)
+        }
     }
 }
 
@@ -747,7 +1826,19 @@ pub fn query_dao(deps: Deps) -> StdResult<Binary> {
 
 pub fn query_proposal(deps: Deps, env: Env, id: u64) -> StdResult<Binary> {
     let proposal = PROPOSALS.load(deps.storage, id)?;
-    to_json_binary(&proposal.into_response(&env.block, id))
+    to_json_binary(&proposal_response(deps.storage, &env.block, id, proposal)?)
+}
+
+pub fn query_proposal_outcome(deps: Deps, env: Env, id: u64) -> StdResult<Binary> {
+    let proposal = PROPOSALS.load(deps.storage, id)?;
+    to_json_binary(&ProposalOutcomeResponse {
+        status: proposal.current_status(&env.block),
+        yes: proposal.votes.yes,
+        no: proposal.votes.no,
+        abstain: proposal.votes.abstain,
+        total_power: proposal.total_power,
+        height: proposal.start_height,
+    })
 }
 
 pub fn query_creation_policy(deps: Deps) -> StdResult<Binary> {
@@ -760,16 +1851,17 @@ pub fn query_list_proposals(
     env: Env,
     start_after: Option<u64>,
     limit: Option<u64>,
+    descending: Option<bool>,
 ) -> StdResult<Binary> {
-    let min = start_after.map(Bound::exclusive);
-    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let order = order(descending);
+    let (min, max) = range_bounds(start_after, order);
     let props: Vec<ProposalResponse> = PROPOSALS
-        .range(deps.storage, min, None, cosmwasm_std::Order::Ascending)
-        .take(limit as usize)
+        .range(deps.storage, min, max, order)
+        .take(clamp_limit_u64(limit))
         .collect::<Result<Vec<(u64, SingleChoiceProposal)>, _>>()?
         .into_iter()
-        .map(|(id, proposal)| proposal.into_response(&env.block, id))
-        .collect();
+        .map(|(id, proposal)| proposal_response(deps.storage, &env.block, id, proposal))
+        .collect::<StdResult<_>>()?;
 
     to_json_binary(&ProposalListResponse { proposals: props })
 }
@@ -780,19 +1872,81 @@ pub fn query_reverse_proposals(
     start_before: Option<u64>,
     limit: Option<u64>,
 ) -> StdResult<Binary> {
-    let limit = limit.unwrap_or(DEFAULT_LIMIT);
     let max = start_before.map(Bound::exclusive);
     let props: Vec<ProposalResponse> = PROPOSALS
         .range(deps.storage, None, max, cosmwasm_std::Order::Descending)
-        .take(limit as usize)
+        .take(clamp_limit_u64(limit))
         .collect::<Result<Vec<(u64, SingleChoiceProposal)>, _>>()?
         .into_iter()
-        .map(|(id, proposal)| proposal.into_response(&env.block, id))
-        .collect();
+        .map(|(id, proposal)| proposal_response(deps.storage, &env.block, id, proposal))
+        .collect::<StdResult<_>>()?;
 
     to_json_binary(&ProposalListResponse { proposals: props })
 }
 
+/// Builds the response to a proposal query, flagging the proposal if
+/// it has been archived and adding its discussion record.
+fn proposal_response(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+    id: u64,
+    proposal: SingleChoiceProposal,
+) -> StdResult<ProposalResponse> {
+    let mut response = proposal.into_response(block, id);
+    response.archived = ARCHIVED_PROPOSALS.has(storage, id);
+    response.discussion = DISCUSSIONS.may_load(storage, id)?;
+    Ok(response)
+}
+
+pub fn query_pending_votes(
+    deps: Deps,
+    env: Env,
+    voter: String,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let voter = deps.api.addr_validate(&voter)?;
+    let limit = clamp_limit_u64(limit);
+    let min = start_after.map(Bound::exclusive);
+
+    let mut proposals = vec![];
+    let mut last_examined = None;
+    for item in PROPOSALS
+        .range(deps.storage, min, None, cosmwasm_std::Order::Ascending)
+        .take(MAX_PENDING_VOTES_SCAN)
+    {
+        let (id, proposal) = item?;
+        last_examined = Some(id);
+        let proposal = proposal.into_response(&env.block, id);
+        if proposal.proposal.status == Status::Open && !BALLOTS.has(deps.storage, (id, &voter)) {
+            proposals.push(proposal);
+            if proposals.len() == limit {
+                break;
+            }
+        }
+    }
+
+    // Only return a cursor if there are proposals left to examine.
+    let next_start_after = match last_examined {
+        Some(id) => PROPOSALS
+            .keys(
+                deps.storage,
+                Some(Bound::exclusive(id)),
+                None,
+                cosmwasm_std::Order::Ascending,
+            )
+            .next()
+            .transpose()?
+            .map(|_| id),
+        None => None,
+    };
+
+    to_json_binary(&PendingVotesResponse {
+        proposals,
+        next_start_after,
+    })
+}
+
 pub fn query_proposal_count(deps: Deps) -> StdResult<Binary> {
     let proposal_count = PROPOSAL_COUNT.load(deps.storage)?;
     to_json_binary(&proposal_count)
@@ -819,17 +1973,18 @@ pub fn query_list_votes(
     proposal_id: u64,
     start_after: Option<String>,
     limit: Option<u64>,
+    descending: Option<bool>,
 ) -> StdResult<Binary> {
-    let limit = limit.unwrap_or(DEFAULT_LIMIT);
     let start_after = start_after
         .map(|addr| deps.api.addr_validate(&addr))
         .transpose()?;
-    let min = start_after.as_ref().map(Bound::<&Addr>::exclusive);
+    let order = order(descending);
+    let (min, max) = range_bounds(start_after.as_ref(), order);
 
     let votes = BALLOTS
         .prefix(proposal_id)
-        .range(deps.storage, min, None, cosmwasm_std::Order::Ascending)
-        .take(limit as usize)
+        .range(deps.storage, min, max, order)
+        .take(clamp_limit_u64(limit))
         .map(|item| {
             let (voter, ballot) = item?;
             Ok(VoteInfo {
@@ -844,6 +1999,28 @@ pub fn query_list_votes(
     to_json_binary(&VoteListResponse { votes })
 }
 
+pub fn query_subscriptions(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let min = start_after.map(Bound::exclusive);
+    let subscriptions = SUBSCRIPTIONS
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(clamp_limit(limit))
+        .map(|item| {
+            let (id, subscription) = item?;
+            Ok(SubscriptionResponse {
+                id,
+                subscriber: subscription.subscriber,
+                filter: subscription.filter,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_json_binary(&subscriptions)
+}
+
 pub fn query_info(deps: Deps) -> StdResult<Binary> {
     let info = cw2::get_contract_version(deps.storage)?;
     to_json_binary(&dao_interface::voting::InfoResponse { info })
@@ -920,10 +2097,12 @@ pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, Co
                         expiration: v1_expiration_to_v2(prop.expiration),
                         threshold: v1_threshold_to_v2(prop.threshold),
                         total_power: prop.total_power,
+                        quorum_power: None,
                         msgs: prop.msgs,
                         status: v1_status_to_v2(prop.status),
                         votes: v1_votes_to_v2(prop.votes),
                         allow_revoting: prop.allow_revoting,
+                        instant_finality: false,
                     };
 
                     PROPOSALS
@@ -944,7 +2123,7 @@ pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, Co
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
     let repl = TaggedReplyId::new(msg.id)?;
     match repl {
         TaggedReplyId::FailedProposalExecution(proposal_id) => {
@@ -969,10 +2148,31 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
             let addr = VOTE_HOOKS.remove_hook_by_index(deps.storage, idx)?;
             Ok(Response::new().add_attribute("removed_vote_hook", format!("{addr}:{idx}")))
         }
+        TaggedReplyId::FailedOutcomeHook(idx) => {
+            let addr = OUTCOME_HOOKS.remove_hook_by_index(deps.storage, idx)?;
+            Ok(Response::new().add_attribute("removed_outcome_hook", format!("{addr}:{idx}")))
+        }
+        TaggedReplyId::FailedSubscriptionHook(id) => {
+            let subscription = SUBSCRIPTIONS.load(deps.storage, id)?;
+            SUBSCRIPTIONS.remove(deps.storage, id);
+            Ok(Response::new().add_attribute(
+                "removed_subscription",
+                format!("{}:{id}", subscription.subscriber),
+            ))
+        }
         TaggedReplyId::PreProposeModuleInstantiation => {
             let res = parse_reply_instantiate_data(msg)?;
 
             let module = deps.api.addr_validate(&res.contract_address)?;
+            if PENDING_PRE_PROPOSE_SWAP.may_load(deps.storage)?.is_some() {
+                PENDING_PRE_PROPOSE_SWAP.remove(deps.storage);
+                let proposal_module: Addr = deps
+                    .querier
+                    .query_wasm_smart(&module, &PreProposeQueryMsg::ProposalModule {})?;
+                if proposal_module != env.contract.address {
+                    return Err(ContractError::InvalidPreProposeModule {});
+                }
+            }
             CREATION_POLICY.save(
                 deps.storage,
                 &ProposalCreationPolicy::Module { addr: module },
@@ -1012,5 +2212,10 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
             };
             Ok(Response::new().add_attribute("failed_prepropose_hook", format!("{addr}")))
         }
+        // A replaced pre-propose module is not removed when it fails,
+        // as it is no longer installed. The proposal completes
+        // regardless.
+        TaggedReplyId::FailedPreviousPreProposeModuleHook(proposal_id) => Ok(Response::new()
+            .add_attribute("failed_previous_prepropose_hook", proposal_id.to_string())),
     }
 }
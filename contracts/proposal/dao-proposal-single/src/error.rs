@@ -87,6 +87,78 @@ pub enum ContractError {
     #[error("received a reply failure with an invalid ID: ({id})")]
     InvalidReplyID { id: u64 },
 
+    #[error("vote rebate amount must be non-zero and no more than the per-proposal budget")]
+    InvalidVoteRebate {},
+
+    #[error("proposal ({id}) has no vote rebate")]
+    NoVoteRebate { id: u64 },
+
+    #[error("vote rebates may only be claimed once voting has closed")]
+    RebateVotingOpen {},
+
+    #[error("vote rebates are only paid for proposals that reach quorum")]
+    RebateQuorumNotReached {},
+
+    #[error("ballot voting power is below the minimum required for a rebate")]
+    RebatePowerTooLow {},
+
+    #[error("vote rebate has already been claimed")]
+    RebateClaimed {},
+
+    #[error("the rebate budget for this proposal has been exhausted")]
+    RebateBudgetExhausted {},
+
+    #[error("the rebate pool does not hold enough ({denom}) to pay this rebate")]
+    RebatePoolEmpty { denom: String },
+
+    #[error("ballot cap must be greater than zero and at most one")]
+    InvalidBallotCap {},
+
+    #[error("critical proposals are not enabled")]
+    CriticalProposalsDisabled {},
+
+    #[error("critical proposals must have a non-zero voting period and at least one proposer")]
+    InvalidCriticalConfig {},
+
+    #[error("proposal ({id}) is not critical")]
+    NotCritical { id: u64 },
+
+    #[error("proposal may not be escalated until half of its voting period has passed")]
+    EscalationNotDue {},
+
+    #[error("proposal has reached quorum and may not be escalated")]
+    QuorumReached {},
+
+    #[error("proposal has already been escalated")]
+    AlreadyEscalated {},
+
+    #[error("proposal has not been escalated")]
+    NotEscalated {},
+
+    #[error("the discussion record for proposal ({id}) has already been set")]
+    DiscussionSet { id: u64 },
+
+    #[error("the discussion record may only be set before any votes are cast")]
+    VotingStarted {},
+
+    #[error("proposal is not open")]
+    NotOpen {},
+
+    #[error("subscription tags may not be empty or contain whitespace")]
+    InvalidSubscriptionTag {},
+
+    #[error("no subscription with id ({id})")]
+    NoSuchSubscription { id: u64 },
+
     #[error("can not migrate. current version is up to date")]
     AlreadyMigrated {},
+
+    #[error("settings managed by council mode may not be changed while it is enabled")]
+    CouncilModeEnabled {},
+
+    #[error("no pre-propose module is installed")]
+    NoPreProposeModule {},
+
+    #[error("the new pre-propose module is not associated with this proposal module")]
+    InvalidPreProposeModule {},
 }
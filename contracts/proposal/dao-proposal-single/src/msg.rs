@@ -1,4 +1,5 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Api, Coin, HexBinary, StdResult, Uint128};
 use cw_utils::Duration;
 use dao_dao_macros::proposal_module_query;
 use dao_voting::{
@@ -6,6 +7,10 @@ use dao_voting::{
     voting::Vote,
 };
 
+use crate::state::{BallotCap, ConfigField, CriticalConfig, SubscriptionFilter, VoteRebateConfig};
+
+pub use dao_voting::quorum::UncheckedQuorumSource;
+
 #[cw_serde]
 pub struct InstantiateMsg {
     /// The threshold a proposal must reach to complete.
@@ -44,6 +49,13 @@ pub struct InstantiateMsg {
 pub enum ExecuteMsg {
     /// Creates a proposal in the module.
     Propose(SingleChoiceProposeMsg),
+    /// Creates a critical proposal in the module. Critical proposals
+    /// use the voting period in the critical config and have no
+    /// minimum voting period. The sender must be one of the critical
+    /// config's proposers, and creates the proposal directly without
+    /// going through the pre-propose module. `proposer` must be
+    /// `None`.
+    ProposeCritical(SingleChoiceProposeMsg),
     /// Votes on a proposal. Voting power is determined by the DAO's
     /// voting power module.
     Vote {
@@ -62,6 +74,16 @@ pub enum ExecuteMsg {
         proposal_id: u64,
         rationale: Option<String>,
     },
+    /// Records where a proposal is being discussed and, optionally, a
+    /// hash committing to the discussion record. Only the proposer
+    /// may call this method, once, while the proposal is open and
+    /// before any votes are cast. The record may not be changed once
+    /// set.
+    SetDiscussion {
+        proposal_id: u64,
+        uri: String,
+        freeze_hash: Option<HexBinary>,
+    },
     /// Causes the messages associated with a passed proposal to be
     /// executed by the DAO.
     Execute {
@@ -114,6 +136,14 @@ pub enum ExecuteMsg {
     /// Update's the proposal creation policy used for this
     /// module. Only the DAO may call this method.
     UpdatePreProposeInfo { info: PreProposeInfo },
+    /// Replaces the installed pre-propose module. Unlike
+    /// `UpdatePreProposeInfo`, proposals created before the swap
+    /// continue to send their completion hooks to the replaced
+    /// module, so that it returns the deposits it holds for them. A
+    /// new module is checked to be associated with this module when
+    /// it is instantiated, failing the swap otherwise. Only the DAO
+    /// may call this method.
+    SwapPreProposeModule { info: PreProposeInfo },
     /// Adds an address as a consumer of proposal hooks. Consumers of
     /// proposal hooks have hook messages executed on them whenever
     /// the status of a proposal changes or a proposal is created. If
@@ -129,6 +159,139 @@ pub enum ExecuteMsg {
     AddVoteHook { address: String },
     /// Removed a consumer of vote hooks.
     RemoveVoteHook { address: String },
+    /// Adds an address as a consumer of proposal outcome
+    /// hooks. Consumers of outcome hooks have a hook message
+    /// executed on them once when a proposal passes. If a consumer
+    /// contract errors when handling a hook message it will be
+    /// removed from the list of consumers.
+    AddOutcomeHook { address: String },
+    /// Removes a consumer of proposal outcome hooks.
+    RemoveOutcomeHook { address: String },
+    /// Subscribes ADDRESS to proposal hooks for the proposals that
+    /// pass FILTER. Filters are checked each time a hook fires, so a
+    /// proposal's messages, title, and description decide whether
+    /// the subscriber hears about it. Subscriptions whose hooks fail
+    /// are removed. Only the DAO may call this method.
+    AddSubscription {
+        address: String,
+        filter: SubscriptionFilter,
+    },
+    /// Removes a subscription. Only the DAO may call this method.
+    RemoveSubscription { id: u64 },
+    /// Updates the voting power that quorum is computed against. This
+    /// will only apply to proposals created after the update. Only
+    /// the DAO may call this method.
+    UpdateQuorumSource {
+        quorum_source: UncheckedQuorumSource,
+    },
+    /// Sets or removes the rebate paid to voters on new proposals
+    /// that reach quorum. Rebates are paid out of native tokens held
+    /// by this module. Only the DAO may call this method.
+    UpdateVoteRebate { rebate: Option<VoteRebateConfig> },
+    /// Claims the sender's vote rebate for a proposal whose voting
+    /// period has ended.
+    ClaimVoteRebate { proposal_id: u64 },
+    /// Sends tokens held for vote rebates back to the DAO. Only the
+    /// DAO may call this method.
+    WithdrawRebatePool { amount: Coin },
+    /// Sets or removes the cap on the voting power a single ballot
+    /// counts for. This will only apply to proposals created after
+    /// the update. Only the DAO may call this method.
+    UpdateBallotCap { cap: Option<BallotCap> },
+    /// Archives executed and closed proposals created before
+    /// OLDER_THAN_HEIGHT, removing their descriptions and messages
+    /// and recording a hash of their full records. Each call examines
+    /// up to LIMIT proposals, capped at the paginated query maximum,
+    /// continuing from where the previous call left off. Only the DAO
+    /// may call this method.
+    Archive {
+        older_than_height: u64,
+        limit: Option<u32>,
+    },
+    /// Enables council mode if APPROVALS is set, or disables it
+    /// otherwise. Enabling council mode sets the threshold to an
+    /// absolute count of APPROVALS, removes the minimum voting
+    /// period, disables revoting, and closes proposals whose
+    /// execution fails. While enabled, proposals are executed as soon
+    /// as they pass, and those settings may not be updated by other
+    /// means. Disabling it restores them to what they were before it
+    /// was enabled. Only the DAO may call this method.
+    UpdateCouncilMode { approvals: Option<Uint128> },
+    /// Enables or disables instant finality for proposals created
+    /// after the update. Such a proposal is passed or rejected as
+    /// soon as all of its voting power has been cast, regardless of
+    /// the minimum voting period or revoting. Only the DAO may call
+    /// this method.
+    UpdateInstantFinality { enabled: bool },
+    /// Updates the passing threshold for proposals created after the
+    /// update. May be called by the DAO, or by the operations admin
+    /// if it is permitted to update `ConfigField::Threshold`.
+    UpdateThreshold { threshold: Threshold },
+    /// Updates the minimum and maximum voting periods for proposals
+    /// created after the update. Permissioned as `UpdateThreshold`,
+    /// with `ConfigField::VotingPeriod`.
+    UpdateVotingPeriod {
+        min_voting_period: Option<Duration>,
+        max_voting_period: Duration,
+    },
+    /// Updates whether only members may execute passed
+    /// proposals. Permissioned as `UpdateThreshold`, with
+    /// `ConfigField::OnlyMembersExecute`.
+    UpdateOnlyMembersExecute { only_members_execute: bool },
+    /// Updates whether votes may be changed. Permissioned as
+    /// `UpdateThreshold`, with `ConfigField::AllowRevoting`.
+    UpdateAllowRevoting { allow_revoting: bool },
+    /// Updates whether proposals are closed when their execution
+    /// fails. Permissioned as `UpdateThreshold`, with
+    /// `ConfigField::CloseProposalOnExecutionFailure`.
+    UpdateCloseProposalOnExecutionFailure {
+        close_proposal_on_execution_failure: bool,
+    },
+    /// Sets the operations admin, an address that may update FIELDS
+    /// of the config without a proposal. If ADDRESS is `None`, the
+    /// operations admin is removed and FIELDS is ignored. Only the
+    /// DAO may call this method.
+    UpdateOperationsAdmin {
+        address: Option<String>,
+        fields: Vec<ConfigField>,
+    },
+    /// Sets or removes the configuration for critical proposals. The
+    /// critical voting period must be no longer than the maximum
+    /// voting period. Only the DAO may call this method.
+    UpdateCriticalConfig {
+        config: Option<UncheckedCriticalConfig>,
+    },
+    /// Escalates a critical proposal that has not reached quorum
+    /// halfway through its voting period to its council. Callable by
+    /// anyone.
+    Escalate { proposal_id: u64 },
+    /// Decides an escalated proposal that is still open, passing it
+    /// if PASS is true and rejecting it otherwise. Only the council
+    /// the proposal was escalated to may call this method.
+    DecideEscalated { proposal_id: u64, pass: bool },
+}
+
+/// The configuration for critical proposals, before addresses are
+/// validated.
+#[cw_serde]
+pub struct UncheckedCriticalConfig {
+    pub proposers: Vec<String>,
+    pub voting_period: Duration,
+    pub council: String,
+}
+
+impl UncheckedCriticalConfig {
+    pub fn into_checked(self, api: &dyn Api) -> StdResult<CriticalConfig> {
+        Ok(CriticalConfig {
+            proposers: self
+                .proposers
+                .iter()
+                .map(|proposer| api.addr_validate(proposer))
+                .collect::<StdResult<_>>()?,
+            voting_period: self.voting_period,
+            council: api.addr_validate(&self.council)?,
+        })
+    }
 }
 
 #[proposal_module_query]
@@ -147,12 +310,14 @@ pub enum QueryMsg {
     ListProposals {
         /// The proposal ID to start listing proposals after. For
         /// example, if this is set to 2 proposals with IDs 3 and
-        /// higher will be returned.
+        /// higher will be returned, or 1 if DESCENDING is set.
         start_after: Option<u64>,
         /// The maximum number of proposals to return as part of this
-        /// query. If no limit is set a max of 30 proposals will be
-        /// returned.
+        /// query. If no limit is set 10 proposals are returned, and
+        /// at most 30 are.
         limit: Option<u64>,
+        /// Lists proposals in descending order of proposal ID if set.
+        descending: Option<bool>,
     },
     /// Lists all of the proposals that have been cast in this module
     /// in decending order of proposal ID.
@@ -163,8 +328,24 @@ pub enum QueryMsg {
         /// lower will be returned.
         start_before: Option<u64>,
         /// The maximum number of proposals to return as part of this
-        /// query. If no limit is set a max of 30 proposals will be
-        /// returned.
+        /// query. If no limit is set 10 proposals are returned, and
+        /// at most 30 are.
+        limit: Option<u64>,
+    },
+    /// Lists open proposals that `voter` has not yet voted on, in
+    /// ascending order of proposal ID. Voting power is not checked,
+    /// so proposals that `voter` may not vote on are included. At
+    /// most 100 proposals are examined per query, so a page may hold
+    /// fewer than `limit` proposals while more remain; continue from
+    /// the response's `next_start_after`.
+    #[returns(crate::query::PendingVotesResponse)]
+    PendingVotes {
+        voter: String,
+        /// The proposal ID to start listing proposals after.
+        start_after: Option<u64>,
+        /// The maximum number of proposals to return as part of this
+        /// query. If no limit is set 10 proposals are returned, and
+        /// at most 30 are.
         limit: Option<u64>,
     },
     /// Returns a voters position on a propsal.
@@ -180,8 +361,11 @@ pub enum QueryMsg {
         /// alphabetically.
         start_after: Option<String>,
         /// The maximum number of votes to return in response to this
-        /// query. If no limit is specified a max of 30 are returned.
+        /// query. If no limit is specified 10 are returned, and at
+        /// most 30 are.
         limit: Option<u64>,
+        /// Lists votes in reverse alphabetical order if set.
+        descending: Option<bool>,
     },
     /// Returns the number of proposals that have been created in this module.
     #[returns(::std::primitive::u64)]
@@ -195,6 +379,57 @@ pub enum QueryMsg {
     /// Lists all of the consumers of vote hooks for this module.
     #[returns(::cw_hooks::HooksResponse)]
     VoteHooks {},
+    /// Gets the outcome of a proposal. Unlike `Proposal`, the
+    /// response to this query is kept minimal and stable for
+    /// consumption by other contracts.
+    #[returns(crate::query::ProposalOutcomeResponse)]
+    ProposalOutcome { proposal_id: u64 },
+    /// Lists all of the consumers of proposal outcome hooks for this
+    /// module.
+    #[returns(::cw_hooks::HooksResponse)]
+    OutcomeHooks {},
+    /// Gets the SHA-256 hash of an archived proposal's full record, as
+    /// JSON, or `None` if the proposal has not been archived.
+    #[returns(Option<::cosmwasm_std::Binary>)]
+    ProposalArchiveHash { proposal_id: u64 },
+    /// Returns true if council mode is enabled.
+    #[returns(bool)]
+    CouncilMode {},
+    /// Returns true if instant finality is enabled for new proposals.
+    #[returns(bool)]
+    InstantFinality {},
+    /// Lists filtered proposal hook subscriptions.
+    #[returns(Vec<crate::query::SubscriptionResponse>)]
+    Subscriptions {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Gets the voting power that quorum is computed against for new
+    /// proposals.
+    #[returns(crate::state::QuorumSource)]
+    QuorumSource {},
+    /// Gets the vote rebate applied to new proposals, if any.
+    #[returns(Option<crate::state::VoteRebateConfig>)]
+    VoteRebate {},
+    /// Gets the vote rebate for a proposal, if it has one.
+    #[returns(Option<crate::state::ProposalRebate>)]
+    ProposalRebate { proposal_id: u64 },
+    /// Gets the ballot cap applied to new proposals, if any.
+    #[returns(Option<crate::state::BallotCap>)]
+    BallotCap {},
+    /// Gets the ballot cap for a proposal, if it has one.
+    #[returns(Option<crate::state::BallotCap>)]
+    ProposalBallotCap { proposal_id: u64 },
+    /// Gets the operations admin and the config fields it may
+    /// update, if one is set.
+    #[returns(Option<crate::state::OperationsAdmin>)]
+    OperationsAdmin {},
+    /// Gets the configuration for critical proposals, if set.
+    #[returns(Option<crate::state::CriticalConfig>)]
+    CriticalConfig {},
+    /// Gets the escalation state of a proposal, if it is critical.
+    #[returns(Option<crate::state::CriticalProposal>)]
+    CriticalProposal { proposal_id: u64 },
 }
 
 #[cw_serde]
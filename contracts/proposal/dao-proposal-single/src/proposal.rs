@@ -29,11 +29,21 @@ pub struct SingleChoiceProposal {
     /// The total amount of voting power at the time of this
     /// proposal's creation.
     pub total_power: Uint128,
+    /// The voting power that quorum is computed against, if it
+    /// differs from `total_power`. Set when the module computes
+    /// quorum against active voting power only.
+    #[serde(default)]
+    pub quorum_power: Option<Uint128>,
     /// The messages that will be executed should this proposal pass.
     pub msgs: Vec<CosmosMsg<Empty>>,
     pub status: Status,
     pub votes: Votes,
     pub allow_revoting: bool,
+    /// If true, the proposal is passed or rejected as soon as every
+    /// address with voting power has voted, regardless of the minimum
+    /// voting period and revoting.
+    #[serde(default)]
+    pub instant_finality: bool,
 }
 
 pub fn next_proposal_id(store: &dyn Storage) -> StdResult<u64> {
@@ -56,7 +66,30 @@ impl SingleChoiceProposal {
     /// queries get accurate information.
     pub fn into_response(mut self, block: &BlockInfo, id: u64) -> ProposalResponse {
         self.update_status(block);
-        ProposalResponse { id, proposal: self }
+        ProposalResponse {
+            id,
+            proposal: self,
+            archived: false,
+            discussion: None,
+        }
+    }
+
+    /// The voting power that quorum is computed against.
+    pub fn quorum_power(&self) -> Uint128 {
+        self.quorum_power.unwrap_or(self.total_power)
+    }
+
+    /// Returns true if the votes cast on this proposal meet its
+    /// quorum. Thresholds without a quorum are met by any vote.
+    pub fn reached_quorum(&self) -> bool {
+        match self.threshold {
+            Threshold::ThresholdQuorum { quorum, .. } => {
+                does_vote_count_pass(self.votes.total(), self.quorum_power(), quorum)
+            }
+            Threshold::AbsolutePercentage { .. } | Threshold::AbsoluteCount { .. } => {
+                !self.votes.total().is_zero()
+            }
+        }
     }
 
     /// Gets the current status of the proposal.
@@ -82,6 +115,9 @@ impl SingleChoiceProposal {
     /// expiration if no future sequence of possible votes can cause
     /// it to fail).
     pub fn is_passed(&self, block: &BlockInfo) -> bool {
+        if self.is_final() {
+            return self.meets_threshold(block);
+        }
         // If re-voting is allowed nothing is known until the proposal
         // has expired.
         if self.allow_revoting && !self.expiration.is_expired(block) {
@@ -98,13 +134,29 @@ impl SingleChoiceProposal {
             }
         }
 
+        self.meets_threshold(block)
+    }
+
+    /// Returns true if this proposal has instant finality and every
+    /// address with voting power has voted, so no further votes may
+    /// change its outcome. Revotes are not accepted on final
+    /// proposals.
+    pub fn is_final(&self) -> bool {
+        self.instant_finality
+            && !self.total_power.is_zero()
+            && self.votes.total() >= self.total_power
+    }
+
+    /// Returns true if the votes on this proposal meet its threshold,
+    /// ignoring the minimum voting period and revoting.
+    fn meets_threshold(&self, block: &BlockInfo) -> bool {
         match self.threshold {
             Threshold::AbsolutePercentage { percentage } => {
                 let options = self.total_power - self.votes.abstain;
                 does_vote_count_pass(self.votes.yes, options, percentage)
             }
             Threshold::ThresholdQuorum { threshold, quorum } => {
-                if !does_vote_count_pass(self.votes.total(), self.total_power, quorum) {
+                if !does_vote_count_pass(self.votes.total(), self.quorum_power(), quorum) {
                     return false;
                 }
 
@@ -127,6 +179,9 @@ impl SingleChoiceProposal {
     /// As above for the passed check, used to check if a proposal is
     /// already rejected.
     pub fn is_rejected(&self, block: &BlockInfo) -> bool {
+        if self.is_final() {
+            return !self.meets_threshold(block);
+        }
         // If re-voting is allowed and the proposal is not expired no
         // information is known.
         if self.allow_revoting && !self.expiration.is_expired(block) {
@@ -163,7 +218,7 @@ impl SingleChoiceProposal {
             }
             Threshold::ThresholdQuorum { threshold, quorum } => {
                 match (
-                    does_vote_count_pass(self.votes.total(), self.total_power, quorum),
+                    does_vote_count_pass(self.votes.total(), self.quorum_power(), quorum),
                     self.expiration.is_expired(block),
                 ) {
                     // Has met quorum and is expired.
@@ -245,6 +300,7 @@ mod test {
         testing::{mock_dependencies, mock_env},
         Decimal,
     };
+    use dao_voting::voting::Vote;
 
     fn setup_prop(
         threshold: Threshold,
@@ -276,7 +332,9 @@ mod test {
             status: Status::Open,
             threshold,
             total_power,
+            quorum_power: None,
             votes,
+            instant_finality: false,
         };
         (prop, block)
     }
@@ -623,6 +681,49 @@ mod test {
         ));
     }
 
+    /// Tests that proposals with instant finality are decided once all
+    /// voting power has been cast, even with revoting and a minimum
+    /// voting period that has not elapsed.
+    #[test]
+    fn test_instant_finality() {
+        let threshold = Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        };
+        let (mut prop, block) = setup_prop(
+            threshold,
+            Votes {
+                yes: Uint128::new(2),
+                no: Uint128::zero(),
+                abstain: Uint128::zero(),
+            },
+            Uint128::new(3),
+            false,
+            false,
+            true,
+        );
+        prop.instant_finality = true;
+        assert!(!prop.is_final());
+        assert!(!prop.is_passed(&block));
+        assert!(!prop.is_rejected(&block));
+
+        prop.votes.add_vote(Vote::No, Uint128::new(1));
+        assert!(prop.is_final());
+        assert!(prop.is_passed(&block));
+        assert!(!prop.is_rejected(&block));
+
+        prop.votes = Votes {
+            yes: Uint128::new(1),
+            no: Uint128::new(1),
+            abstain: Uint128::new(1),
+        };
+        assert!(!prop.is_passed(&block));
+        assert!(prop.is_rejected(&block));
+
+        prop.instant_finality = false;
+        assert!(!prop.is_final());
+        assert!(!prop.is_rejected(&block));
+    }
+
     #[test]
     fn test_tricky_pass() {
         let threshold = Threshold::AbsolutePercentage {
@@ -1,6 +1,8 @@
 use crate::proposal::SingleChoiceProposal;
+use crate::state::{Discussion, SubscriptionFilter};
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Uint128};
+use dao_voting::status::Status;
 use dao_voting::voting::Vote;
 
 /// Information about a proposal returned by proposal queries.
@@ -9,6 +11,13 @@ pub struct ProposalResponse {
     /// The ID of the proposal being returned.
     pub id: u64,
     pub proposal: SingleChoiceProposal,
+    /// True if the proposal has been archived, in which case its
+    /// description and messages have been removed.
+    #[serde(default)]
+    pub archived: bool,
+    /// The proposal's discussion record, if its proposer set one.
+    #[serde(default)]
+    pub discussion: Option<Discussion>,
 }
 
 /// Information about a vote that was cast.
@@ -43,3 +52,38 @@ pub struct VoteListResponse {
 pub struct ProposalListResponse {
     pub proposals: Vec<ProposalResponse>,
 }
+
+/// A page of proposals returned by the `PendingVotes` query.
+#[cw_serde]
+pub struct PendingVotesResponse {
+    pub proposals: Vec<ProposalResponse>,
+    /// The ID of the last proposal examined, to be passed as
+    /// `start_after` to continue listing. None once every proposal
+    /// has been examined.
+    pub next_start_after: Option<u64>,
+}
+
+/// The outcome of a proposal returned by the `ProposalOutcome`
+/// query. Intended to be consumed by other contracts, so its shape
+/// is kept minimal and stable.
+#[cw_serde]
+pub struct ProposalOutcomeResponse {
+    /// The status of the proposal at the time of the query.
+    pub status: Status,
+    pub yes: Uint128,
+    pub no: Uint128,
+    pub abstain: Uint128,
+    /// The total voting power of the DAO at `height`.
+    pub total_power: Uint128,
+    /// The height voting power was measured at.
+    pub height: u64,
+}
+
+/// A filtered proposal hook subscription returned by the
+/// `Subscriptions` query.
+#[cw_serde]
+pub struct SubscriptionResponse {
+    pub id: u64,
+    pub subscriber: Addr,
+    pub filter: SubscriptionFilter,
+}
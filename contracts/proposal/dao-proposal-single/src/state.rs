@@ -1,12 +1,16 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{
+    from_json_binary, Addr, Binary, CosmosMsg, Decimal, Empty, HexBinary, Uint128, WasmMsg,
+};
 use cw_hooks::Hooks;
 use cw_storage_plus::{Item, Map};
-use cw_utils::Duration;
+use cw_utils::{Duration, Expiration};
 use dao_voting::{pre_propose::ProposalCreationPolicy, threshold::Threshold, voting::Vote};
 
 use crate::proposal::SingleChoiceProposal;
 
+pub use dao_voting::quorum::QuorumSource;
+
 /// A vote cast for a proposal.
 #[cw_serde]
 pub struct Ballot {
@@ -57,6 +61,204 @@ pub struct Config {
     pub close_proposal_on_execution_failure: bool,
 }
 
+/// Configuration for rebating the gas costs of voters out of native
+/// tokens held by this module.
+#[cw_serde]
+pub struct VoteRebateConfig {
+    /// The native denom rebates are paid in.
+    pub denom: String,
+    /// The amount paid to each eligible voter.
+    pub amount: Uint128,
+    /// The most that may be paid out in rebates for a single
+    /// proposal.
+    pub proposal_budget: Uint128,
+    /// The minimum voting power a ballot must have for its voter to
+    /// be eligible for a rebate.
+    pub min_power: Uint128,
+}
+
+/// The vote rebate for a proposal, fixed at the time of its
+/// creation.
+#[cw_serde]
+pub struct ProposalRebate {
+    pub config: VoteRebateConfig,
+    /// The amount of the proposal's budget that has not yet been
+    /// claimed.
+    pub remaining: Uint128,
+}
+
+/// What becomes of the voting power a ballot has above the ballot
+/// cap.
+#[cw_serde]
+#[derive(Copy)]
+pub enum ExcessPower {
+    /// The excess is counted as an abstain vote.
+    Abstain,
+    /// The excess is not counted.
+    Discard,
+}
+
+/// A cap on the voting power that a single ballot counts for.
+#[cw_serde]
+pub struct BallotCap {
+    /// The largest share of a proposal's total voting power that a
+    /// single ballot may count for. Between zero (exclusive) and one.
+    pub max_share: Decimal,
+    pub excess: ExcessPower,
+}
+
+impl BallotCap {
+    /// Splits POWER into the power counted for the voter's position
+    /// and the power counted as abstaining.
+    pub fn apply(&self, power: Uint128, total_power: Uint128) -> (Uint128, Uint128) {
+        let cap = total_power * self.max_share;
+        if power <= cap {
+            return (power, Uint128::zero());
+        }
+        match self.excess {
+            ExcessPower::Abstain => (cap, power - cap),
+            ExcessPower::Discard => (cap, Uint128::zero()),
+        }
+    }
+}
+
+/// A field of the config that may be updated on its own.
+#[cw_serde]
+#[derive(Copy)]
+pub enum ConfigField {
+    Threshold,
+    /// The minimum and maximum voting periods, which are validated
+    /// together.
+    VotingPeriod,
+    OnlyMembersExecute,
+    AllowRevoting,
+    CloseProposalOnExecutionFailure,
+}
+
+impl std::fmt::Display for ConfigField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigField::Threshold => write!(f, "threshold"),
+            ConfigField::VotingPeriod => write!(f, "voting_period"),
+            ConfigField::OnlyMembersExecute => write!(f, "only_members_execute"),
+            ConfigField::AllowRevoting => write!(f, "allow_revoting"),
+            ConfigField::CloseProposalOnExecutionFailure => {
+                write!(f, "close_proposal_on_execution_failure")
+            }
+        }
+    }
+}
+
+/// An address other than the DAO that may update some fields of the
+/// config without a proposal.
+#[cw_serde]
+pub struct OperationsAdmin {
+    pub address: Addr,
+    /// The fields this address may update.
+    pub fields: Vec<ConfigField>,
+}
+
+/// Configuration for critical proposals, which have a shorter voting
+/// period and are escalated to a council if they have not reached
+/// quorum halfway through it.
+#[cw_serde]
+pub struct CriticalConfig {
+    /// The addresses that may create critical proposals.
+    pub proposers: Vec<Addr>,
+    /// The voting period of critical proposals.
+    pub voting_period: Duration,
+    /// The contract that decides critical proposals that are
+    /// escalated.
+    pub council: Addr,
+}
+
+/// The escalation state of a critical proposal, fixed at the time of
+/// its creation.
+#[cw_serde]
+pub struct CriticalProposal {
+    /// The council the proposal is escalated to.
+    pub council: Addr,
+    /// The time after which the proposal may be escalated if it has
+    /// not reached quorum.
+    pub escalate_at: Expiration,
+    /// True once the proposal has been escalated to the council.
+    pub escalated: bool,
+}
+
+/// The proposals a subscription is notified about.
+#[cw_serde]
+pub enum SubscriptionFilter {
+    /// Every proposal.
+    All {},
+    /// Proposals with messages that move funds out of the treasury:
+    /// native sends and burns, messages with funds attached, and cw20
+    /// transfers, sends, and burns.
+    TreasurySpend {},
+    /// Proposals with messages that execute on the DAO or on this
+    /// module, or that migrate or change the admin of a contract.
+    ConfigChange {},
+    /// Proposals whose title or description contains the hashtag
+    /// `#TAG`.
+    Tag { tag: String },
+}
+
+impl SubscriptionFilter {
+    /// Returns true if PROP, created in DAO by MODULE, passes this
+    /// filter.
+    pub fn matches(&self, dao: &Addr, module: &Addr, prop: &SingleChoiceProposal) -> bool {
+        match self {
+            SubscriptionFilter::All {} => true,
+            SubscriptionFilter::TreasurySpend {} => prop.msgs.iter().any(is_treasury_spend),
+            SubscriptionFilter::ConfigChange {} => prop.msgs.iter().any(|msg| match msg {
+                CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. }) => {
+                    contract_addr == dao.as_str() || contract_addr == module.as_str()
+                }
+                CosmosMsg::Wasm(WasmMsg::Migrate { .. })
+                | CosmosMsg::Wasm(WasmMsg::UpdateAdmin { .. })
+                | CosmosMsg::Wasm(WasmMsg::ClearAdmin { .. }) => true,
+                _ => false,
+            }),
+            SubscriptionFilter::Tag { tag } => {
+                let hashtag = format!("#{tag}");
+                prop.title
+                    .split_whitespace()
+                    .chain(prop.description.split_whitespace())
+                    .any(|word| {
+                        word.trim_end_matches(|c: char| c.is_ascii_punctuation()) == hashtag
+                    })
+            }
+        }
+    }
+}
+
+fn is_treasury_spend(msg: &CosmosMsg) -> bool {
+    match msg {
+        CosmosMsg::Bank(_) => true,
+        CosmosMsg::Wasm(WasmMsg::Execute { msg, funds, .. }) => {
+            !funds.is_empty()
+                || matches!(
+                    from_json_binary(msg),
+                    Ok(cw20::Cw20ExecuteMsg::Transfer { .. }
+                        | cw20::Cw20ExecuteMsg::Send { .. }
+                        | cw20::Cw20ExecuteMsg::Burn { .. }
+                        | cw20::Cw20ExecuteMsg::TransferFrom { .. }
+                        | cw20::Cw20ExecuteMsg::SendFrom { .. }
+                        | cw20::Cw20ExecuteMsg::BurnFrom { .. })
+                )
+        }
+        CosmosMsg::Wasm(WasmMsg::Instantiate { funds, .. }) => !funds.is_empty(),
+        _ => false,
+    }
+}
+
+/// A contract that is sent proposal hooks for the proposals that
+/// pass its filter.
+#[cw_serde]
+pub struct Subscription {
+    pub subscriber: Addr,
+    pub filter: SubscriptionFilter,
+}
+
 /// The current top level config for the module.  The "config" key was
 /// previously used to store configs for v1 DAOs.
 pub const CONFIG: Item<Config> = Item::new("config_v2");
@@ -68,6 +270,67 @@ pub const BALLOTS: Map<(u64, &Addr), Ballot> = Map::new("ballots");
 pub const PROPOSAL_HOOKS: Hooks = Hooks::new("proposal_hooks");
 /// Consumers of vote hooks.
 pub const VOTE_HOOKS: Hooks = Hooks::new("vote_hooks");
+/// Consumers of proposal outcome hooks.
+pub const OUTCOME_HOOKS: Hooks = Hooks::new("outcome_hooks");
 /// The address of the pre-propose module associated with this
 /// proposal module (if any).
 pub const CREATION_POLICY: Item<ProposalCreationPolicy> = Item::new("creation_policy");
+/// The quorum source used for new proposals. If unset, quorum is
+/// computed against total voting power.
+pub const QUORUM_SOURCE: Item<QuorumSource> = Item::new("quorum_source");
+/// The vote rebate applied to new proposals, if any.
+pub const VOTE_REBATE: Item<VoteRebateConfig> = Item::new("vote_rebate");
+/// Vote rebates for proposals created while a rebate was configured.
+pub const PROPOSAL_REBATES: Map<u64, ProposalRebate> = Map::new("proposal_rebates");
+/// Voters who have claimed their rebate for a proposal.
+pub const REBATES_CLAIMED: Map<(u64, &Addr), Empty> = Map::new("rebates_claimed");
+/// The ballot cap applied to new proposals, if any.
+pub const BALLOT_CAP: Item<BallotCap> = Item::new("ballot_cap");
+/// Ballot caps for proposals created while a cap was configured.
+pub const PROPOSAL_BALLOT_CAPS: Map<u64, BallotCap> = Map::new("proposal_ballot_caps");
+/// The operations admin, if one is set.
+pub const OPERATIONS_ADMIN: Item<OperationsAdmin> = Item::new("operations_admin");
+/// The configuration for critical proposals. If unset, critical
+/// proposals may not be created.
+pub const CRITICAL_CONFIG: Item<CriticalConfig> = Item::new("critical_config");
+/// Critical proposals by proposal ID.
+pub const CRITICAL_PROPOSALS: Map<u64, CriticalProposal> = Map::new("critical_proposals");
+/// Filtered proposal hook subscriptions by ID.
+pub const SUBSCRIPTIONS: Map<u64, Subscription> = Map::new("subscriptions");
+/// The number of subscriptions that have been created.
+pub const SUBSCRIPTION_COUNT: Item<u64> = Item::new("subscription_count");
+/// The SHA-256 hash of the full record of each archived proposal,
+/// serialized as JSON, taken before its description and messages were
+/// removed.
+pub const ARCHIVED_PROPOSALS: Map<u64, Binary> = Map::new("archived_proposals");
+/// The last proposal examined by archival.
+pub const ARCHIVE_CURSOR: Item<u64> = Item::new("archive_cursor");
+/// A proposal's discussion record, committed to by its proposer
+/// before voting.
+#[cw_serde]
+pub struct Discussion {
+    /// Where the proposal is discussed.
+    pub uri: String,
+    /// A hash of the discussion record as it stood when it was
+    /// frozen, if the proposer committed to one.
+    pub freeze_hash: Option<HexBinary>,
+}
+
+/// Discussion records by proposal ID. Entries are never changed once
+/// saved.
+pub const DISCUSSIONS: Map<u64, Discussion> = Map::new("discussions");
+/// Set while council mode is enabled, to the config from before it was
+/// enabled. Proposals are executed as soon as they pass.
+pub const COUNCIL_MODE: Item<Config> = Item::new("council_mode");
+/// Set while instant finality is enabled. Proposals created while it
+/// is set are decided as soon as every vote has been cast.
+pub const INSTANT_FINALITY: Item<Empty> = Item::new("instant_finality");
+/// Pre-propose modules replaced by `SwapPreProposeModule`, keyed by
+/// the ID of the last proposal created before they were replaced.
+/// Proposals up to that ID send their completion hooks to the module
+/// that holds their deposits rather than to its replacement.
+pub const PREVIOUS_PRE_PROPOSE_MODULES: Map<u64, Addr> = Map::new("previous_pre_propose_modules");
+/// Set while a pre-propose module instantiated by
+/// `SwapPreProposeModule` awaits validation in its instantiation
+/// reply.
+pub const PENDING_PRE_PROPOSE_SWAP: Item<Empty> = Item::new("pending_pre_propose_swap");
@@ -1,6 +1,11 @@
-use cosmwasm_std::Empty;
+use cosmwasm_std::{
+    to_json_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult, Uint128,
+};
 
 use cw_multi_test::{Contract, ContractWrapper};
+use cw_storage_plus::Item;
+use dao_hooks::escalation::{EscalationHookExecuteMsg, EscalationHookMsg};
+use dao_interface::activity::{ActivityOracleQuery, InactivePowerAtHeightResponse};
 use dao_pre_propose_single as cppbps;
 
 pub(crate) fn cw20_base_contract() -> Box<dyn Contract<Empty>> {
@@ -66,7 +71,8 @@ pub(crate) fn pre_propose_single_contract() -> Box<dyn Contract<Empty>> {
         cppbps::contract::execute,
         cppbps::contract::instantiate,
         cppbps::contract::query,
-    );
+    )
+    .with_reply(cppbps::contract::reply);
     Box::new(contract)
 }
 
@@ -117,3 +123,80 @@ pub(crate) fn cw4_voting_contract() -> Box<dyn Contract<Empty>> {
     .with_reply(dao_voting_cw4::contract::reply);
     Box::new(contract)
 }
+
+const INACTIVE_POWER: Item<Uint128> = Item::new("inactive_power");
+
+fn activity_oracle_instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: Uint128,
+) -> StdResult<Response> {
+    INACTIVE_POWER.save(deps.storage, &msg)?;
+    Ok(Response::default())
+}
+
+fn activity_oracle_execute(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: Empty,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+fn activity_oracle_query(deps: Deps, env: Env, msg: ActivityOracleQuery) -> StdResult<Binary> {
+    match msg {
+        ActivityOracleQuery::InactivePowerAtHeight { height } => {
+            to_json_binary(&InactivePowerAtHeightResponse {
+                power: INACTIVE_POWER.load(deps.storage)?,
+                height: height.unwrap_or(env.block.height),
+            })
+        }
+    }
+}
+
+/// An activity oracle that reports a fixed amount of inactive voting
+/// power, set at instantiation.
+pub(crate) fn activity_oracle_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        activity_oracle_execute,
+        activity_oracle_instantiate,
+        activity_oracle_query,
+    );
+    Box::new(contract)
+}
+
+const ESCALATED: Item<Vec<u64>> = Item::new("escalated");
+
+fn council_instantiate(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: Empty,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+fn council_execute(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: EscalationHookExecuteMsg,
+) -> StdResult<Response> {
+    let EscalationHookExecuteMsg::EscalationHook(EscalationHookMsg::ProposalEscalated { id }) = msg;
+    let mut escalated = ESCALATED.may_load(deps.storage)?.unwrap_or_default();
+    escalated.push(id);
+    ESCALATED.save(deps.storage, &escalated)?;
+    Ok(Response::default())
+}
+
+fn council_query(deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+    to_json_binary(&ESCALATED.may_load(deps.storage)?.unwrap_or_default())
+}
+
+/// A council that records the IDs of proposals escalated to it.
+pub(crate) fn council_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(council_execute, council_instantiate, council_query);
+    Box::new(contract)
+}
@@ -449,3 +449,20 @@ pub(crate) fn remove_vote_hook_should_fail(
     .downcast()
     .unwrap()
 }
+
+pub(crate) fn add_outcome_hook(
+    app: &mut App,
+    proposal_module: &Addr,
+    sender: &str,
+    hook_addr: &str,
+) {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        proposal_module.clone(),
+        &ExecuteMsg::AddOutcomeHook {
+            address: hook_addr.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+}
@@ -8,7 +8,10 @@ use dao_voting::pre_propose::ProposalCreationPolicy;
 
 use crate::{
     msg::QueryMsg,
-    query::{ProposalListResponse, ProposalResponse, VoteListResponse, VoteResponse},
+    query::{
+        PendingVotesResponse, ProposalListResponse, ProposalOutcomeResponse, ProposalResponse,
+        VoteListResponse, VoteResponse,
+    },
     state::Config,
 };
 
@@ -48,7 +51,30 @@ pub(crate) fn query_list_proposals(
     app.wrap()
         .query_wasm_smart(
             proposal_single,
-            &QueryMsg::ListProposals { start_after, limit },
+            &QueryMsg::ListProposals {
+                start_after,
+                limit,
+                descending: None,
+            },
+        )
+        .unwrap()
+}
+
+pub(crate) fn query_pending_votes(
+    app: &App,
+    proposal_single: &Addr,
+    voter: &str,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> PendingVotesResponse {
+    app.wrap()
+        .query_wasm_smart(
+            proposal_single,
+            &QueryMsg::PendingVotes {
+                voter: voter.to_string(),
+                start_after,
+                limit,
+            },
         )
         .unwrap()
 }
@@ -67,6 +93,7 @@ pub(crate) fn query_list_votes(
                 proposal_id,
                 start_after,
                 limit,
+                descending: None,
             },
         )
         .unwrap()
@@ -101,6 +128,22 @@ pub(crate) fn query_vote_hooks(app: &App, proposal_single: &Addr) -> HooksRespon
         .unwrap()
 }
 
+pub(crate) fn query_outcome_hooks(app: &App, proposal_single: &Addr) -> HooksResponse {
+    app.wrap()
+        .query_wasm_smart(proposal_single, &QueryMsg::OutcomeHooks {})
+        .unwrap()
+}
+
+pub(crate) fn query_proposal_outcome(
+    app: &App,
+    proposal_single: &Addr,
+    proposal_id: u64,
+) -> ProposalOutcomeResponse {
+    app.wrap()
+        .query_wasm_smart(proposal_single, &QueryMsg::ProposalOutcome { proposal_id })
+        .unwrap()
+}
+
 pub(crate) fn query_list_proposals_reverse(
     app: &App,
     proposal_single: &Addr,
@@ -142,6 +185,7 @@ pub(crate) fn query_single_proposal_module(app: &App, core_addr: &Addr) -> Addr
             &dao_interface::msg::QueryMsg::ProposalModules {
                 start_after: None,
                 limit: None,
+                descending: None,
             },
         )
         .unwrap();
@@ -2,7 +2,7 @@ use cosmwasm_std::{
     coins,
     testing::{mock_dependencies, mock_env},
     to_json_binary, Addr, Attribute, BankMsg, Binary, ContractInfoResponse, CosmosMsg, Decimal, Empty,
-    Reply, StdError, SubMsgResult, Uint128, WasmMsg, WasmQuery,
+    HexBinary, Reply, StdError, SubMsgResult, Uint128, WasmMsg, WasmQuery,
 };
 use cw2::ContractVersion;
 use cw20::Cw20Coin;
@@ -20,8 +20,8 @@ use dao_voting::{
     pre_propose::{PreProposeInfo, ProposalCreationPolicy},
     proposal::{SingleChoiceProposeMsg as ProposeMsg, MAX_PROPOSAL_SIZE},
     reply::{
-        failed_pre_propose_module_hook_id, mask_proposal_execution_proposal_id,
-        mask_proposal_hook_index, mask_vote_hook_index,
+        failed_pre_propose_module_hook_id, mask_outcome_hook_index,
+        mask_proposal_execution_proposal_id, mask_proposal_hook_index, mask_vote_hook_index,
     },
     status::Status,
     threshold::{ActiveThreshold, PercentageThreshold, Threshold},
@@ -30,18 +30,24 @@ use dao_voting::{
 
 use crate::{
     contract::{migrate, CONTRACT_NAME, CONTRACT_VERSION},
-    msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
+    msg::{
+        ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, UncheckedCriticalConfig,
+        UncheckedQuorumSource,
+    },
     proposal::SingleChoiceProposal,
-    query::{ProposalResponse, VoteInfo},
-    state::Config,
+    query::{ProposalListResponse, ProposalResponse, SubscriptionResponse, VoteInfo},
+    state::{
+        BallotCap, Config, ConfigField, CriticalProposal, Discussion, ExcessPower, OperationsAdmin,
+        ProposalRebate, QuorumSource, SubscriptionFilter, VoteRebateConfig,
+    },
     testing::{
         contracts::{
-            cw20_base_contract, cw20_stake_contract, cw20_staked_balances_voting_contract,
-            cw_core_contract, pre_propose_single_contract, proposal_single_contract,
-            v1_proposal_single_contract,
+            activity_oracle_contract, council_contract, cw20_base_contract, cw20_stake_contract,
+            cw20_staked_balances_voting_contract, cw_core_contract, pre_propose_single_contract,
+            proposal_single_contract, v1_proposal_single_contract,
         },
         execute::{
-            add_proposal_hook, add_proposal_hook_should_fail, add_vote_hook,
+            add_outcome_hook, add_proposal_hook, add_proposal_hook_should_fail, add_vote_hook,
             add_vote_hook_should_fail, close_proposal, close_proposal_should_fail,
             execute_proposal, execute_proposal_should_fail, instantiate_cw20_base_default,
             make_proposal, mint_cw20s, mint_natives, remove_proposal_hook,
@@ -57,10 +63,11 @@ use crate::{
         queries::{
             query_balance_cw20, query_balance_native, query_creation_policy, query_dao_token,
             query_deposit_config_and_pre_propose_module, query_list_proposals,
-            query_list_proposals_reverse, query_list_votes, query_pre_proposal_single_config,
+            query_list_proposals_reverse, query_list_votes, query_outcome_hooks,
+            query_pending_votes, query_pre_proposal_single_config,
             query_pre_proposal_single_deposit_info, query_proposal, query_proposal_config,
-            query_proposal_hooks, query_single_proposal_module, query_vote_hooks,
-            query_voting_module,
+            query_proposal_hooks, query_proposal_outcome, query_single_proposal_module,
+            query_vote_hooks, query_voting_module,
         },
     },
     ContractError,
@@ -127,7 +134,9 @@ fn test_simple_propose_staked_balances() {
             threshold: PercentageThreshold::Majority {},
         },
         allow_revoting: false,
+        instant_finality: false,
         total_power: Uint128::new(100_000_000),
+        quorum_power: None,
         msgs: vec![],
         status: Status::Open,
         votes: Votes::zero(),
@@ -176,7 +185,9 @@ fn test_simple_proposal_cw4_voting() {
             quorum: PercentageThreshold::Majority {},
         },
         allow_revoting: false,
+        instant_finality: false,
         total_power: Uint128::new(1),
+        quorum_power: None,
         msgs: vec![],
         status: Status::Open,
         votes: Votes::zero(),
@@ -280,7 +291,9 @@ fn test_instantiate_with_non_voting_module_cw20_deposit() {
             quorum: PercentageThreshold::Majority {},
         },
         allow_revoting: false,
+        instant_finality: false,
         total_power: Uint128::new(1),
+        quorum_power: None,
         msgs: vec![],
         status: Status::Open,
         votes: Votes::zero(),
@@ -670,11 +683,34 @@ fn test_anyone_may_propose_and_proposal_listing() {
     // Now that we've got all these proposals sitting around, lets
     // test that we can query them.
 
-    let proposals_forward = query_list_proposals(&app, &proposal_module, None, None);
-    let mut proposals_reverse = query_list_proposals_reverse(&app, &proposal_module, None, None);
+    let proposals_forward = query_list_proposals(&app, &proposal_module, None, Some(30));
+    let mut proposals_reverse =
+        query_list_proposals_reverse(&app, &proposal_module, None, Some(30));
+
+    // Listing in descending order matches the reverse query.
+    let proposals_descending: ProposalListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_module.clone(),
+            &QueryMsg::ListProposals {
+                start_after: Some(12),
+                limit: Some(30),
+                descending: Some(true),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        proposals_descending.proposals,
+        proposals_reverse.proposals[1..]
+    );
+
     proposals_reverse.proposals.reverse();
     assert_eq!(proposals_reverse, proposals_forward);
 
+    // Without a limit, only the first ten are listed.
+    let first_ten = query_list_proposals(&app, &proposal_module, None, None);
+    assert_eq!(first_ten.proposals, proposals_forward.proposals[..10]);
+
     // Check the proposers and (implicitly) the ordering.
     for (index, addr) in ('m'..'z').enumerate() {
         let addr = addr.to_string().repeat(6);
@@ -711,7 +747,9 @@ fn test_anyone_may_propose_and_proposal_listing() {
                     threshold: PercentageThreshold::Majority {},
                 },
                 allow_revoting: false,
+                instant_finality: false,
                 total_power: Uint128::new(100_000_000),
+                quorum_power: None,
                 msgs: vec![],
                 status: Status::Executed,
                 votes: Votes {
@@ -719,7 +757,9 @@ fn test_anyone_may_propose_and_proposal_listing() {
                     no: Uint128::zero(),
                     abstain: Uint128::zero()
                 },
-            }
+            },
+            archived: false,
+            discussion: None,
         }
     )
 }
@@ -1981,7 +2021,9 @@ fn test_reply_proposal_mock() {
                     percentage: PercentageThreshold::Majority {},
                 },
                 allow_revoting: false,
+                instant_finality: false,
                 total_power: Uint128::new(100_000_000),
+                quorum_power: None,
                 msgs: vec![],
                 status: Status::Open,
                 votes: Votes::zero(),
@@ -2155,7 +2197,7 @@ fn test_proposal_creation_permissions() {
 #[test]
 fn test_reply_hooks_mock() {
     use crate::contract::reply;
-    use crate::state::{CREATION_POLICY, PROPOSAL_HOOKS, VOTE_HOOKS};
+    use crate::state::{CREATION_POLICY, OUTCOME_HOOKS, PROPOSAL_HOOKS, VOTE_HOOKS};
 
     let mut deps = mock_dependencies();
     let env = mock_env();
@@ -2224,7 +2266,7 @@ fn test_reply_hooks_mock() {
         id: m_vote_hook_idx,
         result: SubMsgResult::Err("error_msg".to_string()),
     };
-    let res = reply(deps.as_mut(), env, reply_msg).unwrap();
+    let res = reply(deps.as_mut(), env.clone(), reply_msg).unwrap();
     assert_eq!(
         res.attributes[0],
         Attribute {
@@ -2232,6 +2274,153 @@ fn test_reply_hooks_mock() {
             value: format! {"{CREATOR_ADDR}:{}", 0}
         }
     );
+
+    // Outcome hook
+    let m_outcome_hook_idx = mask_outcome_hook_index(0);
+    OUTCOME_HOOKS
+        .add_hook(deps.as_mut().storage, Addr::unchecked(CREATOR_ADDR))
+        .unwrap();
+
+    let reply_msg = Reply {
+        id: m_outcome_hook_idx,
+        result: SubMsgResult::Err("error_msg".to_string()),
+    };
+    let res = reply(deps.as_mut(), env, reply_msg).unwrap();
+    assert_eq!(
+        res.attributes[0],
+        Attribute {
+            key: "removed_outcome_hook".to_string(),
+            value: format! {"{CREATOR_ADDR}:{}", 0}
+        }
+    );
+}
+
+#[test]
+fn test_proposal_outcome() {
+    let CommonTest {
+        mut app,
+        core_addr,
+        proposal_module,
+        gov_token: _,
+        proposal_id,
+    } = setup_test(vec![]);
+
+    // "outcomehook" is not a contract, so executing the hook will
+    // fail and it will be removed.
+    add_outcome_hook(
+        &mut app,
+        &proposal_module,
+        core_addr.as_str(),
+        "outcomehook",
+    );
+    let outcome_hooks = query_outcome_hooks(&app, &proposal_module);
+    assert_eq!(outcome_hooks.hooks, vec!["outcomehook".to_string()]);
+
+    let outcome = query_proposal_outcome(&app, &proposal_module, proposal_id);
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(outcome.status, Status::Open);
+    assert_eq!(outcome.yes, Uint128::zero());
+    assert_eq!(outcome.total_power, proposal.proposal.total_power);
+    assert_eq!(outcome.height, proposal.proposal.start_height);
+
+    vote_on_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        proposal_id,
+        Vote::Yes,
+    );
+
+    let outcome = query_proposal_outcome(&app, &proposal_module, proposal_id);
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(outcome.status, Status::Passed);
+    assert_eq!(outcome.yes, proposal.proposal.votes.yes);
+    assert_eq!(outcome.no, Uint128::zero());
+    assert_eq!(outcome.abstain, Uint128::zero());
+
+    let outcome_hooks = query_outcome_hooks(&app, &proposal_module);
+    assert!(outcome_hooks.hooks.is_empty());
+}
+
+#[test]
+fn test_pending_votes() {
+    let CommonTest {
+        mut app,
+        core_addr: _,
+        proposal_module,
+        gov_token: _,
+        proposal_id,
+    } = setup_test(vec![]);
+    let second = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+    let third = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+
+    let pending = query_pending_votes(&app, &proposal_module, CREATOR_ADDR, None, None);
+    let ids: Vec<u64> = pending.proposals.iter().map(|p| p.id).collect();
+    assert_eq!(ids, vec![proposal_id, second, third]);
+
+    // Voting on a proposal removes it from the list. As the creator
+    // holds all voting power, this also passes the proposal.
+    vote_on_proposal(&mut app, &proposal_module, CREATOR_ADDR, second, Vote::Yes);
+    let pending = query_pending_votes(&app, &proposal_module, CREATOR_ADDR, None, None);
+    let ids: Vec<u64> = pending.proposals.iter().map(|p| p.id).collect();
+    assert_eq!(ids, vec![proposal_id, third]);
+
+    // Proposals that are no longer open are not pending for anyone.
+    let pending = query_pending_votes(&app, &proposal_module, "ekez", None, None);
+    let ids: Vec<u64> = pending.proposals.iter().map(|p| p.id).collect();
+    assert_eq!(ids, vec![proposal_id, third]);
+
+    let pending = query_pending_votes(
+        &app,
+        &proposal_module,
+        CREATOR_ADDR,
+        Some(proposal_id),
+        Some(1),
+    );
+    let ids: Vec<u64> = pending.proposals.iter().map(|p| p.id).collect();
+    assert_eq!(ids, vec![third]);
+    assert_eq!(pending.next_start_after, None);
+
+    // A full page returns a cursor if proposals remain.
+    let pending = query_pending_votes(&app, &proposal_module, CREATOR_ADDR, None, Some(1));
+    let ids: Vec<u64> = pending.proposals.iter().map(|p| p.id).collect();
+    assert_eq!(ids, vec![proposal_id]);
+    assert_eq!(pending.next_start_after, Some(proposal_id));
+}
+
+#[test]
+fn test_pending_votes_scan_limit() {
+    let CommonTest {
+        mut app,
+        core_addr,
+        proposal_module,
+        gov_token,
+        proposal_id: _,
+    } = setup_test(vec![]);
+    mint_cw20s(
+        &mut app,
+        &gov_token,
+        &core_addr,
+        CREATOR_ADDR,
+        1_000_000_000,
+    );
+    for _ in 0..99 {
+        make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+    }
+    // Expire the first 100 proposals.
+    app.update_block(|b| b.time = b.time.plus_seconds(604800));
+    let open = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+
+    // Only 100 proposals are examined per query, so the first page
+    // is empty but has a cursor.
+    let pending = query_pending_votes(&app, &proposal_module, CREATOR_ADDR, None, None);
+    assert!(pending.proposals.is_empty());
+    assert_eq!(pending.next_start_after, Some(100));
+
+    let pending = query_pending_votes(&app, &proposal_module, CREATOR_ADDR, Some(100), None);
+    let ids: Vec<u64> = pending.proposals.iter().map(|p| p.id).collect();
+    assert_eq!(ids, vec![open]);
+    assert_eq!(pending.next_start_after, None);
 }
 
 #[test]
@@ -2514,6 +2703,111 @@ fn test_update_pre_propose_module() {
     assert_eq!(balance, Uint128::new(10_000_000));
 }
 
+#[test]
+fn test_swap_pre_propose_module() {
+    let CommonTest {
+        mut app,
+        core_addr,
+        proposal_module,
+        gov_token,
+        proposal_id: pre_swap_proposal_id,
+    } = setup_test(vec![]);
+
+    let pre_propose_id = app.store_code(pre_propose_single_contract());
+    let swap = ExecuteMsg::SwapPreProposeModule {
+        info: PreProposeInfo::ModuleMayPropose {
+            info: ModuleInstantiateInfo {
+                code_id: pre_propose_id,
+                msg: to_json_binary(&dao_pre_propose_single::InstantiateMsg {
+                    deposit_info: Some(UncheckedDepositInfo {
+                        denom: dao_voting::deposit::DepositToken::VotingModuleToken {},
+                        amount: Uint128::new(1),
+                        refund_policy: dao_voting::deposit::DepositRefundPolicy::OnlyPassed,
+                    }),
+                    open_proposal_submission: false,
+                    extension: Empty::default(),
+                })
+                .unwrap(),
+                admin: Some(Admin::CoreModule {}),
+                funds: vec![],
+                label: "new pre-propose module".to_string(),
+            },
+        },
+    };
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &swap,
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    // Make a proposal to swap to a new pre-propose module.
+    mint_cw20s(&mut app, &gov_token, &core_addr, CREATOR_ADDR, 10_000_000);
+    let proposal_id = make_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        vec![WasmMsg::Execute {
+            contract_addr: proposal_module.to_string(),
+            msg: to_json_binary(&swap).unwrap(),
+            funds: vec![],
+        }
+        .into()],
+    );
+    vote_on_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        proposal_id,
+        Vote::Yes,
+    );
+    execute_proposal(&mut app, &proposal_module, CREATOR_ADDR, proposal_id);
+
+    // The swap proposal's own deposit was returned by the old module.
+    let balance = query_balance_cw20(&app, gov_token.as_str(), CREATOR_ADDR);
+    assert_eq!(balance, Uint128::new(10_000_000));
+
+    let pre_propose = match query_creation_policy(&app, &proposal_module) {
+        ProposalCreationPolicy::Anyone {} => panic!("expected a pre-propose module"),
+        ProposalCreationPolicy::Module { addr } => addr,
+    };
+    let pre_propose_config = query_pre_proposal_single_config(&app, &pre_propose);
+    assert_eq!(
+        pre_propose_config.deposit_info.unwrap().amount,
+        Uint128::new(1)
+    );
+
+    // Proposals made with the new module pay its deposit.
+    make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+    let balance = query_balance_cw20(&app, gov_token.as_str(), CREATOR_ADDR);
+    assert_eq!(balance, Uint128::new(9_999_999));
+
+    // Unlike with `UpdatePreProposeInfo`, the proposal created before
+    // the swap has its deposit returned by the old module when it
+    // completes.
+    vote_on_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        pre_swap_proposal_id,
+        Vote::Yes,
+    );
+    execute_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        pre_swap_proposal_id,
+    );
+    let balance = query_balance_cw20(&app, gov_token.as_str(), CREATOR_ADDR);
+    assert_eq!(balance, Uint128::new(19_999_999));
+}
+
 /// DAO should be admin of the pre-propose contract despite the fact
 /// that the proposal module instantiates it.
 #[test]
@@ -2687,3 +2981,1377 @@ fn test_proposal_count_goes_up() {
     let next = query_next_proposal_id(&app, &proposal_module);
     assert_eq!(next, 3);
 }
+
+#[test]
+fn test_quorum_source() {
+    let mut app = App::default();
+    let instantiate = get_default_non_token_dao_proposal_module_instantiate(&mut app);
+    let core_addr = instantiate_with_cw4_groups_governance(
+        &mut app,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: CREATOR_ADDR.to_string(),
+                amount: Uint128::new(10),
+            },
+            Cw20Coin {
+                address: "dormant".to_string(),
+                amount: Uint128::new(90),
+            },
+        ]),
+    );
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+
+    let oracle_id = app.store_code(activity_oracle_contract());
+    let oracle = app
+        .instantiate_contract(
+            oracle_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &Uint128::new(90),
+            &[],
+            "oracle",
+            None,
+        )
+        .unwrap();
+
+    let quorum_source: QuorumSource = app
+        .wrap()
+        .query_wasm_smart(&proposal_module, &QueryMsg::QuorumSource {})
+        .unwrap();
+    assert_eq!(quorum_source, QuorumSource::TotalPower {});
+
+    // Only the DAO may update the quorum source.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &ExecuteMsg::UpdateQuorumSource {
+                quorum_source: UncheckedQuorumSource::ActivePower {
+                    oracle: oracle.to_string(),
+                },
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    // Against total power, a 10% turnout does not meet the majority
+    // quorum.
+    let total = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+    vote_on_proposal(&mut app, &proposal_module, CREATOR_ADDR, total, Vote::Yes);
+
+    app.execute_contract(
+        core_addr,
+        proposal_module.clone(),
+        &ExecuteMsg::UpdateQuorumSource {
+            quorum_source: UncheckedQuorumSource::ActivePower {
+                oracle: oracle.to_string(),
+            },
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Against active power, the same turnout is 100%.
+    let active = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+    vote_on_proposal(&mut app, &proposal_module, CREATOR_ADDR, active, Vote::Yes);
+
+    let proposal = query_proposal(&app, &proposal_module, active);
+    assert_eq!(proposal.proposal.total_power, Uint128::new(100));
+    assert_eq!(proposal.proposal.quorum_power, Some(Uint128::new(10)));
+
+    app.update_block(|block| block.time = block.time.plus_seconds(604800));
+
+    let proposal = query_proposal(&app, &proposal_module, total);
+    assert_eq!(proposal.proposal.status, Status::Rejected);
+    let proposal = query_proposal(&app, &proposal_module, active);
+    assert_eq!(proposal.proposal.status, Status::Passed);
+}
+
+#[test]
+fn test_vote_rebates() {
+    let mut app = App::default();
+    let instantiate = get_default_non_token_dao_proposal_module_instantiate(&mut app);
+    let core_addr = instantiate_with_cw4_groups_governance(
+        &mut app,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: CREATOR_ADDR.to_string(),
+                amount: Uint128::new(10),
+            },
+            Cw20Coin {
+                address: "voter".to_string(),
+                amount: Uint128::new(30),
+            },
+            Cw20Coin {
+                address: "third".to_string(),
+                amount: Uint128::new(20),
+            },
+            Cw20Coin {
+                address: "minnow".to_string(),
+                amount: Uint128::new(1),
+            },
+            Cw20Coin {
+                address: "dormant".to_string(),
+                amount: Uint128::new(39),
+            },
+        ]),
+    );
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+    mint_natives(&mut app, proposal_module.as_str(), coins(100, "ujuno"));
+
+    // Proposals created before a rebate is configured have none.
+    let unfunded = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+
+    let rebate = VoteRebateConfig {
+        denom: "ujuno".to_string(),
+        amount: Uint128::new(10),
+        proposal_budget: Uint128::new(20),
+        min_power: Uint128::new(5),
+    };
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &ExecuteMsg::UpdateVoteRebate {
+                rebate: Some(rebate.clone()),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    let err: ContractError = app
+        .execute_contract(
+            core_addr.clone(),
+            proposal_module.clone(),
+            &ExecuteMsg::UpdateVoteRebate {
+                rebate: Some(VoteRebateConfig {
+                    amount: Uint128::new(30),
+                    ..rebate.clone()
+                }),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::InvalidVoteRebate {}));
+
+    app.execute_contract(
+        core_addr.clone(),
+        proposal_module.clone(),
+        &ExecuteMsg::UpdateVoteRebate {
+            rebate: Some(rebate.clone()),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let funded = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+    for voter in [CREATOR_ADDR, "voter", "third", "minnow"] {
+        vote_on_proposal(&mut app, &proposal_module, voter, funded, Vote::Yes);
+    }
+    let no_quorum = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+    vote_on_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        no_quorum,
+        Vote::Yes,
+    );
+
+    let claim = |app: &mut App, voter: &str, proposal_id: u64| {
+        app.execute_contract(
+            Addr::unchecked(voter),
+            proposal_module.clone(),
+            &ExecuteMsg::ClaimVoteRebate { proposal_id },
+            &[],
+        )
+    };
+
+    // Voting closes when the proposal expires.
+    let err: ContractError = claim(&mut app, CREATOR_ADDR, funded)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::RebateVotingOpen {}));
+
+    app.update_block(|block| block.time = block.time.plus_seconds(604800));
+
+    let err: ContractError = claim(&mut app, CREATOR_ADDR, unfunded)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NoVoteRebate { id } if id == unfunded));
+    let err: ContractError = claim(&mut app, CREATOR_ADDR, no_quorum)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::RebateQuorumNotReached {}));
+    let err: ContractError = claim(&mut app, "minnow", funded)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::RebatePowerTooLow {}));
+    let err: ContractError = claim(&mut app, "dormant", funded)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NoSuchVote { .. }));
+
+    claim(&mut app, CREATOR_ADDR, funded).unwrap();
+    assert_eq!(
+        query_balance_native(&app, CREATOR_ADDR, "ujuno"),
+        Uint128::new(10)
+    );
+    let err: ContractError = claim(&mut app, CREATOR_ADDR, funded)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::RebateClaimed {}));
+
+    claim(&mut app, "voter", funded).unwrap();
+    let proposal_rebate: Option<ProposalRebate> = app
+        .wrap()
+        .query_wasm_smart(
+            &proposal_module,
+            &QueryMsg::ProposalRebate {
+                proposal_id: funded,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        proposal_rebate,
+        Some(ProposalRebate {
+            config: rebate,
+            remaining: Uint128::zero(),
+        })
+    );
+
+    // The budget for the proposal only covers two rebates.
+    let err: ContractError = claim(&mut app, "third", funded)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::RebateBudgetExhausted {}));
+
+    app.execute_contract(
+        core_addr.clone(),
+        proposal_module.clone(),
+        &ExecuteMsg::WithdrawRebatePool {
+            amount: cosmwasm_std::coin(80, "ujuno"),
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        query_balance_native(&app, core_addr.as_str(), "ujuno"),
+        Uint128::new(80)
+    );
+}
+
+#[test]
+fn test_operations_admin() {
+    let mut app = App::default();
+    let instantiate = get_default_non_token_dao_proposal_module_instantiate(&mut app);
+    let core_addr = instantiate_with_cw4_groups_governance(&mut app, instantiate, None);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ops"),
+            proposal_module.clone(),
+            &ExecuteMsg::UpdateAllowRevoting {
+                allow_revoting: true,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    // Only the DAO may set the operations admin.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ops"),
+            proposal_module.clone(),
+            &ExecuteMsg::UpdateOperationsAdmin {
+                address: Some("ops".to_string()),
+                fields: vec![ConfigField::Threshold],
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    app.execute_contract(
+        core_addr.clone(),
+        proposal_module.clone(),
+        &ExecuteMsg::UpdateOperationsAdmin {
+            address: Some("ops".to_string()),
+            fields: vec![ConfigField::VotingPeriod, ConfigField::AllowRevoting],
+        },
+        &[],
+    )
+    .unwrap();
+    let admin: Option<OperationsAdmin> = app
+        .wrap()
+        .query_wasm_smart(proposal_module.clone(), &QueryMsg::OperationsAdmin {})
+        .unwrap();
+    assert_eq!(
+        admin,
+        Some(OperationsAdmin {
+            address: Addr::unchecked("ops"),
+            fields: vec![ConfigField::VotingPeriod, ConfigField::AllowRevoting],
+        })
+    );
+
+    // Updates made by the operations admin are validated.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ops"),
+            proposal_module.clone(),
+            &ExecuteMsg::UpdateVotingPeriod {
+                min_voting_period: Some(Duration::Height(10)),
+                max_voting_period: Duration::Time(100),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::VotingError(_)));
+
+    app.execute_contract(
+        Addr::unchecked("ops"),
+        proposal_module.clone(),
+        &ExecuteMsg::UpdateVotingPeriod {
+            min_voting_period: Some(Duration::Time(10)),
+            max_voting_period: Duration::Time(100),
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("ops"),
+        proposal_module.clone(),
+        &ExecuteMsg::UpdateAllowRevoting {
+            allow_revoting: true,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Fields the operations admin has not been permitted to update
+    // remain with the DAO.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ops"),
+            proposal_module.clone(),
+            &ExecuteMsg::UpdateThreshold {
+                threshold: Threshold::AbsolutePercentage {
+                    percentage: PercentageThreshold::Majority {},
+                },
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+    app.execute_contract(
+        core_addr.clone(),
+        proposal_module.clone(),
+        &ExecuteMsg::UpdateThreshold {
+            threshold: Threshold::AbsolutePercentage {
+                percentage: PercentageThreshold::Majority {},
+            },
+        },
+        &[],
+    )
+    .unwrap();
+
+    let config = query_proposal_config(&app, &proposal_module);
+    assert_eq!(
+        config.threshold,
+        Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        }
+    );
+    assert_eq!(config.min_voting_period, Some(Duration::Time(10)));
+    assert_eq!(config.max_voting_period, Duration::Time(100));
+    assert!(config.allow_revoting);
+    assert!(config.only_members_execute);
+
+    app.execute_contract(
+        core_addr,
+        proposal_module.clone(),
+        &ExecuteMsg::UpdateOperationsAdmin {
+            address: None,
+            fields: vec![],
+        },
+        &[],
+    )
+    .unwrap();
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ops"),
+            proposal_module,
+            &ExecuteMsg::UpdateAllowRevoting {
+                allow_revoting: false,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+}
+
+#[test]
+fn test_critical_proposal_escalation() {
+    let mut app = App::default();
+    let instantiate = get_default_non_token_dao_proposal_module_instantiate(&mut app);
+    let core_addr = instantiate_with_cw4_groups_governance(&mut app, instantiate, None);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+    let council_id = app.store_code(council_contract());
+    let council = app
+        .instantiate_contract(
+            council_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &Empty {},
+            &[],
+            "council",
+            None,
+        )
+        .unwrap();
+
+    let propose_critical = ExecuteMsg::ProposeCritical(ProposeMsg {
+        title: "Pause the bridge".to_string(),
+        description: "It is being exploited".to_string(),
+        msgs: vec![],
+        proposer: None,
+    });
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &propose_critical,
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::CriticalProposalsDisabled {}));
+
+    // The critical voting period must be in the same units as, and
+    // no longer than, the maximum voting period.
+    let err: ContractError = app
+        .execute_contract(
+            core_addr.clone(),
+            proposal_module.clone(),
+            &ExecuteMsg::UpdateCriticalConfig {
+                config: Some(UncheckedCriticalConfig {
+                    proposers: vec![CREATOR_ADDR.to_string()],
+                    voting_period: Duration::Height(10),
+                    council: council.to_string(),
+                }),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::VotingError(_)));
+    app.execute_contract(
+        core_addr,
+        proposal_module.clone(),
+        &ExecuteMsg::UpdateCriticalConfig {
+            config: Some(UncheckedCriticalConfig {
+                proposers: vec![CREATOR_ADDR.to_string()],
+                voting_period: Duration::Time(100),
+                council: council.to_string(),
+            }),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            proposal_module.clone(),
+            &propose_critical,
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    // Critical proposals bypass the pre-propose module.
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        proposal_module.clone(),
+        &propose_critical,
+        &[],
+    )
+    .unwrap();
+    let proposal = query_proposal(&app, &proposal_module, 1);
+    assert_eq!(
+        proposal.proposal.expiration,
+        Duration::Time(100).after(&app.block_info())
+    );
+
+    let escalate = ExecuteMsg::Escalate { proposal_id: 1 };
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            proposal_module.clone(),
+            &escalate,
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::EscalationNotDue {}));
+
+    app.update_block(|block| block.time = block.time.plus_seconds(50));
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        proposal_module.clone(),
+        &escalate,
+        &[],
+    )
+    .unwrap();
+    let escalated: Vec<u64> = app
+        .wrap()
+        .query_wasm_smart(council.clone(), &Empty {})
+        .unwrap();
+    assert_eq!(escalated, vec![1]);
+    let critical: Option<CriticalProposal> = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_module.clone(),
+            &QueryMsg::CriticalProposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert!(critical.unwrap().escalated);
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            proposal_module.clone(),
+            &escalate,
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::AlreadyEscalated {}));
+
+    let decide = ExecuteMsg::DecideEscalated {
+        proposal_id: 1,
+        pass: true,
+    };
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &decide,
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+    app.execute_contract(council, proposal_module.clone(), &decide, &[])
+        .unwrap();
+
+    let proposal = query_proposal(&app, &proposal_module, 1);
+    assert_eq!(proposal.proposal.status, Status::Passed);
+    execute_proposal(&mut app, &proposal_module, CREATOR_ADDR, 1);
+    let proposal = query_proposal(&app, &proposal_module, 1);
+    assert_eq!(proposal.proposal.status, Status::Executed);
+}
+
+#[test]
+fn test_ballot_cap() {
+    let mut app = App::default();
+    let instantiate = get_default_non_token_dao_proposal_module_instantiate(&mut app);
+    let core_addr = instantiate_with_cw4_groups_governance(
+        &mut app,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: "whale".to_string(),
+                amount: Uint128::new(60),
+            },
+            Cw20Coin {
+                address: CREATOR_ADDR.to_string(),
+                amount: Uint128::new(40),
+            },
+        ]),
+    );
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+
+    // Proposals created before a cap is configured count full power.
+    let uncapped = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+
+    let cap = BallotCap {
+        max_share: Decimal::percent(25),
+        excess: ExcessPower::Abstain,
+    };
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("whale"),
+            proposal_module.clone(),
+            &ExecuteMsg::UpdateBallotCap {
+                cap: Some(cap.clone()),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    let err: ContractError = app
+        .execute_contract(
+            core_addr.clone(),
+            proposal_module.clone(),
+            &ExecuteMsg::UpdateBallotCap {
+                cap: Some(BallotCap {
+                    max_share: Decimal::zero(),
+                    ..cap.clone()
+                }),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::InvalidBallotCap {}));
+
+    app.execute_contract(
+        core_addr.clone(),
+        proposal_module.clone(),
+        &ExecuteMsg::UpdateBallotCap {
+            cap: Some(cap.clone()),
+        },
+        &[],
+    )
+    .unwrap();
+    let current: Option<BallotCap> = app
+        .wrap()
+        .query_wasm_smart(&proposal_module, &QueryMsg::BallotCap {})
+        .unwrap();
+    assert_eq!(current, Some(cap.clone()));
+
+    let capped = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+    let snapshot: Option<BallotCap> = app
+        .wrap()
+        .query_wasm_smart(
+            &proposal_module,
+            &QueryMsg::ProposalBallotCap {
+                proposal_id: capped,
+            },
+        )
+        .unwrap();
+    assert_eq!(snapshot, Some(cap.clone()));
+
+    vote_on_proposal(&mut app, &proposal_module, "whale", uncapped, Vote::Yes);
+    let proposal = query_proposal(&app, &proposal_module, uncapped);
+    assert_eq!(proposal.proposal.votes.yes, Uint128::new(60));
+    assert_eq!(proposal.proposal.status, Status::Passed);
+
+    // The whale's power over a quarter of the total is counted as
+    // abstaining.
+    vote_on_proposal(&mut app, &proposal_module, "whale", capped, Vote::Yes);
+    let proposal = query_proposal(&app, &proposal_module, capped);
+    assert_eq!(
+        proposal.proposal.votes,
+        Votes {
+            yes: Uint128::new(25),
+            no: Uint128::zero(),
+            abstain: Uint128::new(35),
+        }
+    );
+
+    app.execute_contract(
+        core_addr.clone(),
+        proposal_module.clone(),
+        &ExecuteMsg::UpdateBallotCap {
+            cap: Some(BallotCap {
+                excess: ExcessPower::Discard,
+                ..cap
+            }),
+        },
+        &[],
+    )
+    .unwrap();
+    let discarded = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+    vote_on_proposal(&mut app, &proposal_module, "whale", discarded, Vote::No);
+    let proposal = query_proposal(&app, &proposal_module, discarded);
+    assert_eq!(
+        proposal.proposal.votes,
+        Votes {
+            yes: Uint128::zero(),
+            no: Uint128::new(25),
+            abstain: Uint128::zero(),
+        }
+    );
+
+    // Removing the cap does not change proposals that snapshotted it.
+    app.execute_contract(
+        core_addr,
+        proposal_module.clone(),
+        &ExecuteMsg::UpdateBallotCap { cap: None },
+        &[],
+    )
+    .unwrap();
+    let snapshot: Option<BallotCap> = app
+        .wrap()
+        .query_wasm_smart(
+            &proposal_module,
+            &QueryMsg::ProposalBallotCap {
+                proposal_id: discarded,
+            },
+        )
+        .unwrap();
+    assert!(snapshot.is_some());
+}
+
+#[test]
+fn test_subscriptions() {
+    let mut app = App::default();
+    let instantiate = get_default_non_token_dao_proposal_module_instantiate(&mut app);
+    let core_addr = instantiate_with_cw4_groups_governance(&mut app, instantiate, None);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &ExecuteMsg::AddSubscription {
+                address: "spends".to_string(),
+                filter: SubscriptionFilter::TreasurySpend {},
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    let err: ContractError = app
+        .execute_contract(
+            core_addr.clone(),
+            proposal_module.clone(),
+            &ExecuteMsg::AddSubscription {
+                address: "grants".to_string(),
+                filter: SubscriptionFilter::Tag {
+                    tag: "two words".to_string(),
+                },
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::InvalidSubscriptionTag {}));
+
+    // None of the subscribers are contracts, so each subscription is
+    // removed the first time a hook is sent to it.
+    for (address, filter) in [
+        ("spends", SubscriptionFilter::TreasurySpend {}),
+        ("config", SubscriptionFilter::ConfigChange {}),
+        (
+            "grants",
+            SubscriptionFilter::Tag {
+                tag: "grants".to_string(),
+            },
+        ),
+    ] {
+        app.execute_contract(
+            core_addr.clone(),
+            proposal_module.clone(),
+            &ExecuteMsg::AddSubscription {
+                address: address.to_string(),
+                filter,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+    let subscribers = |app: &App| -> Vec<String> {
+        let subscriptions: Vec<SubscriptionResponse> = app
+            .wrap()
+            .query_wasm_smart(
+                &proposal_module,
+                &QueryMsg::Subscriptions {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        subscriptions
+            .into_iter()
+            .map(|s| s.subscriber.into_string())
+            .collect()
+    };
+    assert_eq!(subscribers(&app), vec!["spends", "config", "grants"]);
+
+    make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+    assert_eq!(subscribers(&app), vec!["spends", "config", "grants"]);
+
+    make_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        vec![BankMsg::Send {
+            to_address: "grantee".to_string(),
+            amount: coins(10, "ujuno"),
+        }
+        .into()],
+    );
+    assert_eq!(subscribers(&app), vec!["config", "grants"]);
+
+    let pre_propose = match query_creation_policy(&app, &proposal_module) {
+        ProposalCreationPolicy::Module { addr } => addr,
+        ProposalCreationPolicy::Anyone {} => panic!("expected a pre-propose module"),
+    };
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        pre_propose,
+        &dao_pre_propose_single::ExecuteMsg::Propose {
+            msg: dao_pre_propose_single::ProposeMessage::Propose {
+                title: "Grants".to_string(),
+                description: "Renew the #grants program.".to_string(),
+                msgs: vec![],
+            },
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(subscribers(&app), vec!["config"]);
+
+    make_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        vec![WasmMsg::Execute {
+            contract_addr: core_addr.to_string(),
+            msg: to_json_binary(&dao_interface::msg::ExecuteMsg::SetItem {
+                key: "k".to_string(),
+                value: "v".to_string(),
+            })
+            .unwrap(),
+            funds: vec![],
+        }
+        .into()],
+    );
+    assert!(subscribers(&app).is_empty());
+
+    let err: ContractError = app
+        .execute_contract(
+            core_addr,
+            proposal_module,
+            &ExecuteMsg::RemoveSubscription { id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NoSuchSubscription { id: 1 }));
+}
+
+#[test]
+fn test_archive() {
+    use cosmwasm_std::to_json_vec;
+    use sha2::{Digest, Sha256};
+
+    let CommonTest {
+        mut app,
+        core_addr,
+        proposal_module,
+        gov_token: _,
+        proposal_id: executed,
+    } = setup_test(vec![BankMsg::Send {
+        to_address: CREATOR_ADDR.to_string(),
+        amount: coins(1, "ujuno"),
+    }
+    .into()]);
+    mint_natives(&mut app, core_addr.as_str(), coins(1, "ujuno"));
+    vote_on_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        executed,
+        Vote::Yes,
+    );
+    execute_proposal(&mut app, &proposal_module, CREATOR_ADDR, executed);
+    let open = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+    let closed = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+    vote_on_proposal(&mut app, &proposal_module, CREATOR_ADDR, closed, Vote::No);
+    close_proposal(&mut app, &proposal_module, CREATOR_ADDR, closed);
+    app.update_block(next_block);
+
+    let before = query_proposal(&app, &proposal_module, executed);
+    assert!(!before.archived);
+
+    let cutoff = app.block_info().height;
+    let archive = |limit: u32| ExecuteMsg::Archive {
+        older_than_height: cutoff,
+        limit: Some(limit),
+    };
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &archive(10),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    // Proposals are examined a few at a time.
+    let msg = archive(1);
+    app.execute_contract(core_addr.clone(), proposal_module.clone(), &msg, &[])
+        .unwrap();
+    let after = query_proposal(&app, &proposal_module, executed);
+    assert!(after.archived);
+    assert_eq!(after.proposal.status, Status::Executed);
+    assert_eq!(after.proposal.votes, before.proposal.votes);
+    assert_eq!(after.proposal.description, "");
+    assert!(after.proposal.msgs.is_empty());
+    let hash: Option<Binary> = app
+        .wrap()
+        .query_wasm_smart(
+            &proposal_module,
+            &QueryMsg::ProposalArchiveHash {
+                proposal_id: executed,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        hash,
+        Some(Binary::from(
+            Sha256::digest(to_json_vec(&before.proposal).unwrap()).as_slice()
+        ))
+    );
+    assert!(!query_proposal(&app, &proposal_module, closed).archived);
+
+    // The open proposal is skipped, then the closed one is archived.
+    app.execute_contract(core_addr.clone(), proposal_module.clone(), &msg, &[])
+        .unwrap();
+    assert!(!query_proposal(&app, &proposal_module, open).archived);
+    app.execute_contract(core_addr, proposal_module.clone(), &msg, &[])
+        .unwrap();
+    assert!(query_proposal(&app, &proposal_module, closed).archived);
+
+    let list = query_list_proposals(&app, &proposal_module, None, None);
+    let archived: Vec<bool> = list.proposals.iter().map(|p| p.archived).collect();
+    assert_eq!(archived, vec![true, false, true]);
+}
+
+#[test]
+fn test_council_mode() {
+    let mut app = App::default();
+    let mut instantiate = get_default_non_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.allow_revoting = true;
+    instantiate.close_proposal_on_execution_failure = false;
+    let core_addr = instantiate_with_cw4_groups_governance(
+        &mut app,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: CREATOR_ADDR.to_string(),
+                amount: Uint128::new(1),
+            },
+            Cw20Coin {
+                address: "alice".to_string(),
+                amount: Uint128::new(1),
+            },
+            Cw20Coin {
+                address: "bob".to_string(),
+                amount: Uint128::new(1),
+            },
+        ]),
+    );
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+    let status = |app: &App, id: u64| query_proposal(app, &proposal_module, id).proposal.status;
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &ExecuteMsg::UpdateCouncilMode {
+                approvals: Some(Uint128::new(2)),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    let original = query_proposal_config(&app, &proposal_module);
+    // Changing the approvals while enabled keeps the original config
+    // to restore.
+    for approvals in [3, 2] {
+        app.execute_contract(
+            core_addr.clone(),
+            proposal_module.clone(),
+            &ExecuteMsg::UpdateCouncilMode {
+                approvals: Some(Uint128::new(approvals)),
+            },
+            &[],
+        )
+        .unwrap();
+    }
+    let council_mode: bool = app
+        .wrap()
+        .query_wasm_smart(&proposal_module, &QueryMsg::CouncilMode {})
+        .unwrap();
+    assert!(council_mode);
+    let config = query_proposal_config(&app, &proposal_module);
+    assert_eq!(
+        config.threshold,
+        Threshold::AbsoluteCount {
+            threshold: Uint128::new(2)
+        }
+    );
+    assert!(!config.allow_revoting);
+    assert!(config.close_proposal_on_execution_failure);
+
+    // Settings managed by council mode may not be changed while it is
+    // enabled, so disabling it can not overwrite them.
+    for msg in [
+        ExecuteMsg::UpdateCloseProposalOnExecutionFailure {
+            close_proposal_on_execution_failure: false,
+        },
+        ExecuteMsg::UpdateThreshold {
+            threshold: Threshold::AbsoluteCount {
+                threshold: Uint128::new(3),
+            },
+        },
+    ] {
+        let err: ContractError = app
+            .execute_contract(core_addr.clone(), proposal_module.clone(), &msg, &[])
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+        assert!(matches!(err, ContractError::CouncilModeEnabled {}));
+    }
+    app.execute_contract(
+        core_addr.clone(),
+        proposal_module.clone(),
+        &ExecuteMsg::UpdateOnlyMembersExecute {
+            only_members_execute: false,
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        core_addr.clone(),
+        proposal_module.clone(),
+        &ExecuteMsg::UpdateOnlyMembersExecute {
+            only_members_execute: original.only_members_execute,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // The proposal executes as soon as it reaches two approvals.
+    mint_natives(&mut app, core_addr.as_str(), coins(10, "ujuno"));
+    let passed = make_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        vec![BankMsg::Send {
+            to_address: "bob".to_string(),
+            amount: coins(10, "ujuno"),
+        }
+        .into()],
+    );
+    vote_on_proposal(&mut app, &proposal_module, "alice", passed, Vote::Yes);
+    assert_eq!(status(&app, passed), Status::Open);
+    vote_on_proposal(&mut app, &proposal_module, "bob", passed, Vote::Yes);
+    assert_eq!(status(&app, passed), Status::Executed);
+    assert_eq!(query_balance_native(&app, "bob", "ujuno"), Uint128::new(10));
+
+    // Two rejections make two approvals impossible.
+    let rejected = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+    vote_on_proposal(&mut app, &proposal_module, "alice", rejected, Vote::No);
+    vote_on_proposal(&mut app, &proposal_module, "bob", rejected, Vote::No);
+    assert_eq!(status(&app, rejected), Status::Rejected);
+
+    // A failed execution closes the proposal without reverting the
+    // vote.
+    let failed = make_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        vec![BankMsg::Send {
+            to_address: "bob".to_string(),
+            amount: coins(10, "ujuno"),
+        }
+        .into()],
+    );
+    vote_on_proposal(&mut app, &proposal_module, "alice", failed, Vote::Yes);
+    vote_on_proposal(&mut app, &proposal_module, "bob", failed, Vote::Yes);
+    assert_eq!(status(&app, failed), Status::ExecutionFailed);
+
+    // Once disabled, the original config is restored and passed
+    // proposals wait to be executed.
+    app.execute_contract(
+        core_addr,
+        proposal_module.clone(),
+        &ExecuteMsg::UpdateCouncilMode { approvals: None },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(query_proposal_config(&app, &proposal_module), original);
+    let council_mode: bool = app
+        .wrap()
+        .query_wasm_smart(&proposal_module, &QueryMsg::CouncilMode {})
+        .unwrap();
+    assert!(!council_mode);
+    let manual = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+    vote_on_proposal(&mut app, &proposal_module, "alice", manual, Vote::Yes);
+    vote_on_proposal(&mut app, &proposal_module, "bob", manual, Vote::Yes);
+    // Revoting is allowed again, so the proposal stays open until it
+    // expires.
+    assert_eq!(status(&app, manual), Status::Open);
+    app.update_block(|block| block.time = block.time.plus_seconds(604800));
+    assert_eq!(status(&app, manual), Status::Passed);
+}
+
+#[test]
+fn test_instant_finality() {
+    let mut app = App::default();
+    let mut instantiate = get_default_non_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.allow_revoting = true;
+    let core_addr = instantiate_with_cw4_groups_governance(
+        &mut app,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: CREATOR_ADDR.to_string(),
+                amount: Uint128::new(1),
+            },
+            Cw20Coin {
+                address: "alice".to_string(),
+                amount: Uint128::new(1),
+            },
+            Cw20Coin {
+                address: "bob".to_string(),
+                amount: Uint128::new(1),
+            },
+        ]),
+    );
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+    let status = |app: &App, id: u64| query_proposal(app, &proposal_module, id).proposal.status;
+
+    // Without instant finality, revoting keeps a unanimous proposal
+    // open until it expires.
+    let before = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+    vote_on_proposal(&mut app, &proposal_module, CREATOR_ADDR, before, Vote::Yes);
+    vote_on_proposal(&mut app, &proposal_module, "alice", before, Vote::Yes);
+    vote_on_proposal(&mut app, &proposal_module, "bob", before, Vote::Yes);
+    assert_eq!(status(&app, before), Status::Open);
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &ExecuteMsg::UpdateInstantFinality { enabled: true },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    app.execute_contract(
+        core_addr.clone(),
+        proposal_module.clone(),
+        &ExecuteMsg::UpdateInstantFinality { enabled: true },
+        &[],
+    )
+    .unwrap();
+    let instant_finality: bool = app
+        .wrap()
+        .query_wasm_smart(&proposal_module, &QueryMsg::InstantFinality {})
+        .unwrap();
+    assert!(instant_finality);
+
+    // Proposals created before instant finality was enabled are not
+    // affected.
+    vote_on_proposal(&mut app, &proposal_module, "bob", before, Vote::No);
+    assert_eq!(status(&app, before), Status::Open);
+
+    // The proposal passes once every member has voted.
+    let passed = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+    vote_on_proposal(&mut app, &proposal_module, CREATOR_ADDR, passed, Vote::Yes);
+    vote_on_proposal(&mut app, &proposal_module, "alice", passed, Vote::Yes);
+    assert_eq!(status(&app, passed), Status::Open);
+    vote_on_proposal(&mut app, &proposal_module, "bob", passed, Vote::Yes);
+    assert_eq!(status(&app, passed), Status::Passed);
+
+    // Votes may not be changed once the outcome is final.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("bob"),
+            proposal_module.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id: passed,
+                vote: Vote::No,
+                rationale: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::AlreadyVoted {}));
+
+    // A split vote is rejected before the proposal expires.
+    let rejected = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+    vote_on_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        rejected,
+        Vote::Yes,
+    );
+    vote_on_proposal(&mut app, &proposal_module, "alice", rejected, Vote::No);
+    vote_on_proposal(&mut app, &proposal_module, "bob", rejected, Vote::Abstain);
+    assert_eq!(status(&app, rejected), Status::Rejected);
+
+    app.execute_contract(
+        core_addr,
+        proposal_module.clone(),
+        &ExecuteMsg::UpdateInstantFinality { enabled: false },
+        &[],
+    )
+    .unwrap();
+    let instant_finality: bool = app
+        .wrap()
+        .query_wasm_smart(&proposal_module, &QueryMsg::InstantFinality {})
+        .unwrap();
+    assert!(!instant_finality);
+}
+
+#[test]
+fn test_set_discussion() {
+    let mut app = App::default();
+    let instantiate = get_default_non_token_dao_proposal_module_instantiate(&mut app);
+    let core_addr = instantiate_with_cw4_groups_governance(
+        &mut app,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: CREATOR_ADDR.to_string(),
+                amount: Uint128::new(1),
+            },
+            Cw20Coin {
+                address: "alice".to_string(),
+                amount: Uint128::new(1),
+            },
+        ]),
+    );
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+    let proposal_id = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+    assert_eq!(
+        query_proposal(&app, &proposal_module, proposal_id).discussion,
+        None
+    );
+
+    let discussion = Discussion {
+        uri: "https://forum.example.com/t/1".to_string(),
+        freeze_hash: Some(HexBinary::from(vec![1; 32])),
+    };
+    let set_discussion = |discussion: &Discussion, id: u64| ExecuteMsg::SetDiscussion {
+        proposal_id: id,
+        uri: discussion.uri.clone(),
+        freeze_hash: discussion.freeze_hash.clone(),
+    };
+
+    // Only the proposer may anchor a discussion.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("alice"),
+            proposal_module.clone(),
+            &set_discussion(&discussion, proposal_id),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        proposal_module.clone(),
+        &set_discussion(&discussion, proposal_id),
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        query_proposal(&app, &proposal_module, proposal_id).discussion,
+        Some(discussion.clone())
+    );
+
+    // The record may not be replaced.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &set_discussion(&discussion, proposal_id),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::DiscussionSet { id } if id == proposal_id));
+
+    // Nor set once voting has started.
+    let voted = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+    vote_on_proposal(&mut app, &proposal_module, "alice", voted, Vote::Yes);
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &set_discussion(&discussion, voted),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::VotingStarted {}));
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module,
+            &set_discussion(&discussion, 100),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NoSuchProposal { id: 100 }));
+}
@@ -1,10 +1,11 @@
 use crate::msg::{
-    ExecuteMsg, InfoResponse, InstantiateMsg, MigrateMsg, PendingRewardsResponse, QueryMsg,
-    ReceiveMsg,
+    BoostQueryMsg, BoostResponse, ExecuteMsg, InfoResponse, InstantiateMsg, MigrateMsg,
+    PendingRewardsResponse, QueryMsg, ReceiveMsg, UncheckedBoostConfig,
 };
 use crate::state::{
-    Config, RewardConfig, CONFIG, LAST_UPDATE_BLOCK, PENDING_REWARDS, REWARD_CONFIG,
-    REWARD_PER_TOKEN, USER_REWARD_PER_TOKEN,
+    BoostConfig, CachedBoost, Config, RewardConfig, BOOSTS, BOOST_CONFIG, BOOST_WEIGHTS, CONFIG,
+    LAST_UPDATE_BLOCK, PENDING_REWARDS, REWARD_CONFIG, REWARD_PER_TOKEN, TOTAL_BOOST_WEIGHT,
+    USER_REWARD_PER_TOKEN,
 };
 use crate::ContractError;
 use crate::ContractError::{
@@ -14,8 +15,9 @@ use crate::ContractError::{
 use cosmwasm_std::entry_point;
 
 use cosmwasm_std::{
-    from_json_binary, to_json_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Empty, Env,
-    MessageInfo, Response, StdError, StdResult, Uint128, Uint256, WasmMsg,
+    from_json_binary, to_json_binary, Addr, Attribute, BankMsg, Binary, Coin, CosmosMsg, Decimal,
+    Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError, StdResult, Uint128, Uint256,
+    WasmMsg,
 };
 use cw2::{get_contract_version, set_contract_version, ContractVersion};
 use cw20::{Cw20ReceiveMsg, Denom};
@@ -133,6 +135,7 @@ pub fn execute(
         ExecuteMsg::UpdateRewardDuration { new_duration } => {
             execute_update_reward_duration(deps, env, info, new_duration)
         }
+        ExecuteMsg::UpdateBoost { boost } => execute_update_boost(deps, info, boost),
         ExecuteMsg::UpdateOwnership(action) => execute_update_owner(deps, info, env, action),
     }
 }
@@ -178,7 +181,7 @@ pub fn execute_fund(
 ) -> Result<Response<Empty>, ContractError> {
     cw_ownable::assert_owner(deps.storage, &sender)?;
 
-    update_rewards(&mut deps, &env, &sender)?;
+    let boost_attributes = update_rewards(&mut deps, &env, &sender)?;
     let reward_config = REWARD_CONFIG.load(deps.storage)?;
     if reward_config.period_finish > env.block.height {
         return Err(RewardPeriodNotFinished {});
@@ -204,7 +207,8 @@ pub fn execute_fund(
     Ok(Response::new()
         .add_attribute("action", "fund")
         .add_attribute("amount", amount)
-        .add_attribute("new_reward_rate", new_reward_config.reward_rate.to_string()))
+        .add_attribute("new_reward_rate", new_reward_config.reward_rate.to_string())
+        .add_attributes(boost_attributes))
 }
 
 pub fn execute_stake_changed(
@@ -228,8 +232,10 @@ pub fn execute_stake(
     env: Env,
     addr: Addr,
 ) -> Result<Response<Empty>, ContractError> {
-    update_rewards(&mut deps, &env, &addr)?;
-    Ok(Response::new().add_attribute("action", "stake"))
+    let boost_attributes = update_rewards(&mut deps, &env, &addr)?;
+    Ok(Response::new()
+        .add_attribute("action", "stake")
+        .add_attributes(boost_attributes))
 }
 
 pub fn execute_unstake(
@@ -237,8 +243,10 @@ pub fn execute_unstake(
     env: Env,
     addr: Addr,
 ) -> Result<Response<Empty>, ContractError> {
-    update_rewards(&mut deps, &env, &addr)?;
-    Ok(Response::new().add_attribute("action", "unstake"))
+    let boost_attributes = update_rewards(&mut deps, &env, &addr)?;
+    Ok(Response::new()
+        .add_attribute("action", "unstake")
+        .add_attributes(boost_attributes))
 }
 
 pub fn execute_claim(
@@ -246,7 +254,7 @@ pub fn execute_claim(
     env: Env,
     info: MessageInfo,
 ) -> Result<Response<Empty>, ContractError> {
-    update_rewards(&mut deps, &env, &info.sender)?;
+    let boost_attributes = update_rewards(&mut deps, &env, &info.sender)?;
     let rewards = PENDING_REWARDS
         .load(deps.storage, info.sender.clone())
         .map_err(|_| NoRewardsClaimable {})?;
@@ -259,7 +267,8 @@ pub fn execute_claim(
     Ok(Response::new()
         .add_message(transfer_msg)
         .add_attribute("action", "claim")
-        .add_attribute("amount", rewards))
+        .add_attribute("amount", rewards)
+        .add_attributes(boost_attributes))
 }
 
 pub fn execute_update_owner(
@@ -272,6 +281,88 @@ pub fn execute_update_owner(
     Ok(Response::default().add_attributes(ownership.into_attributes()))
 }
 
+pub fn execute_update_boost(
+    deps: DepsMut,
+    info: MessageInfo,
+    boost: Option<UncheckedBoostConfig>,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    match boost {
+        Some(boost) => {
+            if boost.cap < Decimal::one() {
+                return Err(ContractError::InvalidBoostCap {});
+            }
+            BOOST_CONFIG.save(
+                deps.storage,
+                &BoostConfig {
+                    adapter: deps.api.addr_validate(&boost.adapter)?,
+                    cap: boost.cap,
+                    cache_blocks: boost.cache_blocks,
+                },
+            )?;
+        }
+        None => BOOST_CONFIG.remove(deps.storage),
+    }
+
+    Ok(Response::new().add_attribute("action", "update_boost"))
+}
+
+/// Gets the boost for ADDR, or `None` if no boost is configured. A
+/// cached multiplier is used until it expires, after which the
+/// adapter is queried again. Multipliers are clamped between one and
+/// the configured cap.
+///
+/// If the adapter can not be queried, the previous multiplier (or
+/// one, if there is none) is kept until the next update so that
+/// staking is never blocked by the adapter, and the query error is
+/// returned alongside the boost so that it may be reported.
+pub fn get_boost(
+    deps: Deps,
+    env: &Env,
+    addr: &Addr,
+) -> StdResult<Option<(CachedBoost, Option<StdError>)>> {
+    let config = match BOOST_CONFIG.may_load(deps.storage)? {
+        Some(config) => config,
+        None => return Ok(None),
+    };
+    let cached = BOOSTS.may_load(deps.storage, addr.clone())?;
+    let (boost, error) = match cached {
+        Some(cached) if cached.expires > env.block.height => (cached, None),
+        _ => {
+            let res: StdResult<BoostResponse> = deps.querier.query_wasm_smart(
+                &config.adapter,
+                &BoostQueryMsg::Boost {
+                    address: addr.to_string(),
+                },
+            );
+            match res {
+                Ok(res) => (
+                    CachedBoost {
+                        multiplier: res.multiplier,
+                        expires: env.block.height + config.cache_blocks,
+                    },
+                    None,
+                ),
+                Err(error) => (
+                    CachedBoost {
+                        multiplier: cached.map_or(Decimal::one(), |c| c.multiplier),
+                        expires: env.block.height,
+                    },
+                    Some(error),
+                ),
+            }
+        }
+    };
+    Ok(Some((
+        CachedBoost {
+            multiplier: boost.multiplier.clamp(Decimal::one(), config.cap),
+            ..boost
+        },
+        error,
+    )))
+}
+
 pub fn get_transfer_msg(recipient: Addr, amount: Uint128, denom: Denom) -> StdResult<CosmosMsg> {
     match denom {
         Denom::Native(denom) => Ok(BankMsg::Send {
@@ -294,7 +385,11 @@ pub fn get_transfer_msg(recipient: Addr, amount: Uint128, denom: Denom) -> StdRe
     }
 }
 
-pub fn update_rewards(deps: &mut DepsMut, env: &Env, addr: &Addr) -> StdResult<()> {
+/// Settles the rewards ADDR has earned since its last update at the
+/// boost weight recorded then, and then records a new boost weight
+/// from ADDR's current stake and multiplier. Returns attributes
+/// describing any error querying the boost adapter.
+pub fn update_rewards(deps: &mut DepsMut, env: &Env, addr: &Addr) -> StdResult<Vec<Attribute>> {
     let config = CONFIG.load(deps.storage)?;
     let reward_per_token = get_reward_per_token(deps.as_ref(), env, &config.staking_contract)?;
     REWARD_PER_TOKEN.save(deps.storage, &reward_per_token)?;
@@ -313,16 +408,49 @@ pub fn update_rewards(deps: &mut DepsMut, env: &Env, addr: &Addr) -> StdResult<(
     USER_REWARD_PER_TOKEN.save(deps.storage, addr.clone(), &reward_per_token)?;
     let last_time_reward_applicable = get_last_time_reward_applicable(deps.as_ref(), env)?;
     LAST_UPDATE_BLOCK.save(deps.storage, &last_time_reward_applicable)?;
-    Ok(())
+
+    // Now that rewards up to this block are settled, the boost may
+    // change without applying retroactively.
+    let mut attributes = vec![];
+    let multiplier = match get_boost(deps.as_ref(), env, addr)? {
+        Some((boost, error)) => {
+            if let Some(error) = error {
+                attributes.push(Attribute::new("boost_error", error.to_string()));
+            }
+            BOOSTS.save(deps.storage, addr.clone(), &boost)?;
+            boost.multiplier
+        }
+        None => Decimal::one(),
+    };
+    let staked_balance = get_staked_balance(deps.as_ref(), &config.staking_contract, addr)?;
+    let weight = staked_balance * (multiplier - Decimal::one());
+    let old_weight = BOOST_WEIGHTS
+        .may_load(deps.storage, addr.clone())?
+        .unwrap_or_default();
+    if weight.is_zero() {
+        BOOST_WEIGHTS.remove(deps.storage, addr.clone());
+    } else {
+        BOOST_WEIGHTS.save(deps.storage, addr.clone(), &weight)?;
+    }
+    let total_weight = TOTAL_BOOST_WEIGHT
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        .checked_sub(old_weight)?
+        + weight;
+    TOTAL_BOOST_WEIGHT.save(deps.storage, &total_weight)?;
+    Ok(attributes)
 }
 
 pub fn get_reward_per_token(deps: Deps, env: &Env, staking_contract: &Addr) -> StdResult<Uint256> {
     let reward_config = REWARD_CONFIG.load(deps.storage)?;
-    let total_staked = get_total_staked(deps, staking_contract)?;
+    let total_weight = get_total_staked(deps, staking_contract)?
+        + TOTAL_BOOST_WEIGHT
+            .may_load(deps.storage)?
+            .unwrap_or_default();
     let last_time_reward_applicable = get_last_time_reward_applicable(deps, env)?;
     let last_update_block = LAST_UPDATE_BLOCK.load(deps.storage).unwrap_or_default();
     let prev_reward_per_token = REWARD_PER_TOKEN.load(deps.storage).unwrap_or_default();
-    let additional_reward_per_token = if total_staked == Uint128::zero() {
+    let additional_reward_per_token = if total_weight == Uint128::zero() {
         Uint256::zero()
     } else {
         // It is impossible for this to overflow as total rewards can never exceed max value of
@@ -333,7 +461,7 @@ pub fn get_reward_per_token(deps: Deps, env: &Env, staking_contract: &Addr) -> S
                 last_time_reward_applicable - last_update_block,
             ))
             .checked_mul(scale_factor())?;
-        let denominator = Uint256::from(total_staked);
+        let denominator = Uint256::from(total_weight);
         numerator.checked_div(denominator)?
     };
 
@@ -348,12 +476,19 @@ pub fn get_rewards_earned(
     staking_contract: &Addr,
 ) -> StdResult<Uint128> {
     let _config = CONFIG.load(deps.storage)?;
-    let staked_balance = Uint256::from(get_staked_balance(deps, staking_contract, addr)?);
+    // Rewards are earned on stake plus the boost weight recorded at
+    // the address's last update.
+    let weight = Uint256::from(
+        get_staked_balance(deps, staking_contract, addr)?
+            + BOOST_WEIGHTS
+                .may_load(deps.storage, addr.clone())?
+                .unwrap_or_default(),
+    );
     let user_reward_per_token = USER_REWARD_PER_TOKEN
         .load(deps.storage, addr.clone())
         .unwrap_or_default();
     let reward_factor = reward_per_token.checked_sub(user_reward_per_token)?;
-    Ok(staked_balance
+    Ok(weight
         .checked_mul(reward_factor)?
         .checked_div(scale_factor())?
         .try_into()?)
@@ -419,6 +554,13 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             Ok(to_json_binary(&query_pending_rewards(deps, env, address)?)?)
         }
         QueryMsg::Ownership {} => to_json_binary(&cw_ownable::get_ownership(deps.storage)?),
+        QueryMsg::BoostConfig {} => to_json_binary(&BOOST_CONFIG.may_load(deps.storage)?),
+        QueryMsg::Boost { address } => {
+            let addr = deps.api.addr_validate(&address)?;
+            let multiplier =
+                get_boost(deps, &env, &addr)?.map_or(Decimal::one(), |(b, _)| b.multiplier);
+            to_json_binary(&BoostResponse { multiplier })
+        }
     }
 }
 
@@ -462,16 +604,24 @@ mod tests {
 
     use crate::{msg::MigrateMsg, ContractError};
 
-    use cosmwasm_std::{coin, to_json_binary, Addr, Empty, Uint128, WasmMsg};
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::{
+        coin, to_json_binary, Addr, Binary, Decimal, Deps, DepsMut, Empty, Env, MessageInfo,
+        Response, StdResult, Uint128, WasmMsg,
+    };
     use cw20::{Cw20Coin, Cw20ExecuteMsg, Denom};
     use cw_ownable::{Action, Ownership, OwnershipError};
+    use cw_storage_plus::Map;
     use cw_utils::Duration;
 
     use cw_multi_test::{next_block, App, BankSudo, Contract, ContractWrapper, Executor, SudoMsg};
 
     use cw20_stake_external_rewards_v1 as v1;
 
-    use crate::msg::{ExecuteMsg, InfoResponse, PendingRewardsResponse, QueryMsg, ReceiveMsg};
+    use crate::msg::{
+        BoostQueryMsg, BoostResponse, ExecuteMsg, InfoResponse, PendingRewardsResponse, QueryMsg,
+        ReceiveMsg, UncheckedBoostConfig,
+    };
 
     const OWNER: &str = "owner";
     const ADDR1: &str = "addr0001";
@@ -506,6 +656,52 @@ mod tests {
         Box::new(contract)
     }
 
+    /// Sets the multiplier the mock boost adapter reports for an
+    /// address.
+    #[cw_serde]
+    struct SetBoost {
+        address: String,
+        multiplier: Decimal,
+    }
+
+    const MOCK_BOOSTS: Map<&str, Decimal> = Map::new("mock_boosts");
+
+    fn boost_adapter_execute(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        msg: SetBoost,
+    ) -> StdResult<Response> {
+        MOCK_BOOSTS.save(deps.storage, &msg.address, &msg.multiplier)?;
+        Ok(Response::default())
+    }
+
+    fn boost_adapter_instantiate(
+        _deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: Empty,
+    ) -> StdResult<Response> {
+        Ok(Response::default())
+    }
+
+    fn boost_adapter_query(deps: Deps, _env: Env, msg: BoostQueryMsg) -> StdResult<Binary> {
+        let BoostQueryMsg::Boost { address } = msg;
+        let multiplier = MOCK_BOOSTS
+            .may_load(deps.storage, &address)?
+            .unwrap_or_else(Decimal::one);
+        to_json_binary(&BoostResponse { multiplier })
+    }
+
+    pub fn contract_boost_adapter() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(
+            boost_adapter_execute,
+            boost_adapter_instantiate,
+            boost_adapter_query,
+        );
+        Box::new(contract)
+    }
+
     pub fn contract_cw20() -> Box<dyn Contract<Empty>> {
         let contract = ContractWrapper::new(
             cw20_base::contract::execute,
@@ -2034,4 +2230,186 @@ mod tests {
             .unwrap();
         assert_eq!(err, ContractError::AlreadyMigrated {});
     }
+
+    #[test]
+    fn test_boosted_rewards() {
+        let mut app = mock_app();
+        let admin = Addr::unchecked(OWNER);
+        app.borrow_mut().update_block(|b| b.height = 0);
+        let initial_balances = vec![
+            Cw20Coin {
+                address: ADDR1.to_string(),
+                amount: Uint128::new(100),
+            },
+            Cw20Coin {
+                address: ADDR2.to_string(),
+                amount: Uint128::new(50),
+            },
+            Cw20Coin {
+                address: ADDR3.to_string(),
+                amount: Uint128::new(50),
+            },
+        ];
+        let denom = "utest".to_string();
+        let (staking_addr, _) = setup_staking_contract(&mut app, initial_balances);
+        let reward_funding = vec![coin(100000000, denom.clone())];
+        app.sudo(SudoMsg::Bank({
+            BankSudo::Mint {
+                to_address: admin.to_string(),
+                amount: reward_funding.clone(),
+            }
+        }))
+        .unwrap();
+        let reward_addr = setup_reward_contract(
+            &mut app,
+            staking_addr.clone(),
+            Denom::Native(denom.clone()),
+            admin.clone(),
+        );
+
+        let adapter_id = app.store_code(contract_boost_adapter());
+        let adapter = app
+            .instantiate_contract(adapter_id, admin.clone(), &Empty {}, &[], "boost", None)
+            .unwrap();
+        let set_boost = |app: &mut App, address: &str, multiplier: Decimal| {
+            app.execute_contract(
+                admin.clone(),
+                adapter.clone(),
+                &SetBoost {
+                    address: address.to_string(),
+                    multiplier,
+                },
+                &[],
+            )
+            .unwrap();
+        };
+        set_boost(&mut app, ADDR1, Decimal::percent(300));
+        set_boost(&mut app, ADDR2, Decimal::percent(150));
+        set_boost(&mut app, ADDR3, Decimal::percent(50));
+
+        let update_boost = |cap: Decimal| ExecuteMsg::UpdateBoost {
+            boost: Some(UncheckedBoostConfig {
+                adapter: adapter.to_string(),
+                cap,
+                cache_blocks: 10,
+            }),
+        };
+        let err: ContractError = app
+            .execute_contract(
+                Addr::unchecked(ADDR1),
+                reward_addr.clone(),
+                &update_boost(Decimal::percent(200)),
+                &[],
+            )
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+        assert_eq!(err, ContractError::Ownable(OwnershipError::NotOwner));
+        let err: ContractError = app
+            .execute_contract(
+                admin.clone(),
+                reward_addr.clone(),
+                &update_boost(Decimal::percent(50)),
+                &[],
+            )
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+        assert_eq!(err, ContractError::InvalidBoostCap {});
+        app.execute_contract(
+            admin.clone(),
+            reward_addr.clone(),
+            &update_boost(Decimal::percent(200)),
+            &[],
+        )
+        .unwrap();
+
+        app.borrow_mut().update_block(|b| b.height = 1000);
+        app.execute_contract(
+            admin.clone(),
+            reward_addr.clone(),
+            &ExecuteMsg::Fund {},
+            &reward_funding,
+        )
+        .unwrap();
+
+        // Boosts only apply once recorded by a reward update.
+        app.borrow_mut().update_block(next_block);
+        assert_pending_rewards(&mut app, &reward_addr, ADDR1, 500);
+        assert_pending_rewards(&mut app, &reward_addr, ADDR2, 250);
+        assert_pending_rewards(&mut app, &reward_addr, ADDR3, 250);
+        claim_rewards(&mut app, reward_addr.clone(), ADDR1);
+        claim_rewards(&mut app, reward_addr.clone(), ADDR2);
+        claim_rewards(&mut app, reward_addr.clone(), ADDR3);
+        assert_eq!(get_balance_native(&app, ADDR1, &denom), Uint128::new(500));
+        assert_eq!(get_balance_native(&app, ADDR2, &denom), Uint128::new(250));
+        assert_eq!(get_balance_native(&app, ADDR3, &denom), Uint128::new(250));
+
+        // Multipliers are clamped between one and the cap, and boosted
+        // stakes share the reward rate, so no more than the funded
+        // 1000 per block is paid out. The weights are 200, 75, and 50.
+        app.borrow_mut().update_block(next_block);
+        assert_pending_rewards(&mut app, &reward_addr, ADDR1, 615);
+        assert_pending_rewards(&mut app, &reward_addr, ADDR2, 230);
+        assert_pending_rewards(&mut app, &reward_addr, ADDR3, 153);
+
+        // Claiming cached ADDR2's multiplier for ten blocks.
+        claim_rewards(&mut app, reward_addr.clone(), ADDR2);
+        assert_eq!(get_balance_native(&app, ADDR2, &denom), Uint128::new(480));
+        set_boost(&mut app, ADDR2, Decimal::percent(200));
+
+        app.borrow_mut().update_block(next_block);
+        assert_pending_rewards(&mut app, &reward_addr, ADDR2, 230);
+
+        // The new multiplier is not applied to rewards earned before
+        // it is recorded.
+        app.borrow_mut().update_block(|b| b.height = 1011);
+        assert_pending_rewards(&mut app, &reward_addr, ADDR2, 2076);
+        claim_rewards(&mut app, reward_addr.clone(), ADDR2);
+        assert_eq!(get_balance_native(&app, ADDR2, &denom), Uint128::new(2556));
+
+        app.borrow_mut().update_block(next_block);
+        assert_pending_rewards(&mut app, &reward_addr, ADDR2, 285);
+        let boost: BoostResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &reward_addr,
+                &QueryMsg::Boost {
+                    address: ADDR2.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(boost.multiplier, Decimal::percent(200));
+
+        // If the adapter can not be queried the previous multiplier
+        // is kept and the error is reported.
+        app.execute_contract(
+            admin,
+            reward_addr.clone(),
+            &ExecuteMsg::UpdateBoost {
+                boost: Some(UncheckedBoostConfig {
+                    adapter: staking_addr.to_string(),
+                    cap: Decimal::percent(200),
+                    cache_blocks: 10,
+                }),
+            },
+            &[],
+        )
+        .unwrap();
+        app.borrow_mut().update_block(|b| b.height = 1021);
+        assert_pending_rewards(&mut app, &reward_addr, ADDR2, 2857);
+        let res = app
+            .execute_contract(
+                Addr::unchecked(ADDR2),
+                reward_addr.clone(),
+                &ExecuteMsg::Claim {},
+                &[],
+            )
+            .unwrap();
+        assert!(res
+            .events
+            .iter()
+            .any(|e| e.attributes.iter().any(|a| a.key == "boost_error")));
+        assert_eq!(get_balance_native(&app, ADDR2, &denom), Uint128::new(5413));
+    }
 }
@@ -23,6 +23,8 @@ pub enum ContractError {
     RewardRateLessThenOnePerBlock {},
     #[error("Reward duration can not be zero")]
     ZeroRewardDuration {},
+    #[error("Boost cap must be at least one")]
+    InvalidBoostCap {},
     #[error("can not migrate. current version is up to date")]
     AlreadyMigrated {},
 }
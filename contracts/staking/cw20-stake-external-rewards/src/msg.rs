@@ -1,9 +1,9 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Uint128;
+use cosmwasm_std::{Decimal, Uint128};
 use cw20::{Cw20ReceiveMsg, Denom};
 use dao_hooks::stake::StakeChangedHookMsg;
 
-use crate::state::{Config, RewardConfig};
+use crate::state::{BoostConfig, Config, RewardConfig};
 
 pub use cw_controllers::ClaimsResponse;
 // so that consumers don't need a cw_ownable dependency to consume
@@ -28,6 +28,30 @@ pub enum ExecuteMsg {
     Receive(Cw20ReceiveMsg),
     Fund {},
     UpdateRewardDuration { new_duration: u64 },
+    UpdateBoost { boost: Option<UncheckedBoostConfig> },
+}
+
+#[cw_serde]
+pub struct UncheckedBoostConfig {
+    pub adapter: String,
+    /// Must be at least one.
+    pub cap: Decimal,
+    pub cache_blocks: u64,
+}
+
+/// The query interface implemented by boost adapters. An adapter
+/// reports a reward multiplier for each staker, for example based on
+/// the NFTs they hold.
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum BoostQueryMsg {
+    #[returns(BoostResponse)]
+    Boost { address: String },
+}
+
+#[cw_serde]
+pub struct BoostResponse {
+    pub multiplier: Decimal,
 }
 
 #[cw_serde]
@@ -53,6 +77,11 @@ pub enum QueryMsg {
     GetPendingRewards { address: String },
     #[returns(::cw_ownable::Ownership<::cosmwasm_std::Addr>)]
     Ownership {},
+    #[returns(Option<BoostConfig>)]
+    BoostConfig {},
+    /// Gets the multiplier currently applied to an address's rewards.
+    #[returns(BoostResponse)]
+    Boost { address: String },
 }
 
 #[cw_serde]
@@ -1,5 +1,5 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint128, Uint256};
+use cosmwasm_std::{Addr, Decimal, Uint128, Uint256};
 use cw20::Denom;
 
 use cw_storage_plus::{Item, Map};
@@ -28,3 +28,32 @@ pub const LAST_UPDATE_BLOCK: Item<u64> = Item::new("last_update_block");
 pub const PENDING_REWARDS: Map<Addr, Uint128> = Map::new("pending_rewards");
 
 pub const USER_REWARD_PER_TOKEN: Map<Addr, Uint256> = Map::new("user_reward_per_token");
+
+/// Configuration for boosting the rewards of individual stakers.
+#[cw_serde]
+pub struct BoostConfig {
+    /// The contract queried for each staker's multiplier. Must
+    /// implement `BoostQueryMsg`.
+    pub adapter: Addr,
+    /// The largest multiplier that will be applied.
+    pub cap: Decimal,
+    /// The number of blocks a multiplier queried from the adapter is
+    /// reused for before being queried again.
+    pub cache_blocks: u64,
+}
+pub const BOOST_CONFIG: Item<BoostConfig> = Item::new("boost_config");
+
+#[cw_serde]
+pub struct CachedBoost {
+    pub multiplier: Decimal,
+    /// The height at which this multiplier must be queried again.
+    pub expires: u64,
+}
+pub const BOOSTS: Map<Addr, CachedBoost> = Map::new("boosts");
+
+/// The weight each staker's boost adds on top of their stake, as of
+/// their last reward update. Rewards are divided by stake plus boost
+/// weight, so boosted rewards never exceed the funded reward rate.
+pub const BOOST_WEIGHTS: Map<Addr, Uint128> = Map::new("boost_weights");
+/// The sum of all `BOOST_WEIGHTS`.
+pub const TOTAL_BOOST_WEIGHT: Item<Uint128> = Item::new("total_boost_weight");
@@ -5,8 +5,10 @@ use cosmwasm_std::entry_point;
 use cosmwasm_std::{to_json_binary, Addr, CosmosMsg, StdError, Uint128, WasmMsg};
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InfoResponse, InstantiateMsg, MigrateMsg, QueryMsg};
-use crate::state::{Config, CONFIG, LAST_PAYMENT_BLOCK};
+use crate::msg::{
+    ExecuteMsg, InfoResponse, InstantiateMsg, MigrateMsg, QueryMsg, TargetInfo, TargetResponse,
+};
+use crate::state::{Config, Target, TargetKind, CONFIG, DISTRIBUTED, LAST_PAYMENT_BLOCK, TARGETS};
 use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
 use cw2::{get_contract_version, set_contract_version, ContractVersion};
 
@@ -66,12 +68,13 @@ pub fn execute(
         } => execute_update_config(deps, info, env, staking_addr, reward_rate, reward_token),
         ExecuteMsg::Distribute {} => execute_distribute(deps, env),
         ExecuteMsg::Withdraw {} => execute_withdraw(deps, info, env),
+        ExecuteMsg::UpdateTargets { targets } => execute_update_targets(deps, env, info, targets),
         ExecuteMsg::UpdateOwnership(action) => execute_update_owner(deps, info, env, action),
     }
 }
 
 pub fn execute_update_config(
-    deps: DepsMut,
+    mut deps: DepsMut,
     info: MessageInfo,
     env: Env,
     staking_addr: String,
@@ -86,6 +89,13 @@ pub fn execute_update_config(
     if !validate_staking(deps.as_ref(), staking_addr.clone()) {
         return Err(ContractError::InvalidStakingContract {});
     }
+    // The config's staking contract is only paid while no targets
+    // are set, so it may not be changed without updating the targets.
+    if TARGETS.may_load(deps.storage)?.is_some()
+        && CONFIG.load(deps.storage)?.staking_addr != staking_addr
+    {
+        return Err(ContractError::TargetsSet {});
+    }
 
     let reward_token = deps.api.addr_validate(&reward_token)?;
     if !validate_cw20(deps.as_ref(), reward_token.clone()) {
@@ -99,9 +109,9 @@ pub fn execute_update_config(
     };
     CONFIG.save(deps.storage, &config)?;
 
-    let resp = match get_distribution_msg(deps.as_ref(), &env) {
+    let resp = match distribute(deps.branch(), &env) {
         // distribution succeeded
-        Ok(msg) => Response::new().add_message(msg),
+        Ok(msgs) => Response::new().add_messages(msgs),
         // distribution failed (either zero rewards or already distributed for block)
         _ => Response::new(),
     };
@@ -139,7 +149,124 @@ pub fn validate_staking(deps: Deps, staking_addr: Addr) -> bool {
     response.is_ok()
 }
 
-fn get_distribution_msg(deps: Deps, env: &Env) -> Result<CosmosMsg, ContractError> {
+pub fn execute_update_targets(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    targets: Vec<TargetInfo>,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    let mut checked: Vec<Target> = Vec::with_capacity(targets.len());
+    for TargetInfo {
+        address,
+        kind,
+        weight,
+    } in targets
+    {
+        let address = deps.api.addr_validate(&address)?;
+        if weight == 0 || checked.iter().any(|target| target.address == address) {
+            return Err(ContractError::InvalidTargets {});
+        }
+        if kind == TargetKind::Staking && !validate_staking(deps.as_ref(), address.clone()) {
+            return Err(ContractError::InvalidStakingContract {});
+        }
+        checked.push(Target {
+            address,
+            kind,
+            weight,
+        });
+    }
+
+    // Rewards accrued so far go to the old targets, so that the new
+    // targets are only paid from this block on.
+    let msgs = distribute(deps.branch(), &env).unwrap_or_default();
+    LAST_PAYMENT_BLOCK.save(deps.storage, &env.block.height)?;
+
+    if checked.is_empty() {
+        TARGETS.remove(deps.storage);
+    } else {
+        TARGETS.save(deps.storage, &checked)?;
+    }
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("action", "update_targets")
+        .add_attributes(
+            checked
+                .iter()
+                .map(|target| ("target", format!("{}:{}", target.address, target.weight))),
+        ))
+}
+
+/// Gets the targets rewards are split between, which is the staking
+/// contract alone if no targets are set.
+fn load_targets(deps: Deps, config: &Config) -> StdResult<Vec<Target>> {
+    Ok(TARGETS.may_load(deps.storage)?.unwrap_or_else(|| {
+        vec![Target {
+            address: config.staking_addr.clone(),
+            kind: TargetKind::Staking,
+            weight: 1,
+        }]
+    }))
+}
+
+/// Splits AMOUNT between TARGETS by weight. Whatever is left over
+/// from rounding goes to the first target.
+fn split_rewards(targets: &[Target], amount: Uint128) -> Vec<Uint128> {
+    let total_weight: u128 = targets.iter().map(|target| target.weight as u128).sum();
+    let mut shares: Vec<Uint128> = targets
+        .iter()
+        .map(|target| amount.multiply_ratio(target.weight, total_weight))
+        .collect();
+    let remainder = amount - shares.iter().sum::<Uint128>();
+    if let Some(first) = shares.first_mut() {
+        *first += remainder;
+    }
+    shares
+}
+
+fn get_send_msg(reward_token: &Addr, target: &Target, amount: Uint128) -> StdResult<CosmosMsg> {
+    let msg = match target.kind {
+        TargetKind::Staking => to_json_binary(&cw20::Cw20ExecuteMsg::Send {
+            contract: target.address.to_string(),
+            amount,
+            msg: to_json_binary(&cw20_stake::msg::ReceiveMsg::Fund {})?,
+        })?,
+        TargetKind::Address => to_json_binary(&cw20::Cw20ExecuteMsg::Transfer {
+            recipient: target.address.to_string(),
+            amount,
+        })?,
+    };
+    Ok(WasmMsg::Execute {
+        contract_addr: reward_token.to_string(),
+        msg,
+        funds: vec![],
+    }
+    .into())
+}
+
+/// Sends the pending rewards to each target, recording the amount
+/// distributed to them.
+fn distribute(deps: DepsMut, env: &Env) -> Result<Vec<CosmosMsg>, ContractError> {
+    let amount = get_pending_rewards(deps.as_ref(), env)?;
+    let config = CONFIG.load(deps.storage)?;
+    let targets = load_targets(deps.as_ref(), &config)?;
+
+    let mut msgs = Vec::with_capacity(targets.len());
+    for (target, share) in targets.iter().zip(split_rewards(&targets, amount)) {
+        if share.is_zero() {
+            continue;
+        }
+        DISTRIBUTED.update(deps.storage, &target.address, |distributed| {
+            StdResult::Ok(distributed.unwrap_or_default() + share)
+        })?;
+        msgs.push(get_send_msg(&config.reward_token, target, share)?);
+    }
+    Ok(msgs)
+}
+
+fn get_pending_rewards(deps: Deps, env: &Env) -> Result<Uint128, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     let last_payment_block = LAST_PAYMENT_BLOCK.load(deps.storage)?;
     if last_payment_block >= env.block.height {
@@ -162,26 +289,14 @@ fn get_distribution_msg(deps: Deps, env: &Env) -> Result<CosmosMsg, ContractErro
         return Err(ContractError::ZeroRewards {});
     }
 
-    let msg = to_json_binary(&cw20::Cw20ExecuteMsg::Send {
-        contract: config.staking_addr.clone().into_string(),
-        amount,
-        msg: to_json_binary(&cw20_stake::msg::ReceiveMsg::Fund {}).unwrap(),
-    })?;
-    let send_msg: CosmosMsg = WasmMsg::Execute {
-        contract_addr: config.reward_token.into(),
-        msg,
-        funds: vec![],
-    }
-    .into();
-
-    Ok(send_msg)
+    Ok(amount)
 }
 
-pub fn execute_distribute(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
-    let msg = get_distribution_msg(deps.as_ref(), &env)?;
+pub fn execute_distribute(mut deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let msgs = distribute(deps.branch(), &env)?;
     LAST_PAYMENT_BLOCK.save(deps.storage, &env.block.height)?;
     Ok(Response::new()
-        .add_message(msg)
+        .add_messages(msgs)
         .add_attribute("action", "distribute"))
 }
 
@@ -225,6 +340,7 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Info {} => to_json_binary(&query_info(deps, env)?),
         QueryMsg::Ownership {} => to_json_binary(&cw_ownable::get_ownership(deps.storage)?),
+        QueryMsg::Targets {} => to_json_binary(&query_targets(deps)?),
     }
 }
 
@@ -274,3 +390,19 @@ fn query_info(deps: Deps, env: Env) -> StdResult<InfoResponse> {
         balance: balance_info.balance,
     })
 }
+
+fn query_targets(deps: Deps) -> StdResult<Vec<TargetResponse>> {
+    let config = CONFIG.load(deps.storage)?;
+    load_targets(deps, &config)?
+        .into_iter()
+        .map(|target| {
+            let distributed = DISTRIBUTED
+                .may_load(deps.storage, &target.address)?
+                .unwrap_or_default();
+            Ok(TargetResponse {
+                target,
+                distributed,
+            })
+        })
+        .collect()
+}
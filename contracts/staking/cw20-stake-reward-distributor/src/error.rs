@@ -15,6 +15,12 @@ pub enum ContractError {
     #[error("Invalid Staking Contract")]
     InvalidStakingContract {},
 
+    #[error("Targets must have distinct addresses and non-zero weights")]
+    InvalidTargets {},
+
+    #[error("The staking contract may not be changed while targets are set")]
+    TargetsSet {},
+
     #[error("Zero eligible rewards")]
     ZeroRewards {},
 
@@ -1,4 +1,4 @@
-use crate::state::Config;
+use crate::state::{Config, Target, TargetKind};
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::Uint128;
 
@@ -16,6 +16,13 @@ pub struct InstantiateMsg {
     pub reward_token: String,
 }
 
+#[cw_serde]
+pub struct TargetInfo {
+    pub address: String,
+    pub kind: TargetKind,
+    pub weight: u64,
+}
+
 #[cw_ownable_execute]
 #[cw_serde]
 pub enum ExecuteMsg {
@@ -26,6 +33,13 @@ pub enum ExecuteMsg {
     },
     Distribute {},
     Withdraw {},
+    /// Splits rewards between TARGETS in proportion to their
+    /// weights. An empty list sends all rewards to the staking
+    /// contract in the config. Pending rewards are first distributed
+    /// to the current targets. Only the owner may call this method.
+    UpdateTargets {
+        targets: Vec<TargetInfo>,
+    },
 }
 
 #[cw_serde]
@@ -36,6 +50,11 @@ pub enum QueryMsg {
 
     #[returns(::cw_ownable::Ownership<::cosmwasm_std::Addr>)]
     Ownership {},
+
+    /// Lists the targets rewards are split between and the total
+    /// distributed to each.
+    #[returns(Vec<TargetResponse>)]
+    Targets {},
 }
 
 #[cw_serde]
@@ -45,6 +64,12 @@ pub struct InfoResponse {
     pub balance: Uint128,
 }
 
+#[cw_serde]
+pub struct TargetResponse {
+    pub target: Target,
+    pub distributed: Uint128,
+}
+
 #[cw_serde]
 pub enum MigrateMsg {
     /// Updates the contract from v1 -> v2. Version two implements a
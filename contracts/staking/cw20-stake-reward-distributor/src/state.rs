@@ -1,6 +1,6 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Uint128};
-use cw_storage_plus::Item;
+use cw_storage_plus::{Item, Map};
 
 #[cw_serde]
 pub struct Config {
@@ -13,3 +13,29 @@ pub struct Config {
 pub const CONFIG: Item<Config> = Item::new("config_v2");
 
 pub const LAST_PAYMENT_BLOCK: Item<u64> = Item::new("last_payment_block");
+
+#[cw_serde]
+pub enum TargetKind {
+    /// Rewards are sent to a cw20-stake contract with a `Fund`
+    /// message.
+    Staking,
+    /// Rewards are transferred to the address.
+    Address,
+}
+
+/// A recipient of distributed rewards and its weight.
+#[cw_serde]
+pub struct Target {
+    pub address: Addr,
+    pub kind: TargetKind,
+    /// The target's share of each distribution is its weight over the
+    /// sum of all targets' weights.
+    pub weight: u64,
+}
+
+/// The targets rewards are split between. If unset, all rewards go to
+/// the staking contract in the config.
+pub const TARGETS: Item<Vec<Target>> = Item::new("targets");
+
+/// The total rewards distributed to each address.
+pub const DISTRIBUTED: Map<&Addr, Uint128> = Map::new("distributed");
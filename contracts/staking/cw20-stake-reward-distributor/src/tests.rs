@@ -1,6 +1,8 @@
 use crate::{
-    msg::{ExecuteMsg, InfoResponse, InstantiateMsg, MigrateMsg, QueryMsg},
-    state::Config,
+    msg::{
+        ExecuteMsg, InfoResponse, InstantiateMsg, MigrateMsg, QueryMsg, TargetInfo, TargetResponse,
+    },
+    state::{Config, Target, TargetKind},
     ContractError,
 };
 
@@ -332,6 +334,196 @@ fn test_distribute() {
     assert!(matches!(err, ContractError::RewardsDistributedForBlock {}));
 }
 
+#[test]
+fn test_distribute_to_targets() {
+    let mut app = App::default();
+
+    let cw20_addr = instantiate_cw20(
+        &mut app,
+        vec![cw20::Cw20Coin {
+            address: OWNER.to_string(),
+            amount: Uint128::from(1000u64),
+        }],
+    );
+    let staking_addr = instantiate_staking(&mut app, cw20_addr.clone());
+
+    let msg = InstantiateMsg {
+        owner: OWNER.to_string(),
+        staking_addr: staking_addr.to_string(),
+        reward_rate: Uint128::new(10),
+        reward_token: cw20_addr.to_string(),
+    };
+    let distributor_addr = instantiate_distributor(&mut app, msg);
+
+    let msg = cw20::Cw20ExecuteMsg::Transfer {
+        recipient: distributor_addr.to_string(),
+        amount: Uint128::from(1000u128),
+    };
+    app.execute_contract(Addr::unchecked(OWNER), cw20_addr.clone(), &msg, &[])
+        .unwrap();
+
+    let targets = vec![
+        TargetInfo {
+            address: staking_addr.to_string(),
+            kind: TargetKind::Staking,
+            weight: 70,
+        },
+        TargetInfo {
+            address: "treasury".to_string(),
+            kind: TargetKind::Address,
+            weight: 20,
+        },
+        TargetInfo {
+            address: "grants".to_string(),
+            kind: TargetKind::Address,
+            weight: 10,
+        },
+    ];
+
+    // Only the owner may set targets.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(OWNER2),
+            distributor_addr.clone(),
+            &ExecuteMsg::UpdateTargets {
+                targets: targets.clone(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Ownership(OwnershipError::NotOwner));
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            distributor_addr.clone(),
+            &ExecuteMsg::UpdateTargets {
+                targets: vec![TargetInfo {
+                    address: "treasury".to_string(),
+                    kind: TargetKind::Address,
+                    weight: 0,
+                }],
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::InvalidTargets {});
+
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        distributor_addr.clone(),
+        &ExecuteMsg::UpdateTargets { targets },
+        &[],
+    )
+    .unwrap();
+
+    app.update_block(|block| block.height += 10);
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        distributor_addr.clone(),
+        &ExecuteMsg::Distribute {},
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(
+        get_balance_cw20(&app, cw20_addr.clone(), staking_addr.clone()),
+        Uint128::new(70)
+    );
+    assert_eq!(
+        get_balance_cw20(&app, cw20_addr.clone(), "treasury"),
+        Uint128::new(20)
+    );
+    assert_eq!(
+        get_balance_cw20(&app, cw20_addr.clone(), "grants"),
+        Uint128::new(10)
+    );
+
+    // Removing the targets sends everything to the staking contract
+    // again, and amounts distributed so far are kept.
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        distributor_addr.clone(),
+        &ExecuteMsg::UpdateTargets { targets: vec![] },
+        &[],
+    )
+    .unwrap();
+    app.update_block(|block| block.height += 1);
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        distributor_addr.clone(),
+        &ExecuteMsg::Distribute {},
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        get_balance_cw20(&app, cw20_addr.clone(), staking_addr.clone()),
+        Uint128::new(80)
+    );
+
+    let targets: Vec<TargetResponse> = app
+        .wrap()
+        .query_wasm_smart(distributor_addr.clone(), &QueryMsg::Targets {})
+        .unwrap();
+    assert_eq!(
+        targets,
+        vec![TargetResponse {
+            target: Target {
+                address: staking_addr.clone(),
+                kind: TargetKind::Staking,
+                weight: 1,
+            },
+            distributed: Uint128::new(80),
+        }]
+    );
+
+    // Pending rewards go to the old targets before they are replaced.
+    app.update_block(|block| block.height += 5);
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        distributor_addr.clone(),
+        &ExecuteMsg::UpdateTargets {
+            targets: vec![TargetInfo {
+                address: "treasury".to_string(),
+                kind: TargetKind::Address,
+                weight: 1,
+            }],
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        get_balance_cw20(&app, cw20_addr.clone(), staking_addr.clone()),
+        Uint128::new(130)
+    );
+    assert_eq!(
+        get_balance_cw20(&app, cw20_addr.clone(), "treasury"),
+        Uint128::new(20)
+    );
+
+    // The staking contract may not be changed while targets are set.
+    let other_staking = instantiate_staking(&mut app, cw20_addr.clone());
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            distributor_addr,
+            &ExecuteMsg::UpdateConfig {
+                staking_addr: other_staking.to_string(),
+                reward_rate: Uint128::new(10),
+                reward_token: cw20_addr.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::TargetsSet {});
+}
+
 #[test]
 fn test_instantiate_invalid_addrs() {
     let mut app = App::default();
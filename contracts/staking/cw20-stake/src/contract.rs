@@ -2,8 +2,8 @@
 use cosmwasm_std::entry_point;
 
 use cosmwasm_std::{
-    from_json_binary, to_json_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response,
-    StdError, StdResult, Uint128,
+    from_json_binary, to_json_binary, Addr, Binary, BlockInfo, Deps, DepsMut, Empty, Env,
+    MessageInfo, Order, Response, StdError, StdResult, Storage, Timestamp, Uint128,
 };
 use cw2::{get_contract_version, set_contract_version, ContractVersion};
 use cw20::{Cw20ReceiveMsg, TokenInfoResponse};
@@ -17,9 +17,14 @@ pub use cw20_base::contract::{
     query_token_info,
 };
 pub use cw20_base::enumerable::{query_all_accounts, query_owner_allowances};
+use cw721::{Cw721ReceiveMsg, NftInfoResponse, NumTokensResponse, OwnerOfResponse, TokensResponse};
 use cw_controllers::ClaimsResponse;
+use cw_storage_plus::Bound;
 use cw_utils::Duration;
 use dao_hooks::stake::{stake_hook_msgs, unstake_hook_msgs};
+use dao_voting::compaction::{
+    compact_snapshot_balances, Compaction, DEFAULT_COMPACT_LIMIT, MAX_COMPACT_LIMIT,
+};
 use dao_voting::duration::validate_duration;
 
 use crate::math;
@@ -29,13 +34,19 @@ use crate::msg::{
     TotalStakedAtHeightResponse, TotalValueResponse,
 };
 use crate::state::{
-    Config, BALANCE, CLAIMS, CONFIG, HOOKS, MAX_CLAIMS, STAKED_BALANCES, STAKED_TOTAL,
+    Config, GovernanceUnbonding, Position, BALANCE, CLAIMS, COMPACTION_CURSOR, CONFIG,
+    EMERGENCY_ACTIVATION, GOVERNANCE_UNBONDING, HOOKS, IMMEDIATE_UNSTAKES, MAX_CLAIMS,
+    OWNER_POSITIONS, POSITIONS, POSITION_BALANCES, POSITION_COUNT, POSITION_NFTS,
+    SNAPSHOT_RETENTION, STAKED_BALANCES, STAKED_TOTAL,
 };
 use crate::ContractError;
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:cw20-stake";
 pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+const DEFAULT_TOKENS_LIMIT: u32 = 10;
+const MAX_TOKENS_LIMIT: u32 = 30;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -89,6 +100,29 @@ pub fn execute(
         ExecuteMsg::AddHook { addr } => execute_add_hook(deps, env, info, addr),
         ExecuteMsg::RemoveHook { addr } => execute_remove_hook(deps, env, info, addr),
         ExecuteMsg::UpdateOwnership(action) => execute_update_owner(deps, info, env, action),
+        ExecuteMsg::UpdateSnapshotRetention { blocks } => {
+            execute_update_snapshot_retention(deps, info, blocks)
+        }
+        ExecuteMsg::Compact { limit } => execute_compact(deps, env, limit),
+        ExecuteMsg::TriggerEmergencyMode { delay } => {
+            execute_trigger_emergency_mode(deps, env, info, delay)
+        }
+        ExecuteMsg::UpdateGovernanceUnbonding { unbonding } => {
+            execute_update_governance_unbonding(deps, info, unbonding)
+        }
+        ExecuteMsg::UpdatePositionNfts { enabled } => {
+            execute_update_position_nfts(deps, info, enabled)
+        }
+        ExecuteMsg::TransferNft {
+            recipient,
+            token_id,
+        } => execute_transfer_nft(deps, env, info, recipient, token_id),
+        ExecuteMsg::SendNft {
+            contract,
+            token_id,
+            msg,
+        } => execute_send_nft(deps, env, info, contract, token_id, msg),
+        ExecuteMsg::RedeemPosition { token_id } => execute_redeem_position(deps, info, token_id),
     }
 }
 
@@ -134,6 +168,7 @@ pub fn execute_receive(
     match msg {
         ReceiveMsg::Stake {} => execute_stake(deps, env, sender, wrapper.amount),
         ReceiveMsg::Fund {} => execute_fund(deps, env, &sender, wrapper.amount),
+        ReceiveMsg::StakePosition {} => execute_stake_position(deps, env, sender, wrapper.amount),
     }
 }
 
@@ -172,6 +207,44 @@ pub fn execute_stake(
         .add_attribute("amount", amount))
 }
 
+pub fn execute_stake_position(
+    mut deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    if !POSITION_NFTS.may_load(deps.storage)?.unwrap_or(false) {
+        return Err(ContractError::PositionNftsDisabled {});
+    }
+
+    let before = STAKED_BALANCES
+        .may_load(deps.storage, &sender)?
+        .unwrap_or_default();
+    let response = execute_stake(deps.branch(), env.clone(), sender.clone(), amount)?;
+    let after = STAKED_BALANCES.load(deps.storage, &sender)?;
+    let position_amount = after - before;
+
+    let id = POSITION_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+    POSITION_COUNT.save(deps.storage, &id)?;
+    POSITIONS.save(
+        deps.storage,
+        id,
+        &Position {
+            owner: sender.clone(),
+            amount: position_amount,
+            stake_height: env.block.height,
+        },
+    )?;
+    OWNER_POSITIONS.save(deps.storage, (&sender, id), &Empty {})?;
+    POSITION_BALANCES.update(deps.storage, &sender, |bal| -> StdResult<Uint128> {
+        Ok(bal.unwrap_or_default().checked_add(position_amount)?)
+    })?;
+
+    Ok(response
+        .add_attribute("token_id", id.to_string())
+        .add_attribute("position_amount", position_amount))
+}
+
 pub fn execute_unstake(
     deps: DepsMut,
     env: Env,
@@ -191,8 +264,19 @@ pub fn execute_unstake(
     if amount > staked_total {
         return Err(ContractError::ImpossibleUnstake {});
     }
+    let in_positions = POSITION_BALANCES
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    if !in_positions.is_zero() {
+        let staked = STAKED_BALANCES
+            .may_load(deps.storage, &info.sender)?
+            .unwrap_or_default();
+        if amount > staked.saturating_sub(in_positions) {
+            return Err(ContractError::PositionLocked {});
+        }
+    }
     let amount_to_claim = math::amount_to_claim(staked_total, balance, amount);
-    STAKED_BALANCES.update(
+    let staked = STAKED_BALANCES.update(
         deps.storage,
         &info.sender,
         env.block.height,
@@ -206,6 +290,9 @@ pub fn execute_unstake(
             Ok(total.unwrap().checked_sub(amount)?)
         },
     )?;
+    if GOVERNANCE_UNBONDING.may_load(deps.storage)? == Some(GovernanceUnbonding::Immediate) {
+        remove_past_voting_power(deps.storage, &info.sender, staked)?;
+    }
     BALANCE.save(
         deps.storage,
         &balance
@@ -213,7 +300,12 @@ pub fn execute_unstake(
             .map_err(StdError::overflow)?,
     )?;
     let hook_msgs = unstake_hook_msgs(HOOKS, deps.storage, info.sender.clone(), amount)?;
-    match config.unstaking_duration {
+    let unstaking_duration = if is_emergency_mode_active(deps.storage, &env.block)? {
+        None
+    } else {
+        config.unstaking_duration
+    };
+    match unstaking_duration {
         None => {
             let cw_send_msg = cw20::Cw20ExecuteMsg::Transfer {
                 recipient: info.sender.to_string(),
@@ -254,12 +346,57 @@ pub fn execute_unstake(
     }
 }
 
+/// Caps ADDRESS's staked balance at every past height to STAKED, its
+/// balance after unstaking, and records the amounts removed so that
+/// they are also removed from the total staked at those heights.
+fn remove_past_voting_power(
+    storage: &mut dyn Storage,
+    address: &Addr,
+    staked: Uint128,
+) -> StdResult<()> {
+    let changes = STAKED_BALANCES
+        .changelog()
+        .prefix(address)
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    // Each change records the balance held from the previous change
+    // up to and including its height.
+    let mut first = 0;
+    for (last, mut change) in changes {
+        if let Some(old) = change.old {
+            if old > staked {
+                change.old = Some(staked);
+                STAKED_BALANCES
+                    .changelog()
+                    .save(storage, (address, last), &change)?;
+                IMMEDIATE_UNSTAKES.update(storage, (last, first), |removed| -> StdResult<_> {
+                    Ok(removed.unwrap_or_default().checked_add(old - staked)?)
+                })?;
+            }
+        }
+        first = last;
+    }
+    Ok(())
+}
+
 pub fn execute_claim(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
 ) -> Result<Response, ContractError> {
-    let release = CLAIMS.claim_tokens(deps.storage, &info.sender, &_env.block, None)?;
+    let release = if is_emergency_mode_active(deps.storage, &_env.block)? {
+        // Claims are released once the block passes their release
+        // time, so claiming at the last possible block releases all
+        // of them.
+        let end_of_time = BlockInfo {
+            height: u64::MAX,
+            time: Timestamp::from_nanos(u64::MAX),
+            chain_id: _env.block.chain_id.clone(),
+        };
+        CLAIMS.claim_tokens(deps.storage, &info.sender, &end_of_time, None)?
+    } else {
+        CLAIMS.claim_tokens(deps.storage, &info.sender, &_env.block, None)?
+    };
     if release.is_zero() {
         return Err(ContractError::NothingToClaim {});
     }
@@ -323,6 +460,251 @@ pub fn execute_remove_hook(
         .add_attribute("hook", addr))
 }
 
+pub fn execute_update_snapshot_retention(
+    deps: DepsMut,
+    info: MessageInfo,
+    blocks: Option<u64>,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    match blocks {
+        Some(0) => return Err(ContractError::ZeroSnapshotRetention {}),
+        Some(blocks) => SNAPSHOT_RETENTION.save(deps.storage, &blocks)?,
+        None => SNAPSHOT_RETENTION.remove(deps.storage),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "update_snapshot_retention")
+        .add_attribute(
+            "blocks",
+            blocks
+                .map(|b| b.to_string())
+                .unwrap_or_else(|| "none".to_string()),
+        ))
+}
+
+pub fn execute_update_governance_unbonding(
+    deps: DepsMut,
+    info: MessageInfo,
+    unbonding: GovernanceUnbonding,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    GOVERNANCE_UNBONDING.save(deps.storage, &unbonding)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_governance_unbonding")
+        .add_attribute("unbonding", format!("{unbonding:?}")))
+}
+
+pub fn execute_update_position_nfts(
+    deps: DepsMut,
+    info: MessageInfo,
+    enabled: bool,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    POSITION_NFTS.save(deps.storage, &enabled)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_position_nfts")
+        .add_attribute("enabled", enabled.to_string()))
+}
+
+/// Loads the position with TOKEN_ID and errors if SENDER does not own
+/// it.
+fn load_owned_position(
+    storage: &dyn Storage,
+    sender: &Addr,
+    token_id: &str,
+) -> Result<(u64, Position), ContractError> {
+    let no_such_position = || ContractError::NoSuchPosition {
+        token_id: token_id.to_string(),
+    };
+    let id = parse_token_id(token_id).ok_or_else(no_such_position)?;
+    let position = POSITIONS
+        .may_load(storage, id)?
+        .ok_or_else(no_such_position)?;
+    if position.owner != *sender {
+        return Err(ContractError::NotPositionOwner {});
+    }
+    Ok((id, position))
+}
+
+fn parse_token_id(token_id: &str) -> Option<u64> {
+    token_id.parse().ok()
+}
+
+/// Moves a position, and the staked balance it holds, from its owner
+/// to RECIPIENT. Balances are updated at the current height so that
+/// voting power moves with the position from the next block.
+fn transfer_position(
+    deps: DepsMut,
+    env: &Env,
+    id: u64,
+    mut position: Position,
+    recipient: Addr,
+) -> Result<Response, ContractError> {
+    let sender = position.owner.clone();
+    let amount = position.amount;
+
+    STAKED_BALANCES.update(
+        deps.storage,
+        &sender,
+        env.block.height,
+        |bal| -> StdResult<Uint128> { Ok(bal.unwrap_or_default().checked_sub(amount)?) },
+    )?;
+    STAKED_BALANCES.update(
+        deps.storage,
+        &recipient,
+        env.block.height,
+        |bal| -> StdResult<Uint128> { Ok(bal.unwrap_or_default().checked_add(amount)?) },
+    )?;
+    POSITION_BALANCES.update(deps.storage, &sender, |bal| -> StdResult<Uint128> {
+        Ok(bal.unwrap_or_default().checked_sub(amount)?)
+    })?;
+    POSITION_BALANCES.update(deps.storage, &recipient, |bal| -> StdResult<Uint128> {
+        Ok(bal.unwrap_or_default().checked_add(amount)?)
+    })?;
+    OWNER_POSITIONS.remove(deps.storage, (&sender, id));
+    OWNER_POSITIONS.save(deps.storage, (&recipient, id), &Empty {})?;
+
+    position.owner = recipient.clone();
+    POSITIONS.save(deps.storage, id, &position)?;
+
+    let unstake_msgs = unstake_hook_msgs(HOOKS, deps.storage, sender.clone(), amount)?;
+    let stake_msgs = stake_hook_msgs(HOOKS, deps.storage, recipient.clone(), amount)?;
+
+    Ok(Response::new()
+        .add_submessages(unstake_msgs)
+        .add_submessages(stake_msgs)
+        .add_attribute("sender", sender)
+        .add_attribute("recipient", recipient)
+        .add_attribute("token_id", id.to_string())
+        .add_attribute("amount", amount))
+}
+
+pub fn execute_transfer_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let recipient = deps.api.addr_validate(&recipient)?;
+    let (id, position) = load_owned_position(deps.storage, &info.sender, &token_id)?;
+    let response = transfer_position(deps, &env, id, position, recipient)?;
+    Ok(response.add_attribute("action", "transfer_nft"))
+}
+
+pub fn execute_send_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    contract: String,
+    token_id: String,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    let contract = deps.api.addr_validate(&contract)?;
+    let (id, position) = load_owned_position(deps.storage, &info.sender, &token_id)?;
+    let response = transfer_position(deps, &env, id, position, contract.clone())?;
+    let receive = Cw721ReceiveMsg {
+        sender: info.sender.into_string(),
+        token_id,
+        msg,
+    }
+    .into_cosmos_msg(contract)?;
+    Ok(response
+        .add_message(receive)
+        .add_attribute("action", "send_nft"))
+}
+
+pub fn execute_redeem_position(
+    deps: DepsMut,
+    info: MessageInfo,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let (id, position) = load_owned_position(deps.storage, &info.sender, &token_id)?;
+
+    POSITION_BALANCES.update(deps.storage, &info.sender, |bal| -> StdResult<Uint128> {
+        Ok(bal.unwrap_or_default().checked_sub(position.amount)?)
+    })?;
+    OWNER_POSITIONS.remove(deps.storage, (&info.sender, id));
+    POSITIONS.remove(deps.storage, id);
+
+    Ok(Response::new()
+        .add_attribute("action", "redeem_position")
+        .add_attribute("owner", info.sender)
+        .add_attribute("token_id", token_id)
+        .add_attribute("amount", position.amount))
+}
+
+pub fn execute_compact(
+    deps: DepsMut,
+    env: Env,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let retention = SNAPSHOT_RETENTION
+        .may_load(deps.storage)?
+        .ok_or(ContractError::CompactionDisabled {})?;
+    let limit = limit
+        .unwrap_or(DEFAULT_COMPACT_LIMIT)
+        .min(MAX_COMPACT_LIMIT);
+    let start_after = COMPACTION_CURSOR.may_load(deps.storage)?;
+
+    let Compaction {
+        pruned,
+        next_start_after,
+    } = compact_snapshot_balances(
+        deps.storage,
+        &STAKED_BALANCES,
+        start_after,
+        limit,
+        env.block.height,
+        retention,
+    )?;
+
+    match next_start_after {
+        Some(addr) => COMPACTION_CURSOR.save(deps.storage, &addr)?,
+        None => COMPACTION_CURSOR.remove(deps.storage),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "compact")
+        .add_attribute("pruned", pruned.to_string()))
+}
+
+pub fn execute_trigger_emergency_mode(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    delay: Duration,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    if EMERGENCY_ACTIVATION.may_load(deps.storage)?.is_some() {
+        return Err(ContractError::EmergencyModeTriggered {});
+    }
+    if matches!(delay, Duration::Height(0) | Duration::Time(0)) {
+        return Err(ContractError::ZeroEmergencyDelay {});
+    }
+
+    let activation = delay.after(&env.block);
+    EMERGENCY_ACTIVATION.save(deps.storage, &activation)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "trigger_emergency_mode")
+        .add_attribute("activation", activation.to_string()))
+}
+
+/// Returns true if emergency mode has been triggered and its
+/// activation delay has passed.
+fn is_emergency_mode_active(storage: &dyn Storage, block: &BlockInfo) -> StdResult<bool> {
+    Ok(EMERGENCY_ACTIVATION
+        .may_load(storage)?
+        .map_or(false, |activation| activation.is_expired(block)))
+}
+
 pub fn execute_update_owner(
     deps: DepsMut,
     info: MessageInfo,
@@ -347,13 +729,91 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::TotalValue {} => to_json_binary(&query_total_value(deps, env)?),
         QueryMsg::Claims { address } => to_json_binary(&query_claims(deps, address)?),
         QueryMsg::GetHooks {} => to_json_binary(&query_hooks(deps)?),
-        QueryMsg::ListStakers { start_after, limit } => {
-            query_list_stakers(deps, start_after, limit)
-        }
+        QueryMsg::ListStakers {
+            start_after,
+            limit,
+            descending,
+        } => query_list_stakers(deps, start_after, limit, descending),
         QueryMsg::Ownership {} => to_json_binary(&cw_ownable::get_ownership(deps.storage)?),
+        QueryMsg::SnapshotRetention {} => {
+            to_json_binary(&SNAPSHOT_RETENTION.may_load(deps.storage)?)
+        }
+        QueryMsg::EmergencyMode {} => to_json_binary(&EMERGENCY_ACTIVATION.may_load(deps.storage)?),
+        QueryMsg::GovernanceUnbonding {} => to_json_binary(
+            &GOVERNANCE_UNBONDING
+                .may_load(deps.storage)?
+                .unwrap_or(GovernanceUnbonding::Snapshot),
+        ),
+        QueryMsg::PositionNfts {} => {
+            to_json_binary(&POSITION_NFTS.may_load(deps.storage)?.unwrap_or(false))
+        }
+        QueryMsg::OwnerOf { token_id, .. } => to_json_binary(&query_owner_of(deps, token_id)?),
+        QueryMsg::NftInfo { token_id } => to_json_binary(&query_nft_info(deps, token_id)?),
+        QueryMsg::Tokens {
+            owner,
+            start_after,
+            limit,
+        } => to_json_binary(&query_tokens(deps, owner, start_after, limit)?),
+        QueryMsg::NumTokens {} => to_json_binary(&NumTokensResponse {
+            count: POSITIONS
+                .keys(deps.storage, None, None, Order::Ascending)
+                .count() as u64,
+        }),
     }
 }
 
+fn load_position(deps: Deps, token_id: &str) -> StdResult<Position> {
+    let id = parse_token_id(token_id)
+        .ok_or_else(|| StdError::generic_err(format!("invalid token ID: {token_id}")))?;
+    POSITIONS.load(deps.storage, id)
+}
+
+pub fn query_owner_of(deps: Deps, token_id: String) -> StdResult<OwnerOfResponse> {
+    let position = load_position(deps, &token_id)?;
+    Ok(OwnerOfResponse {
+        owner: position.owner.into_string(),
+        approvals: vec![],
+    })
+}
+
+pub fn query_nft_info(deps: Deps, token_id: String) -> StdResult<NftInfoResponse<Position>> {
+    let position = load_position(deps, &token_id)?;
+    Ok(NftInfoResponse {
+        token_uri: None,
+        extension: position,
+    })
+}
+
+pub fn query_tokens(
+    deps: Deps,
+    owner: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<TokensResponse> {
+    let owner = deps.api.addr_validate(&owner)?;
+    let start_after = start_after
+        .map(|token_id| {
+            parse_token_id(&token_id)
+                .ok_or_else(|| StdError::generic_err(format!("invalid token ID: {token_id}")))
+        })
+        .transpose()?;
+    let limit = limit.unwrap_or(DEFAULT_TOKENS_LIMIT).min(MAX_TOKENS_LIMIT) as usize;
+
+    let tokens = OWNER_POSITIONS
+        .prefix(&owner)
+        .keys(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|id| id.map(|id| id.to_string()))
+        .collect::<StdResult<_>>()?;
+
+    Ok(TokensResponse { tokens })
+}
+
 pub fn query_staked_balance_at_height(
     deps: Deps,
     env: Env,
@@ -377,6 +837,18 @@ pub fn query_total_staked_at_height(
     let total = STAKED_TOTAL
         .may_load_at_height(deps.storage, height)?
         .unwrap_or_default();
+    let removed = IMMEDIATE_UNSTAKES
+        .range(
+            deps.storage,
+            Some(Bound::inclusive((height, 0))),
+            None,
+            Order::Ascending,
+        )
+        .filter(|entry| !matches!(entry, Ok(((_, first), _)) if *first >= height))
+        .try_fold(Uint128::zero(), |sum, entry| {
+            entry.map(|(_, removed)| sum + removed)
+        })?;
+    let total = total.checked_sub(removed)?;
     Ok(TotalStakedAtHeightResponse { total, height })
 }
 
@@ -429,6 +901,7 @@ pub fn query_list_stakers(
     deps: Deps,
     start_after: Option<String>,
     limit: Option<u32>,
+    descending: Option<bool>,
 ) -> StdResult<Binary> {
     let start_at = start_after
         .map(|addr| deps.api.addr_validate(&addr))
@@ -439,7 +912,7 @@ pub fn query_list_stakers(
         &STAKED_BALANCES,
         start_at.as_ref(),
         limit,
-        cosmwasm_std::Order::Ascending,
+        cw_paginate_storage::order(descending),
     )?;
 
     let stakers = stakers
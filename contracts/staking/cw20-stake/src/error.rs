@@ -39,6 +39,30 @@ pub enum ContractError {
     #[error("Nothing to unstake")]
     NothingStaked {},
 
+    #[error("Snapshot compaction is disabled")]
+    CompactionDisabled {},
+
+    #[error("Snapshot retention must be greater than zero")]
+    ZeroSnapshotRetention {},
+
+    #[error("Emergency mode has already been triggered")]
+    EmergencyModeTriggered {},
+
+    #[error("Emergency mode activation delay must be greater than zero")]
+    ZeroEmergencyDelay {},
+
     #[error("Too many outstanding claims. Claim some tokens before unstaking more.")]
     TooManyClaims {},
+
+    #[error("Position NFTs are not enabled")]
+    PositionNftsDisabled {},
+
+    #[error("No position with token ID ({token_id})")]
+    NoSuchPosition { token_id: String },
+
+    #[error("Only the owner of a position may transfer or redeem it")]
+    NotPositionOwner {},
+
+    #[error("Can not unstake a balance held in positions. Redeem the positions first.")]
+    PositionLocked {},
 }
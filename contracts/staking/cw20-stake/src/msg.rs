@@ -1,9 +1,11 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Uint128;
+use cosmwasm_std::{Binary, Uint128};
 use cw20::Cw20ReceiveMsg;
 
 use cw_utils::Duration;
 
+use crate::state::GovernanceUnbonding;
+
 use cw_ownable::cw_ownable_execute;
 
 pub use cw_controllers::ClaimsResponse;
@@ -23,17 +25,64 @@ pub struct InstantiateMsg {
 #[cw_serde]
 pub enum ExecuteMsg {
     Receive(Cw20ReceiveMsg),
-    Unstake { amount: Uint128 },
+    Unstake {
+        amount: Uint128,
+    },
     Claim {},
-    UpdateConfig { duration: Option<Duration> },
-    AddHook { addr: String },
-    RemoveHook { addr: String },
+    UpdateConfig {
+        duration: Option<Duration>,
+    },
+    AddHook {
+        addr: String,
+    },
+    RemoveHook {
+        addr: String,
+    },
+    UpdateSnapshotRetention {
+        blocks: Option<u64>,
+    },
+    Compact {
+        limit: Option<u32>,
+    },
+    TriggerEmergencyMode {
+        delay: Duration,
+    },
+    UpdateGovernanceUnbonding {
+        unbonding: GovernanceUnbonding,
+    },
+    /// Enables or disables minting position NFTs. Existing positions
+    /// may be transferred and redeemed either way. Only the owner may
+    /// call this method.
+    UpdatePositionNfts {
+        enabled: bool,
+    },
+    /// Transfers a position NFT, and the staked balance it holds, to
+    /// RECIPIENT.
+    TransferNft {
+        recipient: String,
+        token_id: String,
+    },
+    /// Transfers a position NFT, and the staked balance it holds, to
+    /// CONTRACT and notifies it with a `Cw721ReceiveMsg`.
+    SendNft {
+        contract: String,
+        token_id: String,
+        msg: Binary,
+    },
+    /// Burns a position NFT. Its staked balance stays staked by its
+    /// owner and may then be unstaked.
+    RedeemPosition {
+        token_id: String,
+    },
 }
 
 #[cw_serde]
 pub enum ReceiveMsg {
     Stake {},
     Fund {},
+    /// Stakes the tokens and mints a position NFT holding the staked
+    /// balance to the sender. Requires position NFTs to be enabled.
+    StakePosition {},
 }
 
 #[cw_serde]
@@ -60,9 +109,44 @@ pub enum QueryMsg {
     ListStakers {
         start_after: Option<String>,
         limit: Option<u32>,
+        descending: Option<bool>,
     },
     #[returns(::cw_ownable::Ownership::<::cosmwasm_std::Addr>)]
     Ownership {},
+    #[returns(Option<u64>)]
+    SnapshotRetention {},
+    /// Returns when emergency mode activates, or `None` if it has not
+    /// been triggered.
+    #[returns(Option<::cw_utils::Expiration>)]
+    EmergencyMode {},
+    /// Returns when unstaked tokens stop counting towards voting
+    /// power.
+    #[returns(GovernanceUnbonding)]
+    GovernanceUnbonding {},
+    /// Returns true if position NFTs may be minted.
+    #[returns(bool)]
+    PositionNfts {},
+    /// Returns the owner of a position NFT. Positions have no
+    /// approvals.
+    #[returns(::cw721::OwnerOfResponse)]
+    OwnerOf {
+        token_id: String,
+        include_expired: Option<bool>,
+    },
+    /// Returns the staked balance and stake height of a position NFT.
+    #[returns(::cw721::NftInfoResponse<crate::state::Position>)]
+    NftInfo { token_id: String },
+    /// Lists the token IDs of the position NFTs held by OWNER.
+    #[returns(::cw721::TokensResponse)]
+    Tokens {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the number of position NFTs that have been minted and
+    /// not redeemed.
+    #[returns(::cw721::NumTokensResponse)]
+    NumTokens {},
 }
 
 #[cw_serde]
@@ -1,9 +1,9 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Empty, Uint128};
 use cw_controllers::Claims;
 use cw_hooks::Hooks;
-use cw_storage_plus::{Item, SnapshotItem, SnapshotMap, Strategy};
-use cw_utils::Duration;
+use cw_storage_plus::{Item, Map, SnapshotItem, SnapshotMap, Strategy};
+use cw_utils::{Duration, Expiration};
 
 #[cw_serde]
 pub struct Config {
@@ -11,6 +11,22 @@ pub struct Config {
     pub unstaking_duration: Option<Duration>,
 }
 
+/// When unstaked tokens stop counting towards voting power. Tokens
+/// remain illiquid for `Config::unstaking_duration` either way.
+#[cw_serde]
+#[derive(Copy)]
+pub enum GovernanceUnbonding {
+    /// Voting power at past heights is unchanged by unstaking, so
+    /// tokens unstaked after a proposal is created may still vote on
+    /// it.
+    Snapshot,
+    /// Unstaking removes voting power at every past height, so an
+    /// address never has more voting power at a past height than it
+    /// had staked after its last unstake. The removed power is also
+    /// removed from the total staked at those heights.
+    Immediate,
+}
+
 // `"config"` key stores v1 configuration.
 pub const CONFIG: Item<Config> = Item::new("config_v2");
 
@@ -35,5 +51,54 @@ pub const CLAIMS: Claims = Claims::new("claims");
 
 pub const BALANCE: Item<Uint128> = Item::new("balance");
 
+/// The number of blocks an address must have had no staked balance
+/// for before its balance history may be compacted. Compaction is
+/// disabled if unset.
+pub const SNAPSHOT_RETENTION: Item<u64> = Item::new("snapshot_retention");
+
+/// The last address examined by compaction.
+pub const COMPACTION_CURSOR: Item<Addr> = Item::new("compaction_cursor");
+
+/// The point at which emergency mode becomes active, if it has been
+/// triggered. While active, unstaked tokens are returned immediately
+/// and any outstanding claims may be claimed regardless of their
+/// release time. Once set, this may not be changed or removed.
+pub const EMERGENCY_ACTIVATION: Item<Expiration> = Item::new("emergency_activation");
+
+/// When unstaked tokens stop counting towards voting power. Defaults
+/// to `GovernanceUnbonding::Snapshot` if unset.
+pub const GOVERNANCE_UNBONDING: Item<GovernanceUnbonding> = Item::new("governance_unbonding");
+
+/// Amounts removed from the total staked at past heights by unstaking
+/// while governance unbonding is `GovernanceUnbonding::Immediate`,
+/// keyed by the last and first heights they were removed from. The
+/// first height is exclusive.
+pub const IMMEDIATE_UNSTAKES: Map<(u64, u64), Uint128> = Map::new("immediate_unstakes");
+
+/// A staked balance represented by a transferable NFT. The position's
+/// amount remains part of its owner's staked balance, but may not be
+/// unstaked until the position is redeemed.
+#[cw_serde]
+pub struct Position {
+    pub owner: Addr,
+    /// The staked balance held by the position, in the same units as
+    /// `StakedBalanceAtHeight`.
+    pub amount: Uint128,
+    /// The height at which the position was staked.
+    pub stake_height: u64,
+}
+
+/// If true, stakers may mint position NFTs with
+/// `ReceiveMsg::StakePosition`. Defaults to false if unset.
+pub const POSITION_NFTS: Item<bool> = Item::new("position_nfts");
+/// Positions by token ID.
+pub const POSITIONS: Map<u64, Position> = Map::new("positions");
+/// The number of positions that have been minted.
+pub const POSITION_COUNT: Item<u64> = Item::new("position_count");
+/// The token IDs of the positions held by each address.
+pub const OWNER_POSITIONS: Map<(&Addr, u64), Empty> = Map::new("owner_positions");
+/// The part of each address's staked balance held in positions.
+pub const POSITION_BALANCES: Map<&Addr, Uint128> = Map::new("position_balances");
+
 // Hooks to contracts that will receive staking and unstaking messages
 pub const HOOKS: Hooks = Hooks::new("hooks");
@@ -15,7 +15,7 @@ use crate::msg::{
     StakedBalanceAtHeightResponse, StakedValueResponse, StakerBalanceResponse,
     TotalStakedAtHeightResponse, TotalValueResponse,
 };
-use crate::state::{Config, MAX_CLAIMS};
+use crate::state::{Config, GovernanceUnbonding, Position, MAX_CLAIMS};
 use crate::ContractError;
 
 use cw20_stake_v1 as v1;
@@ -1017,6 +1017,7 @@ fn test_query_list_stakers() {
             &QueryMsg::ListStakers {
                 start_after: None,
                 limit: Some(2),
+                descending: None,
             },
         )
         .unwrap();
@@ -1040,10 +1041,11 @@ fn test_query_list_stakers() {
     let stakers: ListStakersResponse = app
         .wrap()
         .query_wasm_smart(
-            staking_addr,
+            staking_addr.clone(),
             &QueryMsg::ListStakers {
                 start_after: Some("ekez1".to_string()),
                 limit: Some(2),
+                descending: None,
             },
         )
         .unwrap();
@@ -1061,6 +1063,34 @@ fn test_query_list_stakers() {
         ],
     };
 
+    assert_eq!(stakers, test_res);
+
+    // page backwards from the last staker
+    let stakers: ListStakersResponse = app
+        .wrap()
+        .query_wasm_smart(
+            staking_addr,
+            &QueryMsg::ListStakers {
+                start_after: Some("ekez4".to_string()),
+                limit: Some(2),
+                descending: Some(true),
+            },
+        )
+        .unwrap();
+
+    let test_res = ListStakersResponse {
+        stakers: vec![
+            StakerBalanceResponse {
+                address: "ekez3".to_string(),
+                balance: Uint128::new(30),
+            },
+            StakerBalanceResponse {
+                address: "ekez2".to_string(),
+                balance: Uint128::new(20),
+            },
+        ],
+    };
+
     assert_eq!(stakers, test_res)
 }
 
@@ -1116,6 +1146,416 @@ fn test_ownership_transfer() {
     );
 }
 
+#[test]
+fn test_compact() {
+    let mut app = App::default();
+    let (staking_addr, cw20_addr) = setup_test_case(
+        &mut app,
+        vec![
+            Cw20Coin {
+                address: ADDR1.to_string(),
+                amount: Uint128::new(100),
+            },
+            Cw20Coin {
+                address: ADDR2.to_string(),
+                amount: Uint128::new(100),
+            },
+        ],
+        None,
+    );
+
+    stake_tokens(
+        &mut app,
+        &staking_addr,
+        &cw20_addr,
+        mock_info(ADDR1, &[]),
+        Uint128::new(100),
+    )
+    .unwrap();
+    stake_tokens(
+        &mut app,
+        &staking_addr,
+        &cw20_addr,
+        mock_info(ADDR2, &[]),
+        Uint128::new(100),
+    )
+    .unwrap();
+    app.update_block(next_block);
+    let staked_height = app.block_info().height;
+    unstake_tokens(
+        &mut app,
+        &staking_addr,
+        mock_info(ADDR1, &[]),
+        Uint128::new(100),
+    )
+    .unwrap();
+    app.update_block(next_block);
+
+    // Compaction is disabled until the owner sets a retention period.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR3),
+            staking_addr.clone(),
+            &ExecuteMsg::Compact { limit: None },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::CompactionDisabled {});
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR3),
+            staking_addr.clone(),
+            &ExecuteMsg::UpdateSnapshotRetention { blocks: Some(10) },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Ownership(OwnershipError::NotOwner));
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            staking_addr.clone(),
+            &ExecuteMsg::UpdateSnapshotRetention { blocks: Some(0) },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::ZeroSnapshotRetention {});
+
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        staking_addr.clone(),
+        &ExecuteMsg::UpdateSnapshotRetention { blocks: Some(10) },
+        &[],
+    )
+    .unwrap();
+    let retention: Option<u64> = app
+        .wrap()
+        .query_wasm_smart(&staking_addr, &QueryMsg::SnapshotRetention {})
+        .unwrap();
+    assert_eq!(retention, Some(10));
+
+    // ADDR1's balance changed inside the retention window so their
+    // history is kept.
+    app.execute_contract(
+        Addr::unchecked(ADDR3),
+        staking_addr.clone(),
+        &ExecuteMsg::Compact { limit: None },
+        &[],
+    )
+    .unwrap();
+    let res: StakedBalanceAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &staking_addr,
+            &QueryMsg::StakedBalanceAtHeight {
+                address: ADDR1.to_string(),
+                height: Some(staked_height),
+            },
+        )
+        .unwrap();
+    assert_eq!(res.balance, Uint128::new(100));
+
+    app.update_block(|b| b.height += 10);
+    app.execute_contract(
+        Addr::unchecked(ADDR3),
+        staking_addr.clone(),
+        &ExecuteMsg::Compact { limit: None },
+        &[],
+    )
+    .unwrap();
+
+    let res: StakedBalanceAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &staking_addr,
+            &QueryMsg::StakedBalanceAtHeight {
+                address: ADDR1.to_string(),
+                height: Some(staked_height),
+            },
+        )
+        .unwrap();
+    assert_eq!(res.balance, Uint128::zero());
+    assert_eq!(
+        query_staked_balance(&app, &staking_addr, ADDR2),
+        Uint128::new(100)
+    );
+
+    let stakers: ListStakersResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &staking_addr,
+            &QueryMsg::ListStakers {
+                start_after: None,
+                limit: None,
+                descending: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        stakers.stakers,
+        vec![StakerBalanceResponse {
+            address: ADDR2.to_string(),
+            balance: Uint128::new(100),
+        }]
+    );
+}
+
+#[test]
+fn test_emergency_mode() {
+    let mut app = App::default();
+    let (staking_addr, cw20_addr) = setup_test_case(
+        &mut app,
+        vec![Cw20Coin {
+            address: ADDR1.to_string(),
+            amount: Uint128::new(100),
+        }],
+        Some(Duration::Height(100)),
+    );
+
+    stake_tokens(
+        &mut app,
+        &staking_addr,
+        &cw20_addr,
+        mock_info(ADDR1, &[]),
+        Uint128::new(100),
+    )
+    .unwrap();
+    app.update_block(next_block);
+    unstake_tokens(
+        &mut app,
+        &staking_addr,
+        mock_info(ADDR1, &[]),
+        Uint128::new(50),
+    )
+    .unwrap();
+
+    // Only the owner may trigger emergency mode.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            staking_addr.clone(),
+            &ExecuteMsg::TriggerEmergencyMode {
+                delay: Duration::Height(5),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Ownership(OwnershipError::NotOwner));
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            staking_addr.clone(),
+            &ExecuteMsg::TriggerEmergencyMode {
+                delay: Duration::Height(0),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::ZeroEmergencyDelay {});
+
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        staking_addr.clone(),
+        &ExecuteMsg::TriggerEmergencyMode {
+            delay: Duration::Height(5),
+        },
+        &[],
+    )
+    .unwrap();
+    let activation: Option<cw_utils::Expiration> = app
+        .wrap()
+        .query_wasm_smart(&staking_addr, &QueryMsg::EmergencyMode {})
+        .unwrap();
+    assert_eq!(activation, Some(AtHeight(app.block_info().height + 5)));
+
+    // Emergency mode is a one-way switch.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            staking_addr.clone(),
+            &ExecuteMsg::TriggerEmergencyMode {
+                delay: Duration::Height(1),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::EmergencyModeTriggered {});
+
+    // Before activation the unstaking duration still applies.
+    let err: ContractError = claim_tokens(&mut app, &staking_addr, mock_info(ADDR1, &[]))
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NothingToClaim {});
+
+    app.update_block(|b| b.height += 5);
+
+    // Outstanding claims are released immediately.
+    claim_tokens(&mut app, &staking_addr, mock_info(ADDR1, &[])).unwrap();
+    assert_eq!(get_balance(&app, &cw20_addr, ADDR1), Uint128::new(50));
+
+    // Unstaking returns tokens without creating a claim.
+    unstake_tokens(
+        &mut app,
+        &staking_addr,
+        mock_info(ADDR1, &[]),
+        Uint128::new(50),
+    )
+    .unwrap();
+    assert_eq!(get_balance(&app, &cw20_addr, ADDR1), Uint128::new(100));
+    assert_eq!(query_claims(&app, &staking_addr, ADDR1), vec![]);
+}
+
+#[test]
+fn test_governance_unbonding() {
+    let mut app = App::default();
+    let (staking_addr, cw20_addr) = setup_test_case(
+        &mut app,
+        vec![Cw20Coin {
+            address: ADDR1.to_string(),
+            amount: Uint128::new(150),
+        }],
+        Some(Duration::Height(100)),
+    );
+
+    stake_tokens(
+        &mut app,
+        &staking_addr,
+        &cw20_addr,
+        mock_info(ADDR1, &[]),
+        Uint128::new(100),
+    )
+    .unwrap();
+    app.update_block(next_block);
+    // A proposal created now would measure voting power at this
+    // height.
+    let snapshot = app.block_info().height;
+    app.update_block(next_block);
+    unstake_tokens(
+        &mut app,
+        &staking_addr,
+        mock_info(ADDR1, &[]),
+        Uint128::new(60),
+    )
+    .unwrap();
+
+    let power_at_snapshot = |app: &App| -> Uint128 {
+        let res: StakedBalanceAtHeightResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &staking_addr,
+                &QueryMsg::StakedBalanceAtHeight {
+                    address: ADDR1.to_string(),
+                    height: Some(snapshot),
+                },
+            )
+            .unwrap();
+        res.balance
+    };
+
+    let unbonding: GovernanceUnbonding = app
+        .wrap()
+        .query_wasm_smart(&staking_addr, &QueryMsg::GovernanceUnbonding {})
+        .unwrap();
+    assert_eq!(unbonding, GovernanceUnbonding::Snapshot);
+    assert_eq!(power_at_snapshot(&app), Uint128::new(100));
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            staking_addr.clone(),
+            &ExecuteMsg::UpdateGovernanceUnbonding {
+                unbonding: GovernanceUnbonding::Immediate,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Ownership(OwnershipError::NotOwner));
+
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        staking_addr.clone(),
+        &ExecuteMsg::UpdateGovernanceUnbonding {
+            unbonding: GovernanceUnbonding::Immediate,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Unstakes made before the mode was set are unaffected.
+    assert_eq!(power_at_snapshot(&app), Uint128::new(100));
+
+    app.update_block(next_block);
+    unstake_tokens(
+        &mut app,
+        &staking_addr,
+        mock_info(ADDR1, &[]),
+        Uint128::new(20),
+    )
+    .unwrap();
+
+    // The unstaked tokens no longer vote, but are still unbonding.
+    assert_eq!(power_at_snapshot(&app), Uint128::new(20));
+    assert_eq!(
+        query_staked_balance(&app, &staking_addr, ADDR1),
+        Uint128::new(20)
+    );
+    let err: ContractError = claim_tokens(&mut app, &staking_addr, mock_info(ADDR1, &[]))
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NothingToClaim {});
+    assert_eq!(get_balance(&app, &cw20_addr, ADDR1), Uint128::new(50));
+
+    // The removed power is also removed from the total, so the
+    // unstaked tokens can not count against a proposal's quorum.
+    let total_at_snapshot = |app: &App| -> Uint128 {
+        let res: TotalStakedAtHeightResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &staking_addr,
+                &QueryMsg::TotalStakedAtHeight {
+                    height: Some(snapshot),
+                },
+            )
+            .unwrap();
+        res.total
+    };
+    assert_eq!(total_at_snapshot(&app), Uint128::new(20));
+    assert_eq!(query_total_staked(&app, &staking_addr), Uint128::new(20));
+
+    // Staking again does not restore power at past heights.
+    app.update_block(next_block);
+    stake_tokens(
+        &mut app,
+        &staking_addr,
+        &cw20_addr,
+        mock_info(ADDR1, &[]),
+        Uint128::new(50),
+    )
+    .unwrap();
+    app.update_block(next_block);
+    assert_eq!(power_at_snapshot(&app), Uint128::new(20));
+    assert_eq!(total_at_snapshot(&app), Uint128::new(20));
+    assert_eq!(query_total_staked(&app, &staking_addr), Uint128::new(70));
+}
+
 #[test]
 fn test_migrate_from_v1() {
     let mut app = App::default();
@@ -1195,3 +1635,216 @@ fn test_migrate_from_v1() {
         }
     );
 }
+
+#[test]
+fn test_position_nfts() {
+    let mut app = App::default();
+    let (staking_addr, cw20_addr) = setup_test_case(
+        &mut app,
+        vec![Cw20Coin {
+            address: ADDR1.to_string(),
+            amount: Uint128::new(100),
+        }],
+        None,
+    );
+
+    let stake_position = |app: &mut App, amount: u128| {
+        app.execute_contract(
+            Addr::unchecked(ADDR1),
+            cw20_addr.clone(),
+            &cw20::Cw20ExecuteMsg::Send {
+                contract: staking_addr.to_string(),
+                amount: Uint128::new(amount),
+                msg: to_json_binary(&ReceiveMsg::StakePosition {}).unwrap(),
+            },
+            &[],
+        )
+    };
+
+    // Disabled by default.
+    let err: ContractError = stake_position(&mut app, 60)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::PositionNftsDisabled {});
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            staking_addr.clone(),
+            &ExecuteMsg::UpdatePositionNfts { enabled: true },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Ownership(OwnershipError::NotOwner));
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        staking_addr.clone(),
+        &ExecuteMsg::UpdatePositionNfts { enabled: true },
+        &[],
+    )
+    .unwrap();
+
+    stake_position(&mut app, 60).unwrap();
+    stake_tokens(
+        &mut app,
+        &staking_addr,
+        &cw20_addr,
+        mock_info(ADDR1, &[]),
+        Uint128::new(40),
+    )
+    .unwrap();
+    let stake_height = app.block_info().height;
+    app.update_block(next_block);
+
+    let owner: cw721::OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &staking_addr,
+            &QueryMsg::OwnerOf {
+                token_id: "1".to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(owner.owner, ADDR1);
+    let info: cw721::NftInfoResponse<Position> = app
+        .wrap()
+        .query_wasm_smart(
+            &staking_addr,
+            &QueryMsg::NftInfo {
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        info.extension,
+        Position {
+            owner: Addr::unchecked(ADDR1),
+            amount: Uint128::new(60),
+            stake_height,
+        }
+    );
+
+    // The balance held in the position may not be unstaked.
+    let err: ContractError = unstake_tokens(
+        &mut app,
+        &staking_addr,
+        mock_info(ADDR1, &[]),
+        Uint128::new(41),
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, ContractError::PositionLocked {});
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR2),
+            staking_addr.clone(),
+            &ExecuteMsg::TransferNft {
+                recipient: ADDR2.to_string(),
+                token_id: "1".to_string(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NotPositionOwner {});
+
+    let before_transfer = app.block_info().height;
+    app.execute_contract(
+        Addr::unchecked(ADDR1),
+        staking_addr.clone(),
+        &ExecuteMsg::TransferNft {
+            recipient: ADDR2.to_string(),
+            token_id: "1".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+
+    // Voting power moves with the position, and snapshots taken
+    // before the transfer are unchanged.
+    assert_eq!(
+        query_staked_balance(&app, &staking_addr, ADDR1),
+        Uint128::new(40)
+    );
+    assert_eq!(
+        query_staked_balance(&app, &staking_addr, ADDR2),
+        Uint128::new(60)
+    );
+    let res: StakedBalanceAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &staking_addr,
+            &QueryMsg::StakedBalanceAtHeight {
+                address: ADDR1.to_string(),
+                height: Some(before_transfer),
+            },
+        )
+        .unwrap();
+    assert_eq!(res.balance, Uint128::new(100));
+    assert_eq!(query_total_staked(&app, &staking_addr), Uint128::new(100));
+
+    let tokens: cw721::TokensResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &staking_addr,
+            &QueryMsg::Tokens {
+                owner: ADDR2.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(tokens.tokens, vec!["1".to_string()]);
+
+    // ADDR1's remaining balance is unlocked.
+    unstake_tokens(
+        &mut app,
+        &staking_addr,
+        mock_info(ADDR1, &[]),
+        Uint128::new(40),
+    )
+    .unwrap();
+    assert_eq!(get_balance(&app, &cw20_addr, ADDR1), Uint128::new(40));
+
+    // Redeeming the position unlocks its balance.
+    let err: ContractError = unstake_tokens(
+        &mut app,
+        &staking_addr,
+        mock_info(ADDR2, &[]),
+        Uint128::new(60),
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, ContractError::PositionLocked {});
+    app.execute_contract(
+        Addr::unchecked(ADDR2),
+        staking_addr.clone(),
+        &ExecuteMsg::RedeemPosition {
+            token_id: "1".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    let count: cw721::NumTokensResponse = app
+        .wrap()
+        .query_wasm_smart(&staking_addr, &QueryMsg::NumTokens {})
+        .unwrap();
+    assert_eq!(count.count, 0);
+    unstake_tokens(
+        &mut app,
+        &staking_addr,
+        mock_info(ADDR2, &[]),
+        Uint128::new(60),
+    )
+    .unwrap();
+    assert_eq!(get_balance(&app, &cw20_addr, ADDR2), Uint128::new(60));
+}
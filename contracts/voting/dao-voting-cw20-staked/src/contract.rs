@@ -1,21 +1,28 @@
+use cosmwasm_schema::serde::{de::DeserializeOwned, Serialize};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, Addr, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult,
-    SubMsg, Uint128, Uint256, WasmMsg,
+    to_json_binary, Addr, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Reply, Response,
+    StdResult, Storage, SubMsg, Uint128, Uint256, WasmMsg,
 };
 use cw2::{get_contract_version, set_contract_version, ContractVersion};
 use cw20::{Cw20Coin, TokenInfoResponse};
+use cw_paginate_storage::{clamp_limit, order, range_bounds};
+use cw_storage_plus::SnapshotMap;
 use cw_utils::parse_reply_instantiate_data;
+use dao_hooks::stake::StakeChangedHookMsg;
 use dao_interface::voting::IsActiveResponse;
 use dao_voting::threshold::{ActiveThreshold, ActiveThresholdResponse};
 use std::convert::TryInto;
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, StakingInfo, TokenInfo};
+use crate::msg::{
+    ActiveThresholdInputsResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, PowerBreakdownResponse,
+    QueryMsg, StakingInfo, TokenInfo,
+};
 use crate::state::{
-    ACTIVE_THRESHOLD, DAO, STAKING_CONTRACT, STAKING_CONTRACT_CODE_ID,
-    STAKING_CONTRACT_UNSTAKING_DURATION, TOKEN,
+    Governor, ACTIVE_THRESHOLD, DAO, DELEGATED_POWER, DELEGATIONS, GOVERNORS, STAKING_CONTRACT,
+    STAKING_CONTRACT_CODE_ID, STAKING_CONTRACT_UNSTAKING_DURATION, TOKEN, TOTAL_SUPPLY,
 };
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-voting-cw20-staked";
@@ -193,9 +200,35 @@ pub fn execute(
         ExecuteMsg::UpdateActiveThreshold { new_threshold } => {
             execute_update_active_threshold(deps, env, info, new_threshold)
         }
+        ExecuteMsg::RegisterGovernor { manifesto_hash } => {
+            execute_register_governor(deps, env, info, manifesto_hash)
+        }
+        ExecuteMsg::Delegate { governor } => execute_delegate(deps, env, info, governor),
+        ExecuteMsg::Undelegate {} => execute_undelegate(deps, env, info),
+        ExecuteMsg::SlashGovernor { governor } => execute_slash_governor(deps, env, info, governor),
+        ExecuteMsg::StakeChangeHook(msg) => execute_stake_changed(deps, env, info, msg),
+        ExecuteMsg::SnapshotSupply {} => execute_snapshot_supply(deps, env),
+        ExecuteMsg::SupplyChangeHook {} => {
+            if info.sender != TOKEN.load(deps.storage)? {
+                return Err(ContractError::Unauthorized {});
+            }
+            execute_snapshot_supply(deps, env)
+        }
     }
 }
 
+pub fn execute_snapshot_supply(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let token_contract = TOKEN.load(deps.storage)?;
+    let token_info: TokenInfoResponse = deps
+        .querier
+        .query_wasm_smart(token_contract, &cw20_base::msg::QueryMsg::TokenInfo {})?;
+    TOTAL_SUPPLY.save(deps.storage, &token_info.total_supply, env.block.height)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "snapshot_supply")
+        .add_attribute("total_supply", token_info.total_supply))
+}
+
 pub fn execute_update_active_threshold(
     deps: DepsMut,
     _env: Env,
@@ -227,6 +260,243 @@ pub fn execute_update_active_threshold(
     Ok(Response::new().add_attribute("action", "update_active_threshold"))
 }
 
+pub fn execute_register_governor(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    manifesto_hash: String,
+) -> Result<Response, ContractError> {
+    if manifesto_hash.is_empty() {
+        return Err(ContractError::EmptyManifestoHash {});
+    }
+    if DELEGATIONS.has(deps.storage, &info.sender) {
+        return Err(ContractError::GovernorCannotDelegate {});
+    }
+    if let Some(governor) = GOVERNORS.may_load(deps.storage, &info.sender)? {
+        if governor.slashed {
+            return Err(ContractError::GovernorSlashed {
+                addr: info.sender.into_string(),
+            });
+        }
+    }
+
+    GOVERNORS.save(
+        deps.storage,
+        &info.sender,
+        &Governor {
+            addr: info.sender.clone(),
+            manifesto_hash: manifesto_hash.clone(),
+            slashed: false,
+        },
+        env.block.height,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "register_governor")
+        .add_attribute("governor", info.sender)
+        .add_attribute("manifesto_hash", manifesto_hash))
+}
+
+pub fn execute_delegate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    governor: String,
+) -> Result<Response, ContractError> {
+    let governor = deps.api.addr_validate(&governor)?;
+    match GOVERNORS.may_load(deps.storage, &governor)? {
+        None => {
+            return Err(ContractError::GovernorNotFound {
+                addr: governor.into_string(),
+            })
+        }
+        Some(Governor { slashed: true, .. }) => {
+            return Err(ContractError::GovernorSlashed {
+                addr: governor.into_string(),
+            })
+        }
+        Some(_) => (),
+    }
+    if GOVERNORS.has(deps.storage, &info.sender) {
+        return Err(ContractError::GovernorCannotDelegate {});
+    }
+    assert_stake_hook_registered(deps.as_ref(), &env)?;
+
+    let staked = query_staked_balance(deps.as_ref(), &info.sender)?;
+    if let Some(previous) = DELEGATIONS.may_load(deps.storage, &info.sender)? {
+        update_delegated_power(deps.storage, &previous, staked, false, env.block.height)?;
+    }
+    update_delegated_power(deps.storage, &governor, staked, true, env.block.height)?;
+    DELEGATIONS.save(deps.storage, &info.sender, &governor, env.block.height)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "delegate")
+        .add_attribute("delegator", info.sender)
+        .add_attribute("governor", governor))
+}
+
+pub fn execute_undelegate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let governor = DELEGATIONS
+        .may_load(deps.storage, &info.sender)?
+        .ok_or(ContractError::NotDelegating {})?;
+
+    let staked = query_staked_balance(deps.as_ref(), &info.sender)?;
+    update_delegated_power(deps.storage, &governor, staked, false, env.block.height)?;
+    DELEGATIONS.remove(deps.storage, &info.sender, env.block.height)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "undelegate")
+        .add_attribute("delegator", info.sender)
+        .add_attribute("governor", governor))
+}
+
+pub fn execute_slash_governor(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    governor: String,
+) -> Result<Response, ContractError> {
+    let dao = DAO.load(deps.storage)?;
+    if info.sender != dao {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let governor = deps.api.addr_validate(&governor)?;
+    let mut record = GOVERNORS
+        .may_load(deps.storage, &governor)?
+        .ok_or_else(|| ContractError::GovernorNotFound {
+            addr: governor.to_string(),
+        })?;
+    record.slashed = true;
+    GOVERNORS.save(deps.storage, &governor, &record, env.block.height)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "slash_governor")
+        .add_attribute("governor", governor))
+}
+
+pub fn execute_stake_changed(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: StakeChangedHookMsg,
+) -> Result<Response, ContractError> {
+    let staking_contract = STAKING_CONTRACT.load(deps.storage)?;
+    if info.sender != staking_contract {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let (addr, amount, increase) = match msg {
+        StakeChangedHookMsg::Stake { addr, amount } => (addr, amount, true),
+        StakeChangedHookMsg::Unstake { addr, amount } => (addr, amount, false),
+    };
+    if let Some(governor) = DELEGATIONS.may_load(deps.storage, &addr)? {
+        update_delegated_power(deps.storage, &governor, amount, increase, env.block.height)?;
+    }
+
+    Ok(Response::new().add_attribute("action", "stake_changed"))
+}
+
+/// Adds or removes `amount` from the power delegated to
+/// `governor`. Removals saturate at zero so that a stake change hook
+/// can never cause an unstake to fail.
+fn update_delegated_power(
+    storage: &mut dyn Storage,
+    governor: &Addr,
+    amount: Uint128,
+    increase: bool,
+    height: u64,
+) -> StdResult<Uint128> {
+    DELEGATED_POWER.update(storage, governor, height, |power| {
+        let power = power.unwrap_or_default();
+        if increase {
+            power.checked_add(amount).map_err(Into::into)
+        } else {
+            Ok(power.saturating_sub(amount))
+        }
+    })
+}
+
+/// Delegated power is only kept up to date by stake change hooks, so
+/// delegations are refused until this contract is registered as a
+/// hook on the staking contract.
+fn assert_stake_hook_registered(deps: Deps, env: &Env) -> Result<(), ContractError> {
+    let staking_contract = STAKING_CONTRACT.load(deps.storage)?;
+    let hooks: cw20_stake::msg::GetHooksResponse = deps
+        .querier
+        .query_wasm_smart(staking_contract, &cw20_stake::msg::QueryMsg::GetHooks {})?;
+    if hooks.hooks.contains(&env.contract.address.to_string()) {
+        Ok(())
+    } else {
+        Err(ContractError::StakeHookNotRegistered {})
+    }
+}
+
+fn query_staked_balance(deps: Deps, address: &Addr) -> StdResult<Uint128> {
+    let staking_contract = STAKING_CONTRACT.load(deps.storage)?;
+    let res: cw20_stake::msg::StakedBalanceAtHeightResponse = deps.querier.query_wasm_smart(
+        staking_contract,
+        &cw20_stake::msg::QueryMsg::StakedBalanceAtHeight {
+            address: address.to_string(),
+            height: None,
+        },
+    )?;
+    Ok(res.balance)
+}
+
+/// Loads a value from a snapshot map at `height`, or its current
+/// value if no height is provided. This matches how the staking
+/// contract answers balance queries.
+fn load_at_height<T>(
+    storage: &dyn Storage,
+    map: &SnapshotMap<&Addr, T>,
+    key: &Addr,
+    height: Option<u64>,
+) -> StdResult<Option<T>>
+where
+    T: Serialize + DeserializeOwned,
+{
+    match height {
+        Some(height) => map.may_load_at_height(storage, key, height),
+        None => map.may_load(storage, key),
+    }
+}
+
+/// Whether `address`'s staked balance counts towards a governor at
+/// `height`. Delegations to slashed governors do not count.
+fn is_delegated_at_height(
+    storage: &dyn Storage,
+    address: &Addr,
+    height: Option<u64>,
+) -> StdResult<bool> {
+    match load_at_height(storage, &DELEGATIONS, address, height)? {
+        Some(governor) => Ok(matches!(
+            load_at_height(storage, &GOVERNORS, &governor, height)?,
+            Some(Governor { slashed: false, .. })
+        )),
+        None => Ok(false),
+    }
+}
+
+/// Gets the power delegated to `governor` at `height`, which is zero
+/// if the governor was not registered or was slashed at that height.
+fn governor_power_at_height(
+    storage: &dyn Storage,
+    governor: &Addr,
+    height: Option<u64>,
+) -> StdResult<Uint128> {
+    match load_at_height(storage, &GOVERNORS, governor, height)? {
+        Some(Governor { slashed: false, .. }) => {
+            Ok(load_at_height(storage, &DELEGATED_POWER, governor, height)?.unwrap_or_default())
+        }
+        _ => Ok(Uint128::zero()),
+    }
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -240,6 +510,20 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::Dao {} => query_dao(deps),
         QueryMsg::IsActive {} => query_is_active(deps),
         QueryMsg::ActiveThreshold {} => query_active_threshold(deps),
+        QueryMsg::Governor { address } => query_governor(deps, address),
+        QueryMsg::ListGovernors {
+            start_after,
+            limit,
+            descending,
+        } => query_list_governors(deps, start_after, limit, descending),
+        QueryMsg::GovernorPower { address, height } => {
+            query_governor_power(deps, env, address, height)
+        }
+        QueryMsg::Delegation { address, height } => query_delegation(deps, address, height),
+        QueryMsg::PowerBreakdown { address } => query_power_breakdown(deps, env, address),
+        QueryMsg::ActiveThresholdInputs { height } => {
+            query_active_threshold_inputs(deps, env, height)
+        }
     }
 }
 
@@ -268,8 +552,18 @@ pub fn query_voting_power_at_height(
             height,
         },
     )?;
+
+    // Power delegated to a governor counts towards the governor's
+    // voting power instead of the delegator's, unless the governor
+    // has been slashed.
+    let mut power = res.balance;
+    if is_delegated_at_height(deps.storage, &address, height)? {
+        power = Uint128::zero();
+    }
+    power = power.checked_add(governor_power_at_height(deps.storage, &address, height)?)?;
+
     to_json_binary(&dao_interface::voting::VotingPowerAtHeightResponse {
-        power: res.balance,
+        power,
         height: res.height,
     })
 }
@@ -290,6 +584,92 @@ pub fn query_total_power_at_height(
     })
 }
 
+pub fn query_governor(deps: Deps, address: String) -> StdResult<Binary> {
+    let address = deps.api.addr_validate(&address)?;
+    to_json_binary(&GOVERNORS.may_load(deps.storage, &address)?)
+}
+
+pub fn query_list_governors(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+    descending: Option<bool>,
+) -> StdResult<Binary> {
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let order = order(descending);
+    let (min, max) = range_bounds(start_after.as_ref(), order);
+    let governors = GOVERNORS
+        .range(deps.storage, min, max, order)
+        .take(clamp_limit(limit))
+        .map(|item| item.map(|(_, governor)| governor))
+        .collect::<StdResult<Vec<Governor>>>()?;
+    to_json_binary(&governors)
+}
+
+pub fn query_governor_power(
+    deps: Deps,
+    env: Env,
+    address: String,
+    height: Option<u64>,
+) -> StdResult<Binary> {
+    let address = deps.api.addr_validate(&address)?;
+    to_json_binary(&dao_interface::voting::VotingPowerAtHeightResponse {
+        power: governor_power_at_height(deps.storage, &address, height)?,
+        height: height.unwrap_or(env.block.height),
+    })
+}
+
+pub fn query_delegation(deps: Deps, address: String, height: Option<u64>) -> StdResult<Binary> {
+    let address = deps.api.addr_validate(&address)?;
+    to_json_binary(&load_at_height(
+        deps.storage,
+        &DELEGATIONS,
+        &address,
+        height,
+    )?)
+}
+
+pub fn query_power_breakdown(deps: Deps, env: Env, address: String) -> StdResult<Binary> {
+    let address = deps.api.addr_validate(&address)?;
+    let staked = query_staked_balance(deps, &address)?;
+    let delegated = if is_delegated_at_height(deps.storage, &address, None)? {
+        staked
+    } else {
+        Uint128::zero()
+    };
+    let received = governor_power_at_height(deps.storage, &address, None)?;
+
+    let staking_contract = STAKING_CONTRACT.load(deps.storage)?;
+    let claims: cw20_stake::msg::ClaimsResponse = deps.querier.query_wasm_smart(
+        staking_contract,
+        &cw20_stake::msg::QueryMsg::Claims {
+            address: address.to_string(),
+        },
+    )?;
+    let (claimable, pending_unbond) = claims.claims.into_iter().try_fold(
+        (Uint128::zero(), Uint128::zero()),
+        |(claimable, pending), claim| -> StdResult<_> {
+            if claim.release_at.is_expired(&env.block) {
+                Ok((claimable.checked_add(claim.amount)?, pending))
+            } else {
+                Ok((claimable, pending.checked_add(claim.amount)?))
+            }
+        },
+    )?;
+
+    to_json_binary(&PowerBreakdownResponse {
+        active_power: (staked - delegated).checked_add(received)?,
+        staked,
+        delegated,
+        received,
+        pending_unbond,
+        claimable,
+        height: env.block.height,
+    })
+}
+
 pub fn query_info(deps: Deps) -> StdResult<Binary> {
     let info = cw2::get_contract_version(deps.storage)?;
     to_json_binary(&dao_interface::voting::InfoResponse { info })
@@ -315,45 +695,10 @@ pub fn query_is_active(deps: Deps) -> StdResult<Binary> {
                 active: actual_power.total >= count,
             }),
             ActiveThreshold::Percentage { percent } => {
-                // percent is bounded between [0, 100]. decimal
-                // represents percents in u128 terms as p *
-                // 10^15. this bounds percent between [0, 10^17].
-                //
-                // total_potential_power is bounded between [0, 2^128]
-                // as it tracks the balances of a cw20 token which has
-                // a max supply of 2^128.
-                //
-                // with our precision factor being 10^9:
-                //
-                // total_power <= 2^128 * 10^9 <= 2^256
-                //
-                // so we're good to put that in a u256.
-                //
-                // multiply_ratio promotes to a u512 under the hood,
-                // so it won't overflow, multiplying by a percent less
-                // than 100 is gonna make something the same size or
-                // smaller, applied + 10^9 <= 2^128 * 10^9 + 10^9 <=
-                // 2^256, so the top of the round won't overflow, and
-                // rounding is rounding down, so the whole thing can
-                // be safely unwrapped at the end of the day thank you
-                // for coming to my ted talk.
                 let total_potential_power: TokenInfoResponse = deps
                     .querier
                     .query_wasm_smart(token_contract, &cw20_base::msg::QueryMsg::TokenInfo {})?;
-                let total_power = total_potential_power
-                    .total_supply
-                    .full_mul(PRECISION_FACTOR);
-                // under the hood decimals are `atomics / 10^decimal_places`.
-                // cosmwasm doesn't give us a Decimal * Uint256
-                // implementation so we take the decimal apart and
-                // multiply by the fraction.
-                let applied = total_power.multiply_ratio(
-                    percent.atomics(),
-                    Uint256::from(10u64).pow(percent.decimal_places()),
-                );
-                let rounded = (applied + Uint256::from(PRECISION_FACTOR) - Uint256::from(1u128))
-                    / Uint256::from(PRECISION_FACTOR);
-                let count: Uint128 = rounded.try_into().unwrap();
+                let count = percentage_threshold_count(percent, total_potential_power.total_supply);
                 to_json_binary(&IsActiveResponse {
                     active: actual_power.total >= count,
                 })
@@ -364,6 +709,83 @@ pub fn query_is_active(deps: Deps) -> StdResult<Binary> {
     }
 }
 
+/// Gets the staked balance required to be active under a percentage
+/// active threshold of PERCENT, given the token's TOTAL_SUPPLY.
+fn percentage_threshold_count(percent: Decimal, total_supply: Uint128) -> Uint128 {
+    // percent is bounded between [0, 100]. decimal
+    // represents percents in u128 terms as p *
+    // 10^15. this bounds percent between [0, 10^17].
+    //
+    // total_supply is bounded between [0, 2^128] as it
+    // tracks the balances of a cw20 token which has a max
+    // supply of 2^128.
+    //
+    // with our precision factor being 10^9:
+    //
+    // total_power <= 2^128 * 10^9 <= 2^256
+    //
+    // so we're good to put that in a u256.
+    //
+    // multiply_ratio promotes to a u512 under the hood,
+    // so it won't overflow, multiplying by a percent less
+    // than 100 is gonna make something the same size or
+    // smaller, applied + 10^9 <= 2^128 * 10^9 + 10^9 <=
+    // 2^256, so the top of the round won't overflow, and
+    // rounding is rounding down, so the whole thing can
+    // be safely unwrapped at the end of the day thank you
+    // for coming to my ted talk.
+    let total_power = total_supply.full_mul(PRECISION_FACTOR);
+    // under the hood decimals are `atomics / 10^decimal_places`.
+    // cosmwasm doesn't give us a Decimal * Uint256
+    // implementation so we take the decimal apart and
+    // multiply by the fraction.
+    let applied = total_power.multiply_ratio(
+        percent.atomics(),
+        Uint256::from(10u64).pow(percent.decimal_places()),
+    );
+    let rounded = (applied + Uint256::from(PRECISION_FACTOR) - Uint256::from(1u128))
+        / Uint256::from(PRECISION_FACTOR);
+    rounded.try_into().unwrap()
+}
+
+pub fn query_active_threshold_inputs(
+    deps: Deps,
+    env: Env,
+    height: Option<u64>,
+) -> StdResult<Binary> {
+    let active_threshold = ACTIVE_THRESHOLD.may_load(deps.storage)?;
+    let staking_contract = STAKING_CONTRACT.load(deps.storage)?;
+    let total_staked: cw20_stake::msg::TotalStakedAtHeightResponse =
+        deps.querier.query_wasm_smart(
+            staking_contract,
+            &cw20_stake::msg::QueryMsg::TotalStakedAtHeight { height },
+        )?;
+    let total_supply = match height {
+        Some(height) => TOTAL_SUPPLY.may_load_at_height(deps.storage, height)?,
+        None => {
+            let token_info: TokenInfoResponse = deps.querier.query_wasm_smart(
+                TOKEN.load(deps.storage)?,
+                &cw20_base::msg::QueryMsg::TokenInfo {},
+            )?;
+            Some(token_info.total_supply)
+        }
+    };
+    let required = match active_threshold {
+        Some(ActiveThreshold::AbsoluteCount { count }) => Some(count),
+        Some(ActiveThreshold::Percentage { percent }) => {
+            total_supply.map(|total_supply| percentage_threshold_count(percent, total_supply))
+        }
+        None => None,
+    };
+    to_json_binary(&ActiveThresholdInputsResponse {
+        active_threshold,
+        total_staked: total_staked.total,
+        total_supply,
+        required,
+        height: height.unwrap_or(env.block.height),
+    })
+}
+
 pub fn query_active_threshold(deps: Deps) -> StdResult<Binary> {
     to_json_binary(&ActiveThresholdResponse {
         active_threshold: ACTIVE_THRESHOLD.may_load(deps.storage)?,
@@ -1,7 +1,7 @@
 use cosmwasm_std::StdError;
 use thiserror::Error;
 
-#[derive(Error, Debug)]
+#[derive(Error, Debug, PartialEq)]
 pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
@@ -38,4 +38,22 @@ pub enum ContractError {
 
     #[error("Absolute count threshold cannot be greater than the total token supply")]
     InvalidAbsoluteCount {},
+
+    #[error("Manifesto hash must not be empty")]
+    EmptyManifestoHash {},
+
+    #[error("No governor is registered at ({addr})")]
+    GovernorNotFound { addr: String },
+
+    #[error("Governor ({addr}) has been slashed")]
+    GovernorSlashed { addr: String },
+
+    #[error("Governors may not delegate their voting power")]
+    GovernorCannotDelegate {},
+
+    #[error("Sender has not delegated their voting power")]
+    NotDelegating {},
+
+    #[error("This contract must be registered as a stake change hook before delegating")]
+    StakeHookNotRegistered {},
 }
@@ -5,6 +5,7 @@ use cw20_base::msg::InstantiateMarketingInfo;
 use cw_utils::Duration;
 
 use dao_dao_macros::{active_query, token_query, voting_module_query};
+use dao_hooks::stake::StakeChangedHookMsg;
 use dao_voting::threshold::{ActiveThreshold, ActiveThresholdResponse};
 
 /// Information about the staking contract to be used with this voting
@@ -68,6 +69,31 @@ pub enum ExecuteMsg {
     UpdateActiveThreshold {
         new_threshold: Option<ActiveThreshold>,
     },
+    /// Registers the sender as a governor that stakers may delegate
+    /// their voting power to, or updates the manifesto hash of an
+    /// already registered governor.
+    RegisterGovernor { manifesto_hash: String },
+    /// Delegates the sender's staked voting power to a registered
+    /// governor, replacing any existing delegation.
+    Delegate { governor: String },
+    /// Removes the sender's delegation, returning its voting power
+    /// to it.
+    Undelegate {},
+    /// Slashes a governor for misconduct. Only the DAO may call this
+    /// method.
+    SlashGovernor { governor: String },
+    /// Called by the staking contract when an address's stake
+    /// changes so that delegated power can be kept up to date. The
+    /// DAO must add this contract as a hook on the staking contract
+    /// for delegation to work.
+    StakeChangeHook(StakeChangedHookMsg),
+    /// Records the token's current total supply so that percentage
+    /// active thresholds may be computed at this height. Anyone may
+    /// call this method after tokens are minted or burned.
+    SnapshotSupply {},
+    /// Called by the token contract when its supply changes. Has the
+    /// same effect as `SnapshotSupply`, for tokens able to send it.
+    SupplyChangeHook {},
 }
 
 #[voting_module_query]
@@ -82,6 +108,74 @@ pub enum QueryMsg {
     StakingContract {},
     #[returns(ActiveThresholdResponse)]
     ActiveThreshold {},
+    /// Gets a governor, if one is registered at `address`.
+    #[returns(Option<crate::state::Governor>)]
+    Governor { address: String },
+    /// Lists registered governors, including slashed ones.
+    #[returns(Vec<crate::state::Governor>)]
+    ListGovernors {
+        start_after: Option<String>,
+        limit: Option<u32>,
+        descending: Option<bool>,
+    },
+    /// Gets the voting power delegated to a governor at a height. A
+    /// slashed governor has no delegated power.
+    #[returns(dao_interface::voting::VotingPowerAtHeightResponse)]
+    GovernorPower {
+        address: String,
+        height: Option<u64>,
+    },
+    /// Gets the governor that `address` had delegated to at a
+    /// height, if any.
+    #[returns(Option<cosmwasm_std::Addr>)]
+    Delegation {
+        address: String,
+        height: Option<u64>,
+    },
+    /// Breaks down the current voting power of `address` and the
+    /// tokens it has unstaking.
+    #[returns(PowerBreakdownResponse)]
+    PowerBreakdown { address: String },
+    /// Gets the inputs used to decide whether the DAO is active at a
+    /// height.
+    #[returns(ActiveThresholdInputsResponse)]
+    ActiveThresholdInputs { height: Option<u64> },
+}
+
+#[cw_serde]
+pub struct ActiveThresholdInputsResponse {
+    /// The current active threshold. Changes to the threshold are
+    /// not recorded, so this is used at every height.
+    pub active_threshold: Option<ActiveThreshold>,
+    /// The total staked balance at `height`.
+    pub total_staked: Uint128,
+    /// The token's total supply at `height`. For past heights, this
+    /// is the last supply recorded at or before `height`, or `None`
+    /// if none was recorded.
+    pub total_supply: Option<Uint128>,
+    /// The staked balance required to be active, if it is known.
+    pub required: Option<Uint128>,
+    pub height: u64,
+}
+
+#[cw_serde]
+pub struct PowerBreakdownResponse {
+    /// The address's voting power at `height`.
+    pub active_power: Uint128,
+    /// The address's staked balance.
+    pub staked: Uint128,
+    /// Staked balance counted towards a governor the address has
+    /// delegated to instead of towards the address.
+    pub delegated: Uint128,
+    /// Power delegated to the address if it is a governor.
+    pub received: Uint128,
+    /// Unstaked tokens that have not finished unbonding. These have
+    /// no voting power.
+    pub pending_unbond: Uint128,
+    /// Unstaked tokens that have finished unbonding and may be
+    /// claimed from the staking contract.
+    pub claimable: Uint128,
+    pub height: u64,
 }
 
 #[cw_serde]
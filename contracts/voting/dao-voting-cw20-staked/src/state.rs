@@ -1,5 +1,6 @@
-use cosmwasm_std::Addr;
-use cw_storage_plus::Item;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Item, SnapshotItem, SnapshotMap, Strategy};
 use cw_utils::Duration;
 use dao_voting::threshold::ActiveThreshold;
 
@@ -10,3 +11,47 @@ pub const STAKING_CONTRACT: Item<Addr> = Item::new("staking_contract");
 pub const STAKING_CONTRACT_UNSTAKING_DURATION: Item<Option<Duration>> =
     Item::new("staking_contract_unstaking_duration");
 pub const STAKING_CONTRACT_CODE_ID: Item<u64> = Item::new("staking_contract_code_id");
+
+/// The token's total supply, recorded when it changes. Used to
+/// compute percentage active thresholds at past heights.
+pub const TOTAL_SUPPLY: SnapshotItem<Uint128> = SnapshotItem::new(
+    "total_supply",
+    "total_supply__checkpoints",
+    "total_supply__changelog",
+    Strategy::EveryBlock,
+);
+
+/// A registered governor that stakers may delegate their voting power
+/// to.
+#[cw_serde]
+pub struct Governor {
+    pub addr: Addr,
+    /// Hash of the governor's published manifesto.
+    pub manifesto_hash: String,
+    /// Set when the DAO slashes the governor. A slashed governor may
+    /// not receive new delegations and its delegated power is
+    /// returned to its delegators.
+    pub slashed: bool,
+}
+
+/// Registered governors.
+pub const GOVERNORS: SnapshotMap<&Addr, Governor> = SnapshotMap::new(
+    "governors",
+    "governors__checkpoints",
+    "governors__changelog",
+    Strategy::EveryBlock,
+);
+/// Maps a delegator to the governor it has delegated to.
+pub const DELEGATIONS: SnapshotMap<&Addr, Addr> = SnapshotMap::new(
+    "delegations",
+    "delegations__checkpoints",
+    "delegations__changelog",
+    Strategy::EveryBlock,
+);
+/// The staked balance delegated to each governor.
+pub const DELEGATED_POWER: SnapshotMap<&Addr, Uint128> = SnapshotMap::new(
+    "delegated_power",
+    "delegated_power__checkpoints",
+    "delegated_power__changelog",
+    Strategy::EveryBlock,
+);
@@ -5,12 +5,18 @@ use cosmwasm_std::{
 use cw2::ContractVersion;
 use cw20::{BalanceResponse, Cw20Coin, MinterResponse, TokenInfoResponse};
 use cw_multi_test::{next_block, App, Contract, ContractWrapper, Executor};
+use cw_utils::Duration;
 use dao_interface::voting::{InfoResponse, IsActiveResponse, VotingPowerAtHeightResponse};
 use dao_voting::threshold::{ActiveThreshold, ActiveThresholdResponse};
 
 use crate::{
     contract::{migrate, CONTRACT_NAME, CONTRACT_VERSION},
-    msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, StakingInfo},
+    msg::{
+        ActiveThresholdInputsResponse, ExecuteMsg, InstantiateMsg, MigrateMsg,
+        PowerBreakdownResponse, QueryMsg, StakingInfo,
+    },
+    state::Governor,
+    ContractError,
 };
 
 const DAO_ADDR: &str = "dao";
@@ -923,6 +929,461 @@ fn test_different_heights() {
     );
 }
 
+#[test]
+fn test_governor_delegation() {
+    let mut app = App::default();
+    let cw20_id = app.store_code(cw20_contract());
+    let voting_id = app.store_code(staked_balance_voting_contract());
+    let staking_id = app.store_code(staking_contract());
+
+    let voting_addr = instantiate_voting(
+        &mut app,
+        voting_id,
+        InstantiateMsg {
+            token_info: crate::msg::TokenInfo::New {
+                code_id: cw20_id,
+                label: "DAO DAO voting".to_string(),
+                name: "DAO DAO".to_string(),
+                symbol: "DAO".to_string(),
+                decimals: 6,
+                initial_balances: vec![
+                    Cw20Coin {
+                        address: "ekez".to_string(),
+                        amount: Uint128::new(10),
+                    },
+                    Cw20Coin {
+                        address: "governor".to_string(),
+                        amount: Uint128::new(5),
+                    },
+                ],
+                marketing: None,
+                staking_code_id: staking_id,
+                unstaking_duration: None,
+                initial_dao_balance: None,
+            },
+            active_threshold: None,
+        },
+    );
+    let token_addr: Addr = app
+        .wrap()
+        .query_wasm_smart(voting_addr.clone(), &QueryMsg::TokenContract {})
+        .unwrap();
+    let staking_addr: Addr = app
+        .wrap()
+        .query_wasm_smart(voting_addr.clone(), &QueryMsg::StakingContract {})
+        .unwrap();
+
+    stake_tokens(
+        &mut app,
+        staking_addr.clone(),
+        token_addr.clone(),
+        "ekez",
+        6,
+    );
+    stake_tokens(
+        &mut app,
+        staking_addr.clone(),
+        token_addr.clone(),
+        "governor",
+        5,
+    );
+
+    // Can't delegate to an unregistered governor.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            voting_addr.clone(),
+            &ExecuteMsg::Delegate {
+                governor: "governor".to_string(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::GovernorNotFound {
+            addr: "governor".to_string()
+        }
+    );
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("governor"),
+            voting_addr.clone(),
+            &ExecuteMsg::RegisterGovernor {
+                manifesto_hash: "".to_string(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::EmptyManifestoHash {});
+
+    app.execute_contract(
+        Addr::unchecked("governor"),
+        voting_addr.clone(),
+        &ExecuteMsg::RegisterGovernor {
+            manifesto_hash: "abc123".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Delegations are refused until the voting module is a stake
+    // change hook, as delegated power would otherwise go stale.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            voting_addr.clone(),
+            &ExecuteMsg::Delegate {
+                governor: "governor".to_string(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::StakeHookNotRegistered {});
+
+    // The DAO registers the voting module as a stake change hook.
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        staking_addr.clone(),
+        &cw20_stake::msg::ExecuteMsg::AddHook {
+            addr: voting_addr.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        voting_addr.clone(),
+        &ExecuteMsg::Delegate {
+            governor: "governor".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+    let delegated_height = app.block_info().height;
+
+    let power = |app: &App, address: &str, height: Option<u64>| -> Uint128 {
+        let res: VotingPowerAtHeightResponse = app
+            .wrap()
+            .query_wasm_smart(
+                voting_addr.clone(),
+                &QueryMsg::VotingPowerAtHeight {
+                    address: address.to_string(),
+                    height,
+                },
+            )
+            .unwrap();
+        res.power
+    };
+    assert_eq!(power(&app, "ekez", None), Uint128::zero());
+    assert_eq!(power(&app, "governor", None), Uint128::new(11));
+
+    let governor_power: VotingPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            voting_addr.clone(),
+            &QueryMsg::GovernorPower {
+                address: "governor".to_string(),
+                height: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(governor_power.power, Uint128::new(6));
+
+    // Stake changes of a delegator flow through to its governor.
+    stake_tokens(&mut app, staking_addr.clone(), token_addr, "ekez", 4);
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        staking_addr,
+        &cw20_stake::msg::ExecuteMsg::Unstake {
+            amount: Uint128::new(2),
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+    assert_eq!(power(&app, "governor", None), Uint128::new(13));
+
+    // Governors may not delegate.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("governor"),
+            voting_addr.clone(),
+            &ExecuteMsg::Delegate {
+                governor: "governor".to_string(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::GovernorCannotDelegate {});
+
+    // Only the DAO may slash.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            voting_addr.clone(),
+            &ExecuteMsg::SlashGovernor {
+                governor: "governor".to_string(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        voting_addr.clone(),
+        &ExecuteMsg::SlashGovernor {
+            governor: "governor".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+
+    // Slashing returns delegated power to delegators, but historical
+    // power is unchanged.
+    assert_eq!(power(&app, "ekez", None), Uint128::new(8));
+    assert_eq!(power(&app, "governor", None), Uint128::new(5));
+    assert_eq!(
+        power(&app, "governor", Some(delegated_height)),
+        Uint128::new(11)
+    );
+    assert_eq!(power(&app, "ekez", Some(delegated_height)), Uint128::zero());
+
+    let governor: Option<Governor> = app
+        .wrap()
+        .query_wasm_smart(
+            voting_addr.clone(),
+            &QueryMsg::Governor {
+                address: "governor".to_string(),
+            },
+        )
+        .unwrap();
+    assert!(governor.unwrap().slashed);
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            voting_addr.clone(),
+            &ExecuteMsg::Delegate {
+                governor: "governor".to_string(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::GovernorSlashed {
+            addr: "governor".to_string()
+        }
+    );
+
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        voting_addr.clone(),
+        &ExecuteMsg::Undelegate {},
+        &[],
+    )
+    .unwrap();
+    let delegation: Option<Addr> = app
+        .wrap()
+        .query_wasm_smart(
+            voting_addr.clone(),
+            &QueryMsg::Delegation {
+                address: "ekez".to_string(),
+                height: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(delegation, None);
+
+    let governors: Vec<Governor> = app
+        .wrap()
+        .query_wasm_smart(
+            voting_addr,
+            &QueryMsg::ListGovernors {
+                start_after: None,
+                limit: None,
+                descending: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(governors.len(), 1);
+}
+
+#[test]
+fn test_power_breakdown() {
+    let mut app = App::default();
+    let cw20_id = app.store_code(cw20_contract());
+    let voting_id = app.store_code(staked_balance_voting_contract());
+    let staking_id = app.store_code(staking_contract());
+
+    let voting_addr = instantiate_voting(
+        &mut app,
+        voting_id,
+        InstantiateMsg {
+            token_info: crate::msg::TokenInfo::New {
+                code_id: cw20_id,
+                label: "DAO DAO voting".to_string(),
+                name: "DAO DAO".to_string(),
+                symbol: "DAO".to_string(),
+                decimals: 6,
+                initial_balances: vec![
+                    Cw20Coin {
+                        address: "ekez".to_string(),
+                        amount: Uint128::new(10),
+                    },
+                    Cw20Coin {
+                        address: "governor".to_string(),
+                        amount: Uint128::new(5),
+                    },
+                ],
+                marketing: None,
+                staking_code_id: staking_id,
+                unstaking_duration: Some(Duration::Height(5)),
+                initial_dao_balance: None,
+            },
+            active_threshold: None,
+        },
+    );
+    let token_addr: Addr = app
+        .wrap()
+        .query_wasm_smart(voting_addr.clone(), &QueryMsg::TokenContract {})
+        .unwrap();
+    let staking_addr: Addr = app
+        .wrap()
+        .query_wasm_smart(voting_addr.clone(), &QueryMsg::StakingContract {})
+        .unwrap();
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        staking_addr.clone(),
+        &cw20_stake::msg::ExecuteMsg::AddHook {
+            addr: voting_addr.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    stake_tokens(
+        &mut app,
+        staking_addr.clone(),
+        token_addr.clone(),
+        "ekez",
+        10,
+    );
+    stake_tokens(&mut app, staking_addr.clone(), token_addr, "governor", 5);
+    app.update_block(next_block);
+
+    let breakdown = |app: &App, address: &str| -> PowerBreakdownResponse {
+        app.wrap()
+            .query_wasm_smart(
+                voting_addr.clone(),
+                &QueryMsg::PowerBreakdown {
+                    address: address.to_string(),
+                },
+            )
+            .unwrap()
+    };
+    assert_eq!(
+        breakdown(&app, "ekez"),
+        PowerBreakdownResponse {
+            active_power: Uint128::new(10),
+            staked: Uint128::new(10),
+            delegated: Uint128::zero(),
+            received: Uint128::zero(),
+            pending_unbond: Uint128::zero(),
+            claimable: Uint128::zero(),
+            height: app.block_info().height,
+        }
+    );
+
+    app.execute_contract(
+        Addr::unchecked("governor"),
+        voting_addr.clone(),
+        &ExecuteMsg::RegisterGovernor {
+            manifesto_hash: "abc123".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        voting_addr.clone(),
+        &ExecuteMsg::Delegate {
+            governor: "governor".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        staking_addr.clone(),
+        &cw20_stake::msg::ExecuteMsg::Unstake {
+            amount: Uint128::new(4),
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+
+    // Unbonding tokens and delegated stake don't count towards the
+    // delegator's power.
+    assert_eq!(
+        breakdown(&app, "ekez"),
+        PowerBreakdownResponse {
+            active_power: Uint128::zero(),
+            staked: Uint128::new(6),
+            delegated: Uint128::new(6),
+            received: Uint128::zero(),
+            pending_unbond: Uint128::new(4),
+            claimable: Uint128::zero(),
+            height: app.block_info().height,
+        }
+    );
+    assert_eq!(
+        breakdown(&app, "governor"),
+        PowerBreakdownResponse {
+            active_power: Uint128::new(11),
+            staked: Uint128::new(5),
+            delegated: Uint128::zero(),
+            received: Uint128::new(6),
+            pending_unbond: Uint128::zero(),
+            claimable: Uint128::zero(),
+            height: app.block_info().height,
+        }
+    );
+
+    app.update_block(|block| block.height += 5);
+    let res = breakdown(&app, "ekez");
+    assert_eq!(res.pending_unbond, Uint128::zero());
+    assert_eq!(res.claimable, Uint128::new(4));
+
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        staking_addr,
+        &cw20_stake::msg::ExecuteMsg::Claim {},
+        &[],
+    )
+    .unwrap();
+    assert_eq!(breakdown(&app, "ekez").claimable, Uint128::zero());
+}
+
 #[test]
 fn test_active_threshold_absolute_count() {
     let mut app = App::default();
@@ -1043,6 +1504,124 @@ fn test_active_threshold_percent() {
     assert!(is_active.active);
 }
 
+#[test]
+fn test_active_threshold_inputs() {
+    let mut app = App::default();
+    let cw20_id = app.store_code(cw20_contract());
+    let voting_id = app.store_code(staked_balance_voting_contract());
+    let staking_contract_id = app.store_code(staking_contract());
+
+    let active_threshold = ActiveThreshold::Percentage {
+        percent: Decimal::percent(20),
+    };
+    let voting_addr = instantiate_voting(
+        &mut app,
+        voting_id,
+        InstantiateMsg {
+            token_info: crate::msg::TokenInfo::New {
+                code_id: cw20_id,
+                label: "DAO DAO voting".to_string(),
+                name: "DAO DAO".to_string(),
+                symbol: "DAO".to_string(),
+                decimals: 6,
+                initial_balances: vec![Cw20Coin {
+                    address: CREATOR_ADDR.to_string(),
+                    amount: Uint128::from(200u64),
+                }],
+                marketing: None,
+                unstaking_duration: None,
+                staking_code_id: staking_contract_id,
+                initial_dao_balance: Some(Uint128::from(100u64)),
+            },
+            active_threshold: Some(active_threshold.clone()),
+        },
+    );
+    let token_addr: Addr = app
+        .wrap()
+        .query_wasm_smart(voting_addr.clone(), &QueryMsg::TokenContract {})
+        .unwrap();
+    let inputs = |app: &App, height: Option<u64>| -> ActiveThresholdInputsResponse {
+        app.wrap()
+            .query_wasm_smart(
+                voting_addr.clone(),
+                &QueryMsg::ActiveThresholdInputs { height },
+            )
+            .unwrap()
+    };
+
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        voting_addr.clone(),
+        &ExecuteMsg::SnapshotSupply {},
+        &[],
+    )
+    .unwrap();
+    let before_burn = app.block_info().height;
+    app.update_block(next_block);
+
+    // Burning tokens lowers the required stake.
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        token_addr,
+        &cw20::Cw20ExecuteMsg::Burn {
+            amount: Uint128::new(100),
+        },
+        &[],
+    )
+    .unwrap();
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            voting_addr.clone(),
+            &ExecuteMsg::SupplyChangeHook {},
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        voting_addr.clone(),
+        &ExecuteMsg::SnapshotSupply {},
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+
+    assert_eq!(
+        inputs(&app, None),
+        ActiveThresholdInputsResponse {
+            active_threshold: Some(active_threshold.clone()),
+            total_staked: Uint128::zero(),
+            total_supply: Some(Uint128::new(200)),
+            required: Some(Uint128::new(40)),
+            height: app.block_info().height,
+        }
+    );
+    assert_eq!(
+        inputs(&app, Some(before_burn + 1)),
+        ActiveThresholdInputsResponse {
+            active_threshold: Some(active_threshold.clone()),
+            total_staked: Uint128::zero(),
+            total_supply: Some(Uint128::new(300)),
+            required: Some(Uint128::new(60)),
+            height: before_burn + 1,
+        }
+    );
+    // No supply was recorded before the first snapshot.
+    assert_eq!(
+        inputs(&app, Some(before_burn)),
+        ActiveThresholdInputsResponse {
+            active_threshold: Some(active_threshold),
+            total_staked: Uint128::zero(),
+            total_supply: None,
+            required: None,
+            height: before_burn,
+        }
+    );
+}
+
 #[test]
 fn test_active_threshold_percent_rounds_up() {
     let mut app = App::default();
@@ -1,22 +1,36 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult, SubMsg,
-    Uint128, WasmMsg,
+    to_json_binary, Addr, BankMsg, Binary, Deps, DepsMut, Env, HexBinary, MessageInfo, Order,
+    Reply, Response, StdResult, SubMsg, Uint128, WasmMsg,
 };
 use cw2::{get_contract_version, set_contract_version, ContractVersion};
 use cw4::{MemberListResponse, MemberResponse, TotalWeightResponse};
-use cw_utils::parse_reply_instantiate_data;
+use cw_storage_plus::Bound;
+use cw_utils::{must_pay, parse_reply_instantiate_data, Expiration};
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, GroupContract, InstantiateMsg, MigrateMsg, QueryMsg};
-use crate::state::{DAO, GROUP_CONTRACT};
+use crate::merkle;
+use crate::msg::{
+    ExecuteMsg, ExpiringMember, GroupContract, InstantiateMsg, MemberExpiration,
+    MemberProofResponse, MigrateMsg, QueryMsg,
+};
+use crate::state::{
+    MemberRoot, RenewalFee, DAO, EXPIRATIONS, GROUP_CONTRACT, MEMBER_ROOTS, MEMBER_SNAPSHOTS,
+    RENEWAL_FEE,
+};
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-voting-cw4";
 pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 const INSTANTIATE_GROUP_REPLY_ID: u64 = 0;
 
+const DEFAULT_LIMIT: u32 = 30;
+const MAX_LIMIT: u32 = 100;
+
+/// The largest page of members the cw4-group contract returns.
+const MEMBER_PAGE_LIMIT: u32 = 30;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -108,12 +122,158 @@ pub fn instantiate(
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
-    _deps: DepsMut,
-    _env: Env,
-    _info: MessageInfo,
-    _msg: ExecuteMsg,
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::UpdateExpirations { expirations } => {
+            execute_update_expirations(deps, env, info, expirations)
+        }
+        ExecuteMsg::UpdateRenewalFee { renewal_fee } => {
+            execute_update_renewal_fee(deps, info, renewal_fee)
+        }
+        ExecuteMsg::Renew {} => execute_renew(deps, env, info),
+        ExecuteMsg::SnapshotMembers {} => execute_snapshot_members(deps, env, info),
+    }
+}
+
+pub fn execute_update_expirations(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    expirations: Vec<MemberExpiration>,
+) -> Result<Response, ContractError> {
+    if info.sender != DAO.load(deps.storage)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for MemberExpiration { addr, expiration } in expirations.iter() {
+        if let Expiration::AtTime(_) = expiration {
+            return Err(ContractError::InvalidExpiration {});
+        }
+        let addr = deps.api.addr_validate(addr)?;
+        EXPIRATIONS.save(deps.storage, &addr, expiration, env.block.height)?;
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "update_expirations")
+        .add_attribute("updated", expirations.len().to_string()))
+}
+
+pub fn execute_update_renewal_fee(
+    deps: DepsMut,
+    info: MessageInfo,
+    renewal_fee: Option<RenewalFee>,
+) -> Result<Response, ContractError> {
+    if info.sender != DAO.load(deps.storage)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match renewal_fee {
+        Some(renewal_fee) => {
+            if renewal_fee.fee.amount.is_zero() || renewal_fee.period == 0 {
+                return Err(ContractError::ZeroRenewalFee {});
+            }
+            RENEWAL_FEE.save(deps.storage, &renewal_fee)?;
+        }
+        None => RENEWAL_FEE.remove(deps.storage),
+    }
+
+    Ok(Response::default().add_attribute("action", "update_renewal_fee"))
+}
+
+pub fn execute_renew(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let renewal_fee = RENEWAL_FEE
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoRenewalFee {})?;
+    let paid = must_pay(&info, &renewal_fee.fee.denom)?;
+    if paid != renewal_fee.fee.amount {
+        return Err(ContractError::InvalidRenewalPayment {
+            expected: renewal_fee.fee.to_string(),
+        });
+    }
+
+    let current = match EXPIRATIONS.may_load(deps.storage, &info.sender)? {
+        Some(Expiration::AtHeight(height)) => height,
+        _ => return Err(ContractError::NotExpiring {}),
+    };
+    let expiration = Expiration::AtHeight(current.max(env.block.height) + renewal_fee.period);
+    EXPIRATIONS.save(deps.storage, &info.sender, &expiration, env.block.height)?;
+
+    let dao = DAO.load(deps.storage)?;
+    Ok(Response::default()
+        .add_message(BankMsg::Send {
+            to_address: dao.into_string(),
+            amount: vec![renewal_fee.fee],
+        })
+        .add_attribute("action", "renew")
+        .add_attribute("member", info.sender)
+        .add_attribute("expiration", expiration.to_string()))
+}
+
+pub fn execute_snapshot_members(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
 ) -> Result<Response, ContractError> {
-    Err(ContractError::NoExecute {})
+    if info.sender != DAO.load(deps.storage)? {
+        return Err(ContractError::Unauthorized {});
+    }
+    let height = env.block.height;
+    if MEMBER_ROOTS.has(deps.storage, height) {
+        return Err(ContractError::MemberRootExists {});
+    }
+
+    // The group contract only lists its current members, so the
+    // snapshot must be taken at the current height. Members are
+    // listed in ascending order of address.
+    let group_contract = GROUP_CONTRACT.load(deps.storage)?;
+    let mut members: Vec<(Addr, u64)> = vec![];
+    let mut start_after = None;
+    loop {
+        let page: MemberListResponse = deps.querier.query_wasm_smart(
+            &group_contract,
+            &cw4_group::msg::QueryMsg::ListMembers {
+                start_after: start_after.take(),
+                limit: Some(MEMBER_PAGE_LIMIT),
+            },
+        )?;
+        let last = page.members.last().map(|member| member.addr.clone());
+        for member in page.members {
+            let addr = Addr::unchecked(member.addr);
+            if member.weight > 0 && !is_expired(deps.as_ref(), &env, &addr, None)? {
+                members.push((addr, member.weight));
+            }
+        }
+        match last {
+            Some(last) => start_after = Some(last),
+            None => break,
+        }
+    }
+
+    let leaves = members
+        .iter()
+        .map(|(addr, power)| merkle::leaf_hash(addr, *power))
+        .collect();
+    let root = merkle::root(leaves).ok_or(ContractError::NoMembers {})?;
+    let member_root = MemberRoot {
+        root: HexBinary::from(root.as_slice()),
+        total_power: members.iter().map(|(_, power)| Uint128::from(*power)).sum(),
+        members: members.len() as u64,
+    };
+    MEMBER_ROOTS.save(deps.storage, height, &member_root)?;
+    MEMBER_SNAPSHOTS.save(deps.storage, height, &members)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "snapshot_members")
+        .add_attribute("height", height.to_string())
+        .add_attribute("root", member_root.root.to_hex()))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -126,27 +286,55 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::Info {} => query_info(deps),
         QueryMsg::GroupContract {} => to_json_binary(&GROUP_CONTRACT.load(deps.storage)?),
         QueryMsg::Dao {} => to_json_binary(&DAO.load(deps.storage)?),
+        QueryMsg::MemberExpiration { address } => query_member_expiration(deps, address),
+        QueryMsg::ExpiringMembers {
+            before_height,
+            start_after,
+            limit,
+        } => query_expiring_members(deps, before_height, start_after, limit),
+        QueryMsg::RenewalFee {} => to_json_binary(&RENEWAL_FEE.may_load(deps.storage)?),
+        QueryMsg::MemberRoot { height } => {
+            to_json_binary(&MEMBER_ROOTS.may_load(deps.storage, height)?)
+        }
+        QueryMsg::MemberProof { height, address } => query_member_proof(deps, height, address),
     }
 }
 
+/// Returns true if ADDR's membership had expired as of HEIGHT, or as
+/// of the current block if HEIGHT is `None`.
+fn is_expired(deps: Deps, env: &Env, addr: &Addr, height: Option<u64>) -> StdResult<bool> {
+    let expiration = match height {
+        Some(height) => EXPIRATIONS.may_load_at_height(deps.storage, addr, height)?,
+        None => EXPIRATIONS.may_load(deps.storage, addr)?,
+    };
+    let height = height.unwrap_or(env.block.height);
+    Ok(matches!(expiration, Some(Expiration::AtHeight(expires)) if expires <= height))
+}
+
 pub fn query_voting_power_at_height(
     deps: Deps,
     env: Env,
     address: String,
     height: Option<u64>,
 ) -> StdResult<Binary> {
-    let addr = deps.api.addr_validate(&address)?.to_string();
+    let addr = deps.api.addr_validate(&address)?;
     let group_contract = GROUP_CONTRACT.load(deps.storage)?;
     let res: MemberResponse = deps.querier.query_wasm_smart(
         group_contract,
         &cw4_group::msg::QueryMsg::Member {
-            addr,
+            addr: addr.to_string(),
             at_height: height,
         },
     )?;
 
+    let power = if is_expired(deps, &env, &addr, height)? {
+        Uint128::zero()
+    } else {
+        res.weight.unwrap_or(0).into()
+    };
+
     to_json_binary(&dao_interface::voting::VotingPowerAtHeightResponse {
-        power: res.weight.unwrap_or(0).into(),
+        power,
         height: height.unwrap_or(env.block.height),
     })
 }
@@ -154,15 +342,102 @@ pub fn query_voting_power_at_height(
 pub fn query_total_power_at_height(deps: Deps, env: Env, height: Option<u64>) -> StdResult<Binary> {
     let group_contract = GROUP_CONTRACT.load(deps.storage)?;
     let res: TotalWeightResponse = deps.querier.query_wasm_smart(
-        group_contract,
+        group_contract.clone(),
         &cw4_group::msg::QueryMsg::TotalWeight { at_height: height },
     )?;
+
+    // Remove the weight of expired members. This is linear in the
+    // number of members that have ever had an expiration set.
+    let mut power = Uint128::from(res.weight);
+    let addrs = EXPIRATIONS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<Addr>>>()?;
+    for addr in addrs {
+        if is_expired(deps, &env, &addr, height)? {
+            let member: MemberResponse = deps.querier.query_wasm_smart(
+                group_contract.clone(),
+                &cw4_group::msg::QueryMsg::Member {
+                    addr: addr.into_string(),
+                    at_height: height,
+                },
+            )?;
+            power = power.saturating_sub(member.weight.unwrap_or(0).into());
+        }
+    }
+
     to_json_binary(&dao_interface::voting::TotalPowerAtHeightResponse {
-        power: res.weight.into(),
+        power,
         height: height.unwrap_or(env.block.height),
     })
 }
 
+pub fn query_member_expiration(deps: Deps, address: String) -> StdResult<Binary> {
+    let addr = deps.api.addr_validate(&address)?;
+    let expiration = EXPIRATIONS
+        .may_load(deps.storage, &addr)?
+        .unwrap_or(Expiration::Never {});
+    to_json_binary(&expiration)
+}
+
+pub fn query_expiring_members(
+    deps: Deps,
+    before_height: u64,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT);
+
+    let members = EXPIRATIONS
+        .range(
+            deps.storage,
+            start_after.as_ref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .filter_map(|item| match item {
+            Ok((addr, Expiration::AtHeight(height))) if height <= before_height => {
+                Some(Ok(ExpiringMember {
+                    addr,
+                    expiration: Expiration::AtHeight(height),
+                }))
+            }
+            Ok(_) => None,
+            Err(e) => Some(Err(e)),
+        })
+        .take(limit as usize)
+        .collect::<StdResult<Vec<ExpiringMember>>>()?;
+
+    to_json_binary(&members)
+}
+
+pub fn query_member_proof(deps: Deps, height: u64, address: String) -> StdResult<Binary> {
+    let addr = deps.api.addr_validate(&address)?;
+    let member_root = MEMBER_ROOTS.load(deps.storage, height)?;
+    let members = MEMBER_SNAPSHOTS.load(deps.storage, height)?;
+
+    let response = members
+        .iter()
+        .position(|(member, _)| *member == addr)
+        .map(|index| {
+            let power = members[index].1;
+            let leaves = members
+                .iter()
+                .map(|(addr, power)| merkle::leaf_hash(addr, *power))
+                .collect();
+            MemberProofResponse {
+                power,
+                leaf: HexBinary::from(merkle::leaf_hash(&addr, power).as_slice()),
+                proof: merkle::proof(leaves, index),
+                root: member_root.root,
+            }
+        });
+
+    to_json_binary(&response)
+}
+
 pub fn query_info(deps: Deps) -> StdResult<Binary> {
     let info = cw2::get_contract_version(deps.storage)?;
     to_json_binary(&dao_interface::voting::InfoResponse { info })
@@ -1,4 +1,5 @@
 use cosmwasm_std::StdError;
+use cw_utils::PaymentError;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -6,6 +7,9 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error(transparent)]
+    Payment(#[from] PaymentError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
@@ -18,15 +22,30 @@ pub enum ContractError {
     #[error("Error occured whilst instantiating group contract")]
     GroupContractInstantiateError {},
 
-    #[error("Contract only supports queries")]
-    NoExecute {},
+    #[error("Member expirations must be at a height or never")]
+    InvalidExpiration {},
+
+    #[error("Renewal requires a payment of {expected}")]
+    InvalidRenewalPayment { expected: String },
+
+    #[error("A member root was already computed at this height")]
+    MemberRootExists {},
 
     #[error("Cannot instantiate or use a group contract with no initial members")]
     NoMembers {},
 
+    #[error("Membership does not expire, so it may not be renewed")]
+    NotExpiring {},
+
+    #[error("Renewal by fee is not enabled")]
+    NoRenewalFee {},
+
     #[error("Got a submessage reply with unknown id: {id}")]
     UnknownReplyId { id: u64 },
 
     #[error("Total weight of the CW4 contract cannot be zero")]
     ZeroTotalWeight {},
+
+    #[error("Renewal fee and period must be non-zero")]
+    ZeroRenewalFee {},
 }
@@ -2,6 +2,7 @@
 
 pub mod contract;
 mod error;
+pub mod merkle;
 pub mod msg;
 pub mod state;
 
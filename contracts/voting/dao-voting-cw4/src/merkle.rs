@@ -0,0 +1,71 @@
+use cosmwasm_std::{Addr, HexBinary};
+use sha2::{Digest, Sha256};
+
+/// Hashes a member's address and voting power into a leaf of the
+/// member tree: `sha256(address || power)`, with the power encoded
+/// as eight big-endian bytes.
+pub fn leaf_hash(addr: &Addr, power: u64) -> [u8; 32] {
+    Sha256::new()
+        .chain_update(addr.as_bytes())
+        .chain_update(power.to_be_bytes())
+        .finalize()
+        .into()
+}
+
+/// Hashes two nodes into their parent. The pair is sorted first, so
+/// proofs need not record which side each sibling is on.
+fn parent_hash(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    Sha256::new()
+        .chain_update(left)
+        .chain_update(right)
+        .finalize()
+        .into()
+}
+
+/// Computes the next level of the tree. A node without a sibling is
+/// carried up unchanged.
+fn next_level(level: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [a, b] => parent_hash(a, b),
+            [a] => *a,
+            _ => unreachable!(),
+        })
+        .collect()
+}
+
+/// Computes the root of the tree with LEAVES, or `None` if there are
+/// no leaves.
+pub fn root(leaves: Vec<[u8; 32]>) -> Option<[u8; 32]> {
+    let mut level = leaves;
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+    level.into_iter().next()
+}
+
+/// Computes the siblings on the path from the leaf at INDEX to the
+/// root, ordered from the leaf upwards.
+pub fn proof(leaves: Vec<[u8; 32]>, mut index: usize) -> Vec<HexBinary> {
+    let mut level = leaves;
+    let mut proof = vec![];
+    while level.len() > 1 {
+        if let Some(sibling) = level.get(index ^ 1) {
+            proof.push(HexBinary::from(sibling.as_slice()));
+        }
+        level = next_level(&level);
+        index /= 2;
+    }
+    proof
+}
+
+/// Checks that PROOF places LEAF in the tree with ROOT.
+pub fn verify(root: &[u8], leaf: [u8; 32], proof: &[HexBinary]) -> bool {
+    let computed = proof.iter().try_fold(leaf, |node, sibling| {
+        let sibling: [u8; 32] = sibling.as_slice().try_into().ok()?;
+        Some(parent_hash(&node, &sibling))
+    });
+    computed.map_or(false, |computed| computed.as_slice() == root)
+}
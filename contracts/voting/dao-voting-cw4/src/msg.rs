@@ -1,6 +1,10 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, HexBinary};
+use cw_utils::Expiration;
 use dao_dao_macros::voting_module_query;
 
+use crate::state::RenewalFee;
+
 #[cw_serde]
 pub enum GroupContract {
     Existing {
@@ -18,7 +22,49 @@ pub struct InstantiateMsg {
 }
 
 #[cw_serde]
-pub enum ExecuteMsg {}
+pub struct MemberExpiration {
+    pub addr: String,
+    /// When the member's voting power drops to zero. Must be
+    /// `AtHeight` or `Never`.
+    pub expiration: Expiration,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Sets when members' voting power expires. Only the DAO may call
+    /// this method.
+    UpdateExpirations { expirations: Vec<MemberExpiration> },
+    /// Sets the fee members may pay to renew their membership, or
+    /// disables renewal by fee if `None`. Only the DAO may call this
+    /// method.
+    UpdateRenewalFee { renewal_fee: Option<RenewalFee> },
+    /// Pays the renewal fee to extend the sender's membership by the
+    /// renewal period, counted from the later of the current block
+    /// and the sender's current expiration.
+    Renew {},
+    /// Computes and stores a Merkle root of the members with voting
+    /// power at the current height, so that contracts on other
+    /// chains may verify membership. Only the DAO may call this
+    /// method.
+    SnapshotMembers {},
+}
+
+#[cw_serde]
+pub struct ExpiringMember {
+    pub addr: Addr,
+    pub expiration: Expiration,
+}
+
+#[cw_serde]
+pub struct MemberProofResponse {
+    pub power: u64,
+    /// The leaf for this member, `sha256(address || power)`.
+    pub leaf: HexBinary,
+    /// Siblings on the path from the leaf to the root. Each parent
+    /// is the hash of its two children in ascending byte order.
+    pub proof: Vec<HexBinary>,
+    pub root: HexBinary,
+}
 
 #[voting_module_query]
 #[cw_serde]
@@ -26,6 +72,30 @@ pub enum ExecuteMsg {}
 pub enum QueryMsg {
     #[returns(cosmwasm_std::Addr)]
     GroupContract {},
+    /// Returns when an address's voting power expires.
+    #[returns(cw_utils::Expiration)]
+    MemberExpiration { address: String },
+    /// Lists members whose voting power expires at or before
+    /// `before_height`, including those already expired, in
+    /// ascending order of address.
+    #[returns(Vec<ExpiringMember>)]
+    ExpiringMembers {
+        before_height: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the fee members may pay to renew their membership,
+    /// if any.
+    #[returns(Option<RenewalFee>)]
+    RenewalFee {},
+    /// Returns the member root computed at `height`, if any.
+    #[returns(Option<crate::state::MemberRoot>)]
+    MemberRoot { height: u64 },
+    /// Returns a proof of an address's voting power against the
+    /// member root computed at `height`, or `None` if the address
+    /// had no voting power in it.
+    #[returns(Option<MemberProofResponse>)]
+    MemberProof { height: u64, address: String },
 }
 
 #[cw_serde]
@@ -1,5 +1,44 @@
-use cosmwasm_std::Addr;
-use cw_storage_plus::Item;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Coin, HexBinary, Uint128};
+use cw_storage_plus::{Item, Map, SnapshotMap, Strategy};
+use cw_utils::Expiration;
 
 pub const GROUP_CONTRACT: Item<Addr> = Item::new("group_contract");
 pub const DAO: Item<Addr> = Item::new("dao_address");
+
+/// The fee a member may pay to extend their membership.
+#[cw_serde]
+pub struct RenewalFee {
+    /// The native tokens that must be paid to renew.
+    pub fee: Coin,
+    /// The number of blocks a renewal extends membership by.
+    pub period: u64,
+}
+
+/// Member expirations. Members without an entry do not expire. Only
+/// `Expiration::AtHeight` and `Expiration::Never` are stored, so that
+/// whether a member was expired at a past height may be known.
+pub const EXPIRATIONS: SnapshotMap<&Addr, Expiration> = SnapshotMap::new(
+    "expirations",
+    "expirations__checkpoints",
+    "expirations__changelog",
+    Strategy::EveryBlock,
+);
+pub const RENEWAL_FEE: Item<RenewalFee> = Item::new("renewal_fee");
+
+/// The root of a Merkle tree of the members with voting power at a
+/// height. Each leaf is the hash of a member's address and voting
+/// power, in ascending order of address.
+#[cw_serde]
+pub struct MemberRoot {
+    pub root: HexBinary,
+    pub total_power: Uint128,
+    /// The number of leaves in the tree.
+    pub members: u64,
+}
+
+/// Member roots by the height they were computed at.
+pub const MEMBER_ROOTS: Map<u64, MemberRoot> = Map::new("member_roots");
+/// The members and voting powers each member root was computed
+/// from, kept so that proofs may be built.
+pub const MEMBER_SNAPSHOTS: Map<u64, Vec<(Addr, u64)>> = Map::new("member_snapshots");
@@ -1,16 +1,23 @@
 use cosmwasm_std::{
+    coin, coins,
     testing::{mock_dependencies, mock_env},
     to_json_binary, Addr, CosmosMsg, Empty, Uint128, WasmMsg,
 };
 use cw2::ContractVersion;
-use cw_multi_test::{next_block, App, Contract, ContractWrapper, Executor};
+use cw_multi_test::{next_block, App, BankSudo, Contract, ContractWrapper, Executor};
+use cw_utils::Expiration;
 use dao_interface::voting::{
     InfoResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
 };
 
 use crate::{
     contract::{migrate, CONTRACT_NAME, CONTRACT_VERSION},
-    msg::{GroupContract, InstantiateMsg, MigrateMsg, QueryMsg},
+    merkle,
+    msg::{
+        ExecuteMsg, ExpiringMember, GroupContract, InstantiateMsg, MemberExpiration,
+        MemberProofResponse, MigrateMsg, QueryMsg,
+    },
+    state::{MemberRoot, RenewalFee},
     ContractError,
 };
 
@@ -741,3 +748,271 @@ pub fn test_migrate_update_version() {
     assert_eq!(version.version, CONTRACT_VERSION);
     assert_eq!(version.contract, CONTRACT_NAME);
 }
+
+#[test]
+fn test_member_expiration() {
+    let mut app = App::default();
+    let voting_addr = setup_test_case(&mut app);
+    let start = app.block_info().height;
+
+    let power = |app: &App, addr: &str, height: Option<u64>| -> Uint128 {
+        let res: VotingPowerAtHeightResponse = app
+            .wrap()
+            .query_wasm_smart(
+                voting_addr.clone(),
+                &QueryMsg::VotingPowerAtHeight {
+                    address: addr.to_string(),
+                    height,
+                },
+            )
+            .unwrap();
+        res.power
+    };
+    let total_power = |app: &App, height: Option<u64>| -> Uint128 {
+        let res: TotalPowerAtHeightResponse = app
+            .wrap()
+            .query_wasm_smart(
+                voting_addr.clone(),
+                &QueryMsg::TotalPowerAtHeight { height },
+            )
+            .unwrap();
+        res.power
+    };
+
+    // Only the DAO may set expirations.
+    let expirations = ExecuteMsg::UpdateExpirations {
+        expirations: vec![
+            MemberExpiration {
+                addr: ADDR1.to_string(),
+                expiration: Expiration::AtHeight(start + 5),
+            },
+            MemberExpiration {
+                addr: ADDR2.to_string(),
+                expiration: Expiration::AtHeight(start + 10),
+            },
+        ],
+    };
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            voting_addr.clone(),
+            &expirations,
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        voting_addr.clone(),
+        &expirations,
+        &[],
+    )
+    .unwrap();
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(DAO_ADDR),
+            voting_addr.clone(),
+            &ExecuteMsg::UpdateExpirations {
+                expirations: vec![MemberExpiration {
+                    addr: ADDR3.to_string(),
+                    expiration: Expiration::AtTime(app.block_info().time),
+                }],
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::InvalidExpiration {});
+
+    let expiring: Vec<ExpiringMember> = app
+        .wrap()
+        .query_wasm_smart(
+            voting_addr.clone(),
+            &QueryMsg::ExpiringMembers {
+                before_height: start + 5,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        expiring,
+        vec![ExpiringMember {
+            addr: Addr::unchecked(ADDR1),
+            expiration: Expiration::AtHeight(start + 5),
+        }]
+    );
+
+    app.update_block(|block| block.height = start + 5);
+    assert_eq!(power(&app, ADDR1, None), Uint128::zero());
+    assert_eq!(power(&app, ADDR2, None), Uint128::new(1));
+    assert_eq!(total_power(&app, None), Uint128::new(2));
+    // Power before expiry is unchanged.
+    assert_eq!(power(&app, ADDR1, Some(start + 4)), Uint128::new(1));
+    assert_eq!(total_power(&app, Some(start + 4)), Uint128::new(3));
+
+    // Renewal by fee.
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        voting_addr.clone(),
+        &ExecuteMsg::UpdateRenewalFee {
+            renewal_fee: Some(RenewalFee {
+                fee: coin(10, "ujuno"),
+                period: 100,
+            }),
+        },
+        &[],
+    )
+    .unwrap();
+    app.sudo(cw_multi_test::SudoMsg::Bank(BankSudo::Mint {
+        to_address: ADDR1.to_string(),
+        amount: coins(20, "ujuno"),
+    }))
+    .unwrap();
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            voting_addr.clone(),
+            &ExecuteMsg::Renew {},
+            &coins(5, "ujuno"),
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::InvalidRenewalPayment {
+            expected: "10ujuno".to_string()
+        }
+    );
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR3),
+            voting_addr.clone(),
+            &ExecuteMsg::Renew {},
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Payment(_)));
+
+    app.execute_contract(
+        Addr::unchecked(ADDR1),
+        voting_addr.clone(),
+        &ExecuteMsg::Renew {},
+        &coins(10, "ujuno"),
+    )
+    .unwrap();
+    let expiration: Expiration = app
+        .wrap()
+        .query_wasm_smart(
+            voting_addr.clone(),
+            &QueryMsg::MemberExpiration {
+                address: ADDR1.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(expiration, Expiration::AtHeight(start + 105));
+    assert_eq!(
+        app.wrap().query_balance(DAO_ADDR, "ujuno").unwrap().amount,
+        Uint128::new(10)
+    );
+
+    // Renewal takes effect from the next block, and past power is
+    // unchanged.
+    app.update_block(next_block);
+    assert_eq!(power(&app, ADDR1, None), Uint128::new(1));
+    assert_eq!(total_power(&app, None), Uint128::new(3));
+    assert_eq!(power(&app, ADDR1, Some(start + 5)), Uint128::zero());
+}
+
+#[test]
+fn test_member_root() {
+    let mut app = App::default();
+    let voting_addr = setup_test_case(&mut app);
+    let height = app.block_info().height;
+
+    // ADDR3 is expired, and ADDR4 has no weight, so neither is in
+    // the tree.
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        voting_addr.clone(),
+        &ExecuteMsg::UpdateExpirations {
+            expirations: vec![MemberExpiration {
+                addr: ADDR3.to_string(),
+                expiration: Expiration::AtHeight(height),
+            }],
+        },
+        &[],
+    )
+    .unwrap();
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            voting_addr.clone(),
+            &ExecuteMsg::SnapshotMembers {},
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        voting_addr.clone(),
+        &ExecuteMsg::SnapshotMembers {},
+        &[],
+    )
+    .unwrap();
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(DAO_ADDR),
+            voting_addr.clone(),
+            &ExecuteMsg::SnapshotMembers {},
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::MemberRootExists {});
+
+    let member_root: Option<MemberRoot> = app
+        .wrap()
+        .query_wasm_smart(voting_addr.clone(), &QueryMsg::MemberRoot { height })
+        .unwrap();
+    let member_root = member_root.unwrap();
+    assert_eq!(member_root.total_power, Uint128::new(2));
+    assert_eq!(member_root.members, 2);
+
+    let proof_of = |app: &App, addr: &str| -> Option<MemberProofResponse> {
+        app.wrap()
+            .query_wasm_smart(
+                voting_addr.clone(),
+                &QueryMsg::MemberProof {
+                    height,
+                    address: addr.to_string(),
+                },
+            )
+            .unwrap()
+    };
+    for addr in [ADDR1, ADDR2] {
+        let proof = proof_of(&app, addr).unwrap();
+        assert_eq!(proof.power, 1);
+        assert_eq!(proof.root, member_root.root);
+        let leaf = merkle::leaf_hash(&Addr::unchecked(addr), 1);
+        assert!(merkle::verify(&member_root.root, leaf, &proof.proof));
+        // The proof does not hold for a different power.
+        let leaf = merkle::leaf_hash(&Addr::unchecked(addr), 2);
+        assert!(!merkle::verify(&member_root.root, leaf, &proof.proof));
+    }
+    assert_eq!(proof_of(&app, ADDR3), None);
+    assert_eq!(proof_of(&app, ADDR4), None);
+}
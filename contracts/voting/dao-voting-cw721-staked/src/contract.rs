@@ -11,16 +11,22 @@ use cw_utils::{parse_reply_execute_data, parse_reply_instantiate_data, Duration}
 use dao_hooks::nft_stake::{stake_nft_hook_msgs, unstake_nft_hook_msgs};
 use dao_interface::state::ModuleInstantiateCallback;
 use dao_interface::{nft::NftFactoryCallback, voting::IsActiveResponse};
+use dao_voting::compaction::{
+    compact_snapshot_balances, Compaction, DEFAULT_COMPACT_LIMIT, MAX_COMPACT_LIMIT,
+};
 use dao_voting::duration::validate_duration;
 use dao_voting::threshold::{
     assert_valid_absolute_count_threshold, assert_valid_percentage_threshold, ActiveThreshold,
     ActiveThresholdResponse,
 };
 
-use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, NftContract, QueryMsg};
+use crate::msg::{
+    ExecuteMsg, InstantiateMsg, MigrateMsg, NftContract, QueryMsg, TokenUnstakingDuration,
+};
 use crate::state::{
-    register_staked_nft, register_unstaked_nfts, Config, ACTIVE_THRESHOLD, CONFIG, DAO, HOOKS,
-    INITIAL_NFTS, MAX_CLAIMS, NFT_BALANCES, NFT_CLAIMS, STAKED_NFTS_PER_OWNER, TOTAL_STAKED_NFTS,
+    register_staked_nft, register_unstaked_nfts, Config, ACTIVE_THRESHOLD, COMPACTION_CURSOR,
+    CONFIG, DAO, HOOKS, INITIAL_NFTS, MAX_CLAIMS, NFT_BALANCES, NFT_CLAIMS, SNAPSHOT_RETENTION,
+    STAKED_NFTS_PER_OWNER, TOKEN_UNSTAKING_DURATIONS, TOTAL_STAKED_NFTS,
 };
 use crate::ContractError;
 
@@ -206,11 +212,18 @@ pub fn execute(
         ExecuteMsg::Unstake { token_ids } => execute_unstake(deps, env, info, token_ids),
         ExecuteMsg::ClaimNfts {} => execute_claim_nfts(deps, env, info),
         ExecuteMsg::UpdateConfig { duration } => execute_update_config(info, deps, duration),
+        ExecuteMsg::UpdateTokenUnstakingDurations { to_set, to_remove } => {
+            execute_update_token_unstaking_durations(deps, info, to_set, to_remove)
+        }
         ExecuteMsg::AddHook { addr } => execute_add_hook(deps, info, addr),
         ExecuteMsg::RemoveHook { addr } => execute_remove_hook(deps, info, addr),
         ExecuteMsg::UpdateActiveThreshold { new_threshold } => {
             execute_update_active_threshold(deps, env, info, new_threshold)
         }
+        ExecuteMsg::UpdateSnapshotRetention { blocks } => {
+            execute_update_snapshot_retention(deps, info, blocks)
+        }
+        ExecuteMsg::Compact { limit } => execute_compact(deps, env, limit),
     }
 }
 
@@ -229,11 +242,13 @@ pub fn execute_stake(
     }
     let staker = deps.api.addr_validate(&wrapper.sender)?;
     register_staked_nft(deps.storage, env.block.height, &staker, &wrapper.token_id)?;
+    let power = NFT_BALANCES.load(deps.storage, &staker)?;
     let hook_msgs = stake_nft_hook_msgs(
         HOOKS,
         deps.storage,
         staker.clone(),
         wrapper.token_id.clone(),
+        power,
     )?;
     Ok(Response::default()
         .add_submessages(hook_msgs)
@@ -285,58 +300,79 @@ pub fn execute_unstake(
     // so if we reach this point in execution, we may safely create
     // claims.
 
-    let hook_msgs =
-        unstake_nft_hook_msgs(HOOKS, deps.storage, info.sender.clone(), token_ids.clone())?;
+    let power = NFT_BALANCES.load(deps.storage, &info.sender)?;
+    let hook_msgs = unstake_nft_hook_msgs(
+        HOOKS,
+        deps.storage,
+        info.sender.clone(),
+        token_ids.clone(),
+        power,
+    )?;
 
+    // Tokens are returned immediately or claimable after their
+    // unstaking duration, grouped by when they may be claimed.
     let config = CONFIG.load(deps.storage)?;
-    match config.unstaking_duration {
-        None => {
-            let return_messages = token_ids
-                .into_iter()
-                .map(|token_id| -> StdResult<WasmMsg> {
-                    Ok(cosmwasm_std::WasmMsg::Execute {
-                        contract_addr: config.nft_address.to_string(),
-                        msg: to_json_binary(&cw721::Cw721ExecuteMsg::TransferNft {
-                            recipient: info.sender.to_string(),
-                            token_id,
-                        })?,
-                        funds: vec![],
-                    })
-                })
-                .collect::<StdResult<Vec<_>>>()?;
-
-            Ok(Response::default()
-                .add_messages(return_messages)
-                .add_submessages(hook_msgs)
-                .add_attribute("action", "unstake")
-                .add_attribute("from", info.sender)
-                .add_attribute("claim_duration", "None"))
+    let mut returned = vec![];
+    let mut claims: Vec<(Duration, Vec<String>)> = vec![];
+    for token_id in token_ids {
+        let duration = TOKEN_UNSTAKING_DURATIONS
+            .may_load(deps.storage, &token_id)?
+            .unwrap_or(config.unstaking_duration);
+        match duration {
+            None => returned.push(token_id),
+            Some(duration) => match claims.iter_mut().find(|(d, _)| *d == duration) {
+                Some((_, tokens)) => tokens.push(token_id),
+                None => claims.push((duration, vec![token_id])),
+            },
         }
+    }
 
-        Some(duration) => {
-            let outstanding_claims = NFT_CLAIMS
-                .query_claims(deps.as_ref(), &info.sender)?
-                .nft_claims;
-            if outstanding_claims.len() + token_ids.len() > MAX_CLAIMS as usize {
-                return Err(ContractError::TooManyClaims {});
-            }
+    let claimed = claims.iter().map(|(_, tokens)| tokens.len()).sum::<usize>();
+    if claimed > 0 {
+        let outstanding_claims = NFT_CLAIMS
+            .query_claims(deps.as_ref(), &info.sender)?
+            .nft_claims;
+        if outstanding_claims.len() + claimed > MAX_CLAIMS as usize {
+            return Err(ContractError::TooManyClaims {});
+        }
+    }
 
-            // Out of gas here is fine - just try again with fewer
-            // tokens.
-            NFT_CLAIMS.create_nft_claims(
-                deps.storage,
-                &info.sender,
-                token_ids,
-                duration.after(&env.block),
-            )?;
+    let return_messages = returned
+        .into_iter()
+        .map(|token_id| -> StdResult<WasmMsg> {
+            Ok(cosmwasm_std::WasmMsg::Execute {
+                contract_addr: config.nft_address.to_string(),
+                msg: to_json_binary(&cw721::Cw721ExecuteMsg::TransferNft {
+                    recipient: info.sender.to_string(),
+                    token_id,
+                })?,
+                funds: vec![],
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
 
-            Ok(Response::default()
-                .add_attribute("action", "unstake")
-                .add_submessages(hook_msgs)
-                .add_attribute("from", info.sender)
-                .add_attribute("claim_duration", format!("{duration}")))
-        }
+    let any_returned = !return_messages.is_empty();
+    let mut response = Response::default()
+        .add_messages(return_messages)
+        .add_submessages(hook_msgs)
+        .add_attribute("action", "unstake")
+        .add_attribute("from", info.sender.clone());
+    if any_returned {
+        response = response.add_attribute("claim_duration", "None");
     }
+    for (duration, tokens) in claims {
+        // Out of gas here is fine - just try again with fewer
+        // tokens.
+        NFT_CLAIMS.create_nft_claims(
+            deps.storage,
+            &info.sender,
+            tokens,
+            duration.after(&env.block),
+        )?;
+        response = response.add_attribute("claim_duration", format!("{duration}"));
+    }
+
+    Ok(response)
 }
 
 pub fn execute_claim_nfts(
@@ -402,6 +438,31 @@ pub fn execute_update_config(
         ))
 }
 
+pub fn execute_update_token_unstaking_durations(
+    deps: DepsMut,
+    info: MessageInfo,
+    to_set: Vec<TokenUnstakingDuration>,
+    to_remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    let dao = DAO.load(deps.storage)?;
+    if info.sender != dao {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for TokenUnstakingDuration { token_id, duration } in to_set.iter() {
+        validate_duration(*duration)?;
+        TOKEN_UNSTAKING_DURATIONS.save(deps.storage, token_id, duration)?;
+    }
+    for token_id in to_remove.iter() {
+        TOKEN_UNSTAKING_DURATIONS.remove(deps.storage, token_id);
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "update_token_unstaking_durations")
+        .add_attribute("set", to_set.len().to_string())
+        .add_attribute("removed", to_remove.len().to_string()))
+}
+
 pub fn execute_add_hook(
     deps: DepsMut,
     info: MessageInfo,
@@ -477,6 +538,67 @@ pub fn execute_update_active_threshold(
     Ok(Response::new().add_attribute("action", "update_active_threshold"))
 }
 
+pub fn execute_update_snapshot_retention(
+    deps: DepsMut,
+    info: MessageInfo,
+    blocks: Option<u64>,
+) -> Result<Response, ContractError> {
+    let dao = DAO.load(deps.storage)?;
+    if info.sender != dao {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match blocks {
+        Some(0) => return Err(ContractError::ZeroSnapshotRetention {}),
+        Some(blocks) => SNAPSHOT_RETENTION.save(deps.storage, &blocks)?,
+        None => SNAPSHOT_RETENTION.remove(deps.storage),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "update_snapshot_retention")
+        .add_attribute(
+            "blocks",
+            blocks
+                .map(|b| b.to_string())
+                .unwrap_or_else(|| "none".to_string()),
+        ))
+}
+
+pub fn execute_compact(
+    deps: DepsMut,
+    env: Env,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let retention = SNAPSHOT_RETENTION
+        .may_load(deps.storage)?
+        .ok_or(ContractError::CompactionDisabled {})?;
+    let limit = limit
+        .unwrap_or(DEFAULT_COMPACT_LIMIT)
+        .min(MAX_COMPACT_LIMIT);
+    let start_after = COMPACTION_CURSOR.may_load(deps.storage)?;
+
+    let Compaction {
+        pruned,
+        next_start_after,
+    } = compact_snapshot_balances(
+        deps.storage,
+        &NFT_BALANCES,
+        start_after,
+        limit,
+        env.block.height,
+        retention,
+    )?;
+
+    match next_start_after {
+        Some(addr) => COMPACTION_CURSOR.save(deps.storage, &addr)?,
+        None => COMPACTION_CURSOR.remove(deps.storage),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "compact")
+        .add_attribute("pruned", pruned.to_string()))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -487,11 +609,18 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::IsActive {} => query_is_active(deps, env),
         QueryMsg::NftClaims { address } => query_nft_claims(deps, address),
         QueryMsg::Hooks {} => query_hooks(deps),
+        QueryMsg::SnapshotRetention {} => {
+            to_json_binary(&SNAPSHOT_RETENTION.may_load(deps.storage)?)
+        }
         QueryMsg::StakedNfts {
             address,
             start_after,
             limit,
         } => query_staked_nfts(deps, address, start_after, limit),
+        QueryMsg::TokenUnstakingDurations { start_after, limit } => {
+            query_token_unstaking_durations(deps, start_after, limit)
+        }
+        QueryMsg::UnstakingDuration { token_id } => query_unstaking_duration(deps, token_id),
         QueryMsg::TotalPowerAtHeight { height } => query_total_power_at_height(deps, env, height),
         QueryMsg::VotingPowerAtHeight { address, height } => {
             query_voting_power_at_height(deps, env, address, height)
@@ -642,6 +771,35 @@ pub fn query_staked_nfts(
     to_json_binary(&range?)
 }
 
+pub fn query_token_unstaking_durations(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let start_after = start_after.as_deref().map(Bound::exclusive);
+    let range = TOKEN_UNSTAKING_DURATIONS
+        .range(
+            deps.storage,
+            start_after,
+            None,
+            cosmwasm_std::Order::Ascending,
+        )
+        .map(|item| item.map(|(token_id, duration)| TokenUnstakingDuration { token_id, duration }));
+    let range: StdResult<Vec<TokenUnstakingDuration>> = match limit {
+        Some(l) => range.take(l as usize).collect(),
+        None => range.collect(),
+    };
+    to_json_binary(&range?)
+}
+
+pub fn query_unstaking_duration(deps: Deps, token_id: String) -> StdResult<Binary> {
+    let duration = match TOKEN_UNSTAKING_DURATIONS.may_load(deps.storage, &token_id)? {
+        Some(duration) => duration,
+        None => CONFIG.load(deps.storage)?.unstaking_duration,
+    };
+    to_json_binary(&duration)
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
     let storage_version: ContractVersion = get_contract_version(deps.storage)?;
@@ -26,6 +26,9 @@ pub enum ContractError {
     #[error("Invalid token. Got ({received}), expected ({expected})")]
     InvalidToken { received: Addr, expected: Addr },
 
+    #[error("Snapshot compaction is disabled")]
+    CompactionDisabled {},
+
     #[error("Error instantiating NFT contract")]
     NftInstantiateError {},
 
@@ -56,6 +59,9 @@ pub enum ContractError {
     #[error("Factory message must serialize to WasmMsg::Execute")]
     UnsupportedFactoryMsg {},
 
+    #[error("Snapshot retention must be greater than zero")]
+    ZeroSnapshotRetention {},
+
     #[error("Can't unstake zero NFTs.")]
     ZeroUnstake {},
 }
@@ -32,6 +32,15 @@ pub enum NftContract {
     Factory(Binary),
 }
 
+/// An unstaking duration for a single token.
+#[cw_serde]
+pub struct TokenUnstakingDuration {
+    pub token_id: String,
+    /// Amount of time between unstaking the token and it being
+    /// avaliable. `None` returns the token with no delay.
+    pub duration: Option<Duration>,
+}
+
 #[cw_serde]
 pub struct InstantiateMsg {
     /// Address of the cw721 NFT contract that may be staked.
@@ -59,12 +68,31 @@ pub enum ExecuteMsg {
     /// Updates the contract configuration, namely unstaking duration.
     /// Only callable by the DAO that initialized this voting contract.
     UpdateConfig { duration: Option<Duration> },
+    /// Sets unstaking durations for individual tokens, which apply in
+    /// place of the configured unstaking duration, and removes those
+    /// of the tokens in `to_remove`. Only callable by the DAO that
+    /// initialized this voting contract.
+    UpdateTokenUnstakingDurations {
+        to_set: Vec<TokenUnstakingDuration>,
+        to_remove: Vec<String>,
+    },
     /// Adds a hook which is called on staking / unstaking events.
     /// Only callable by the DAO that initialized this voting contract.
     AddHook { addr: String },
     /// Removes a hook which is called on staking / unstaking events.
     /// Only callable by the DAO that initialized this voting contract.
     RemoveHook { addr: String },
+    /// Sets the number of blocks that an address must have had no
+    /// staked NFTs for before its balance history may be removed by
+    /// `Compact`. This should be at least as long as the longest voting
+    /// period of any proposal module in the DAO. `None` disables
+    /// compaction. Only the DAO may call this method.
+    UpdateSnapshotRetention { blocks: Option<u64> },
+    /// Removes the balance history of addresses that have had no
+    /// staked NFTs for longer than the snapshot retention period.
+    /// Examines at most `limit` addresses, continuing from where the
+    /// previous call left off. Callable by anyone.
+    Compact { limit: Option<u32> },
     /// Sets the active threshold to a new value.
     /// Only callable by the DAO that initialized this voting contract.
     UpdateActiveThreshold {
@@ -90,8 +118,19 @@ pub enum QueryMsg {
         start_after: Option<String>,
         limit: Option<u32>,
     },
+    /// Lists the tokens with their own unstaking duration.
+    #[returns(Vec<TokenUnstakingDuration>)]
+    TokenUnstakingDurations {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Gets the unstaking duration that applies to a token.
+    #[returns(Option<Duration>)]
+    UnstakingDuration { token_id: String },
     #[returns(ActiveThresholdResponse)]
     ActiveThreshold {},
+    #[returns(Option<u64>)]
+    SnapshotRetention {},
 }
 
 #[cw_serde]
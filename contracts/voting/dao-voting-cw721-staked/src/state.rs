@@ -42,12 +42,26 @@ pub const TOTAL_STAKED_NFTS: SnapshotItem<Uint128> = SnapshotItem::new(
     Strategy::EveryBlock,
 );
 
+/// Unstaking durations for individual tokens, which apply in place of
+/// the configured unstaking duration. A `None` value returns the token
+/// as soon as it is unstaked.
+pub const TOKEN_UNSTAKING_DURATIONS: Map<&str, Option<Duration>> =
+    Map::new("token_unstaking_durations");
+
 /// The maximum number of claims that may be outstanding.
 pub const MAX_CLAIMS: u64 = 70;
 pub const NFT_CLAIMS: NftClaims = NftClaims::new("nft_claims");
 
 // Hooks to contracts that will receive staking and unstaking
 // messages.
+/// The number of blocks an address must have had no staked NFTs
+/// for before its balance history may be compacted. Compaction is
+/// disabled if unset.
+pub const SNAPSHOT_RETENTION: Item<u64> = Item::new("snapshot_retention");
+
+/// The last address examined by compaction.
+pub const COMPACTION_CURSOR: Item<Addr> = Item::new("compaction_cursor");
+
 pub const HOOKS: Hooks = Hooks::new("hooks");
 
 pub fn register_staked_nft(
@@ -141,3 +141,31 @@ pub fn remove_hook(
         &[],
     )
 }
+
+pub fn update_snapshot_retention(
+    app: &mut App,
+    module: &Addr,
+    sender: &str,
+    blocks: Option<u64>,
+) -> AnyResult<AppResponse> {
+    app.execute_contract(
+        addr!(sender),
+        module.clone(),
+        &ExecuteMsg::UpdateSnapshotRetention { blocks },
+        &[],
+    )
+}
+
+pub fn compact(
+    app: &mut App,
+    module: &Addr,
+    sender: &str,
+    limit: Option<u32>,
+) -> AnyResult<AppResponse> {
+    app.execute_contract(
+        addr!(sender),
+        module.clone(),
+        &ExecuteMsg::Compact { limit },
+        &[],
+    )
+}
@@ -1,8 +1,11 @@
 use cosmwasm_std::{
     testing::{mock_dependencies, mock_env, mock_info},
-    Addr,
+    to_json_binary, Addr, SubMsg, Uint128, WasmMsg,
+};
+use cw721::Cw721ReceiveMsg;
+use dao_hooks::nft_stake::{
+    stake_nft_hook_msgs, unstake_nft_hook_msgs, NftStakeChangedExecuteMsg, NftStakeChangedHookMsg,
 };
-use dao_hooks::nft_stake::{stake_nft_hook_msgs, unstake_nft_hook_msgs};
 
 use crate::{
     contract::execute,
@@ -18,6 +21,7 @@ fn test_hooks() {
         &deps.storage,
         Addr::unchecked("ekez"),
         "ekez-token".to_string(),
+        Uint128::new(1),
     )
     .unwrap();
     assert_eq!(messages.len(), 0);
@@ -27,6 +31,7 @@ fn test_hooks() {
         &deps.storage,
         Addr::unchecked("ekez"),
         vec!["ekez-token".to_string()],
+        Uint128::zero(),
     )
     .unwrap();
     assert_eq!(messages.len(), 0);
@@ -64,6 +69,7 @@ fn test_hooks() {
         &deps.storage,
         Addr::unchecked("ekez"),
         "ekez-token".to_string(),
+        Uint128::new(1),
     )
     .unwrap();
     assert_eq!(messages.len(), 1);
@@ -73,6 +79,7 @@ fn test_hooks() {
         &deps.storage,
         Addr::unchecked("ekez"),
         vec!["ekez-token".to_string()],
+        Uint128::zero(),
     )
     .unwrap();
     assert_eq!(messages.len(), 1);
@@ -95,6 +102,7 @@ fn test_hooks() {
         &deps.storage,
         Addr::unchecked("ekez"),
         "ekez-token".to_string(),
+        Uint128::new(1),
     )
     .unwrap();
     assert_eq!(messages.len(), 0);
@@ -104,7 +112,81 @@ fn test_hooks() {
         &deps.storage,
         Addr::unchecked("ekez"),
         vec!["ekez-token".to_string()],
+        Uint128::zero(),
     )
     .unwrap();
     assert_eq!(messages.len(), 0);
 }
+
+#[test]
+fn test_hook_power() {
+    let mut deps = mock_dependencies();
+
+    DAO.save(deps.as_mut().storage, &Addr::unchecked("ekez"))
+        .unwrap();
+    CONFIG
+        .save(
+            deps.as_mut().storage,
+            &Config {
+                nft_address: Addr::unchecked("ekez-token"),
+                unstaking_duration: None,
+            },
+        )
+        .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("ekez", &[]),
+        crate::msg::ExecuteMsg::AddHook {
+            addr: "hook".to_string(),
+        },
+    )
+    .unwrap();
+
+    let hook_msg = |msg: NftStakeChangedHookMsg| {
+        SubMsg::new(WasmMsg::Execute {
+            contract_addr: "hook".to_string(),
+            msg: to_json_binary(&NftStakeChangedExecuteMsg::NftStakeChangeHook(msg)).unwrap(),
+            funds: vec![],
+        })
+    };
+
+    for (token_id, power) in [("1", 1u128), ("2", 2)] {
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("ekez-token", &[]),
+            crate::msg::ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+                sender: "ekez".to_string(),
+                token_id: token_id.to_string(),
+                msg: to_json_binary("").unwrap(),
+            }),
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![hook_msg(NftStakeChangedHookMsg::Stake {
+                addr: Addr::unchecked("ekez"),
+                token_id: token_id.to_string(),
+                power: Uint128::new(power),
+            })]
+        );
+    }
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("ekez", &[]),
+        crate::msg::ExecuteMsg::Unstake {
+            token_ids: vec!["2".to_string()],
+        },
+    )
+    .unwrap();
+    assert!(res
+        .messages
+        .contains(&hook_msg(NftStakeChangedHookMsg::Unstake {
+            addr: Addr::unchecked("ekez"),
+            token_ids: vec!["2".to_string()],
+            power: Uint128::new(1),
+        })));
+}
@@ -162,6 +162,7 @@ impl TestEnvBuilder {
             .query(&DaoQueryMsg::ProposalModules {
                 limit: None,
                 start_after: None,
+                descending: None,
             })
             .unwrap();
         let proposal_single = DaoProposalSingle::new_with_values(
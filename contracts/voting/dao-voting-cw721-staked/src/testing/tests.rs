@@ -12,11 +12,12 @@ use dao_voting::threshold::{ActiveThreshold, ActiveThresholdResponse};
 
 use crate::{
     contract::{migrate, CONTRACT_NAME, CONTRACT_VERSION},
-    msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, NftContract, QueryMsg},
+    msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, NftContract, QueryMsg, TokenUnstakingDuration},
     state::MAX_CLAIMS,
     testing::{
         execute::{
-            claim_nfts, mint_and_stake_nft, mint_nft, stake_nft, unstake_nfts, update_config,
+            claim_nfts, compact, mint_and_stake_nft, mint_nft, stake_nft, unstake_nfts,
+            update_config, update_snapshot_retention,
         },
         queries::{query_config, query_hooks, query_nft_owner, query_total_and_voting_power},
     },
@@ -323,6 +324,99 @@ fn test_max_claims() -> anyhow::Result<()> {
     Ok(())
 }
 
+// Tokens may have their own unstaking durations, which apply in place
+// of the configured one.
+#[test]
+fn test_token_unstaking_durations() -> anyhow::Result<()> {
+    let CommonTest {
+        mut app,
+        module,
+        nft,
+    } = setup_test(Some(Duration::Height(1)));
+
+    mint_and_stake_nft(&mut app, &nft, &module, CREATOR_ADDR, "1")?;
+    mint_and_stake_nft(&mut app, &nft, &module, CREATOR_ADDR, "2")?;
+    mint_and_stake_nft(&mut app, &nft, &module, CREATOR_ADDR, "3")?;
+
+    let update = ExecuteMsg::UpdateTokenUnstakingDurations {
+        to_set: vec![
+            TokenUnstakingDuration {
+                token_id: "2".to_string(),
+                duration: Some(Duration::Height(5)),
+            },
+            TokenUnstakingDuration {
+                token_id: "3".to_string(),
+                duration: None,
+            },
+        ],
+        to_remove: vec![],
+    };
+    let res = app.execute_contract(Addr::unchecked("ekez"), module.clone(), &update, &[]);
+    is_error!(res => "Unauthorized");
+    app.execute_contract(Addr::unchecked(CREATOR_ADDR), module.clone(), &update, &[])?;
+
+    let durations: Vec<TokenUnstakingDuration> = app.wrap().query_wasm_smart(
+        &module,
+        &QueryMsg::TokenUnstakingDurations {
+            start_after: Some("2".to_string()),
+            limit: None,
+        },
+    )?;
+    assert_eq!(
+        durations,
+        vec![TokenUnstakingDuration {
+            token_id: "3".to_string(),
+            duration: None,
+        }]
+    );
+    let duration: Option<Duration> = app.wrap().query_wasm_smart(
+        &module,
+        &QueryMsg::UnstakingDuration {
+            token_id: "1".to_string(),
+        },
+    )?;
+    assert_eq!(duration, Some(Duration::Height(1)));
+
+    unstake_nfts(&mut app, &module, CREATOR_ADDR, &["1", "2", "3"])?;
+
+    // Voting power is removed immediately, and the token without a
+    // duration is returned.
+    let power = query_voting_power(&app, &module, CREATOR_ADDR, None)?;
+    assert_eq!(power.power, Uint128::zero());
+    let owner = query_nft_owner(&app, &nft, "3")?;
+    assert_eq!(owner.owner, CREATOR_ADDR.to_string());
+
+    let height = app.block_info().height;
+    let claims = query_claims(&app, &module, CREATOR_ADDR)?;
+    assert_eq!(
+        claims.nft_claims,
+        vec![
+            NftClaim {
+                token_id: "1".to_string(),
+                release_at: cw_utils::Expiration::AtHeight(height + 1)
+            },
+            NftClaim {
+                token_id: "2".to_string(),
+                release_at: cw_utils::Expiration::AtHeight(height + 5)
+            },
+        ]
+    );
+
+    app.update_block(next_block);
+    claim_nfts(&mut app, &module, CREATOR_ADDR)?;
+    let owner = query_nft_owner(&app, &nft, "1")?;
+    assert_eq!(owner.owner, CREATOR_ADDR.to_string());
+    let owner = query_nft_owner(&app, &nft, "2")?;
+    assert_eq!(owner.owner, module.to_string());
+
+    app.update_block(|block| block.height += 4);
+    claim_nfts(&mut app, &module, CREATOR_ADDR)?;
+    let owner = query_nft_owner(&app, &nft, "2")?;
+    assert_eq!(owner.owner, CREATOR_ADDR.to_string());
+
+    Ok(())
+}
+
 // I can list all of the currently staked NFTs for an address.
 #[test]
 fn test_list_staked_nfts() -> anyhow::Result<()> {
@@ -1438,6 +1532,122 @@ fn test_factory_no_callback() {
     .unwrap();
 }
 
+fn pruned(res: &cw_multi_test::AppResponse) -> String {
+    res.events
+        .iter()
+        .flat_map(|e| e.attributes.iter())
+        .find(|a| a.key == "pruned")
+        .map(|a| a.value.clone())
+        .unwrap()
+}
+
+// The DAO may set a snapshot retention period, after which anyone may
+// remove the balance history of addresses with nothing staked.
+#[test]
+fn test_compact() -> anyhow::Result<()> {
+    let CommonTest {
+        mut app,
+        module,
+        nft,
+    } = setup_test(None);
+
+    mint_and_stake_nft(&mut app, &nft, &module, CREATOR_ADDR, "1")?;
+    mint_nft(&mut app, &nft, CREATOR_ADDR, "friend1", "2")?;
+    mint_nft(&mut app, &nft, CREATOR_ADDR, "friend2", "3")?;
+    stake_nft(&mut app, &nft, &module, "friend1", "2")?;
+    stake_nft(&mut app, &nft, &module, "friend2", "3")?;
+    app.update_block(next_block);
+    let staked_height = app.block_info().height;
+
+    unstake_nfts(&mut app, &module, "friend1", &["2"])?;
+    unstake_nfts(&mut app, &module, "friend2", &["3"])?;
+    app.update_block(next_block);
+
+    // Compaction is disabled until the DAO sets a retention period.
+    let res = compact(&mut app, &module, "friend1", None);
+    is_error!(res => "Snapshot compaction is disabled");
+
+    let res = update_snapshot_retention(&mut app, &module, "friend1", Some(10));
+    is_error!(res => "Unauthorized");
+    let res = update_snapshot_retention(&mut app, &module, CREATOR_ADDR, Some(0));
+    is_error!(res => "Snapshot retention must be greater than zero");
+
+    update_snapshot_retention(&mut app, &module, CREATOR_ADDR, Some(10))?;
+    let retention: Option<u64> = app
+        .wrap()
+        .query_wasm_smart(&module, &QueryMsg::SnapshotRetention {})?;
+    assert_eq!(retention, Some(10));
+
+    // The friends' balances changed inside the retention window so
+    // their history is kept.
+    let res = compact(&mut app, &module, "friend1", None)?;
+    assert_eq!(pruned(&res), "0");
+    let power = query_voting_power(&app, &module, "friend1", Some(staked_height))?;
+    assert_eq!(power.power, Uint128::new(1));
+
+    app.update_block(|b| b.height += 10);
+    let res = compact(&mut app, &module, "friend1", None)?;
+    assert_eq!(pruned(&res), "2");
+
+    for friend in ["friend1", "friend2"] {
+        let power = query_voting_power(&app, &module, friend, Some(staked_height))?;
+        assert_eq!(power.power, Uint128::zero());
+    }
+    // Addresses with NFTs staked and the total are untouched.
+    let (total, personal) =
+        query_total_and_voting_power(&app, &module, CREATOR_ADDR, Some(staked_height))?;
+    assert_eq!(total, Uint128::new(3));
+    assert_eq!(personal, Uint128::new(1));
+
+    // A pruned address may stake again.
+    stake_nft(&mut app, &nft, &module, "friend1", "2")?;
+    app.update_block(next_block);
+    let power = query_voting_power(&app, &module, "friend1", None)?;
+    assert_eq!(power.power, Uint128::new(1));
+
+    Ok(())
+}
+
+// Compaction examines at most `limit` addresses per call, continuing
+// from where the previous call left off.
+#[test]
+fn test_compact_limit() -> anyhow::Result<()> {
+    let CommonTest {
+        mut app,
+        module,
+        nft,
+    } = setup_test(None);
+
+    mint_and_stake_nft(&mut app, &nft, &module, CREATOR_ADDR, "1")?;
+    for (friend, token_id) in [("friend1", "2"), ("friend2", "3")] {
+        mint_nft(&mut app, &nft, CREATOR_ADDR, friend, token_id)?;
+        stake_nft(&mut app, &nft, &module, friend, token_id)?;
+    }
+    app.update_block(next_block);
+    unstake_nfts(&mut app, &module, "friend1", &["2"])?;
+    unstake_nfts(&mut app, &module, "friend2", &["3"])?;
+    update_snapshot_retention(&mut app, &module, CREATOR_ADDR, Some(1))?;
+    app.update_block(|b| b.height += 2);
+
+    // Addresses are examined in order: "creator" then "friend1".
+    let res = compact(&mut app, &module, "friend1", Some(2))?;
+    assert_eq!(pruned(&res), "1");
+    let power = query_voting_power(&app, &module, "friend2", Some(app.block_info().height - 2))?;
+    assert_eq!(power.power, Uint128::new(1));
+
+    // The next call continues from "friend1".
+    let res = compact(&mut app, &module, "friend1", Some(2))?;
+    assert_eq!(pruned(&res), "1");
+    let power = query_voting_power(&app, &module, "friend2", Some(app.block_info().height - 2))?;
+    assert_eq!(power.power, Uint128::zero());
+
+    // Having reached the end, compaction starts over.
+    let res = compact(&mut app, &module, "friend1", None)?;
+    assert_eq!(pruned(&res), "0");
+
+    Ok(())
+}
+
 #[test]
 pub fn test_migrate_update_version() {
     let mut deps = mock_dependencies();
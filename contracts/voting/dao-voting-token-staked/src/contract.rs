@@ -3,11 +3,11 @@ use cosmwasm_std::entry_point;
 
 use cosmwasm_std::{
     coins, from_json_binary, to_json_binary, BankMsg, BankQuery, Binary, Coin, CosmosMsg, Deps, DepsMut, Env,
-    MessageInfo, Order, Reply, Response, StdResult, SubMsg, Uint128, Uint256, WasmMsg,
+    MessageInfo, Reply, Response, StdResult, SubMsg, Uint128, Uint256, WasmMsg,
 };
 use cw2::{get_contract_version, set_contract_version, ContractVersion};
 use cw_controllers::ClaimsResponse;
-use cw_storage_plus::Bound;
+use cw_paginate_storage::{clamp_limit, order, range_bounds};
 use cw_tokenfactory_issuer::msg::{
     DenomUnit, ExecuteMsg as IssuerExecuteMsg, InstantiateMsg as IssuerInstantiateMsg, Metadata,
 };
@@ -21,6 +21,7 @@ use dao_interface::{
     voting::{IsActiveResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse},
 };
 use dao_voting::{
+    compaction::{compact_snapshot_balances, Compaction, DEFAULT_COMPACT_LIMIT, MAX_COMPACT_LIMIT},
     duration::validate_duration,
     threshold::{
         assert_valid_absolute_count_threshold, assert_valid_percentage_threshold, ActiveThreshold,
@@ -34,17 +35,14 @@ use crate::msg::{
     QueryMsg, StakerBalanceResponse, TokenInfo,
 };
 use crate::state::{
-    Config, ACTIVE_THRESHOLD, CLAIMS, CONFIG, DAO, DENOM, HOOKS, MAX_CLAIMS, STAKED_BALANCES,
-    STAKED_TOTAL, TOKEN_INSTANTIATION_INFO, TOKEN_ISSUER_CONTRACT,
+    Config, ACTIVE_THRESHOLD, CLAIMS, COMPACTION_CURSOR, CONFIG, DAO, DENOM, HOOKS, MAX_CLAIMS,
+    SNAPSHOT_RETENTION, STAKED_BALANCES, STAKED_TOTAL, TOKEN_INSTANTIATION_INFO,
+    TOKEN_ISSUER_CONTRACT,
 };
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-voting-token-staked";
 pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
-// Settings for query pagination
-const MAX_LIMIT: u32 = 30;
-const DEFAULT_LIMIT: u32 = 10;
-
 const INSTANTIATE_TOKEN_FACTORY_ISSUER_REPLY_ID: u64 = 0;
 const FACTORY_EXECUTE_REPLY_ID: u64 = 2;
 
@@ -169,6 +167,10 @@ pub fn execute(
         }
         ExecuteMsg::AddHook { addr } => execute_add_hook(deps, env, info, addr),
         ExecuteMsg::RemoveHook { addr } => execute_remove_hook(deps, env, info, addr),
+        ExecuteMsg::UpdateSnapshotRetention { blocks } => {
+            execute_update_snapshot_retention(deps, info, blocks)
+        }
+        ExecuteMsg::Compact { limit } => execute_compact(deps, env, limit),
     }
 }
 
@@ -385,6 +387,67 @@ pub fn execute_remove_hook(
         .add_attribute("hook", addr))
 }
 
+pub fn execute_update_snapshot_retention(
+    deps: DepsMut,
+    info: MessageInfo,
+    blocks: Option<u64>,
+) -> Result<Response, ContractError> {
+    let dao = DAO.load(deps.storage)?;
+    if info.sender != dao {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match blocks {
+        Some(0) => return Err(ContractError::ZeroSnapshotRetention {}),
+        Some(blocks) => SNAPSHOT_RETENTION.save(deps.storage, &blocks)?,
+        None => SNAPSHOT_RETENTION.remove(deps.storage),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "update_snapshot_retention")
+        .add_attribute(
+            "blocks",
+            blocks
+                .map(|b| b.to_string())
+                .unwrap_or_else(|| "none".to_string()),
+        ))
+}
+
+pub fn execute_compact(
+    deps: DepsMut,
+    env: Env,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let retention = SNAPSHOT_RETENTION
+        .may_load(deps.storage)?
+        .ok_or(ContractError::CompactionDisabled {})?;
+    let limit = limit
+        .unwrap_or(DEFAULT_COMPACT_LIMIT)
+        .min(MAX_COMPACT_LIMIT);
+    let start_after = COMPACTION_CURSOR.may_load(deps.storage)?;
+
+    let Compaction {
+        pruned,
+        next_start_after,
+    } = compact_snapshot_balances(
+        deps.storage,
+        &STAKED_BALANCES,
+        start_after,
+        limit,
+        env.block.height,
+        retention,
+    )?;
+
+    match next_start_after {
+        Some(addr) => COMPACTION_CURSOR.save(deps.storage, &addr)?,
+        None => COMPACTION_CURSOR.remove(deps.storage),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "compact")
+        .add_attribute("pruned", pruned.to_string()))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -401,12 +464,17 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::Denom {} => to_json_binary(&DenomResponse {
             denom: DENOM.load(deps.storage)?,
         }),
-        QueryMsg::ListStakers { start_after, limit } => {
-            query_list_stakers(deps, start_after, limit)
-        }
+        QueryMsg::ListStakers {
+            start_after,
+            limit,
+            descending,
+        } => query_list_stakers(deps, start_after, limit, descending),
         QueryMsg::IsActive {} => query_is_active(deps),
         QueryMsg::ActiveThreshold {} => query_active_threshold(deps),
         QueryMsg::GetHooks {} => to_json_binary(&query_hooks(deps)?),
+        QueryMsg::SnapshotRetention {} => {
+            to_json_binary(&SNAPSHOT_RETENTION.may_load(deps.storage)?)
+        }
         QueryMsg::TokenContract {} => to_json_binary(&TOKEN_ISSUER_CONTRACT.may_load(deps.storage)?),
     }
 }
@@ -455,14 +523,15 @@ pub fn query_list_stakers(
     deps: Deps,
     start_after: Option<String>,
     limit: Option<u32>,
+    descending: Option<bool>,
 ) -> StdResult<Binary> {
-    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
     let addr = maybe_addr(deps.api, start_after)?;
-    let start = addr.as_ref().map(Bound::exclusive);
+    let order = order(descending);
+    let (min, max) = range_bounds(addr.as_ref(), order);
 
     let stakers = STAKED_BALANCES
-        .range(deps.storage, start, None, Order::Ascending)
-        .take(limit)
+        .range(deps.storage, min, max, order)
+        .take(clamp_limit(limit))
         .map(|item| {
             item.map(|(address, balance)| StakerBalanceResponse {
                 address: address.into_string(),
@@ -23,6 +23,9 @@ pub enum ContractError {
     #[error(transparent)]
     UnstakingDurationError(#[from] dao_voting::duration::UnstakingDurationError),
 
+    #[error("Snapshot compaction is disabled")]
+    CompactionDisabled {},
+
     #[error("Initial governance token balances must not be empty")]
     InitialBalancesError {},
 
@@ -47,6 +50,9 @@ pub enum ContractError {
     #[error("Factory message must serialize to WasmMsg::Execute")]
     UnsupportedFactoryMsg {},
 
+    #[error("Snapshot retention must be greater than zero")]
+    ZeroSnapshotRetention {},
+
     #[error("Amount being unstaked must be non-zero")]
     ZeroUnstake {},
 }
@@ -55,6 +55,17 @@ pub enum ExecuteMsg {
     AddHook { addr: String },
     /// Removes a hook that fires on staking / unstaking
     RemoveHook { addr: String },
+    /// Sets the number of blocks that an address must have had no
+    /// staked tokens for before its balance history may be removed by
+    /// `Compact`. This should be at least as long as the longest voting
+    /// period of any proposal module in the DAO. `None` disables
+    /// compaction. Only the DAO may call this method.
+    UpdateSnapshotRetention { blocks: Option<u64> },
+    /// Removes the balance history of addresses that have had no
+    /// staked tokens for longer than the snapshot retention period.
+    /// Examines at most `limit` addresses, continuing from where the
+    /// previous call left off. Callable by anyone.
+    Compact { limit: Option<u32> },
 }
 
 #[active_query]
@@ -72,6 +83,7 @@ pub enum QueryMsg {
     ListStakers {
         start_after: Option<String>,
         limit: Option<u32>,
+        descending: Option<bool>,
     },
     #[returns(ActiveThresholdResponse)]
     ActiveThreshold {},
@@ -79,6 +91,8 @@ pub enum QueryMsg {
     GetHooks {},
     #[returns(Option<cosmwasm_std::Addr>)]
     TokenContract {},
+    #[returns(Option<u64>)]
+    SnapshotRetention {},
 }
 
 #[cw_serde]
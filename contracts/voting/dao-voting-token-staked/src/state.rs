@@ -46,6 +46,14 @@ pub const CLAIMS: Claims = Claims::new("claims");
 /// The minimum amount of staked tokens for the DAO to be active
 pub const ACTIVE_THRESHOLD: Item<ActiveThreshold> = Item::new("active_threshold");
 
+/// The number of blocks an address must have had no staked tokens
+/// for before its balance history may be compacted. Compaction is
+/// disabled if unset.
+pub const SNAPSHOT_RETENTION: Item<u64> = Item::new("snapshot_retention");
+
+/// The last address examined by compaction.
+pub const COMPACTION_CURSOR: Item<Addr> = Item::new("compaction_cursor");
+
 /// Hooks to contracts that will receive staking and unstaking messages
 pub const HOOKS: Hooks = Hooks::new("hooks");
 
@@ -899,6 +899,7 @@ fn test_query_list_stakers() {
             &QueryMsg::ListStakers {
                 start_after: None,
                 limit: None,
+                descending: None,
             },
         )
         .unwrap();
@@ -926,6 +927,7 @@ fn test_query_list_stakers() {
             &QueryMsg::ListStakers {
                 start_after: Some(ADDR1.to_string()),
                 limit: None,
+                descending: None,
             },
         )
         .unwrap();
@@ -947,6 +949,7 @@ fn test_query_list_stakers() {
             &QueryMsg::ListStakers {
                 start_after: Some(ADDR2.to_string()),
                 limit: None,
+                descending: None,
             },
         )
         .unwrap();
@@ -954,6 +957,84 @@ fn test_query_list_stakers() {
     assert_eq!(stakers, ListStakersResponse { stakers: vec![] });
 }
 
+#[test]
+fn test_compact() {
+    let mut app = mock_app();
+    let staking_id = app.store_code(staking_contract());
+    let addr = instantiate_staking(
+        &mut app,
+        staking_id,
+        InstantiateMsg {
+            token_info: TokenInfo::Existing {
+                denom: DENOM.to_string(),
+            },
+            unstaking_duration: None,
+            active_threshold: None,
+        },
+    );
+
+    stake_tokens(&mut app, addr.clone(), ADDR1, 100, DENOM).unwrap();
+    stake_tokens(&mut app, addr.clone(), ADDR2, 50, DENOM).unwrap();
+    app.update_block(next_block);
+    let staked_height = app.block_info().height;
+    unstake_tokens(&mut app, addr.clone(), ADDR1, 100).unwrap();
+    app.update_block(next_block);
+
+    // Only the DAO may set the retention period.
+    app.execute_contract(
+        Addr::unchecked(ADDR1),
+        addr.clone(),
+        &ExecuteMsg::UpdateSnapshotRetention { blocks: Some(10) },
+        &[],
+    )
+    .unwrap_err();
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        addr.clone(),
+        &ExecuteMsg::UpdateSnapshotRetention { blocks: Some(10) },
+        &[],
+    )
+    .unwrap();
+
+    app.update_block(|b| b.height += 10);
+    app.execute_contract(
+        Addr::unchecked(ADDR2),
+        addr.clone(),
+        &ExecuteMsg::Compact { limit: None },
+        &[],
+    )
+    .unwrap();
+
+    let resp = get_voting_power_at_height(
+        &mut app,
+        addr.clone(),
+        ADDR1.to_string(),
+        Some(staked_height),
+    );
+    assert_eq!(resp.power, Uint128::zero());
+    let resp = get_voting_power_at_height(&mut app, addr.clone(), ADDR2.to_string(), None);
+    assert_eq!(resp.power, Uint128::new(50));
+
+    let stakers: ListStakersResponse = app
+        .wrap()
+        .query_wasm_smart(
+            addr,
+            &QueryMsg::ListStakers {
+                start_after: None,
+                limit: None,
+                descending: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        stakers.stakers,
+        vec![StakerBalanceResponse {
+            address: ADDR2.to_string(),
+            balance: Uint128::new(50),
+        }]
+    );
+}
+
 #[test]
 #[should_panic(expected = "Active threshold count must be greater than zero")]
 fn test_instantiate_zero_active_threshold_count() {
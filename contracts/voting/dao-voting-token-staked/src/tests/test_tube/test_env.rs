@@ -268,6 +268,7 @@ impl TestEnvBuilder {
             .query(&DaoQueryMsg::ProposalModules {
                 limit: None,
                 start_after: None,
+                descending: None,
             })
             .unwrap();
         let proposal_single = DaoProposalSingle::new_with_values(
@@ -1,9 +1,63 @@
 #![doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/README.md"))]
 
-use cosmwasm_std::{Deps, Order, StdResult};
+use cosmwasm_std::{from_json_binary, to_json_binary, Binary, Deps, Order, StdResult};
 
 #[allow(unused_imports)]
-use cw_storage_plus::{Bound, Bounder, KeyDeserialize, Map, SnapshotMap, Strategy};
+use cw_storage_plus::{Bound, Bounder, KeyDeserialize, Map, PrimaryKey, SnapshotMap, Strategy};
+
+/// The number of entries a list query returns if no limit is given.
+pub const DEFAULT_LIMIT: u32 = 10;
+/// The most entries a list query returns.
+pub const MAX_LIMIT: u32 = 30;
+
+/// Gets the order a list query iterates in from its `descending`
+/// option. List queries are ascending unless `descending` is set.
+pub fn order(descending: Option<bool>) -> Order {
+    if descending.unwrap_or_default() {
+        Order::Descending
+    } else {
+        Order::Ascending
+    }
+}
+
+/// Gets the number of entries a list query should return given its
+/// `limit` option, applying `DEFAULT_LIMIT` and `MAX_LIMIT`.
+pub fn clamp_limit(limit: Option<u32>) -> usize {
+    limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize
+}
+
+/// As `clamp_limit`, for list queries that take a `u64` limit.
+pub fn clamp_limit_u64(limit: Option<u64>) -> usize {
+    limit.map_or(DEFAULT_LIMIT as usize, |limit| {
+        limit.min(MAX_LIMIT.into()) as usize
+    })
+}
+
+/// Encodes the key of the last entry a list query returned as an
+/// opaque cursor, for queries whose keys clients should not need to
+/// construct, such as composite keys.
+pub fn encode_cursor<K: serde::Serialize>(key: &K) -> StdResult<Binary> {
+    to_json_binary(key)
+}
+
+/// Decodes a cursor made by `encode_cursor`.
+pub fn decode_cursor<K: serde::de::DeserializeOwned>(cursor: &Binary) -> StdResult<K> {
+    from_json_binary(cursor)
+}
+
+/// Gets the `(min, max)` bounds of a range over the entries after
+/// `start_after` in `order`. In descending order, entries after
+/// `start_after` are those with smaller keys.
+pub fn range_bounds<'a, K: PrimaryKey<'a>>(
+    start_after: Option<K>,
+    order: Order,
+) -> (Option<Bound<'a, K>>, Option<Bound<'a, K>>) {
+    let bound = start_after.map(Bound::exclusive);
+    match order {
+        Order::Ascending => (bound, None),
+        Order::Descending => (None, bound),
+    }
+}
 
 /// Generic function for paginating a list of (K, V) pairs in a
 /// CosmWasm Map.
@@ -146,6 +200,39 @@ mod tests {
     use cosmwasm_std::testing::{mock_dependencies, mock_env};
     use cosmwasm_std::{Addr, Uint128};
 
+    #[test]
+    fn list_query_options() {
+        assert_eq!(order(None), Order::Ascending);
+        assert_eq!(order(Some(false)), Order::Ascending);
+        assert_eq!(order(Some(true)), Order::Descending);
+
+        assert_eq!(clamp_limit(None), DEFAULT_LIMIT as usize);
+        assert_eq!(clamp_limit(Some(5)), 5);
+        assert_eq!(clamp_limit(Some(MAX_LIMIT + 1)), MAX_LIMIT as usize);
+        assert_eq!(clamp_limit_u64(None), DEFAULT_LIMIT as usize);
+        assert_eq!(clamp_limit_u64(Some(u64::MAX)), MAX_LIMIT as usize);
+
+        let cursor = encode_cursor(&(7u64, "voter".to_string())).unwrap();
+        let key: (u64, String) = decode_cursor(&cursor).unwrap();
+        assert_eq!(key, (7, "voter".to_string()));
+        decode_cursor::<(u64, String)>(&Binary::from(b"7")).unwrap_err();
+
+        let mut deps = mock_dependencies();
+        let map: Map<u64, u64> = Map::new("items");
+        for num in 1..5 {
+            map.save(&mut deps.storage, num, &num).unwrap();
+        }
+        let keys = |start_after: Option<u64>, order: Order| -> Vec<u64> {
+            let (min, max) = range_bounds(start_after, order);
+            map.keys(&deps.storage, min, max, order)
+                .collect::<StdResult<_>>()
+                .unwrap()
+        };
+        assert_eq!(keys(Some(2), Order::Ascending), vec![3, 4]);
+        assert_eq!(keys(Some(3), Order::Descending), vec![2, 1]);
+        assert_eq!(keys(None, Order::Descending), vec![4, 3, 2, 1]);
+    }
+
     #[test]
     fn pagination() {
         let mut deps = mock_dependencies();
@@ -1,14 +1,22 @@
 use cosmwasm_schema::cw_serde;
 use cw4::MemberChangedHookMsg;
 
+use crate::escalation::EscalationHookMsg;
+use crate::lifecycle::DaoLifecycleHookMsg;
 use crate::nft_stake::NftStakeChangedHookMsg;
+use crate::outcome::ProposalOutcomeHookMsg;
 use crate::proposal::ProposalHookMsg;
 use crate::stake::StakeChangedHookMsg;
+use crate::submission::SubmissionCreatedHookMsg;
 use crate::vote::VoteHookMsg;
 
 /// An enum representing all possible DAO hooks.
 #[cw_serde]
 pub enum DaoHooks {
+    /// Called when a DAO's SubDAOs or admin change.
+    DaoLifecycleHook(DaoLifecycleHookMsg),
+    /// Called when a proposal is escalated to a council.
+    EscalationHook(EscalationHookMsg),
     /// Called when a member is added or removed
     /// to a cw4-groups or cw721-roles contract.
     MemberChangedHook(MemberChangedHookMsg),
@@ -16,8 +24,12 @@ pub enum DaoHooks {
     NftStakeChangeHook(NftStakeChangedHookMsg),
     /// Called when a proposal status changes.
     ProposalHook(ProposalHookMsg),
+    /// Called when a proposal passes.
+    ProposalOutcomeHook(ProposalOutcomeHookMsg),
     /// Called when tokens are staked or unstaked.
     StakeChangeHook(StakeChangedHookMsg),
+    /// Called when a proposal is submitted to a pre-propose module.
+    SubmissionCreatedHook(SubmissionCreatedHookMsg),
     /// Called when a vote is cast.
     VoteHook(VoteHookMsg),
 }
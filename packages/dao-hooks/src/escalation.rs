@@ -0,0 +1,27 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{to_json_binary, Addr, StdResult, WasmMsg};
+
+/// Sent to a council contract by a proposal module when a proposal
+/// is escalated to it for a decision.
+#[cw_serde]
+pub enum EscalationHookMsg {
+    /// Fired when a critical proposal has not reached quorum by the
+    /// middle of its voting period.
+    ProposalEscalated { id: u64 },
+}
+
+/// Prepares a message notifying COUNCIL of an escalation. Unlike
+/// other hooks, this message does not reply on error, so a council
+/// that fails to handle the hook causes the escalation to fail.
+pub fn proposal_escalated_hook(council: &Addr, msg: EscalationHookMsg) -> StdResult<WasmMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: council.to_string(),
+        msg: to_json_binary(&EscalationHookExecuteMsg::EscalationHook(msg))?,
+        funds: vec![],
+    })
+}
+
+#[cw_serde]
+pub enum EscalationHookExecuteMsg {
+    EscalationHook(EscalationHookMsg),
+}
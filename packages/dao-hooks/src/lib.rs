@@ -1,9 +1,13 @@
 #![doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/README.md"))]
 
 mod all_hooks;
+pub mod escalation;
+pub mod lifecycle;
 pub mod nft_stake;
+pub mod outcome;
 pub mod proposal;
 pub mod stake;
+pub mod submission;
 pub mod vote;
 
 pub use all_hooks::DaoHooks;
@@ -0,0 +1,50 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{to_json_binary, Addr, StdResult, Storage, SubMsg, WasmMsg};
+use cw_hooks::Hooks;
+
+/// An enum representing DAO lifecycle hook messages, fired when a
+/// DAO's SubDAOs or admin change.
+#[cw_serde]
+pub enum DaoLifecycleHookMsg {
+    /// Fired when SubDAOs are registered or removed. `added`
+    /// includes SubDAOs whose charter was updated.
+    SubDaosUpdated { added: Vec<Addr>, removed: Vec<Addr> },
+    /// Fired when a new admin is nominated.
+    AdminNominated { nominee: Addr },
+    /// Fired when an admin nomination is withdrawn.
+    AdminNominationWithdrawn { nominee: Addr },
+    /// Fired when the DAO's admin changes, either because a
+    /// nomination was accepted or because the admin returned control
+    /// to the DAO itself.
+    AdminChanged { old_admin: Addr, new_admin: Addr },
+}
+
+/// Prepares lifecycle hook messages. These messages reply on error
+/// with a reply ID of `reply_id_offset` plus the index of the hook
+/// receiver so that the caller may remove receivers that fail rather
+/// than letting them block governance.
+pub fn dao_lifecycle_hooks(
+    hooks: Hooks,
+    storage: &dyn Storage,
+    msg: DaoLifecycleHookMsg,
+    reply_id_offset: u64,
+) -> StdResult<Vec<SubMsg>> {
+    let msg = to_json_binary(&DaoLifecycleHookExecuteMsg::DaoLifecycleHook(msg))?;
+
+    let mut index: u64 = 0;
+    hooks.prepare_hooks(storage, |a| {
+        let execute = WasmMsg::Execute {
+            contract_addr: a.to_string(),
+            msg: msg.clone(),
+            funds: vec![],
+        };
+        let tmp = SubMsg::reply_on_error(execute, reply_id_offset + index);
+        index += 1;
+        Ok(tmp)
+    })
+}
+
+#[cw_serde]
+pub enum DaoLifecycleHookExecuteMsg {
+    DaoLifecycleHook(DaoLifecycleHookMsg),
+}
@@ -1,24 +1,39 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{to_json_binary, Addr, StdResult, Storage, SubMsg, WasmMsg};
+use cosmwasm_std::{to_json_binary, Addr, StdResult, Storage, SubMsg, Uint128, WasmMsg};
 use cw_hooks::Hooks;
 
-/// An enum representing NFT staking hooks.
+/// An enum representing NFT staking hooks. `power` is the staker's
+/// voting power after the change.
 #[cw_serde]
 pub enum NftStakeChangedHookMsg {
-    Stake { addr: Addr, token_id: String },
-    Unstake { addr: Addr, token_ids: Vec<String> },
+    Stake {
+        addr: Addr,
+        token_id: String,
+        power: Uint128,
+    },
+    Unstake {
+        addr: Addr,
+        token_ids: Vec<String>,
+        power: Uint128,
+    },
 }
 
 /// Prepares NftStakeChangedHookMsg::Stake hook SubMsgs,
-/// containing the address and the token_id staked.
+/// containing the address, the token_id staked, and the address's
+/// resulting voting power.
 pub fn stake_nft_hook_msgs(
     hooks: Hooks,
     storage: &dyn Storage,
     addr: Addr,
     token_id: String,
+    power: Uint128,
 ) -> StdResult<Vec<SubMsg>> {
     let msg = to_json_binary(&NftStakeChangedExecuteMsg::NftStakeChangeHook(
-        NftStakeChangedHookMsg::Stake { addr, token_id },
+        NftStakeChangedHookMsg::Stake {
+            addr,
+            token_id,
+            power,
+        },
     ))?;
     hooks.prepare_hooks(storage, |a| {
         let execute = WasmMsg::Execute {
@@ -31,15 +46,21 @@ pub fn stake_nft_hook_msgs(
 }
 
 /// Prepares NftStakeChangedHookMsg::Unstake hook SubMsgs,
-/// containing the address and the token_ids unstaked.
+/// containing the address, the token_ids unstaked, and the address's
+/// resulting voting power.
 pub fn unstake_nft_hook_msgs(
     hooks: Hooks,
     storage: &dyn Storage,
     addr: Addr,
     token_ids: Vec<String>,
+    power: Uint128,
 ) -> StdResult<Vec<SubMsg>> {
     let msg = to_json_binary(&NftStakeChangedExecuteMsg::NftStakeChangeHook(
-        NftStakeChangedHookMsg::Unstake { addr, token_ids },
+        NftStakeChangedHookMsg::Unstake {
+            addr,
+            token_ids,
+            power,
+        },
     ))?;
 
     hooks.prepare_hooks(storage, |a| {
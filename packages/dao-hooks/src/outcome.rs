@@ -0,0 +1,54 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{to_json_binary, StdResult, Storage, SubMsg, Uint128, WasmMsg};
+use cw_hooks::Hooks;
+use dao_voting::reply::mask_outcome_hook_index;
+
+/// An enum representing proposal outcome hook messages. Outcome
+/// hooks are intended for third-party contracts that act on the
+/// result of a vote, and so carry the final tally along with the
+/// outcome.
+#[cw_serde]
+pub enum ProposalOutcomeHookMsg {
+    /// Fired once when a proposal passes.
+    ProposalPassed {
+        id: u64,
+        yes: Uint128,
+        no: Uint128,
+        abstain: Uint128,
+        /// The total voting power of the DAO at `height`.
+        total_power: Uint128,
+        /// The height voting power was measured at.
+        height: u64,
+    },
+}
+
+/// Prepares proposal passed hook messages. These messages reply on
+/// error so that a failing hook receiver is removed rather than
+/// blocking the vote or execution that caused the proposal to pass.
+pub fn proposal_passed_hooks(
+    hooks: Hooks,
+    storage: &dyn Storage,
+    msg: ProposalOutcomeHookMsg,
+) -> StdResult<Vec<SubMsg>> {
+    let msg = to_json_binary(&ProposalOutcomeHookExecuteMsg::ProposalOutcomeHook(msg))?;
+
+    let mut index: u64 = 0;
+    let messages = hooks.prepare_hooks(storage, |a| {
+        let execute = WasmMsg::Execute {
+            contract_addr: a.to_string(),
+            msg: msg.clone(),
+            funds: vec![],
+        };
+        let masked_index = mask_outcome_hook_index(index);
+        let tmp = SubMsg::reply_on_error(execute, masked_index);
+        index += 1;
+        Ok(tmp)
+    })?;
+
+    Ok(messages)
+}
+
+#[cw_serde]
+pub enum ProposalOutcomeHookExecuteMsg {
+    ProposalOutcomeHook(ProposalOutcomeHookMsg),
+}
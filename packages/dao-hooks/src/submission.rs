@@ -0,0 +1,49 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{to_json_binary, Addr, StdResult, Storage, SubMsg, WasmMsg};
+use cw_hooks::Hooks;
+
+/// Sent to submission hook receivers whenever a pre-propose module
+/// receives a proposal submission.
+#[cw_serde]
+pub struct SubmissionCreatedHookMsg {
+    /// The address that made the submission.
+    pub proposer: Addr,
+    /// The proposal module that the submission is for.
+    pub proposal_module: Addr,
+    /// If `pending`, the ID the pre-propose module uses to identify
+    /// the submission while it awaits approval. Otherwise, the ID of
+    /// the proposal created in the proposal module.
+    pub id: u64,
+    /// True if the submission is awaiting approval and has not yet
+    /// created a proposal.
+    pub pending: bool,
+}
+
+/// Prepares submission created hook messages. These messages reply
+/// on error with the index of the hook receiver as the reply ID, so
+/// that a failing receiver may be removed rather than blocking
+/// submissions.
+pub fn submission_created_hooks(
+    hooks: &Hooks,
+    storage: &dyn Storage,
+    msg: SubmissionCreatedHookMsg,
+) -> StdResult<Vec<SubMsg>> {
+    let msg = to_json_binary(&SubmissionHookExecuteMsg::SubmissionCreatedHook(msg))?;
+
+    let mut index: u64 = 0;
+    hooks.prepare_hooks(storage, |a| {
+        let execute = WasmMsg::Execute {
+            contract_addr: a.to_string(),
+            msg: msg.clone(),
+            funds: vec![],
+        };
+        let tmp = SubMsg::reply_on_error(execute, index);
+        index += 1;
+        Ok(tmp)
+    })
+}
+
+#[cw_serde]
+pub enum SubmissionHookExecuteMsg {
+    SubmissionCreatedHook(SubmissionCreatedHookMsg),
+}
@@ -0,0 +1,23 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Uint128;
+
+/// The query interface implemented by activity oracles. An activity
+/// oracle tracks which of a DAO's members are inactive, for example
+/// because they have not voted in some time, so that proposal
+/// modules may exclude their voting power when computing quorum.
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum ActivityOracleQuery {
+    /// Returns the total voting power held by addresses flagged as
+    /// inactive at a given block height.
+    #[returns(InactivePowerAtHeightResponse)]
+    InactivePowerAtHeight {
+        height: ::std::option::Option<::std::primitive::u64>,
+    },
+}
+
+#[cw_serde]
+pub struct InactivePowerAtHeightResponse {
+    pub power: Uint128,
+    pub height: u64,
+}
@@ -1,10 +1,15 @@
 #![doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/README.md"))]
 
+pub mod activity;
 pub mod migrate_msg;
 pub mod msg;
 pub mod nft;
 pub mod proposal;
 pub mod query;
+pub mod receive;
+pub mod registry;
+pub mod remote;
 pub mod state;
 pub mod token;
+pub mod vault;
 pub mod voting;
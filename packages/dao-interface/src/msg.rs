@@ -1,9 +1,12 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{CosmosMsg, Empty};
+use cosmwasm_std::{CosmosMsg, Decimal, Empty, Uint128};
 use cw_utils::Duration;
 
-use crate::state::Config;
-use crate::{migrate_msg::MigrateParams, query::SubDao, state::ModuleInstantiateInfo};
+use crate::state::{CircuitBreaker, Config, ManagedContractRegistration, MergeRole, TreasuryTiers};
+use crate::{
+    migrate_msg::MigrateParams, query::SubDao, receive::ReceiveHandler, remote::RemoteCommand,
+    state::ModuleInstantiateInfo, vault::VaultRegistration,
+};
 
 /// Information about an item to be stored in the items list.
 #[cw_serde]
@@ -52,6 +55,22 @@ pub struct InstantiateMsg {
     pub dao_uri: Option<String>,
 }
 
+/// A proposal hook message, as sent by proposal modules to consumers
+/// of their proposal hooks. This mirrors `ProposalHookMsg` in the
+/// `dao-hooks` package, which depends on this package.
+#[cw_serde]
+pub enum ProposalHookMsg {
+    NewProposal {
+        id: u64,
+        proposer: String,
+    },
+    ProposalStatusChanged {
+        id: u64,
+        old_status: String,
+        new_status: String,
+    },
+}
+
 #[cw_serde]
 pub enum ExecuteMsg {
     /// Callable by the Admin, if one is configured.
@@ -65,7 +84,8 @@ pub enum ExecuteMsg {
     Pause { duration: Duration },
     /// Executed when the contract receives a cw20 token. Depending on
     /// the contract's configuration the contract will automatically
-    /// add the token to its treasury.
+    /// add the token to its treasury. If the message is a memo for a
+    /// route with a registered handler, the handler is notified.
     Receive(cw20::Cw20ReceiveMsg),
     /// Executed when the contract receives a cw721 token. Depending
     /// on the contract's configuration the contract will
@@ -101,16 +121,125 @@ pub enum ExecuteMsg {
     /// Callable by the core contract. Replaces the current
     /// governance contract config with the provided config.
     UpdateConfig { config: Config },
+    /// Callable by the core contract. Restores the governance
+    /// contract config to the config that was in effect at the end
+    /// of block `height`. Only the config is restored; treasury,
+    /// modules, and items are left unchanged.
+    RollbackConfig { height: u64 },
     /// Updates the list of cw20 tokens this contract has registered.
     UpdateCw20List {
         to_add: Vec<String>,
         to_remove: Vec<String>,
     },
+    /// Callable by anyone. Updates the cached balance of registered
+    /// cw20 tokens from the tokens' balance queries. Useful for
+    /// accounting for tokens transferred to the DAO without a
+    /// `Send`, or spent via an allowance.
+    RefreshCw20Balances {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
     /// Updates the list of cw721 tokens this contract has registered.
     UpdateCw721List {
         to_add: Vec<String>,
         to_remove: Vec<String>,
     },
+    /// Callable by the core contract. Freezes and unfreezes
+    /// contracts. Proposals with messages that execute, migrate, or
+    /// change the admin of a frozen contract fail to execute. Freezing
+    /// a cw20 token prevents proposals from spending it.
+    UpdateFrozenContracts {
+        to_add: Vec<String>,
+        to_remove: Vec<String>,
+    },
+    /// Callable by the core contract. Freezes and unfreezes native
+    /// denoms. Proposals with messages that send, burn, or attach
+    /// funds in a frozen denom fail to execute.
+    UpdateFrozenDenoms {
+        to_add: Vec<String>,
+        to_remove: Vec<String>,
+    },
+    /// Callable by the core contract. Registers and removes yield
+    /// vaults that treasury funds may be deposited into. Vaults must
+    /// be emptied before they are removed.
+    UpdateVaults {
+        to_add: Vec<VaultRegistration>,
+        to_remove: Vec<String>,
+    },
+    /// Callable by the core contract. Sets the largest share of the
+    /// DAO's holdings of a denom that may be deposited in vaults.
+    UpdateVaultAllocationLimit { limit: Decimal },
+    /// Callable by the core contract. Deposits `amount` of a
+    /// registered vault's denom into the vault.
+    DepositToVault { vault: String, amount: Uint128 },
+    /// Callable by the core contract. Withdraws `amount` of a
+    /// registered vault's denom from the vault.
+    WithdrawFromVault { vault: String, amount: Uint128 },
+    /// Callable by the core contract. Sets or removes the bounds on
+    /// treasury outflows and proposal executions that trip the
+    /// circuit breaker.
+    UpdateCircuitBreaker {
+        circuit_breaker: Option<CircuitBreaker>,
+    },
+    /// Callable by the core contract. Resets a tripped circuit
+    /// breaker and clears recorded outflows, allowing proposals to
+    /// move treasury funds again.
+    ResetCircuitBreaker {},
+    /// Callable by the core contract. Adds and removes entries in the
+    /// registry of contracts the DAO administers. Contracts that
+    /// proposals instantiate with the DAO as admin are added
+    /// automatically.
+    UpdateManagedContracts {
+        to_add: Vec<ManagedContractRegistration>,
+        to_remove: Vec<String>,
+    },
+    /// Callable by the core contract. Registers and removes the
+    /// contracts that handle cw20 tokens received with a memo, by
+    /// route.
+    UpdateReceiveHandlers {
+        to_add: Vec<ReceiveHandler>,
+        to_remove: Vec<String>,
+    },
+    /// Callable by the core contract. Adds and removes the addresses
+    /// that may send remote commands other than `Fund`. These are the
+    /// addresses ibc-hooks derives for members sending transfers from
+    /// other chains.
+    UpdateRemoteMembers {
+        to_add: Vec<String>,
+        to_remove: Vec<String>,
+    },
+    /// Executes a command sent from another chain by way of
+    /// ibc-hooks, with the transferred funds attached.
+    RemoteCommand { command: RemoteCommand },
+    /// Callable by the core contract. Proposes merging with another
+    /// DAO. The merge begins once the other DAO proposes the mirrored
+    /// merge, naming this DAO as its counterparty with the opposite
+    /// role.
+    ProposeMerge {
+        counterparty: String,
+        role: MergeRole,
+    },
+    /// Callable by the core contract. Withdraws a proposed merge that
+    /// has not yet begun on either side.
+    CancelMerge {},
+    /// Performs the next step of the merge, once the counterparty has
+    /// proposed the mirrored merge. Callable by anyone.
+    AdvanceMerge {},
+    /// Callable by the core contract. Moves a begun merge past its
+    /// current step without performing it, for steps that cannot
+    /// complete, e.g. because an NFT transfer fails. Assets the step
+    /// would have moved stay with this DAO.
+    SkipMergeStep {},
+    /// Callable by the core contract. Records code IDs that the DAO
+    /// would like pinned in, or unpinned from, the chain's wasm
+    /// cache, which reduces the gas cost of executing contracts with
+    /// those code IDs. Pinning is a privileged operation, so no
+    /// messages are sent; the list is for chain governance to act
+    /// on.
+    UpdatePinnedCodes {
+        to_add: Vec<u64>,
+        to_remove: Vec<u64>,
+    },
     /// Updates the governance contract's governance modules. Module
     /// instantiate info in `to_add` is used to create new modules and
     /// install them.
@@ -129,6 +258,46 @@ pub enum ExecuteMsg {
         to_add: Vec<SubDao>,
         to_remove: Vec<String>,
     },
+    /// Callable by the DAO's proposal modules. Assigns new proposals
+    /// an index that is unique across all of the DAO's proposal
+    /// modules. A proposal module only sends this message if the DAO
+    /// has been added as a consumer of its proposal hooks.
+    ProposalHook(ProposalHookMsg),
+    /// Callable by the core contract. Adds an address as a consumer
+    /// of DAO lifecycle hooks, which are executed whenever SubDAOs
+    /// are added or removed and whenever the admin is nominated or
+    /// changes. If a consumer errors when handling a hook message it
+    /// will be removed from the list of consumers.
+    AddLifecycleHook { address: String },
+    /// Callable by the core contract. Removes a consumer of DAO
+    /// lifecycle hooks.
+    RemoveLifecycleHook { address: String },
+    /// Callable by the core contract. Sets the registrar that the
+    /// DAO's official code IDs are synced from and syncs them, or
+    /// removes the registrar and the official code IDs if `None`. The
+    /// registrar's admin must be the core contract. While a registrar
+    /// is set, modules may only be installed, and contracts
+    /// instantiated or migrated by proposal, with official code IDs.
+    UpdateCodeIdRegistrar { registrar: Option<String> },
+    /// Replaces the DAO's official code IDs with those currently
+    /// listed by its registrar. Callable by the core contract or the
+    /// registrar.
+    SyncCodeIds {},
+    /// Callable by the core contract. Sets or removes the DAO's
+    /// treasury tiers. While tiers are set, proposals may not execute
+    /// this message directly; it must be part of a confirmed cold
+    /// spend.
+    UpdateTreasuryTiers { tiers: Option<TreasuryTiers> },
+    /// Callable by the core contract. Requests that MSGS be executed
+    /// outside of the hot budget once the cold delay has passed and a
+    /// proposal executes `ConfirmColdSpend`.
+    RequestColdSpend { msgs: Vec<CosmosMsg<Empty>> },
+    /// Callable by the core contract. Executes a requested cold spend
+    /// whose delay has passed.
+    ConfirmColdSpend { id: u64 },
+    /// Callable by the core contract. Removes a requested cold spend
+    /// without executing it.
+    CancelColdSpend { id: u64 },
 }
 
 #[cw_serde]
@@ -143,6 +312,11 @@ pub enum QueryMsg {
     /// Gets the contract's config.
     #[returns(Config)]
     Config {},
+    /// Gets the contract's config as of the end of block
+    /// `height`. Returns `None` if no config was recorded at or
+    /// before that height.
+    #[returns(Option<Config>)]
+    ConfigAtHeight { height: u64 },
     /// Gets the token balance for each cw20 registered with the
     /// contract.
     #[returns(crate::query::Cw20BalanceResponse)]
@@ -150,19 +324,90 @@ pub enum QueryMsg {
         start_after: Option<String>,
         limit: Option<u32>,
     },
+    /// Gets the cached token balance for each cw20 registered with
+    /// the contract, and the height at which it was last updated.
+    /// Unlike `Cw20Balances`, this does not query each token.
+    #[returns(Vec<crate::query::CachedCw20BalanceResponse>)]
+    CachedCw20Balances {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
     /// Lists the addresses of the cw20 tokens in this contract's
-    /// treasury.
+    /// treasury, in descending order unless DESCENDING is set to
+    /// false.
     #[returns(Vec<cosmwasm_std::Addr>)]
     Cw20TokenList {
         start_after: Option<String>,
         limit: Option<u32>,
+        descending: Option<bool>,
     },
     /// Lists the addresses of the cw721 tokens in this contract's
-    /// treasury.
+    /// treasury, in descending order unless DESCENDING is set to
+    /// false.
     #[returns(Vec<cosmwasm_std::Addr>)]
     Cw721TokenList {
         start_after: Option<String>,
         limit: Option<u32>,
+        descending: Option<bool>,
+    },
+    /// Lists the contracts that proposals may not interact with.
+    #[returns(Vec<cosmwasm_std::Addr>)]
+    FrozenContracts {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Lists the native denoms that proposals may not spend.
+    #[returns(Vec<String>)]
+    FrozenDenoms {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Lists the registered yield vaults and the amount deposited in
+    /// each.
+    #[returns(Vec<crate::query::VaultResponse>)]
+    Vaults {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Gets the DAO's position in a registered yield vault.
+    #[returns(crate::query::VaultPositionResponse)]
+    VaultPosition { vault: String },
+    /// Gets the largest share of the DAO's holdings of a denom that
+    /// may be deposited in vaults.
+    #[returns(Decimal)]
+    VaultAllocationLimit {},
+    /// Gets the circuit breaker's bounds, whether it has tripped,
+    /// and the treasury outflows in the current window.
+    #[returns(crate::query::CircuitBreakerResponse)]
+    CircuitBreaker {},
+    /// Lists the contracts the DAO administers.
+    #[returns(Vec<crate::query::ManagedContractResponse>)]
+    ManagedContracts {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Lists the handlers for cw20 tokens received with a memo.
+    #[returns(Vec<crate::receive::ReceiveHandler>)]
+    ReceiveHandlers {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Lists the addresses that may send remote commands other than
+    /// `Fund`.
+    #[returns(Vec<cosmwasm_std::Addr>)]
+    RemoteMembers {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Gets the DAO's merge with another DAO, if one has been
+    /// proposed.
+    #[returns(Option<crate::state::Merge>)]
+    Merge {},
+    /// Lists the code IDs that this contract would like pinned.
+    #[returns(Vec<u64>)]
+    PinnedCodes {
+        start_after: Option<u64>,
+        limit: Option<u32>,
     },
     /// Dumps all of the core contract's state in a single
     /// query. Useful for frontends as performance for queries is more
@@ -176,27 +421,42 @@ pub enum QueryMsg {
     /// example, given the items `{ "group": "foo", "subdao": "bar"}`
     /// this query would return `[("group", "foo"), ("subdao",
     /// "bar")]`.
+    ///
+    /// Items are listed in descending order unless DESCENDING is
+    /// set to false.
     #[returns(Vec<String>)]
     ListItems {
         start_after: Option<String>,
         limit: Option<u32>,
+        descending: Option<bool>,
     },
     /// Returns contract version info
     #[returns(crate::voting::InfoResponse)]
     Info {},
     /// Gets all proposal modules associated with the
-    /// contract.
+    /// contract, in ascending order unless DESCENDING is set.
     #[returns(Vec<crate::state::ProposalModule>)]
     ProposalModules {
         start_after: Option<String>,
         limit: Option<u32>,
+        descending: Option<bool>,
     },
     /// Gets the active proposal modules associated with the
-    /// contract.
+    /// contract, in ascending order unless DESCENDING is set.
     #[returns(Vec<crate::state::ProposalModule>)]
     ActiveProposalModules {
         start_after: Option<String>,
         limit: Option<u32>,
+        descending: Option<bool>,
+    },
+    /// Gets the proposal modules associated with the contract as of
+    /// the end of block `height`, with the status each had then.
+    /// Modules added after `height` are omitted.
+    #[returns(Vec<crate::state::ProposalModule>)]
+    ProposalModulesAtHeight {
+        height: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
     },
     /// Gets the number of active and total proposal modules
     /// registered with this module.
@@ -210,11 +470,25 @@ pub enum QueryMsg {
     VotingModule {},
     /// Returns all SubDAOs with their charters in a vec.
     /// start_after is bound exclusive and asks for a string address.
+    /// SubDAOs are listed in ascending order unless DESCENDING is set.
     #[returns(Vec<crate::query::SubDao>)]
     ListSubDaos {
         start_after: Option<String>,
         limit: Option<u32>,
+        descending: Option<bool>,
     },
+    /// Gets the global index of a proposal given the prefix of its
+    /// proposal module and its ID in that module. Only proposals
+    /// created while the DAO was a consumer of the module's proposal
+    /// hooks are indexed.
+    #[returns(crate::query::GlobalProposalResponse)]
+    ProposalGlobal { module_prefix: String, id: u64 },
+    /// Gets a proposal's module and ID given its global index.
+    #[returns(crate::query::GlobalProposalResponse)]
+    ProposalByGlobalId { global_id: u64 },
+    /// Gets the consumers of DAO lifecycle hooks.
+    #[returns(::cw_hooks::HooksResponse)]
+    LifecycleHooks {},
     /// Implements the DAO Star standard: <https://daostar.one/EIP>
     #[returns(crate::query::DaoURIResponse)]
     DaoURI {},
@@ -227,6 +501,34 @@ pub enum QueryMsg {
     /// Returns the total voting power at a given block height.
     #[returns(crate::voting::TotalPowerAtHeightResponse)]
     TotalPowerAtHeight { height: Option<u64> },
+    /// Checks a list of messages for problems that would cause them
+    /// to fail if executed by the DAO, without executing them. This
+    /// lets frontends catch doomed proposals before they are
+    /// submitted. An empty list of problems does not guarantee that
+    /// execution will succeed.
+    #[returns(crate::query::DryRunResponse)]
+    DryRunMsgs { msgs: Vec<CosmosMsg<Empty>> },
+    /// Gets the registrar that the DAO's official code IDs are synced
+    /// from, if any.
+    #[returns(Option<cosmwasm_std::Addr>)]
+    CodeIdRegistrar {},
+    /// Lists the DAO's official code IDs, in order of code ID.
+    #[returns(Vec<crate::registry::OfficialCodeId>)]
+    OfficialCodeIds {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Gets the DAO's treasury tiers, if set, and the hot budget spent
+    /// in the current period.
+    #[returns(crate::query::TreasuryTiersResponse)]
+    TreasuryTiers {},
+    /// Lists the cold spends awaiting confirmation, in the order they
+    /// were requested.
+    #[returns(Vec<crate::query::ColdSpendResponse>)]
+    ColdSpends {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
 }
 
 #[allow(clippy::large_enum_variant)]
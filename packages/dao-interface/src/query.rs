@@ -1,9 +1,12 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Coin, Uint128};
 use cw2::ContractVersion;
+use cw20::Cw20Coin;
 use cw_utils::Expiration;
 
-use crate::state::{Config, ProposalModule};
+use crate::state::{
+    CircuitBreaker, CircuitBreakerTrip, ColdSpend, Config, ProposalModule, TreasuryTiers,
+};
 
 /// Relevant state for the governance module. Returned by the
 /// `DumpState` query.
@@ -52,6 +55,31 @@ pub struct Cw20BalanceResponse {
     pub balance: Uint128,
 }
 
+/// Returned by the `CachedCw20Balances` query.
+#[cw_serde]
+pub struct CachedCw20BalanceResponse {
+    /// The address of the token.
+    pub addr: Addr,
+    /// The contract's balance as of `height`.
+    pub balance: Uint128,
+    /// The block height at which the cached balance was last
+    /// updated.
+    pub height: u64,
+}
+
+/// Returned by the `ProposalGlobal` and `ProposalByGlobalId` queries.
+#[cw_serde]
+pub struct GlobalProposalResponse {
+    /// The proposal's index across all of the DAO's proposal modules.
+    pub global_id: u64,
+    /// The proposal module the proposal was created in.
+    pub module: Addr,
+    /// The prefix of the proposal module.
+    pub module_prefix: String,
+    /// The proposal's ID in its proposal module.
+    pub id: u64,
+}
+
 /// Returned by the `AdminNomination` query.
 #[cw_serde]
 pub struct AdminNominationResponse {
@@ -80,3 +108,122 @@ pub struct ProposalModuleCountResponse {
     /// The total number of proposal modules.
     pub total_proposal_module_count: u32,
 }
+
+/// A problem found by the `DryRunMsgs` query.
+#[cw_serde]
+pub enum DryRunIssue {
+    /// An address in the message is not a valid address.
+    InvalidAddress { address: String },
+    /// A coin in the message has a malformed denomination.
+    InvalidDenom { denom: String },
+    /// A coin in the message has an amount of zero.
+    ZeroAmount { denom: String },
+    /// This message, together with the messages before it, spends
+    /// more of a native or registered cw20 token than the DAO
+    /// holds. cw20 tokens are identified by their contract address.
+    InsufficientBalance {
+        denom: String,
+        required: Uint128,
+        available: Uint128,
+    },
+    /// The message executes or migrates an address which is not a
+    /// contract.
+    NotAContract { address: String },
+    /// The message transfers, sends, or burns a cw20 token which is
+    /// not in the DAO's cw20 token list, so its balance can not be
+    /// checked.
+    UnregisteredCw20 { address: String },
+    /// The messages are larger than a proposal may be.
+    TooLarge { size: u64, max: u64 },
+    /// The message interacts with a frozen contract or spends a
+    /// frozen denom, so the proposal would fail to execute.
+    Frozen { target: String },
+    /// The message moves treasury funds while the circuit breaker
+    /// is tripped, so the proposal would fail to execute.
+    CircuitBreakerTripped {},
+    /// The message migrates a contract to a code ID which is not one
+    /// of the DAO's official code IDs, so the proposal would fail to
+    /// execute.
+    UnofficialCodeId { code_id: u64 },
+}
+
+#[cw_serde]
+pub struct DryRunProblem {
+    /// The index of the message with the problem, or `None` if the
+    /// problem concerns the list of messages as a whole.
+    pub msg_index: Option<u32>,
+    pub issue: DryRunIssue,
+}
+
+/// Returned by the `DryRunMsgs` query.
+#[cw_serde]
+pub struct DryRunResponse {
+    pub problems: Vec<DryRunProblem>,
+}
+
+/// Returned by the `CircuitBreaker` query.
+#[cw_serde]
+pub struct CircuitBreakerResponse {
+    pub circuit_breaker: Option<CircuitBreaker>,
+    /// Set if the circuit breaker has tripped and not been reset.
+    pub tripped: Option<CircuitBreakerTrip>,
+    /// Native tokens that have left the treasury within the current
+    /// window.
+    pub outflows: Vec<Coin>,
+    /// Cw20 tokens that have left the treasury within the current
+    /// window.
+    pub cw20_outflows: Vec<Cw20Coin>,
+}
+
+/// Returned by the `TreasuryTiers` query.
+#[cw_serde]
+pub struct TreasuryTiersResponse {
+    pub tiers: Option<TreasuryTiers>,
+    /// Native tokens spent from the hot budget in the current period.
+    pub hot_spent: Vec<Coin>,
+    /// Cw20 tokens spent from the hot budget in the current period.
+    pub hot_cw20_spent: Vec<Cw20Coin>,
+    /// When the current period ends, if one has begun.
+    pub period_ends: Option<Expiration>,
+}
+
+/// A cold spend awaiting confirmation. Returned by the `ColdSpends`
+/// query.
+#[cw_serde]
+pub struct ColdSpendResponse {
+    pub id: u64,
+    pub spend: ColdSpend,
+}
+
+/// A contract the DAO administers. Returned by the
+/// `ManagedContracts` query.
+#[cw_serde]
+pub struct ManagedContractResponse {
+    pub address: Addr,
+    pub code_id: u64,
+    pub label: String,
+}
+
+/// A registered yield vault. Returned by the `Vaults` query.
+#[cw_serde]
+pub struct VaultResponse {
+    pub vault: Addr,
+    pub denom: String,
+    pub cap: Uint128,
+    /// The amount the DAO has deposited in the vault, less what it
+    /// has withdrawn.
+    pub deposited: Uint128,
+}
+
+/// Returned by the `VaultPosition` query.
+#[cw_serde]
+pub struct VaultPositionResponse {
+    pub vault: Addr,
+    pub denom: String,
+    /// The amount the DAO has deposited in the vault, less what it
+    /// has withdrawn.
+    pub deposited: Uint128,
+    /// The amount the DAO could currently withdraw, as reported by
+    /// the vault.
+    pub value: Uint128,
+}
@@ -0,0 +1,23 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Binary;
+
+/// A contract that handles cw20 tokens sent to the DAO with a memo
+/// for ROUTE. A memo is a JSON object with a single key, the route,
+/// for example `{"pay_invoice": 4}`.
+#[cw_serde]
+pub struct ReceiveHandler {
+    pub route: String,
+    pub address: String,
+}
+
+/// The message the DAO forwards cw20 tokens to a receive handler
+/// with. Handlers implement the cw20 receiver interface: the DAO
+/// `Send`s them the tokens with this as the message, so the token
+/// and amount are those of the `Receive` the handler gets.
+#[cw_serde]
+pub enum ReceiveHandlerMsg {
+    /// SENDER sent the tokens to the DAO with a memo for the
+    /// handler's route. MEMO is the whole memo, including the route
+    /// key. If the handler errors, the transfer to the DAO fails.
+    Cw20Received { sender: String, memo: Binary },
+}
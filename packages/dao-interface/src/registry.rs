@@ -0,0 +1,23 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+
+/// The query interface implemented by code ID registrars. A DAO with
+/// a registrar configured syncs its list of official code IDs from
+/// the registrar, and may only install modules and migrate contracts
+/// to code IDs on that list.
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum RegistrarQuery {
+    /// Lists the official code IDs.
+    #[returns(Vec<OfficialCodeId>)]
+    CodeIds {},
+}
+
+/// A code ID published by a registrar as the official code for a
+/// version of a type of module, for example version `2.4.0` of
+/// `dao-proposal-single`.
+#[cw_serde]
+pub struct OfficialCodeId {
+    pub module_type: String,
+    pub version: String,
+    pub code_id: u64,
+}
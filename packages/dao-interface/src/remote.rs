@@ -0,0 +1,16 @@
+use cosmwasm_schema::cw_serde;
+
+/// A command sent to the DAO from another chain with an ICS-20
+/// transfer. The transfer's memo names the DAO as the ibc-hooks
+/// contract and `ExecuteMsg::RemoteCommand` as the message, for
+/// example `{"wasm": {"contract": "<dao>", "msg": {"remote_command":
+/// {"command": {"fund": {}}}}}}`. The transferred funds are attached.
+/// If the command fails, the transfer is refunded.
+#[cw_serde]
+pub enum RemoteCommand {
+    /// Adds the transferred funds to the treasury. Callable by anyone.
+    Fund {},
+    /// Deposits the transferred funds of a registered vault's denom
+    /// into the vault. Callable by remote members.
+    DepositToVault { vault: String },
+}
@@ -1,5 +1,7 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Binary, Coin, CosmosMsg, WasmMsg};
+use cosmwasm_std::{Addr, Binary, Coin, CosmosMsg, Empty, WasmMsg};
+use cw20::Cw20Coin;
+use cw_utils::{Duration, Expiration};
 
 /// Top level config type for core module.
 #[cw_serde]
@@ -21,6 +23,119 @@ pub struct Config {
     pub dao_uri: Option<String>,
 }
 
+/// Bounds on the DAO's activity. If a proposal's execution exceeds
+/// them, the circuit breaker trips and proposals may not move funds
+/// out of the treasury until the DAO resets it.
+#[cw_serde]
+pub struct CircuitBreaker {
+    /// The rolling window over which treasury outflows are summed.
+    pub window: Duration,
+    /// The largest amount of each native denom that may leave the
+    /// treasury within a window. Denoms not listed are not bounded.
+    pub outflow_limits: Vec<Coin>,
+    /// The largest amount of each cw20 token that may leave the
+    /// treasury within a window. Tokens not listed are not bounded.
+    #[serde(default)]
+    pub cw20_outflow_limits: Vec<Cw20Coin>,
+    /// The largest number of proposals that may execute in one
+    /// block, if bounded.
+    pub max_executions_per_block: Option<u32>,
+}
+
+/// Why and when the circuit breaker tripped.
+#[cw_serde]
+pub struct CircuitBreakerTrip {
+    pub height: u64,
+    pub reason: String,
+}
+
+/// Splits the DAO's treasury into a hot budget, which
+/// proposals may spend each period, and a cold reserve, which may only
+/// be spent by a cold spend confirmed after a delay.
+#[cw_serde]
+pub struct TreasuryTiers {
+    /// The largest amount of each native denom that proposals may move
+    /// out of the treasury within a period. Denoms not listed may only
+    /// be spent cold.
+    pub hot_budget: Vec<Coin>,
+    /// The largest amount of each cw20 token that proposals may move
+    /// out of the treasury within a period. Tokens not listed may
+    /// only be spent cold.
+    #[serde(default)]
+    pub hot_cw20_budget: Vec<Cw20Coin>,
+    /// The period over which the hot budget is spent. The budget is
+    /// restored at the end of each period.
+    pub period: Duration,
+    /// How long after a cold spend is requested it may be confirmed.
+    pub cold_delay: Duration,
+    /// If set, only proposals from this proposal module may confirm
+    /// cold spends. Its proposals are not bound by the hot budget.
+    /// Typically a module with a higher passing threshold.
+    pub cold_module: Option<Addr>,
+}
+
+/// Messages requested by a proposal that are executed outside of the
+/// hot budget once a second proposal confirms them.
+#[cw_serde]
+pub struct ColdSpend {
+    pub msgs: Vec<CosmosMsg<Empty>>,
+    /// When the spend may first be confirmed.
+    pub confirmable_at: Expiration,
+}
+
+/// A contract the DAO administers.
+#[cw_serde]
+pub struct ManagedContract {
+    pub code_id: u64,
+    pub label: String,
+}
+
+/// A contract to record as administered by the DAO.
+#[cw_serde]
+pub struct ManagedContractRegistration {
+    pub address: String,
+    pub code_id: u64,
+    pub label: String,
+}
+
+/// This DAO's side of a merge.
+#[cw_serde]
+#[derive(Copy)]
+pub enum MergeRole {
+    /// This DAO receives the other DAO's assets and registrations.
+    Absorbing,
+    /// This DAO's assets and registrations move to the other DAO.
+    Absorbed,
+}
+
+/// The progress of a merge. The absorbed DAO moves through `Tokens`
+/// and `Nfts`, and the absorbing DAO through `Items` and `SubDaos`.
+#[cw_serde]
+#[derive(Copy)]
+pub enum MergeStep {
+    /// Waiting for the other DAO to propose the mirrored merge.
+    AwaitingCounterparty,
+    /// Native and cw20 balances are sent to the absorbing DAO.
+    Tokens,
+    /// NFTs are sent to the absorbing DAO, a batch per collection at
+    /// a time.
+    Nfts,
+    /// The absorbed DAO's items are copied to the absorbing DAO.
+    Items,
+    /// The absorbed DAO's SubDAOs and token registrations are added
+    /// to the absorbing DAO.
+    SubDaos,
+    Complete,
+}
+
+/// A merge of this DAO with another.
+#[cw_serde]
+pub struct Merge {
+    pub counterparty: Addr,
+    pub role: MergeRole,
+    pub step: MergeStep,
+}
+
 /// Top level type describing a proposal module.
 #[cw_serde]
 pub struct ProposalModule {
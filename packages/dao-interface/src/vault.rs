@@ -0,0 +1,44 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Uint128;
+
+/// A yield vault that the DAO may deposit treasury funds into, as
+/// registered with the DAO.
+#[cw_serde]
+pub struct Vault {
+    /// The native denom the vault accepts.
+    pub denom: String,
+    /// The most the DAO may have deposited in the vault at once.
+    pub cap: Uint128,
+}
+
+/// A vault to register with the DAO.
+#[cw_serde]
+pub struct VaultRegistration {
+    pub address: String,
+    pub denom: String,
+    pub cap: Uint128,
+}
+
+/// The execute interface implemented by yield vaults.
+#[cw_serde]
+pub enum VaultExecuteMsg {
+    /// Deposits the native funds sent with the message.
+    Deposit {},
+    /// Withdraws `amount` of the vault's denom to the sender.
+    Withdraw { amount: Uint128 },
+}
+
+/// The query interface implemented by yield vaults.
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum VaultQueryMsg {
+    /// Returns the amount of the vault's denom that `depositor` could
+    /// currently withdraw, including any yield earned.
+    #[returns(PositionResponse)]
+    Position { depositor: String },
+}
+
+#[cw_serde]
+pub struct PositionResponse {
+    pub amount: Uint128,
+}
@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{Coin, StdError};
 use cw_denom::DenomError;
 use cw_utils::ParseReplyError;
 use thiserror::Error;
@@ -32,6 +32,15 @@ pub enum PreProposeError {
     #[error("You must be a member of this DAO (have voting power) to create a proposal")]
     NotMember {},
 
+    #[error("invalid zero submission fee. set the fee to `None` to have no fee")]
+    ZeroSubmissionFee {},
+
+    #[error("Invalid payment for proposal deposit and submission fee. Expected ({expected:?})")]
+    InvalidPayment { expected: Vec<Coin> },
+
+    #[error("No deposit option at index ({option})")]
+    NoSuchDepositOption { option: u32 },
+
     #[error("No denomination for withdrawal. specify a denomination to withdraw")]
     NoWithdrawalDenom {},
 
@@ -44,6 +53,36 @@ pub enum PreProposeError {
     #[error("Proposal not found")]
     ProposalNotFound {},
 
+    #[error("Drafters may have at most ({max}) drafts awaiting sponsorship")]
+    TooManyDrafts { max: u64 },
+
+    #[error("Reputation curve must be non-empty with strictly increasing scores")]
+    InvalidReputationCurve {},
+
+    #[error("Co-authored proposals require a proposal deposit")]
+    DepositRequired {},
+
+    #[error("Co-authors must be distinct from each other and the proposer, with non-zero shares no greater in total than the deposit")]
+    InvalidCoAuthors {},
+
+    #[error("Sender has no unpaid share of this proposal's deposit")]
+    NoShareDue {},
+
+    #[error("Every share of the deposit must be paid before the proposal is submitted")]
+    SharesUnpaid {},
+
+    #[error("Review config must have at least one reviewer and a non-zero review period")]
+    InvalidReviewConfig {},
+
+    #[error("Reviews are not enabled")]
+    ReviewsDisabled {},
+
+    #[error("Only reviewers may review proposals")]
+    NotReviewer {},
+
+    #[error("Proposal ({id}) is already assigned to a reviewer")]
+    ReviewAlreadyAssigned { id: u64 },
+
     #[error("Unauthorized")]
     Unauthorized {},
 
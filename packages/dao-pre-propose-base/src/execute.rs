@@ -1,22 +1,24 @@
 use cosmwasm_schema::schemars::JsonSchema;
 use cosmwasm_std::{
-    to_json_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, SubMsg, WasmMsg,
+    to_json_binary, Addr, Binary, Coin, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Reply,
+    Response, StdResult, Storage, SubMsg, WasmMsg,
 };
 
 use cw2::set_contract_version;
 
-use cw_denom::UncheckedDenom;
+use cw_denom::{CheckedDenom, UncheckedDenom};
+use dao_hooks::submission::{submission_created_hooks, SubmissionCreatedHookMsg};
 use dao_interface::voting::{Query as CwCoreQuery, VotingPowerAtHeightResponse};
 use dao_voting::{
-    deposit::{DepositRefundPolicy, UncheckedDepositInfo},
+    deposit::{CheckedDepositInfo, DepositRefundPolicy, UncheckedDepositInfo},
     status::Status,
 };
 use serde::Serialize;
 
 use crate::{
     error::PreProposeError,
-    msg::{DepositInfoResponse, ExecuteMsg, InstantiateMsg, QueryMsg},
-    state::{Config, PreProposeContract},
+    msg::{DepositInfoResponse, ExecuteMsg, InstantiateMsg, QueryMsg, UncheckedSubmissionFee},
+    state::{Config, PreProposeContract, SubmissionFee},
 };
 
 const CONTRACT_NAME: &str = "crates.io::dao-pre-propose-base";
@@ -82,10 +84,20 @@ where
     ) -> Result<Response, PreProposeError> {
         match msg {
             ExecuteMsg::Propose { msg } => self.execute_propose(deps, env, info, msg),
+            ExecuteMsg::ProposeWithDepositOption { msg, option } => {
+                let deposit_info = self.deposit_option(deps.storage, option)?;
+                self.execute_propose_with_deposit(deps, env, info, msg, Some(deposit_info))
+            }
             ExecuteMsg::UpdateConfig {
                 deposit_info,
                 open_proposal_submission,
             } => self.execute_update_config(deps, info, deposit_info, open_proposal_submission),
+            ExecuteMsg::UpdateSubmissionFee { submission_fee } => {
+                self.execute_update_submission_fee(deps, info, submission_fee)
+            }
+            ExecuteMsg::UpdateDepositOptions { options } => {
+                self.execute_update_deposit_options(deps, info, options)
+            }
             ExecuteMsg::Withdraw { denom } => {
                 self.execute_withdraw(deps.as_ref(), env, info, denom)
             }
@@ -95,6 +107,12 @@ where
             ExecuteMsg::RemoveProposalSubmittedHook { address } => {
                 self.execute_remove_proposal_submitted_hook(deps, info, address)
             }
+            ExecuteMsg::AddSubmissionHook { address } => {
+                self.execute_add_submission_hook(deps, info, address)
+            }
+            ExecuteMsg::RemoveSubmissionHook { address } => {
+                self.execute_remove_submission_hook(deps, info, address)
+            }
             ExecuteMsg::ProposalCompletedHook {
                 proposal_id,
                 new_status,
@@ -111,16 +129,26 @@ where
         info: MessageInfo,
         msg: ProposalMessage,
     ) -> Result<Response, PreProposeError> {
-        self.check_can_submit(deps.as_ref(), info.sender.clone())?;
-
         let config = self.config.load(deps.storage)?;
+        self.execute_propose_with_deposit(deps, env, info, msg, config.deposit_info)
+    }
 
-        let deposit_messages = if let Some(ref deposit_info) = config.deposit_info {
-            deposit_info.check_native_deposit_paid(&info)?;
-            deposit_info.get_take_deposit_messages(&info.sender, &env.contract.address)?
-        } else {
-            vec![]
-        };
+    /// Creates a proposal as `execute_propose` does, but takes
+    /// DEPOSIT_INFO from the sender in place of the configured
+    /// deposit. Contracts that vary the deposit by proposer may call
+    /// this with the deposit they have derived.
+    pub fn execute_propose_with_deposit(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: ProposalMessage,
+        deposit_info: Option<CheckedDepositInfo>,
+    ) -> Result<Response, PreProposeError> {
+        self.check_can_submit(deps.as_ref(), info.sender.clone())?;
+
+        let deposit_messages =
+            self.take_deposit_and_fee(deps.as_ref(), &env, &info, deposit_info.as_ref())?;
 
         let proposal_module = self.proposal_module.load(deps.storage)?;
 
@@ -130,11 +158,11 @@ where
             &proposal_module,
             &dao_interface::proposal::Query::NextProposalId {},
         )?;
-        self.deposits.save(
-            deps.storage,
-            next_id,
-            &(config.deposit_info, info.sender.clone()),
-        )?;
+        self.deposits
+            .save(deps.storage, next_id, &(deposit_info, info.sender.clone()))?;
+
+        let submission_hooks =
+            self.submission_created_hooks(deps.storage, info.sender.clone(), next_id, false)?;
 
         let propose_messsage = WasmMsg::Execute {
             contract_addr: proposal_module.into_string(),
@@ -162,6 +190,7 @@ where
             // {}` query.
             .add_message(propose_messsage)
             .add_submessages(hooks_msgs)
+            .add_submessages(submission_hooks)
             .add_messages(deposit_messages))
     }
 
@@ -193,6 +222,71 @@ where
         }
     }
 
+    pub fn execute_update_submission_fee(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        submission_fee: Option<UncheckedSubmissionFee>,
+    ) -> Result<Response, PreProposeError> {
+        let dao = self.dao.load(deps.storage)?;
+        if info.sender != dao {
+            return Err(PreProposeError::NotDao {});
+        }
+
+        match submission_fee {
+            Some(fee) => {
+                let fee = fee.into_checked(deps.as_ref())?;
+                self.submission_fee.save(deps.storage, &fee)?;
+            }
+            None => self.submission_fee.remove(deps.storage),
+        }
+
+        Ok(Response::default()
+            .add_attribute("method", "update_submission_fee")
+            .add_attribute("sender", info.sender))
+    }
+
+    pub fn execute_update_deposit_options(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        options: Vec<UncheckedDepositInfo>,
+    ) -> Result<Response, PreProposeError> {
+        let dao = self.dao.load(deps.storage)?;
+        if info.sender != dao {
+            return Err(PreProposeError::NotDao {});
+        }
+
+        let options = options
+            .into_iter()
+            .map(|option| option.into_checked(deps.as_ref(), dao.clone()))
+            .collect::<Result<Vec<_>, _>>()?;
+        if options.is_empty() {
+            self.deposit_options.remove(deps.storage);
+        } else {
+            self.deposit_options.save(deps.storage, &options)?;
+        }
+
+        Ok(Response::default()
+            .add_attribute("method", "update_deposit_options")
+            .add_attribute("sender", info.sender)
+            .add_attribute("options", options.len().to_string()))
+    }
+
+    /// Loads the deposit option at index OPTION.
+    pub fn deposit_option(
+        &self,
+        storage: &dyn Storage,
+        option: u32,
+    ) -> Result<CheckedDepositInfo, PreProposeError> {
+        self.deposit_options
+            .may_load(storage)?
+            .unwrap_or_default()
+            .into_iter()
+            .nth(option as usize)
+            .ok_or(PreProposeError::NoSuchDepositOption { option })
+    }
+
     pub fn execute_withdraw(
         &self,
         deps: Deps,
@@ -268,6 +362,82 @@ where
         Ok(Response::default())
     }
 
+    pub fn execute_add_submission_hook(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        address: String,
+    ) -> Result<Response, PreProposeError> {
+        let dao = self.dao.load(deps.storage)?;
+        if info.sender != dao {
+            return Err(PreProposeError::NotDao {});
+        }
+
+        let addr = deps.api.addr_validate(&address)?;
+        self.submission_hooks.add_hook(deps.storage, addr.clone())?;
+
+        Ok(Response::default()
+            .add_attribute("method", "add_submission_hook")
+            .add_attribute("address", addr))
+    }
+
+    pub fn execute_remove_submission_hook(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        address: String,
+    ) -> Result<Response, PreProposeError> {
+        let dao = self.dao.load(deps.storage)?;
+        if info.sender != dao {
+            return Err(PreProposeError::NotDao {});
+        }
+
+        let addr = deps.api.addr_validate(&address)?;
+        self.submission_hooks
+            .remove_hook(deps.storage, addr.clone())?;
+
+        Ok(Response::default()
+            .add_attribute("method", "remove_submission_hook")
+            .add_attribute("address", addr))
+    }
+
+    /// Prepares messages notifying submission hook receivers of a
+    /// new submission. Contracts that accept submissions other than
+    /// through `execute_propose`, for example to await approval,
+    /// should call this so that receivers learn of them.
+    pub fn submission_created_hooks(
+        &self,
+        storage: &dyn Storage,
+        proposer: Addr,
+        id: u64,
+        pending: bool,
+    ) -> StdResult<Vec<SubMsg>> {
+        let proposal_module = self.proposal_module.load(storage)?;
+        submission_created_hooks(
+            &self.submission_hooks,
+            storage,
+            SubmissionCreatedHookMsg {
+                proposer,
+                proposal_module,
+                id,
+                pending,
+            },
+        )
+    }
+
+    /// Handles replies from failed submission hook messages by
+    /// removing the receiver that failed. Submission hook messages
+    /// are the only submessages that reply, with the index of their
+    /// receiver as the reply ID.
+    pub fn reply(&self, deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, PreProposeError> {
+        let addr = self
+            .submission_hooks
+            .remove_hook_by_index(deps.storage, msg.id)?;
+        Ok(Response::default()
+            .add_attribute("method", "remove_submission_hook")
+            .add_attribute("address", addr))
+    }
+
     pub fn execute_proposal_completed_hook(
         &self,
         deps: Deps,
@@ -328,6 +498,39 @@ where
         }
     }
 
+    /// Checks that the native tokens sent with a proposal pay for its
+    /// deposit and submission fee, and returns the messages that take
+    /// the fee and any cw20 deposit from the proposer.
+    pub fn take_deposit_and_fee(
+        &self,
+        deps: Deps,
+        env: &Env,
+        info: &MessageInfo,
+        deposit_info: Option<&CheckedDepositInfo>,
+    ) -> Result<Vec<CosmosMsg>, PreProposeError> {
+        let fee = self.submission_fee.may_load(deps.storage)?;
+
+        let mut messages = match deposit_info {
+            Some(deposit_info) => {
+                // With a fee, native payment for the deposit is
+                // checked along with the fee.
+                if fee.is_none() {
+                    deposit_info.check_native_deposit_paid(info)?;
+                }
+                deposit_info.get_take_deposit_messages(&info.sender, &env.contract.address)?
+            }
+            None => vec![],
+        };
+
+        if let Some(fee) = fee {
+            check_native_payment(info, deposit_info, &fee)?;
+            let dao = self.dao.load(deps.storage)?;
+            messages.extend(fee.get_take_fee_messages(&info.sender, &dao)?);
+        }
+
+        Ok(messages)
+    }
+
     pub fn check_can_submit(&self, deps: Deps, who: Addr) -> Result<(), PreProposeError> {
         let config = self.config.load(deps.storage)?;
 
@@ -359,10 +562,62 @@ where
                     proposer,
                 })
             }
+            QueryMsg::SubmissionFee {} => {
+                to_json_binary(&self.submission_fee.may_load(deps.storage)?)
+            }
+            QueryMsg::DepositOptions {} => to_json_binary(
+                &self
+                    .deposit_options
+                    .may_load(deps.storage)?
+                    .unwrap_or_default(),
+            ),
             QueryMsg::ProposalSubmittedHooks {} => {
                 to_json_binary(&self.proposal_submitted_hooks.query_hooks(deps)?)
             }
+            QueryMsg::SubmissionHooks {} => {
+                to_json_binary(&self.submission_hooks.query_hooks(deps)?)
+            }
             QueryMsg::QueryExtension { .. } => Ok(Binary::default()),
         }
     }
 }
+
+/// Checks that the native tokens sent in INFO are exactly those
+/// needed to pay the deposit and submission fee.
+fn check_native_payment(
+    info: &MessageInfo,
+    deposit_info: Option<&CheckedDepositInfo>,
+    fee: &SubmissionFee,
+) -> Result<(), PreProposeError> {
+    let mut expected: Vec<Coin> = vec![];
+    let required = deposit_info
+        .map(|d| (&d.denom, d.amount))
+        .into_iter()
+        .chain(std::iter::once((&fee.denom, fee.amount)));
+    for (denom, amount) in required {
+        if let CheckedDenom::Native(denom) = denom {
+            match expected.iter_mut().find(|c| &c.denom == denom) {
+                Some(coin) => coin.amount += amount,
+                None => expected.push(Coin {
+                    denom: denom.clone(),
+                    amount,
+                }),
+            }
+        }
+    }
+
+    let mut paid: Vec<Coin> = info
+        .funds
+        .iter()
+        .filter(|c| !c.amount.is_zero())
+        .cloned()
+        .collect();
+    paid.sort_by(|a, b| a.denom.cmp(&b.denom));
+    expected.sort_by(|a, b| a.denom.cmp(&b.denom));
+
+    if paid != expected {
+        Err(PreProposeError::InvalidPayment { expected })
+    } else {
+        Ok(())
+    }
+}
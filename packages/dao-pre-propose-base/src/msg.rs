@@ -1,10 +1,16 @@
 use cosmwasm_schema::{cw_serde, schemars::JsonSchema, QueryResponses};
+use cosmwasm_std::{Deps, Uint128};
 use cw_denom::UncheckedDenom;
 use dao_voting::{
     deposit::{CheckedDepositInfo, UncheckedDepositInfo},
     status::Status,
 };
 
+use crate::{
+    error::PreProposeError,
+    state::{SubmissionFee, SubmissionFeeDestination},
+};
+
 #[cw_serde]
 pub struct InstantiateMsg<InstantiateExt> {
     /// Information about the deposit requirements for this
@@ -19,12 +25,41 @@ pub struct InstantiateMsg<InstantiateExt> {
     pub extension: InstantiateExt,
 }
 
+/// Information about the fee charged for submitting a proposal.
+#[cw_serde]
+pub struct UncheckedSubmissionFee {
+    /// The denomination of the fee.
+    pub denom: UncheckedDenom,
+    /// The amount of the fee. Must be non-zero.
+    pub amount: Uint128,
+    /// Where the fee is sent once paid.
+    pub destination: SubmissionFeeDestination,
+}
+
+impl UncheckedSubmissionFee {
+    pub fn into_checked(self, deps: Deps) -> Result<SubmissionFee, PreProposeError> {
+        if self.amount.is_zero() {
+            return Err(PreProposeError::ZeroSubmissionFee {});
+        }
+        Ok(SubmissionFee {
+            denom: self.denom.into_checked(deps)?,
+            amount: self.amount,
+            destination: self.destination,
+        })
+    }
+}
+
 #[cw_serde]
 pub enum ExecuteMsg<ProposalMessage, ExecuteExt> {
     /// Creates a new proposal in the pre-propose module. MSG will be
     /// serialized and used as the proposal creation message.
     Propose { msg: ProposalMessage },
 
+    /// Creates a new proposal as `Propose` does, but pays the deposit
+    /// option at index OPTION in place of the configured deposit. The
+    /// proposal's deposit is refunded in the same tokens.
+    ProposeWithDepositOption { msg: ProposalMessage, option: u32 },
+
     /// Updates the configuration of this module. This will completely
     /// override the existing configuration. This new configuration
     /// will only apply to proposals created after the config is
@@ -34,6 +69,19 @@ pub enum ExecuteMsg<ProposalMessage, ExecuteExt> {
         open_proposal_submission: bool,
     },
 
+    /// Sets the non-refundable fee charged for submitting a
+    /// proposal, in addition to any deposit. `None` removes the
+    /// fee. Only the DAO may execute this message.
+    UpdateSubmissionFee {
+        submission_fee: Option<UncheckedSubmissionFee>,
+    },
+
+    /// Sets the deposits that proposers may choose to pay in place of
+    /// the configured deposit, for example to accept a deposit in
+    /// one of several tokens. An empty list removes the options. Only
+    /// the DAO may execute this message.
+    UpdateDepositOptions { options: Vec<UncheckedDepositInfo> },
+
     /// Withdraws funds inside of this contract to the message
     /// sender. The contracts entire balance for the specifed DENOM is
     /// withdrawn to the message sender. Only the DAO may call this
@@ -77,6 +125,16 @@ pub enum ExecuteMsg<ProposalMessage, ExecuteExt> {
     /// Removes a proposal submitted hook. Only the DAO may call this method.
     RemoveProposalSubmittedHook { address: String },
 
+    /// Adds a submission hook receiver. Receivers are sent a
+    /// `SubmissionCreatedHook` message whenever a proposal is
+    /// submitted, and are removed if they fail to handle it. Only the
+    /// DAO may call this method.
+    AddSubmissionHook { address: String },
+
+    /// Removes a submission hook receiver. Only the DAO may call this
+    /// method.
+    RemoveSubmissionHook { address: String },
+
     /// Handles proposal hook fired by the associated proposal
     /// module when a proposal is completed (ie executed or rejected).
     /// By default, the base contract will return deposits
@@ -109,9 +167,20 @@ where
     /// PROPOSAL_ID.
     #[returns(DepositInfoResponse)]
     DepositInfo { proposal_id: u64 },
+    /// Gets the fee charged for submitting a proposal, if any.
+    #[returns(Option<crate::state::SubmissionFee>)]
+    SubmissionFee {},
+    /// Gets the deposits that may be paid in place of the configured
+    /// deposit, in the order they are indexed by
+    /// `ProposeWithDepositOption`.
+    #[returns(Vec<CheckedDepositInfo>)]
+    DepositOptions {},
     /// Returns list of proposal submitted hooks.
     #[returns(cw_hooks::HooksResponse)]
     ProposalSubmittedHooks {},
+    /// Returns list of submission hook receivers.
+    #[returns(cw_hooks::HooksResponse)]
+    SubmissionHooks {},
     /// Extension for queries. The default implementation will do
     /// nothing if queried for will return `Binary::default()`.
     #[returns(cosmwasm_std::Binary)]
@@ -1,7 +1,8 @@
 use std::marker::PhantomData;
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Addr;
+use cosmwasm_std::{coins, to_json_binary, Addr, BankMsg, CosmosMsg, StdResult, Uint128, WasmMsg};
+use cw_denom::CheckedDenom;
 use cw_hooks::Hooks;
 use cw_storage_plus::{Item, Map};
 
@@ -18,6 +19,64 @@ pub struct Config {
     pub open_proposal_submission: bool,
 }
 
+/// Where a proposal submission fee is sent once paid.
+#[cw_serde]
+pub enum SubmissionFeeDestination {
+    /// The fee is sent to the DAO's treasury.
+    Dao,
+    /// The fee is burned.
+    Burn,
+}
+
+/// A flat fee charged for submitting a proposal. Unlike the deposit,
+/// the fee is never refunded.
+#[cw_serde]
+pub struct SubmissionFee {
+    /// The denomination of the fee.
+    pub denom: CheckedDenom,
+    /// The amount of the fee.
+    pub amount: Uint128,
+    /// Where the fee is sent once paid.
+    pub destination: SubmissionFeeDestination,
+}
+
+impl SubmissionFee {
+    /// Gets the messages that move the fee to its destination. Native
+    /// fees are paid to this contract with the proposal, and cw20
+    /// fees are taken from PAYER's allowance.
+    pub fn get_take_fee_messages(&self, payer: &Addr, dao: &Addr) -> StdResult<Vec<CosmosMsg>> {
+        let message = match (&self.denom, &self.destination) {
+            (CheckedDenom::Native(_), SubmissionFeeDestination::Dao) => {
+                self.denom.get_transfer_to_message(dao, self.amount)?
+            }
+            (CheckedDenom::Native(denom), SubmissionFeeDestination::Burn) => BankMsg::Burn {
+                amount: coins(self.amount.u128(), denom),
+            }
+            .into(),
+            (CheckedDenom::Cw20(address), SubmissionFeeDestination::Dao) => WasmMsg::Execute {
+                contract_addr: address.to_string(),
+                msg: to_json_binary(&cw20::Cw20ExecuteMsg::TransferFrom {
+                    owner: payer.to_string(),
+                    recipient: dao.to_string(),
+                    amount: self.amount,
+                })?,
+                funds: vec![],
+            }
+            .into(),
+            (CheckedDenom::Cw20(address), SubmissionFeeDestination::Burn) => WasmMsg::Execute {
+                contract_addr: address.to_string(),
+                msg: to_json_binary(&cw20::Cw20ExecuteMsg::BurnFrom {
+                    owner: payer.to_string(),
+                    amount: self.amount,
+                })?,
+                funds: vec![],
+            }
+            .into(),
+        };
+        Ok(vec![message])
+    }
+}
+
 pub struct PreProposeContract<InstantiateExt, ExecuteExt, QueryExt, ProposalMessage> {
     /// The proposal module that this module is associated with.
     pub proposal_module: Item<'static, Addr>,
@@ -30,6 +89,13 @@ pub struct PreProposeContract<InstantiateExt, ExecuteExt, QueryExt, ProposalMess
     pub deposits: Map<'static, u64, (Option<CheckedDepositInfo>, Addr)>,
     /// Consumers of proposal submitted hooks.
     pub proposal_submitted_hooks: Hooks<'static>,
+    /// The fee charged for submitting a proposal, if any.
+    pub submission_fee: Item<'static, SubmissionFee>,
+    /// Consumers of submission created hooks.
+    pub submission_hooks: Hooks<'static>,
+    /// Deposits that proposers may pay in place of the configured
+    /// deposit.
+    pub deposit_options: Item<'static, Vec<CheckedDepositInfo>>,
 
     // These types are used in associated functions, but not
     // assocaited data. To stop the compiler complaining about unused
@@ -49,6 +115,9 @@ impl<InstantiateExt, ExecuteExt, QueryExt, ProposalMessage>
         config_key: &'static str,
         deposits_key: &'static str,
         proposal_submitted_hooks_key: &'static str,
+        submission_fee_key: &'static str,
+        submission_hooks_key: &'static str,
+        deposit_options_key: &'static str,
     ) -> Self {
         Self {
             proposal_module: Item::new(proposal_key),
@@ -56,6 +125,9 @@ impl<InstantiateExt, ExecuteExt, QueryExt, ProposalMessage>
             config: Item::new(config_key),
             deposits: Map::new(deposits_key),
             proposal_submitted_hooks: Hooks::new(proposal_submitted_hooks_key),
+            submission_fee: Item::new(submission_fee_key),
+            submission_hooks: Hooks::new(submission_hooks_key),
+            deposit_options: Item::new(deposit_options_key),
             execute_type: PhantomData,
             instantiate_type: PhantomData,
             query_type: PhantomData,
@@ -77,6 +149,9 @@ impl<InstantiateExt, ExecuteExt, QueryExt, ProposalMessage> Default
             "config",
             "deposits",
             "proposal_submitted_hooks",
+            "submission_fee",
+            "submission_hooks",
+            "deposit_options",
         )
     }
 }
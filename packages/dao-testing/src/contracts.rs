@@ -75,7 +75,8 @@ pub fn pre_propose_single_contract() -> Box<dyn Contract<Empty>> {
         cpps::contract::execute,
         cpps::contract::instantiate,
         cpps::contract::query,
-    );
+    )
+    .with_reply(cpps::contract::reply);
     Box::new(contract)
 }
 
@@ -84,7 +85,8 @@ pub fn pre_propose_multiple_contract() -> Box<dyn Contract<Empty>> {
         cppm::contract::execute,
         cppm::contract::instantiate,
         cppm::contract::query,
-    );
+    )
+    .with_reply(cppm::contract::reply);
     Box::new(contract)
 }
 
@@ -0,0 +1,161 @@
+use cosmwasm_std::{Addr, Order, StdResult, Storage, Uint128};
+use cw_storage_plus::{Bound, SnapshotMap};
+
+/// The number of addresses examined by a compaction call if no limit
+/// is specified.
+pub const DEFAULT_COMPACT_LIMIT: u32 = 30;
+/// The maximum number of addresses that may be examined by a single
+/// compaction call.
+pub const MAX_COMPACT_LIMIT: u32 = 100;
+
+/// The result of a single compaction pass.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Compaction {
+    /// The number of addresses whose snapshot history was removed.
+    pub pruned: u32,
+    /// The last address examined, from which the next pass should
+    /// continue. `None` if the pass reached the end of the map.
+    pub next_start_after: Option<Addr>,
+}
+
+/// Removes the snapshot history of addresses in `balances` whose
+/// current balance is zero and whose balance last changed at least
+/// `retention` blocks before `height`.
+///
+/// Removing an address's history only changes the answer to balance
+/// queries at heights before its last change, so `retention` must be
+/// at least as long as the oldest height that a proposal module may
+/// still query voting power at. Examines at most `limit` addresses,
+/// starting after `start_after`.
+pub fn compact_snapshot_balances(
+    storage: &mut dyn Storage,
+    balances: &SnapshotMap<&Addr, Uint128>,
+    start_after: Option<Addr>,
+    limit: u32,
+    height: u64,
+    retention: u64,
+) -> StdResult<Compaction> {
+    let entries = balances
+        .range(
+            storage,
+            start_after.as_ref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit as usize)
+        .collect::<StdResult<Vec<(Addr, Uint128)>>>()?;
+
+    let cutoff = height.saturating_sub(retention);
+    let mut pruned = 0;
+    for (addr, balance) in entries.iter() {
+        if !balance.is_zero() {
+            continue;
+        }
+        let last_change = balances
+            .changelog()
+            .prefix(addr)
+            .keys(storage, None, None, Order::Descending)
+            .next()
+            .transpose()?;
+        if last_change.map_or(false, |changed| changed > cutoff) {
+            continue;
+        }
+
+        // Removing the entry records one last change at this
+        // height, which is cleared along with the rest of the
+        // address's history below.
+        balances.remove(storage, addr, height)?;
+        let heights = balances
+            .changelog()
+            .prefix(addr)
+            .keys(storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<u64>>>()?;
+        for changed in heights {
+            balances.changelog().remove(storage, (addr, changed));
+        }
+        pruned += 1;
+    }
+
+    let next_start_after = if entries.len() < limit as usize {
+        None
+    } else {
+        entries.last().map(|(addr, _)| addr.clone())
+    };
+
+    Ok(Compaction {
+        pruned,
+        next_start_after,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::mock_dependencies;
+    use cw_storage_plus::Strategy;
+
+    use super::*;
+
+    const BALANCES: SnapshotMap<&Addr, Uint128> = SnapshotMap::new(
+        "balances",
+        "balances__checkpoints",
+        "balances__changelog",
+        Strategy::EveryBlock,
+    );
+
+    #[test]
+    fn test_compact_snapshot_balances() {
+        let mut deps = mock_dependencies();
+        let storage = deps.as_mut().storage;
+        let ekez = Addr::unchecked("ekez");
+        let keze = Addr::unchecked("keze");
+
+        BALANCES.save(storage, &ekez, &Uint128::new(10), 1).unwrap();
+        BALANCES.save(storage, &keze, &Uint128::new(10), 1).unwrap();
+        BALANCES.save(storage, &ekez, &Uint128::zero(), 5).unwrap();
+
+        // Inside the retention window, nothing is pruned.
+        let res = compact_snapshot_balances(storage, &BALANCES, None, 10, 10, 10).unwrap();
+        assert_eq!(
+            res,
+            Compaction {
+                pruned: 0,
+                next_start_after: None
+            }
+        );
+        assert_eq!(
+            BALANCES.may_load_at_height(storage, &ekez, 3).unwrap(),
+            Some(Uint128::new(10))
+        );
+
+        // Limit is respected and the last examined key returned.
+        let res = compact_snapshot_balances(storage, &BALANCES, None, 1, 20, 10).unwrap();
+        assert_eq!(
+            res,
+            Compaction {
+                pruned: 1,
+                next_start_after: Some(ekez.clone())
+            }
+        );
+        assert_eq!(BALANCES.may_load(storage, &ekez).unwrap(), None);
+        assert_eq!(
+            BALANCES.may_load_at_height(storage, &ekez, 3).unwrap(),
+            None
+        );
+        assert_eq!(
+            BALANCES
+                .changelog()
+                .prefix(&ekez)
+                .keys(storage, None, None, Order::Ascending)
+                .count(),
+            0
+        );
+
+        // Addresses with power are never pruned.
+        let res = compact_snapshot_balances(storage, &BALANCES, Some(ekez), 1, 20, 10).unwrap();
+        assert_eq!(res.pruned, 0);
+        assert_eq!(
+            BALANCES.may_load(storage, &keze).unwrap(),
+            Some(Uint128::new(10))
+        );
+    }
+}
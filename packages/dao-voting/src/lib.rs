@@ -1,11 +1,13 @@
 #![doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/README.md"))]
 
+pub mod compaction;
 pub mod deposit;
 pub mod duration;
 pub mod error;
 pub mod multiple_choice;
 pub mod pre_propose;
 pub mod proposal;
+pub mod quorum;
 pub mod reply;
 pub mod status;
 pub mod threshold;
@@ -1,5 +1,5 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{CosmosMsg, Empty, StdError, StdResult, Uint128};
+use cosmwasm_std::{Addr, Api, BankMsg, CosmosMsg, Empty, StdError, StdResult, Uint128};
 
 use crate::threshold::{validate_quorum, PercentageThreshold, ThresholdError};
 
@@ -11,19 +11,38 @@ const NONE_OPTION_DESCRIPTION: &str = "None of the above";
 /// Determines how many choices may be selected.
 #[cw_serde]
 pub enum VotingStrategy {
-    SingleChoice { quorum: PercentageThreshold },
+    SingleChoice {
+        quorum: PercentageThreshold,
+    },
+    /// As `SingleChoice`, except that a choice must win a majority
+    /// of the votes cast. If voting closes without one, the two
+    /// leading choices go to a runoff.
+    TopTwoRunoff {
+        quorum: PercentageThreshold,
+    },
+    /// Elects up to `seats` choices once voting closes: those with
+    /// the most votes that also have more votes than "None of the
+    /// above". The messages of each winning choice are executed.
+    Election {
+        quorum: PercentageThreshold,
+        seats: u32,
+    },
 }
 
 impl VotingStrategy {
     pub fn validate(&self) -> Result<(), ThresholdError> {
         match self {
-            VotingStrategy::SingleChoice { quorum } => validate_quorum(quorum),
+            VotingStrategy::SingleChoice { quorum }
+            | VotingStrategy::TopTwoRunoff { quorum }
+            | VotingStrategy::Election { quorum, .. } => validate_quorum(quorum),
         }
     }
 
     pub fn get_quorum(&self) -> PercentageThreshold {
         match self {
-            VotingStrategy::SingleChoice { quorum } => *quorum,
+            VotingStrategy::SingleChoice { quorum }
+            | VotingStrategy::TopTwoRunoff { quorum }
+            | VotingStrategy::Election { quorum, .. } => *quorum,
         }
     }
 }
@@ -102,6 +121,121 @@ pub struct MultipleChoiceOption {
     pub title: String,
     pub description: String,
     pub msgs: Vec<CosmosMsg<Empty>>,
+    /// Optional structured information about what this option
+    /// requests. Informational only, the option's messages are what
+    /// get executed if it wins.
+    #[serde(default)]
+    pub metadata: Option<OptionMetadata>,
+    /// Amounts in this option's messages that are resolved from an
+    /// oracle when the option is executed.
+    #[serde(default)]
+    pub oracle_amounts: Vec<OracleAmount>,
+}
+
+/// Sets the amount sent by one of an option's messages from a rate
+/// queried from the proposal module's oracle when the option is
+/// executed, for example to send a fixed value of tokens at the
+/// exchange rate at the time of execution.
+#[cw_serde]
+pub struct OracleAmount {
+    /// The index in the option's messages of the message whose amount
+    /// is resolved. The message must be a bank send of a single coin,
+    /// whose amount is replaced.
+    pub msg_index: u32,
+    /// The key to query the oracle's rate for.
+    pub key: String,
+    /// The amount is resolved to `base_amount * rate`.
+    pub base_amount: Uint128,
+    /// The smallest amount the message may resolve to. Execution
+    /// fails if the resolved amount is lower.
+    pub min: Uint128,
+    /// The largest amount the message may resolve to. Execution fails
+    /// if the resolved amount is higher.
+    pub max: Uint128,
+}
+
+impl OracleAmount {
+    fn validate(&self, msgs: &[CosmosMsg<Empty>]) -> StdResult<()> {
+        match msgs.get(self.msg_index as usize) {
+            Some(CosmosMsg::Bank(BankMsg::Send { amount, .. })) if amount.len() == 1 => (),
+            _ => {
+                return Err(StdError::generic_err(
+                    "Oracle amounts must refer to a bank send of a single coin",
+                ))
+            }
+        }
+        if self.min > self.max || self.max.is_zero() {
+            return Err(StdError::generic_err(
+                "Oracle amount bounds must be non-zero with min <= max",
+            ));
+        }
+        if self.key.is_empty() {
+            return Err(StdError::generic_err("Oracle amount key must be non-empty"));
+        }
+        Ok(())
+    }
+}
+
+/// Structured budget information attached to a multiple choice
+/// option so that frontends and other contracts need not parse it out
+/// of the option's description.
+#[cw_serde]
+#[derive(Default)]
+pub struct OptionMetadata {
+    /// The amount of tokens requested. If set, `denom` must be set as
+    /// well.
+    pub amount: Option<Uint128>,
+    /// The denom of the tokens requested.
+    pub denom: Option<String>,
+    /// The address that will receive the requested funds.
+    pub recipient: Option<String>,
+    /// The number of milestones the requested funds will be paid out
+    /// over.
+    pub milestones: Option<u32>,
+}
+
+/// Option metadata that has been validated.
+#[cw_serde]
+pub struct CheckedOptionMetadata {
+    pub amount: Option<Uint128>,
+    pub denom: Option<String>,
+    pub recipient: Option<Addr>,
+    pub milestones: Option<u32>,
+}
+
+impl OptionMetadata {
+    pub fn into_checked(self, api: &dyn Api) -> StdResult<CheckedOptionMetadata> {
+        if self.amount.is_some() != self.denom.is_some() {
+            return Err(StdError::generic_err(
+                "Option metadata amount and denom must be set together",
+            ));
+        }
+        if self.amount.map_or(false, |amount| amount.is_zero()) {
+            return Err(StdError::generic_err(
+                "Option metadata amount must be non-zero",
+            ));
+        }
+        if self.denom.as_ref().map_or(false, |denom| denom.is_empty()) {
+            return Err(StdError::generic_err(
+                "Option metadata denom must be non-empty",
+            ));
+        }
+        if self.milestones == Some(0) {
+            return Err(StdError::generic_err(
+                "Option metadata milestones must be non-zero",
+            ));
+        }
+        let recipient = self
+            .recipient
+            .map(|recipient| api.addr_validate(&recipient))
+            .transpose()?;
+        Ok(CheckedOptionMetadata {
+            amount: self.amount,
+            denom: self.denom,
+            recipient,
+            milestones: self.milestones,
+        })
+    }
 }
 
 /// Multiple choice options that have been verified for correctness, and have all fields
@@ -122,10 +256,43 @@ pub struct CheckedMultipleChoiceOption {
     pub description: String,
     pub msgs: Vec<CosmosMsg<Empty>>,
     pub vote_count: Uint128,
+    #[serde(default)]
+    pub metadata: Option<CheckedOptionMetadata>,
+    #[serde(default)]
+    pub oracle_amounts: Vec<OracleAmount>,
+}
+
+impl MultipleChoiceOption {
+    /// Validates this option as a standard option at INDEX.
+    pub fn into_checked(self, api: &dyn Api, index: u32) -> StdResult<CheckedMultipleChoiceOption> {
+        let mut resolved = Vec::with_capacity(self.oracle_amounts.len());
+        for oracle_amount in &self.oracle_amounts {
+            oracle_amount.validate(&self.msgs)?;
+            if resolved.contains(&oracle_amount.msg_index) {
+                return Err(StdError::generic_err(
+                    "Each message may have at most one oracle amount",
+                ));
+            }
+            resolved.push(oracle_amount.msg_index);
+        }
+        Ok(CheckedMultipleChoiceOption {
+            index,
+            option_type: MultipleChoiceOptionType::Standard,
+            description: self.description,
+            msgs: self.msgs,
+            vote_count: Uint128::zero(),
+            title: self.title,
+            metadata: self
+                .metadata
+                .map(|metadata| metadata.into_checked(api))
+                .transpose()?,
+            oracle_amounts: self.oracle_amounts,
+        })
+    }
 }
 
 impl MultipleChoiceOptions {
-    pub fn into_checked(self) -> StdResult<CheckedMultipleChoiceOptions> {
+    pub fn into_checked(self, api: &dyn Api) -> StdResult<CheckedMultipleChoiceOptions> {
         if self.options.len() < 2 || self.options.len() > MAX_NUM_CHOICES as usize {
             return Err(StdError::GenericErr {
                 msg: "Wrong number of choices".to_string(),
@@ -136,20 +303,9 @@ impl MultipleChoiceOptions {
             Vec::with_capacity(self.options.len() + 1);
 
         // Iterate through choices and save the index and option type for each
-        self.options
-            .into_iter()
-            .enumerate()
-            .for_each(|(idx, choice)| {
-                let checked_option = CheckedMultipleChoiceOption {
-                    index: idx as u32,
-                    option_type: MultipleChoiceOptionType::Standard,
-                    description: choice.description,
-                    msgs: choice.msgs,
-                    vote_count: Uint128::zero(),
-                    title: choice.title,
-                };
-                checked_options.push(checked_option)
-            });
+        for (idx, choice) in self.options.into_iter().enumerate() {
+            checked_options.push(choice.into_checked(api, idx as u32)?)
+        }
 
         // Add a "None of the above" option, required for every multiple choice proposal.
         let none_option = CheckedMultipleChoiceOption {
@@ -159,6 +315,8 @@ impl MultipleChoiceOptions {
             msgs: vec![],
             vote_count: Uint128::zero(),
             title: NONE_OPTION_DESCRIPTION.to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         };
 
         checked_options.push(none_option);
@@ -174,6 +332,8 @@ impl MultipleChoiceOptions {
 mod test {
     use std::vec;
 
+    use cosmwasm_std::testing::MockApi;
+
     use super::*;
 
     #[test]
@@ -213,17 +373,21 @@ mod test {
                 description: "multiple choice option 1".to_string(),
                 msgs: vec![],
                 title: "title".to_string(),
+                metadata: None,
+                oracle_amounts: vec![],
             },
             super::MultipleChoiceOption {
                 description: "multiple choice option 2".to_string(),
                 msgs: vec![],
                 title: "title".to_string(),
+                metadata: None,
+                oracle_amounts: vec![],
             },
         ];
 
         let mc_options = super::MultipleChoiceOptions { options };
 
-        let checked_mc_options = mc_options.into_checked().unwrap();
+        let checked_mc_options = mc_options.into_checked(&MockApi::default()).unwrap();
         assert_eq!(checked_mc_options.options.len(), 3);
         assert_eq!(
             checked_mc_options.options[0].option_type,
@@ -258,9 +422,136 @@ mod test {
             description: "multiple choice option 1".to_string(),
             msgs: vec![],
             title: "title".to_string(),
+            metadata: None,
+            oracle_amounts: vec![],
         }];
 
         let mc_options = super::MultipleChoiceOptions { options };
-        mc_options.into_checked().unwrap();
+        mc_options.into_checked(&MockApi::default()).unwrap();
+    }
+
+    #[test]
+    fn test_into_checked_metadata() {
+        let option = |metadata: OptionMetadata| super::MultipleChoiceOption {
+            description: "multiple choice option".to_string(),
+            msgs: vec![],
+            title: "title".to_string(),
+            metadata: Some(metadata),
+            oracle_amounts: vec![],
+        };
+        let check = |metadata: OptionMetadata| {
+            super::MultipleChoiceOptions {
+                options: vec![option(metadata), option(OptionMetadata::default())],
+            }
+            .into_checked(&MockApi::default())
+        };
+
+        let checked = check(OptionMetadata {
+            amount: Some(Uint128::new(100)),
+            denom: Some("ujuno".to_string()),
+            recipient: Some("recipient".to_string()),
+            milestones: Some(3),
+        })
+        .unwrap();
+        assert_eq!(
+            checked.options[0].metadata,
+            Some(CheckedOptionMetadata {
+                amount: Some(Uint128::new(100)),
+                denom: Some("ujuno".to_string()),
+                recipient: Some(Addr::unchecked("recipient")),
+                milestones: Some(3),
+            })
+        );
+        assert_eq!(checked.options[2].metadata, None);
+
+        // Amount without a denom.
+        check(OptionMetadata {
+            amount: Some(Uint128::new(100)),
+            ..Default::default()
+        })
+        .unwrap_err();
+        // Zero amount.
+        check(OptionMetadata {
+            amount: Some(Uint128::zero()),
+            denom: Some("ujuno".to_string()),
+            ..Default::default()
+        })
+        .unwrap_err();
+        // Zero milestones.
+        check(OptionMetadata {
+            milestones: Some(0),
+            ..Default::default()
+        })
+        .unwrap_err();
+        // Invalid recipient.
+        check(OptionMetadata {
+            recipient: Some("".to_string()),
+            ..Default::default()
+        })
+        .unwrap_err();
+    }
+
+    #[test]
+    fn test_into_checked_oracle_amounts() {
+        let send = CosmosMsg::Bank(BankMsg::Send {
+            to_address: "recipient".to_string(),
+            amount: cosmwasm_std::coins(1, "ujuno"),
+        });
+        let oracle_amount = OracleAmount {
+            msg_index: 0,
+            key: "ujuno/uusd".to_string(),
+            base_amount: Uint128::new(100),
+            min: Uint128::new(50),
+            max: Uint128::new(200),
+        };
+        let check = |msgs: Vec<CosmosMsg<Empty>>, oracle_amounts: Vec<OracleAmount>| {
+            super::MultipleChoiceOptions {
+                options: vec![
+                    super::MultipleChoiceOption {
+                        description: "multiple choice option".to_string(),
+                        msgs,
+                        title: "title".to_string(),
+                        metadata: None,
+                        oracle_amounts,
+                    },
+                    super::MultipleChoiceOption {
+                        description: "multiple choice option".to_string(),
+                        msgs: vec![],
+                        title: "title".to_string(),
+                        metadata: None,
+                        oracle_amounts: vec![],
+                    },
+                ],
+            }
+            .into_checked(&MockApi::default())
+        };
+
+        let checked = check(vec![send.clone()], vec![oracle_amount.clone()]).unwrap();
+        assert_eq!(
+            checked.options[0].oracle_amounts,
+            vec![oracle_amount.clone()]
+        );
+
+        // Out of range message.
+        check(vec![], vec![oracle_amount.clone()]).unwrap_err();
+        // Not a bank send.
+        check(
+            vec![CosmosMsg::Bank(BankMsg::Burn {
+                amount: cosmwasm_std::coins(1, "ujuno"),
+            })],
+            vec![oracle_amount.clone()],
+        )
+        .unwrap_err();
+        // Min above max.
+        check(
+            vec![send.clone()],
+            vec![OracleAmount {
+                min: Uint128::new(300),
+                ..oracle_amount.clone()
+            }],
+        )
+        .unwrap_err();
+        // Two oracle amounts for the same message.
+        check(vec![send], vec![oracle_amount.clone(), oracle_amount]).unwrap_err();
     }
 }
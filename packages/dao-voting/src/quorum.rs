@@ -0,0 +1,66 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Api, QuerierWrapper, StdResult, Uint128};
+use dao_interface::activity::{ActivityOracleQuery, InactivePowerAtHeightResponse};
+
+/// The voting power that quorum is computed against for new
+/// proposals. Shared by proposal modules so that quorum means the
+/// same thing in each of them.
+#[cw_serde]
+pub enum QuorumSource {
+    /// Quorum is computed against the total voting power at the time
+    /// of proposal creation.
+    TotalPower {},
+    /// Quorum is computed against the total voting power less the
+    /// voting power of addresses that `oracle` reports as inactive.
+    ActivePower { oracle: Addr },
+}
+
+/// The voting power that quorum is computed against, before the
+/// oracle address is validated.
+#[cw_serde]
+pub enum UncheckedQuorumSource {
+    /// Quorum is computed against the total voting power at the time
+    /// of proposal creation.
+    TotalPower {},
+    /// Quorum is computed against the total voting power less the
+    /// voting power of addresses that `oracle` reports as
+    /// inactive. `oracle` must implement
+    /// `dao_interface::activity::ActivityOracleQuery`.
+    ActivePower { oracle: String },
+}
+
+impl UncheckedQuorumSource {
+    pub fn into_checked(self, api: &dyn Api) -> StdResult<QuorumSource> {
+        Ok(match self {
+            Self::TotalPower {} => QuorumSource::TotalPower {},
+            Self::ActivePower { oracle } => QuorumSource::ActivePower {
+                oracle: api.addr_validate(&oracle)?,
+            },
+        })
+    }
+}
+
+impl QuorumSource {
+    /// Gets the voting power that quorum is computed against for a
+    /// proposal created at HEIGHT with TOTAL_POWER, or `None` if that
+    /// is the total power.
+    pub fn quorum_power(
+        &self,
+        querier: &QuerierWrapper,
+        total_power: Uint128,
+        height: u64,
+    ) -> StdResult<Option<Uint128>> {
+        match self {
+            QuorumSource::TotalPower {} => Ok(None),
+            QuorumSource::ActivePower { oracle } => {
+                let inactive: InactivePowerAtHeightResponse = querier.query_wasm_smart(
+                    oracle,
+                    &ActivityOracleQuery::InactivePowerAtHeight {
+                        height: Some(height),
+                    },
+                )?;
+                Ok(Some(total_power.saturating_sub(inactive.power)))
+            }
+        }
+    }
+}
@@ -3,6 +3,9 @@ use dao_dao_macros::limit_variant_count;
 const FAILED_PROPOSAL_EXECUTION_MASK: u64 = 0b000;
 const FAILED_PROPOSAL_HOOK_MASK: u64 = 0b001;
 const FAILED_VOTE_HOOK_MASK: u64 = 0b010;
+const FAILED_OUTCOME_HOOK_MASK: u64 = 0b101;
+const FAILED_SUBSCRIPTION_HOOK_MASK: u64 = 0b110;
+const FAILED_PREVIOUS_PRE_PROPOSE_MODULE_HOOK_MASK: u64 = 0b111;
 
 /// These are IDs as opposed to bitmasks since they only need to
 /// convey one piece of information (the type of reply the reply
@@ -28,8 +31,15 @@ pub enum TaggedReplyId {
     FailedProposalHook(u64),
     /// Fired when a vote hook's execution fails.
     FailedVoteHook(u64),
+    /// Fired when a proposal outcome hook's execution fails.
+    FailedOutcomeHook(u64),
+    /// Fired when a hook sent to a filtered subscription fails.
+    FailedSubscriptionHook(u64),
     /// Fired when a pre-propse module's execution fails.
     FailedPreProposeModuleHook,
+    /// Fired when a completion hook sent to a pre-propose module that
+    /// has since been replaced fails, with the proposal's ID.
+    FailedPreviousPreProposeModuleHook(u64),
     /// Fired when a pre-propose module is successfully instantiated.
     PreProposeModuleInstantiation,
 }
@@ -48,8 +58,15 @@ impl TaggedReplyId {
             }
             FAILED_PROPOSAL_HOOK_MASK => Ok(TaggedReplyId::FailedProposalHook(id_after_shift)),
             FAILED_VOTE_HOOK_MASK => Ok(TaggedReplyId::FailedVoteHook(id_after_shift)),
+            FAILED_OUTCOME_HOOK_MASK => Ok(TaggedReplyId::FailedOutcomeHook(id_after_shift)),
+            FAILED_SUBSCRIPTION_HOOK_MASK => {
+                Ok(TaggedReplyId::FailedSubscriptionHook(id_after_shift))
+            }
             PRE_PROPOSE_MODULE_INSTANTIATION_ID => Ok(TaggedReplyId::PreProposeModuleInstantiation),
             FAILED_PRE_PROPOSE_MODULE_HOOK_ID => Ok(TaggedReplyId::FailedPreProposeModuleHook),
+            FAILED_PREVIOUS_PRE_PROPOSE_MODULE_HOOK_MASK => Ok(
+                TaggedReplyId::FailedPreviousPreProposeModuleHook(id_after_shift),
+            ),
             _ => Err(error::TagError::UnknownReplyId { id }),
         }
     }
@@ -68,6 +85,18 @@ pub const fn mask_vote_hook_index(index: u64) -> u64 {
     FAILED_VOTE_HOOK_MASK | (index << BITS_RESERVED_FOR_REPLY_TYPE)
 }
 
+pub const fn mask_outcome_hook_index(index: u64) -> u64 {
+    FAILED_OUTCOME_HOOK_MASK | (index << BITS_RESERVED_FOR_REPLY_TYPE)
+}
+
+pub const fn mask_subscription_id(id: u64) -> u64 {
+    FAILED_SUBSCRIPTION_HOOK_MASK | (id << BITS_RESERVED_FOR_REPLY_TYPE)
+}
+
+pub const fn mask_previous_pre_propose_module_hook(proposal_id: u64) -> u64 {
+    FAILED_PREVIOUS_PRE_PROPOSE_MODULE_HOOK_MASK | (proposal_id << BITS_RESERVED_FOR_REPLY_TYPE)
+}
+
 pub const fn pre_propose_module_instantiation_id() -> u64 {
     PRE_PROPOSE_MODULE_INSTANTIATION_ID
 }
@@ -96,10 +125,17 @@ mod test {
         let proposal_id_max: u64 = 2_u64.pow(61) - 1;
         let proposal_hook_idx = 1234;
         let vote_hook_idx = 4321;
+        let outcome_hook_idx = 2143;
+        let subscription_id = 3412;
+        let previous_pre_propose_proposal_id = 1432;
 
         let m_proposal_id = mask_proposal_execution_proposal_id(proposal_id_max);
         let m_proposal_hook_idx = mask_proposal_hook_index(proposal_hook_idx);
         let m_vote_hook_idx = mask_vote_hook_index(vote_hook_idx);
+        let m_outcome_hook_idx = mask_outcome_hook_index(outcome_hook_idx);
+        let m_subscription_id = mask_subscription_id(subscription_id);
+        let m_previous_pre_propose_proposal_id =
+            mask_previous_pre_propose_module_hook(previous_pre_propose_proposal_id);
 
         assert_eq!(
             TaggedReplyId::new(m_proposal_id).unwrap(),
@@ -114,8 +150,16 @@ mod test {
             TaggedReplyId::FailedVoteHook(vote_hook_idx)
         );
         assert_eq!(
-            TaggedReplyId::new(0b110).unwrap_err(),
-            error::TagError::UnknownReplyId { id: 0b110 }
+            TaggedReplyId::new(m_outcome_hook_idx).unwrap(),
+            TaggedReplyId::FailedOutcomeHook(outcome_hook_idx)
+        );
+        assert_eq!(
+            TaggedReplyId::new(m_subscription_id).unwrap(),
+            TaggedReplyId::FailedSubscriptionHook(subscription_id)
+        );
+        assert_eq!(
+            TaggedReplyId::new(m_previous_pre_propose_proposal_id).unwrap(),
+            TaggedReplyId::FailedPreviousPreProposeModuleHook(previous_pre_propose_proposal_id)
         );
     }
 }